@@ -2,6 +2,7 @@ use crate::err::Error;
 use crate::key::change;
 use crate::key::debug::Sprintable;
 use crate::kvs::Transaction;
+use crate::sql::statements::DefineDatabaseStatement;
 use crate::vs;
 use crate::vs::Versionstamp;
 use std::str;
@@ -36,32 +37,8 @@ pub async fn gc_ns(tx: &Transaction, ts: u64, ns: &str) -> Result<(), Error> {
 		yield_now!();
 		// Trace for debugging
 		trace!("Performing garbage collection on {ns}:{} for timestamp {ts}", db.name);
-		// Fetch all tables
-		let tbs = tx.all_tb(ns, &db.name, None).await?;
-		// Get the database changefeed expiration
-		let db_cf_expiry = db.changefeed.map(|v| v.expiry.as_secs()).unwrap_or_default();
-		// Get the maximum table changefeed expiration
-		let tb_cf_expiry = tbs.as_ref().iter().fold(0, |acc, tb| match &tb.changefeed {
-			None => acc,
-			Some(cf) => {
-				if cf.expiry.is_zero() {
-					acc
-				} else {
-					acc.max(cf.expiry.as_secs())
-				}
-			}
-		});
-		// Calculate the maximum changefeed expiration
-		let cf_expiry = db_cf_expiry.max(tb_cf_expiry);
-		// Ignore this database if the expiry is greater
-		if ts < cf_expiry {
-			continue;
-		}
-		// Calculate the watermark expiry window
-		let watermark_ts = ts - cf_expiry;
 		// Calculate the watermark versionstamp
-		let watermark_vs =
-			tx.lock().await.get_versionstamp_from_timestamp(watermark_ts, ns, &db.name).await?;
+		let watermark_vs = watermark(tx, ts, ns, db).await?;
 		// If a versionstamp exists, then garbage collect
 		if let Some(watermark_vs) = watermark_vs {
 			gc_range(tx, ns, &db.name, watermark_vs).await?;
@@ -70,6 +47,53 @@ pub async fn gc_ns(tx: &Transaction, ts: u64, ns: &str) -> Result<(), Error> {
 	Ok(())
 }
 
+// watermark calculates the versionstamp before which the change feed entries of a database are stale at the given timestamp.
+// The entries which an ASYNC table view has not yet applied are never stale.
+pub(crate) async fn watermark(
+	tx: &Transaction,
+	ts: u64,
+	ns: &str,
+	db: &DefineDatabaseStatement,
+) -> Result<Option<Versionstamp>, Error> {
+	// Fetch all tables
+	let tbs = tx.all_tb(ns, &db.name, None).await?;
+	// Get the database changefeed expiration
+	let db_cf_expiry = db.changefeed.map(|v| v.expiry.as_secs()).unwrap_or_default();
+	// Get the maximum table changefeed expiration
+	let tb_cf_expiry = tbs.as_ref().iter().fold(0, |acc, tb| match &tb.changefeed {
+		None => acc,
+		Some(cf) => {
+			if cf.expiry.is_zero() {
+				acc
+			} else {
+				acc.max(cf.expiry.as_secs())
+			}
+		}
+	});
+	// Calculate the maximum changefeed expiration
+	let cf_expiry = db_cf_expiry.max(tb_cf_expiry);
+	// Ignore this database if the expiry is greater
+	if ts < cf_expiry {
+		return Ok(None);
+	}
+	// Calculate the watermark expiry window
+	let watermark_ts = ts - cf_expiry;
+	// Calculate the watermark versionstamp
+	let mut watermark_vs =
+		tx.lock().await.get_versionstamp_from_timestamp(watermark_ts, ns, &db.name).await?;
+	// Keep the change feed entries which ASYNC views have not yet applied
+	for tb in tbs.iter().filter(|tb| tb.view.as_ref().is_some_and(|v| v.asynchronous)) {
+		let key = crate::key::table::vp::new(ns, &db.name, &tb.name);
+		if let Some(progress) = tx.get(key, None).await? {
+			let progress =
+				<[u8; 10]>::try_from(progress.as_slice()).map_err(|e| Error::Tx(e.to_string()))?;
+			let next = vs::u64_to_versionstamp(vs::versionstamp_to_u64(&progress) + 1);
+			watermark_vs = watermark_vs.map(|vs| vs.min(next));
+		}
+	}
+	Ok(watermark_vs)
+}
+
 // gc_db deletes all change feed entries in the given database that are older than the given watermark.
 #[instrument(level = "trace", target = "surrealdb::core::cfs", skip(tx))]
 pub async fn gc_range(tx: &Transaction, ns: &str, db: &str, vt: Versionstamp) -> Result<(), Error> {
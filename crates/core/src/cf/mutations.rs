@@ -1,10 +1,12 @@
 use crate::fflags::FFLAGS;
 use crate::sql::array::Array;
 use crate::sql::object::Object;
-use crate::sql::statements::DefineTableStatement;
+use crate::sql::statements::{
+	DefineStatement, DefineTableStatement, DeleteStatement, UpsertStatement,
+};
 use crate::sql::thing::Thing;
 use crate::sql::value::Value;
-use crate::sql::Operation;
+use crate::sql::{Data, Operation, Output, Statement, Values};
 use crate::vs::to_u128_be;
 use derive::Store;
 use revision::revisioned;
@@ -135,6 +137,37 @@ impl TableMutation {
 	}
 }
 
+impl TableMutation {
+	/// Convert a stored change feed table mutation into a statement which
+	/// reapplies the same change when replicating to another datastore
+	pub(crate) fn into_statement(self) -> Statement {
+		match self {
+			TableMutation::Set(id, v) | TableMutation::SetWithDiff(id, v, _) => {
+				Statement::Upsert(UpsertStatement {
+					what: Values(vec![Value::Thing(id)]),
+					data: Some(Data::ContentExpression(v)),
+					output: Some(Output::None),
+					..Default::default()
+				})
+			}
+			TableMutation::Del(id) | TableMutation::DelWithOriginal(id, _) => {
+				Statement::Delete(DeleteStatement {
+					what: Values(vec![Value::Thing(id)]),
+					output: Some(Output::None),
+					..Default::default()
+				})
+			}
+			TableMutation::Def(dt) => {
+				Statement::Define(DefineStatement::Table(DefineTableStatement {
+					if_not_exists: false,
+					overwrite: true,
+					..dt
+				}))
+			}
+		}
+	}
+}
+
 impl DatabaseMutation {
 	pub fn into_value(self) -> Value {
 		let mut changes = Vec::<Value>::new();
@@ -56,6 +56,14 @@ pub static MAX_STREAM_BATCH_SIZE: LazyLock<u32> =
 pub static INDEXING_BATCH_SIZE: LazyLock<u32> =
 	lazy_env_parse!("SURREAL_INDEXING_BATCH_SIZE", u32, 250);
 
+/// The maximum number of attempts which a query retry policy can specify over RPC.
+pub static MAX_RETRY_ATTEMPTS: LazyLock<u32> =
+	lazy_env_parse!("SURREAL_MAX_RETRY_ATTEMPTS", u32, 10);
+
+/// The maximum delay in milliseconds between attempts which a query retry policy can specify over RPC.
+pub static MAX_RETRY_BACKOFF: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_MAX_RETRY_BACKOFF", u64, 5_000);
+
 /// The maximum stack size of the JavaScript function runtime (defaults to 256 KiB)
 pub static SCRIPTING_MAX_STACK_SIZE: LazyLock<usize> =
 	lazy_env_parse!("SURREAL_SCRIPTING_MAX_STACK_SIZE", usize, 256 * 1024);
@@ -73,6 +81,12 @@ pub static INSECURE_FORWARD_ACCESS_ERRORS: LazyLock<bool> =
 pub static EXTERNAL_SORTING_BUFFER_LIMIT: LazyLock<usize> =
 	lazy_env_parse!("SURREAL_EXTERNAL_SORTING_BUFFER_LIMIT", usize, 50_000);
 
+/// The maximum number of records matching the condition of a filtered approximate nearest
+/// neighbour search for which the nearest neighbours are computed exactly, rather than
+/// searched in the HNSW graph.
+pub static KNN_PREFILTER_BRUTEFORCE_LIMIT: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_KNN_PREFILTER_BRUTEFORCE_LIMIT", usize, 1_000);
+
 /// Specifies whether GraphQL querying and schema definition is enabled.
 pub static GRAPHQL_ENABLE: LazyLock<bool> =
 	lazy_env_parse!("SURREAL_EXPERIMENTAL_GRAPHQL", bool, false);
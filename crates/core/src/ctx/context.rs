@@ -3,7 +3,7 @@ use crate::ctx::canceller::Canceller;
 use crate::ctx::reason::Reason;
 #[cfg(feature = "http")]
 use crate::dbs::capabilities::NetTarget;
-use crate::dbs::{Capabilities, Notification};
+use crate::dbs::{Capabilities, Notification, QueryChunk};
 use crate::err::Error;
 use crate::idx::planner::executor::QueryExecutor;
 use crate::idx::planner::{IterationStage, QueryPlanner};
@@ -11,6 +11,7 @@ use crate::idx::trees::store::IndexStores;
 use crate::kvs::cache::ds::Cache;
 #[cfg(not(target_arch = "wasm32"))]
 use crate::kvs::IndexBuilder;
+use crate::kvs::Sequences;
 use crate::kvs::Transaction;
 use crate::sql::value::Value;
 use async_channel::Sender;
@@ -40,6 +41,9 @@ pub struct MutableContext {
 	values: HashMap<Cow<'static, str>, Arc<Value>>,
 	// Stores the notification channel if available
 	notifications: Option<Sender<Notification>>,
+	// The channel for streaming the records of this statement, which is not
+	// inherited by child contexts, so that subqueries are never streamed
+	records: Option<RecordSender>,
 	// An optional query planner
 	query_planner: Option<Arc<QueryPlanner>>,
 	// An optional query executor
@@ -53,6 +57,8 @@ pub struct MutableContext {
 	// The index concurrent builders
 	#[cfg(not(target_arch = "wasm32"))]
 	index_builder: Option<IndexBuilder>,
+	// The sequences batch allocator
+	sequences: Option<Sequences>,
 	// Capabilities
 	capabilities: Arc<Capabilities>,
 	#[cfg(storage)]
@@ -98,6 +104,7 @@ impl MutableContext {
 			deadline: None,
 			cancelled: Arc::new(AtomicBool::new(false)),
 			notifications: None,
+			records: None,
 			query_planner: None,
 			query_executor: None,
 			iteration_stage: None,
@@ -106,6 +113,7 @@ impl MutableContext {
 			cache: None,
 			#[cfg(not(target_arch = "wasm32"))]
 			index_builder: None,
+			sequences: None,
 			#[cfg(storage)]
 			temporary_directory: None,
 			transaction: None,
@@ -120,6 +128,7 @@ impl MutableContext {
 			deadline: parent.deadline,
 			cancelled: Arc::new(AtomicBool::new(false)),
 			notifications: parent.notifications.clone(),
+			records: None,
 			query_planner: parent.query_planner.clone(),
 			query_executor: parent.query_executor.clone(),
 			iteration_stage: parent.iteration_stage.clone(),
@@ -128,6 +137,7 @@ impl MutableContext {
 			cache: parent.cache.clone(),
 			#[cfg(not(target_arch = "wasm32"))]
 			index_builder: parent.index_builder.clone(),
+			sequences: parent.sequences.clone(),
 			#[cfg(storage)]
 			temporary_directory: parent.temporary_directory.clone(),
 			transaction: parent.transaction.clone(),
@@ -145,6 +155,7 @@ impl MutableContext {
 			deadline: parent.deadline,
 			cancelled: Arc::new(AtomicBool::new(false)),
 			notifications: parent.notifications.clone(),
+			records: None,
 			query_planner: parent.query_planner.clone(),
 			query_executor: parent.query_executor.clone(),
 			iteration_stage: parent.iteration_stage.clone(),
@@ -153,6 +164,7 @@ impl MutableContext {
 			cache: parent.cache.clone(),
 			#[cfg(not(target_arch = "wasm32"))]
 			index_builder: parent.index_builder.clone(),
+			sequences: parent.sequences.clone(),
 			#[cfg(storage)]
 			temporary_directory: parent.temporary_directory.clone(),
 			transaction: parent.transaction.clone(),
@@ -170,6 +182,7 @@ impl MutableContext {
 			deadline: None,
 			cancelled: Arc::new(AtomicBool::new(false)),
 			notifications: from.notifications.clone(),
+			records: None,
 			query_planner: from.query_planner.clone(),
 			query_executor: from.query_executor.clone(),
 			iteration_stage: from.iteration_stage.clone(),
@@ -178,6 +191,7 @@ impl MutableContext {
 			cache: from.cache.clone(),
 			#[cfg(not(target_arch = "wasm32"))]
 			index_builder: from.index_builder.clone(),
+			sequences: from.sequences.clone(),
 			#[cfg(storage)]
 			temporary_directory: from.temporary_directory.clone(),
 			transaction: None,
@@ -193,6 +207,7 @@ impl MutableContext {
 		index_stores: IndexStores,
 		cache: Arc<Cache>,
 		#[cfg(not(target_arch = "wasm32"))] index_builder: IndexBuilder,
+		sequences: Sequences,
 		#[cfg(storage)] temporary_directory: Option<Arc<PathBuf>>,
 	) -> Result<MutableContext, Error> {
 		let mut ctx = Self {
@@ -201,6 +216,7 @@ impl MutableContext {
 			deadline: None,
 			cancelled: Arc::new(AtomicBool::new(false)),
 			notifications: None,
+			records: None,
 			query_planner: None,
 			query_executor: None,
 			iteration_stage: None,
@@ -209,6 +225,7 @@ impl MutableContext {
 			cache: Some(cache),
 			#[cfg(not(target_arch = "wasm32"))]
 			index_builder: Some(index_builder),
+			sequences: Some(sequences),
 			#[cfg(storage)]
 			temporary_directory,
 			transaction: None,
@@ -303,6 +320,16 @@ impl MutableContext {
 		self.deadline.map(|v| v.saturating_duration_since(Instant::now()))
 	}
 
+	/// Add the channel for streaming the records selected by the statement
+	/// which is run with this context.
+	pub(crate) fn set_records(&mut self, records: Option<RecordSender>) {
+		self.records = records
+	}
+
+	pub(crate) fn records(&self) -> Option<RecordSender> {
+		self.records.clone()
+	}
+
 	pub(crate) fn notifications(&self) -> Option<Sender<Notification>> {
 		self.notifications.clone()
 	}
@@ -334,6 +361,11 @@ impl MutableContext {
 		self.index_builder.as_ref()
 	}
 
+	/// Get the sequences batch allocator for this context/ds
+	pub(crate) fn get_sequences(&self) -> Option<&Sequences> {
+		self.sequences.as_ref()
+	}
+
 	// Get the current datastore cache
 	pub(crate) fn get_cache(&self) -> Option<Arc<Cache>> {
 		self.cache.clone()
@@ -452,3 +484,27 @@ impl MutableContext {
 		}
 	}
 }
+
+/// Sends the records selected by a statement, while the statement is running.
+#[derive(Clone, Debug)]
+pub(crate) struct RecordSender {
+	/// The index of the statement in the query set
+	query: usize,
+	/// The channel to send the records through
+	chn: Sender<QueryChunk>,
+}
+
+impl RecordSender {
+	pub(crate) fn new(query: usize, chn: Sender<QueryChunk>) -> Self {
+		Self {
+			query,
+			chn,
+		}
+	}
+
+	/// Sends a batch of records
+	pub(crate) async fn send(&self, records: Vec<Value>) -> Result<(), Error> {
+		self.chn.send(QueryChunk::Records(self.query, records)).await?;
+		Ok(())
+	}
+}
@@ -238,6 +238,7 @@ pub enum RouteTarget {
 	Health,
 	Export,
 	Import,
+	Backup,
 	Rpc,
 	Version,
 	Sync,
@@ -256,6 +257,7 @@ impl fmt::Display for RouteTarget {
 			RouteTarget::Health => write!(f, "health"),
 			RouteTarget::Export => write!(f, "export"),
 			RouteTarget::Import => write!(f, "import"),
+			RouteTarget::Backup => write!(f, "backup"),
 			RouteTarget::Rpc => write!(f, "rpc"),
 			RouteTarget::Version => write!(f, "version"),
 			RouteTarget::Sync => write!(f, "sync"),
@@ -293,6 +295,7 @@ impl std::str::FromStr for RouteTarget {
 			"health" => Ok(RouteTarget::Health),
 			"export" => Ok(RouteTarget::Export),
 			"import" => Ok(RouteTarget::Import),
+			"backup" => Ok(RouteTarget::Backup),
 			"rpc" => Ok(RouteTarget::Rpc),
 			"version" => Ok(RouteTarget::Version),
 			"sync" => Ok(RouteTarget::Sync),
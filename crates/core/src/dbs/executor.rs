@@ -1,8 +1,10 @@
 use crate::ctx::reason::Reason;
-use crate::ctx::Context;
+use crate::ctx::{Context, RecordSender};
 use crate::dbs::response::Response;
 use crate::dbs::Force;
+use crate::dbs::OpenTransaction;
 use crate::dbs::Options;
+use crate::dbs::QueryChunk;
 use crate::dbs::QueryType;
 use crate::err::Error;
 use crate::iam::Action;
@@ -17,6 +19,7 @@ use crate::sql::statement::Statement;
 use crate::sql::statements::{OptionStatement, UseStatement};
 use crate::sql::value::Value;
 use crate::sql::Base;
+use async_channel::Sender;
 use futures::{Stream, StreamExt};
 use reblessive::TreeStack;
 use std::pin::{pin, Pin};
@@ -36,6 +39,10 @@ pub struct Executor {
 	results: Vec<Response>,
 	opt: Options,
 	ctx: Context,
+	/// The channel for sending the output as it is produced, if streaming
+	chunks: Option<Sender<QueryChunk>>,
+	/// The number of responses which have been sent through the channel
+	sent: usize,
 }
 
 impl Executor {
@@ -45,9 +52,36 @@ impl Executor {
 			results: Vec::new(),
 			opt,
 			ctx,
+			chunks: None,
+			sent: 0,
 		}
 	}
 
+	/// Sends the completed responses, if the output is being streamed
+	async fn flush(&mut self) -> Result<(), Error> {
+		if let Some(chn) = &self.chunks {
+			for res in self.results.drain(..) {
+				chn.send(QueryChunk::Response(self.sent, res)).await?;
+				self.sent += 1;
+			}
+		}
+		Ok(())
+	}
+
+	/// Streams the records of a SELECT statement which is run outside of a transaction block
+	fn set_records(&mut self, stmt: &Statement) -> Result<(), Error> {
+		let records = match (&self.chunks, stmt) {
+			(Some(chn), Statement::Select(_)) => {
+				Some(RecordSender::new(self.sent + self.results.len(), chn.clone()))
+			}
+			_ => None,
+		};
+		Arc::get_mut(&mut self.ctx)
+			.ok_or_else(|| fail!("Tried to unfreeze a Context with multiple references"))?
+			.set_records(records);
+		Ok(())
+	}
+
 	fn execute_use_statement(&mut self, stmt: UseStatement) -> Result<(), Error> {
 		let ctx_ref = Arc::get_mut(&mut self.ctx)
 			.ok_or_else(|| fail!("Tried to unfreeze a Context with multiple references"))?;
@@ -159,10 +193,39 @@ impl Executor {
 	}
 
 	/// Execute a query not wrapped in a transaction block.
+	///
+	/// Writeable statements which fail with a transaction conflict are
+	/// re-executed in a new transaction, if a retry policy is specified,
+	/// until the query is cancelled or reaches its timeout.
 	async fn execute_bare_statement(
 		&mut self,
 		kvs: &Datastore,
 		stmt: Statement,
+	) -> Result<Value, Error> {
+		let Some(retry) = self.opt.retry.filter(|_| stmt.writeable()) else {
+			return self.execute_bare_attempt(kvs, stmt).await;
+		};
+		let mut attempts = 1;
+		loop {
+			match self.execute_bare_attempt(kvs, stmt.clone()).await {
+				Err(e)
+					if e.is_retryable() && retry.allows(attempts) && self.ctx.done().is_none() =>
+				{
+					trace!(target: TARGET, attempts, "Retrying statement after a transaction conflict");
+					// Don't wait beyond the deadline of the query
+					retry.wait_within(attempts, self.ctx.timeout()).await;
+					attempts += 1;
+				}
+				res => return res,
+			}
+		}
+	}
+
+	/// Execute a single attempt of a query not wrapped in a transaction block.
+	async fn execute_bare_attempt(
+		&mut self,
+		kvs: &Datastore,
+		stmt: Statement,
 	) -> Result<Value, Error> {
 		// Don't even try to run if the query should already be finished.
 		match self.ctx.done() {
@@ -205,15 +268,11 @@ impl Executor {
 						if let Err(e) = lock.complete_changes(false).await {
 							let _ = lock.cancel().await;
 
-							return Err(Error::QueryNotExecutedDetail {
-								message: e.to_string(),
-							});
+							return Err(Error::not_executed(e));
 						}
 
 						if let Err(e) = lock.commit().await {
-							return Err(Error::QueryNotExecutedDetail {
-								message: e.to_string(),
-							});
+							return Err(Error::not_executed(e));
 						}
 
 						// flush notifications.
@@ -531,6 +590,87 @@ impl Executor {
 		Self::execute_stream(kvs, ctx, opt, stream).await
 	}
 
+	/// Execute a query within a transaction which is held open across queries.
+	///
+	/// When a statement fails, its partial changes may remain in the
+	/// transaction, so the transaction is marked as failed. Any subsequent
+	/// statements are not executed, and the transaction can then only be
+	/// cancelled, as committing it cancels it and returns an error.
+	#[instrument(level = "debug", name = "executor", target = "surrealdb::core::dbs", skip_all)]
+	pub async fn execute_with_transaction(
+		ctx: Context,
+		opt: Options,
+		qry: Query,
+		txn: &OpenTransaction,
+	) -> Result<Vec<Response>, Error> {
+		let mut this = Executor::new(ctx, opt);
+		// Hold back any notifications until the transaction commits
+		if this.ctx.has_notifications() {
+			this.opt.sender = txn.sender();
+		}
+
+		for stmt in qry {
+			let query_type = match stmt {
+				Statement::Live(_) => QueryType::Live,
+				Statement::Kill(_) => QueryType::Kill,
+				_ => QueryType::Other,
+			};
+
+			let now = Instant::now();
+			let result = match stmt {
+				Statement::Option(stmt) => {
+					this.execute_option_statement(stmt)?;
+					continue;
+				}
+				Statement::Begin(_) | Statement::Cancel(_) | Statement::Commit(_) => {
+					Err(Error::QueryNotExecutedDetail {
+						message:
+							"Tried to start or finish a transaction within an open transaction"
+								.to_string(),
+					})
+				}
+				_ if txn.failed() => Err(Error::QueryNotExecutedDetail {
+					message: "A previous statement within the transaction failed".to_string(),
+				}),
+				Statement::Use(stmt) => this.execute_use_statement(stmt).map(|_| Value::None),
+				stmt => match this.execute_transaction_statement(txn.transaction(), stmt).await {
+					Err(Error::Return {
+						value,
+					}) => Ok(value),
+					Err(e) => {
+						// The statement may have left partial changes behind
+						txn.fail();
+						Err(e)
+					}
+					res => res,
+				},
+			};
+			this.results.push(Response {
+				time: now.elapsed(),
+				result,
+				query_type,
+			});
+		}
+		Ok(this.results)
+	}
+
+	/// Execute a query, sending the selected records and the responses
+	/// through the channel as they are produced.
+	#[instrument(level = "debug", name = "executor", target = "surrealdb::core::dbs", skip_all)]
+	pub async fn execute_chunked(
+		kvs: &Datastore,
+		ctx: Context,
+		opt: Options,
+		qry: Query,
+		chn: Sender<QueryChunk>,
+	) -> Result<(), Error> {
+		let mut this = Executor::new(ctx, opt);
+		this.chunks = Some(chn);
+		let stream = futures::stream::iter(qry.into_iter().map(Ok));
+		this.execute_statements(kvs, stream).await?;
+		Ok(())
+	}
+
 	#[instrument(level = "debug", name = "executor", target = "surrealdb::core::dbs", skip_all)]
 	pub async fn execute_stream<S>(
 		kvs: &Datastore,
@@ -541,10 +681,23 @@ impl Executor {
 	where
 		S: Stream<Item = Result<Statement, Error>>,
 	{
-		let mut this = Executor::new(ctx, opt);
+		Executor::new(ctx, opt).execute_statements(kvs, stream).await
+	}
+
+	async fn execute_statements<S>(
+		mut self,
+		kvs: &Datastore,
+		stream: S,
+	) -> Result<Vec<Response>, Error>
+	where
+		S: Stream<Item = Result<Statement, Error>>,
+	{
+		let this = &mut self;
 		let mut stream = pin!(stream);
 
 		while let Some(stmt) = stream.next().await {
+			// Send the responses of the previous statements
+			this.flush().await?;
 			let stmt = match stmt {
 				Ok(x) => x,
 				Err(e) => {
@@ -554,10 +707,13 @@ impl Executor {
 						query_type: QueryType::Other,
 					});
 
-					return Ok(this.results);
+					break;
 				}
 			};
 
+			// Only stream the records of statements outside of transaction blocks
+			this.set_records(&stmt)?;
+
 			match stmt {
 				Statement::Option(stmt) => this.execute_option_statement(stmt)?,
 				// handle option here because it doesn't produce a result.
@@ -569,7 +725,7 @@ impl Executor {
 							query_type: QueryType::Other,
 						});
 
-						return Ok(this.results);
+						break;
 					}
 				}
 				stmt => {
@@ -589,7 +745,9 @@ impl Executor {
 				}
 			}
 		}
-		Ok(this.results)
+		// Send the responses of the last statements
+		this.flush().await?;
+		Ok(self.results)
 	}
 }
 
@@ -705,4 +863,81 @@ mod tests {
 			);
 		}
 	}
+
+	#[tokio::test]
+	async fn check_execute_chunked() {
+		use crate::dbs::QueryChunk;
+		use crate::sql::Value;
+		let ds = Datastore::new("memory").await.unwrap().with_auth_enabled(false);
+		let ses = Session::owner().with_ns("NS").with_db("DB");
+		ds.execute("FOR $i IN 0..2500 { CREATE test:[$i] }", &ses, None).await.unwrap();
+		let sql = "
+			SELECT * FROM test START 10 LIMIT 2000;
+			SELECT * FROM test ORDER BY id DESC LIMIT 5;
+			BEGIN; SELECT * FROM test LIMIT 5; COMMIT;
+			SELECT * FROM ONLY test:[1];
+		";
+		let ast = crate::syn::parse(sql).unwrap();
+		let (send, recv) = async_channel::unbounded();
+		ds.process_chunked(ast, &ses, None, send).await.unwrap();
+		let mut records = [0; 4];
+		let mut results = Vec::new();
+		while let Ok(chunk) = recv.try_recv() {
+			match chunk {
+				QueryChunk::Records(i, v) => {
+					assert!(v.len() <= 1000, "batches are limited in size");
+					assert_eq!(results.len(), i, "records are sent before the response");
+					records[i] += v.len();
+				}
+				QueryChunk::Response(i, res) => {
+					assert_eq!(results.len(), i, "responses are sent in order");
+					results.push(res.result.unwrap());
+				}
+			}
+		}
+		// The records of the first statement were streamed, after START and LIMIT
+		assert_eq!(records, [2000, 0, 0, 0]);
+		assert_eq!(results[0], Value::from(Vec::<Value>::new()));
+		// Ordered and transaction block statements are not streamed
+		assert!(matches!(&results[1], Value::Array(v) if v.len() == 5));
+		assert!(matches!(&results[2], Value::Array(v) if v.len() == 5));
+		// Single record selections are not streamed
+		assert!(matches!(&results[3], Value::Object(_)));
+	}
+
+	#[tokio::test]
+	async fn retry_stops_at_query_timeout() {
+		use crate::dbs::RetryPolicy;
+		use std::sync::Arc;
+		use std::time::{Duration, Instant};
+
+		let ds = Arc::new(
+			Datastore::new("memory")
+				.await
+				.unwrap()
+				.with_query_timeout(Some(Duration::from_millis(200))),
+		);
+		let ses = Session::owner().with_ns("NS").with_db("DB");
+		ds.execute("CREATE counter:one SET count = 0", &ses, None).await.unwrap();
+		// Update the record while a slower query is updating it
+		let slow = tokio::spawn({
+			let ds = ds.clone();
+			let ses = ses
+				.clone()
+				.with_retry(Some(RetryPolicy::new(100).with_backoff(Duration::from_secs(10))));
+			async move {
+				let start = Instant::now();
+				let sql = "UPDATE counter:one SET count += 1, slept = sleep(100ms)";
+				let mut res = ds.execute(sql, &ses, None).await.unwrap();
+				(res.remove(0).result, start.elapsed())
+			}
+		});
+		tokio::time::sleep(Duration::from_millis(20)).await;
+		let mut res = ds.execute("UPDATE counter:one SET count += 1", &ses, None).await.unwrap();
+		res.remove(0).result.unwrap();
+		// The conflicting statement is not retried beyond the query timeout
+		let (res, elapsed) = slow.await.unwrap();
+		assert!(res.is_err(), "result: {res:?}");
+		assert!(elapsed < Duration::from_secs(1), "elapsed: {elapsed:?}");
+	}
 }
@@ -1,5 +1,5 @@
 use crate::ctx::Context;
-use crate::ctx::{Canceller, MutableContext};
+use crate::ctx::{Canceller, MutableContext, RecordSender};
 #[cfg(not(target_arch = "wasm32"))]
 use crate::dbs::distinct::AsyncDistinct;
 use crate::dbs::distinct::SyncDistinct;
@@ -9,6 +9,8 @@ use crate::dbs::processor::Collected;
 #[cfg(not(target_arch = "wasm32"))]
 use crate::dbs::processor::ParallelCollector;
 use crate::dbs::result::Results;
+use crate::dbs::store::StreamCollector;
+use crate::dbs::window;
 use crate::dbs::Options;
 use crate::dbs::Statement;
 use crate::doc::Document;
@@ -133,6 +135,8 @@ pub(crate) struct Iterator {
 	guaranteed: Option<Iterable>,
 	/// Set if the iterator can be cancelled once it reaches start/limit
 	cancel_on_limit: Option<u32>,
+	/// The channel for streaming the output records, if requested
+	records: Option<RecordSender>,
 }
 
 impl Clone for Iterator {
@@ -147,6 +151,7 @@ impl Clone for Iterator {
 			entries: self.entries.clone(),
 			guaranteed: None,
 			cancel_on_limit: None,
+			records: None,
 		}
 	}
 }
@@ -157,6 +162,21 @@ impl Iterator {
 		Self::default()
 	}
 
+	/// Requests that the output records are streamed, when the statement allows it
+	pub(crate) fn stream_records(&mut self, records: Option<RecordSender>) {
+		self.records = records;
+	}
+
+	/// Check if the records of a statement can be sent as they are produced
+	fn is_streamable(stm: &Statement<'_>) -> bool {
+		stm.group().is_none()
+			&& stm.order().is_none()
+			&& stm.split().is_none()
+			&& stm.fetch().is_none()
+			&& stm.explain().is_none()
+			&& !stm.expr().is_some_and(|v| v.has_windows())
+	}
+
 	/// Ingests an iterable for processing
 	pub(crate) fn ingest(&mut self, val: Iterable) {
 		self.entries.push(val)
@@ -324,11 +344,17 @@ impl Iterator {
 		// Process the query START clause
 		self.setup_start(stk, &cancel_ctx, opt, stm).await?;
 		// Prepare the results with possible optimisations on groups
-		self.results = self.results.prepare(
-			#[cfg(storage)]
-			ctx,
-			stm,
-		)?;
+		self.results = match self.records.take() {
+			// Send the records as they are produced
+			Some(records) if Self::is_streamable(stm) => {
+				Results::Stream(StreamCollector::new(records, self.start, self.limit))
+			}
+			_ => self.results.prepare(
+				#[cfg(storage)]
+				ctx,
+				stm,
+			)?,
+		};
 		// Extract the expected behaviour depending on the presence of EXPLAIN with or without FULL
 		let mut plan = Plan::new(ctx, stm, &self.entries, &self.results);
 		if plan.do_iterate {
@@ -364,6 +390,8 @@ impl Iterator {
 			self.output_split(stk, ctx, opt, stm).await?;
 			// Process any GROUP BY clause
 			self.output_group(stk, ctx, opt, stm).await?;
+			// Process any window functions
+			self.output_windows(stk, ctx, opt, stm).await?;
 			// Process any ORDER BY clause
 			if let Some(orders) = stm.order() {
 				#[cfg(not(target_arch = "wasm32"))]
@@ -433,6 +461,10 @@ impl Iterator {
 		if stm.group().is_some() {
 			return false;
 		}
+		// If there are window functions we can't
+		if stm.expr().is_some_and(|v| v.has_windows()) {
+			return false;
+		}
 		// If there is no specified order, we can
 		if stm.order().is_none() {
 			return true;
@@ -524,6 +556,24 @@ impl Iterator {
 		Ok(())
 	}
 
+	async fn output_windows(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		stm: &Statement<'_>,
+	) -> Result<(), Error> {
+		if let Some(fields) = stm.expr().filter(|v| v.has_windows()) {
+			// Compute the window functions over all results
+			let mut values = self.results.take().await?;
+			window::compute(stk, ctx, opt, fields, &mut values).await?;
+			// Collect the results again, ready for any ORDER BY clause
+			self.results = Results::ordered(stm.order(), values);
+		}
+		// Everything ok
+		Ok(())
+	}
+
 	async fn output_fetch(
 		&mut self,
 		stk: &mut Stk,
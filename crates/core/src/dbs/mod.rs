@@ -12,10 +12,13 @@ mod plan;
 mod processor;
 mod response;
 mod result;
+mod retry;
 mod session;
 mod statement;
 mod store;
+mod transaction;
 mod variables;
+mod window;
 
 pub mod capabilities;
 pub mod node;
@@ -26,8 +29,10 @@ pub(crate) use self::iterator::*;
 pub use self::notification::*;
 pub use self::options::*;
 pub use self::response::*;
+pub use self::retry::*;
 pub use self::session::*;
 pub(crate) use self::statement::*;
+pub use self::transaction::*;
 pub(crate) use self::variables::*;
 
 pub mod fuzzy_eq;
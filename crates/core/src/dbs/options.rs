@@ -1,5 +1,5 @@
 use crate::cnf::MAX_COMPUTATION_DEPTH;
-use crate::dbs::Notification;
+use crate::dbs::{Notification, RetryPolicy};
 use crate::err::Error;
 use crate::iam::{Action, Auth, ResourceKind};
 use crate::sql::statements::define::{DefineIndexStatement, DefineTableStatement};
@@ -46,6 +46,8 @@ pub struct Options {
 	pub(crate) version: Option<u64>,
 	/// The channel over which we send notifications
 	pub(crate) sender: Option<Sender<Notification>>,
+	/// How statements which fail with a transaction conflict are retried
+	pub(crate) retry: Option<RetryPolicy>,
 }
 
 #[derive(Clone, Debug)]
@@ -88,6 +90,7 @@ impl Options {
 			sender: None,
 			auth: Arc::new(Auth::default()),
 			version: None,
+			retry: None,
 		}
 	}
 
@@ -217,6 +220,12 @@ impl Options {
 		self
 	}
 
+	/// Set the retry policy for statements which fail with a transaction conflict
+	pub fn with_retry(mut self, retry: Option<RetryPolicy>) -> Self {
+		self.retry = retry;
+		self
+	}
+
 	// --------------------------------------------------
 
 	/// Create a new Options object for a subquery
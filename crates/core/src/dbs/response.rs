@@ -1,4 +1,5 @@
-use crate::err::Error;
+use crate::err::{Error, ErrorData};
+use crate::sql::Uuid;
 use crate::sql::Value as CoreValue;
 use revision::revisioned;
 use revision::Revisioned;
@@ -42,6 +43,44 @@ impl Response {
 	}
 }
 
+/// A part of the output of a query set, sent while the query set is running.
+///
+/// The records selected by a statement are sent in batches, before the
+/// response of the statement. The complete result of a statement consists
+/// of its records, followed by the items of its response result.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum QueryChunk {
+	/// A batch of records selected by the statement at this index
+	Records(usize, Vec<CoreValue>),
+	/// The response of the statement at this index
+	Response(usize, Response),
+}
+
+/// A batch of records streamed to an RPC client while a query set is running.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct Records {
+	/// The id of the stream to which these records belong
+	pub stream: Uuid,
+	/// The index of the statement which selected these records
+	pub query: usize,
+	/// The batch of selected records
+	pub records: Vec<CoreValue>,
+}
+
+impl Records {
+	/// Construct a new batch of streamed records
+	pub const fn new(stream: Uuid, query: usize, records: Vec<CoreValue>) -> Self {
+		Self {
+			stream,
+			query,
+			records,
+		}
+	}
+}
+
 #[revisioned(revision = 1)]
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
@@ -56,19 +95,24 @@ impl Serialize for Response {
 	where
 		S: serde::Serializer,
 	{
-		let mut val = serializer.serialize_struct(TOKEN, 3)?;
-		val.serialize_field("time", self.speed().as_str())?;
 		match &self.result {
 			Ok(v) => {
+				let mut val = serializer.serialize_struct(TOKEN, 3)?;
+				val.serialize_field("time", self.speed().as_str())?;
 				val.serialize_field("status", &Status::Ok)?;
 				val.serialize_field("result", v)?;
+				val.end()
 			}
 			Err(e) => {
+				let mut val = serializer.serialize_struct(TOKEN, 5)?;
+				val.serialize_field("time", self.speed().as_str())?;
 				val.serialize_field("status", &Status::Err)?;
 				val.serialize_field("result", &CoreValue::from(e.to_string()))?;
+				val.serialize_field("code", &e.code())?;
+				val.serialize_field("details", &e.details())?;
+				val.end()
 			}
 		}
-		val.end()
 	}
 }
 
@@ -96,6 +140,49 @@ impl From<&Response> for QueryMethodResponse {
 	}
 }
 
+/// A query response which includes the code and details of the error if
+/// the query failed, for clients which negotiated the RPC error details
+#[revisioned(revision = 1)]
+#[derive(Debug, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct DetailedQueryMethodResponse {
+	pub time: String,
+	pub status: Status,
+	pub result: CoreValue,
+	pub error: Option<ErrorData>,
+}
+
+impl From<&Response> for DetailedQueryMethodResponse {
+	fn from(res: &Response) -> Self {
+		let time = res.speed();
+		let (status, result, error) = match &res.result {
+			Ok(value) => (Status::Ok, value.clone(), None),
+			Err(error) => {
+				(Status::Err, CoreValue::from(error.to_string()), Some(ErrorData::from(error)))
+			}
+		};
+		Self {
+			status,
+			result,
+			time,
+			error,
+		}
+	}
+}
+
+/// Serializes query responses with the same fields for every response.
+///
+/// Binary formats which are not self-describing, such as bincode, can not
+/// represent the additional error fields which are only present on failed
+/// responses, so these formats receive each response without them. Clients
+/// which negotiated the error details receive [`DetailedQueryMethodResponse`]s.
+pub(crate) fn serialize_fixed<S>(responses: &[Response], serializer: S) -> Result<S::Ok, S::Error>
+where
+	S: serde::Serializer,
+{
+	serializer.collect_seq(responses.iter().map(QueryMethodResponse::from))
+}
+
 impl Revisioned for Response {
 	#[inline]
 	fn serialize_revisioned<W: std::io::Write>(
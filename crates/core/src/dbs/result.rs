@@ -5,7 +5,7 @@ use crate::dbs::plan::Explanation;
 use crate::dbs::store::asynchronous::AsyncMemoryOrdered;
 #[cfg(storage)]
 use crate::dbs::store::file::FileCollector;
-use crate::dbs::store::{MemoryCollector, MemoryOrdered, MemoryRandom};
+use crate::dbs::store::{MemoryCollector, MemoryOrdered, MemoryRandom, StreamCollector};
 use crate::dbs::{Options, Statement};
 use crate::err::Error;
 use crate::sql::order::Ordering;
@@ -22,6 +22,7 @@ pub(super) enum Results {
 	#[cfg(storage)]
 	File(Box<FileCollector>),
 	Groups(GroupsCollector),
+	Stream(StreamCollector),
 }
 
 impl Results {
@@ -33,6 +34,10 @@ impl Results {
 		if stm.expr().is_some() && stm.group().is_some() {
 			return Ok(Self::Groups(GroupsCollector::new(stm)));
 		}
+		// Window functions are computed over all results before any ordering
+		if stm.expr().is_some_and(|v| v.has_windows()) {
+			return Ok(Self::Memory(Default::default()));
+		}
 		#[cfg(storage)]
 		if stm.tempfiles() {
 			if let Some(temp_dir) = ctx.temporary_directory() {
@@ -54,6 +59,23 @@ impl Results {
 		Ok(Self::Memory(Default::default()))
 	}
 
+	/// Collects already computed values, ready to be sorted by an ORDER BY clause
+	pub(super) fn ordered(ordering: Option<&Ordering>, values: Vec<Value>) -> Self {
+		match ordering {
+			Some(Ordering::Random) => {
+				let mut c = MemoryRandom::new(None);
+				values.into_iter().for_each(|v| c.push(v));
+				Self::MemoryRandom(c)
+			}
+			Some(Ordering::Order(orders)) => {
+				let mut c = MemoryOrdered::new(orders.clone(), None);
+				values.into_iter().for_each(|v| c.push(v));
+				Self::MemoryOrdered(c)
+			}
+			None => values.into(),
+		}
+	}
+
 	pub(super) async fn push(
 		&mut self,
 		stk: &mut Stk,
@@ -84,6 +106,9 @@ impl Results {
 			Self::Groups(g) => {
 				g.push(stk, ctx, opt, stm, val).await?;
 			}
+			Self::Stream(s) => {
+				s.push(val).await?;
+			}
 		}
 		Ok(())
 	}
@@ -98,7 +123,7 @@ impl Results {
 				c.finalize().await?;
 			}
 			Self::MemoryRandom(c) => c.sort(),
-			Self::None | Self::Memory(_) | Self::Groups(_) => {}
+			Self::None | Self::Memory(_) | Self::Groups(_) | Self::Stream(_) => {}
 		}
 		Ok(())
 	}
@@ -110,7 +135,7 @@ impl Results {
 			Self::MemoryRandom(c) => c.sort(),
 			#[cfg(storage)]
 			Self::File(f) => f.sort(orders),
-			Self::None | Self::Groups(_) | Self::Memory(_) => {}
+			Self::None | Self::Groups(_) | Self::Memory(_) | Self::Stream(_) => {}
 		}
 	}
 
@@ -130,7 +155,7 @@ impl Results {
 			Self::MemoryRandom(c) => c.start_limit(start, limit),
 			#[cfg(storage)]
 			Self::File(f) => f.start_limit(start, limit),
-			Self::Groups(_) => {}
+			Self::Groups(_) | Self::Stream(_) => {}
 		}
 		Ok(())
 	}
@@ -150,6 +175,7 @@ impl Results {
 			#[cfg(storage)]
 			Self::File(e) => e.len(),
 			Self::Groups(g) => g.len(),
+			Self::Stream(s) => s.len(),
 		}
 	}
 
@@ -162,6 +188,7 @@ impl Results {
 			Self::MemoryRandom(c) => c.take_vec(),
 			#[cfg(storage)]
 			Self::File(f) => f.take_vec().await?,
+			Self::Stream(s) => s.take_vec().await?,
 			Self::None | Self::Groups(_) => vec![],
 		})
 	}
@@ -183,6 +210,7 @@ impl Results {
 			Self::Groups(g) => {
 				g.explain(exp);
 			}
+			Self::Stream(s) => s.explain(exp),
 		}
 	}
 }
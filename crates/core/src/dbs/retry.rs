@@ -0,0 +1,174 @@
+use crate::cnf::{MAX_RETRY_ATTEMPTS, MAX_RETRY_BACKOFF};
+use crate::err::Error;
+use crate::sql::{Duration as SqlDuration, Number, Value};
+use std::time::Duration;
+
+/// Specifies how a statement which failed with a transaction conflict is retried.
+///
+/// A statement is re-executed in a new transaction until it succeeds, fails with
+/// an error which can not be retried, or the maximum number of attempts is reached.
+/// The delay between attempts doubles after each attempt, up to the maximum backoff.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub struct RetryPolicy {
+	/// The maximum number of attempts, including the first attempt
+	pub max_attempts: u32,
+	/// The delay before the first retry
+	pub backoff: Duration,
+	/// The maximum delay between attempts
+	pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+	fn default() -> Self {
+		RetryPolicy {
+			max_attempts: 3,
+			backoff: Duration::from_millis(10),
+			max_backoff: Duration::from_secs(1),
+		}
+	}
+}
+
+impl RetryPolicy {
+	/// Create a retry policy with the specified maximum number of attempts
+	pub fn new(max_attempts: u32) -> Self {
+		RetryPolicy {
+			max_attempts,
+			..Default::default()
+		}
+	}
+
+	/// Set the delay before the first retry
+	pub fn with_backoff(mut self, backoff: Duration) -> Self {
+		self.backoff = backoff;
+		self
+	}
+
+	/// Set the maximum delay between attempts
+	pub fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+		self.max_backoff = max_backoff;
+		self
+	}
+
+	/// Checks if another attempt can be made after the specified number of attempts
+	pub fn allows(&self, attempts: u32) -> bool {
+		attempts < self.max_attempts
+	}
+
+	/// Calculates the delay before the next attempt, after the specified number of attempts
+	pub fn delay(&self, attempts: u32) -> Duration {
+		let factor = 2u32.saturating_pow(attempts.saturating_sub(1));
+		self.backoff.saturating_mul(factor).min(self.max_backoff)
+	}
+
+	/// Waits before the next attempt, after the specified number of attempts
+	pub async fn wait(&self, attempts: u32) {
+		sleep(self.delay(attempts)).await;
+	}
+
+	/// Waits before the next attempt, but no longer than the specified timeout
+	pub(crate) async fn wait_within(&self, attempts: u32, timeout: Option<Duration>) {
+		let dur = self.delay(attempts);
+		sleep(timeout.map_or(dur, |timeout| dur.min(timeout))).await;
+	}
+
+	/// Limits the policy to the maximums configured for the server
+	fn clamp(mut self) -> Self {
+		self.max_attempts = self.max_attempts.min(*MAX_RETRY_ATTEMPTS);
+		self.max_backoff = self.max_backoff.min(Duration::from_millis(*MAX_RETRY_BACKOFF));
+		self
+	}
+}
+
+async fn sleep(dur: Duration) {
+	#[cfg(target_arch = "wasm32")]
+	wasmtimer::tokio::sleep(dur).await;
+	#[cfg(not(target_arch = "wasm32"))]
+	tokio::time::sleep(dur).await;
+}
+
+impl TryFrom<&Value> for RetryPolicy {
+	type Error = Error;
+	fn try_from(value: &Value) -> Result<Self, Self::Error> {
+		match value {
+			Value::Object(obj) => {
+				let mut policy = RetryPolicy::default();
+				match obj.get("max_attempts") {
+					Some(Value::Number(Number::Int(v))) if *v > 0 => {
+						policy.max_attempts = (*v).try_into().unwrap_or(u32::MAX);
+					}
+					Some(v) => {
+						return Err(Error::InvalidRetryPolicy(
+							v.to_owned(),
+							"a positive integer".into(),
+						))
+					}
+					None => (),
+				}
+				// Durations can be specified as strings, for formats without a duration type
+				let duration = |v: &Value| match v {
+					Value::Duration(d) => Ok(d.0),
+					Value::Strand(s) => match SqlDuration::try_from(s.as_str()) {
+						Ok(d) => Ok(d.0),
+						Err(_) => Err(Error::InvalidRetryPolicy(v.to_owned(), "a duration".into())),
+					},
+					_ => Err(Error::InvalidRetryPolicy(v.to_owned(), "a duration".into())),
+				};
+				if let Some(v) = obj.get("backoff") {
+					policy.backoff = duration(v)?;
+				}
+				if let Some(v) = obj.get("max_backoff") {
+					policy.max_backoff = duration(v)?;
+				}
+				Ok(policy.clamp())
+			}
+			v => Err(Error::InvalidRetryPolicy(v.to_owned(), "an object".into())),
+		}
+	}
+}
+
+impl From<RetryPolicy> for Value {
+	fn from(policy: RetryPolicy) -> Self {
+		Value::from(map! {
+			"max_attempts".to_string() => Value::from(policy.max_attempts),
+			"backoff".to_string() => Value::from(policy.backoff),
+			"max_backoff".to_string() => Value::from(policy.max_backoff),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn exponential_backoff() {
+		let policy = RetryPolicy::new(5)
+			.with_backoff(Duration::from_millis(10))
+			.with_max_backoff(Duration::from_millis(50));
+		assert_eq!(policy.delay(1), Duration::from_millis(10));
+		assert_eq!(policy.delay(2), Duration::from_millis(20));
+		assert_eq!(policy.delay(3), Duration::from_millis(40));
+		assert_eq!(policy.delay(4), Duration::from_millis(50));
+		assert!(policy.allows(4));
+		assert!(!policy.allows(5));
+	}
+
+	#[test]
+	fn convert_value() {
+		let policy = RetryPolicy::new(5).with_backoff(Duration::from_millis(20));
+		assert_eq!(RetryPolicy::try_from(&Value::from(policy)).unwrap(), policy);
+		let value = crate::syn::value("{ max_attempts: 5, backoff: '20ms' }").unwrap();
+		assert_eq!(RetryPolicy::try_from(&value).unwrap(), policy);
+		let value = crate::syn::value("{ max_attempts: 0 }").unwrap();
+		assert!(RetryPolicy::try_from(&value).is_err());
+	}
+
+	#[test]
+	fn clamp_value() {
+		let value = crate::syn::value("{ max_attempts: 1000000, max_backoff: 1d }").unwrap();
+		let policy = RetryPolicy::try_from(&value).unwrap();
+		assert_eq!(policy.max_attempts, *MAX_RETRY_ATTEMPTS);
+		assert_eq!(policy.max_backoff, Duration::from_millis(*MAX_RETRY_BACKOFF));
+	}
+}
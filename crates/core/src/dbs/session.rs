@@ -1,4 +1,5 @@
 use crate::ctx::MutableContext;
+use crate::dbs::RetryPolicy;
 use crate::iam::Auth;
 use crate::iam::{Level, Role};
 use crate::sql::value::Value;
@@ -31,6 +32,8 @@ pub struct Session {
 	pub rd: Option<Value>,
 	/// The current expiration time of the session
 	pub exp: Option<i64>,
+	/// How statements which fail with a transaction conflict are retried
+	pub retry: Option<RetryPolicy>,
 }
 
 impl Session {
@@ -58,6 +61,12 @@ impl Session {
 		self
 	}
 
+	/// Set the retry policy for statements which fail with a transaction conflict
+	pub fn with_retry(mut self, retry: Option<RetryPolicy>) -> Session {
+		self.retry = retry;
+		self
+	}
+
 	/// Retrieves the selected namespace
 	pub(crate) fn ns(&self) -> Option<Arc<str>> {
 		self.ns.as_deref().map(Into::into)
@@ -145,6 +154,7 @@ impl Session {
 			tk: None,
 			rd: Some(rid),
 			exp: None,
+			retry: None,
 		}
 	}
 
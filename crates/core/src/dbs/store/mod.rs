@@ -5,8 +5,9 @@ pub(super) mod file;
 #[cfg(not(target_arch = "wasm32"))]
 mod llrbtree;
 
+use crate::cnf::MAX_STREAM_BATCH_SIZE;
+use crate::ctx::RecordSender;
 use crate::dbs::plan::Explanation;
-#[cfg(not(target_arch = "wasm32"))]
 use crate::err::Error;
 use crate::sql::order::OrderList;
 use crate::sql::value::Value;
@@ -62,6 +63,78 @@ impl From<Vec<Value>> for MemoryCollector {
 	}
 }
 
+/// Sends the records of a statement in batches, as they are produced.
+///
+/// The START and LIMIT clauses are applied as the records are pushed, as the
+/// records which have already been sent can not be removed afterwards.
+pub(in crate::dbs) struct StreamCollector {
+	/// The channel and statement index to send the records with
+	records: RecordSender,
+	/// The current batch of records, sent once full
+	batch: Vec<Value>,
+	/// The number of records pushed to the collector
+	count: usize,
+	/// The number of records to skip
+	start: usize,
+	/// The maximum number of records to send
+	limit: Option<usize>,
+}
+
+impl StreamCollector {
+	pub(in crate::dbs) fn new(
+		records: RecordSender,
+		start: Option<u32>,
+		limit: Option<u32>,
+	) -> Self {
+		Self {
+			records,
+			batch: Vec::new(),
+			count: 0,
+			start: start.unwrap_or_default() as usize,
+			limit: limit.map(|l| l as usize),
+		}
+	}
+
+	pub(in crate::dbs) async fn push(&mut self, val: Value) -> Result<(), Error> {
+		self.count += 1;
+		// Skip the records before the START clause
+		let Some(pos) = self.count.checked_sub(self.start + 1) else {
+			return Ok(());
+		};
+		// Ignore the records after the LIMIT clause
+		if self.limit.is_some_and(|l| pos >= l) {
+			return Ok(());
+		}
+		self.batch.push(val);
+		if self.batch.len() >= *MAX_STREAM_BATCH_SIZE as usize {
+			self.flush().await?;
+		}
+		Ok(())
+	}
+
+	/// The number of records pushed, including any skipped records
+	pub(in crate::dbs) fn len(&self) -> usize {
+		self.count
+	}
+
+	/// Sends any remaining records, leaving no records to output
+	pub(in crate::dbs) async fn take_vec(&mut self) -> Result<Vec<Value>, Error> {
+		self.flush().await?;
+		Ok(Vec::new())
+	}
+
+	async fn flush(&mut self) -> Result<(), Error> {
+		if !self.batch.is_empty() {
+			self.records.send(mem::take(&mut self.batch)).await?;
+		}
+		Ok(())
+	}
+
+	pub(in crate::dbs) fn explain(&self, exp: &mut Explanation) {
+		exp.add_collector("Stream", vec![]);
+	}
+}
+
 pub(super) const DEFAULT_BATCH_SIZE: usize = 1024;
 
 /// The struct MemoryRandom represents an in-memory store that aggregates data randomly.
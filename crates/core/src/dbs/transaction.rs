@@ -0,0 +1,97 @@
+use crate::dbs::Notification;
+use crate::err::Error;
+use crate::kvs::Transaction;
+use async_channel::{Receiver, Sender};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A write transaction which is held open across multiple requests.
+///
+/// Queries processed within an open transaction only see each other's
+/// changes, until the transaction is committed. This allows application
+/// code to read, modify, and write data atomically. Any live query
+/// notifications are held back until the transaction commits.
+#[non_exhaustive]
+pub struct OpenTransaction {
+	/// The underlying datastore transaction
+	txn: Arc<Transaction>,
+	/// The channel which buffers notifications until the transaction commits
+	buffer: Option<(Sender<Notification>, Receiver<Notification>)>,
+	/// The channel which notifications are sent to once the transaction commits
+	sink: Option<Sender<Notification>>,
+	/// Whether a statement within this transaction has failed
+	failed: AtomicBool,
+}
+
+impl OpenTransaction {
+	pub(crate) fn new(txn: Transaction, sink: Option<Sender<Notification>>) -> Self {
+		Self {
+			txn: txn.enclose(),
+			buffer: sink.is_some().then(async_channel::unbounded),
+			sink,
+			failed: AtomicBool::new(false),
+		}
+	}
+
+	/// The underlying datastore transaction
+	pub(crate) fn transaction(&self) -> Arc<Transaction> {
+		self.txn.clone()
+	}
+
+	/// The channel for buffering notifications within this transaction
+	pub(crate) fn sender(&self) -> Option<Sender<Notification>> {
+		self.buffer.as_ref().map(|(send, _)| send.clone())
+	}
+
+	/// Mark this transaction as failed, so that it can no longer be committed
+	pub(crate) fn fail(&self) {
+		self.failed.store(true, Ordering::Release);
+	}
+
+	/// Check if a statement within this transaction has failed
+	pub fn failed(&self) -> bool {
+		self.failed.load(Ordering::Acquire)
+	}
+
+	/// Check if this transaction has been committed or cancelled
+	pub async fn closed(&self) -> bool {
+		self.txn.closed().await
+	}
+
+	/// Commit all of the changes made within this transaction
+	///
+	/// If any statement within the transaction failed, the transaction is
+	/// cancelled instead, so that its partial changes are never committed.
+	pub async fn commit(&self) -> Result<(), Error> {
+		let mut lock = self.txn.lock().await;
+		// Refuse to commit the partial changes of a failed statement
+		if self.failed() {
+			lock.cancel().await?;
+			return Err(Error::QueryNotExecutedDetail {
+				message:
+					"A statement within the transaction failed, so the transaction was cancelled"
+						.to_string(),
+			});
+		}
+		// Complete the change feeds, and then commit
+		if let Err(e) = lock.complete_changes(false).await {
+			let _ = lock.cancel().await;
+			return Err(e);
+		}
+		lock.commit().await?;
+		// Release the buffered notifications
+		if let (Some((_, recv)), Some(sink)) = (&self.buffer, &self.sink) {
+			while let Ok(notification) = recv.try_recv() {
+				if sink.send(notification).await.is_err() {
+					break;
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Cancel all of the changes made within this transaction
+	pub async fn cancel(&self) -> Result<(), Error> {
+		self.txn.cancel().await
+	}
+}
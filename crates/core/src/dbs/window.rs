@@ -0,0 +1,183 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::err::Error;
+use crate::sql::order::OrderList;
+use crate::sql::window::{DENSE_RANK, FIRST_VALUE, LAG, LAST_VALUE, LEAD, RANK, ROW_NUMBER};
+use crate::sql::{Array, Field, Fields, Function, Value, Window};
+use reblessive::tree::Stk;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+/// The values which were computed for a single row, before the window is computed
+struct Row {
+	/// The computed function arguments
+	args: Vec<Value>,
+	/// The computed ordering values
+	order: Vec<Value>,
+}
+
+/// Computes every window function field over the collected results of a statement.
+///
+/// While each document is being processed, a window field stores the
+/// computed function arguments, partition values and ordering values
+/// at the output field. Once all of the results have been collected,
+/// each row is replaced with the result of the window function.
+pub(super) async fn compute(
+	stk: &mut Stk,
+	ctx: &Context,
+	opt: &Options,
+	fields: &Fields,
+	values: &mut [Value],
+) -> Result<(), Error> {
+	let single = fields.single().is_some();
+	for field in fields.iter() {
+		let Field::Window {
+			expr,
+			window,
+			alias,
+		} = field
+		else {
+			continue;
+		};
+		let name = alias.clone().unwrap_or_else(|| expr.to_idiom());
+		// Extract the stored values for each row
+		let mut rows = Vec::with_capacity(values.len());
+		let mut partitions: BTreeMap<Array, Vec<usize>> = BTreeMap::new();
+		for (i, v) in values.iter().enumerate() {
+			let stored = match single {
+				true => v.clone(),
+				false => v.pick(&name),
+			};
+			let (args, partition, order) = match stored {
+				Value::Array(Array(v)) => match <[Value; 3]>::try_from(v) {
+					Ok([Value::Array(a), Value::Array(p), Value::Array(o)]) => (a.0, p, o.0),
+					_ => (vec![], Array::new(), vec![]),
+				},
+				_ => (vec![], Array::new(), vec![]),
+			};
+			partitions.entry(partition).or_default().push(i);
+			rows.push(Row {
+				args,
+				order,
+			});
+		}
+		// Compute the window function over each partition
+		for mut indexes in partitions.into_values() {
+			if let Some(orders) = &window.order {
+				indexes.sort_by(|a, b| compare(orders, &rows[*a].order, &rows[*b].order));
+			}
+			let results =
+				stk.run(|stk| partition(stk, ctx, opt, expr, window, &rows, &indexes)).await?;
+			for (i, x) in indexes.into_iter().zip(results) {
+				match single {
+					true => values[i] = x,
+					false => values[i].set(stk, ctx, opt, &name, x).await?,
+				}
+			}
+		}
+	}
+	Ok(())
+}
+
+/// Compares the ordering values of two rows
+fn compare(orders: &OrderList, a: &[Value], b: &[Value]) -> Ordering {
+	for (i, order) in orders.0.iter().enumerate() {
+		let (Some(a), Some(b)) = (a.get(i), b.get(i)) else {
+			continue;
+		};
+		// Reverse the ordering if DESC
+		let o = match order.direction {
+			true => a.compare(b, &[], order.collate, order.numeric),
+			false => b.compare(a, &[], order.collate, order.numeric),
+		};
+		match o {
+			Some(Ordering::Equal) | None => continue,
+			Some(o) => return o,
+		}
+	}
+	Ordering::Equal
+}
+
+/// Computes the window function for each of the ordered rows in a partition
+async fn partition(
+	stk: &mut Stk,
+	ctx: &Context,
+	opt: &Options,
+	func: &Function,
+	window: &Window,
+	rows: &[Row],
+	indexes: &[usize],
+) -> Result<Vec<Value>, Error> {
+	let len = indexes.len();
+	let row = |pos: usize| &rows[indexes[pos]];
+	let arg = |pos: usize, i: usize| row(pos).args.get(i).cloned().unwrap_or_default();
+	// Check whether two rows share the same ordering values
+	let peers = |a: usize, b: usize| match &window.order {
+		Some(orders) => compare(orders, &row(a).order, &row(b).order) == Ordering::Equal,
+		None => true,
+	};
+	// Find the last peer of each row, as the end of its peer group
+	let mut last_peer = vec![len; len];
+	for pos in (0..len.saturating_sub(1)).rev() {
+		last_peer[pos] = match peers(pos, pos + 1) {
+			true => last_peer[pos + 1],
+			false => pos + 1,
+		};
+	}
+	// Without an explicit frame, aggregates run up to the last peer of the current row,
+	// as with the default `RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW` frame
+	let range = |pos: usize| match &window.frame {
+		Some(frame) => frame.range(pos, len),
+		None => 0..last_peer[pos],
+	};
+	let mut out = Vec::with_capacity(len);
+	let mut rank = 0;
+	let mut dense_rank = 0;
+	for pos in 0..len {
+		// Track the rank of each group of peer rows
+		if pos == 0 || !peers(pos - 1, pos) {
+			rank = pos + 1;
+			dense_rank += 1;
+		}
+		let val = match func.name() {
+			Some(ROW_NUMBER) => Value::from(pos as i64 + 1),
+			Some(RANK) => Value::from(rank as i64),
+			Some(DENSE_RANK) => Value::from(dense_rank as i64),
+			Some(LAG) | Some(LEAD) => {
+				let offset = match arg(pos, 1) {
+					Value::None => 1,
+					v => v.coerce_to_i64()?,
+				};
+				let target = match func.name() {
+					Some(LAG) => pos as i64 - offset,
+					_ => pos as i64 + offset,
+				};
+				match target {
+					x if x >= 0 && (x as usize) < len => arg(x as usize, 0),
+					_ => arg(pos, 2),
+				}
+			}
+			Some(FIRST_VALUE) => {
+				let range = range(pos);
+				match range.is_empty() {
+					true => Value::None,
+					false => arg(range.start, 0),
+				}
+			}
+			Some(LAST_VALUE) => {
+				let range = range(pos);
+				match range.is_empty() {
+					true => Value::None,
+					false => arg(range.end - 1, 0),
+				}
+			}
+			// This is an aggregate function computed over the frame
+			_ => {
+				let vals: Vec<Value> = range(pos).map(|p| arg(p, 0)).collect();
+				func.aggregate(vals.into())?.compute(stk, ctx, opt, None).await?
+			}
+		};
+		out.push(val);
+	}
+	Ok(out)
+}
@@ -20,6 +20,7 @@ impl Document {
 		self.check_permissions_table(stk, ctx, opt, stm).await?;
 		self.clear_record_data(ctx, opt, stm).await?;
 		self.store_index_data(stk, ctx, opt, stm).await?;
+		self.store_expiry_data(ctx, opt, stm).await?;
 		self.purge(stk, ctx, opt, stm).await?;
 		self.process_table_views(stk, ctx, opt, stm).await?;
 		self.process_table_lives(stk, ctx, opt, stm).await?;
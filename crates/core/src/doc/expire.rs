@@ -0,0 +1,59 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::dbs::Statement;
+use crate::doc::Document;
+use crate::err::Error;
+
+impl Document {
+	pub(super) async fn store_expiry_data(
+		&self,
+		ctx: &Context,
+		opt: &Options,
+		_stm: &Statement<'_>,
+	) -> Result<(), Error> {
+		// Check if changed
+		if !self.changed() {
+			return Ok(());
+		}
+		// Get the table definition
+		let tb = self.tb(ctx, opt).await?;
+		// Records of DROP tables are never stored
+		if tb.drop {
+			return Ok(());
+		}
+		// Check if the table has an expiry
+		let Some(expire) = &tb.expire else {
+			return Ok(());
+		};
+		// Get the transaction
+		let txn = ctx.tx();
+		// Get the record id
+		let rid = self.id()?;
+		// Get NS & DB
+		let ns = opt.ns()?;
+		let db = opt.db()?;
+		// Remove the previous expiry entry
+		let key = crate::key::table::er::new(ns, db, &rid.tb, &rid.id);
+		if let Some(v) = txn.get(key.clone(), None).await? {
+			let ts = u64::from_be_bytes(
+				v.try_into()
+					.map_err(|_| Error::Internal("Invalid record expiry timestamp".to_string()))?,
+			);
+			txn.del(crate::key::table::ex::new(ns, db, &rid.tb, ts, &rid.id)).await?;
+			txn.del(key).await?;
+		}
+		// Check if the record has been deleted
+		if self.current.doc.as_ref().is_none() {
+			return Ok(());
+		}
+		// Store the new expiry entry
+		if let Some(ts) = expire.timestamp(self.current.doc.as_ref()) {
+			let key = crate::key::table::ex::new(ns, db, &rid.tb, ts, &rid.id);
+			txn.set(key, vec![], None).await?;
+			let key = crate::key::table::er::new(ns, db, &rid.tb, &rid.id);
+			txn.set(key, ts.to_be_bytes().to_vec(), None).await?;
+		}
+		// Carry on
+		Ok(())
+	}
+}
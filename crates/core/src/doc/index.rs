@@ -4,6 +4,7 @@ use crate::dbs::{Force, Statement};
 use crate::doc::{CursorDoc, Document};
 use crate::err::Error;
 use crate::idx::ft::FtIndex;
+use crate::idx::spatial::SpatialIndex;
 use crate::idx::trees::mtree::MTreeIndex;
 use crate::idx::IndexKeyBase;
 use crate::key;
@@ -11,7 +12,7 @@ use crate::key;
 use crate::kvs::ConsumeResult;
 use crate::kvs::TransactionType;
 use crate::sql::array::Array;
-use crate::sql::index::{HnswParams, Index, MTreeParams, SearchParams};
+use crate::sql::index::{HnswParams, Index, MTreeParams, SearchParams, SpatialParams};
 use crate::sql::statements::DefineIndexStatement;
 use crate::sql::{Part, Thing, Value};
 use reblessive::tree::Stk;
@@ -94,6 +95,7 @@ impl Document {
 			Index::Search(p) => ic.index_full_text(stk, ctx, p).await?,
 			Index::MTree(p) => ic.index_mtree(stk, ctx, p).await?,
 			Index::Hnsw(p) => ic.index_hnsw(ctx, p).await?,
+			Index::Spatial(p) => ic.index_spatial(ctx, p).await?,
 		}
 		Ok(())
 	}
@@ -440,4 +442,19 @@ impl<'a> IndexOperation<'a> {
 		}
 		Ok(())
 	}
+
+	async fn index_spatial(&mut self, ctx: &Context, p: &SpatialParams) -> Result<(), Error> {
+		let txn = ctx.tx();
+		let ikb = IndexKeyBase::new(self.opt.ns()?, self.opt.db()?, self.ix)?;
+		let si = SpatialIndex::new(ikb, p);
+		// Delete the old index data
+		if let Some(o) = self.o.take() {
+			si.remove_document(&txn, &self.rid.id, &o).await?;
+		}
+		// Create the new index data
+		if let Some(n) = self.n.take() {
+			si.index_document(&txn, &self.rid.id, &n).await?;
+		}
+		Ok(())
+	}
 }
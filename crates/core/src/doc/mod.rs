@@ -25,6 +25,7 @@ mod changefeeds; // Processes any change feeds relevant for this document
 mod check; // Data and condition checking for this document
 mod edges; // Attempts to store the edge data for this document
 mod event; // Processes any table events relevant for this document
+mod expire; // Attempts to store the expiry data for this document
 mod field; // Processes any schema-defined fields for this document
 mod index; // Attempts to store the index data for this document
 mod lives; // Processes any live queries relevant for this document
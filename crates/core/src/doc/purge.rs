@@ -12,6 +12,7 @@ use crate::sql::statements::DeleteStatement;
 use crate::sql::table::Tables;
 use crate::sql::value::{Value, Values};
 use reblessive::tree::Stk;
+use std::sync::Arc;
 
 impl Document {
 	pub(super) async fn purge(
@@ -38,6 +39,8 @@ impl Document {
 			// Purge the record data
 			let key = crate::key::thing::new(ns, db, &rid.tb, &rid.id);
 			txn.del(key).await?;
+			// Purge the cached record data
+			ctx.tx().set_record_cache(ns, db, &rid.tb, &rid.id, Arc::new(Value::None))?;
 			// Purge the record edges
 			match (
 				self.initial.doc.as_ref().pick(&*EDGE),
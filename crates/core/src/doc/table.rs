@@ -1,6 +1,7 @@
+use crate::cnf::NORMAL_FETCH_SIZE;
 use crate::ctx::Context;
 use crate::dbs::Options;
-use crate::dbs::{Force, Statement};
+use crate::dbs::{Force, Statement, Workable};
 use crate::doc::{CursorDoc, Document};
 use crate::err::Error;
 use crate::sql::data::Data;
@@ -10,7 +11,7 @@ use crate::sql::idiom::Idiom;
 use crate::sql::number::Number;
 use crate::sql::operator::Operator;
 use crate::sql::part::Part;
-use crate::sql::paths::ID;
+use crate::sql::paths::{ID, IN, OUT};
 use crate::sql::statements::delete::DeleteStatement;
 use crate::sql::statements::ifelse::IfelseStatement;
 use crate::sql::statements::upsert::UpsertStatement;
@@ -21,6 +22,7 @@ use crate::sql::value::{Value, Values};
 use crate::sql::{Cond, Function, Groups, View};
 use futures::future::try_join_all;
 use reblessive::tree::Stk;
+use std::sync::Arc;
 
 type Ops = Vec<(Idiom, Operator, Value)>;
 
@@ -95,186 +97,356 @@ impl Document {
 		// Loop through all foreign table statements
 		for ft in fts.iter() {
 			// Get the table definition
-			let tb = ft.view.as_ref().unwrap();
-			// Check if there is a GROUP BY clause
-			match &tb.group {
-				// There is a GROUP BY clause specified
-				Some(group) => {
-					// Check if a WHERE clause is specified
-					match &tb.cond {
-						// There is a WHERE clause specified
-						Some(cond) => {
-							// What do we do with the initial value on UPDATE and DELETE?
-							if !targeted_force
-								&& act != Action::Create && cond
-								.compute(stk, ctx, opt, Some(&self.initial))
-								.await?
-								.is_truthy()
-							{
-								// Delete the old value in the table
-								let fdc = FieldDataContext {
-									ft,
-									act: FieldAction::Sub,
-									view: tb,
-									groups: group,
-									group_ids: Self::get_group_ids(
-										stk,
-										ctx,
-										opt,
-										group,
-										&self.initial,
-									)
-									.await?,
-									doc: &self.initial,
-								};
-								self.data(stk, ctx, opt, fdc).await?;
-							}
-							// What do we do with the current value on CREATE and UPDATE?
-							if act != Action::Delete
-								&& cond
-									.compute(stk, ctx, opt, Some(&self.current))
-									.await?
-									.is_truthy()
-							{
-								// Update the new value in the table
-								let fdc = FieldDataContext {
-									ft,
-									act: FieldAction::Add,
-									view: tb,
-									groups: group,
-									group_ids: Self::get_group_ids(
-										stk,
-										ctx,
-										opt,
-										group,
-										&self.current,
-									)
+			let view = ft.view.as_ref().unwrap();
+			// Check if this record belongs to a source table of the view
+			if view.what.iter().any(|p| p.0 == rid.tb) {
+				// ASYNC views are processed from the change feed
+				if !view.asynchronous {
+					self.process_table_view(stk, ctx, opt, ft, &act, targeted_force).await?;
+				}
+			}
+			// Refresh the view rows which were projected through this record
+			if !targeted_force && view.group.is_none() && view.has_links() {
+				self.process_view_links(stk, ctx, opt, ft).await?;
+			}
+		}
+		// Carry on
+		Ok(())
+	}
+
+	/// Applies a change which was read from the change feed
+	/// of a source table to an ASYNC table view. The initial
+	/// value is NONE when the record was created, and the
+	/// current value is NONE when the record was deleted.
+	pub(crate) async fn process_view_change(
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		ft: &DefineTableStatement,
+		rid: Thing,
+		initial: Value,
+		current: Value,
+	) -> Result<(), Error> {
+		// Don't run permissions
+		let opt = &opt.new_with_perms(false);
+		// Get the query action
+		let act = if current.is_none() {
+			Action::Delete
+		} else if initial.is_none() {
+			Action::Create
+		} else {
+			Action::Update
+		};
+		// Create a document for the change
+		let rid = Arc::new(rid);
+		let mut doc = Document::new(
+			Some(rid.clone()),
+			None,
+			None,
+			Arc::new(current),
+			Workable::Normal,
+			false,
+		);
+		doc.initial = CursorDoc::new(Some(rid), None, initial);
+		// Process the view
+		doc.process_table_view(stk, ctx, opt, ft, &act, false).await
+	}
+
+	/// Processes a single foreign table for this record
+	async fn process_table_view(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		ft: &DefineTableStatement,
+		act: &Action,
+		targeted_force: bool,
+	) -> Result<(), Error> {
+		// Get the record id
+		let rid = self.id()?;
+		// Get the table definition
+		let view = ft.view.as_ref().unwrap();
+		// Check if there is a GROUP BY clause
+		match &view.group {
+			// There is a GROUP BY clause specified
+			Some(group) => {
+				// Check if a WHERE clause is specified
+				match &view.cond {
+					// There is a WHERE clause specified
+					Some(cond) => {
+						// What do we do with the initial value on UPDATE and DELETE?
+						if !targeted_force
+							&& *act != Action::Create
+							&& cond.compute(stk, ctx, opt, Some(&self.initial)).await?.is_truthy()
+						{
+							// Delete the old value in the table
+							let fdc = FieldDataContext {
+								ft,
+								act: FieldAction::Sub,
+								view,
+								groups: group,
+								group_ids: Self::get_group_ids(stk, ctx, opt, group, &self.initial)
 									.await?,
-									doc: &self.current,
-								};
-								self.data(stk, ctx, opt, fdc).await?;
-							}
+								doc: &self.initial,
+							};
+							self.data(stk, ctx, opt, fdc).await?;
 						}
-						// No WHERE clause is specified
-						None => {
-							if !targeted_force && act != Action::Create {
-								// Delete the old value in the table
-								let fdc = FieldDataContext {
-									ft,
-									act: FieldAction::Sub,
-									view: tb,
-									groups: group,
-									group_ids: Self::get_group_ids(
-										stk,
-										ctx,
-										opt,
-										group,
-										&self.initial,
-									)
-									.await?,
-									doc: &self.initial,
-								};
-								self.data(stk, ctx, opt, fdc).await?;
-							}
-							if act != Action::Delete {
-								// Update the new value in the table
-								let fdc = FieldDataContext {
-									ft,
-									act: FieldAction::Add,
-									view: tb,
-									groups: group,
-									group_ids: Self::get_group_ids(
-										stk,
-										ctx,
-										opt,
-										group,
-										&self.current,
-									)
+						// What do we do with the current value on CREATE and UPDATE?
+						if *act != Action::Delete
+							&& cond.compute(stk, ctx, opt, Some(&self.current)).await?.is_truthy()
+						{
+							// Update the new value in the table
+							let fdc = FieldDataContext {
+								ft,
+								act: FieldAction::Add,
+								view,
+								groups: group,
+								group_ids: Self::get_group_ids(stk, ctx, opt, group, &self.current)
 									.await?,
-									doc: &self.current,
-								};
-								self.data(stk, ctx, opt, fdc).await?;
-							}
+								doc: &self.current,
+							};
+							self.data(stk, ctx, opt, fdc).await?;
 						}
 					}
-				}
-				// No GROUP BY clause is specified
-				None => {
-					// Set the current record id
-					let rid = Thing {
-						tb: ft.name.to_raw(),
-						id: rid.id.clone(),
-					};
-					// Check if a WHERE clause is specified
-					match &tb.cond {
-						// There is a WHERE clause specified
-						Some(cond) => {
-							match cond.compute(stk, ctx, opt, Some(&self.current)).await? {
-								v if v.is_truthy() => {
-									// Define the statement
-									match act {
-										// Delete the value in the table
-										Action::Delete => {
-											let stm = DeleteStatement {
-												what: Values(vec![Value::from(rid)]),
-												..DeleteStatement::default()
-											};
-											// Execute the statement
-											stm.compute(stk, ctx, opt, None).await?;
-										}
-										// Update the value in the table
-										_ => {
-											let stm = UpsertStatement {
-												what: Values(vec![Value::from(rid)]),
-												data: Some(
-													self.full(stk, ctx, opt, &tb.expr).await?,
-												),
-												..UpsertStatement::default()
-											};
-											// Execute the statement
-											stm.compute(stk, ctx, opt, None).await?;
-										}
-									};
-								}
-								_ => {
-									// Delete the value in the table
-									let stm = DeleteStatement {
-										what: Values(vec![Value::from(rid)]),
-										..DeleteStatement::default()
-									};
-									// Execute the statement
-									stm.compute(stk, ctx, opt, None).await?;
-								}
-							}
+					// No WHERE clause is specified
+					None => {
+						if !targeted_force && *act != Action::Create {
+							// Delete the old value in the table
+							let fdc = FieldDataContext {
+								ft,
+								act: FieldAction::Sub,
+								view,
+								groups: group,
+								group_ids: Self::get_group_ids(stk, ctx, opt, group, &self.initial)
+									.await?,
+								doc: &self.initial,
+							};
+							self.data(stk, ctx, opt, fdc).await?;
 						}
-						// No WHERE clause is specified
-						None => {
-							// Define the statement
-							match act {
-								// Delete the value in the table
-								Action::Delete => {
-									let stm = DeleteStatement {
-										what: Values(vec![Value::from(rid)]),
-										..DeleteStatement::default()
-									};
-									// Execute the statement
-									stm.compute(stk, ctx, opt, None).await?;
-								}
-								// Update the value in the table
-								_ => {
-									let stm = UpsertStatement {
-										what: Values(vec![Value::from(rid)]),
-										data: Some(self.full(stk, ctx, opt, &tb.expr).await?),
-										..UpsertStatement::default()
-									};
-									// Execute the statement
-									stm.compute(stk, ctx, opt, None).await?;
-								}
+						if *act != Action::Delete {
+							// Update the new value in the table
+							let fdc = FieldDataContext {
+								ft,
+								act: FieldAction::Add,
+								view,
+								groups: group,
+								group_ids: Self::get_group_ids(stk, ctx, opt, group, &self.current)
+									.await?,
+								doc: &self.current,
 							};
+							self.data(stk, ctx, opt, fdc).await?;
 						}
 					}
 				}
 			}
+			// No GROUP BY clause is specified
+			None => {
+				// Check if the record was deleted
+				let doc = match act {
+					Action::Delete => None,
+					_ => Some(&self.current),
+				};
+				// Update or delete the record in the table
+				Self::process_view_record(stk, ctx, opt, ft, &rid, doc).await?;
+			}
+		}
+		// Carry on
+		Ok(())
+	}
+
+	/// Updates the record of a view without a GROUP BY clause,
+	/// or deletes it if the source record no longer exists or
+	/// no longer matches the view condition. This returns the
+	/// records which the view record was projected through.
+	async fn process_view_record(
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		ft: &DefineTableStatement,
+		rid: &Thing,
+		doc: Option<&CursorDoc>,
+	) -> Result<Vec<Thing>, Error> {
+		// Get the table definition
+		let view = ft.view.as_ref().unwrap();
+		// Set the current record id
+		let vid = Thing {
+			tb: ft.name.to_raw(),
+			id: rid.id.clone(),
+		};
+		// Check if a WHERE clause is specified
+		let doc = match (doc, &view.cond) {
+			(Some(doc), Some(cond)) => match cond.compute(stk, ctx, opt, Some(doc)).await? {
+				v if v.is_truthy() => Some(doc),
+				_ => None,
+			},
+			(doc, _) => doc,
+		};
+		match doc {
+			// Update the value in the table
+			Some(doc) => {
+				let stm = UpsertStatement {
+					what: Values(vec![Value::from(vid)]),
+					data: Some(Self::full(stk, ctx, opt, &view.expr, doc).await?),
+					..UpsertStatement::default()
+				};
+				// Execute the statement
+				stm.compute(stk, ctx, opt, None).await?;
+				// Store the records which the value was projected through
+				Self::store_view_links(stk, ctx, opt, ft, rid, doc).await
+			}
+			// Delete the value in the table
+			None => {
+				let stm = DeleteStatement {
+					what: Values(vec![Value::from(vid)]),
+					..DeleteStatement::default()
+				};
+				// Execute the statement
+				stm.compute(stk, ctx, opt, None).await?;
+				Ok(vec![])
+			}
+		}
+	}
+
+	/// Stores a link to the source record under each record
+	/// which a view record was projected through, either by
+	/// following a record link or by traversing a graph edge.
+	async fn store_view_links(
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		ft: &DefineTableStatement,
+		rid: &Thing,
+		doc: &CursorDoc,
+	) -> Result<Vec<Thing>, Error> {
+		// Get the table definition
+		let view = ft.view.as_ref().unwrap();
+		// Compute each idiom prefix which could return a record
+		let mut links = Vec::new();
+		for idiom in view.idioms() {
+			// Only follow idioms which start from the record
+			if !matches!(idiom.first(), Some(Part::Field(_) | Part::Graph(_))) {
+				continue;
+			}
+			for i in 1..idiom.len() {
+				let val = Value::Idiom(Idiom::from(&idiom[..i]));
+				let val = val.compute(stk, ctx, opt, Some(doc)).await?;
+				Self::collect_links(val, &mut links);
+			}
+		}
+		links.retain(|v| v != rid && v.tb != ft.name.as_str());
+		links.sort();
+		links.dedup();
+		// Store the links to the source record
+		let ns = opt.ns()?;
+		let db = opt.db()?;
+		let txn = ctx.tx();
+		for link in links.iter() {
+			let key =
+				crate::key::table::vd::new(ns, db, &link.tb, &ft.name, &link.id, &rid.tb, &rid.id);
+			txn.set(key, vec![], None).await?;
+		}
+		Ok(links)
+	}
+
+	/// Collects the record ids within a computed value
+	fn collect_links(val: Value, links: &mut Vec<Thing>) {
+		match val {
+			Value::Thing(v) => links.push(v),
+			Value::Array(v) => v.into_iter().for_each(|v| Self::collect_links(v, links)),
+			_ => {}
+		}
+	}
+
+	/// Refreshes the records of a view which were projected
+	/// through this record. This also refreshes the records
+	/// of both sides of a graph edge, so that a view which
+	/// traverses the edge is updated when it is created.
+	async fn process_view_links(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		ft: &DefineTableStatement,
+	) -> Result<(), Error> {
+		// Get the NS + DB
+		let ns = opt.ns()?;
+		let db = opt.db()?;
+		// Get the record id
+		let rid = self.id()?;
+		// Get the table definition
+		let view = ft.view.as_ref().unwrap();
+		// Get the transaction
+		let txn = ctx.tx();
+		// Refresh the source records linked through this record
+		let beg = crate::key::table::vd::prefix_id(ns, db, &rid.tb, &ft.name, &rid.id);
+		let end = crate::key::table::vd::suffix_id(ns, db, &rid.tb, &ft.name, &rid.id);
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let res = txn.batch(rng, *NORMAL_FETCH_SIZE, false, None).await?;
+			next = res.next;
+			let mut srcs = Vec::with_capacity(res.values.len());
+			for (key, _) in res.values.iter() {
+				let key = crate::key::table::vd::Vd::decode(key)?;
+				srcs.push(Thing {
+					tb: key.st.to_owned(),
+					id: key.sk,
+				});
+			}
+			self.refresh_view_sources(stk, ctx, opt, ft, srcs).await?;
+		}
+		// Refresh the source records connected by this edge
+		let mut srcs = Vec::new();
+		for doc in [&self.initial, &self.current] {
+			for side in [&*IN, &*OUT] {
+				if let Value::Thing(v) = doc.doc.as_ref().pick(side) {
+					if view.what.iter().any(|p| p.0 == v.tb) {
+						srcs.push(v);
+					}
+				}
+			}
+		}
+		self.refresh_view_sources(stk, ctx, opt, ft, srcs).await
+	}
+
+	/// Refreshes the view records of the given source records,
+	/// removing the links which are no longer projected through
+	/// this record.
+	async fn refresh_view_sources(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		ft: &DefineTableStatement,
+		mut srcs: Vec<Thing>,
+	) -> Result<(), Error> {
+		// Get the NS + DB
+		let ns = opt.ns()?;
+		let db = opt.db()?;
+		// Get the record id
+		let rid = self.id()?;
+		// Get the transaction
+		let txn = ctx.tx();
+		srcs.retain(|v| v != rid.as_ref());
+		srcs.sort();
+		srcs.dedup();
+		// Refresh the record of each source record
+		for src in srcs {
+			// Fetch the latest value of the source record
+			let val = txn.get_record(ns, db, &src.tb, &src.id, None).await?;
+			let links = match val.is_some() {
+				true => {
+					let doc = CursorDoc::new(Some(Arc::new(src.clone())), None, val);
+					Self::process_view_record(stk, ctx, opt, ft, &src, Some(&doc)).await?
+				}
+				false => Self::process_view_record(stk, ctx, opt, ft, &src, None).await?,
+			};
+			// Remove the link if it is no longer projected through this record
+			if !links.contains(&rid) {
+				let key = crate::key::table::vd::new(
+					ns, db, &rid.tb, &ft.name, &rid.id, &src.tb, &src.id,
+				);
+				txn.del(key).await?;
+			}
 		}
 		// Carry on
 		Ok(())
@@ -300,13 +472,13 @@ impl Document {
 
 	//
 	async fn full(
-		&self,
 		stk: &mut Stk,
 		ctx: &Context,
 		opt: &Options,
 		exp: &Fields,
+		doc: &CursorDoc,
 	) -> Result<Data, Error> {
-		let mut data = exp.compute(stk, ctx, opt, Some(&self.current), false).await?;
+		let mut data = exp.compute(stk, ctx, opt, Some(doc), false).await?;
 		data.cut(ID.as_ref());
 		Ok(Data::ReplaceExpression(data))
 	}
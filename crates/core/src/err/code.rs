@@ -0,0 +1,915 @@
+use super::Error;
+use crate::iam::Error as IamError;
+use crate::sql::Object;
+use crate::sql::Value;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// A stable, machine-readable code identifying the kind of an error.
+///
+/// Unlike error messages, which can change between releases, the string
+/// representation of each code is stable, so clients can rely on it when
+/// deciding how to handle an error, for instance whether to retry it.
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ErrorCode {
+	/// The database encountered an internal problem
+	Internal,
+	/// An error was thrown by a query or an event
+	Thrown,
+	/// There was a problem with the underlying datastore
+	Datastore,
+	/// A transaction failed and can not be retried as is
+	TransactionFailed,
+	/// A transaction conflicted with another, and can be retried
+	TransactionConflict,
+	/// A query exceeded its timeout
+	QueryTimeout,
+	/// A query was cancelled
+	QueryCancelled,
+	/// A query was not executed because of an earlier failure
+	QueryNotExecuted,
+	/// A query, or an RPC request, could not be parsed
+	ParseError,
+	/// A query is not valid
+	InvalidQuery,
+	/// A function was called with invalid arguments
+	InvalidArguments,
+	/// An arithmetic operation failed
+	Arithmetic,
+	/// No namespace was selected
+	NoNamespace,
+	/// No database was selected
+	NoDatabase,
+	/// A resource does not exist
+	NotFound,
+	/// A resource already exists
+	AlreadyExists,
+	/// A record with the same id already exists
+	RecordExists,
+	/// A value violated a unique index
+	UniqueIndexViolation,
+	/// A field value could not be coerced to the field type
+	FieldCoercion,
+	/// A field value did not pass the field assertion
+	FieldAssertion,
+	/// A readonly field was changed
+	FieldReadonly,
+	/// A field is not defined on a schemafull table
+	FieldUndefined,
+	/// A value could not be coerced or converted to a type
+	TypeCoercion,
+	/// A record id did not match the record being processed
+	RecordMismatch,
+	/// The session is not permitted to perform an action
+	PermissionDenied,
+	/// An action is not allowed by the capabilities of the server
+	CapabilityDenied,
+	/// Authentication failed
+	AuthenticationFailed,
+	/// The session or token has expired
+	SessionExpired,
+	/// A query exceeded a limit
+	LimitExceeded,
+	/// A request to a remote service failed
+	ExternalRequest,
+	/// A feature is unsupported
+	Unsupported,
+	/// An RPC request is not valid
+	InvalidRequest,
+	/// The parameters of an RPC request are not valid
+	InvalidParams,
+	/// The RPC method does not exist
+	MethodNotFound,
+	/// The RPC method is not allowed
+	MethodNotAllowed,
+}
+
+impl ErrorCode {
+	/// Returns the stable string representation of this code
+	pub const fn as_str(&self) -> &'static str {
+		match self {
+			Self::Internal => "internal",
+			Self::Thrown => "thrown",
+			Self::Datastore => "datastore",
+			Self::TransactionFailed => "transaction_failed",
+			Self::TransactionConflict => "transaction_conflict",
+			Self::QueryTimeout => "query_timeout",
+			Self::QueryCancelled => "query_cancelled",
+			Self::QueryNotExecuted => "query_not_executed",
+			Self::ParseError => "parse_error",
+			Self::InvalidQuery => "invalid_query",
+			Self::InvalidArguments => "invalid_arguments",
+			Self::Arithmetic => "arithmetic",
+			Self::NoNamespace => "no_namespace",
+			Self::NoDatabase => "no_database",
+			Self::NotFound => "not_found",
+			Self::AlreadyExists => "already_exists",
+			Self::RecordExists => "record_exists",
+			Self::UniqueIndexViolation => "unique_index_violation",
+			Self::FieldCoercion => "field_coercion",
+			Self::FieldAssertion => "field_assertion",
+			Self::FieldReadonly => "field_readonly",
+			Self::FieldUndefined => "field_undefined",
+			Self::TypeCoercion => "type_coercion",
+			Self::RecordMismatch => "record_mismatch",
+			Self::PermissionDenied => "permission_denied",
+			Self::CapabilityDenied => "capability_denied",
+			Self::AuthenticationFailed => "authentication_failed",
+			Self::SessionExpired => "session_expired",
+			Self::LimitExceeded => "limit_exceeded",
+			Self::ExternalRequest => "external_request",
+			Self::Unsupported => "unsupported",
+			Self::InvalidRequest => "invalid_request",
+			Self::InvalidParams => "invalid_params",
+			Self::MethodNotFound => "method_not_found",
+			Self::MethodNotAllowed => "method_not_allowed",
+		}
+	}
+
+	/// Check if an operation which failed with this code can be retried
+	pub const fn is_retryable(&self) -> bool {
+		matches!(self, Self::TransactionConflict)
+	}
+}
+
+impl Display for ErrorCode {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+impl From<ErrorCode> for Value {
+	fn from(code: ErrorCode) -> Self {
+		code.as_str().into()
+	}
+}
+
+/// The machine-readable description of an error, sent alongside its message
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ErrorData {
+	/// The stable code of the error
+	pub code: ErrorCode,
+	/// The structured details of the error, which depend on the code
+	pub details: Object,
+}
+
+impl ErrorData {
+	/// Construct the description of an error from its code and details
+	pub fn new(code: ErrorCode, details: Object) -> Self {
+		Self {
+			code,
+			details,
+		}
+	}
+}
+
+impl From<&Error> for ErrorData {
+	fn from(error: &Error) -> Self {
+		Self::new(error.code(), error.details())
+	}
+}
+
+impl From<ErrorData> for Value {
+	fn from(data: ErrorData) -> Self {
+		map! {
+			"code" => Value::from(data.code),
+			"details" => Value::from(data.details),
+		}
+		.into()
+	}
+}
+
+impl Error {
+	/// Returns the stable code identifying the kind of this error
+	pub fn code(&self) -> ErrorCode {
+		match self {
+			Error::Ignore
+			| Error::Break
+			| Error::Continue
+			| Error::RetryWithId(_)
+			| Error::Return {
+				..
+			}
+			| Error::Unreachable(_)
+			| Error::Channel(_)
+			| Error::Io(_)
+			| Error::Encode(_)
+			| Error::Decode(_)
+			| Error::Revision(_)
+			| Error::CorruptedIndex(_)
+			| Error::Bincode(_)
+			| Error::FstError(_)
+			| Error::Utf8Error(_)
+			| Error::ObsError(_)
+			| Error::ModelComputation(_)
+			| Error::DuplicatedMatchRef {
+				..
+			}
+			| Error::TimestampOverflow(_)
+			| Error::Internal(_)
+			| Error::CorruptedVersionstampInKey(_)
+			| Error::NodeAgent(_)
+			| Error::Serialization(_)
+			| Error::MissingStorageEngine
+			| Error::InvalidStorageVersion
+			| Error::OutdatedStorageVersion
+			| Error::NonComputed
+			| Error::InsufficientReserve(_)
+			| Error::QueryStream(_)
+			| Error::OrderingError(_)
+			| Error::TokenMakingFailed => ErrorCode::Internal,
+			Error::Thrown(_) => ErrorCode::Thrown,
+			Error::Ds(_) => ErrorCode::Datastore,
+			Error::Tx(_)
+			| Error::TxFailure
+			| Error::TxFinished
+			| Error::TxReadonly
+			| Error::TxConditionNotMet
+			| Error::TxKeyAlreadyExists
+			| Error::TxKeyTooLarge
+			| Error::TxValueTooLarge
+			| Error::TxTooLarge
+			| Error::TxNotFound(_) => ErrorCode::TransactionFailed,
+			Error::TxRetryable => ErrorCode::TransactionConflict,
+			Error::QueryTimedout => ErrorCode::QueryTimeout,
+			Error::QueryCancelled => ErrorCode::QueryCancelled,
+			Error::QueryNotExecuted
+			| Error::QueryNotExecutedDetail {
+				..
+			} => ErrorCode::QueryNotExecuted,
+			Error::InvalidQuery(_) | Error::QueryRemaining => ErrorCode::ParseError,
+			Error::QueryEmpty
+			| Error::InvalidContent {
+				..
+			}
+			| Error::InvalidMerge {
+				..
+			}
+			| Error::InvalidPatch {
+				..
+			}
+			| Error::PatchTest {
+				..
+			}
+			| Error::InvalidParam {
+				..
+			}
+			| Error::InvalidField {
+				..
+			}
+			| Error::InvalidFetch {
+				..
+			}
+			| Error::InvalidSplit {
+				..
+			}
+			| Error::InvalidOrder {
+				..
+			}
+			| Error::InvalidGroup {
+				..
+			}
+			| Error::InvalidLimit {
+				..
+			}
+			| Error::InvalidStart {
+				..
+			}
+			| Error::InvalidScript {
+				..
+			}
+			| Error::InvalidModel {
+				..
+			}
+			| Error::InvalidFunction {
+				..
+			}
+			| Error::InvalidUrl(_)
+			| Error::InvalidVectorDimension {
+				..
+			}
+			| Error::InvalidVectorDistance {
+				..
+			}
+			| Error::InvalidVectorType {
+				..
+			}
+			| Error::InvalidVectorValue(_)
+			| Error::InvalidRegex(_)
+			| Error::InvalidTimeout(_)
+			| Error::InvalidStatementTarget {
+				..
+			}
+			| Error::CreateStatement {
+				..
+			}
+			| Error::UpsertStatement {
+				..
+			}
+			| Error::UpdateStatement {
+				..
+			}
+			| Error::RelateStatement {
+				..
+			}
+			| Error::RelateStatementIn {
+				..
+			}
+			| Error::RelateStatementId {
+				..
+			}
+			| Error::RelateStatementOut {
+				..
+			}
+			| Error::DeleteStatement {
+				..
+			}
+			| Error::InsertStatement {
+				..
+			}
+			| Error::InsertStatementIn {
+				..
+			}
+			| Error::InsertStatementId {
+				..
+			}
+			| Error::InsertStatementOut {
+				..
+			}
+			| Error::LiveStatement {
+				..
+			}
+			| Error::KillStatement {
+				..
+			}
+			| Error::SingleOnlyOutput
+			| Error::TableIsView {
+				..
+			}
+			| Error::ViewChangefeedRequired {
+				..
+			}
+			| Error::ViewLinkUntyped {
+				..
+			}
+			| Error::NoIndexFoundForMatch {
+				..
+			}
+			| Error::AnalyzerError(_)
+			| Error::HighlightError(_)
+			| Error::InvalidLevel(_)
+			| Error::IamError(IamError::InvalidRole(_))
+			| Error::IndexAlreadyBuilding {
+				..
+			}
+			| Error::AccessInvalidDuration
+			| Error::AccessInvalidExpiration
+			| Error::TbInvalid {
+				..
+			}
+			| Error::InvalidRangeValue {
+				..
+			}
+			| Error::InvalidExportConfig(..)
+			| Error::InvalidImportConfig(..)
+			| Error::InvalidRetryPolicy(..)
+			| Error::InvalidFormatData(..)
+			| Error::InvalidBackup(_)
+			| Error::ChangefeedRequired {
+				..
+			}
+			| Error::FullBackupRequired {
+				..
+			}
+			| Error::InvalidBound {
+				..
+			}
+			| Error::RepeatRecurseNotRecursing
+			| Error::IdiomRecursionAlreadyRecursing {
+				..
+			}
+			| Error::InvalidVersion {
+				..
+			}
+			| Error::RecursionInstructionPlanConflict => ErrorCode::InvalidQuery,
+			Error::InvalidArguments {
+				..
+			}
+			| Error::InvalidAggregation {
+				..
+			} => ErrorCode::InvalidArguments,
+			Error::TryAdd(..)
+			| Error::TrySub(..)
+			| Error::TryMul(..)
+			| Error::TryDiv(..)
+			| Error::TryRem(..)
+			| Error::TryPow(..)
+			| Error::TryNeg(_)
+			| Error::ArithmeticOverflow(_)
+			| Error::ArithmeticNegativeOverflow(_) => ErrorCode::Arithmetic,
+			Error::NsEmpty => ErrorCode::NoNamespace,
+			Error::DbEmpty => ErrorCode::NoDatabase,
+			Error::NsNotFound {
+				..
+			}
+			| Error::NlNotFound {
+				..
+			}
+			| Error::DbNotFound {
+				..
+			}
+			| Error::DlNotFound {
+				..
+			}
+			| Error::EvNotFound {
+				..
+			}
+			| Error::FcNotFound {
+				..
+			}
+			| Error::FdNotFound {
+				..
+			}
+			| Error::MlNotFound {
+				..
+			}
+			| Error::NdNotFound {
+				..
+			}
+			| Error::PaNotFound {
+				..
+			}
+			| Error::SqNotFound {
+				..
+			}
+			| Error::CgNotFound {
+				..
+			}
+			| Error::TbNotFound {
+				..
+			}
+			| Error::LvNotFound {
+				..
+			}
+			| Error::LqNotFound {
+				..
+			}
+			| Error::AzNotFound {
+				..
+			}
+			| Error::IxNotFound {
+				..
+			}
+			| Error::IdNotFound {
+				..
+			}
+			| Error::UserRootNotFound {
+				..
+			}
+			| Error::UserNsNotFound {
+				..
+			}
+			| Error::UserDbNotFound {
+				..
+			}
+			| Error::AccessRootNotFound {
+				..
+			}
+			| Error::AccessGrantRootNotFound {
+				..
+			}
+			| Error::AccessNsNotFound {
+				..
+			}
+			| Error::AccessGrantNsNotFound {
+				..
+			}
+			| Error::AccessDbNotFound {
+				..
+			}
+			| Error::AccessGrantDbNotFound {
+				..
+			}
+			| Error::AccessNotFound => ErrorCode::NotFound,
+			Error::ClAlreadyExists {
+				..
+			}
+			| Error::AzAlreadyExists {
+				..
+			}
+			| Error::DbAlreadyExists {
+				..
+			}
+			| Error::EvAlreadyExists {
+				..
+			}
+			| Error::FdAlreadyExists {
+				..
+			}
+			| Error::FcAlreadyExists {
+				..
+			}
+			| Error::IxAlreadyExists {
+				..
+			}
+			| Error::MlAlreadyExists {
+				..
+			}
+			| Error::NsAlreadyExists {
+				..
+			}
+			| Error::PaAlreadyExists {
+				..
+			}
+			| Error::SqAlreadyExists {
+				..
+			}
+			| Error::CgAlreadyExists {
+				..
+			}
+			| Error::TbAlreadyExists {
+				..
+			}
+			| Error::NtAlreadyExists {
+				..
+			}
+			| Error::DtAlreadyExists {
+				..
+			}
+			| Error::UserRootAlreadyExists {
+				..
+			}
+			| Error::UserNsAlreadyExists {
+				..
+			}
+			| Error::UserDbAlreadyExists {
+				..
+			}
+			| Error::AccessRootAlreadyExists {
+				..
+			}
+			| Error::AccessNsAlreadyExists {
+				..
+			}
+			| Error::AccessDbAlreadyExists {
+				..
+			} => ErrorCode::AlreadyExists,
+			Error::RecordExists {
+				..
+			} => ErrorCode::RecordExists,
+			Error::IndexExists {
+				..
+			} => ErrorCode::UniqueIndexViolation,
+			Error::FieldCheck {
+				..
+			} => ErrorCode::FieldCoercion,
+			Error::FieldValue {
+				..
+			} => ErrorCode::FieldAssertion,
+			Error::FieldReadonly {
+				..
+			} => ErrorCode::FieldReadonly,
+			Error::FieldUndefined {
+				..
+			} => ErrorCode::FieldUndefined,
+			Error::CoerceTo {
+				..
+			}
+			| Error::ConvertTo {
+				..
+			}
+			| Error::SetCheck {
+				..
+			}
+			| Error::FunctionCheck {
+				..
+			}
+			| Error::TableCheck {
+				..
+			}
+			| Error::LengthInvalid {
+				..
+			}
+			| Error::TryFrom(..)
+			| Error::IdInvalid {
+				..
+			}
+			| Error::InInvalid {
+				..
+			}
+			| Error::OutInvalid {
+				..
+			} => ErrorCode::TypeCoercion,
+			Error::IdMismatch {
+				..
+			}
+			| Error::InMismatch {
+				..
+			}
+			| Error::InOverride {
+				..
+			}
+			| Error::OutMismatch {
+				..
+			}
+			| Error::OutOverride {
+				..
+			} => ErrorCode::RecordMismatch,
+			Error::NsNotAllowed {
+				..
+			}
+			| Error::DbNotAllowed {
+				..
+			}
+			| Error::TablePermissions {
+				..
+			}
+			| Error::ParamPermissions {
+				..
+			}
+			| Error::FunctionPermissions {
+				..
+			}
+			| Error::IamError(IamError::NotAllowed {
+				..
+			}) => ErrorCode::PermissionDenied,
+			Error::HttpDisabled
+			| Error::RealtimeDisabled
+			| Error::ScriptingNotAllowed
+			| Error::FunctionNotAllowed(_)
+			| Error::NetTargetNotAllowed(_) => ErrorCode::CapabilityDenied,
+			Error::NoRecordFound
+			| Error::SignupQueryFailed
+			| Error::SigninQueryFailed
+			| Error::MissingUserOrPass
+			| Error::NoSigninTarget
+			| Error::InvalidPass
+			| Error::InvalidAuth
+			| Error::UnexpectedAuth
+			| Error::InvalidSignup
+			| Error::UnknownAuth
+			| Error::MissingTokenHeader(_)
+			| Error::MissingTokenClaim(_)
+			| Error::AccessLevelMismatch
+			| Error::AccessMethodMismatch
+			| Error::AccessRecordSignupQueryFailed
+			| Error::AccessRecordSigninQueryFailed
+			| Error::AccessRecordNoSignup
+			| Error::AccessRecordNoSignin
+			| Error::AccessBearerMissingKey
+			| Error::AccessGrantBearerInvalid
+			| Error::AccessGrantInvalidSubject
+			| Error::AccessGrantRevoked => ErrorCode::AuthenticationFailed,
+			Error::ExpiredToken | Error::ExpiredSession => ErrorCode::SessionExpired,
+			Error::SqExhausted {
+				..
+			}
+			| Error::ComputationDepthExceeded
+			| Error::RangeTooBig {
+				..
+			}
+			| Error::QueryTooLarge
+			| Error::IdiomRecursionLimitExceeded {
+				..
+			} => ErrorCode::LimitExceeded,
+			Error::Http(_) => ErrorCode::ExternalRequest,
+			Error::Deprecated(_)
+			| Error::UnsupportedDistance(_)
+			| Error::FeatureNotYetImplemented {
+				..
+			}
+			| Error::Unimplemented(_)
+			| Error::UnsupportedDestructure {
+				..
+			}
+			| Error::UnsupportedVersionedQueries
+			| Error::UnsupportedRepeatRecurse
+			| Error::UnsupportedFormat(_) => ErrorCode::Unsupported,
+		}
+	}
+
+	/// Returns the structured details of this error.
+	///
+	/// The details are an object whose fields depend on the error code,
+	/// and which is empty for errors which carry no further information.
+	pub fn details(&self) -> Object {
+		match self {
+			Error::IndexExists {
+				thing,
+				index,
+				value,
+			} => map! {
+				"record" => Value::from(thing.clone()),
+				"index" => Value::from(index.as_str()),
+				"value" => Value::from(value.as_str()),
+			},
+			Error::RecordExists {
+				thing,
+			} => map! {
+				"record" => Value::from(thing.clone()),
+			},
+			Error::FieldCheck {
+				thing,
+				value,
+				field,
+				check,
+			}
+			| Error::FieldValue {
+				thing,
+				value,
+				field,
+				check,
+			} => map! {
+				"record" => Value::from(thing.as_str()),
+				"field" => Value::from(field.to_string()),
+				"value" => Value::from(value.as_str()),
+				"check" => Value::from(check.as_str()),
+			},
+			Error::FieldReadonly {
+				thing,
+				field,
+			} => map! {
+				"record" => Value::from(thing.as_str()),
+				"field" => Value::from(field.to_string()),
+			},
+			Error::FieldUndefined {
+				table,
+				field,
+			} => map! {
+				"table" => Value::from(table.as_str()),
+				"field" => Value::from(field.to_string()),
+			},
+			Error::CoerceTo {
+				from,
+				into,
+			}
+			| Error::ConvertTo {
+				from,
+				into,
+			} => map! {
+				"value" => Value::from(from.to_string()),
+				"into" => Value::from(into.as_str()),
+			},
+			Error::SetCheck {
+				value,
+				name,
+				check,
+			}
+			| Error::FunctionCheck {
+				name,
+				value,
+				check,
+			} => map! {
+				"name" => Value::from(name.as_str()),
+				"value" => Value::from(value.as_str()),
+				"check" => Value::from(check.as_str()),
+			},
+			Error::InvalidArguments {
+				name,
+				message,
+			} => map! {
+				"function" => Value::from(name.as_str()),
+				"message" => Value::from(message.as_str()),
+			},
+			Error::TablePermissions {
+				table,
+			} => map! {
+				"table" => Value::from(table.as_str()),
+			},
+			Error::ParamPermissions {
+				name,
+			} => map! {
+				"param" => Value::from(name.as_str()),
+			},
+			Error::FunctionPermissions {
+				name,
+			} => map! {
+				"function" => Value::from(name.as_str()),
+			},
+			Error::NsNotAllowed {
+				ns,
+			} => map! {
+				"namespace" => Value::from(ns.as_str()),
+			},
+			Error::DbNotAllowed {
+				db,
+			} => map! {
+				"database" => Value::from(db.as_str()),
+			},
+			Error::IamError(IamError::NotAllowed {
+				actor,
+				action,
+				resource,
+			}) => map! {
+				"actor" => Value::from(actor.as_str()),
+				"action" => Value::from(action.as_str()),
+				"resource" => Value::from(resource.as_str()),
+			},
+			Error::NsNotFound {
+				value,
+			}
+			| Error::DbNotFound {
+				value,
+			}
+			| Error::TbNotFound {
+				value,
+			}
+			| Error::FdNotFound {
+				value,
+			}
+			| Error::IxNotFound {
+				value,
+			}
+			| Error::FcNotFound {
+				value,
+			}
+			| Error::PaNotFound {
+				value,
+			}
+			| Error::NsAlreadyExists {
+				value,
+			}
+			| Error::DbAlreadyExists {
+				value,
+			}
+			| Error::TbAlreadyExists {
+				value,
+			}
+			| Error::FdAlreadyExists {
+				value,
+			}
+			| Error::IxAlreadyExists {
+				value,
+			}
+			| Error::FcAlreadyExists {
+				value,
+			}
+			| Error::PaAlreadyExists {
+				value,
+			} => map! {
+				"name" => Value::from(value.as_str()),
+			},
+			Error::IdiomRecursionLimitExceeded {
+				limit,
+			} => map! {
+				"limit" => Value::from(*limit),
+			},
+			Error::RangeTooBig {
+				max,
+			} => map! {
+				"max" => Value::from(*max),
+			},
+			_ => return Object::default(),
+		}
+		.into()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::dbs::{QueryType, Response};
+	use crate::sql::Thing;
+	use std::time::Duration;
+
+	#[test]
+	fn unique_index_violation() {
+		let error = Error::IndexExists {
+			thing: Thing::from(("user", "one")),
+			index: "email".to_owned(),
+			value: "'one@example.com'".to_owned(),
+		};
+		assert_eq!(error.code(), ErrorCode::UniqueIndexViolation);
+		assert!(!error.code().is_retryable());
+		let details = error.details();
+		assert_eq!(details.get("index"), Some(&Value::from("email")));
+		assert_eq!(details.get("record"), Some(&Value::from(Thing::from(("user", "one")))));
+	}
+
+	#[test]
+	fn transaction_conflict_is_retryable() {
+		assert!(Error::TxRetryable.code().is_retryable());
+		assert!(!Error::TxFailure.code().is_retryable());
+		assert!(Error::TxRetryable.details().is_empty());
+	}
+
+	#[test]
+	fn serialized_response_has_code() {
+		let res = Response {
+			time: Duration::ZERO,
+			result: Err(Error::QueryTimedout),
+			query_type: QueryType::Other,
+		};
+		let Value::Object(res) = crate::sql::to_value(res).unwrap() else {
+			panic!("response is not an object");
+		};
+		assert_eq!(res.get("code"), Some(&Value::from("query_timeout")));
+		assert_eq!(res.get("details"), Some(&Value::from(Object::default())));
+	}
+}
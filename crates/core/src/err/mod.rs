@@ -22,6 +22,10 @@ use storekey::decode::Error as DecodeError;
 use storekey::encode::Error as EncodeError;
 use thiserror::Error;
 
+mod code;
+
+pub use self::code::{ErrorCode, ErrorData};
+
 /// An error originating from an embedded SurrealDB database.
 #[derive(Error, Debug)]
 #[non_exhaustive]
@@ -102,6 +106,10 @@ pub enum Error {
 	#[error("Transaction is too large")]
 	TxTooLarge,
 
+	/// The open transaction does not exist, or was already committed, cancelled or timed out
+	#[error("The transaction '{0}' does not exist, or has already finished")]
+	TxNotFound(String),
+
 	/// No namespace has been selected
 	#[error("Specify a namespace to use")]
 	NsEmpty,
@@ -369,6 +377,18 @@ pub enum Error {
 		value: String,
 	},
 
+	/// The requested sequence does not exist
+	#[error("The sequence '{value}' does not exist")]
+	SqNotFound {
+		value: String,
+	},
+
+	/// The sequence has reached the maximum value
+	#[error("The sequence '{value}' has been exhausted")]
+	SqExhausted {
+		value: String,
+	},
+
 	/// The requested config does not exist
 	#[error("The config for {value} does not exist")]
 	CgNotFound {
@@ -561,6 +581,21 @@ pub enum Error {
 		table: String,
 	},
 
+	/// An ASYNC table view can only be defined on tables which record a change feed
+	#[error("Unable to define the ASYNC view `{view}`, as the `{table}` table has no change feed{}", if *original { " which includes original values" } else { "" })]
+	ViewChangefeedRequired {
+		view: String,
+		table: String,
+		original: bool,
+	},
+
+	/// A table view can only follow record links through fields which specify the linked tables
+	#[error("Unable to define the view `{view}`, as the `{field}` field does not specify the tables which it links to")]
+	ViewLinkUntyped {
+		view: String,
+		field: String,
+	},
+
 	/// A database entry for the specified record already exists
 	#[error("Database record `{thing}` already exists")]
 	RecordExists {
@@ -956,6 +991,12 @@ pub enum Error {
 		value: String,
 	},
 
+	/// The requested sequence already exists
+	#[error("The sequence '{value}' already exists")]
+	SqAlreadyExists {
+		value: String,
+	},
+
 	/// The requested config already exists
 	#[error("The config for {value} already exists")]
 	CgAlreadyExists {
@@ -1199,6 +1240,38 @@ pub enum Error {
 	#[error("Encountered an issue while processed export config: found {0}, but expected {1}.")]
 	InvalidExportConfig(Value, String),
 
+	#[error("Encountered an issue while processed import config: found {0}, but expected {1}.")]
+	InvalidImportConfig(Value, String),
+
+	#[error(
+		"Encountered an issue while processing the retry policy: found {0}, but expected {1}."
+	)]
+	InvalidRetryPolicy(Value, String),
+
+	/// The requested import or export format is unknown, or not enabled in this build
+	#[error("The data format `{0}` is not supported")]
+	UnsupportedFormat(String),
+
+	/// There was a problem encoding or decoding import or export data
+	#[error("There was a problem processing the {0} data: {1}")]
+	InvalidFormatData(String, String),
+
+	/// The backup being restored is not valid
+	#[error("The backup is not valid: {0}")]
+	InvalidBackup(String),
+
+	/// An incremental backup can only include the changes to tables with a change feed
+	#[error("The `{table}` table has no change feed, so its changes can not be included in an incremental backup, a full backup is required")]
+	ChangefeedRequired {
+		table: String,
+	},
+
+	/// The change feed no longer holds all of the changes for an incremental backup
+	#[error("The change feed no longer holds all of the changes after versionstamp {since}, a full backup is required")]
+	FullBackupRequired {
+		since: u64,
+	},
+
 	/// Found an unexpected value in a range
 	#[error("Found {found} for bound but expected {expected}.")]
 	InvalidBound {
@@ -1371,6 +1444,23 @@ impl Serialize for Error {
 	}
 }
 impl Error {
+	/// Check if the operation which failed with this error can be retried
+	pub fn is_retryable(&self) -> bool {
+		matches!(self, Error::TxRetryable)
+	}
+
+	/// Convert an error which prevented a statement from committing
+	///
+	/// Transaction conflicts are kept as they are, so that they can be retried.
+	pub(crate) fn not_executed(self) -> Error {
+		match self {
+			Error::TxRetryable => Error::TxRetryable,
+			e => Error::QueryNotExecutedDetail {
+				message: e.to_string(),
+			},
+		}
+	}
+
 	/// Check if this error is related to schema checks
 	pub fn is_schema_related(&self) -> bool {
 		matches!(
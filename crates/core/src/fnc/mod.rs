@@ -26,6 +26,7 @@ pub mod rand;
 pub mod record;
 pub mod script;
 pub mod search;
+pub mod sequence;
 pub mod session;
 pub mod sleep;
 pub mod string;
@@ -34,6 +35,7 @@ pub mod r#type;
 pub mod util;
 pub mod value;
 pub mod vector;
+pub mod window;
 
 /// Attempts to run any function
 pub async fn run(
@@ -65,6 +67,7 @@ pub async fn run(
 		|| name.eq("value::patch")
 		|| name.starts_with("http")
 		|| name.starts_with("search")
+		|| name.starts_with("sequence")
 		|| name.starts_with("crypto::argon2")
 		|| name.starts_with("crypto::bcrypt")
 		|| name.starts_with("crypto::pbkdf2")
@@ -446,6 +449,14 @@ pub fn synchronous(
 		"vector::similarity::jaccard" => vector::similarity::jaccard,
 		"vector::similarity::pearson" => vector::similarity::pearson,
 		"vector::similarity::spearman" => vector::similarity::spearman,
+		//
+		"dense_rank" => window::dense_rank,
+		"first_value" => window::first_value,
+		"lag" => window::lag,
+		"last_value" => window::last_value,
+		"lead" => window::lead,
+		"rank" => window::rank,
+		"row_number" => window::row_number,
 	)
 }
 
@@ -514,7 +525,11 @@ pub async fn asynchronous(
 		"search::analyze" => search::analyze((stk, ctx, Some(opt))).await,
 		"search::score" => search::score((ctx, doc)).await,
 		"search::highlight" => search::highlight((ctx, doc)).await,
+		"search::linear" => search::linear((ctx, doc)).await,
 		"search::offsets" => search::offsets((ctx, doc)).await,
+		"search::rrf" => search::rrf((ctx, doc)).await,
+		//
+		"sequence::nextval" => sequence::nextval((ctx, Some(opt))).await,
 		//
 		"sleep" => sleep::sleep(ctx).await,
 		//
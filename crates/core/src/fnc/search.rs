@@ -5,7 +5,7 @@ use crate::err::Error;
 use crate::fnc::get_execution_context;
 use crate::idx::ft::analyzer::Analyzer;
 use crate::idx::ft::highlighter::HighlightParams;
-use crate::sql::Value;
+use crate::sql::{Number, Value};
 use reblessive::tree::Stk;
 
 pub async fn analyze(
@@ -52,3 +52,30 @@ pub async fn offsets(
 	}
 	Ok(Value::None)
 }
+
+pub async fn rrf(
+	(ctx, doc): (&Context, Option<&CursorDoc>),
+	(k,): (Option<Number>,),
+) -> Result<Value, Error> {
+	let k = k.map(|k| k.to_float()).unwrap_or(60.0);
+	if k < 0.0 {
+		return Err(Error::InvalidArguments {
+			name: String::from("search::rrf"),
+			message: String::from("The rank constant should not be negative."),
+		});
+	}
+	if let Some((exe, _, thg)) = get_execution_context(ctx, doc) {
+		return exe.rrf(ctx, thg, k).await;
+	}
+	Ok(Value::None)
+}
+
+pub async fn linear(
+	(ctx, doc): (&Context, Option<&CursorDoc>),
+	(text_weight, vector_weight): (Number, Number),
+) -> Result<Value, Error> {
+	if let Some((exe, _, thg)) = get_execution_context(ctx, doc) {
+		return exe.linear(ctx, thg, text_weight.to_float(), vector_weight.to_float()).await;
+	}
+	Ok(Value::None)
+}
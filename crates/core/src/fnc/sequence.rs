@@ -0,0 +1,17 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::err::Error;
+use crate::sql::value::Value;
+
+/// Returns the next value of the sequence with the provided name.
+pub async fn nextval(
+	(ctx, opt): (&Context, Option<&Options>),
+	(name,): (String,),
+) -> Result<Value, Error> {
+	if let Some(opt) = opt {
+		let sequences = ctx.get_sequences().ok_or_else(|| fail!("No Sequences"))?;
+		Ok(sequences.next_val(ctx, opt, &name).await?.into())
+	} else {
+		Ok(Value::None)
+	}
+}
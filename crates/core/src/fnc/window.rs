@@ -0,0 +1,40 @@
+//! Window functions are computed over the rows of a window, in a `SELECT` statement
+//! with an `OVER` clause. Calling any of them directly results in an error.
+use crate::err::Error;
+use crate::sql::window::{DENSE_RANK, FIRST_VALUE, LAG, LAST_VALUE, LEAD, RANK, ROW_NUMBER};
+use crate::sql::Value;
+
+fn outside_window(name: &str) -> Error {
+	Error::InvalidFunction {
+		name: name.to_owned(),
+		message: "This function can only be used with an OVER clause.".to_owned(),
+	}
+}
+
+pub fn dense_rank(_: Vec<Value>) -> Result<Value, Error> {
+	Err(outside_window(DENSE_RANK))
+}
+
+pub fn first_value(_: Vec<Value>) -> Result<Value, Error> {
+	Err(outside_window(FIRST_VALUE))
+}
+
+pub fn lag(_: Vec<Value>) -> Result<Value, Error> {
+	Err(outside_window(LAG))
+}
+
+pub fn last_value(_: Vec<Value>) -> Result<Value, Error> {
+	Err(outside_window(LAST_VALUE))
+}
+
+pub fn lead(_: Vec<Value>) -> Result<Value, Error> {
+	Err(outside_window(LEAD))
+}
+
+pub fn rank(_: Vec<Value>) -> Result<Value, Error> {
+	Err(outside_window(RANK))
+}
+
+pub fn row_number(_: Vec<Value>) -> Result<Value, Error> {
+	Err(outside_window(ROW_NUMBER))
+}
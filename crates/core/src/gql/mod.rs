@@ -4,8 +4,10 @@ pub mod cache;
 pub mod error;
 mod ext;
 pub mod schema;
+pub mod subscription;
 mod utils;
 
 pub use error::GqlError;
+pub use subscription::Subscriptions;
 
 pub use cache::*;
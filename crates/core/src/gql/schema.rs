@@ -1,15 +1,21 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 use std::fmt::Display;
+use std::ops::Bound;
 use std::sync::Arc;
 
-use crate::dbs::Session;
+use crate::dbs::{Action, Session};
 use crate::kvs::Datastore;
 use crate::sql::kind::Literal;
 use crate::sql::order::{OrderList, Ordering};
-use crate::sql::statements::define::config::graphql::TablesConfig;
-use crate::sql::statements::{DefineFieldStatement, SelectStatement};
-use crate::sql::{self, Ident, Order, Part, Table};
+use crate::sql::statements::define::config::graphql::{FunctionsConfig, TablesConfig};
+use crate::sql::statements::{
+	CreateStatement, DefineFieldStatement, DefineFunctionStatement, DefineTableStatement,
+	DeleteStatement, LiveStatement, RelateStatement, SelectStatement, UpdateStatement,
+	UpsertStatement,
+};
+use crate::sql::{self, Data, Ident, Order, Output, Part, Table, TableType};
 use crate::sql::{Cond, Fields};
+use crate::sql::{Dir, Edges, Graph, IdRange, Tables};
 use crate::sql::{Expression, Geometry};
 use crate::sql::{Idiom, Kind};
 use crate::sql::{Statement, Thing};
@@ -19,9 +25,13 @@ use async_graphql::dynamic::{FieldFuture, InterfaceField};
 use async_graphql::dynamic::{InputObject, Object};
 use async_graphql::dynamic::{InputValue, Schema};
 use async_graphql::dynamic::{Scalar, TypeRef};
+use async_graphql::dynamic::{Subscription, SubscriptionField, SubscriptionFieldFuture};
 use async_graphql::indexmap::IndexMap;
 use async_graphql::Name;
 use async_graphql::Value as GqlValue;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use futures::StreamExt;
 use rust_decimal::prelude::FromPrimitive;
 use rust_decimal::Decimal;
 use serde_json::Number;
@@ -32,12 +42,22 @@ use super::ext::IntoExt;
 use super::ext::ValidatorExt;
 use crate::gql::error::{internal_error, schema_error, type_error};
 use crate::gql::ext::{NamedContainer, TryAsExt};
-use crate::gql::utils::{GQLTx, GqlValueUtils};
+use crate::gql::subscription::Subscriptions;
+use crate::gql::utils::{process_write, GQLTx, GqlValueUtils};
 use crate::kvs::LockType;
 use crate::kvs::TransactionType;
 use crate::sql::Value as SqlValue;
 
 type ErasedRecord = (GQLTx, Thing);
+type ErasedNotification = (GQLTx, Action, Thing);
+
+/// A page of records of a Relay connection
+struct ErasedConnection {
+	gtx: GQLTx,
+	rids: Vec<Thing>,
+	has_next: bool,
+	has_previous: bool,
+}
 
 fn field_val_erase_owned(val: ErasedRecord) -> FieldValue<'static> {
 	FieldValue::owned_any(val)
@@ -95,7 +115,43 @@ pub async fn generate_schema(
 		}
 	};
 
+	// The relation tables which can link the records of each table, with the
+	// direction of the edges seen from the records of that table, and the
+	// tables of the records on the other side of the edges
+	let mut tb_edges: BTreeMap<String, Vec<(Dir, String, Vec<String>)>> = BTreeMap::new();
+	for tb in tbs.iter() {
+		let TableType::Relation(ref rel) = tb.kind else {
+			continue;
+		};
+		let names = |kind: &Option<Kind>| -> Vec<String> {
+			let names: Vec<String> = match kind {
+				Some(Kind::Record(ts)) if !ts.is_empty() => {
+					ts.iter().map(|t| t.0.clone()).collect()
+				}
+				_ => tbs.iter().map(|t| t.name.to_raw()).collect(),
+			};
+			names.into_iter().filter(|n| tbs.iter().any(|t| &t.name.0 == n)).collect()
+		};
+		let (from, to) = (names(&rel.from), names(&rel.to));
+		for name in from.iter() {
+			tb_edges.entry(name.clone()).or_default().push((
+				Dir::Out,
+				tb.name.to_raw(),
+				to.clone(),
+			));
+		}
+		for name in to.iter() {
+			tb_edges.entry(name.clone()).or_default().push((
+				Dir::In,
+				tb.name.to_raw(),
+				from.clone(),
+			));
+		}
+	}
+
 	let mut query = Object::new("Query");
+	let mut mutation = Object::new("Mutation");
+	let mut subscription = Subscription::new("Subscription");
 	let mut types: Vec<Type> = Vec::new();
 
 	trace!(ns, db, ?tbs, "generating schema");
@@ -331,6 +387,126 @@ pub async fn generate_schema(
 				.argument(id_input!()),
 			);
 
+		let sess5 = session.to_owned();
+		let kvs5 = datastore.to_owned();
+		let fds5 = fds.clone();
+		let conn_tb_name = tb.name.to_string();
+		let conn_ty_name = format!("{}Connection", tb.name);
+		let edge_ty_name = format!("{}Edge", tb.name);
+		query = query.field(
+			Field::new(
+				format!("_connection_{}", tb.name),
+				TypeRef::named_nn(&conn_ty_name),
+				move |ctx| {
+					let tb_name = conn_tb_name.clone();
+					let sess5 = sess5.clone();
+					let kvs5 = kvs5.clone();
+					let fds5 = fds5.clone();
+					FieldFuture::new(async move {
+						let gtx = GQLTx::new(&kvs5, &sess5).await?;
+
+						let args = ctx.args.as_index_map();
+						trace!("received request with args: {args:?}");
+
+						let first = match args.get("first").and_then(|v| v.as_i64()) {
+							Some(f) if f < 0 => {
+								return Err(resolver_error("`first` must not be negative").into())
+							}
+							f => f,
+						};
+
+						let after = match args.get("after").and_then(GqlValueUtils::as_string) {
+							Some(c) => Some(cursor_to_thing(&tb_name, &c)?),
+							None => None,
+						};
+
+						let cond = match args.get("filter") {
+							Some(GqlValue::Object(o)) => Some(cond_from_filter(o, &fds5)?),
+							_ => None,
+						};
+
+						// Records are ordered by their ids, so the page after
+						// a cursor is the range of ids following the cursor
+						let what = match after {
+							Some(ref t) => SqlValue::Thing(Thing::from((
+								tb_name,
+								IdRange {
+									beg: Bound::Excluded(t.id.clone()),
+									end: Bound::Unbounded,
+								},
+							))),
+							None => SqlValue::Table(tb_name.intox()),
+						};
+
+						// SELECT VALUE id FROM ... ORDER BY id ASC LIMIT first + 1
+						let ast = Statement::Select({
+							SelectStatement {
+								what: vec![what].into(),
+								expr: Fields(
+									vec![sql::Field::Single {
+										expr: SqlValue::Idiom(Idiom::from("id")),
+										alias: None,
+									}],
+									// this means the `value` keyword
+									true,
+								),
+								order: Some(Ordering::Order(OrderList(vec![Order {
+									value: Idiom::from("id"),
+									direction: true,
+									..Default::default()
+								}]))),
+								cond,
+								// fetch one more record to find if there is a next page
+								limit: first.map(|f| (f + 1).intox()),
+								..Default::default()
+							}
+						});
+
+						trace!("generated query ast: {ast:?}");
+
+						let mut rids = match gtx.process_stmt(ast).await? {
+							SqlValue::Array(a) => {
+								a.0.into_iter()
+									.map(|v| v.try_as_thing())
+									.collect::<Result<Vec<Thing>, SqlValue>>()
+									.map_err(|v| {
+										internal_error(format!("expected thing, found: {v:?}"))
+									})?
+							}
+							v => {
+								return Err(internal_error(format!(
+									"found top level value, in result which should be array: {v:?}"
+								))
+								.into())
+							}
+						};
+
+						let has_next = first.is_some_and(|f| rids.len() as i64 > f);
+						if let Some(f) = first {
+							rids.truncate(f as usize);
+						}
+
+						Ok(Some(FieldValue::owned_any(ErasedConnection {
+							gtx,
+							rids,
+							has_next,
+							has_previous: after.is_some(),
+						})))
+					})
+				},
+			)
+			.description(format!(
+				"Generated from table `{}`\nallows paginating through a table with cursors",
+				tb.name
+			))
+			.argument(InputValue::new("first", TypeRef::named(TypeRef::INT)))
+			.argument(InputValue::new("after", TypeRef::named(TypeRef::STRING)))
+			.argument(InputValue::new("filter", TypeRef::named(&table_filter_name))),
+		);
+
+		types.push(Type::Object(connection_type(&conn_ty_name, &edge_ty_name)));
+		types.push(Type::Object(edge_type(&edge_ty_name, &tb.name)));
+
 		let mut table_ty_obj = Object::new(tb.name.to_string())
 			.field(Field::new(
 				"id",
@@ -375,12 +551,157 @@ pub async fn generate_schema(
 				});
 		}
 
+		for (dir, rel, targets) in tb_edges.get(&tb.name.0).into_iter().flatten() {
+			table_ty_obj = table_ty_obj.field(edge_field(dir.clone(), rel));
+			for target in targets {
+				table_ty_obj = table_ty_obj.field(edge_target_field(dir.clone(), rel, target));
+			}
+		}
+
+		// Dynamic schemas require the fields of an object to have the exact type
+		// of the fields of its interfaces, so only relations with untyped ends can
+		// implement the `relation` interface
+		let untyped_end = |name: &str| {
+			fds.iter().any(|fd| {
+				fd.name.to_string() == name
+					&& matches!(fd.kind, Some(Kind::Record(ref ts)) if ts.is_empty())
+			})
+		};
+		if matches!(tb.kind, TableType::Relation(_)) && untyped_end("in") && untyped_end("out") {
+			table_ty_obj = table_ty_obj.implement("relation");
+		}
+
+		mutation = table_mutations(tb, &fds, datastore, session, mutation, &mut types);
+
+		let sess4 = session.to_owned();
+		let kvs4 = datastore.to_owned();
+		let fds4 = fds.clone();
+		let live_tb_name = tb.name.to_string();
+		let live_ty_name = format!("_live_{}", tb.name);
+		subscription = subscription.field(
+			SubscriptionField::new(
+				tb.name.to_string(),
+				TypeRef::named_nn(&live_ty_name),
+				move |ctx| {
+					let tb_name = live_tb_name.clone();
+					let sess4 = sess4.clone().with_rt(true);
+					let kvs4 = kvs4.clone();
+					let fds4 = fds4.clone();
+					SubscriptionFieldFuture::new(async move {
+						let subs = ctx.data::<Subscriptions>().map_err(|_| {
+							resolver_error("Subscriptions are not available on this connection")
+						})?;
+
+						let cond = match ctx.args.as_index_map().get("filter") {
+							Some(GqlValue::Object(o)) => Some(cond_from_filter(o, &fds4)?),
+							_ => None,
+						};
+
+						// LIVE SELECT * FROM ...
+						let mut live = LiveStatement::new(Fields::all());
+						live.what = SqlValue::Table(tb_name.intox());
+						live.cond = cond;
+
+						let stream = subs.register(live.id.0, kvs4.clone(), sess4.clone()).await;
+						process_write(&kvs4, &sess4, Statement::Live(live)).await?;
+
+						Ok(stream.then(move |n| {
+							let kvs4 = kvs4.clone();
+							let sess4 = sess4.clone();
+							async move {
+								let SqlValue::Thing(rid) = n.record else {
+									return Err(internal_error(format!(
+										"expected thing in notification, found: {:?}",
+										n.record
+									))
+									.into());
+								};
+								let gtx = GQLTx::new(&kvs4, &sess4).await?;
+								let erased: ErasedNotification = (gtx, n.action, rid);
+								Ok(FieldValue::owned_any(erased))
+							}
+						}))
+					})
+				},
+			)
+			.description(format!(
+				"Generated from table `{}`\nallows subscribing to the changes of records in a table",
+				tb.name
+			))
+			.argument(InputValue::new("filter", TypeRef::named(&table_filter_name))),
+		);
+
+		let live_ty_obj = Object::new(&live_ty_name)
+			.description(format!("Generated from `{}` a change of a record", tb.name))
+			.field(Field::new("action", TypeRef::named_nn("_live_action"), |ctx| {
+				FieldFuture::new(async move {
+					let (_, action, _) = ctx
+						.parent_value
+						.downcast_ref::<ErasedNotification>()
+						.ok_or_else(|| internal_error("failed to downcast"))?;
+					Ok(Some(FieldValue::value(GqlValue::Enum(Name::new(action.to_string())))))
+				})
+			}))
+			.field(Field::new("id", TypeRef::named_nn(TypeRef::ID), |ctx| {
+				FieldFuture::new(async move {
+					let (_, _, rid) = ctx
+						.parent_value
+						.downcast_ref::<ErasedNotification>()
+						.ok_or_else(|| internal_error("failed to downcast"))?;
+					Ok(Some(FieldValue::value(GqlValue::String(rid.to_string()))))
+				})
+			}))
+			.field(
+				Field::new("record", TypeRef::named(tb.name.to_string()), |ctx| {
+					FieldFuture::new(async move {
+						let (gtx, action, rid) = ctx
+							.parent_value
+							.downcast_ref::<ErasedNotification>()
+							.ok_or_else(|| internal_error("failed to downcast"))?;
+						match action {
+							Action::Delete => Ok(None),
+							_ => Ok(Some(field_val_erase_owned((gtx.clone(), rid.clone())))),
+						}
+					})
+				})
+				.description("The record after the change, which is null for deletions"),
+			);
+		types.push(Type::Object(live_ty_obj));
+
 		types.push(Type::Object(table_ty_obj));
 		types.push(table_order.into());
 		types.push(Type::Enum(table_orderable));
 		types.push(Type::InputObject(table_filter));
 	}
 
+	let fns = tx.all_db_functions(ns, db).await?;
+
+	let fns: Vec<DefineFunctionStatement> = match config.functions {
+		FunctionsConfig::None => vec![],
+		FunctionsConfig::Auto => fns.to_vec(),
+		FunctionsConfig::Include(inc) => {
+			fns.iter().filter(|f| inc.contains(&f.name)).cloned().collect()
+		}
+		FunctionsConfig::Exclude(exc) => {
+			fns.iter().filter(|f| !exc.contains(&f.name)).cloned().collect()
+		}
+	};
+
+	// Different function names can map to the same field name, as with `fn::a::b` and `fn::a_b`
+	let mut field_names: HashSet<String> = tbs.iter().map(|t| t.name.to_raw()).collect();
+	for fnd in fns.iter() {
+		let field_name = function_field_name(fnd);
+		if !field_names.insert(field_name.clone()) {
+			warn!(
+				"Skipping function `fn::{}` in the GraphQL schema, as the field `{field_name}` already exists",
+				fnd.name.to_raw()
+			);
+			continue;
+		}
+		trace!("Adding function: {}", fnd.name);
+		query = query.field(function_field(fnd, field_name, datastore, session, &mut types)?);
+	}
+
 	let sess3 = session.to_owned();
 	let kvs3 = datastore.to_owned();
 	query = query.field(
@@ -423,8 +744,17 @@ pub async fn generate_schema(
 	);
 
 	trace!("current Query object for schema: {:?}", query);
-
-	let mut schema = Schema::build("Query", None, None).register(query);
+	trace!("current Mutation object for schema: {:?}", mutation);
+
+	let mut schema = Schema::build("Query", Some("Mutation"), Some("Subscription"))
+		.register(query)
+		.register(mutation)
+		.register(subscription)
+		.register(
+			Enum::new("_live_action")
+				.description("The kind of change of a record")
+				.items(["CREATE", "UPDATE", "DELETE"]),
+		);
 	for ty in types {
 		trace!("adding type: {ty:?}");
 		schema = schema.register(ty);
@@ -462,7 +792,7 @@ pub async fn generate_schema(
 					tmp = tmp.specified_by_url(url);
 				}
 				#[cfg(debug_assertions)]
-				tmp.add_validator(|v| gql_to_sql_kind(v, $kind).is_ok());
+				tmp.add_validator(|v| gql_to_sql_kind_or_strand(v, $kind).is_ok());
 				tmp
 			});
 			$schema = $schema.register(new_type);
@@ -489,7 +819,6 @@ pub async fn generate_schema(
 		Interface::new("record").field(InterfaceField::new("id", TypeRef::named_nn(TypeRef::ID)));
 	schema = schema.register(id_interface);
 
-	// TODO: when used get: `Result::unwrap()` on an `Err` value: SchemaError("Field \"like.in\" is not sub-type of \"relation.in\"")
 	let relation_interface = Interface::new("relation")
 		.field(InterfaceField::new("id", TypeRef::named_nn(TypeRef::ID)))
 		.field(InterfaceField::new("in", TypeRef::named_nn("record")))
@@ -497,11 +826,669 @@ pub async fn generate_schema(
 		.implement("record");
 	schema = schema.register(relation_interface);
 
+	schema = schema.register(page_info_type());
+
 	schema
 		.finish()
 		.map_err(|e| schema_error(format!("there was an error generating schema: {e:?}")))
 }
 
+/// Encodes the id of a record as an opaque cursor
+fn thing_to_cursor(rid: &Thing) -> String {
+	URL_SAFE_NO_PAD.encode(rid.to_string())
+}
+
+/// Decodes a cursor to the id of a record in the given table
+fn cursor_to_thing(tb: &str, cursor: &str) -> Result<Thing, GqlError> {
+	URL_SAFE_NO_PAD
+		.decode(cursor)
+		.ok()
+		.and_then(|b| String::from_utf8(b).ok())
+		.and_then(|s| Thing::try_from(s.as_str()).ok())
+		.filter(|t| t.tb == tb)
+		.ok_or_else(|| resolver_error(format!("invalid cursor: {cursor}")))
+}
+
+fn connection_type(conn_ty_name: &str, edge_ty_name: &str) -> Object {
+	Object::new(conn_ty_name)
+		.description("Generated a page of records following the Relay connection specification")
+		.field(Field::new("edges", TypeRef::named_nn_list_nn(edge_ty_name), |ctx| {
+			FieldFuture::new(async move {
+				let conn = ctx
+					.parent_value
+					.downcast_ref::<ErasedConnection>()
+					.ok_or_else(|| internal_error("failed to downcast"))?;
+				let edges = conn
+					.rids
+					.iter()
+					.map(|rid| field_val_erase_owned((conn.gtx.clone(), rid.clone())));
+				Ok(Some(FieldValue::list(edges)))
+			})
+		}))
+		.field(Field::new("pageInfo", TypeRef::named_nn("PageInfo"), |ctx| {
+			FieldFuture::new(async move {
+				let conn = ctx
+					.parent_value
+					.downcast_ref::<ErasedConnection>()
+					.ok_or_else(|| internal_error("failed to downcast"))?;
+				let cursor = |rid: Option<&Thing>| match rid {
+					Some(rid) => GqlValue::String(thing_to_cursor(rid)),
+					None => GqlValue::Null,
+				};
+				let mut info = IndexMap::new();
+				info.insert(Name::new("hasNextPage"), GqlValue::Boolean(conn.has_next));
+				info.insert(Name::new("hasPreviousPage"), GqlValue::Boolean(conn.has_previous));
+				info.insert(Name::new("startCursor"), cursor(conn.rids.first()));
+				info.insert(Name::new("endCursor"), cursor(conn.rids.last()));
+				Ok(Some(FieldValue::value(GqlValue::Object(info))))
+			})
+		}))
+}
+
+fn edge_type(edge_ty_name: &str, tb_name: impl Display) -> Object {
+	Object::new(edge_ty_name)
+		.description(format!("Generated from `{tb_name}` a record in a page of records"))
+		.field(Field::new("cursor", TypeRef::named_nn(TypeRef::STRING), |ctx| {
+			FieldFuture::new(async move {
+				let (_, rid) = ctx
+					.parent_value
+					.downcast_ref::<ErasedRecord>()
+					.ok_or_else(|| internal_error("failed to downcast"))?;
+				Ok(Some(FieldValue::value(GqlValue::String(thing_to_cursor(rid)))))
+			})
+		}))
+		.field(Field::new("node", TypeRef::named_nn(tb_name.to_string()), |ctx| {
+			FieldFuture::new(async move {
+				let (gtx, rid) = ctx
+					.parent_value
+					.downcast_ref::<ErasedRecord>()
+					.ok_or_else(|| internal_error("failed to downcast"))?;
+				Ok(Some(field_val_erase_owned((gtx.clone(), rid.clone()))))
+			})
+		}))
+}
+
+fn page_info_type() -> Object {
+	let mut page_info = Object::new("PageInfo")
+		.description("Information about a page of records in a Relay connection");
+	for (name, ty) in [
+		("hasNextPage", TypeRef::named_nn(TypeRef::BOOLEAN)),
+		("hasPreviousPage", TypeRef::named_nn(TypeRef::BOOLEAN)),
+		("startCursor", TypeRef::named(TypeRef::STRING)),
+		("endCursor", TypeRef::named(TypeRef::STRING)),
+	] {
+		page_info = page_info.field(Field::new(name, ty, move |ctx| {
+			FieldFuture::new(async move {
+				let val = match ctx.parent_value.as_value() {
+					Some(GqlValue::Object(o)) => o.get(name).cloned(),
+					_ => None,
+				};
+				Ok(val.map(FieldValue::value))
+			})
+		}));
+	}
+	page_info
+}
+
+/// Generates a field listing the edges of a relation table linked to a record,
+/// named `_out_` for the edges from the record (`->rel`) or `_in_` for the edges
+/// to the record (`<-rel`), followed by the name of the relation table
+fn edge_field(dir: Dir, rel: &str) -> Field {
+	let name = match dir {
+		Dir::In => format!("_in_{rel}"),
+		_ => format!("_out_{rel}"),
+	};
+	let rel_name = rel.to_string();
+	Field::new(name, TypeRef::named_nn_list_nn(rel), move |ctx| {
+		let dir = dir.clone();
+		let rel_name = rel_name.clone();
+		FieldFuture::new(async move {
+			let (ref gtx, ref rid) = ctx
+				.parent_value
+				.downcast_ref::<ErasedRecord>()
+				.ok_or_else(|| internal_error("failed to downcast"))?;
+
+			let args = ctx.args.as_index_map();
+			let start = args.get("start").and_then(|v| v.as_i64()).map(|s| s.intox());
+			let limit = args.get("limit").and_then(|v| v.as_i64()).map(|l| l.intox());
+
+			// SELECT VALUE id FROM $rid->rel
+			let ast = Statement::Select({
+				SelectStatement {
+					what: vec![SqlValue::from(Edges {
+						dir,
+						from: rid.clone(),
+						what: Tables(vec![Table(rel_name)]),
+					})]
+					.into(),
+					expr: Fields(
+						vec![sql::Field::Single {
+							expr: SqlValue::Idiom(Idiom::from("id")),
+							alias: None,
+						}],
+						// this means the `value` keyword
+						true,
+					),
+					limit,
+					start,
+					..Default::default()
+				}
+			});
+
+			trace!("generated query ast: {ast:?}");
+
+			let out: Result<Vec<FieldValue>, SqlValue> = match gtx.process_stmt(ast).await? {
+				SqlValue::Array(a) => {
+					a.0.into_iter()
+						.map(|v| v.try_as_thing().map(|t| field_val_erase_owned((gtx.clone(), t))))
+						.collect()
+				}
+				v => Err(v),
+			};
+
+			match out {
+				Ok(l) => Ok(Some(FieldValue::list(l))),
+				Err(v) => Err(internal_error(format!("expected thing, found: {v:?}")).into()),
+			}
+		})
+	})
+	.description(format!("Generated from relation table `{rel}`\nlists the edges of a record"))
+	.argument(limit_input!())
+	.argument(start_input!())
+}
+
+/// Generates a field listing the records of a table which are linked to a record
+/// through the edges of a relation table, named `_out_` for the records reached
+/// from the record (`->rel->tb`) or `_in_` for the records linking to the record
+/// (`<-rel<-tb`), followed by the names of the relation table and the table
+fn edge_target_field(dir: Dir, rel: &str, tb: &str) -> Field {
+	let name = match dir {
+		Dir::In => format!("_in_{rel}_{tb}"),
+		_ => format!("_out_{rel}_{tb}"),
+	};
+	let rel_name = rel.to_string();
+	let tb_name = tb.to_string();
+	Field::new(name, TypeRef::named_nn_list_nn(tb), move |ctx| {
+		let dir = dir.clone();
+		let rel_name = rel_name.clone();
+		let tb_name = tb_name.clone();
+		FieldFuture::new(async move {
+			let (ref gtx, ref rid) = ctx
+				.parent_value
+				.downcast_ref::<ErasedRecord>()
+				.ok_or_else(|| internal_error("failed to downcast"))?;
+
+			let args = ctx.args.as_index_map();
+			let start = args.get("start").and_then(|v| v.as_i64()).map(|s| s.intox());
+			let limit = args.get("limit").and_then(|v| v.as_i64()).map(|l| l.intox());
+
+			let graph = |tb: String| {
+				Part::Graph(Graph {
+					dir: dir.clone(),
+					expr: Fields::all(),
+					what: Tables(vec![Table(tb)]),
+					..Default::default()
+				})
+			};
+
+			// SELECT VALUE id FROM $rid->rel->tb
+			let ast = Statement::Select({
+				SelectStatement {
+					what: vec![SqlValue::Idiom(Idiom(vec![
+						Part::Start(SqlValue::Thing(rid.clone())),
+						graph(rel_name),
+						graph(tb_name),
+					]))]
+					.into(),
+					expr: Fields(
+						vec![sql::Field::Single {
+							expr: SqlValue::Idiom(Idiom::from("id")),
+							alias: None,
+						}],
+						// this means the `value` keyword
+						true,
+					),
+					limit,
+					start,
+					..Default::default()
+				}
+			});
+
+			trace!("generated query ast: {ast:?}");
+
+			let out: Result<Vec<FieldValue>, SqlValue> = match gtx.process_stmt(ast).await? {
+				SqlValue::Array(a) => {
+					a.0.into_iter()
+						.map(|v| v.try_as_thing().map(|t| field_val_erase_owned((gtx.clone(), t))))
+						.collect()
+				}
+				v => Err(v),
+			};
+
+			match out {
+				Ok(l) => Ok(Some(FieldValue::list(l))),
+				Err(v) => Err(internal_error(format!("expected thing, found: {v:?}")).into()),
+			}
+		})
+	})
+	.description(format!(
+		"Generated from relation table `{rel}`\nlists the `{tb}` records linked to a record"
+	))
+	.argument(limit_input!())
+	.argument(start_input!())
+}
+
+/// The state shared by the generated mutations of a table
+#[derive(Clone)]
+struct TableMutation {
+	kvs: Arc<Datastore>,
+	sess: Session,
+	tb: String,
+	fds: Arc<[DefineFieldStatement]>,
+}
+
+impl TableMutation {
+	/// Parses a record id for this table, accepting either a full record id or only its id part
+	fn thing(&self, id: &str) -> Result<Thing, GqlError> {
+		match Thing::try_from(id) {
+			Ok(t) if t.tb == self.tb => Ok(t),
+			Ok(t) => Err(resolver_error(format!("record `{t}` is not in table `{}`", self.tb))),
+			Err(_) => Ok(Thing::from((self.tb.as_str(), id))),
+		}
+	}
+
+	/// Converts the `data` argument to the data clause of a statement,
+	/// where a null value in a merge removes the field
+	fn data(&self, val: Option<&GqlValue>, merge: bool) -> Result<Option<Data>, GqlError> {
+		let Some(GqlValue::Object(o)) = val else {
+			return Ok(None);
+		};
+		let mut out = BTreeMap::new();
+		for (k, v) in o.iter() {
+			let Some(fd) = self.fds.iter().find(|fd| fd.name.to_string() == k.as_str()) else {
+				return Err(resolver_error(format!("Field `{k}` not found")));
+			};
+			let v = match v {
+				GqlValue::Null if merge => SqlValue::None,
+				v => gql_to_sql_kind(v, fd.kind.clone().unwrap_or_default())?,
+			};
+			out.insert(k.to_string(), v);
+		}
+		let obj = SqlValue::Object(out.into());
+		Ok(Some(match merge {
+			true => Data::MergeExpression(obj),
+			false => Data::ContentExpression(obj),
+		}))
+	}
+
+	/// Reads the record returned by a statement in a new transaction
+	async fn record(&self, res: SqlValue) -> Result<Option<FieldValue<'static>>, GqlError> {
+		let rid = match res {
+			SqlValue::Array(a) => a.0.into_iter().next(),
+			v => Some(v),
+		};
+		match rid {
+			Some(SqlValue::Thing(t)) => {
+				let gtx = GQLTx::new(&self.kvs, &self.sess).await?;
+				Ok(Some(field_val_erase_owned((gtx, t))))
+			}
+			_ => Ok(None),
+		}
+	}
+}
+
+/// The output clause returning the ids of the written records
+fn output_id() -> Option<Output> {
+	Some(Output::Fields(Fields(
+		vec![sql::Field::Single {
+			expr: SqlValue::Idiom(Idiom::from("id")),
+			alias: None,
+		}],
+		true,
+	)))
+}
+
+/// Checks if a field can be written directly through a mutation input
+fn is_input_field(tb: &DefineTableStatement, fd: &DefineFieldStatement) -> bool {
+	let [Part::Field(name)] = fd.name.0.as_slice() else {
+		return false;
+	};
+	match name.as_str() {
+		"id" => false,
+		"in" | "out" => !matches!(tb.kind, TableType::Relation(_)),
+		_ => true,
+	}
+}
+
+fn table_mutations(
+	tb: &DefineTableStatement,
+	fds: &[DefineFieldStatement],
+	kvs: &Arc<Datastore>,
+	sess: &Session,
+	mut mutation: Object,
+	types: &mut Vec<Type>,
+) -> Object {
+	let tb_name = tb.name.to_string();
+	let fds: Vec<DefineFieldStatement> =
+		fds.iter().filter(|fd| is_input_field(tb, fd)).cloned().collect();
+
+	let create_input_name = format!("_create_{tb_name}");
+	let mut create_input = InputObject::new(&create_input_name).description(format!(
+		"Generated from `{tb_name}` the fields which can be set when creating a record"
+	));
+	let update_input_name = format!("_update_{tb_name}");
+	let mut update_input = InputObject::new(&update_input_name).description(format!(
+		"Generated from `{tb_name}` the fields which can be changed on an existing record"
+	));
+	let mut create_required = false;
+	let mut updatable = false;
+	for fd in fds.iter() {
+		let ty = kind_to_input_type(fd.kind.clone().unwrap_or_default());
+		let required =
+			matches!(ty, TypeRef::NonNull(_)) && fd.default.is_none() && fd.value.is_none();
+		create_required |= required;
+		create_input = create_input.field(InputValue::new(
+			fd.name.to_string(),
+			match required {
+				true => ty.clone(),
+				false => unwrap_type(ty.clone()),
+			},
+		));
+		if !fd.readonly {
+			updatable = true;
+			update_input =
+				update_input.field(InputValue::new(fd.name.to_string(), unwrap_type(ty)));
+		}
+	}
+	let create_data = match (fds.is_empty(), create_required) {
+		(true, _) => None,
+		(false, true) => Some(TypeRef::named_nn(&create_input_name)),
+		(false, false) => Some(TypeRef::named(&create_input_name)),
+	};
+	if create_data.is_some() {
+		types.push(Type::InputObject(create_input));
+	}
+	if updatable {
+		types.push(Type::InputObject(update_input));
+	}
+
+	let m = TableMutation {
+		kvs: kvs.clone(),
+		sess: sess.clone(),
+		tb: tb_name.clone(),
+		fds: fds.into(),
+	};
+
+	// Records of relation tables can only be created by relating two records
+	let is_relation = matches!(tb.kind, TableType::Relation(_));
+
+	let m1 = m.clone();
+	let mut create =
+		Field::new(format!("create_{tb_name}"), TypeRef::named(&tb_name), move |ctx| {
+			let m = m1.clone();
+			FieldFuture::new(async move {
+				let args = ctx.args.as_index_map();
+				let what = match args.get("id").and_then(GqlValueUtils::as_string) {
+					Some(id) => SqlValue::Thing(m.thing(&id)?),
+					None => SqlValue::Table(m.tb.as_str().into()),
+				};
+				let stmt = Statement::Create(CreateStatement {
+					only: true,
+					what: vec![what].into(),
+					data: m.data(args.get("data"), false)?,
+					output: output_id(),
+					..Default::default()
+				});
+				let res = process_write(&m.kvs, &m.sess, stmt).await?;
+				Ok(m.record(res).await?)
+			})
+		})
+		.description(format!("Generated from table `{tb_name}`\nallows creating a record"))
+		.argument(InputValue::new("id", TypeRef::named(TypeRef::ID)));
+	if let Some(ty) = create_data.clone() {
+		create = create.argument(InputValue::new("data", ty));
+	}
+	if !is_relation {
+		mutation = mutation.field(create);
+	}
+
+	if updatable {
+		let m2 = m.clone();
+		mutation = mutation.field(
+			Field::new(format!("update_{tb_name}"), TypeRef::named(&tb_name), move |ctx| {
+				let m = m2.clone();
+				FieldFuture::new(async move {
+					let args = ctx.args.as_index_map();
+					let id =
+						args.get("id").and_then(GqlValueUtils::as_string).ok_or_else(|| {
+							internal_error("Schema validation failed: No id found in update_")
+						})?;
+					let stmt = Statement::Update(UpdateStatement {
+						what: vec![SqlValue::Thing(m.thing(&id)?)].into(),
+						data: m.data(args.get("data"), true)?,
+						output: output_id(),
+						..Default::default()
+					});
+					let res = process_write(&m.kvs, &m.sess, stmt).await?;
+					Ok(m.record(res).await?)
+				})
+			})
+			.description(format!(
+				"Generated from table `{tb_name}`\nallows changing the fields of an existing record"
+			))
+			.argument(id_input!())
+			.argument(InputValue::new("data", TypeRef::named_nn(&update_input_name))),
+		);
+	}
+
+	let m3 = m.clone();
+	let mut upsert =
+		Field::new(format!("upsert_{tb_name}"), TypeRef::named(&tb_name), move |ctx| {
+			let m = m3.clone();
+			FieldFuture::new(async move {
+				let args = ctx.args.as_index_map();
+				let id = args.get("id").and_then(GqlValueUtils::as_string).ok_or_else(|| {
+					internal_error("Schema validation failed: No id found in upsert_")
+				})?;
+				let stmt = Statement::Upsert(UpsertStatement {
+					what: vec![SqlValue::Thing(m.thing(&id)?)].into(),
+					data: m.data(args.get("data"), false)?,
+					output: output_id(),
+					..Default::default()
+				});
+				let res = process_write(&m.kvs, &m.sess, stmt).await?;
+				Ok(m.record(res).await?)
+			})
+		})
+		.description(format!(
+			"Generated from table `{tb_name}`\nallows creating or replacing a record"
+		))
+		.argument(id_input!());
+	if let Some(ty) = create_data.clone() {
+		upsert = upsert.argument(InputValue::new("data", ty));
+	}
+	if !is_relation {
+		mutation = mutation.field(upsert);
+	}
+
+	let m4 = m.clone();
+	mutation = mutation.field(
+		Field::new(format!("delete_{tb_name}"), TypeRef::named(TypeRef::ID), move |ctx| {
+			let m = m4.clone();
+			FieldFuture::new(async move {
+				let args = ctx.args.as_index_map();
+				let id = args.get("id").and_then(GqlValueUtils::as_string).ok_or_else(|| {
+					internal_error("Schema validation failed: No id found in delete_")
+				})?;
+				let stmt = Statement::Delete(DeleteStatement {
+					what: vec![SqlValue::Thing(m.thing(&id)?)].into(),
+					output: Some(Output::Before),
+					..Default::default()
+				});
+				let res = process_write(&m.kvs, &m.sess, stmt).await?;
+				let deleted = match res {
+					SqlValue::Array(a) => a.0.into_iter().next(),
+					_ => None,
+				};
+				match deleted {
+					Some(SqlValue::Object(o)) => match o.get("id") {
+						Some(SqlValue::Thing(t)) => {
+							Ok(Some(FieldValue::value(GqlValue::String(t.to_string()))))
+						}
+						_ => Ok(None),
+					},
+					_ => Ok(None),
+				}
+			})
+		})
+		.description(format!(
+			"Generated from table `{tb_name}`\nallows deleting a record, returning its ID if it existed"
+		))
+		.argument(id_input!()),
+	);
+
+	if is_relation {
+		let m5 = m.clone();
+		let mut relate =
+			Field::new(format!("relate_{tb_name}"), TypeRef::named(&tb_name), move |ctx| {
+				let m = m5.clone();
+				FieldFuture::new(async move {
+					let args = ctx.args.as_index_map();
+					let mut ends = ["in", "out"].into_iter().map(|k| {
+						let id =
+							args.get(k).and_then(GqlValueUtils::as_string).ok_or_else(|| {
+								internal_error(format!(
+									"Schema validation failed: No {k} found in relate_"
+								))
+							})?;
+						Thing::try_from(id.as_str())
+							.map_err(|_| resolver_error(format!("invalid id: {id}")))
+					});
+					let (Some(from), Some(with)) = (ends.next(), ends.next()) else {
+						unreachable!("there are always two ends to a relation");
+					};
+					let stmt = Statement::Relate(RelateStatement {
+						kind: SqlValue::Table(m.tb.as_str().into()),
+						from: SqlValue::Thing(from?),
+						with: SqlValue::Thing(with?),
+						data: m.data(args.get("data"), false)?,
+						output: output_id(),
+						..Default::default()
+					});
+					let res = process_write(&m.kvs, &m.sess, stmt).await?;
+					Ok(m.record(res).await?)
+				})
+			})
+			.description(format!(
+				"Generated from table `{tb_name}`\nallows relating two records with a new edge"
+			))
+			.argument(InputValue::new("in", TypeRef::named_nn(TypeRef::ID)))
+			.argument(InputValue::new("out", TypeRef::named_nn(TypeRef::ID)));
+		if let Some(ty) = create_data {
+			relate = relate.argument(InputValue::new("data", ty));
+		}
+		mutation = mutation.field(relate);
+	}
+
+	mutation
+}
+
+/// The name of the query field of a custom function, `fn_` followed by the name
+/// of the function with its `::` separators replaced by `_`
+fn function_field_name(fnd: &DefineFunctionStatement) -> String {
+	format!("fn_{}", fnd.name.to_raw().replace("::", "_"))
+}
+
+/// Generates a query field calling a custom function
+fn function_field(
+	fnd: &DefineFunctionStatement,
+	field_name: String,
+	kvs: &Arc<Datastore>,
+	sess: &Session,
+	types: &mut Vec<Type>,
+) -> Result<Field, GqlError> {
+	let fn_name = fnd.name.to_raw();
+	let fn_args = fnd.args.clone();
+	let returns = fnd.returns.clone().unwrap_or_default();
+	let ty = match returns {
+		Kind::Any => TypeRef::named("any"),
+		ref k => kind_to_type(k.clone(), types)?,
+	};
+
+	let kvs = kvs.clone();
+	let sess = sess.clone();
+	let mut field = Field::new(field_name, ty, move |ctx| {
+		let fn_name = fn_name.clone();
+		let fn_args = fn_args.clone();
+		let returns = returns.clone();
+		let kvs = kvs.clone();
+		let sess = sess.clone();
+		FieldFuture::new(async move {
+			let gtx = GQLTx::new(&kvs, &sess).await?;
+
+			let args = ctx.args.as_index_map();
+			let mut vals = Vec::with_capacity(fn_args.len());
+			for (arg, kind) in fn_args.into_iter() {
+				let val = match args.get(arg.as_str()) {
+					Some(v) => gql_to_sql_kind_or_strand(v, kind)?,
+					None => SqlValue::None,
+				};
+				vals.push(val);
+			}
+
+			// RETURN fn::...(...)
+			let ast = Statement::Value(SqlValue::Function(Box::new(sql::Function::Custom(
+				fn_name, vals,
+			))));
+
+			trace!("generated query ast: {ast:?}");
+
+			let res = gtx.process_stmt(ast).await?;
+			Ok(sql_value_to_field_value(&gtx, res, &returns)?)
+		})
+	})
+	.description(if let Some(ref c) = fnd.comment {
+		format!("{c}")
+	} else {
+		format!("Generated from function `fn::{}`", fnd.name.to_raw())
+	});
+
+	for (arg, kind) in fnd.args.iter() {
+		field = field.argument(InputValue::new(arg.to_raw(), kind_to_input_type(kind.clone())));
+	}
+
+	Ok(field)
+}
+
+/// Converts a computed value to a field value, where the records of a record kind
+/// are resolved lazily like the records of a table
+fn sql_value_to_field_value(
+	gtx: &GQLTx,
+	val: SqlValue,
+	kind: &Kind,
+) -> Result<Option<FieldValue<'static>>, GqlError> {
+	match (val, kind) {
+		(SqlValue::None | SqlValue::Null, _) => Ok(None),
+		(v, Kind::Option(k)) => sql_value_to_field_value(gtx, v, k),
+		(SqlValue::Thing(t), Kind::Record(ts)) => {
+			let mut out = field_val_erase_owned((gtx.clone(), t.clone()));
+			if ts.len() != 1 {
+				out = out.with_type(t.tb);
+			}
+			Ok(Some(out))
+		}
+		(SqlValue::Array(a), Kind::Array(k, _) | Kind::Set(k, _)) => {
+			let out: Result<Vec<FieldValue>, GqlError> = a
+				.0
+				.into_iter()
+				.map(|v| sql_value_to_field_value(gtx, v, k).map(|v| v.unwrap_or(FieldValue::NULL)))
+				.collect();
+			Ok(Some(FieldValue::list(out?)))
+		}
+		(v, _) => Ok(Some(FieldValue::value(sql_value_to_gql_value(v)?))),
+	}
+}
+
 fn make_table_field_resolver(
 	fd_name: impl Into<String>,
 	kind: Option<Kind>,
@@ -689,6 +1676,38 @@ fn kind_to_type(kind: Kind, types: &mut Vec<Type>) -> Result<TypeRef, GqlError>
 	Ok(out)
 }
 
+/// Converts a field kind to the type of a mutation input, where records are
+/// given by their ID and kinds which can not be input types are accepted as `any`
+fn kind_to_input_type(kind: Kind) -> TypeRef {
+	let (optional, match_kind) = match kind {
+		Kind::Option(op_ty) => (true, *op_ty),
+		Kind::Any => (true, Kind::Any),
+		_ => (false, kind),
+	};
+	let out_ty = match match_kind {
+		Kind::Null => TypeRef::named("null"),
+		Kind::Bool => TypeRef::named(TypeRef::BOOLEAN),
+		Kind::Datetime => TypeRef::named("datetime"),
+		Kind::Decimal => TypeRef::named("decimal"),
+		Kind::Duration => TypeRef::named("duration"),
+		Kind::Float => TypeRef::named(TypeRef::FLOAT),
+		Kind::Int => TypeRef::named(TypeRef::INT),
+		Kind::Number => TypeRef::named("number"),
+		Kind::Object => TypeRef::named("object"),
+		Kind::String => TypeRef::named(TypeRef::STRING),
+		Kind::Uuid => TypeRef::named("uuid"),
+		Kind::Record(_) => TypeRef::named(TypeRef::ID),
+		Kind::Option(t) => return unwrap_type(kind_to_input_type(*t)),
+		Kind::Array(k, _) => TypeRef::List(Box::new(kind_to_input_type(*k))),
+		_ => TypeRef::named("any"),
+	};
+
+	match optional {
+		true => out_ty,
+		false => TypeRef::NonNull(Box::new(out_ty)),
+	}
+}
+
 macro_rules! filter_impl {
 	($filter:ident, $ty:ident, $name:expr) => {
 		$filter = $filter.field(InputValue::new($name, $ty.clone()));
@@ -866,7 +1885,7 @@ fn binop(
 	let (k, v) = obj.iter().next().unwrap();
 	let op = parse_op(k)?;
 
-	let rhs = gql_to_sql_kind(v, fd.kind.clone().unwrap_or_default())?;
+	let rhs = gql_to_sql_kind_or_strand(v, fd.kind.clone().unwrap_or_default())?;
 
 	let expr = sql::Expression::Binary {
 		l: lhs,
@@ -927,6 +1946,18 @@ macro_rules! any_try_kinds {
 	};
 }
 
+/// Converts an input value for a field or an argument, where strings given for the
+/// `any` kind which are not SurrealQL values are taken as they are
+fn gql_to_sql_kind_or_strand(val: &GqlValue, kind: Kind) -> Result<SqlValue, GqlError> {
+	match (val, kind) {
+		(GqlValue::String(s), Kind::Any) => {
+			Ok(gql_to_sql_kind(val, Kind::Any)
+				.unwrap_or_else(|_| SqlValue::Strand(s.as_str().into())))
+		}
+		(val, kind) => gql_to_sql_kind(val, kind),
+	}
+}
+
 fn gql_to_sql_kind(val: &GqlValue, kind: Kind) -> Result<SqlValue, GqlError> {
 	use crate::syn;
 	match kind {
@@ -1103,7 +2134,7 @@ fn gql_to_sql_kind(val: &GqlValue, kind: Kind) -> Result<SqlValue, GqlError> {
 		},
 		Kind::Record(ref ts) => match val {
 			GqlValue::String(s) => match syn::thing(s) {
-				Ok(t) => match ts.contains(&t.tb.as_str().into()) {
+				Ok(t) => match ts.is_empty() || ts.contains(&t.tb.as_str().into()) {
 					true => Ok(SqlValue::Thing(t)),
 					false => Err(type_error(kind, val)),
 				},
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use async_channel::{Receiver, Sender};
+use futures::Stream;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::dbs::{Notification, Session};
+use crate::kvs::Datastore;
+use crate::sql::statements::KillStatement;
+use crate::sql::Statement;
+
+use super::utils::process_write;
+
+/// Routes the live query notifications of the datastore to the GraphQL
+/// subscriptions which started the live queries.
+///
+/// The subscription resolvers expect this to be available in the data of the
+/// GraphQL request, and the owner of the datastore notification channel is
+/// responsible for passing notifications on with [`Subscriptions::notify`].
+#[derive(Clone, Debug, Default)]
+pub struct Subscriptions {
+	inner: Arc<RwLock<HashMap<Uuid, Sender<Notification>>>>,
+}
+
+impl Subscriptions {
+	/// Delivers a notification to the subscription of its live query,
+	/// returning false if no subscription started the live query
+	pub async fn notify(&self, notification: Notification) -> bool {
+		let sender = self.inner.read().await.get(&notification.id.0).cloned();
+		match sender {
+			Some(s) => s.send(notification).await.is_ok(),
+			None => false,
+		}
+	}
+
+	/// Registers a live query, returning the stream of its notifications.
+	/// The live query is killed once the stream is dropped.
+	pub(crate) async fn register(
+		&self,
+		id: Uuid,
+		kvs: Arc<Datastore>,
+		sess: Session,
+	) -> LiveStream {
+		let (snd, rcv) = async_channel::unbounded();
+		self.inner.write().await.insert(id, snd);
+		LiveStream {
+			id,
+			rcv: Box::pin(rcv),
+			subs: self.clone(),
+			kvs,
+			sess,
+		}
+	}
+}
+
+/// The notifications of a live query started by a GraphQL subscription
+pub(crate) struct LiveStream {
+	id: Uuid,
+	rcv: Pin<Box<Receiver<Notification>>>,
+	subs: Subscriptions,
+	kvs: Arc<Datastore>,
+	sess: Session,
+}
+
+impl Stream for LiveStream {
+	type Item = Notification;
+
+	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		self.rcv.as_mut().poll_next(cx)
+	}
+}
+
+impl Drop for LiveStream {
+	fn drop(&mut self) {
+		let id = self.id;
+		let subs = self.subs.clone();
+		let kvs = self.kvs.clone();
+		let sess = self.sess.clone();
+		tokio::spawn(async move {
+			subs.inner.write().await.remove(&id);
+			let stmt = Statement::Kill(KillStatement {
+				id: crate::sql::Uuid::from(id).into(),
+			});
+			if let Err(e) = process_write(&kvs, &sess, stmt).await {
+				warn!("failed to kill the live query of a GraphQL subscription: {e}");
+			}
+		});
+	}
+}
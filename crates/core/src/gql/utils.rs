@@ -51,7 +51,7 @@ use crate::kvs::LockType;
 use crate::kvs::TransactionType;
 use crate::sql::part::Part;
 use crate::sql::Statement;
-use crate::sql::{Thing, Value as SqlValue};
+use crate::sql::{Query, Thing, Value as SqlValue};
 
 use super::error::GqlError;
 
@@ -107,3 +107,25 @@ impl GQLTx {
 		Ok(res)
 	}
 }
+
+/// Runs a writing statement in its own transaction, so that the change is
+/// committed and checked against the permissions of the session.
+pub async fn process_write(
+	kvs: &Arc<Datastore>,
+	sess: &Session,
+	stmt: Statement,
+) -> Result<SqlValue, GqlError> {
+	kvs.check_anon(sess).map_err(|_| {
+		Error::IamError(IamError::NotAllowed {
+			actor: "anonymous".to_string(),
+			action: "process".to_string(),
+			resource: "graphql".to_string(),
+		})
+	})?;
+
+	let mut res = kvs.process(Query::from(stmt), sess, None).await?;
+	match res.pop() {
+		Some(r) => Ok(r.result?),
+		None => Ok(SqlValue::None),
+	}
+}
@@ -9,7 +9,7 @@ use super::Level;
 use cedar_policy::{Entity, EntityId, EntityTypeName, EntityUid, RestrictedExpression};
 use serde::{Deserialize, Serialize};
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Default, Debug, Eq, PartialEq, PartialOrd, Hash, Serialize, Deserialize)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -32,6 +32,8 @@ pub enum ResourceKind {
 	Access,
 	#[revision(start = 2)]
 	Config(ConfigKind),
+	#[revision(start = 3)]
+	Sequence,
 
 	// IAM
 	Actor,
@@ -65,6 +67,7 @@ impl std::fmt::Display for ResourceKind {
 			ResourceKind::Access => write!(f, "Access"),
 			ResourceKind::Actor => write!(f, "Actor"),
 			ResourceKind::Config(c) => write!(f, "Config::{c}"),
+			ResourceKind::Sequence => write!(f, "Sequence"),
 		}
 	}
 }
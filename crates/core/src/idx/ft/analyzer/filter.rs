@@ -1,5 +1,7 @@
 use crate::err::Error;
 use crate::idx::ft::analyzer::mapper::Mapper;
+use crate::idx::ft::analyzer::stopwords;
+use crate::idx::ft::analyzer::synonym::Synonyms;
 use crate::idx::ft::analyzer::tokenizer::Tokens;
 use crate::idx::ft::offsets::Position;
 use crate::idx::trees::store::IndexStores;
@@ -7,6 +9,7 @@ use crate::sql::filter::Filter as SqlFilter;
 use crate::sql::language::Language;
 use deunicode::deunicode;
 use rust_stemmers::{Algorithm, Stemmer};
+use std::collections::HashSet;
 
 #[derive(Clone, Copy)]
 pub(super) enum FilteringStage {
@@ -21,6 +24,10 @@ pub(super) enum Filter {
 	Lowercase,
 	Uppercase,
 	Mapper(Mapper),
+	Stopwords(HashSet<&'static str>),
+	Synonym(Synonyms),
+	Length(u16, u16),
+	Truncate(u16),
 }
 
 impl Filter {
@@ -54,6 +61,12 @@ impl Filter {
 			}
 			SqlFilter::Uppercase => Filter::Uppercase,
 			SqlFilter::Mapper(path) => Filter::Mapper(ixs.mappers().get(path)?),
+			SqlFilter::Stopwords(l) => {
+				Filter::Stopwords(stopwords::words(l).iter().copied().collect())
+			}
+			SqlFilter::Synonym(path) => Filter::Synonym(ixs.mappers().get_synonyms(path)?),
+			SqlFilter::Length(min, max) => Filter::Length(*min, *max),
+			SqlFilter::Truncate(max) => Filter::Truncate(*max),
 		};
 		Ok(f)
 	}
@@ -75,7 +88,7 @@ impl Filter {
 
 	fn is_stage(&self, stage: FilteringStage) -> bool {
 		if let FilteringStage::Querying = stage {
-			!matches!(self, Filter::EdgeNgram(_, _) | Filter::Ngram(_, _))
+			!matches!(self, Filter::EdgeNgram(_, _) | Filter::Ngram(_, _) | Filter::Synonym(_))
 		} else {
 			true
 		}
@@ -105,6 +118,10 @@ impl Filter {
 			Filter::Stemmer(s) => Self::stem(s, c),
 			Filter::Uppercase => Self::uppercase(c),
 			Filter::Mapper(m) => m.map(c),
+			Filter::Stopwords(w) => Self::stopwords(w, c),
+			Filter::Synonym(s) => s.expand(c),
+			Filter::Length(min, max) => Self::length(c, *min, *max),
+			Filter::Truncate(max) => Self::truncate(c, *max),
 		}
 	}
 
@@ -139,6 +156,33 @@ impl Filter {
 		Self::check_term(c, s.stem(&c.to_lowercase()).into())
 	}
 
+	#[inline]
+	fn stopwords(w: &HashSet<&'static str>, c: &str) -> FilterResult {
+		if w.contains(c.to_lowercase().as_str()) {
+			FilterResult::Ignore
+		} else {
+			FilterResult::Term(Term::Unchanged)
+		}
+	}
+
+	#[inline]
+	fn length(c: &str, min: u16, max: u16) -> FilterResult {
+		let l = c.chars().count();
+		if l < min as usize || l > max as usize {
+			FilterResult::Ignore
+		} else {
+			FilterResult::Term(Term::Unchanged)
+		}
+	}
+
+	#[inline]
+	fn truncate(c: &str, max: u16) -> FilterResult {
+		match c.char_indices().nth(max as usize) {
+			Some((i, _)) => Self::check_term(c, c[..i].to_string()),
+			None => FilterResult::Term(Term::Unchanged),
+		}
+	}
+
 	#[inline]
 	fn ngram(c: &str, min: u16, max: u16) -> FilterResult {
 		let min = min as usize;
@@ -824,6 +868,42 @@ mod tests {
 		.await;
 	}
 
+	#[tokio::test]
+	async fn test_stopwords() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS blank,class FILTERS stopwords(english);",
+			"The quick brown fox jumps over the lazy dog",
+			&["quick", "brown", "fox", "jumps", "lazy", "dog"],
+		)
+		.await;
+		test_analyzer(
+			"ANALYZER test TOKENIZERS blank,class FILTERS lowercase,stopwords(fr);",
+			"Le chat est sur la table",
+			&["chat", "table"],
+		)
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_length() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS blank,class FILTERS length(3,5);",
+			"a to the quick brown foxes",
+			&["the", "quick", "brown", "foxes"],
+		)
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_truncate() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS blank FILTERS truncate(4);",
+			"Ālea iacta est",
+			&["Ālea", "iact", "est"],
+		)
+		.await;
+	}
+
 	#[tokio::test]
 	async fn test_lowercase_tokens() {
 		test_analyzer_tokens(
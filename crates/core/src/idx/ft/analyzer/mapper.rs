@@ -54,36 +54,11 @@ impl Mapper {
 		Ok(())
 	}
 
-	#[cfg(not(target_arch = "wasm32"))]
 	async fn iterate_file(
 		terms: &mut Tree<VariableSizeKey, String>,
 		path: &Path,
 	) -> Result<(), Error> {
-		let file = File::open(path).await?;
-		let reader = BufReader::new(file);
-		let mut lines = reader.lines();
-		let mut line_number = 0;
-		while let Some(line) = lines.next_line().await? {
-			Self::add_line_tree(terms, line, line_number)?;
-			line_number += 1;
-		}
-		Ok(())
-	}
-
-	#[cfg(target_arch = "wasm32")]
-	async fn iterate_file(
-		terms: &mut Tree<VariableSizeKey, String>,
-		path: &Path,
-	) -> Result<(), Error> {
-		let file = File::open(path)?;
-		let reader = BufReader::new(file);
-		let mut line_number = 0;
-		for line_result in reader.lines() {
-			let line = line_result?;
-			Self::add_line_tree(terms, line, line_number)?;
-			line_number += 1;
-		}
-		Ok(())
+		read_lines(path, |line, line_number| Self::add_line_tree(terms, line, line_number)).await
 	}
 
 	pub(super) fn map(&self, token: &str) -> FilterResult {
@@ -95,3 +70,37 @@ impl Mapper {
 		FilterResult::Term(Term::Unchanged)
 	}
 }
+
+/// Reads a file line by line, passing each line along with its line number to the callback
+#[cfg(not(target_arch = "wasm32"))]
+pub(super) async fn read_lines<F>(path: &Path, mut f: F) -> Result<(), Error>
+where
+	F: FnMut(String, usize) -> Result<(), Error>,
+{
+	let file = File::open(path).await?;
+	let reader = BufReader::new(file);
+	let mut lines = reader.lines();
+	let mut line_number = 0;
+	while let Some(line) = lines.next_line().await? {
+		f(line, line_number)?;
+		line_number += 1;
+	}
+	Ok(())
+}
+
+/// Reads a file line by line, passing each line along with its line number to the callback
+#[cfg(target_arch = "wasm32")]
+pub(super) async fn read_lines<F>(path: &Path, mut f: F) -> Result<(), Error>
+where
+	F: FnMut(String, usize) -> Result<(), Error>,
+{
+	let file = File::open(path)?;
+	let reader = BufReader::new(file);
+	let mut line_number = 0;
+	for line_result in reader.lines() {
+		let line = line_result?;
+		f(line, line_number)?;
+		line_number += 1;
+	}
+	Ok(())
+}
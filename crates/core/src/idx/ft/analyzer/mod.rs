@@ -6,6 +6,9 @@ use crate::idx::ft::analyzer::tokenizer::{Tokenizer, Tokens};
 use crate::idx::ft::doclength::DocLength;
 use crate::idx::ft::offsets::{Offset, OffsetRecords};
 use crate::idx::ft::postings::TermFrequency;
+use crate::idx::ft::query::{
+	self, FtQuery, Operand, QueryClause, QueryExpr, QueryTerm, TermPositions,
+};
 use crate::idx::ft::terms::{TermId, TermLen, Terms};
 use crate::idx::trees::store::IndexStores;
 use crate::sql::statements::DefineAnalyzerStatement;
@@ -14,11 +17,13 @@ use crate::sql::{Function, Strand};
 use filter::Filter;
 use reblessive::tree::Stk;
 use std::collections::hash_map::Entry;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::sync::Arc;
 
 mod filter;
 pub(in crate::idx) mod mapper;
+mod stopwords;
+pub(in crate::idx) mod synonym;
 mod tokenizer;
 
 #[derive(Clone)]
@@ -29,27 +34,6 @@ pub(crate) struct Analyzer {
 
 pub(in crate::idx) type TermsList = Vec<Option<(TermId, TermLen)>>;
 
-pub(in crate::idx) struct TermsSet {
-	set: HashSet<TermId>,
-	has_unknown_terms: bool,
-}
-
-impl TermsSet {
-	/// If the query TermsSet contains terms that are unknown in the index
-	/// of if there is no terms in the set then
-	/// we are sure that it does not match any document
-	pub(in crate::idx) fn is_matchable(&self) -> bool {
-		!(self.has_unknown_terms || self.set.is_empty())
-	}
-
-	pub(in crate::idx) fn is_subset(&self, other: &TermsSet) -> bool {
-		if self.has_unknown_terms {
-			return false;
-		}
-		self.set.is_subset(&other.set)
-	}
-}
-
 impl Analyzer {
 	pub(crate) fn new(ixs: &IndexStores, az: Arc<DefineAnalyzerStatement>) -> Result<Self, Error> {
 		Ok(Self {
@@ -58,71 +42,165 @@ impl Analyzer {
 		})
 	}
 
-	pub(super) async fn extract_querying_terms(
+	/// Parses the query string and analyzes its operands.
+	/// The terms are resolved against the index, and prefixes are expanded.
+	/// If a fuzzy distance is given, unquoted terms are expanded to the terms within this distance.
+	pub(super) async fn extract_query(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		t: &Terms,
+		query_string: String,
+		fuzzy: Option<u8>,
+	) -> Result<FtQuery, Error> {
+		let mut clauses = Vec::new();
+		for pc in query::parse(&query_string) {
+			let single = pc.operands.len() == 1;
+			let mut exprs = Vec::with_capacity(pc.operands.len());
+			let mut distances = Vec::with_capacity(pc.distances.len());
+			for (i, op) in pc.operands.into_iter().enumerate() {
+				let (content, is_prefix, is_phrase) = match op {
+					Operand::Text(s) => (s, false, false),
+					Operand::Phrase(s) => (s, false, true),
+					Operand::Prefix(s) => (s, true, false),
+				};
+				let mut terms = self.extract_query_terms(stk, ctx, opt, t, content).await?;
+				// A prefix is only expanded if it is analyzed into a single term
+				let expr = if is_prefix && terms.len() == 1 {
+					let prefix = terms.remove(0).term;
+					let tx = ctx.tx();
+					let expansions = t.get_terms_with_prefix(&tx, &prefix).await?;
+					drop(tx);
+					QueryExpr::Prefix(prefix, Self::expansion_terms(expansions))
+				} else if single && !is_phrase && !pc.must_not {
+					// A bare word analyzed into several terms is matched as a bag of terms
+					for term in terms {
+						clauses.push(QueryClause {
+							must_not: false,
+							expr: Self::term_expr(ctx, t, term, fuzzy).await?,
+						});
+					}
+					continue;
+				} else {
+					// Within a phrase or a proximity expression, punctuation is not positional
+					terms.retain(|qt| !Self::is_punctuation(&qt.term));
+					match terms.len() {
+						0 => continue,
+						1 if is_phrase => QueryExpr::Term(terms.remove(0)),
+						1 => Self::term_expr(ctx, t, terms.remove(0), fuzzy).await?,
+						_ => QueryExpr::Phrase(terms),
+					}
+				};
+				if !exprs.is_empty() {
+					distances.push(pc.distances[i - 1]);
+				}
+				exprs.push(expr);
+			}
+			// Proximity chains are evaluated from left to right
+			let mut exprs = exprs.into_iter();
+			if let Some(first) = exprs.next() {
+				let expr = exprs
+					.zip(distances)
+					.fold(first, |l, (r, d)| QueryExpr::Near(Box::new(l), Box::new(r), d));
+				clauses.push(QueryClause {
+					must_not: pc.must_not,
+					expr,
+				});
+			}
+		}
+		Ok(FtQuery {
+			clauses,
+		})
+	}
+
+	async fn term_expr(
+		ctx: &Context,
+		t: &Terms,
+		term: QueryTerm,
+		fuzzy: Option<u8>,
+	) -> Result<QueryExpr, Error> {
+		match fuzzy {
+			Some(distance) if distance > 0 => {
+				let tx = ctx.tx();
+				let expansions = t.get_fuzzy_terms(&tx, &term.term, distance).await?;
+				drop(tx);
+				Ok(QueryExpr::Fuzzy(term.term, distance, Self::expansion_terms(expansions)))
+			}
+			_ => Ok(QueryExpr::Term(term)),
+		}
+	}
+
+	fn expansion_terms(expansions: Vec<(String, TermId)>) -> Vec<QueryTerm> {
+		expansions
+			.into_iter()
+			.map(|(term, id)| {
+				let len = term.chars().count() as TermLen;
+				QueryTerm {
+					term,
+					id: Some((id, len)),
+				}
+			})
+			.collect()
+	}
+
+	async fn extract_query_terms(
 		&self,
 		stk: &mut Stk,
 		ctx: &Context,
 		opt: &Options,
 		t: &Terms,
 		content: String,
-	) -> Result<(TermsList, TermsSet), Error> {
+	) -> Result<Vec<QueryTerm>, Error> {
 		let tokens = self.generate_tokens(stk, ctx, opt, FilteringStage::Querying, content).await?;
-		// We extract the term ids
-		let mut list = Vec::with_capacity(tokens.list().len());
-		let mut unique_tokens = HashSet::new();
-		let mut set = HashSet::new();
+		let mut terms = Vec::with_capacity(tokens.list().len());
 		let tx = ctx.tx();
-		let mut has_unknown_terms = false;
 		for token in tokens.list() {
-			// Tokens can contains duplicated, not need to evaluate them again
-			if unique_tokens.insert(token) {
-				// Is the term known in the index?
-				let opt_term_id = t.get_term_id(&tx, tokens.get_token_string(token)?).await?;
-				list.push(opt_term_id.map(|tid| (tid, token.get_char_len())));
-				if let Some(term_id) = opt_term_id {
-					set.insert(term_id);
-				} else {
-					has_unknown_terms = true;
-				}
-			}
+			let term = tokens.get_token_string(token)?;
+			// Is the term known in the index?
+			let id = t.get_term_id(&tx, term).await?.map(|id| (id, token.get_char_len()));
+			terms.push(QueryTerm {
+				term: term.to_string(),
+				id,
+			});
 		}
 		drop(tx);
-		Ok((
-			list,
-			TermsSet {
-				set,
-				has_unknown_terms,
-			},
-		))
+		Ok(terms)
 	}
 
-	pub(in crate::idx) async fn extract_indexing_terms(
+	/// Extracts the position of every term of the given value.
+	/// Tokens generated from the same input token (ie. ngrams) share the same position.
+	pub(in crate::idx) async fn extract_positions(
 		&self,
 		stk: &mut Stk,
 		ctx: &Context,
 		opt: &Options,
-		t: &Terms,
 		content: Value,
-	) -> Result<TermsSet, Error> {
+	) -> Result<TermPositions, Error> {
 		let mut tv = Vec::new();
 		self.analyze_value(stk, ctx, opt, content, FilteringStage::Indexing, &mut tv).await?;
-		let mut set = HashSet::new();
-		let mut has_unknown_terms = false;
-		let tx = ctx.tx();
-		for tokens in tv {
+		let mut positions = TermPositions::new();
+		for (i, tokens) in tv.iter().enumerate() {
+			let mut ordinal = 0;
+			let mut last_start = None;
 			for token in tokens.list() {
-				if let Some(term_id) = t.get_term_id(&tx, tokens.get_token_string(token)?).await? {
-					set.insert(term_id);
-				} else {
-					has_unknown_terms = true;
+				let term = tokens.get_token_string(token)?;
+				if Self::is_punctuation(term) {
+					continue;
 				}
+				let start = token.get_char_start();
+				if last_start.is_some_and(|s| s != start) {
+					ordinal += 1;
+				}
+				last_start = Some(start);
+				positions.entry(term.to_string()).or_default().push((i as u32, ordinal));
 			}
 		}
-		drop(tx);
-		Ok(TermsSet {
-			set,
-			has_unknown_terms,
-		})
+		Ok(positions)
+	}
+
+	fn is_punctuation(term: &str) -> bool {
+		!term.chars().any(char::is_alphanumeric)
 	}
 
 	/// This method is used for indexing.
@@ -0,0 +1,717 @@
+//! Lists of common words for each language, based on the Snowball stop word lists
+use crate::sql::language::Language;
+
+pub(super) fn words(l: &Language) -> &'static [&'static str] {
+	match l {
+		Language::Arabic => ARABIC,
+		Language::Danish => DANISH,
+		Language::Dutch => DUTCH,
+		Language::English => ENGLISH,
+		Language::French => FRENCH,
+		Language::German => GERMAN,
+		Language::Greek => GREEK,
+		Language::Hungarian => HUNGARIAN,
+		Language::Italian => ITALIAN,
+		Language::Norwegian => NORWEGIAN,
+		Language::Portuguese => PORTUGUESE,
+		Language::Romanian => ROMANIAN,
+		Language::Russian => RUSSIAN,
+		Language::Spanish => SPANISH,
+		Language::Swedish => SWEDISH,
+		Language::Tamil => TAMIL,
+		Language::Turkish => TURKISH,
+	}
+}
+
+const ARABIC: &[&str] = &[
+	"في",
+	"من",
+	"على",
+	"إلى",
+	"الى",
+	"عن",
+	"مع",
+	"هذا",
+	"هذه",
+	"ذلك",
+	"تلك",
+	"التي",
+	"الذي",
+	"الذين",
+	"اللذين",
+	"اللتين",
+	"هو",
+	"هي",
+	"هم",
+	"هن",
+	"أنا",
+	"نحن",
+	"أنت",
+	"أنتم",
+	"كان",
+	"كانت",
+	"يكون",
+	"تكون",
+	"ليس",
+	"ليست",
+	"لا",
+	"لم",
+	"لن",
+	"ما",
+	"ماذا",
+	"متى",
+	"أين",
+	"كيف",
+	"لماذا",
+	"هل",
+	"قد",
+	"لقد",
+	"و",
+	"أو",
+	"ثم",
+	"أن",
+	"إن",
+	"لكن",
+	"بل",
+	"حتى",
+	"إذا",
+	"كل",
+	"بعض",
+	"غير",
+	"بين",
+	"عند",
+	"بعد",
+	"قبل",
+	"منذ",
+	"فوق",
+	"تحت",
+	"أي",
+	"كما",
+	"أيضا",
+	"لدى",
+];
+
+const DANISH: &[&str] = &[
+	"og", "i", "jeg", "det", "at", "en", "den", "til", "er", "som", "på", "de", "med", "han", "af",
+	"for", "ikke", "der", "var", "mig", "sig", "men", "et", "har", "om", "vi", "min", "havde",
+	"ham", "hun", "nu", "over", "da", "fra", "du", "ud", "sin", "dem", "os", "op", "man", "hans",
+	"hvor", "eller", "hvad", "skal", "selv", "her", "alle", "vil", "blev", "kunne", "ind", "når",
+	"være", "dog", "noget", "ville", "jo", "deres", "efter", "ned", "skulle", "denne", "end",
+	"dette", "mit", "også", "under", "have", "dig", "anden", "hende", "mine", "alt", "meget",
+	"sit", "sine", "vor", "mod", "disse", "hvis", "din", "nogle", "hos", "blive", "mange", "ad",
+	"bliver", "hendes", "været", "thi", "jer", "sådan",
+];
+
+const DUTCH: &[&str] = &[
+	"de", "en", "van", "ik", "te", "dat", "die", "in", "een", "hij", "het", "niet", "zijn", "is",
+	"was", "op", "aan", "met", "als", "voor", "had", "er", "maar", "om", "hem", "dan", "zou", "of",
+	"wat", "mijn", "men", "dit", "zo", "door", "over", "ze", "zich", "bij", "ook", "tot", "je",
+	"mij", "uit", "der", "daar", "haar", "naar", "heb", "hoe", "heeft", "hebben", "deze", "u",
+	"want", "nog", "zal", "me", "zij", "nu", "ge", "geen", "omdat", "iets", "worden", "toch", "al",
+	"waren", "veel", "meer", "doen", "toen", "moet", "ben", "zonder", "kan", "hun", "dus", "alles",
+	"onder", "ja", "eens", "hier", "wie", "werd", "altijd", "doch", "wordt", "wezen", "kunnen",
+	"ons", "zelf", "tegen", "na", "reeds", "wil", "kon", "niets", "uw", "iemand", "geweest",
+	"andere",
+];
+
+const ENGLISH: &[&str] = &[
+	"i",
+	"me",
+	"my",
+	"myself",
+	"we",
+	"our",
+	"ours",
+	"ourselves",
+	"you",
+	"your",
+	"yours",
+	"yourself",
+	"yourselves",
+	"he",
+	"him",
+	"his",
+	"himself",
+	"she",
+	"her",
+	"hers",
+	"herself",
+	"it",
+	"its",
+	"itself",
+	"they",
+	"them",
+	"their",
+	"theirs",
+	"themselves",
+	"what",
+	"which",
+	"who",
+	"whom",
+	"this",
+	"that",
+	"these",
+	"those",
+	"am",
+	"is",
+	"are",
+	"was",
+	"were",
+	"be",
+	"been",
+	"being",
+	"have",
+	"has",
+	"had",
+	"having",
+	"do",
+	"does",
+	"did",
+	"doing",
+	"would",
+	"should",
+	"could",
+	"ought",
+	"a",
+	"an",
+	"the",
+	"and",
+	"but",
+	"if",
+	"or",
+	"because",
+	"as",
+	"until",
+	"while",
+	"of",
+	"at",
+	"by",
+	"for",
+	"with",
+	"about",
+	"against",
+	"between",
+	"into",
+	"through",
+	"during",
+	"before",
+	"after",
+	"above",
+	"below",
+	"to",
+	"from",
+	"up",
+	"down",
+	"in",
+	"out",
+	"on",
+	"off",
+	"over",
+	"under",
+	"again",
+	"further",
+	"then",
+	"once",
+	"here",
+	"there",
+	"when",
+	"where",
+	"why",
+	"how",
+	"all",
+	"any",
+	"both",
+	"each",
+	"few",
+	"more",
+	"most",
+	"other",
+	"some",
+	"such",
+	"no",
+	"nor",
+	"not",
+	"only",
+	"own",
+	"same",
+	"so",
+	"than",
+	"too",
+	"very",
+];
+
+const FRENCH: &[&str] = &[
+	"au", "aux", "avec", "ce", "ces", "dans", "de", "des", "du", "elle", "en", "et", "eux", "il",
+	"je", "la", "le", "les", "leur", "lui", "ma", "mais", "me", "même", "mes", "moi", "mon", "ne",
+	"nos", "notre", "nous", "on", "ou", "par", "pas", "pour", "qu", "que", "qui", "sa", "se",
+	"ses", "son", "sur", "ta", "te", "tes", "toi", "ton", "tu", "un", "une", "vos", "votre",
+	"vous", "c", "d", "j", "l", "à", "m", "n", "s", "t", "y", "été", "étée", "étées", "étés",
+	"étant", "suis", "es", "est", "sommes", "êtes", "sont", "serai", "sera", "serons", "serez",
+	"seront", "serait", "étais", "était", "étions", "étiez", "étaient", "fut", "ai", "as", "avons",
+	"avez", "ont", "aurai", "aura", "aurons", "aurez", "auront", "aurait", "avais", "avait",
+	"avions", "aviez", "avaient", "eu",
+];
+
+const GERMAN: &[&str] = &[
+	"aber", "alle", "allem", "allen", "aller", "alles", "als", "also", "am", "an", "ander",
+	"andere", "anderen", "anderer", "anderes", "auch", "auf", "aus", "bei", "bin", "bis", "bist",
+	"da", "damit", "dann", "der", "den", "des", "dem", "die", "das", "dass", "daß", "derselbe",
+	"dich", "dir", "du", "dies", "diese", "diesem", "diesen", "dieser", "dieses", "doch", "dort",
+	"durch", "ein", "eine", "einem", "einen", "einer", "eines", "er", "es", "euer", "eure", "für",
+	"hatte", "hatten", "hattest", "hattet", "hier", "hin", "hinter", "ich", "mich", "mir", "ihr",
+	"ihre", "ihrem", "ihren", "ihrer", "ihres", "im", "in", "indem", "ins", "ist", "jede", "jedem",
+	"jeden", "jeder", "jedes", "jene", "jetzt", "kann", "kein", "keine", "können", "machen", "man",
+	"manche", "mein", "meine", "mit", "muss", "musste", "nach", "nicht", "nichts", "noch", "nun",
+	"nur", "ob", "oder", "ohne", "sehr", "sein", "seine", "sich", "sie", "sind", "so", "solche",
+	"soll", "sollte", "sondern", "um", "und", "uns", "unser", "unter", "viel", "vom", "von", "vor",
+	"während", "war", "waren", "warst", "was", "weil", "weiter", "welche", "wenn", "werde",
+	"werden", "wie", "wieder", "will", "wir", "wird", "wo", "wollen", "zu", "zum", "zur", "zwar",
+	"zwischen",
+];
+
+const GREEK: &[&str] = &[
+	"ο",
+	"η",
+	"το",
+	"οι",
+	"τα",
+	"του",
+	"της",
+	"των",
+	"τον",
+	"την",
+	"τους",
+	"τις",
+	"και",
+	"κι",
+	"να",
+	"θα",
+	"με",
+	"σε",
+	"για",
+	"από",
+	"απο",
+	"προς",
+	"στο",
+	"στη",
+	"στην",
+	"στον",
+	"στα",
+	"στις",
+	"στους",
+	"που",
+	"πως",
+	"ότι",
+	"οτι",
+	"δεν",
+	"μη",
+	"μην",
+	"αλλά",
+	"αλλα",
+	"ή",
+	"αν",
+	"είναι",
+	"ειναι",
+	"ήταν",
+	"ηταν",
+	"ένα",
+	"ενα",
+	"μια",
+	"μία",
+	"ένας",
+	"ενας",
+	"αυτό",
+	"αυτο",
+	"αυτή",
+	"αυτη",
+	"αυτός",
+	"αυτος",
+	"αυτά",
+	"αυτα",
+	"εγώ",
+	"εγω",
+	"εσύ",
+	"εσυ",
+	"μου",
+	"σου",
+	"μας",
+	"σας",
+	"ως",
+	"όπως",
+	"οπως",
+	"κατά",
+	"κατα",
+	"μετά",
+	"μετα",
+];
+
+const HUNGARIAN: &[&str] = &[
+	"a", "az", "egy", "be", "ki", "le", "fel", "meg", "el", "át", "rá", "ide", "oda", "szét",
+	"össze", "vissza", "de", "hát", "és", "vagy", "hogy", "van", "lesz", "volt", "csak", "nem",
+	"igen", "mint", "én", "te", "ő", "mi", "ti", "ők", "ez", "azt", "ezt", "is", "már", "még",
+	"sem", "mert", "ha", "itt", "ott", "ami", "amely", "aki", "akik", "amit", "kell", "lehet",
+	"nagyon", "pedig", "után", "előtt", "alatt", "között", "szerint", "által", "nincs", "minden",
+	"mely", "mit", "ezért", "azért", "így", "úgy", "vele", "neki", "nekem", "ön",
+];
+
+const ITALIAN: &[&str] = &[
+	"ad", "al", "allo", "ai", "agli", "all", "alla", "alle", "con", "col", "coi", "da", "dal",
+	"dallo", "dai", "dagli", "dall", "dalla", "dalle", "di", "del", "dello", "dei", "degli",
+	"dell", "della", "delle", "in", "nel", "nello", "nei", "negli", "nell", "nella", "nelle", "su",
+	"sul", "sullo", "sui", "sugli", "sull", "sulla", "sulle", "per", "tra", "contro", "io", "tu",
+	"lui", "lei", "noi", "voi", "loro", "mio", "mia", "miei", "mie", "tuo", "tua", "tuoi", "tue",
+	"suo", "sua", "suoi", "sue", "nostro", "nostra", "vostro", "vostra", "mi", "ti", "ci", "vi",
+	"lo", "la", "li", "le", "gli", "ne", "il", "un", "uno", "una", "ma", "ed", "se", "perché",
+	"anche", "come", "dov", "dove", "che", "chi", "cui", "non", "più", "quale", "quanto", "quello",
+	"questo", "sì", "e", "è", "o", "sono", "era", "essere", "stato", "ho", "ha", "hanno",
+	"abbiamo", "avere",
+];
+
+const NORWEGIAN: &[&str] = &[
+	"og", "i", "jeg", "det", "at", "en", "et", "den", "til", "er", "som", "på", "de", "med", "han",
+	"av", "ikke", "der", "så", "var", "meg", "seg", "men", "ett", "har", "om", "vi", "min", "mitt",
+	"ha", "hadde", "hun", "nå", "over", "da", "ved", "fra", "du", "ut", "sin", "dem", "oss", "opp",
+	"man", "kan", "hans", "hvor", "eller", "hva", "skal", "selv", "sjøl", "her", "alle", "vil",
+	"bli", "ble", "blitt", "kunne", "inn", "når", "være", "kom", "noen", "noe", "ville", "dere",
+	"deres", "kun", "ja", "etter", "ned", "skulle", "denne", "for", "deg", "si", "sine", "sitt",
+	"mot", "å", "meget", "hvorfor", "dette", "disse", "uten", "hvordan", "ingen", "din", "ditt",
+	"blir", "samme", "hvilken", "hvilke", "sånn", "inni", "mellom", "vår", "hver", "hvem", "vors",
+	"hvis", "både", "bare", "enn", "fordi", "før", "mange", "også", "slik", "vært",
+];
+
+const PORTUGUESE: &[&str] = &[
+	"de", "a", "o", "que", "e", "do", "da", "em", "um", "para", "com", "não", "uma", "os", "no",
+	"se", "na", "por", "mais", "as", "dos", "como", "mas", "ao", "ele", "das", "à", "seu", "sua",
+	"ou", "quando", "muito", "nos", "já", "eu", "também", "só", "pelo", "pela", "até", "isso",
+	"ela", "entre", "depois", "sem", "mesmo", "aos", "seus", "quem", "nas", "me", "esse", "eles",
+	"você", "essa", "num", "nem", "suas", "meu", "às", "minha", "numa", "pelos", "elas", "qual",
+	"nós", "lhe", "deles", "essas", "esses", "pelas", "este", "dele", "tu", "te", "vocês", "vos",
+	"lhes", "meus", "minhas", "teu", "tua", "nosso", "nossa", "isto", "aquele", "aquela", "é",
+	"são", "foi", "era", "ser", "ter", "tem", "há",
+];
+
+const ROMANIAN: &[&str] = &[
+	"a", "ai", "al", "ale", "am", "ar", "are", "aș", "au", "ca", "că", "care", "ce", "cel", "cea",
+	"cei", "cele", "cu", "cum", "da", "dacă", "dar", "de", "deci", "din", "după", "e", "ea", "ei",
+	"el", "ele", "este", "eu", "fi", "fost", "i", "îi", "îl", "în", "între", "își", "la", "le",
+	"lor", "lui", "mai", "mă", "ne", "nici", "noi", "nu", "o", "or", "pe", "pentru", "prin", "sa",
+	"să", "se", "și", "sau", "sub", "sunt", "te", "tu", "un", "una", "unei", "unui", "va", "voi",
+	"vă", "acest", "această", "acel", "acea", "aici", "acolo", "cine", "când", "unde",
+];
+
+const RUSSIAN: &[&str] = &[
+	"и",
+	"в",
+	"во",
+	"не",
+	"что",
+	"он",
+	"на",
+	"я",
+	"с",
+	"со",
+	"как",
+	"а",
+	"то",
+	"все",
+	"она",
+	"так",
+	"его",
+	"но",
+	"да",
+	"ты",
+	"к",
+	"у",
+	"же",
+	"вы",
+	"за",
+	"бы",
+	"по",
+	"только",
+	"ее",
+	"её",
+	"мне",
+	"было",
+	"вот",
+	"от",
+	"меня",
+	"еще",
+	"ещё",
+	"нет",
+	"о",
+	"из",
+	"ему",
+	"теперь",
+	"когда",
+	"даже",
+	"ну",
+	"вдруг",
+	"ли",
+	"если",
+	"уже",
+	"или",
+	"ни",
+	"быть",
+	"был",
+	"него",
+	"до",
+	"вас",
+	"нибудь",
+	"опять",
+	"уж",
+	"вам",
+	"ведь",
+	"там",
+	"потом",
+	"себя",
+	"ничего",
+	"ей",
+	"может",
+	"они",
+	"тут",
+	"где",
+	"есть",
+	"надо",
+	"ней",
+	"для",
+	"мы",
+	"тебя",
+	"их",
+	"чем",
+	"была",
+	"сам",
+	"чтоб",
+	"без",
+	"будто",
+	"чего",
+	"раз",
+	"тоже",
+	"себе",
+	"под",
+	"будет",
+	"ж",
+	"тогда",
+	"кто",
+	"этот",
+	"того",
+	"потому",
+	"этого",
+	"какой",
+	"совсем",
+	"ним",
+	"здесь",
+	"этом",
+	"один",
+	"почти",
+	"мой",
+	"тем",
+	"чтобы",
+	"нее",
+	"были",
+	"куда",
+	"зачем",
+	"всех",
+	"никогда",
+	"можно",
+	"при",
+	"наконец",
+	"два",
+	"об",
+	"другой",
+	"хоть",
+	"после",
+	"над",
+	"больше",
+	"тот",
+	"через",
+	"эти",
+	"нас",
+	"про",
+	"всего",
+	"них",
+	"какая",
+	"много",
+	"разве",
+	"три",
+	"эту",
+	"моя",
+	"впрочем",
+	"хорошо",
+	"свою",
+	"этой",
+	"перед",
+	"иногда",
+	"лучше",
+	"чуть",
+	"том",
+	"нельзя",
+	"такой",
+	"им",
+	"более",
+	"всегда",
+	"конечно",
+	"всю",
+	"между",
+];
+
+const SPANISH: &[&str] = &[
+	"de", "la", "que", "el", "en", "y", "a", "los", "del", "se", "las", "por", "un", "para", "con",
+	"no", "una", "su", "al", "lo", "como", "más", "pero", "sus", "le", "ya", "o", "este", "sí",
+	"porque", "esta", "entre", "cuando", "muy", "sin", "sobre", "también", "me", "hasta", "hay",
+	"donde", "quien", "desde", "todo", "nos", "durante", "todos", "uno", "les", "ni", "contra",
+	"otros", "ese", "eso", "ante", "ellos", "e", "esto", "mí", "antes", "algunos", "qué", "unos",
+	"yo", "otro", "otras", "otra", "él", "tanto", "esa", "estos", "mucho", "quienes", "nada",
+	"muchos", "cual", "poco", "ella", "estar", "estas", "algunas", "algo", "nosotros", "mi", "mis",
+	"tú", "te", "ti", "tu", "tus", "ellas", "nosotras", "vosotros", "vosotras", "os", "mío", "mía",
+	"es", "son", "era", "fue", "ser", "ha", "han", "he",
+];
+
+const SWEDISH: &[&str] = &[
+	"och", "det", "att", "i", "en", "jag", "hon", "som", "han", "på", "den", "med", "var", "sig",
+	"för", "så", "till", "är", "men", "ett", "om", "hade", "de", "av", "icke", "mig", "du",
+	"henne", "då", "sin", "nu", "har", "inte", "hans", "honom", "skulle", "hennes", "där", "min",
+	"man", "ej", "vid", "kunde", "något", "från", "ut", "när", "efter", "upp", "vi", "dem", "vara",
+	"vad", "över", "än", "dig", "kan", "sina", "här", "ha", "mot", "alla", "under", "någon",
+	"eller", "allt", "mycket", "sedan", "ju", "denna", "själv", "detta", "åt", "utan", "varit",
+	"hur", "ingen", "mitt", "ni", "bli", "blev", "oss", "din", "dessa", "några", "deras", "blir",
+	"mina", "samma", "vilken", "er", "sådan", "vår", "blivit", "dess", "inom", "mellan", "sådant",
+	"varför", "varje", "vilka", "ditt", "vem", "vilket", "sitta", "sådana", "vart", "dina", "vars",
+	"vårt", "våra", "ert", "era", "vilkas",
+];
+
+const TAMIL: &[&str] = &[
+	"ஒரு",
+	"என்று",
+	"மற்றும்",
+	"இந்த",
+	"இது",
+	"என்ற",
+	"கொண்டு",
+	"என்பது",
+	"பல",
+	"ஆகும்",
+	"அல்லது",
+	"அவர்",
+	"நான்",
+	"உள்ள",
+	"அந்த",
+	"இவர்",
+	"என",
+	"முதல்",
+	"என்ன",
+	"இருந்து",
+	"சில",
+	"என்",
+	"போன்ற",
+	"வேண்டும்",
+	"வந்து",
+	"இதன்",
+	"அது",
+	"அவன்",
+	"தான்",
+	"பலரும்",
+	"என்னும்",
+	"மேலும்",
+	"பின்னர்",
+	"கொண்ட",
+	"இருக்கும்",
+	"தனது",
+	"உள்ளது",
+	"போது",
+	"என்றும்",
+	"அதன்",
+	"தன்",
+	"பிறகு",
+	"அவர்கள்",
+	"வரை",
+	"அவள்",
+	"நீ",
+	"ஆகிய",
+	"இருந்தது",
+	"உள்ளன",
+	"வந்த",
+	"இருந்த",
+	"மிகவும்",
+	"இங்கு",
+	"மீது",
+	"ஓர்",
+	"இவை",
+	"இந்தக்",
+	"பற்றி",
+	"வரும்",
+	"வேறு",
+	"இரு",
+	"இதில்",
+	"போல்",
+	"இப்போது",
+	"அவரது",
+	"மட்டும்",
+	"இந்தப்",
+	"எனும்",
+	"மேல்",
+	"பின்",
+	"சேர்ந்த",
+	"ஆகியோர்",
+	"எனக்கு",
+	"இன்னும்",
+	"அந்தப்",
+	"அன்று",
+	"ஒரே",
+	"மிக",
+	"அங்கு",
+	"பல்வேறு",
+	"விட்டு",
+	"பெரும்",
+	"அதை",
+	"பற்றிய",
+	"உன்",
+	"அதிக",
+	"அந்தக்",
+	"பேர்",
+	"இதனால்",
+	"அவை",
+	"அதே",
+	"ஏன்",
+	"முறை",
+	"யார்",
+	"என்பதை",
+	"எல்லாம்",
+	"மட்டுமே",
+	"இங்கே",
+	"அங்கே",
+	"இடம்",
+	"இடத்தில்",
+	"அதில்",
+	"நாம்",
+	"அதற்கு",
+	"எனவே",
+	"பிற",
+	"சிறு",
+	"மற்ற",
+	"விட",
+	"எந்த",
+	"எனவும்",
+	"எனப்படும்",
+	"எனினும்",
+	"அடுத்த",
+	"இதனை",
+	"இதை",
+	"கொள்ள",
+	"இந்தத்",
+	"இதற்கு",
+	"அதனால்",
+	"தவிர",
+	"போல",
+	"வரையில்",
+	"சற்று",
+	"எனக்",
+];
+
+const TURKISH: &[&str] = &[
+	"acaba", "ama", "aslında", "az", "bazı", "belki", "biri", "birkaç", "birşey", "biz", "bu",
+	"çok", "çünkü", "da", "daha", "de", "defa", "diye", "eğer", "en", "gibi", "hem", "hep",
+	"hepsi", "her", "hiç", "için", "ile", "ise", "kez", "ki", "kim", "mı", "mu", "mü", "nasıl",
+	"ne", "neden", "nerde", "nerede", "nereye", "niçin", "niye", "o", "sanki", "şey", "siz", "şu",
+	"tüm", "ve", "veya", "ya", "yani", "ben", "sen", "onlar", "bir", "olan", "olarak", "değil",
+	"kadar", "sonra", "önce", "var", "yok", "ancak", "bile", "böyle", "şöyle",
+];
@@ -0,0 +1,67 @@
+use crate::err::Error;
+use crate::idx::ft::analyzer::filter::{FilterResult, Term};
+use crate::idx::ft::analyzer::mapper::read_lines;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+/// A set of synonyms loaded from a file, where each line
+/// lists a group of equivalent terms separated by commas.
+/// Empty lines, and lines starting with `#`, are ignored.
+#[derive(Clone, Default)]
+pub(in crate::idx) struct Synonyms {
+	terms: Arc<HashMap<String, Arc<[String]>>>,
+}
+
+impl Synonyms {
+	pub(in crate::idx) async fn new(path: &Path) -> Result<Self, Error> {
+		let mut terms = HashMap::new();
+		read_lines(path, |line, line_number| Self::add_line(&mut terms, line, line_number)).await?;
+		Ok(Self {
+			terms: Arc::new(terms),
+		})
+	}
+
+	fn add_line(
+		terms: &mut HashMap<String, Arc<[String]>>,
+		line: String,
+		line_number: usize,
+	) -> Result<(), Error> {
+		let line = line.trim();
+		if line.is_empty() || line.starts_with('#') {
+			return Ok(());
+		}
+		let group: Arc<[String]> = line
+			.split(',')
+			.map(|t| t.trim().to_string())
+			.filter(|t| !t.is_empty())
+			.collect::<Vec<_>>()
+			.into();
+		if group.len() < 2 {
+			return Err(Error::AnalyzerError(format!(
+				"Expected at least two terms separated by a comma line {line_number}: {line}"
+			)));
+		}
+		for term in group.iter() {
+			terms.insert(term.clone(), group.clone());
+		}
+		Ok(())
+	}
+
+	pub(super) fn expand(&self, token: &str) -> FilterResult {
+		if let Some(group) = self.terms.get(token) {
+			let terms = group
+				.iter()
+				.map(|t| {
+					if t == token {
+						Term::Unchanged
+					} else {
+						Term::NewTerm(t.clone(), 0)
+					}
+				})
+				.collect();
+			return FilterResult::Terms(terms);
+		}
+		FilterResult::Term(Term::Unchanged)
+	}
+}
@@ -4,6 +4,8 @@ use crate::idx::ft::analyzer::filter::{Filter, FilterResult, Term};
 use crate::idx::ft::offsets::{Offset, Position};
 use crate::sql::tokenizer::Tokenizer as SqlTokenizer;
 use crate::sql::Value;
+use std::collections::HashSet;
+use unicode_segmentation::UnicodeSegmentation;
 
 pub(in crate::idx) struct Tokens {
 	/// The input string
@@ -144,6 +146,19 @@ impl Token {
 		}
 	}
 
+	pub(super) fn get_char_start(&self) -> Position {
+		match self {
+			Token::Ref {
+				chars,
+				..
+			} => chars.0,
+			Token::String {
+				chars,
+				..
+			} => chars.0,
+		}
+	}
+
 	pub(super) fn get_char_len(&self) -> u32 {
 		match self {
 			Token::Ref {
@@ -183,15 +198,25 @@ impl Token {
 
 pub(super) struct Tokenizer {
 	splitters: Vec<Splitter>,
+	/// The byte positions of the Unicode word boundaries (UAX #29) of the input
+	word_bounds: Option<HashSet<Position>>,
 }
 
 impl Tokenizer {
-	pub(in crate::idx::ft) fn new(t: &[SqlTokenizer]) -> Self {
+	pub(in crate::idx::ft) fn new(t: &[SqlTokenizer], i: &str) -> Self {
+		let word_bounds = t
+			.contains(&SqlTokenizer::Unicode)
+			.then(|| i.split_word_bound_indices().map(|(pos, _)| pos as Position).collect());
 		Self {
 			splitters: t.iter().map(|t| t.into()).collect(),
+			word_bounds,
 		}
 	}
 
+	fn is_word_bound(&self, byte_pos: Position) -> bool {
+		self.word_bounds.as_ref().is_some_and(|b| b.contains(&byte_pos))
+	}
+
 	fn is_valid(c: char) -> bool {
 		c.is_alphanumeric() || c.is_ascii_punctuation()
 	}
@@ -207,7 +232,7 @@ impl Tokenizer {
 	}
 
 	pub(super) fn tokenize(t: &[SqlTokenizer], i: String) -> Tokens {
-		let mut w = Tokenizer::new(t);
+		let mut w = Tokenizer::new(t, &i);
 		let mut last_char_pos = 0;
 		let mut last_byte_pos = 0;
 		let mut current_char_pos = 0;
@@ -216,7 +241,7 @@ impl Tokenizer {
 		for c in i.chars() {
 			let char_len = c.len_utf8() as Position;
 			let is_valid = Self::is_valid(c);
-			let should_split = w.should_split(c);
+			let should_split = w.should_split(c) || w.is_word_bound(current_byte_pos);
 			if should_split || !is_valid {
 				// The last pos may be more advanced due to the is_valid process
 				if last_char_pos < current_char_pos {
@@ -273,6 +298,8 @@ impl Splitter {
 			SqlTokenizer::Camel => self.camel_state(c),
 			SqlTokenizer::Class => self.class_state(c),
 			SqlTokenizer::Punct => self.punct_state(c),
+			// The word boundaries are found on the whole input by the tokenizer
+			SqlTokenizer::Unicode => false,
 		}
 	}
 
@@ -400,4 +427,36 @@ static LANGUAGE: &str = "Rust";"#,
 		)
 		.await;
 	}
+
+	#[tokio::test]
+	async fn test_tokenize_unicode() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS unicode FILTERS lowercase",
+			"東京タワーに行きました with Tokyo2024, can't pay 3.14 我喜欢北京 서울특별시",
+			&[
+				"東",
+				"京",
+				"タワー",
+				"に",
+				"行",
+				"き",
+				"ま",
+				"し",
+				"た",
+				"with",
+				"tokyo2024",
+				",",
+				"can't",
+				"pay",
+				"3.14",
+				"我",
+				"喜",
+				"欢",
+				"北",
+				"京",
+				"서울특별시",
+			],
+		)
+		.await;
+	}
 }
@@ -0,0 +1,146 @@
+use fst::Automaton;
+
+/// A Levenshtein automaton matching the keys within a maximum edit distance
+/// (insertions, deletions and substitutions of characters) of a term.
+/// The keys are fed as UTF-8 bytes, the distance is computed on characters.
+pub(in crate::idx) struct Levenshtein {
+	term: Vec<char>,
+	distance: u32,
+}
+
+#[derive(Clone)]
+pub(in crate::idx) struct LevenshteinState {
+	/// The edit distances between the characters read so far and each prefix of the term
+	row: Vec<u32>,
+	/// The bytes of an incomplete UTF-8 character
+	pending: Vec<u8>,
+}
+
+impl Levenshtein {
+	pub(in crate::idx) fn new(term: &str, distance: u8) -> Self {
+		Self {
+			term: term.chars().collect(),
+			distance: distance as u32,
+		}
+	}
+
+	/// Checks if the given string is within the edit distance of the term
+	pub(in crate::idx) fn is_match_str(&self, s: &str) -> bool {
+		let mut row = self.start_row();
+		for c in s.chars() {
+			row = self.step(&row, c);
+			if !self.can_match_row(&row) {
+				return false;
+			}
+		}
+		self.is_match_row(&row)
+	}
+
+	fn start_row(&self) -> Vec<u32> {
+		(0..=self.term.len() as u32).collect()
+	}
+
+	fn step(&self, row: &[u32], c: char) -> Vec<u32> {
+		let mut next = Vec::with_capacity(row.len());
+		next.push(row[0] + 1);
+		for (i, tc) in self.term.iter().enumerate() {
+			let cost = if *tc == c {
+				0
+			} else {
+				1
+			};
+			next.push((row[i + 1] + 1).min(next[i] + 1).min(row[i] + cost));
+		}
+		next
+	}
+
+	fn is_match_row(&self, row: &[u32]) -> bool {
+		row.last().is_some_and(|d| *d <= self.distance)
+	}
+
+	fn can_match_row(&self, row: &[u32]) -> bool {
+		row.iter().min().is_some_and(|d| *d <= self.distance)
+	}
+
+	fn utf8_len(first: u8) -> usize {
+		match first {
+			b if b >> 5 == 0b110 => 2,
+			b if b >> 4 == 0b1110 => 3,
+			b if b >> 3 == 0b11110 => 4,
+			_ => 1,
+		}
+	}
+}
+
+impl Automaton for Levenshtein {
+	type State = Option<LevenshteinState>;
+
+	fn start(&self) -> Self::State {
+		Some(LevenshteinState {
+			row: self.start_row(),
+			pending: vec![],
+		})
+	}
+
+	fn is_match(&self, state: &Self::State) -> bool {
+		state.as_ref().is_some_and(|s| s.pending.is_empty() && self.is_match_row(&s.row))
+	}
+
+	fn can_match(&self, state: &Self::State) -> bool {
+		state.is_some()
+	}
+
+	fn accept(&self, state: &Self::State, byte: u8) -> Self::State {
+		let state = state.as_ref()?;
+		let mut pending = state.pending.clone();
+		pending.push(byte);
+		if pending.len() < Self::utf8_len(pending[0]) {
+			return Some(LevenshteinState {
+				row: state.row.clone(),
+				pending,
+			});
+		}
+		let c = std::str::from_utf8(&pending)
+			.ok()
+			.and_then(|s| s.chars().next())
+			.unwrap_or(char::REPLACEMENT_CHARACTER);
+		let row = self.step(&state.row, c);
+		self.can_match_row(&row).then_some(LevenshteinState {
+			row,
+			pending: vec![],
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::idx::ft::fuzzy::Levenshtein;
+	use fst::{IntoStreamer, Set, Streamer};
+
+	#[test]
+	fn test_is_match_str() {
+		let lev = Levenshtein::new("surreal", 2);
+		assert!(lev.is_match_str("surreal"));
+		assert!(lev.is_match_str("sureal"));
+		assert!(lev.is_match_str("surrael"));
+		assert!(lev.is_match_str("surrealdb"));
+		assert!(!lev.is_match_str("surrealdb!"));
+		assert!(!lev.is_match_str("real"));
+		let lev = Levenshtein::new("café", 1);
+		assert!(lev.is_match_str("cafe"));
+		assert!(lev.is_match_str("cafés"));
+		assert!(!lev.is_match_str("cofe"));
+	}
+
+	#[test]
+	fn test_automaton() {
+		let set = Set::from_iter(["cafe", "café", "chef", "cofe", "coffee", "kafé"]).unwrap();
+		let lev = Levenshtein::new("café", 1);
+		let mut stream = set.search(&lev).into_stream();
+		let mut res = vec![];
+		while let Some(k) = stream.next() {
+			res.push(String::from_utf8(k.to_vec()).unwrap());
+		}
+		assert_eq!(res, vec!["cafe", "café", "kafé"]);
+	}
+}
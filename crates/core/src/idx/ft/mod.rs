@@ -1,8 +1,10 @@
 pub(crate) mod analyzer;
 mod doclength;
+mod fuzzy;
 pub(crate) mod highlighter;
 mod offsets;
 mod postings;
+pub(super) mod query;
 pub(super) mod scorer;
 pub(super) mod termdocs;
 pub(crate) mod terms;
@@ -11,11 +13,12 @@ use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::err::Error;
 use crate::idx::docids::{DocId, DocIds};
-use crate::idx::ft::analyzer::{Analyzer, TermsList, TermsSet};
+use crate::idx::ft::analyzer::Analyzer;
 use crate::idx::ft::doclength::DocLengths;
 use crate::idx::ft::highlighter::{HighlightParams, Highlighter, Offseter};
 use crate::idx::ft::offsets::Offsets;
 use crate::idx::ft::postings::Postings;
+use crate::idx::ft::query::FtQuery;
 use crate::idx::ft::scorer::BM25Scorer;
 use crate::idx::ft::termdocs::{TermDocs, TermsDocs};
 use crate::idx::ft::terms::{TermId, TermLen, Terms};
@@ -33,7 +36,7 @@ use revision::revisioned;
 use roaring::treemap::IntoIter;
 use roaring::RoaringTreemap;
 use serde::{Deserialize, Serialize};
-use std::ops::BitAnd;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -181,10 +184,6 @@ impl FtIndex {
 		self.doc_ids.clone()
 	}
 
-	pub(super) fn terms(&self) -> Arc<RwLock<Terms>> {
-		self.terms.clone()
-	}
-
 	pub(super) fn analyzer(&self) -> Analyzer {
 		self.analyzer.clone()
 	}
@@ -351,15 +350,16 @@ impl FtIndex {
 		Ok(())
 	}
 
-	pub(super) async fn extract_querying_terms(
+	pub(super) async fn extract_query(
 		&self,
 		stk: &mut Stk,
 		ctx: &Context,
 		opt: &Options,
 		query_string: String,
-	) -> Result<(TermsList, TermsSet), Error> {
+		fuzzy: Option<u8>,
+	) -> Result<FtQuery, Error> {
 		let t = self.terms.read().await;
-		let res = self.analyzer.extract_querying_terms(stk, ctx, opt, &t, query_string).await?;
+		let res = self.analyzer.extract_query(stk, ctx, opt, &t, query_string, fuzzy).await?;
 		drop(t);
 		Ok(res)
 	}
@@ -367,17 +367,14 @@ impl FtIndex {
 	pub(super) async fn get_terms_docs(
 		&self,
 		tx: &Transaction,
-		terms: &TermsList,
+		query: &FtQuery,
 	) -> Result<Vec<Option<(TermId, RoaringTreemap)>>, Error> {
+		let terms = query.terms();
 		let mut terms_docs = Vec::with_capacity(terms.len());
-		for opt_term in terms {
-			if let Some((term_id, _)) = opt_term {
-				let docs = self.term_docs.get_docs(tx, *term_id).await?;
-				if let Some(docs) = docs {
-					terms_docs.push(Some((*term_id, docs)));
-				} else {
-					terms_docs.push(Some((*term_id, RoaringTreemap::new())));
-				}
+		for t in terms {
+			if let Some((term_id, _)) = t.id {
+				let docs = self.term_docs.get_docs(tx, term_id).await?;
+				terms_docs.push(Some((term_id, docs.unwrap_or_default())));
 			} else {
 				terms_docs.push(None);
 			}
@@ -385,28 +382,36 @@ impl FtIndex {
 		Ok(terms_docs)
 	}
 
-	pub(super) fn new_hits_iterator(
+	/// Returns the documents matching the query.
+	/// If the query is positional, the returned documents still need to be checked.
+	pub(super) async fn get_hits(
 		&self,
-		terms_docs: TermsDocs,
-	) -> Result<Option<HitsIterator>, Error> {
-		let mut hits: Option<RoaringTreemap> = None;
-		for opt_term_docs in terms_docs.iter() {
-			if let Some((_, term_docs)) = opt_term_docs {
-				if let Some(h) = hits {
-					hits = Some(h.bitand(term_docs));
-				} else {
-					hits = Some(term_docs.clone());
+		tx: &Transaction,
+		query: &FtQuery,
+		terms_docs: &TermsDocs,
+	) -> Result<RoaringTreemap, Error> {
+		let mut docs: HashMap<TermId, &RoaringTreemap> =
+			terms_docs.iter().flatten().map(|(id, d)| (*id, d)).collect();
+		// The documents of the excluded terms
+		let mut excluded = Vec::new();
+		for t in query.excluded_terms() {
+			if let Some((term_id, _)) = t.id {
+				if !docs.contains_key(&term_id) {
+					if let Some(d) = self.term_docs.get_docs(tx, term_id).await? {
+						excluded.push((term_id, d));
+					}
 				}
-			} else {
-				return Ok(None);
 			}
 		}
-		if let Some(hits) = hits {
-			if !hits.is_empty() {
-				return Ok(Some(HitsIterator::new(self.doc_ids.clone(), hits)));
-			}
+		docs.extend(excluded.iter().map(|(id, d)| (*id, d)));
+		Ok(query.hits(&docs))
+	}
+
+	pub(super) fn new_hits_iterator(&self, hits: RoaringTreemap) -> Option<HitsIterator> {
+		if hits.is_empty() {
+			return None;
 		}
-		Ok(None)
+		Some(HitsIterator::new(self.doc_ids.clone(), hits))
 	}
 
 	pub(super) fn new_scorer(&self, terms_docs: TermsDocs) -> Result<Option<BM25Scorer>, Error> {
@@ -575,13 +580,12 @@ mod tests {
 		fti: &FtIndex,
 		qs: &str,
 	) -> (Option<HitsIterator>, BM25Scorer) {
-		let (term_list, _) =
-			fti.extract_querying_terms(stk, ctx, opt, qs.to_string()).await.unwrap();
+		let query = fti.extract_query(stk, ctx, opt, qs.to_string(), None).await.unwrap();
 		let tx = ctx.tx();
-		let td = Arc::new(fti.get_terms_docs(&tx, &term_list).await.unwrap());
+		let td = Arc::new(fti.get_terms_docs(&tx, &query).await.unwrap());
 		let scr = fti.new_scorer(td.clone()).unwrap().unwrap();
-		let hits = fti.new_hits_iterator(td).unwrap();
-		(hits, scr)
+		let hits = fti.get_hits(&tx, &query, &td).await.unwrap();
+		(fti.new_hits_iterator(hits), scr)
 	}
 
 	pub(super) async fn tx_fti<'a>(
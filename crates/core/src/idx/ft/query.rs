@@ -0,0 +1,438 @@
+use crate::idx::ft::fuzzy::Levenshtein;
+use crate::idx::ft::terms::{TermId, TermLen};
+use roaring::RoaringTreemap;
+use std::collections::{HashMap, HashSet};
+
+/// The positions of every term within a value: (value index, token ordinal)
+pub(in crate::idx) type TermPositions = HashMap<String, Vec<(u32, u32)>>;
+
+/// An operand of the query string, before analysis
+#[derive(Debug, PartialEq)]
+pub(super) enum Operand {
+	/// A bare word
+	Text(String),
+	/// A double-quoted sequence of words
+	Phrase(String),
+	/// A word followed by a `*` wildcard
+	Prefix(String),
+}
+
+/// A clause of the query string, before analysis.
+/// Operands are chained with `NEAR/n` operators, `distances` holds each `n`.
+#[derive(Debug, PartialEq)]
+pub(super) struct ParsedClause {
+	pub(super) must_not: bool,
+	pub(super) operands: Vec<Operand>,
+	pub(super) distances: Vec<u32>,
+}
+
+/// Splits a query string into clauses.
+/// Supports bare words, `"phrases"`, `prefix*` wildcards,
+/// the `+` (must) and `-` (must not) modifiers and the `NEAR/n` operator.
+pub(super) fn parse(qs: &str) -> Vec<ParsedClause> {
+	let mut clauses: Vec<ParsedClause> = Vec::new();
+	let mut near: Option<u32> = None;
+	let mut chars = qs.chars().peekable();
+	loop {
+		// Skip the whitespaces
+		while chars.next_if(|c| c.is_whitespace()).is_some() {}
+		let Some(c) = chars.next() else {
+			break;
+		};
+		// A modifier only applies if it is directly followed by an operand
+		let mut must_not = false;
+		let mut modified = false;
+		let c = match c {
+			'+' | '-' if chars.peek().is_some_and(|n| !n.is_whitespace()) => {
+				must_not = c == '-';
+				modified = true;
+				chars.next().unwrap_or(c)
+			}
+			c => c,
+		};
+		let operand = if c == '"' {
+			let phrase: String = chars.by_ref().take_while(|c| *c != '"').collect();
+			Operand::Phrase(phrase)
+		} else {
+			let mut word = String::from(c);
+			while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+				word.push(c);
+			}
+			if !modified && near.is_none() && !clauses.is_empty() {
+				if let Some(d) = parse_near(&word) {
+					near = Some(d);
+					continue;
+				}
+			}
+			match word.strip_suffix('*') {
+				Some(prefix) if !prefix.is_empty() => Operand::Prefix(prefix.to_string()),
+				_ => Operand::Text(word),
+			}
+		};
+		match (near.take(), clauses.last_mut()) {
+			(Some(d), Some(clause)) => {
+				clause.operands.push(operand);
+				clause.distances.push(d);
+			}
+			_ => clauses.push(ParsedClause {
+				must_not,
+				operands: vec![operand],
+				distances: vec![],
+			}),
+		}
+	}
+	clauses
+}
+
+fn parse_near(word: &str) -> Option<u32> {
+	word.strip_prefix("NEAR/").and_then(|d| d.parse().ok())
+}
+
+/// A term of the query, with its id and length if it is known by the index
+#[derive(Debug, Clone, PartialEq)]
+pub(in crate::idx) struct QueryTerm {
+	pub(super) term: String,
+	pub(in crate::idx) id: Option<(TermId, TermLen)>,
+}
+
+#[derive(Debug, PartialEq)]
+pub(in crate::idx) enum QueryExpr {
+	Term(QueryTerm),
+	/// The prefix, and the terms of the index starting with this prefix
+	Prefix(String, Vec<QueryTerm>),
+	/// The term, the edit distance, and the terms of the index within this distance
+	Fuzzy(String, u8, Vec<QueryTerm>),
+	/// Terms that must be found at consecutive positions
+	Phrase(Vec<QueryTerm>),
+	/// Expressions separated by at most the given number of tokens
+	Near(Box<QueryExpr>, Box<QueryExpr>, u32),
+}
+
+/// A span of tokens: (value index, first ordinal, last ordinal)
+type Span = (u32, u32, u32);
+
+impl QueryExpr {
+	fn is_positional(&self) -> bool {
+		matches!(self, Self::Phrase(_) | Self::Near(..))
+	}
+
+	fn collect_terms<'a>(&'a self, terms: &mut Vec<&'a QueryTerm>) {
+		match self {
+			Self::Term(t) => terms.push(t),
+			Self::Prefix(_, ts) | Self::Fuzzy(_, _, ts) | Self::Phrase(ts) => terms.extend(ts),
+			Self::Near(l, r, _) => {
+				l.collect_terms(terms);
+				r.collect_terms(terms);
+			}
+		}
+	}
+
+	/// The documents which may match this expression.
+	/// For positional expressions, this is a superset of the matching documents.
+	fn docs(&self, docs: &HashMap<TermId, &RoaringTreemap>) -> RoaringTreemap {
+		let term_docs = |t: &QueryTerm| {
+			t.id.and_then(|(id, _)| docs.get(&id)).map(|d| (*d).clone()).unwrap_or_default()
+		};
+		match self {
+			Self::Term(t) => term_docs(t),
+			Self::Prefix(_, ts) | Self::Fuzzy(_, _, ts) => {
+				ts.iter().map(term_docs).fold(RoaringTreemap::new(), |a, d| a | d)
+			}
+			Self::Phrase(ts) => Self::intersect(ts.iter().map(term_docs)),
+			Self::Near(l, r, _) => l.docs(docs) & r.docs(docs),
+		}
+	}
+
+	fn intersect(mut iter: impl Iterator<Item = RoaringTreemap>) -> RoaringTreemap {
+		let Some(first) = iter.next() else {
+			return RoaringTreemap::new();
+		};
+		iter.fold(first, |a, d| a & d)
+	}
+
+	/// Returns every span of tokens matching this expression
+	fn spans(&self, positions: &TermPositions) -> Vec<Span> {
+		match self {
+			Self::Term(t) => Self::term_spans(positions, &t.term),
+			Self::Prefix(p, _) => positions
+				.iter()
+				.filter(|(t, _)| t.starts_with(p.as_str()))
+				.flat_map(|(_, pos)| pos.iter().map(|(v, o)| (*v, *o, *o)))
+				.collect(),
+			Self::Fuzzy(t, d, _) => {
+				let lev = Levenshtein::new(t, *d);
+				positions
+					.iter()
+					.filter(|(t, _)| lev.is_match_str(t))
+					.flat_map(|(_, pos)| pos.iter().map(|(v, o)| (*v, *o, *o)))
+					.collect()
+			}
+			Self::Phrase(ts) => {
+				let Some((first, others)) = ts.split_first() else {
+					return vec![];
+				};
+				let others: Vec<HashSet<(u32, u32)>> = others
+					.iter()
+					.map(|t| positions.get(&t.term).into_iter().flatten().copied().collect())
+					.collect();
+				Self::term_spans(positions, &first.term)
+					.into_iter()
+					.filter(|(v, o, _)| {
+						others
+							.iter()
+							.enumerate()
+							.all(|(i, pos)| pos.contains(&(*v, o + i as u32 + 1)))
+					})
+					.map(|(v, o, _)| (v, o, o + others.len() as u32))
+					.collect()
+			}
+			Self::Near(l, r, distance) => {
+				let r_spans = r.spans(positions);
+				let mut spans = vec![];
+				for (lv, ls, le) in l.spans(positions) {
+					for (rv, rs, re) in &r_spans {
+						if lv != *rv {
+							continue;
+						}
+						// The number of tokens between the two spans, which must not overlap
+						let gap = if le < *rs {
+							rs - le - 1
+						} else if *re < ls {
+							ls - re - 1
+						} else {
+							continue;
+						};
+						if gap <= *distance {
+							spans.push((lv, ls.min(*rs), le.max(*re)));
+						}
+					}
+				}
+				spans
+			}
+		}
+	}
+
+	fn term_spans(positions: &TermPositions, term: &str) -> Vec<Span> {
+		positions.get(term).into_iter().flatten().map(|(v, o)| (*v, *o, *o)).collect()
+	}
+}
+
+#[derive(Debug, PartialEq)]
+pub(in crate::idx) struct QueryClause {
+	pub(super) must_not: bool,
+	pub(super) expr: QueryExpr,
+}
+
+/// An analyzed full-text query
+#[derive(Debug, Default, PartialEq)]
+pub(in crate::idx) struct FtQuery {
+	pub(super) clauses: Vec<QueryClause>,
+}
+
+impl FtQuery {
+	/// Returns true if the query contains phrase or proximity expressions.
+	/// Matching documents can then only be confirmed by looking at term positions.
+	pub(in crate::idx) fn is_positional(&self) -> bool {
+		self.clauses.iter().any(|c| c.expr.is_positional())
+	}
+
+	/// The unique terms the documents are looked up with (excluding the must not clauses)
+	pub(in crate::idx) fn terms(&self) -> Vec<&QueryTerm> {
+		self.collect_terms(false)
+	}
+
+	/// The unique terms of the must not clauses
+	pub(super) fn excluded_terms(&self) -> Vec<&QueryTerm> {
+		self.collect_terms(true)
+	}
+
+	fn collect_terms(&self, must_not: bool) -> Vec<&QueryTerm> {
+		let mut terms = vec![];
+		for c in self.clauses.iter().filter(|c| c.must_not == must_not) {
+			c.expr.collect_terms(&mut terms);
+		}
+		let mut unique = HashSet::new();
+		terms.retain(|t| unique.insert(t.term.as_str()));
+		terms
+	}
+
+	/// The documents matching the query, given the documents of each term.
+	/// If the query is positional, this is a superset of the matching documents.
+	pub(super) fn hits(&self, docs: &HashMap<TermId, &RoaringTreemap>) -> RoaringTreemap {
+		let mut hits = QueryExpr::intersect(
+			self.clauses.iter().filter(|c| !c.must_not).map(|c| c.expr.docs(docs)),
+		);
+		for c in self.clauses.iter().filter(|c| c.must_not) {
+			// Positional exclusions can only be checked against the positions
+			if !hits.is_empty() && !c.expr.is_positional() {
+				hits -= c.expr.docs(docs);
+			}
+		}
+		hits
+	}
+
+	/// Checks if the query matches the given term positions
+	pub(in crate::idx) fn matches(&self, positions: &TermPositions) -> bool {
+		let mut has_must = false;
+		for c in &self.clauses {
+			let found = !c.expr.spans(positions).is_empty();
+			if c.must_not == found {
+				return false;
+			}
+			has_must |= !c.must_not;
+		}
+		has_must
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		parse, FtQuery, Operand, ParsedClause, QueryClause, QueryExpr, QueryTerm, TermPositions,
+	};
+	use roaring::RoaringTreemap;
+	use std::collections::HashMap;
+
+	fn clause(must_not: bool, operands: Vec<Operand>, distances: Vec<u32>) -> ParsedClause {
+		ParsedClause {
+			must_not,
+			operands,
+			distances,
+		}
+	}
+
+	fn text(s: &str) -> Operand {
+		Operand::Text(s.to_string())
+	}
+
+	fn phrase(s: &str) -> Operand {
+		Operand::Phrase(s.to_string())
+	}
+
+	#[test]
+	fn test_parse() {
+		assert_eq!(
+			parse(r#"+hello -world "quick brown" fo* foo NEAR/3 "bar baz" NEAR/1 qux"#),
+			vec![
+				clause(false, vec![text("hello")], vec![]),
+				clause(true, vec![text("world")], vec![]),
+				clause(false, vec![phrase("quick brown")], vec![]),
+				clause(false, vec![Operand::Prefix("fo".to_string())], vec![]),
+				clause(false, vec![text("foo"), phrase("bar baz"), text("qux")], vec![3, 1]),
+			]
+		);
+	}
+
+	#[test]
+	fn test_parse_edge_cases() {
+		// Lone modifiers and wildcards, leading NEAR and unclosed quotes are kept as text
+		assert_eq!(
+			parse(r#"NEAR/2 a - * b NEAR/x c -"d e"#),
+			vec![
+				clause(false, vec![text("NEAR/2")], vec![]),
+				clause(false, vec![text("a")], vec![]),
+				clause(false, vec![text("-")], vec![]),
+				clause(false, vec![text("*")], vec![]),
+				clause(false, vec![text("b")], vec![]),
+				clause(false, vec![text("NEAR/x")], vec![]),
+				clause(false, vec![text("c")], vec![]),
+				clause(true, vec![phrase("d e")], vec![]),
+			]
+		);
+		assert_eq!(parse("   "), vec![]);
+	}
+
+	fn term(t: &str, id: u64) -> QueryTerm {
+		QueryTerm {
+			term: t.to_string(),
+			id: Some((id, t.len() as u32)),
+		}
+	}
+
+	fn query(clauses: Vec<(bool, QueryExpr)>) -> FtQuery {
+		FtQuery {
+			clauses: clauses
+				.into_iter()
+				.map(|(must_not, expr)| QueryClause {
+					must_not,
+					expr,
+				})
+				.collect(),
+		}
+	}
+
+	fn positions(values: &[&str]) -> TermPositions {
+		let mut positions = TermPositions::new();
+		for (v, value) in values.iter().enumerate() {
+			for (o, t) in value.split_whitespace().enumerate() {
+				positions.entry(t.to_string()).or_default().push((v as u32, o as u32));
+			}
+		}
+		positions
+	}
+
+	#[test]
+	fn test_hits() {
+		let docs: HashMap<u64, RoaringTreemap> = HashMap::from([
+			(0, RoaringTreemap::from_iter([1, 2, 3])),
+			(1, RoaringTreemap::from_iter([2, 3, 4])),
+			(2, RoaringTreemap::from_iter([3])),
+		]);
+		let docs = docs.iter().map(|(id, d)| (*id, d)).collect();
+		let q = query(vec![
+			(false, QueryExpr::Term(term("a", 0))),
+			(false, QueryExpr::Prefix("b".to_string(), vec![term("b", 1), term("bb", 2)])),
+			(true, QueryExpr::Term(term("c", 2))),
+		]);
+		assert_eq!(q.hits(&docs), RoaringTreemap::from_iter([2]));
+		// Positional exclusions are not applied on the hits
+		let q = query(vec![
+			(false, QueryExpr::Term(term("a", 0))),
+			(true, QueryExpr::Phrase(vec![term("b", 1), term("c", 2)])),
+		]);
+		assert_eq!(q.hits(&docs), RoaringTreemap::from_iter([1, 2, 3]));
+		// Without a must clause, there is nothing to match
+		let q = query(vec![(true, QueryExpr::Term(term("a", 0)))]);
+		assert!(q.hits(&docs).is_empty());
+	}
+
+	#[test]
+	fn test_matches_phrase() {
+		let q = query(vec![(false, QueryExpr::Phrase(vec![term("quick", 0), term("brown", 1)]))]);
+		assert!(q.is_positional());
+		assert!(q.matches(&positions(&["the quick brown fox"])));
+		assert!(!q.matches(&positions(&["the brown quick fox"])));
+		assert!(!q.matches(&positions(&["the quick", "brown fox"])));
+	}
+
+	#[test]
+	fn test_matches_near() {
+		let near = |d| {
+			QueryExpr::Near(
+				Box::new(QueryExpr::Term(term("quick", 0))),
+				Box::new(QueryExpr::Term(term("fox", 1))),
+				d,
+			)
+		};
+		let p = positions(&["the quick brown fox"]);
+		assert!(q(near(1)).matches(&p));
+		assert!(!q(near(0)).matches(&p));
+		// The order does not matter
+		assert!(q(near(1)).matches(&positions(&["the fox brown quick"])));
+		fn q(expr: QueryExpr) -> FtQuery {
+			query(vec![(false, expr)])
+		}
+	}
+
+	#[test]
+	fn test_matches_boolean_and_prefix() {
+		let q = query(vec![
+			(false, QueryExpr::Prefix("qu".to_string(), vec![])),
+			(true, QueryExpr::Phrase(vec![term("lazy", 0), term("dog", 1)])),
+		]);
+		assert!(q.matches(&positions(&["the quick fox"])));
+		assert!(!q.matches(&positions(&["the quick fox jumps over the lazy dog"])));
+		assert!(q.matches(&positions(&["the quick fox", "the dog is lazy"])));
+		assert!(!q.matches(&positions(&["the fox"])));
+	}
+}
@@ -1,4 +1,5 @@
 use crate::err::Error;
+use crate::idx::ft::fuzzy::Levenshtein;
 use crate::idx::trees::bkeys::FstKeys;
 use crate::idx::trees::btree::{BState, BState1, BState1skip, BStatistics, BTree, BTreeStore};
 use crate::idx::trees::store::{IndexStores, TreeNodeProvider};
@@ -97,6 +98,36 @@ impl Terms {
 		self.btree.search(tx, &self.store, &term.into()).await
 	}
 
+	/// Returns the terms starting with the given prefix, along with their ids
+	pub(super) async fn get_terms_with_prefix(
+		&self,
+		tx: &Transaction,
+		prefix: &str,
+	) -> Result<Vec<(String, TermId)>, Error> {
+		let keys = self.btree.search_by_prefix(tx, &self.store, &prefix.into()).await?;
+		let mut terms = Vec::with_capacity(keys.len());
+		for (key, term_id) in keys {
+			terms.push((String::from_utf8(key)?, term_id));
+		}
+		Ok(terms)
+	}
+
+	/// Returns the terms within the given edit distance of the term, along with their ids
+	pub(super) async fn get_fuzzy_terms(
+		&self,
+		tx: &Transaction,
+		term: &str,
+		distance: u8,
+	) -> Result<Vec<(String, TermId)>, Error> {
+		let lev = Levenshtein::new(term, distance);
+		let keys = self.btree.search_by_automaton(tx, &self.store, &lev).await?;
+		let mut terms = Vec::with_capacity(keys.len());
+		for (key, term_id) in keys {
+			terms.push((String::from_utf8(key)?, term_id));
+		}
+		Ok(terms)
+	}
+
 	pub(super) async fn remove_term_id(
 		&mut self,
 		tx: &Transaction,
@@ -398,4 +429,68 @@ mod tests {
 			finish(tx, t).await;
 		}
 	}
+
+	#[test(tokio::test)]
+	async fn test_get_terms_with_prefix() {
+		const BTREE_ORDER: u32 = 5;
+
+		let ds = Datastore::new("memory").await.unwrap();
+		let words: Vec<String> = (0..200).map(|i| format!("w{:x}", i * 7)).collect();
+		{
+			let (tx, mut t) = new_operation(&ds, BTREE_ORDER, Write).await;
+			for w in &words {
+				t.resolve_term_id(&tx, w).await.unwrap();
+			}
+			finish(tx, t).await;
+		}
+		let (tx, t) = new_operation(&ds, BTREE_ORDER, Read).await;
+		for prefix in ["w", "w1", "w2a", "w3f", "x", ""] {
+			let mut res: Vec<String> = t
+				.get_terms_with_prefix(&tx, prefix)
+				.await
+				.unwrap()
+				.into_iter()
+				.map(|(term, _)| term)
+				.collect();
+			res.sort();
+			let mut expected: Vec<String> =
+				words.iter().filter(|w| w.starts_with(prefix)).cloned().collect();
+			expected.sort();
+			assert_eq!(res, expected, "{prefix}");
+		}
+	}
+
+	#[test(tokio::test)]
+	async fn test_get_fuzzy_terms() {
+		const BTREE_ORDER: u32 = 5;
+
+		let ds = Datastore::new("memory").await.unwrap();
+		let words: Vec<String> = (0..300).map(|i| format!("w{:x}", i * 7)).collect();
+		{
+			let (tx, mut t) = new_operation(&ds, BTREE_ORDER, Write).await;
+			for w in &words {
+				t.resolve_term_id(&tx, w).await.unwrap();
+			}
+			finish(tx, t).await;
+		}
+		let (tx, t) = new_operation(&ds, BTREE_ORDER, Read).await;
+		for (term, distance) in [("w1a", 1), ("w1a", 2), ("w7e", 0), ("x", 1), ("w3f0", 1)] {
+			let mut res: Vec<String> = t
+				.get_fuzzy_terms(&tx, term, distance)
+				.await
+				.unwrap()
+				.into_iter()
+				.map(|(term, _)| term)
+				.collect();
+			res.sort();
+			let mut expected: Vec<String> = words
+				.iter()
+				.filter(|w| strsim::levenshtein(w, term) <= distance as usize)
+				.cloned()
+				.collect();
+			expected.sort();
+			assert!(!expected.is_empty() || term == "x");
+			assert_eq!(res, expected, "{term} {distance}");
+		}
+	}
 }
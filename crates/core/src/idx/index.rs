@@ -4,11 +4,12 @@ use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::err::Error;
 use crate::idx::ft::FtIndex;
+use crate::idx::spatial::SpatialIndex;
 use crate::idx::trees::mtree::MTreeIndex;
 use crate::idx::IndexKeyBase;
 use crate::key;
 use crate::kvs::TransactionType;
-use crate::sql::index::{HnswParams, MTreeParams, SearchParams};
+use crate::sql::index::{HnswParams, MTreeParams, SearchParams, SpatialParams};
 use crate::sql::statements::DefineIndexStatement;
 use crate::sql::{Array, Index, Part, Thing, Value};
 use reblessive::tree::Stk;
@@ -51,6 +52,7 @@ impl<'a> IndexOperation<'a> {
 			Index::Search(p) => self.index_full_text(stk, p).await,
 			Index::MTree(p) => self.index_mtree(stk, p).await,
 			Index::Hnsw(p) => self.index_hnsw(p).await,
+			Index::Spatial(p) => self.index_spatial(p).await,
 		}
 	}
 
@@ -199,6 +201,21 @@ impl<'a> IndexOperation<'a> {
 		}
 		Ok(())
 	}
+
+	async fn index_spatial(&mut self, p: &SpatialParams) -> Result<(), Error> {
+		let txn = self.ctx.tx();
+		let ikb = IndexKeyBase::new(self.opt.ns()?, self.opt.db()?, self.ix)?;
+		let si = SpatialIndex::new(ikb, p);
+		// Delete the old index data
+		if let Some(o) = self.o.take() {
+			si.remove_document(&txn, &self.rid.id, &o).await?;
+		}
+		// Create the new index data
+		if let Some(n) = self.n.take() {
+			si.index_document(&txn, &self.rid.id, &n).await?;
+		}
+		Ok(())
+	}
 }
 
 /// Extract from the given document, the values required by the index and put then in an array.
@@ -2,6 +2,7 @@ pub mod docids;
 pub(crate) mod ft;
 pub(crate) mod index;
 pub mod planner;
+pub(crate) mod spatial;
 pub mod trees;
 
 use crate::err::Error;
@@ -21,6 +22,8 @@ use crate::key::index::bp::Bp;
 use crate::key::index::bs::Bs;
 use crate::key::index::bt::Bt;
 use crate::key::index::bu::Bu;
+use crate::key::index::gh::Gh;
+use crate::key::index::gn::Gn;
 use crate::key::index::hd::Hd;
 use crate::key::index::he::He;
 use crate::key::index::hi::Hi;
@@ -184,6 +187,29 @@ impl IndexKeyBase {
 		.into()
 	}
 
+	fn new_gh_key(&self, cell: &str, id: Id) -> Key {
+		Gh::new(
+			self.inner.ns.as_str(),
+			self.inner.db.as_str(),
+			self.inner.tb.as_str(),
+			self.inner.ix.as_str(),
+			cell,
+			id,
+		)
+		.into()
+	}
+
+	fn new_gn_key(&self, id: Id) -> Key {
+		Gn::new(
+			self.inner.ns.as_str(),
+			self.inner.db.as_str(),
+			self.inner.tb.as_str(),
+			self.inner.ix.as_str(),
+			id,
+		)
+		.into()
+	}
+
 	fn new_hd_key(&self, doc_id: Option<DocId>) -> Key {
 		Hd::new(
 			self.inner.ns.as_str(),
@@ -1,21 +1,21 @@
+use crate::cnf::{KNN_PREFILTER_BRUTEFORCE_LIMIT, NORMAL_FETCH_SIZE};
 use crate::ctx::Context;
-use crate::dbs::Options;
+use crate::dbs::{Iterable, Options};
 use crate::doc::CursorDoc;
 use crate::err::Error;
-use crate::idx::docids::DocIds;
-use crate::idx::ft::analyzer::{Analyzer, TermsList, TermsSet};
+use crate::idx::docids::{DocId, DocIds};
+use crate::idx::ft::analyzer::{Analyzer, TermsList};
 use crate::idx::ft::highlighter::HighlightParams;
+use crate::idx::ft::query::FtQuery;
 use crate::idx::ft::scorer::BM25Scorer;
-use crate::idx::ft::termdocs::TermsDocs;
-use crate::idx::ft::terms::Terms;
 use crate::idx::ft::{FtIndex, MatchRef};
 use crate::idx::planner::checker::{HnswConditionChecker, MTreeConditionChecker};
 use crate::idx::planner::iterators::{
-	IndexEqualThingIterator, IndexJoinThingIterator, IndexRangeThingIterator,
+	CollectorRecord, IndexEqualThingIterator, IndexJoinThingIterator, IndexRangeThingIterator,
 	IndexUnionThingIterator, IteratorRange, IteratorRecord, IteratorRef, KnnIterator,
-	KnnIteratorResult, MatchesThingIterator, MultipleIterators, ThingIterator,
-	UniqueEqualThingIterator, UniqueJoinThingIterator, UniqueRangeThingIterator,
-	UniqueUnionThingIterator, ValueType,
+	KnnIteratorResult, MatchesThingIterator, MultipleIterators, SpatialAreaThingIterator,
+	SpatialNearestThingIterator, ThingIterator, UniqueEqualThingIterator, UniqueJoinThingIterator,
+	UniqueRangeThingIterator, UniqueUnionThingIterator, ValueType,
 };
 use crate::idx::planner::knn::{KnnBruteForceResult, KnnPriorityList};
 use crate::idx::planner::plan::IndexOperator::Matches;
@@ -28,14 +28,18 @@ use crate::idx::IndexKeyBase;
 use crate::kvs::{Key, TransactionType};
 use crate::sql::index::{Distance, Index};
 use crate::sql::statements::DefineIndexStatement;
-use crate::sql::{Array, Cond, Expression, Idiom, Number, Object, Table, Thing, Value};
+use crate::sql::{
+	Array, Cond, Expression, Geometry, Idiom, Number, Object, Operator, Table, Thing, Value,
+};
 use num_traits::{FromPrimitive, ToPrimitive};
 use reblessive::tree::Stk;
+use roaring::RoaringTreemap;
 use rust_decimal::Decimal;
 use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{OnceCell, RwLock};
 
 pub(super) type KnnBruteForceEntry = (KnnPriorityList, Idiom, Arc<Vec<Number>>, Distance);
 
@@ -73,6 +77,7 @@ pub(super) struct InnerQueryExecutor {
 	mt_entries: HashMap<Arc<Expression>, MtEntry>,
 	hnsw_entries: HashMap<Arc<Expression>, HnswEntry>,
 	knn_bruteforce_entries: HashMap<Arc<Expression>, KnnBruteForceEntry>,
+	hybrid_lists: OnceCell<HybridLists>,
 }
 
 impl From<InnerQueryExecutor> for QueryExecutor {
@@ -112,6 +117,7 @@ impl InnerQueryExecutor {
 		knns: KnnExpressions,
 		kbtes: KnnBruteForceExpressions,
 		knn_condition: Option<Cond>,
+		all_and: bool,
 	) -> Result<Self, Error> {
 		let mut mr_entries = HashMap::default();
 		let mut exp_entries = HashMap::default();
@@ -123,6 +129,16 @@ impl InnerQueryExecutor {
 		let mut knn_bruteforce_entries = HashMap::with_capacity(knns.len());
 		let knn_condition = knn_condition.map(Arc::new);
 
+		// When the condition only contains AND relations, the records matching an
+		// expression backed by a B-tree index are a superset of the records matching
+		// the condition, which lets us estimate the selectivity of the condition
+		let prefilter = match (&knn_condition, all_and) {
+			(Some(_), true) => {
+				ios.iter().find(|(_, io)| HnswPrefilter::is_candidate(io)).map(|(_, io)| io.clone())
+			}
+			_ => None,
+		};
+
 		// Create all the instances of index entries.
 		// Map them to Idioms and MatchRef
 		for (exp, io) in ios {
@@ -148,7 +164,7 @@ impl InnerQueryExecutor {
 						}
 					};
 					if let Some(e) = ft_entry {
-						if let Matches(_, Some(mr)) = e.0.index_option.op() {
+						if let Matches(_, Some(mr), _) = e.0.index_option.op() {
 							if mr_entries.insert(*mr, e.clone()).is_some() {
 								return Err(Error::DuplicatedMatchRef {
 									mr: *mr,
@@ -162,8 +178,17 @@ impl InnerQueryExecutor {
 					if let IndexOperator::Knn(a, k) = io.op() {
 						let entry = match mt_map.entry(ixr.clone()) {
 							Entry::Occupied(e) => {
-								MtEntry::new(stk, ctx, opt, e.get(), a, *k, knn_condition.clone())
-									.await?
+								MtEntry::new(
+									stk,
+									ctx,
+									opt,
+									e.get(),
+									(&ixr.cols[0], &p.distance),
+									a,
+									*k,
+									knn_condition.clone(),
+								)
+								.await?
 							}
 							Entry::Vacant(e) => {
 								let ikb = IndexKeyBase::new(opt.ns()?, opt.db()?, e.key())?;
@@ -177,9 +202,17 @@ impl InnerQueryExecutor {
 								)
 								.await?;
 								drop(tx);
-								let entry =
-									MtEntry::new(stk, ctx, opt, &mt, a, *k, knn_condition.clone())
-										.await?;
+								let entry = MtEntry::new(
+									stk,
+									ctx,
+									opt,
+									&mt,
+									(&ixr.cols[0], &p.distance),
+									a,
+									*k,
+									knn_condition.clone(),
+								)
+								.await?;
 								e.insert(mt);
 								entry
 							}
@@ -189,41 +222,30 @@ impl InnerQueryExecutor {
 				}
 				Index::Hnsw(p) => {
 					if let IndexOperator::Ann(a, k, ef) = io.op() {
-						let entry = match hnsw_map.entry(ixr.clone()) {
-							Entry::Occupied(e) => {
-								HnswEntry::new(
-									stk,
-									ctx,
-									opt,
-									e.get().clone(),
-									a,
-									*k,
-									*ef,
-									knn_condition.clone(),
-								)
-								.await?
-							}
+						let hnsw = match hnsw_map.entry(ixr.clone()) {
+							Entry::Occupied(e) => e.get().clone(),
 							Entry::Vacant(e) => {
 								let hnsw =
 									ctx.get_index_stores().get_index_hnsw(ctx, opt, ixr, p).await?;
 								// Ensure the local HNSW index is up to date with the KVS
 								hnsw.write().await.check_state(&ctx.tx()).await?;
-								// Now we can execute the request
-								let entry = HnswEntry::new(
-									stk,
-									ctx,
-									opt,
-									hnsw.clone(),
-									a,
-									*k,
-									*ef,
-									knn_condition.clone(),
-								)
-								.await?;
-								e.insert(hnsw);
-								entry
+								e.insert(hnsw).clone()
 							}
 						};
+						// Now we can execute the request
+						let entry = HnswEntry::new(
+							stk,
+							ctx,
+							opt,
+							hnsw,
+							(&ixr.cols[0], &p.distance),
+							a,
+							*k,
+							*ef,
+							knn_condition.clone(),
+							prefilter.as_ref(),
+						)
+						.await?;
 						hnsw_entries.insert(exp, entry);
 					}
 				}
@@ -245,6 +267,7 @@ impl InnerQueryExecutor {
 			mt_entries,
 			hnsw_entries,
 			knn_bruteforce_entries,
+			hybrid_lists: OnceCell::new(),
 		})
 	}
 
@@ -302,6 +325,12 @@ impl QueryExecutor {
 
 	/// Returns `true` if the expression is matching the current iterator.
 	pub(crate) fn is_iterator_expression(&self, ir: IteratorRef, exp: &Expression) -> bool {
+		// Phrase and proximity queries still have to be checked against the record
+		if let Some(ft) = self.0.exp_entries.get(exp) {
+			if ft.0.query.is_positional() {
+				return false;
+			}
+		}
 		match self.0.it_entries.get(ir) {
 			Some(IteratorEntry::Single(Some(e), ..)) => exp.eq(e.as_ref()),
 			Some(IteratorEntry::Range(es, ..)) => es.contains(exp),
@@ -311,7 +340,16 @@ impl QueryExecutor {
 
 	pub(crate) fn explain(&self, ir: IteratorRef) -> Value {
 		match self.0.it_entries.get(ir) {
-			Some(ie) => ie.explain(),
+			Some(ie) => {
+				let mut e = ie.explain();
+				if let IteratorEntry::Single(Some(exp), _) = ie {
+					let prefilter = self.0.hnsw_entries.get(exp).and_then(|h| h.prefilter.clone());
+					if let (Value::Object(o), Some(p)) = (&mut e, prefilter) {
+						o.insert("prefilter".to_string(), p);
+					}
+				}
+				e
+			}
 			None => Value::None,
 		}
 	}
@@ -357,6 +395,9 @@ impl QueryExecutor {
 			} => self.new_search_index_iterator(irf, io.clone()).await,
 			Index::MTree(_) => Ok(self.new_mtree_index_knn_iterator(irf)),
 			Index::Hnsw(_) => Ok(self.new_hnsw_index_ann_iterator(irf)),
+			Index::Spatial(ref p) => {
+				Ok(Self::new_spatial_index_iterator(opt, irf, ixr, io, p.precision)?)
+			}
 		}
 	}
 
@@ -385,6 +426,9 @@ impl QueryExecutor {
 					Box::new(IndexJoinThingIterator::new(ir, opt, ix.clone(), iterators)?);
 				Some(ThingIterator::IndexJoin(index_join))
 			}
+			IndexOperator::Range(prefix, from, to) => {
+				Some(Self::new_compound_range_iterator(ir, opt, ix, prefix, from, to)?)
+			}
 			IndexOperator::Order => Some(ThingIterator::IndexRange(
 				IndexRangeThingIterator::full_range(ir, opt.ns()?, opt.db()?, ix),
 			)),
@@ -743,6 +787,39 @@ impl QueryExecutor {
 		}
 	}
 
+	/// Composite unique and non-unique indexes share the same key layout,
+	/// so the range can be scanned the same way for both.
+	fn new_compound_range_iterator(
+		ir: IteratorRef,
+		opt: &Options,
+		ix: &DefineIndexStatement,
+		prefix: &[Arc<Value>],
+		from: &RangeValue,
+		to: &RangeValue,
+	) -> Result<ThingIterator, Error> {
+		let ranges = Self::get_ranges_variants(from, to)
+			.unwrap_or_else(|| vec![IteratorRange::new_ref(ValueType::None, from, to)]);
+		let mut iterators = VecDeque::new();
+		for prefix in Self::get_equal_variants(prefix) {
+			for range in &ranges {
+				iterators.push_back(ThingIterator::IndexRange(IndexRangeThingIterator::compound(
+					ir,
+					opt.ns()?,
+					opt.db()?,
+					ix,
+					&prefix,
+					range,
+				)));
+			}
+		}
+		if iterators.len() == 1 {
+			if let Some(it) = iterators.pop_front() {
+				return Ok(it);
+			}
+		}
+		Ok(ThingIterator::Multiples(Box::new(MultipleIterators::new(iterators))))
+	}
+
 	fn new_index_range_iterator(
 		ir: IteratorRef,
 		opt: &Options,
@@ -825,6 +902,9 @@ impl QueryExecutor {
 					Box::new(UniqueJoinThingIterator::new(irf, opt, ixr.clone(), iterators)?);
 				Some(ThingIterator::UniqueJoin(unique_join))
 			}
+			IndexOperator::Range(prefix, from, to) => {
+				Some(Self::new_compound_range_iterator(irf, opt, ixr, prefix, from, to)?)
+			}
 			IndexOperator::Order => Some(ThingIterator::UniqueRange(
 				UniqueRangeThingIterator::full_range(irf, opt.ns()?, opt.db()?, ixr),
 			)),
@@ -860,6 +940,33 @@ impl QueryExecutor {
 		}
 	}
 
+	/// Creates an iterator over the records matching an expression backed by a B-tree index
+	fn new_prefilter_iterator(
+		opt: &Options,
+		io: &IndexOption,
+	) -> Result<Option<ThingIterator>, Error> {
+		let ixr = io.ix_ref();
+		let unique = matches!(ixr.index, Index::Uniq);
+		Ok(match io.op() {
+			IndexOperator::Equality(values) => {
+				let arrays = Self::get_equal_variants(values);
+				Some(match (unique, arrays.len()) {
+					(true, 1) => Self::new_unique_equal_iterator(0, opt, ixr, &arrays[0])?,
+					(true, _) => Self::new_multiple_unique_equal_iterators(0, opt, ixr, arrays)?,
+					(false, 1) => Self::new_index_equal_iterator(0, opt, ixr, &arrays[0])?,
+					(false, _) => Self::new_multiple_index_equal_iterators(0, opt, ixr, arrays)?,
+				})
+			}
+			IndexOperator::Union(value) if unique => {
+				Some(ThingIterator::UniqueUnion(UniqueUnionThingIterator::new(0, opt, ixr, value)?))
+			}
+			IndexOperator::Union(value) => Some(ThingIterator::IndexUnion(
+				IndexUnionThingIterator::new(0, opt.ns()?, opt.db()?, ixr, value),
+			)),
+			_ => None,
+		})
+	}
+
 	fn new_multiple_unique_equal_iterators(
 		irf: IteratorRef,
 		opt: &Options,
@@ -879,11 +986,10 @@ impl QueryExecutor {
 		io: IndexOption,
 	) -> Result<Option<ThingIterator>, Error> {
 		if let Some(IteratorEntry::Single(Some(exp), ..)) = self.0.it_entries.get(ir) {
-			if let Matches(_, _) = io.op() {
+			if let Matches(..) = io.op() {
 				if let Some(fti) = self.0.ft_map.get(io.ix_ref()) {
 					if let Some(fte) = self.0.exp_entries.get(exp) {
-						let it =
-							MatchesThingIterator::new(ir, fti, fte.0.terms_docs.clone()).await?;
+						let it = MatchesThingIterator::new(ir, fti, fte.0.hits.clone());
 						return Ok(Some(ThingIterator::Matches(it)));
 					}
 				}
@@ -892,6 +998,34 @@ impl QueryExecutor {
 		Ok(None)
 	}
 
+	fn new_spatial_index_iterator(
+		opt: &Options,
+		irf: IteratorRef,
+		ix: &DefineIndexStatement,
+		io: &IndexOption,
+		precision: u8,
+	) -> Result<Option<ThingIterator>, Error> {
+		let (ns, db) = (opt.ns()?, opt.db()?);
+		Ok(match io.op() {
+			IndexOperator::Spatial(Operator::Outside, _) => {
+				Some(ThingIterator::SpatialArea(SpatialAreaThingIterator::all(irf, ns, db, ix)))
+			}
+			IndexOperator::Spatial(_, v) => match v.as_ref() {
+				Value::Geometry(g) => Some(ThingIterator::SpatialArea(
+					SpatialAreaThingIterator::new(irf, ns, db, ix, g, precision),
+				)),
+				_ => None,
+			},
+			IndexOperator::Nearest(v) => match v.as_ref() {
+				Value::Geometry(Geometry::Point(p)) => Some(ThingIterator::SpatialNearest(
+					Box::new(SpatialNearestThingIterator::new(irf, ns, db, ix, *p, precision)),
+				)),
+				_ => None,
+			},
+			_ => None,
+		})
+	}
+
 	fn new_mtree_index_knn_iterator(&self, ir: IteratorRef) -> Option<ThingIterator> {
 		if let Some(IteratorEntry::Single(Some(exp), ..)) = self.0.it_entries.get(ir) {
 			if let Some(mte) = self.0.mt_entries.get(exp) {
@@ -941,7 +1075,7 @@ impl QueryExecutor {
 		if let Some(ft) = self.0.exp_entries.get(exp) {
 			let ix = ft.0.index_option.ix_ref();
 			if self.0.table.eq(&ix.what.0) {
-				return self.matches_with_doc_id(ctx, thg, ft).await;
+				return self.matches_with_doc_id(stk, ctx, opt, thg, ft, l, r).await;
 			}
 			return self.matches_with_value(stk, ctx, opt, ft, l, r).await;
 		}
@@ -952,32 +1086,30 @@ impl QueryExecutor {
 		})
 	}
 
+	#[allow(clippy::too_many_arguments)]
 	async fn matches_with_doc_id(
 		&self,
+		stk: &mut Stk,
 		ctx: &Context,
+		opt: &Options,
 		thg: &Thing,
 		ft: &FtEntry,
+		l: Value,
+		r: Value,
 	) -> Result<bool, Error> {
 		let doc_key: Key = thg.into();
 		let tx = ctx.tx();
 		let di = ft.0.doc_ids.read().await;
 		let doc_id = di.get_doc_id(&tx, doc_key).await?;
 		drop(di);
+		drop(tx);
 		if let Some(doc_id) = doc_id {
-			let term_goals = ft.0.terms_docs.len();
-			// If there is no terms, it can't be a match
-			if term_goals == 0 {
+			if !ft.0.hits.contains(doc_id) {
 				return Ok(false);
 			}
-			for opt_td in ft.0.terms_docs.iter() {
-				if let Some((_, docs)) = opt_td {
-					if !docs.contains(doc_id) {
-						return Ok(false);
-					}
-				} else {
-					// If one of the term is missing, it can't be a match
-					return Ok(false);
-				}
+			// The hits of a positional query are only candidates
+			if ft.0.query.is_positional() {
+				return self.matches_with_value(stk, ctx, opt, ft, l, r).await;
 			}
 			return Ok(true);
 		}
@@ -993,22 +1125,15 @@ impl QueryExecutor {
 		l: Value,
 		r: Value,
 	) -> Result<bool, Error> {
-		// If the query terms contains terms that are unknown in the index
-		// of if there are no terms in the query
-		// we are sure that it does not match any document
-		if !ft.0.query_terms_set.is_matchable() {
-			return Ok(false);
-		}
+		// The value of the indexed field is on the same side as the idiom
 		let v = match ft.0.index_option.id_pos() {
-			IdiomPosition::Left => r,
-			IdiomPosition::Right => l,
+			IdiomPosition::Left => l,
+			IdiomPosition::Right => r,
 			IdiomPosition::None => return Ok(false),
 		};
-		let terms = ft.0.terms.read().await;
-		// Extract the terms set from the record
-		let t = ft.0.analyzer.extract_indexing_terms(stk, ctx, opt, &terms, v).await?;
-		drop(terms);
-		Ok(ft.0.query_terms_set.is_subset(&t))
+		// Extract the term positions from the record
+		let positions = ft.0.analyzer.extract_positions(stk, ctx, opt, v).await?;
+		Ok(ft.0.query.matches(&positions))
 	}
 
 	fn get_ft_entry(&self, match_ref: &Value) -> Option<&FtEntry> {
@@ -1091,6 +1216,160 @@ impl QueryExecutor {
 		}
 		Ok(Value::None)
 	}
+
+	/// Fuses the ranks of the record in the full-text and the nearest neighbours
+	/// results of the query, using reciprocal rank fusion
+	pub(crate) async fn rrf(&self, ctx: &Context, rid: &Thing, k: f64) -> Result<Value, Error> {
+		if let Some((text, vector)) = self.hybrid_ranks(ctx, rid).await? {
+			let score: f64 =
+				text.iter().chain(&vector).map(|(rank, _)| 1.0 / (k + *rank as f64)).sum();
+			return Ok(Value::from(score));
+		}
+		Ok(Value::None)
+	}
+
+	/// Fuses the normalised scores of the record in the full-text and the nearest
+	/// neighbours results of the query, using a weighted linear combination
+	pub(crate) async fn linear(
+		&self,
+		ctx: &Context,
+		rid: &Thing,
+		text_weight: f64,
+		vector_weight: f64,
+	) -> Result<Value, Error> {
+		if let Some((text, vector)) = self.hybrid_ranks(ctx, rid).await? {
+			let text: f64 = text.iter().map(|(_, s)| s).sum();
+			let vector: f64 = vector.iter().map(|(_, s)| s).sum();
+			return Ok(Value::from(text_weight * text + vector_weight * vector));
+		}
+		Ok(Value::None)
+	}
+
+	/// Returns the rank and the normalised score of the record in each full-text
+	/// and each nearest neighbours result it belongs to.
+	/// Returns `None` if the query has no such operator, or while the brute force
+	/// nearest neighbours are still being collected.
+	async fn hybrid_ranks(
+		&self,
+		ctx: &Context,
+		rid: &Thing,
+	) -> Result<Option<(Vec<(usize, f64)>, Vec<(usize, f64)>)>, Error> {
+		// The brute force nearest neighbours are only known once they have been collected
+		if self.has_bruteforce_knn()
+			&& !matches!(ctx.get_iteration_stage(), Some(IterationStage::Iterate(Some(_))))
+		{
+			return Ok(None);
+		}
+		let lists = self.hybrid_lists(ctx).await?;
+		if lists.text.is_empty() && lists.vector.is_empty() {
+			return Ok(None);
+		}
+		let mut text = Vec::with_capacity(lists.text.len());
+		if !lists.text.is_empty() {
+			let tx = ctx.tx();
+			for (doc_ids, list) in &lists.text {
+				let key: Key = rid.into();
+				let doc_id = doc_ids.read().await.get_doc_id(&tx, key).await?;
+				if let Some(r) = doc_id.and_then(|d| list.get(&d)) {
+					text.push(r);
+				}
+			}
+		}
+		let vector = lists.vector.iter().filter_map(|list| list.get(rid)).collect();
+		Ok(Some((text, vector)))
+	}
+
+	/// Ranks, once per query, the records matched by the full-text operators
+	/// scored with BM25, and the records found by the nearest neighbours operators
+	async fn hybrid_lists(&self, ctx: &Context) -> Result<&HybridLists, Error> {
+		self.0
+			.hybrid_lists
+			.get_or_try_init(|| async {
+				let mut lists = HybridLists::default();
+				let tx = ctx.tx();
+				for ft in self.0.exp_entries.values() {
+					if let Some(scorer) = &ft.0.scorer {
+						let mut scores = Vec::with_capacity(ft.0.hits.len() as usize);
+						for doc_id in ft.0.hits.iter() {
+							if let Some(score) = scorer.score(&tx, doc_id).await? {
+								scores.push((doc_id, score as f64));
+							}
+						}
+						lists.text.push((ft.0.doc_ids.clone(), RankedList::new(scores)));
+					}
+				}
+				let knn_results = self
+					.0
+					.hnsw_entries
+					.values()
+					.map(|e| &e.res)
+					.chain(self.0.mt_entries.values().map(|e| &e.res));
+				for res in knn_results {
+					// The closest neighbours are the most relevant
+					let scores =
+						res.iter().map(|(rid, dist, _)| (rid.as_ref().clone(), -dist)).collect();
+					lists.vector.push(RankedList::new(scores));
+				}
+				if let Some(IterationStage::Iterate(Some(results))) = ctx.get_iteration_stage() {
+					for res in results.table_results(&self.0.table) {
+						let scores = res
+							.iter()
+							.map(|(rid, dist)| (rid.as_ref().clone(), -dist.to_float()))
+							.collect();
+						lists.vector.push(RankedList::new(scores));
+					}
+				}
+				Ok(lists)
+			})
+			.await
+	}
+}
+
+/// The ranked results of the full-text and nearest neighbours operators of a query,
+/// fused by the hybrid search functions
+#[derive(Default)]
+struct HybridLists {
+	text: Vec<(Arc<RwLock<DocIds>>, RankedList<DocId>)>,
+	vector: Vec<RankedList<Thing>>,
+}
+
+/// The normalised score of the lowest ranked record of a result, so that it
+/// still scores higher than a record which is not part of the result
+const MIN_NORMALISED_SCORE: f64 = 0.01;
+
+/// The rank (starting at 1) and the min-max normalised score of each record of a result,
+/// between `MIN_NORMALISED_SCORE` and 1
+struct RankedList<K>(HashMap<K, (usize, f64)>);
+
+impl<K> RankedList<K>
+where
+	K: Eq + Hash,
+{
+	/// Builds the list from the records and their scores, the higher the better
+	fn new(mut scores: Vec<(K, f64)>) -> Self {
+		scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+		let (min, max) =
+			scores.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), (_, s)| {
+				(min.min(*s), max.max(*s))
+			});
+		let ranks = scores
+			.into_iter()
+			.enumerate()
+			.map(|(i, (k, s))| {
+				let norm = if max > min {
+					MIN_NORMALISED_SCORE + (1.0 - MIN_NORMALISED_SCORE) * (s - min) / (max - min)
+				} else {
+					1.0
+				};
+				(k, (i + 1, norm))
+			})
+			.collect();
+		Self(ranks)
+	}
+
+	fn get(&self, k: &K) -> Option<(usize, f64)> {
+		self.0.get(k).copied()
+	}
 }
 
 #[derive(Clone)]
@@ -1100,10 +1379,9 @@ struct Inner {
 	index_option: IndexOption,
 	doc_ids: Arc<RwLock<DocIds>>,
 	analyzer: Analyzer,
-	query_terms_set: TermsSet,
+	query: FtQuery,
 	query_terms_list: TermsList,
-	terms: Arc<RwLock<Terms>>,
-	terms_docs: TermsDocs,
+	hits: RoaringTreemap,
 	scorer: Option<BM25Scorer>,
 }
 
@@ -1115,21 +1393,21 @@ impl FtEntry {
 		ft: &FtIndex,
 		io: IndexOption,
 	) -> Result<Option<Self>, Error> {
-		if let Matches(qs, _) = io.op() {
-			let (terms_list, terms_set) =
-				ft.extract_querying_terms(stk, ctx, opt, qs.to_owned()).await?;
+		if let Matches(qs, _, fuzzy) = io.op() {
+			let query = ft.extract_query(stk, ctx, opt, qs.to_owned(), *fuzzy).await?;
 			let tx = ctx.tx();
-			let terms_docs = Arc::new(ft.get_terms_docs(&tx, &terms_list).await?);
+			let terms_docs = Arc::new(ft.get_terms_docs(&tx, &query).await?);
+			let hits = ft.get_hits(&tx, &query, &terms_docs).await?;
 			drop(tx);
+			let query_terms_list = query.terms().into_iter().map(|t| t.id).collect();
 			Ok(Some(Self(Arc::new(Inner {
 				index_option: io,
 				doc_ids: ft.doc_ids(),
 				analyzer: ft.analyzer(),
-				query_terms_set: terms_set,
-				query_terms_list: terms_list,
-				scorer: ft.new_scorer(terms_docs.clone())?,
-				terms: ft.terms(),
-				terms_docs,
+				query,
+				query_terms_list,
+				hits,
+				scorer: ft.new_scorer(terms_docs)?,
 			}))))
 		} else {
 			Ok(None)
@@ -1143,11 +1421,13 @@ pub(super) struct MtEntry {
 }
 
 impl MtEntry {
+	#[allow(clippy::too_many_arguments)]
 	async fn new(
 		stk: &mut Stk,
 		ctx: &Context,
 		opt: &Options,
 		mt: &MTreeIndex,
+		(id, dist): (&Idiom, &Distance),
 		o: &[Number],
 		k: u32,
 		cond: Option<Arc<Cond>>,
@@ -1157,7 +1437,10 @@ impl MtEntry {
 		} else {
 			MTreeConditionChecker::new(ctx)
 		};
-		let res = mt.knn_search(stk, ctx, o, k as usize, cond_checker).await?;
+		let mut res = mt.knn_search(stk, ctx, o, k as usize, cond_checker).await?;
+		if mt.is_quantized() {
+			res = rescore_knn_search(stk, ctx, opt, res, (id, dist), o, k).await?;
+		}
 		Ok(Self {
 			res,
 		})
@@ -1167,6 +1450,8 @@ impl MtEntry {
 #[derive(Clone)]
 pub(super) struct HnswEntry {
 	res: VecDeque<KnnIteratorResult>,
+	/// How the condition narrowed down the search, if it did
+	prefilter: Option<Value>,
 }
 
 impl HnswEntry {
@@ -1176,23 +1461,189 @@ impl HnswEntry {
 		ctx: &Context,
 		opt: &Options,
 		h: SharedHnswIndex,
+		(id, dist): (&Idiom, &Distance),
 		v: &[Number],
 		n: u32,
 		ef: u32,
 		cond: Option<Arc<Cond>>,
+		prefilter: Option<&IndexOption>,
 	) -> Result<Self, Error> {
+		let mut ef = ef;
+		let mut explain = None;
+		if let (Some(cond), Some(io)) = (&cond, prefilter) {
+			let total = h.read().await.docs_count() as usize;
+			if let Some(pf) = HnswPrefilter::scan(ctx, opt, io, total).await? {
+				let mut e = HashMap::default();
+				e.insert("index", Value::from(io.ix_ref().name.0.to_owned()));
+				if let Some(rids) = pf.rids {
+					// The filtered set is small enough to compute the exact nearest neighbours
+					let res = exact_knn_search(stk, ctx, opt, rids, (id, dist), v, n, cond).await?;
+					e.insert("strategy", Value::from("exact"));
+					return Ok(Self {
+						res,
+						prefilter: Some(Value::from(Object::from(e))),
+					});
+				}
+				// Otherwise we expand the search, in inverse proportion of the selectivity,
+				// so it can find enough records matching the condition
+				if let Some(expanded) = (ef as usize).saturating_mul(total).checked_div(pf.count) {
+					ef = expanded.clamp(ef as usize, total.max(ef as usize)) as u32;
+				}
+				e.insert("strategy", Value::from("ann"));
+				e.insert("ef", Value::from(ef));
+				explain = Some(Value::from(Object::from(e)));
+			}
+		}
 		let cond_checker = if let Some(cond) = cond {
 			HnswConditionChecker::new_cond(ctx, opt, cond)
 		} else {
 			HnswConditionChecker::new()
 		};
-		let res = h
-			.read()
-			.await
-			.knn_search(&ctx.tx(), stk, v, n as usize, ef as usize, cond_checker)
-			.await?;
+		let h = h.read().await;
+		let mut res =
+			h.knn_search(&ctx.tx(), stk, v, n as usize, ef as usize, cond_checker).await?;
+		if h.is_quantized() {
+			res = rescore_knn_search(stk, ctx, opt, res, (id, dist), v, n).await?;
+		}
 		Ok(Self {
 			res,
+			prefilter: explain,
 		})
 	}
 }
+
+/// The records matching an expression of the condition of a filtered approximate
+/// nearest neighbour search, collected from the B-tree index backing the expression
+struct HnswPrefilter {
+	/// The number of records (may stop short for large sets)
+	count: usize,
+	/// The records, if they are few enough to be checked one by one
+	rids: Option<Vec<Arc<Thing>>>,
+}
+
+impl HnswPrefilter {
+	fn is_candidate(io: &IndexOption) -> bool {
+		matches!(io.ix_ref().index, Index::Idx | Index::Uniq)
+			&& matches!(io.op(), IndexOperator::Equality(_) | IndexOperator::Union(_))
+	}
+
+	async fn scan(
+		ctx: &Context,
+		opt: &Options,
+		io: &IndexOption,
+		total: usize,
+	) -> Result<Option<Self>, Error> {
+		let Some(mut it) = QueryExecutor::new_prefilter_iterator(opt, io)? else {
+			return Ok(None);
+		};
+		let limit = *KNN_PREFILTER_BRUTEFORCE_LIMIT;
+		let txn = ctx.tx();
+		let mut rids = HashSet::new();
+		let mut count = 0;
+		// Past the brute force limit, we only count the records to estimate the selectivity,
+		// and stop once they are a tenth of the index, where the selectivity hardly matters
+		while count <= limit || count < total / 10 {
+			if ctx.is_done() {
+				break;
+			}
+			let batch: Vec<CollectorRecord> = it.next_batch(ctx, &txn, *NORMAL_FETCH_SIZE).await?;
+			if batch.is_empty() {
+				break;
+			}
+			for (rid, _, _) in batch {
+				if count <= limit {
+					if rids.insert(rid) {
+						count += 1;
+					}
+				} else {
+					count += 1;
+				}
+			}
+		}
+		Ok(Some(Self {
+			count,
+			rids: (count <= limit).then(|| rids.into_iter().collect()),
+		}))
+	}
+}
+
+/// Computes the exact nearest neighbours among the given records matching the condition
+#[allow(clippy::too_many_arguments)]
+async fn exact_knn_search(
+	stk: &mut Stk,
+	ctx: &Context,
+	opt: &Options,
+	rids: Vec<Arc<Thing>>,
+	(id, dist): (&Idiom, &Distance),
+	pt: &[Number],
+	n: u32,
+	cond: &Cond,
+) -> Result<VecDeque<KnnIteratorResult>, Error> {
+	let pt = pt.to_vec();
+	let txn = ctx.tx();
+	let mut res = Vec::with_capacity(rids.len());
+	for rid in rids {
+		let val = Iterable::fetch_thing(&txn, opt, &rid).await?;
+		if val.is_none_or_null() {
+			continue;
+		}
+		let mut doc = CursorDoc {
+			rid: Some(rid.clone()),
+			ir: None,
+			doc: val.into(),
+		};
+		if !cond.compute(stk, ctx, opt, Some(&doc)).await?.is_truthy() {
+			continue;
+		}
+		let Ok(v) = id.compute(stk, ctx, opt, Some(&doc)).await?.try_into() else {
+			continue;
+		};
+		if let Ok(d) = dist.compute(&v, &pt) {
+			res.push((rid, d.to_float(), Some(doc.doc.as_arc())));
+		}
+	}
+	res.sort_by(|a, b| a.1.total_cmp(&b.1));
+	res.truncate(n as usize);
+	Ok(res.into())
+}
+
+/// Rescores the candidates found by a quantized index, with the full precision
+/// vectors of the records, and keeps the nearest ones
+#[allow(clippy::too_many_arguments)]
+async fn rescore_knn_search(
+	stk: &mut Stk,
+	ctx: &Context,
+	opt: &Options,
+	candidates: VecDeque<KnnIteratorResult>,
+	(id, dist): (&Idiom, &Distance),
+	pt: &[Number],
+	n: u32,
+) -> Result<VecDeque<KnnIteratorResult>, Error> {
+	let pt = pt.to_vec();
+	let txn = ctx.tx();
+	let mut res = Vec::with_capacity(candidates.len());
+	for (rid, _, val) in candidates {
+		// The condition checker may already have fetched the record
+		let val = match val {
+			Some(val) => val,
+			None => Iterable::fetch_thing(&txn, opt, &rid).await?,
+		};
+		if val.is_none_or_null() {
+			continue;
+		}
+		let mut doc = CursorDoc {
+			rid: Some(rid.clone()),
+			ir: None,
+			doc: val.into(),
+		};
+		let Ok(v) = id.compute(stk, ctx, opt, Some(&doc)).await?.try_into() else {
+			continue;
+		};
+		if let Ok(d) = dist.compute(&v, &pt) {
+			res.push((rid, d.to_float(), Some(doc.doc.as_arc())));
+		}
+	}
+	res.sort_by(|a, b| a.1.total_cmp(&b.1));
+	res.truncate(n as usize);
+	Ok(res.into())
+}
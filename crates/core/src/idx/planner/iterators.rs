@@ -1,20 +1,27 @@
+use crate::cnf::NORMAL_FETCH_SIZE;
 use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::err::Error;
 use crate::idx::docids::DocId;
-use crate::idx::ft::termdocs::TermsDocs;
+
 use crate::idx::ft::{FtIndex, HitsIterator};
 use crate::idx::planner::plan::RangeValue;
 use crate::idx::planner::tree::IndexReference;
+use crate::idx::spatial::{self, Area, MAX_QUERY_CELLS};
+use crate::key::index::gh::{self, Gh};
+use crate::key::index::gn::{self, Gn};
 use crate::key::index::Index;
 use crate::kvs::Key;
 use crate::kvs::Transaction;
 use crate::sql::statements::DefineIndexStatement;
-use crate::sql::{Array, Ident, Number, Thing, Value};
+use crate::sql::{Array, Geometry, Id, Ident, Number, Thing, Value};
+use geo::{HaversineDistance, Point};
 use radix_trie::Trie;
+use roaring::RoaringTreemap;
 use rust_decimal::Decimal;
 use std::borrow::Cow;
-use std::collections::VecDeque;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeSet, BinaryHeap, HashSet, VecDeque};
 use std::sync::Arc;
 
 pub(crate) type IteratorRef = usize;
@@ -116,6 +123,8 @@ pub(crate) enum ThingIterator {
 	UniqueJoin(Box<UniqueJoinThingIterator>),
 	Matches(MatchesThingIterator),
 	Knn(KnnIterator),
+	SpatialArea(SpatialAreaThingIterator),
+	SpatialNearest(Box<SpatialNearestThingIterator>),
 	Multiples(Box<MultipleIterators>),
 }
 
@@ -135,6 +144,8 @@ impl ThingIterator {
 			Self::UniqueUnion(i) => i.next_batch(ctx, txn, size).await,
 			Self::Matches(i) => i.next_batch(ctx, txn, size).await,
 			Self::Knn(i) => i.next_batch(ctx, size).await,
+			Self::SpatialArea(i) => i.next_batch(ctx, txn, size).await,
+			Self::SpatialNearest(i) => i.next_batch(ctx, txn, size).await,
 			Self::IndexJoin(i) => Box::pin(i.next_batch(ctx, txn, size)).await,
 			Self::UniqueJoin(i) => Box::pin(i.next_batch(ctx, txn, size)).await,
 			Self::Multiples(i) => Box::pin(i.next_batch(ctx, txn, size)).await,
@@ -284,6 +295,24 @@ pub(super) enum ValueType {
 }
 
 impl ValueType {
+	fn min_value(&self) -> Option<Value> {
+		match self {
+			Self::None => None,
+			Self::NumberInt => Some(Number::Int(i64::MIN).into()),
+			Self::NumberFloat => Some(Number::Float(f64::MIN).into()),
+			Self::NumberDecimal => Some(Number::Decimal(Decimal::MIN).into()),
+		}
+	}
+
+	fn max_value(&self) -> Option<Value> {
+		match self {
+			Self::None => None,
+			Self::NumberInt => Some(Number::Int(i64::MAX).into()),
+			Self::NumberFloat => Some(Number::Float(f64::MAX).into()),
+			Self::NumberDecimal => Some(Number::Decimal(Decimal::MAX).into()),
+		}
+	}
+
 	fn prefix_beg(&self, ns: &str, db: &str, ix_what: &Ident, ix_name: &Ident) -> Vec<u8> {
 		match self {
 			Self::None => Index::prefix_beg(ns, db, ix_what, ix_name),
@@ -378,6 +407,53 @@ impl IndexRangeThingIterator {
 		Self::new(irf, ns, db, ix, &range)
 	}
 
+	/// Iterates over the records of a compound index
+	/// whose leading columns are equal to the prefix,
+	/// and whose next column is within the range.
+	pub(super) fn compound(
+		irf: IteratorRef,
+		ns: &str,
+		db: &str,
+		ix: &DefineIndexStatement,
+		prefix: &Array,
+		range: &IteratorRange<'_>,
+	) -> Self {
+		let (ix_what, ix_name) = (&ix.what, &ix.name);
+		let mut fd = prefix.clone();
+		let beg = if !range.from.value.is_none() {
+			fd.push(range.from.value.clone());
+			if range.from.inclusive {
+				Index::prefix_ids_composite_beg(ns, db, ix_what, ix_name, &fd)
+			} else {
+				Index::prefix_ids_composite_end(ns, db, ix_what, ix_name, &fd)
+			}
+		} else if let Some(v) = range.value_type.min_value() {
+			fd.push(v);
+			Index::prefix_ids_composite_beg(ns, db, ix_what, ix_name, &fd)
+		} else {
+			Index::prefix_ids_composite_beg(ns, db, ix_what, ix_name, prefix)
+		};
+		let mut fd = prefix.clone();
+		let end = if !range.to.value.is_none() {
+			fd.push(range.to.value.clone());
+			if range.to.inclusive {
+				Index::prefix_ids_composite_end(ns, db, ix_what, ix_name, &fd)
+			} else {
+				Index::prefix_ids_composite_beg(ns, db, ix_what, ix_name, &fd)
+			}
+		} else if let Some(v) = range.value_type.max_value() {
+			fd.push(v);
+			Index::prefix_ids_composite_end(ns, db, ix_what, ix_name, &fd)
+		} else {
+			Index::prefix_ids_composite_end(ns, db, ix_what, ix_name, prefix)
+		};
+		// The boundaries never match an index key, as every key is followed by the record id
+		Self {
+			irf,
+			r: RangeScan::new(beg, true, end, true),
+		}
+	}
+
 	fn compute_beg(
 		ns: &str,
 		db: &str,
@@ -852,22 +928,18 @@ pub(crate) struct MatchesThingIterator {
 }
 
 impl MatchesThingIterator {
-	pub(super) async fn new(
-		irf: IteratorRef,
-		fti: &FtIndex,
-		terms_docs: TermsDocs,
-	) -> Result<Self, Error> {
-		let hits = fti.new_hits_iterator(terms_docs)?;
+	pub(super) fn new(irf: IteratorRef, fti: &FtIndex, hits: RoaringTreemap) -> Self {
+		let hits = fti.new_hits_iterator(hits);
 		let hits_left = if let Some(h) = &hits {
 			h.len()
 		} else {
 			0
 		};
-		Ok(Self {
+		Self {
 			irf,
 			hits,
 			hits_left,
-		})
+		}
 	}
 
 	async fn next_batch<B: IteratorBatch>(
@@ -974,3 +1046,317 @@ impl MultipleIterators {
 		}
 	}
 }
+
+struct SpatialScan {
+	beg: Vec<u8>,
+	end: Vec<u8>,
+	/// Is this a scan of the cell entries, or of the non point entries
+	cells: bool,
+}
+
+impl SpatialScan {
+	fn cells(beg: Vec<u8>, end: Vec<u8>) -> Self {
+		Self {
+			beg,
+			end,
+			cells: true,
+		}
+	}
+
+	fn non_points(beg: Vec<u8>, end: Vec<u8>) -> Self {
+		Self {
+			beg,
+			end,
+			cells: false,
+		}
+	}
+
+	fn decode_id(&self, k: &Key) -> Result<Id, Error> {
+		Ok(if self.cells {
+			Gh::decode(k)?.id
+		} else {
+			Gn::decode(k)?.id
+		})
+	}
+}
+
+/// Iterates over the records stored in a set of geohash cells, returning each record once
+pub(crate) struct SpatialAreaThingIterator {
+	irf: IteratorRef,
+	tb: String,
+	scans: VecDeque<SpatialScan>,
+	distinct: HashSet<Id>,
+}
+
+impl SpatialAreaThingIterator {
+	/// Iterates over the records which may intersect the bounding box of the geometry
+	pub(super) fn new(
+		irf: IteratorRef,
+		ns: &str,
+		db: &str,
+		ix: &DefineIndexStatement,
+		g: &Geometry,
+		precision: u8,
+	) -> Self {
+		let cells = match Area::of(g) {
+			Some(a) => a.covering(precision, MAX_QUERY_CELLS),
+			None => vec![],
+		};
+		// The records stored in the parent cells of the covering cells
+		let mut parents = BTreeSet::new();
+		for cell in cells.iter() {
+			for l in 1..cell.len() {
+				parents.insert(&cell[0..l]);
+			}
+		}
+		let mut scans = VecDeque::with_capacity(parents.len() + cells.len());
+		for cell in parents {
+			let beg = gh::prefix_cell(ns, db, &ix.what, &ix.name, cell);
+			let end = gh::suffix_cell(ns, db, &ix.what, &ix.name, cell);
+			scans.push_back(SpatialScan::cells(beg, end));
+		}
+		// The records stored in the covering cells or in their child cells
+		for cell in cells.iter() {
+			let beg = gh::prefix_area(ns, db, &ix.what, &ix.name, cell);
+			let end = gh::suffix_area(ns, db, &ix.what, &ix.name, cell);
+			scans.push_back(SpatialScan::cells(beg, end));
+		}
+		Self {
+			irf,
+			tb: ix.what.to_raw(),
+			scans,
+			distinct: Default::default(),
+		}
+	}
+
+	/// Iterates over every record of the index
+	pub(super) fn all(irf: IteratorRef, ns: &str, db: &str, ix: &DefineIndexStatement) -> Self {
+		let scans = VecDeque::from([
+			SpatialScan::non_points(
+				gn::prefix(ns, db, &ix.what, &ix.name),
+				gn::suffix(ns, db, &ix.what, &ix.name),
+			),
+			SpatialScan::cells(
+				gh::prefix(ns, db, &ix.what, &ix.name),
+				gh::suffix(ns, db, &ix.what, &ix.name),
+			),
+		]);
+		Self {
+			irf,
+			tb: ix.what.to_raw(),
+			scans,
+			distinct: Default::default(),
+		}
+	}
+
+	async fn next_batch<B: IteratorBatch>(
+		&mut self,
+		ctx: &Context,
+		tx: &Transaction,
+		limit: u32,
+	) -> Result<B, Error> {
+		while let Some(s) = self.scans.front_mut() {
+			if ctx.is_done() {
+				break;
+			}
+			let res = tx.keys(s.beg.clone()..s.end.clone(), limit, None).await?;
+			if let Some(key) = res.last() {
+				s.beg.clone_from(key);
+				s.beg.push(0x00);
+			} else {
+				self.scans.pop_front();
+				continue;
+			}
+			let mut records = B::with_capacity(res.len());
+			for k in res.iter() {
+				let id = s.decode_id(k)?;
+				if self.distinct.insert(id.clone()) {
+					let thg = Thing::from((self.tb.as_str(), id));
+					records.add((Arc::new(thg), self.irf.into(), None));
+				}
+			}
+			if !records.is_empty() {
+				return Ok(records);
+			}
+		}
+		Ok(B::empty())
+	}
+}
+
+/// A geohash cell, or a point, ordered by its distance to the origin
+struct SpatialCandidate {
+	dist: f64,
+	kind: SpatialCandidateKind,
+}
+
+enum SpatialCandidateKind {
+	Cell(String),
+	Point(Id),
+}
+
+impl PartialEq for SpatialCandidate {
+	fn eq(&self, other: &Self) -> bool {
+		self.cmp(other) == Ordering::Equal
+	}
+}
+
+impl Eq for SpatialCandidate {}
+
+impl PartialOrd for SpatialCandidate {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for SpatialCandidate {
+	fn cmp(&self, other: &Self) -> Ordering {
+		// At an equal distance, points are returned before cells are expanded
+		self.dist.total_cmp(&other.dist).then_with(|| match (&self.kind, &other.kind) {
+			(SpatialCandidateKind::Point(_), SpatialCandidateKind::Cell(_)) => Ordering::Less,
+			(SpatialCandidateKind::Cell(_), SpatialCandidateKind::Point(_)) => Ordering::Greater,
+			_ => Ordering::Equal,
+		})
+	}
+}
+
+/// Iterates over the records of a spatial index, ordered by their distance to a point.
+///
+/// The records which are not a point have no distance, and are returned first.
+/// The points are then returned using a best-first search over the geohash cells,
+/// where a cell is only expanded once no closer point can remain.
+pub(crate) struct SpatialNearestThingIterator {
+	irf: IteratorRef,
+	ns: String,
+	db: String,
+	tb: String,
+	ix: String,
+	origin: Point<f64>,
+	precision: u8,
+	non_points: Option<SpatialScan>,
+	candidates: BinaryHeap<Reverse<SpatialCandidate>>,
+}
+
+impl SpatialNearestThingIterator {
+	pub(super) fn new(
+		irf: IteratorRef,
+		ns: &str,
+		db: &str,
+		ix: &DefineIndexStatement,
+		origin: Point<f64>,
+		precision: u8,
+	) -> Self {
+		let non_points = SpatialScan::non_points(
+			gn::prefix(ns, db, &ix.what, &ix.name),
+			gn::suffix(ns, db, &ix.what, &ix.name),
+		);
+		let mut candidates = BinaryHeap::new();
+		for cell in spatial::child_cells("") {
+			let dist = Area::of_cell(&cell).min_distance(&origin);
+			candidates.push(Reverse(SpatialCandidate {
+				dist,
+				kind: SpatialCandidateKind::Cell(cell),
+			}));
+		}
+		Self {
+			irf,
+			ns: ns.to_owned(),
+			db: db.to_owned(),
+			tb: ix.what.to_raw(),
+			ix: ix.name.to_raw(),
+			origin,
+			precision,
+			non_points: Some(non_points),
+			candidates,
+		}
+	}
+
+	fn push_point(&mut self, k: &Key, v: &[u8]) -> Result<(), Error> {
+		// Cell entries without a point belong to other geometries
+		if let Some(p) = spatial::decode_point(v) {
+			self.candidates.push(Reverse(SpatialCandidate {
+				dist: self.origin.haversine_distance(&p),
+				kind: SpatialCandidateKind::Point(Gh::decode(k)?.id),
+			}));
+		}
+		Ok(())
+	}
+
+	async fn expand_cell(&mut self, tx: &Transaction, cell: String) -> Result<(), Error> {
+		let (ns, db, tb, ix) = (&self.ns, &self.db, &self.tb, &self.ix);
+		if cell.len() < self.precision as usize {
+			// Load the points of the cell if there are only a few of them
+			let beg = gh::prefix_area(ns, db, tb, ix, &cell);
+			let end = gh::suffix_area(ns, db, tb, ix, &cell);
+			let res = tx.scan(beg..end, *NORMAL_FETCH_SIZE + 1, None).await?;
+			if res.len() <= *NORMAL_FETCH_SIZE as usize {
+				for (k, v) in res.iter() {
+					self.push_point(k, v)?;
+				}
+				return Ok(());
+			}
+			// Otherwise expand the child cells
+			for cell in spatial::child_cells(&cell) {
+				let dist = Area::of_cell(&cell).min_distance(&self.origin);
+				self.candidates.push(Reverse(SpatialCandidate {
+					dist,
+					kind: SpatialCandidateKind::Cell(cell),
+				}));
+			}
+		} else {
+			// Load every point of the cell
+			let mut beg = gh::prefix_cell(ns, db, tb, ix, &cell);
+			let end = gh::suffix_cell(ns, db, tb, ix, &cell);
+			loop {
+				let res = tx.scan(beg.clone()..end.clone(), *NORMAL_FETCH_SIZE, None).await?;
+				let Some((key, _)) = res.last() else {
+					break;
+				};
+				beg.clone_from(key);
+				beg.push(0x00);
+				for (k, v) in res.iter() {
+					self.push_point(k, v)?;
+				}
+			}
+		}
+		Ok(())
+	}
+
+	async fn next_batch<B: IteratorBatch>(
+		&mut self,
+		ctx: &Context,
+		tx: &Transaction,
+		limit: u32,
+	) -> Result<B, Error> {
+		// Return the records which are not points first
+		if let Some(s) = &mut self.non_points {
+			let res = tx.keys(s.beg.clone()..s.end.clone(), limit, None).await?;
+			if let Some(key) = res.last() {
+				s.beg.clone_from(key);
+				s.beg.push(0x00);
+				let mut records = B::with_capacity(res.len());
+				for k in res.iter() {
+					let thg = Thing::from((self.tb.as_str(), s.decode_id(k)?));
+					records.add((Arc::new(thg), self.irf.into(), None));
+				}
+				return Ok(records);
+			}
+			self.non_points = None;
+		}
+		// Then return the points by increasing distance
+		let limit = limit as usize;
+		let mut records = B::with_capacity(limit);
+		while limit > records.len() && !ctx.is_done() {
+			let Some(Reverse(c)) = self.candidates.pop() else {
+				break;
+			};
+			match c.kind {
+				SpatialCandidateKind::Point(id) => {
+					let thg = Thing::from((self.tb.as_str(), id));
+					records.add((Arc::new(thg), self.irf.into(), None));
+				}
+				SpatialCandidateKind::Cell(cell) => self.expand_cell(tx, cell).await?,
+			}
+		}
+		Ok(records)
+	}
+}
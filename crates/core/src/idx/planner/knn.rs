@@ -143,6 +143,14 @@ impl KnnBruteForceResults {
 		false
 	}
 
+	/// Returns the nearest neighbours found by each brute force operator on the table
+	pub(super) fn table_results(
+		&self,
+		tb: &str,
+	) -> impl Iterator<Item = &HashMap<Arc<Thing>, Number>> {
+		self.0.get(tb).into_iter().flat_map(|result| result.res.iter())
+	}
+
 	pub(crate) fn get_dist(&self, pos: usize, thg: &Thing) -> Option<Number> {
 		if let Some(result) = self.0.get(thg.tb.as_str()) {
 			if let Some(things) = result.res.get(pos) {
@@ -157,6 +157,7 @@ impl QueryPlanner {
 			tree.knn_expressions,
 			tree.knn_brute_force_expressions,
 			tree.knn_condition,
+			tree.all_and,
 		)
 		.await?;
 		match PlanBuilder::build(
@@ -172,11 +173,10 @@ impl QueryPlanner {
 		)
 		.await?
 		{
-			Plan::SingleIndex(exp, io) => {
+			Plan::SingleIndex(exp, io, is_order) => {
 				if io.require_distinct() {
 					self.requires_distinct = true;
 				}
-				let is_order = exp.is_none();
 				let ir = exe.add_iterator(IteratorEntry::Single(exp, io));
 				self.add(t.clone(), Some(ir), exe, it);
 				if is_order {
@@ -1,7 +1,7 @@
 use crate::err::Error;
 use crate::idx::ft::MatchRef;
 use crate::idx::planner::tree::{
-	CompoundIndexes, GroupRef, IdiomCol, IdiomPosition, IndexReference, Node,
+	CompoundColumn, CompoundIndexes, GroupRef, IdiomCol, IdiomPosition, IndexReference, Node,
 };
 use crate::idx::planner::StatementContext;
 use crate::sql::with::With;
@@ -58,22 +58,29 @@ impl PlanBuilder {
 
 		// If all boolean operators are AND, we can use the single index plan
 		if all_and {
-			// We try first the largest compound indexed
+			// Nearest neighbours can only be found by iterating their index
+			if let Some(p) = b.non_range_indexes.iter().position(|(_, io)| io.is_knn()) {
+				let (e, i) = b.non_range_indexes.remove(p);
+				return Ok(Plan::SingleIndex(Some(e), i, false));
+			}
+
+			// We try first the largest compound indexed,
+			// preferring the ones which also satisfy the order
 			let mut compound_index = None;
-			for (ixr, vals) in compound_indexes {
-				if let Some((cols, io)) = b.check_compound_index(ixr, vals) {
-					if let Some((c, _)) = &compound_index {
-						if cols <= *c {
+			for (ixr, columns) in compound_indexes {
+				if let Some((cols, ordered, io)) = b.check_compound_index(ixr, columns) {
+					if let Some((c, o, _)) = &compound_index {
+						if (cols, ordered) <= (*c, *o) {
 							continue;
 						}
 					}
-					if cols > 1 {
-						compound_index = Some((cols, io));
+					if cols > 1 || ordered {
+						compound_index = Some((cols, ordered, io));
 					}
 				}
 			}
-			if let Some((_, io)) = compound_index {
-				return Ok(Plan::SingleIndex(None, io));
+			if let Some((_, ordered, io)) = compound_index {
+				return Ok(Plan::SingleIndex(None, io, ordered));
 			}
 
 			// We take the "first" range query if one is available
@@ -85,11 +92,11 @@ impl PlanBuilder {
 
 			// Otherwise, we try to find the most interesting (todo: TBD) single index option
 			if let Some((e, i)) = b.non_range_indexes.pop() {
-				return Ok(Plan::SingleIndex(Some(e), i));
+				return Ok(Plan::SingleIndex(Some(e), i, false));
 			}
 			// If there is an order option
 			if let Some(o) = order {
-				return Ok(Plan::SingleIndex(None, o.clone()));
+				return Ok(Plan::SingleIndex(None, o.clone(), true));
 			}
 		}
 		// If every expression is backed by an index with can use the MultiIndex plan
@@ -139,29 +146,64 @@ impl PlanBuilder {
 	}
 
 	/// Check if a compound index can be used.
+	/// The index is used with an equality on its leading columns,
+	/// optionally followed by a range on the next column.
+	/// Returns the number of columns used, and whether the index order matches the statement order.
 	fn check_compound_index(
 		&self,
 		ixr: IndexReference,
-		mut vals: Vec<Option<Arc<Value>>>,
-	) -> Option<(IdiomCol, IndexOption)> {
+		columns: Vec<CompoundColumn>,
+	) -> Option<(IdiomCol, bool, IndexOption)> {
 		// Check the index can be used
 		if !self.allowed_index(&ixr) {
 			return None;
 		}
-		// Count continues values (from the left)
-		let mut cols = 0;
-		for val in &vals {
-			if val.is_none() {
-				break;
+		// Collect continuous equal values (from the left)
+		let mut vals = Vec::with_capacity(columns.len());
+		for c in &columns {
+			match &c.equal {
+				Some(v) => vals.push(v.clone()),
+				None => break,
 			}
-			cols += 1;
 		}
-		if cols == 0 {
+		let eq_cols = vals.len();
+		if eq_cols == 0 {
 			return None;
 		}
-		let vals = vals.drain(0..cols).map(|v| v.unwrap()).collect();
+		// The records are ordered by the equal columns, which are constant, and by the first
+		// non equal column, so the order holds if the statement is ordered by no other column.
+		// Numbers are scanned separately for each number type, and the results concatenated,
+		// so the order only holds on the first non equal column if no number is involved.
+		let order = columns.iter().any(|c| c.order);
+		let eq_ordered = order && columns.iter().skip(eq_cols).all(|c| !c.order);
+		let next_ordered = order
+			&& columns.iter().skip(eq_cols + 1).all(|c| !c.order)
+			&& !vals.iter().any(|v| v.is_number());
+		// Check if the next column is restricted by a range
+		if let Some(c) = columns.get(eq_cols) {
+			let mut from = RangeValue::default();
+			let mut to = RangeValue::default();
+			for (op, val) in &c.ranges {
+				RangeValue::set_range(&mut from, &mut to, op, val);
+			}
+			if !from.value.is_none() || !to.value.is_none() {
+				let ordered =
+					eq_ordered || next_ordered && !from.value.is_number() && !to.value.is_number();
+				return Some((
+					eq_cols + 1,
+					ordered,
+					IndexOption::new(
+						ixr,
+						None,
+						IdiomPosition::None,
+						IndexOperator::Range(vals, from, to),
+					),
+				));
+			}
+		}
 		Some((
-			cols,
+			eq_cols,
+			eq_ordered || next_ordered,
 			IndexOption::new(ixr, None, IdiomPosition::None, IndexOperator::Equality(vals)),
 		))
 	}
@@ -208,8 +250,9 @@ impl PlanBuilder {
 pub(super) enum Plan {
 	/// Table full scan
 	TableIterator(Option<String>, bool),
-	/// Index scan filtered on records matching a given expression
-	SingleIndex(Option<Arc<Expression>>, IndexOption),
+	/// Index scan filtered on records matching a given expression.
+	/// The flag is set when the index returns the records in the order of the statement.
+	SingleIndex(Option<Arc<Expression>>, IndexOption, bool),
 	/// Union of filtered index scans
 	MultiIndex(Vec<(Arc<Expression>, IndexOption)>, Vec<(IndexReference, UnionRangeQueryBuilder)>),
 	/// Index scan for record matching a given range
@@ -234,9 +277,12 @@ pub(super) enum IndexOperator {
 	Union(Arc<Value>),
 	Join(Vec<IndexOption>),
 	RangePart(Operator, Arc<Value>),
-	Matches(String, Option<MatchRef>),
+	Range(Vec<Arc<Value>>, RangeValue, RangeValue),
+	Matches(String, Option<MatchRef>, Option<u8>),
 	Knn(Arc<Vec<Number>>, u32),
 	Ann(Arc<Vec<Number>>, u32, u32),
+	Spatial(Operator, Arc<Value>),
+	Nearest(Arc<Value>),
 	Order,
 }
 
@@ -255,6 +301,10 @@ impl IndexOption {
 		}
 	}
 
+	pub(super) fn is_knn(&self) -> bool {
+		matches!(self.op.as_ref(), IndexOperator::Knn(..) | IndexOperator::Ann(..))
+	}
+
 	pub(super) fn require_distinct(&self) -> bool {
 		matches!(self.op.as_ref(), IndexOperator::Union(_))
 	}
@@ -308,14 +358,23 @@ impl IndexOption {
 				let joins = Value::from(joins);
 				e.insert("joins", joins);
 			}
-			IndexOperator::Matches(qs, a) => {
-				e.insert("operator", Value::from(Operator::Matches(*a).to_string()));
+			IndexOperator::Matches(qs, a, fuzzy) => {
+				let op = match fuzzy {
+					Some(d) => Operator::FuzzyMatches(*a, *d),
+					None => Operator::Matches(*a),
+				};
+				e.insert("operator", Value::from(op.to_string()));
 				e.insert("value", Value::from(qs.to_owned()));
 			}
 			IndexOperator::RangePart(op, v) => {
 				e.insert("operator", Value::from(op.to_string()));
 				e.insert("value", v.as_ref().to_owned());
 			}
+			IndexOperator::Range(prefix, from, to) => {
+				e.insert("prefix", Self::reduce_array(prefix));
+				e.insert("from", Value::from(from));
+				e.insert("to", Value::from(to));
+			}
 			IndexOperator::Knn(a, k) => {
 				let op = Value::from(Operator::Knn(*k, None).to_string());
 				let val = Value::Array(Array::from(a.as_ref().clone()));
@@ -328,6 +387,14 @@ impl IndexOption {
 				e.insert("operator", op);
 				e.insert("value", val);
 			}
+			IndexOperator::Spatial(op, v) => {
+				e.insert("operator", Value::from(op.to_string()));
+				e.insert("value", v.as_ref().to_owned());
+			}
+			IndexOperator::Nearest(v) => {
+				e.insert("operator", Value::from("Nearest"));
+				e.insert("value", v.as_ref().to_owned());
+			}
 			IndexOperator::Order => {
 				e.insert("operator", Value::from("Order"));
 			}
@@ -343,6 +410,18 @@ pub(super) struct RangeValue {
 }
 
 impl RangeValue {
+	/// Narrows the range with the given range operator
+	fn set_range(from: &mut Self, to: &mut Self, op: &Operator, v: &Value) -> bool {
+		match op {
+			Operator::LessThan => to.set_to(v),
+			Operator::LessThanOrEqual => to.set_to_inclusive(v),
+			Operator::MoreThan => from.set_from(v),
+			Operator::MoreThanOrEqual => from.set_from_inclusive(v),
+			_ => return false,
+		}
+		true
+	}
+
 	fn set_to(&mut self, v: &Value) {
 		if self.value.is_none() {
 			self.value = v.clone();
@@ -470,12 +549,8 @@ impl UnionRangeQueryBuilder {
 
 	fn add(&mut self, exp: Arc<Expression>, io: IndexOption) -> bool {
 		if let IndexOperator::RangePart(op, val) = io.op() {
-			match op {
-				Operator::LessThan => self.to.set_to(val),
-				Operator::LessThanOrEqual => self.to.set_to_inclusive(val),
-				Operator::MoreThan => self.from.set_from(val),
-				Operator::MoreThanOrEqual => self.from.set_from_inclusive(val),
-				_ => return false,
+			if !RangeValue::set_range(&mut self.from, &mut self.to, op, val) {
+				return false;
 			}
 			self.exps.insert(exp);
 		}
@@ -11,8 +11,8 @@ use crate::sql::index::Index;
 use crate::sql::statements::{DefineFieldStatement, DefineIndexStatement};
 use crate::sql::{
 	order::{OrderList, Ordering},
-	Array, Cond, Expression, Idiom, Kind, Number, Operator, Order, Part, Subquery, Table, Value,
-	With,
+	Array, Cond, Expression, Field, Function, Geometry, Idiom, Kind, Number, Operator, Order, Part,
+	Subquery, Table, Value, With,
 };
 use reblessive::tree::Stk;
 use std::collections::HashMap;
@@ -47,7 +47,7 @@ impl Tree {
 		if let Some(cond) = stm_ctx.cond {
 			b.eval_cond(stk, cond).await?;
 		}
-		b.eval_order().await?;
+		b.eval_order(stk).await?;
 		Ok(Self {
 			root: b.root,
 			index_map: b.index_map,
@@ -142,9 +142,14 @@ impl<'a> TreeBuilder<'a> {
 		Ok(())
 	}
 
-	async fn eval_order(&mut self) -> Result<(), Error> {
+	async fn eval_order(&mut self, stk: &mut Stk) -> Result<(), Error> {
 		if let Some(o) = self.first_order {
 			if o.direction {
+				if let Some(io) = self.eval_nearest_order(stk, o).await? {
+					self.index_map.order_limit = Some(io);
+					return Ok(());
+				}
+				self.eval_compound_order().await?;
 				if let Node::IndexedField(id, irf) = self.resolve_idiom(&o.value).await? {
 					for (ixr, id_col) in &irf {
 						if *id_col == 0 {
@@ -163,6 +168,91 @@ impl<'a> TreeBuilder<'a> {
 		Ok(())
 	}
 
+	/// Marks the columns of the compound indexes which cover every entry of the ORDER clause.
+	async fn eval_compound_order(&mut self) -> Result<(), Error> {
+		let ctx = self.ctx;
+		let Some(Ordering::Order(OrderList(orders))) = ctx.order else {
+			return Ok(());
+		};
+		// The index can only provide an ascending order
+		if !orders.iter().all(|o| o.direction) {
+			return Ok(());
+		}
+		let mut covered: Vec<(IndexReference, Vec<IdiomCol>)> = Vec::new();
+		for o in orders {
+			let Node::IndexedField(_, irf) = self.resolve_idiom(&o.value).await? else {
+				return Ok(());
+			};
+			for (ixr, id_col) in irf.iter() {
+				match covered.iter_mut().find(|(r, _)| r == ixr) {
+					Some((_, cols)) => cols.push(*id_col),
+					None => covered.push((ixr.clone(), vec![*id_col])),
+				}
+			}
+		}
+		for (ixr, cols) in covered {
+			if cols.len() == orders.len() {
+				for col in cols {
+					self.index_map.check_compound_order(&ixr, col);
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Checks if the order is the distance from a point to a field backed by a spatial index.
+	/// Eg. `SELECT *, geo::distance(location, (-0.13, 51.5)) AS dist FROM place ORDER BY dist`
+	async fn eval_nearest_order(
+		&mut self,
+		stk: &mut Stk,
+		o: &Order,
+	) -> Result<Option<IndexOption>, Error> {
+		let Some(fields) = self.ctx.fields else {
+			return Ok(None);
+		};
+		for field in fields.iter() {
+			let Field::Single {
+				expr: Value::Function(f),
+				alias,
+			} = field
+			else {
+				continue;
+			};
+			if alias.as_ref().unwrap_or(&f.to_idiom()) != &o.value {
+				continue;
+			}
+			let Function::Normal(name, args) = f.as_ref() else {
+				return Ok(None);
+			};
+			let (id, v) = match (name.as_str(), args.as_slice()) {
+				("geo::distance", [Value::Idiom(id), v]) => (id, v),
+				("geo::distance", [v, Value::Idiom(id)]) => (id, v),
+				_ => return Ok(None),
+			};
+			let Ok(v) = stk.run(|stk| v.compute(stk, self.ctx.ctx, self.ctx.opt, None)).await
+			else {
+				return Ok(None);
+			};
+			if !matches!(v, Value::Geometry(Geometry::Point(_))) {
+				return Ok(None);
+			}
+			if let Node::IndexedField(id, irf) = self.resolve_idiom(id).await? {
+				for (ixr, id_col) in &irf {
+					if *id_col == 0 && matches!(ixr.index, Index::Spatial(_)) {
+						return Ok(Some(IndexOption::new(
+							ixr.clone(),
+							Some(id),
+							IdiomPosition::None,
+							IndexOperator::Nearest(Arc::new(v)),
+						)));
+					}
+				}
+			}
+			return Ok(None);
+		}
+		Ok(None)
+	}
+
 	async fn eval_cond(&mut self, stk: &mut Stk, cond: &Cond) -> Result<(), Error> {
 		self.root = Some(self.eval_value(stk, 0, &cond.0).await?);
 		self.knn_condition = if self.knn_expressions.is_empty() {
@@ -500,6 +590,7 @@ impl<'a> TreeBuilder<'a> {
 				} if *col == 0 => Self::eval_matches_operator(op, n),
 				Index::MTree(_) if *col == 0 => self.eval_mtree_knn(e, op, n)?,
 				Index::Hnsw(_) if *col == 0 => self.eval_hnsw_knn(e, op, n)?,
+				Index::Spatial(_) if *col == 0 => Self::eval_spatial_operator(op, n),
 				_ => None,
 			};
 			if res.is_none() {
@@ -525,8 +616,25 @@ impl<'a> TreeBuilder<'a> {
 
 	fn eval_matches_operator(op: &Operator, n: &Node) -> Option<IndexOperator> {
 		if let Some(v) = n.is_computed() {
-			if let Operator::Matches(mr) = op {
-				return Some(IndexOperator::Matches(v.to_raw_string(), *mr));
+			match op {
+				Operator::Matches(mr) => {
+					return Some(IndexOperator::Matches(v.to_raw_string(), *mr, None));
+				}
+				Operator::FuzzyMatches(mr, d) => {
+					return Some(IndexOperator::Matches(v.to_raw_string(), *mr, Some(*d)));
+				}
+				_ => {}
+			}
+		}
+		None
+	}
+
+	fn eval_spatial_operator(op: &Operator, n: &Node) -> Option<IndexOperator> {
+		if let Some(v) = n.is_computed() {
+			if let Value::Geometry(_) = v.as_ref() {
+				if let Operator::Inside | Operator::Intersects | Operator::Outside = op {
+					return Some(IndexOperator::Spatial(op.clone(), v));
+				}
 			}
 		}
 		None
@@ -628,6 +736,7 @@ impl<'a> TreeBuilder<'a> {
 					v,
 					p,
 				) => {
+					self.index_map.check_compound_range(ixr, col, p.transform(op), &v);
 					if col == 0 {
 						return Some(IndexOperator::RangePart(p.transform(op), v));
 					}
@@ -647,7 +756,18 @@ impl<'a> TreeBuilder<'a> {
 	}
 }
 
-pub(super) type CompoundIndexes = HashMap<IndexReference, Vec<Option<Arc<Value>>>>;
+pub(super) type CompoundIndexes = HashMap<IndexReference, Vec<CompoundColumn>>;
+
+/// What the statement requests on one column of a compound index
+#[derive(Clone, Default)]
+pub(super) struct CompoundColumn {
+	/// The value the column is equal to
+	pub(super) equal: Option<Arc<Value>>,
+	/// The range parts the column is restricted to
+	pub(super) ranges: Vec<(Operator, Arc<Value>)>,
+	/// Is the statement ordered by this column?
+	pub(super) order: bool,
+}
 
 /// For each expression a possible index option
 #[derive(Default)]
@@ -659,10 +779,33 @@ pub(super) struct IndexesMap {
 }
 
 impl IndexesMap {
-	pub(crate) fn check_compound(&mut self, ixr: &IndexReference, col: usize, val: &Arc<Value>) {
+	fn compound_column(&mut self, ixr: &IndexReference, col: usize) -> &mut CompoundColumn {
 		let cols = ixr.cols.len();
-		let values = self.compound_indexes.entry(ixr.clone()).or_insert(vec![None; cols]);
-		values[col] = Some(val.clone());
+		let columns =
+			self.compound_indexes.entry(ixr.clone()).or_insert(vec![Default::default(); cols]);
+		&mut columns[col]
+	}
+
+	pub(crate) fn check_compound(&mut self, ixr: &IndexReference, col: usize, val: &Arc<Value>) {
+		self.compound_column(ixr, col).equal = Some(val.clone());
+	}
+
+	pub(crate) fn check_compound_range(
+		&mut self,
+		ixr: &IndexReference,
+		col: usize,
+		op: Operator,
+		val: &Arc<Value>,
+	) {
+		if ixr.cols.len() > 1 {
+			self.compound_column(ixr, col).ranges.push((op, val.clone()));
+		}
+	}
+
+	pub(crate) fn check_compound_order(&mut self, ixr: &IndexReference, col: usize) {
+		if ixr.cols.len() > 1 && matches!(ixr.index, Index::Idx | Index::Uniq) {
+			self.compound_column(ixr, col).order = true;
+		}
 	}
 }
 
@@ -0,0 +1,343 @@
+//! A spatial index, which stores the geometries of the records in geohash cells.
+//!
+//! A point is stored in the cell of the index precision which contains it.
+//! Any other geometry is stored in the cells covering its bounding box, using
+//! the longest cells for which the number of cells stays below a threshold.
+//! As a geohash cell shares its prefix with all of its child cells, the records
+//! intersecting an area are found by scanning the cells covering this area,
+//! and the parent cells of these cells.
+use crate::err::Error;
+use crate::idx::IndexKeyBase;
+use crate::kvs::{Key, Transaction, Val};
+use crate::sql::index::SpatialParams;
+use crate::sql::{Geometry, Id, Value};
+use geo::{BoundingRect, HaversineDistance, Point};
+
+static BASE32: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+/// The maximum number of cells used to store the geometry of a record
+const MAX_INDEX_CELLS: u64 = 16;
+
+/// The maximum number of cells used to cover the area of a query
+pub(crate) const MAX_QUERY_CELLS: u64 = 32;
+
+/// A rectangle of longitudes and latitudes, in degrees
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Area {
+	min_lon: f64,
+	min_lat: f64,
+	max_lon: f64,
+	max_lat: f64,
+}
+
+impl Area {
+	/// Returns the bounding box of a geometry
+	pub(crate) fn of(g: &Geometry) -> Option<Self> {
+		let g: geo::Geometry<f64> = g.clone().into();
+		g.bounding_rect().map(|r| Self {
+			min_lon: r.min().x,
+			min_lat: r.min().y,
+			max_lon: r.max().x,
+			max_lat: r.max().y,
+		})
+	}
+
+	/// Returns the area of a geohash cell
+	pub(crate) fn of_cell(cell: &str) -> Self {
+		let (mut lon, mut lat) = (0u64, 0u64);
+		let (mut lon_bits, mut lat_bits) = (0u32, 0u32);
+		let mut even = true;
+		for c in cell.bytes() {
+			let v = BASE32.iter().position(|b| *b == c).unwrap_or(0) as u64;
+			for i in (0..5).rev() {
+				let bit = (v >> i) & 1;
+				if even {
+					lon = (lon << 1) | bit;
+					lon_bits += 1;
+				} else {
+					lat = (lat << 1) | bit;
+					lat_bits += 1;
+				}
+				even = !even;
+			}
+		}
+		let lon_span = 360.0 / (1u64 << lon_bits) as f64;
+		let lat_span = 180.0 / (1u64 << lat_bits) as f64;
+		Self {
+			min_lon: -180.0 + lon as f64 * lon_span,
+			min_lat: -90.0 + lat as f64 * lat_span,
+			max_lon: -180.0 + (lon + 1) as f64 * lon_span,
+			max_lat: -90.0 + (lat + 1) as f64 * lat_span,
+		}
+	}
+
+	/// Returns the cells, no longer than the given precision, which cover the area.
+	///
+	/// The longest cells are used, as long as the number of cells does not exceed the limit.
+	pub(crate) fn covering(&self, precision: u8, max_cells: u64) -> Vec<String> {
+		for p in (1..=precision).rev() {
+			let (lon_bits, lat_bits) = bits(p);
+			let lon_beg = cell_index(self.min_lon, -180.0, 360.0, lon_bits);
+			let lon_end = cell_index(self.max_lon, -180.0, 360.0, lon_bits);
+			let lat_beg = cell_index(self.min_lat, -90.0, 180.0, lat_bits);
+			let lat_end = cell_index(self.max_lat, -90.0, 180.0, lat_bits);
+			let count = (lon_end - lon_beg + 1).saturating_mul(lat_end - lat_beg + 1);
+			if count <= max_cells || p == 1 {
+				let mut cells = Vec::with_capacity(count as usize);
+				for lon in lon_beg..=lon_end {
+					for lat in lat_beg..=lat_end {
+						cells.push(encode(lon, lat, p));
+					}
+				}
+				return cells;
+			}
+		}
+		vec![]
+	}
+
+	/// Returns the minimum distance, in metres, between a point and any point in this area.
+	///
+	/// The distance is measured along the great circle, the same way as `geo::distance`.
+	pub(crate) fn min_distance(&self, p: &Point<f64>) -> f64 {
+		let (lon, lat) = (p.x(), p.y());
+		// Check if the point is within the longitudes of the area
+		if lon >= self.min_lon && lon <= self.max_lon {
+			// The closest point is on the same meridian
+			let closest = Point::new(lon, lat.clamp(self.min_lat, self.max_lat));
+			return p.haversine_distance(&closest);
+		}
+		// Find the closest meridian edge of the area
+		let to_min = lon_difference(lon, self.min_lon);
+		let to_max = lon_difference(lon, self.max_lon);
+		let (edge, dlon) = if to_min <= to_max {
+			(self.min_lon, to_min)
+		} else {
+			(self.max_lon, to_max)
+		};
+		// Find the latitude of the closest point on this meridian
+		let (lat_r, dlon_r) = (lat.to_radians(), dlon.to_radians());
+		let closest = lat_r.sin().atan2(lat_r.cos() * dlon_r.cos()).to_degrees();
+		let closest = Point::new(edge, closest.clamp(self.min_lat, self.max_lat));
+		p.haversine_distance(&closest)
+	}
+}
+
+/// Returns the smallest difference, in degrees, between two longitudes
+fn lon_difference(a: f64, b: f64) -> f64 {
+	let d = (a - b).rem_euclid(360.0);
+	d.min(360.0 - d)
+}
+
+/// Returns the number of longitude bits and latitude bits of a geohash of the given length
+fn bits(precision: u8) -> (u32, u32) {
+	let bits = 5 * precision as u32;
+	(bits.div_ceil(2), bits / 2)
+}
+
+/// Returns the position of a coordinate in a grid of 2^bits cells
+fn cell_index(v: f64, min: f64, span: f64, bits: u32) -> u64 {
+	let n = 1u64 << bits;
+	let i = ((v - min) / span * n as f64).floor();
+	if i.is_nan() || i < 0.0 {
+		0
+	} else {
+		(i as u64).min(n - 1)
+	}
+}
+
+/// Encodes the position of a cell in the longitude and latitude grids as a geohash
+fn encode(lon: u64, lat: u64, precision: u8) -> String {
+	let (mut lon_bits, mut lat_bits) = bits(precision);
+	let mut out = String::with_capacity(precision as usize);
+	let mut hash = 0usize;
+	for bit in 0..5 * precision as u32 {
+		let b = if bit % 2 == 0 {
+			lon_bits -= 1;
+			(lon >> lon_bits) & 1
+		} else {
+			lat_bits -= 1;
+			(lat >> lat_bits) & 1
+		};
+		hash = (hash << 1) | b as usize;
+		if bit % 5 == 4 {
+			out.push(BASE32[hash] as char);
+			hash = 0;
+		}
+	}
+	out
+}
+
+/// Returns the cell of the given precision which contains the point
+pub(crate) fn point_cell(p: &Point<f64>, precision: u8) -> String {
+	let (lon_bits, lat_bits) = bits(precision);
+	let lon = cell_index(p.x(), -180.0, 360.0, lon_bits);
+	let lat = cell_index(p.y(), -90.0, 180.0, lat_bits);
+	encode(lon, lat, precision)
+}
+
+/// Returns the 32 child cells of a cell
+pub(crate) fn child_cells(cell: &str) -> impl Iterator<Item = String> + '_ {
+	BASE32.iter().map(move |c| {
+		let mut s = String::with_capacity(cell.len() + 1);
+		s.push_str(cell);
+		s.push(*c as char);
+		s
+	})
+}
+
+/// Encodes the coordinates of a point, stored as the value of a cell entry
+pub(crate) fn encode_point(p: &Point<f64>) -> Val {
+	let mut v = Vec::with_capacity(16);
+	v.extend_from_slice(&p.x().to_be_bytes());
+	v.extend_from_slice(&p.y().to_be_bytes());
+	v
+}
+
+/// Decodes the coordinates of a point, stored as the value of a cell entry
+pub(crate) fn decode_point(v: &[u8]) -> Option<Point<f64>> {
+	let x = f64::from_be_bytes(v.get(0..8)?.try_into().ok()?);
+	let y = f64::from_be_bytes(v.get(8..16)?.try_into().ok()?);
+	Some(Point::new(x, y))
+}
+
+pub(crate) struct SpatialIndex {
+	ikb: IndexKeyBase,
+	precision: u8,
+}
+
+/// The entries stored for the value of a record
+enum Entries {
+	/// The cell containing a point, and the encoded point
+	Point(String, Val),
+	/// The cells covering any other geometry
+	Cells(Vec<String>),
+	/// The value is not a geometry
+	None,
+}
+
+impl SpatialIndex {
+	pub(crate) fn new(ikb: IndexKeyBase, p: &SpatialParams) -> Self {
+		Self {
+			ikb,
+			precision: p.precision,
+		}
+	}
+
+	fn entries(&self, content: &[Value]) -> Entries {
+		match content.first() {
+			Some(Value::Geometry(Geometry::Point(p))) => {
+				Entries::Point(point_cell(p, self.precision), encode_point(p))
+			}
+			Some(Value::Geometry(g)) => match Area::of(g) {
+				Some(a) => Entries::Cells(a.covering(self.precision, MAX_INDEX_CELLS)),
+				None => Entries::Cells(vec![]),
+			},
+			_ => Entries::None,
+		}
+	}
+
+	fn gh_key(&self, cell: &str, id: &Id) -> Key {
+		self.ikb.new_gh_key(cell, id.clone())
+	}
+
+	fn gn_key(&self, id: &Id) -> Key {
+		self.ikb.new_gn_key(id.clone())
+	}
+
+	pub(crate) async fn index_document(
+		&self,
+		tx: &Transaction,
+		id: &Id,
+		content: &[Value],
+	) -> Result<(), Error> {
+		match self.entries(content) {
+			Entries::Point(cell, val) => {
+				tx.set(self.gh_key(&cell, id), val, None).await?;
+			}
+			Entries::Cells(cells) => {
+				for cell in cells.iter() {
+					tx.set(self.gh_key(cell, id), vec![], None).await?;
+				}
+				tx.set(self.gn_key(id), vec![], None).await?;
+			}
+			Entries::None => {
+				tx.set(self.gn_key(id), vec![], None).await?;
+			}
+		}
+		Ok(())
+	}
+
+	pub(crate) async fn remove_document(
+		&self,
+		tx: &Transaction,
+		id: &Id,
+		content: &[Value],
+	) -> Result<(), Error> {
+		match self.entries(content) {
+			Entries::Point(cell, _) => {
+				tx.del(self.gh_key(&cell, id)).await?;
+			}
+			Entries::Cells(cells) => {
+				for cell in cells.iter() {
+					tx.del(self.gh_key(cell, id)).await?;
+				}
+				tx.del(self.gn_key(id)).await?;
+			}
+			Entries::None => {
+				tx.del(self.gn_key(id)).await?;
+			}
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn point_cell_matches_geohash() {
+		for p in [Point::new(-0.1275, 51.50722), Point::new(-73.9857, 40.7484)] {
+			for precision in 1..=12 {
+				let cell = point_cell(&p, precision);
+				assert_eq!(cell, crate::fnc::util::geo::encode(p, precision as usize).0);
+				let a = Area::of_cell(&cell);
+				assert!(a.min_lon <= p.x() && p.x() <= a.max_lon);
+				assert!(a.min_lat <= p.y() && p.y() <= a.max_lat);
+			}
+		}
+	}
+
+	#[test]
+	fn covering_contains_parent_cells() {
+		let a = Area {
+			min_lon: -0.2,
+			min_lat: 51.4,
+			max_lon: 0.0,
+			max_lat: 51.6,
+		};
+		let cells = a.covering(8, MAX_QUERY_CELLS);
+		assert!(!cells.is_empty());
+		assert!(cells.len() as u64 <= MAX_QUERY_CELLS);
+		let p = Point::new(-0.1275, 51.50722);
+		let cell = point_cell(&p, 8);
+		assert!(cells.iter().any(|c| cell.starts_with(c.as_str())));
+	}
+
+	#[test]
+	fn min_distance_is_a_lower_bound() {
+		let p = Point::new(2.3522, 48.8566);
+		for cell in ["gcpvj", "u09tv", "dr5re", "9q8yy", "u0"] {
+			let a = Area::of_cell(cell);
+			let d = a.min_distance(&p);
+			for i in 0..=10 {
+				for j in 0..=10 {
+					let lon = a.min_lon + (a.max_lon - a.min_lon) * i as f64 / 10.0;
+					let lat = a.min_lat + (a.max_lat - a.min_lat) * j as f64 / 10.0;
+					assert!(d <= p.haversine_distance(&Point::new(lon, lat)) + 1e-6);
+				}
+			}
+		}
+		assert_eq!(Area::of_cell("u09tv").min_distance(&p), 0.0);
+	}
+}
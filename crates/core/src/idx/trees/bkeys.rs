@@ -1,7 +1,7 @@
 use crate::err::Error;
 use crate::idx::trees::btree::Payload;
 use crate::kvs::Key;
-use fst::{IntoStreamer, Map, MapBuilder, Streamer};
+use fst::{Automaton, IntoStreamer, Map, MapBuilder, Streamer};
 use radix_trie::{SubTrie, Trie, TrieCommon};
 use serde::ser;
 use std::collections::VecDeque;
@@ -19,6 +19,7 @@ pub trait BKeys: Default + Debug + Display + Sized {
 	// The size of the Node should be small, therefore one instance of
 	// BKeys would never be store a large volume of keys.
 	fn collect_with_prefix(&self, prefix_key: &Key) -> Result<VecDeque<(Key, Payload)>, Error>;
+	fn collect_matching<A: Automaton>(&self, aut: &A) -> Result<VecDeque<(Key, Payload)>, Error>;
 	fn insert(&mut self, key: Key, payload: Payload) -> Option<Payload>;
 	fn append(&mut self, keys: Self);
 	fn remove(&mut self, key: &Key) -> Option<Payload>;
@@ -44,6 +45,18 @@ where
 	pub(in crate::idx) median_payload: Payload,
 }
 
+/// Runs the automaton over the given bytes
+pub(in crate::idx) fn automaton_matches<A: Automaton>(aut: &A, bytes: &[u8]) -> bool {
+	let mut state = aut.start();
+	for b in bytes {
+		if !aut.can_match(&state) {
+			return false;
+		}
+		state = aut.accept(&state, *b);
+	}
+	aut.is_match(&state)
+}
+
 #[derive(Debug, Clone)]
 #[non_exhaustive]
 pub struct FstKeys {
@@ -102,8 +115,35 @@ impl BKeys for FstKeys {
 		}
 	}
 
-	fn collect_with_prefix(&self, _prefix_key: &Key) -> Result<VecDeque<(Key, Payload)>, Error> {
-		Err(fail!("BKeys/FSTKeys::collect_with_prefix"))
+	fn collect_with_prefix(&self, prefix_key: &Key) -> Result<VecDeque<(Key, Payload)>, Error> {
+		match &self.i {
+			Inner::Map(m) => {
+				let mut r = VecDeque::new();
+				let mut s = m.range().ge(prefix_key).into_stream();
+				while let Some((k, p)) = s.next() {
+					if !k.starts_with(prefix_key) {
+						break;
+					}
+					r.push_back((k.to_vec(), p));
+				}
+				Ok(r)
+			}
+			Inner::Trie(t) => t.collect_with_prefix(prefix_key),
+		}
+	}
+
+	fn collect_matching<A: Automaton>(&self, aut: &A) -> Result<VecDeque<(Key, Payload)>, Error> {
+		match &self.i {
+			Inner::Map(m) => {
+				let mut r = VecDeque::new();
+				let mut s = m.search(aut).into_stream();
+				while let Some((k, p)) = s.next() {
+					r.push_back((k.to_vec(), p));
+				}
+				Ok(r)
+			}
+			Inner::Trie(t) => t.collect_matching(aut),
+		}
 	}
 
 	fn insert(&mut self, key: Key, payload: Payload) -> Option<Payload> {
@@ -349,6 +389,15 @@ impl BKeys for TrieKeys {
 		Ok(r)
 	}
 
+	fn collect_matching<A: Automaton>(&self, aut: &A) -> Result<VecDeque<(Key, Payload)>, Error> {
+		Ok(self
+			.keys
+			.iter()
+			.filter(|(k, _)| automaton_matches(aut, k))
+			.map(|(k, p)| (k.clone(), *p))
+			.collect())
+	}
+
 	fn insert(&mut self, key: Key, payload: Payload) -> Option<Payload> {
 		self.keys.insert(key, payload)
 	}
@@ -591,9 +640,7 @@ mod tests {
 		}
 	}
 
-	#[tokio::test]
-	async fn test_tries_keys_collect_with_prefix() {
-		let mut keys = TrieKeys::default();
+	fn test_keys_collect_with_prefix<BK: BKeys>(mut keys: BK) {
 		keys.insert("apple".into(), 1);
 		keys.insert("applicant".into(), 2);
 		keys.insert("application".into(), 3);
@@ -606,6 +653,7 @@ mod tests {
 		keys.insert("their".into(), 8);
 		keys.insert("theirs".into(), 9);
 		keys.insert("there".into(), 10);
+		keys.compile();
 
 		{
 			let r = keys.collect_with_prefix(&"appli".into()).unwrap();
@@ -646,6 +694,16 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn test_tries_keys_collect_with_prefix() {
+		test_keys_collect_with_prefix(TrieKeys::default())
+	}
+
+	#[test]
+	fn test_fst_keys_collect_with_prefix() {
+		test_keys_collect_with_prefix(FstKeys::default())
+	}
+
 	fn test_keys_split<BK: BKeys>(mut keys: BK) {
 		keys.insert("a".into(), 1);
 		keys.insert("b".into(), 2);
@@ -6,6 +6,7 @@ use crate::kvs::{Key, Transaction, Val};
 use crate::sql::{Object, Value};
 #[cfg(debug_assertions)]
 use ahash::HashSet;
+use fst::Automaton;
 use revision::{revisioned, Revisioned};
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
@@ -327,6 +328,85 @@ where
 		Ok(None)
 	}
 
+	/// Collects every key starting with the given prefix, along with its payload
+	pub(in crate::idx) async fn search_by_prefix(
+		&self,
+		tx: &Transaction,
+		store: &BTreeStore<BK>,
+		prefix: &Key,
+	) -> Result<Vec<(Key, Payload)>, Error> {
+		let mut res = Vec::new();
+		let mut next_nodes: VecDeque<NodeId> = self.state.root.into_iter().collect();
+		while let Some(node_id) = next_nodes.pop_front() {
+			let current = store.get_node(tx, node_id).await?;
+			let keys = current.n.keys();
+			res.extend(keys.collect_with_prefix(prefix)?);
+			if let BTreeNode::Internal(keys, children) = &current.n {
+				// Visit the children which can contain keys starting with the prefix
+				let mut child_idx = keys.get_child_idx(prefix);
+				while let Some(child) = children.get(child_idx) {
+					next_nodes.push_back(*child);
+					match keys.get_key(child_idx) {
+						Some(key) if key.starts_with(prefix) => child_idx += 1,
+						_ => break,
+					}
+				}
+			}
+		}
+		Ok(res)
+	}
+
+	/// Collects every key accepted by the given automaton, along with its payload.
+	/// Subtrees whose keys share a common prefix which cannot lead to a match are skipped.
+	pub(in crate::idx) async fn search_by_automaton<A: Automaton>(
+		&self,
+		tx: &Transaction,
+		store: &BTreeStore<BK>,
+		aut: &A,
+	) -> Result<Vec<(Key, Payload)>, Error> {
+		let mut res = Vec::new();
+		let mut next_nodes: VecDeque<(NodeId, Option<Key>, Option<Key>)> =
+			self.state.root.into_iter().map(|id| (id, None, None)).collect();
+		while let Some((node_id, lower, upper)) = next_nodes.pop_front() {
+			let current = store.get_node(tx, node_id).await?;
+			res.extend(current.n.keys().collect_matching(aut)?);
+			if let BTreeNode::Internal(keys, children) = &current.n {
+				for (i, child) in children.iter().enumerate() {
+					// The keys of the child are bounded by the keys of the parent
+					let child_lower = if i == 0 {
+						lower.clone()
+					} else {
+						keys.get_key(i - 1)
+					};
+					let child_upper = if i < keys.len() as usize {
+						keys.get_key(i)
+					} else {
+						upper.clone()
+					};
+					if let (Some(l), Some(u)) = (&child_lower, &child_upper) {
+						let common = l.iter().zip(u).take_while(|(a, b)| a == b).count();
+						if !Self::can_match_prefix(aut, &l[..common]) {
+							continue;
+						}
+					}
+					next_nodes.push_back((*child, child_lower, child_upper));
+				}
+			}
+		}
+		Ok(res)
+	}
+
+	fn can_match_prefix<A: Automaton>(aut: &A, prefix: &[u8]) -> bool {
+		let mut state = aut.start();
+		for b in prefix {
+			if !aut.can_match(&state) {
+				return false;
+			}
+			state = aut.accept(&state, *b);
+		}
+		aut.can_match(&state)
+	}
+
 	pub async fn insert(
 		&mut self,
 		tx: &Transaction,
@@ -66,6 +66,11 @@ impl HnswDocs {
 		}
 	}
 
+	/// The number of documents in the index
+	pub(in crate::idx) fn len(&self) -> u64 {
+		self.state.next_doc_id - self.state.available.len()
+	}
+
 	fn next_doc_id(&mut self) -> DocId {
 		self.state_updated = true;
 		if let Some(doc_id) = self.state.available.iter().next() {
@@ -9,7 +9,7 @@ use crate::idx::trees::knn::{KnnResult, KnnResultBuilder};
 use crate::idx::trees::vector::{SharedVector, Vector};
 use crate::idx::IndexKeyBase;
 use crate::kvs::Transaction;
-use crate::sql::index::{HnswParams, VectorType};
+use crate::sql::index::{HnswParams, VectorQuantization, VectorType};
 use crate::sql::{Id, Number, Value};
 #[cfg(debug_assertions)]
 use ahash::HashMap;
@@ -19,6 +19,7 @@ use std::collections::VecDeque;
 pub struct HnswIndex {
 	dim: usize,
 	vector_type: VectorType,
+	quantization: Option<VectorQuantization>,
 	hnsw: HnswFlavor,
 	docs: HnswDocs,
 	vec_docs: VecDocs,
@@ -80,6 +81,7 @@ impl HnswIndex {
 		Ok(Self {
 			dim: p.dimension as usize,
 			vector_type: p.vector_type,
+			quantization: p.quantization,
 			hnsw: HnswFlavor::new(ikb.clone(), p),
 			docs: HnswDocs::new(tx, tb, ikb.clone()).await?,
 			vec_docs: VecDocs::new(ikb),
@@ -101,8 +103,8 @@ impl HnswIndex {
 			// Extract the vector
 			let vector = Vector::try_from_value(self.vector_type, self.dim, value)?;
 			vector.check_dimension(self.dim)?;
-			// Insert the vector
-			self.vec_docs.insert(tx, vector, doc_id, &mut self.hnsw).await?;
+			// Insert the vector, compressed if the index is quantized
+			self.vec_docs.insert(tx, self.quantize(vector), doc_id, &mut self.hnsw).await?;
 		}
 		self.docs.finish(tx).await?;
 		Ok(())
@@ -121,8 +123,8 @@ impl HnswIndex {
 				// Extract the vector
 				let vector = Vector::try_from_value(self.vector_type, self.dim, v)?;
 				vector.check_dimension(self.dim)?;
-				// Remove the vector
-				self.vec_docs.remove(tx, &vector, doc_id, &mut self.hnsw).await?;
+				// Remove the vector, compressed if the index is quantized
+				self.vec_docs.remove(tx, &self.quantize(vector), doc_id, &mut self.hnsw).await?;
 			}
 			self.docs.finish(tx).await?;
 		}
@@ -134,6 +136,24 @@ impl HnswIndex {
 		self.hnsw.check_state(tx).await
 	}
 
+	/// The number of documents in the index
+	pub(crate) fn docs_count(&self) -> u64 {
+		self.docs.len()
+	}
+
+	/// Whether the index holds quantized vectors, in which case the results
+	/// of a search have to be rescored with the full precision vectors
+	pub(crate) fn is_quantized(&self) -> bool {
+		self.quantization.is_some()
+	}
+
+	fn quantize(&self, vector: Vector) -> Vector {
+		match self.quantization {
+			Some(q) => vector.quantize(q),
+			None => vector,
+		}
+	}
+
 	pub async fn knn_search(
 		&self,
 		tx: &Transaction,
@@ -144,9 +164,15 @@ impl HnswIndex {
 		mut chk: HnswConditionChecker<'_>,
 	) -> Result<VecDeque<KnnIteratorResult>, Error> {
 		// Extract the vector
-		let vector: SharedVector = Vector::try_from_vector(self.vector_type, pt)?.into();
+		let vector = Vector::try_from_vector(self.vector_type, pt)?;
 		vector.check_dimension(self.dim)?;
-		let search = HnswSearch::new(vector, k, ef);
+		// With quantization, the `ef` closest candidates are returned to be rescored
+		let k = if self.is_quantized() {
+			k.max(ef)
+		} else {
+			k
+		};
+		let search = HnswSearch::new(self.quantize(vector).into(), k, ef);
 		// Do the search
 		let result = self.search(tx, stk, &search, &mut chk).await?;
 		let res = chk.convert_result(tx, &self.docs, result.docs).await?;
@@ -569,6 +569,7 @@ mod tests {
 			efc as u16,
 			extend_candidates,
 			keep_pruned_connections,
+			None,
 		)
 	}
 
@@ -723,6 +723,8 @@ pub(super) mod tests {
 				Self::I64(a) => !a.iter().any(|a| !a.is_zero()),
 				Self::I32(a) => !a.iter().any(|a| !a.is_zero()),
 				Self::I16(a) => !a.iter().any(|a| !a.is_zero()),
+				Self::I8(a, _) => !a.iter().any(|a| !a.is_zero()),
+				Self::Binary(a, _) => !a.iter().any(|a| !a.is_zero()),
 			}
 		}
 	}
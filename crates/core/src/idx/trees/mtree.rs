@@ -24,15 +24,20 @@ use crate::idx::trees::store::{
 use crate::idx::trees::vector::{SharedVector, Vector};
 use crate::idx::{IndexKeyBase, VersionedStore};
 use crate::kvs::{Key, Transaction, TransactionType, Val};
-use crate::sql::index::{Distance, MTreeParams, VectorType};
+use crate::sql::index::{Distance, MTreeParams, VectorQuantization, VectorType};
 use crate::sql::{Number, Object, Thing, Value};
 
+/// With quantization, the number of candidates searched for each requested
+/// neighbour, to be rescored with the full precision vectors
+const QUANTIZED_CANDIDATES: usize = 4;
+
 #[non_exhaustive]
 pub struct MTreeIndex {
 	ixs: IndexStores,
 	state_key: Key,
 	dim: usize,
 	vector_type: VectorType,
+	quantization: Option<VectorQuantization>,
 	store: MTreeStore,
 	doc_ids: Arc<RwLock<DocIds>>,
 	mtree: Arc<RwLock<MTree>>,
@@ -76,6 +81,7 @@ impl MTreeIndex {
 			state_key,
 			dim: p.dimension as usize,
 			vector_type: p.vector_type,
+			quantization: p.quantization,
 			doc_ids,
 			mtree,
 			store,
@@ -100,7 +106,8 @@ impl MTreeIndex {
 			// Extract the vector
 			let vector = Vector::try_from_value(self.vector_type, self.dim, v)?;
 			vector.check_dimension(self.dim)?;
-			// Insert the vector in the index
+			let vector = self.quantize(vector);
+			// Insert the vector in the index, compressed if the index is quantized
 			mtree.insert(stk, txn, &mut self.store, vector.into(), doc_id).await?;
 		}
 		drop(mtree);
@@ -124,7 +131,8 @@ impl MTreeIndex {
 				// Extract the vector
 				let vector = Vector::try_from_value(self.vector_type, self.dim, v)?;
 				vector.check_dimension(self.dim)?;
-				// Remove the vector
+				let vector = self.quantize(vector);
+				// Remove the vector, compressed if the index is quantized
 				mtree.delete(stk, txn, &mut self.store, vector.into(), doc_id).await?;
 			}
 			drop(mtree);
@@ -143,10 +151,16 @@ impl MTreeIndex {
 		// Extract the vector
 		let vector = Vector::try_from_vector(self.vector_type, v)?;
 		vector.check_dimension(self.dim)?;
+		// With quantization, more candidates are returned to be rescored
+		let k = if self.is_quantized() {
+			k.saturating_mul(QUANTIZED_CANDIDATES)
+		} else {
+			k
+		};
 		// Build the search context
 		let search = MTreeSearchContext {
 			ctx,
-			pt: vector.into(),
+			pt: self.quantize(vector).into(),
 			k,
 			store: &self.store,
 		};
@@ -162,6 +176,19 @@ impl MTreeIndex {
 		res
 	}
 
+	/// Whether the tree is built on quantized vectors, the distances of its
+	/// results are then only approximate
+	pub(crate) fn is_quantized(&self) -> bool {
+		self.quantization.is_some()
+	}
+
+	fn quantize(&self, vector: Vector) -> Vector {
+		match self.quantization {
+			Some(q) => vector.quantize(q),
+			None => vector,
+		}
+	}
+
 	pub(crate) async fn statistics(&self, tx: &Transaction) -> Result<MtStatistics, Error> {
 		Ok(MtStatistics {
 			doc_ids: self.doc_ids.read().await.statistics(tx).await?,
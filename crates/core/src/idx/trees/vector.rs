@@ -1,7 +1,7 @@
 use crate::err::Error;
 use crate::fnc::util::math::ToFloat;
 use crate::idx::VersionedStore;
-use crate::sql::index::{Distance, VectorType};
+use crate::sql::index::{Distance, VectorQuantization, VectorType};
 use crate::sql::{Number, Value};
 use ahash::AHasher;
 use ahash::HashSet;
@@ -26,9 +26,13 @@ pub enum Vector {
 	I64(Array1<i64>),
 	I32(Array1<i32>),
 	I16(Array1<i16>),
+	/// A scalar quantized vector, with the scale restoring the original values
+	I8(Array1<i8>, f32),
+	/// A binary quantized vector, packing one sign bit per dimension, with the dimension
+	Binary(Array1<u64>, usize),
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 #[non_exhaustive]
 pub enum SerializedVector {
@@ -37,6 +41,10 @@ pub enum SerializedVector {
 	I64(Vec<i64>),
 	I32(Vec<i32>),
 	I16(Vec<i16>),
+	#[revision(start = 2)]
+	I8(Vec<i8>, f32),
+	#[revision(start = 2)]
+	Binary(Vec<u64>, usize),
 }
 
 impl VersionedStore for SerializedVector {}
@@ -49,6 +57,8 @@ impl From<&Vector> for SerializedVector {
 			Vector::I64(v) => Self::I64(v.to_vec()),
 			Vector::I32(v) => Self::I32(v.to_vec()),
 			Vector::I16(v) => Self::I16(v.to_vec()),
+			Vector::I8(v, s) => Self::I8(v.to_vec(), *s),
+			Vector::Binary(v, d) => Self::Binary(v.to_vec(), *d),
 		}
 	}
 }
@@ -61,6 +71,8 @@ impl From<SerializedVector> for Vector {
 			SerializedVector::I64(v) => Self::I64(Array1::from_vec(v)),
 			SerializedVector::I32(v) => Self::I32(Array1::from_vec(v)),
 			SerializedVector::I16(v) => Self::I16(Array1::from_vec(v)),
+			SerializedVector::I8(v, s) => Self::I8(Array1::from_vec(v), s),
+			SerializedVector::Binary(v, d) => Self::Binary(Array1::from_vec(v), d),
 		}
 	}
 }
@@ -394,6 +406,15 @@ impl Hash for Vector {
 				let h = v.iter().fold(0, |acc, &x| acc ^ x);
 				state.write_i16(h);
 			}
+			Vector::I8(v, s) => {
+				let h = v.iter().fold(0, |acc, &x| acc ^ x);
+				state.write_i8(h);
+				state.write_u32(s.to_bits());
+			}
+			Vector::Binary(v, _) => {
+				let h = v.iter().fold(0, |acc, &x| acc ^ x);
+				state.write_u64(h);
+			}
 		}
 	}
 }
@@ -414,6 +435,8 @@ impl From<&Vector> for Value {
 			Vector::I64(a) => a.iter().map(|i| Number::Int(*i)).collect(),
 			Vector::I32(a) => a.iter().map(|i| Number::Int(*i as i64)).collect(),
 			Vector::I16(a) => a.iter().map(|i| Number::Int(*i as i64)).collect(),
+			Vector::I8(a, s) => a.iter().map(|i| Number::Float((*i as f32 * s) as f64)).collect(),
+			Vector::Binary(a, _) => a.iter().map(|i| Number::Int(*i as i64)).collect(),
 		};
 		Value::from(vec)
 	}
@@ -518,9 +541,93 @@ impl Vector {
 			Self::I64(v) => v.len(),
 			Self::I32(v) => v.len(),
 			Self::I16(v) => v.len(),
+			Self::I8(v, _) => v.len(),
+			Self::Binary(_, d) => *d,
 		}
 	}
 
+	fn to_float_vec(&self) -> Vec<f64> {
+		match self {
+			Self::F64(v) => v.to_vec(),
+			Self::F32(v) => v.iter().map(|x| *x as f64).collect(),
+			Self::I64(v) => v.iter().map(|x| *x as f64).collect(),
+			Self::I32(v) => v.iter().map(|x| *x as f64).collect(),
+			Self::I16(v) => v.iter().map(|x| *x as f64).collect(),
+			Self::I8(v, s) => v.iter().map(|x| (*x as f32 * s) as f64).collect(),
+			Self::Binary(v, d) => (0..*d)
+				.map(|i| {
+					if v[i / 64] >> (i % 64) & 1 == 1 {
+						1.0
+					} else {
+						-1.0
+					}
+				})
+				.collect(),
+		}
+	}
+
+	/// Compresses the vector. Scalar quantization maps each value on a signed byte,
+	/// relative to the largest absolute value of the vector.
+	/// Binary quantization only keeps a bit per dimension, set when the value is positive.
+	pub(super) fn quantize(&self, q: VectorQuantization) -> Self {
+		if matches!(self, Self::I8(..) | Self::Binary(..)) {
+			return self.clone();
+		}
+		let v = self.to_float_vec();
+		match q {
+			VectorQuantization::Scalar => {
+				let max = v.iter().fold(0.0_f64, |acc, x| acc.max(x.abs()));
+				let scale = if max > 0.0 {
+					max / i8::MAX as f64
+				} else {
+					1.0
+				};
+				let codes = v.iter().map(|x| (x / scale).round() as i8).collect();
+				Self::I8(codes, scale as f32)
+			}
+			VectorQuantization::Binary => {
+				let mut bits = vec![0u64; v.len().div_ceil(64)];
+				for (i, x) in v.iter().enumerate() {
+					if *x > 0.0 {
+						bits[i / 64] |= 1 << (i % 64);
+					}
+				}
+				Self::Binary(Array1::from_vec(bits), v.len())
+			}
+		}
+	}
+
+	fn dequantize(&self) -> Self {
+		match self {
+			Self::I8(v, s) => Self::F32(v.mapv(|x| x as f32 * s)),
+			_ => self.clone(),
+		}
+	}
+
+	#[inline]
+	fn euclidean_i8(a: &Array1<i8>, sa: f32, b: &Array1<i8>, sb: f32) -> f64 {
+		Zip::from(a)
+			.and(b)
+			.fold(0.0_f32, |acc, x, y| acc + (*x as f32 * sa - *y as f32 * sb).powi(2))
+			.sqrt() as f64
+	}
+
+	#[inline]
+	fn cosine_i8(a: &Array1<i8>, b: &Array1<i8>) -> f64 {
+		// The scales are positive, they cancel out
+		let (dot, norm_a, norm_b) =
+			Zip::from(a).and(b).fold((0_i64, 0_i64, 0_i64), |(d, na, nb), x, y| {
+				let (x, y) = (*x as i64, *y as i64);
+				(d + x * y, na + x * x, nb + y * y)
+			});
+		1.0 - dot as f64 / ((norm_a as f64).sqrt() * (norm_b as f64).sqrt())
+	}
+
+	#[inline]
+	fn hamming_binary(a: &Array1<u64>, b: &Array1<u64>) -> f64 {
+		Zip::from(a).and(b).fold(0, |acc, x, y| acc + (x ^ y).count_ones()) as f64
+	}
+
 	pub(super) fn check_expected_dimension(current: usize, expected: usize) -> Result<(), Error> {
 		if current != expected {
 			Err(Error::InvalidVectorDimension {
@@ -539,6 +646,22 @@ impl Vector {
 
 impl Distance {
 	pub(super) fn calculate(&self, a: &Vector, b: &Vector) -> f64 {
+		match (self, a, b) {
+			// Binary quantized vectors are always compared on their differing bits
+			(_, Vector::Binary(a, _), Vector::Binary(b, _)) => {
+				return Vector::hamming_binary(a, b);
+			}
+			(Distance::Euclidean, Vector::I8(a, sa), Vector::I8(b, sb)) => {
+				return Vector::euclidean_i8(a, *sa, b, *sb);
+			}
+			(Distance::Cosine, Vector::I8(a, _), Vector::I8(b, _)) => {
+				return Vector::cosine_i8(a, b);
+			}
+			(_, Vector::I8(..), _) | (_, _, Vector::I8(..)) => {
+				return self.calculate(&a.dequantize(), &b.dequantize());
+			}
+			_ => {}
+		}
 		match self {
 			Distance::Chebyshev => a.chebyshev_distance(b),
 			Distance::Cosine => a.cosine_distance(b),
@@ -556,7 +679,8 @@ impl Distance {
 mod tests {
 	use crate::idx::trees::knn::tests::{get_seed_rnd, new_random_vec, RandomItemGenerator};
 	use crate::idx::trees::vector::{SharedVector, Vector};
-	use crate::sql::index::{Distance, VectorType};
+	use crate::sql::index::{Distance, VectorQuantization, VectorType};
+	use ndarray::Array1;
 
 	fn test_distance(dist: Distance, a1: &[f64], a2: &[f64], res: f64) {
 		// Convert the arrays to Vec<Number>
@@ -651,4 +775,27 @@ mod tests {
 		test_distance_collection(Distance::Pearson, 100, 1536);
 		test_distance(Distance::Pearson, &[1.0, 2.0, 3.0], &[2.0, 3.0, 4.0], 1.0);
 	}
+
+	#[test]
+	fn test_quantization() {
+		let v = Vector::F64(Array1::from_vec(vec![-2.0, 0.5, 1.0, 0.0]));
+		let w = Vector::F64(Array1::from_vec(vec![-1.0, 1.0, -1.0, 0.0]));
+
+		let (qv, qw) =
+			(v.quantize(VectorQuantization::Scalar), w.quantize(VectorQuantization::Scalar));
+		assert_eq!(qv, Vector::I8(Array1::from_vec(vec![-127, 32, 64, 0]), 2.0 / 127.0));
+		assert_eq!(qv.len(), 4);
+		let exact = Distance::Euclidean.calculate(&v, &w);
+		assert!((Distance::Euclidean.calculate(&qv, &qw) - exact).abs() < 0.01);
+		let exact = Distance::Cosine.calculate(&v, &w);
+		assert!((Distance::Cosine.calculate(&qv, &qw) - exact).abs() < 0.01);
+		let exact = Distance::Manhattan.calculate(&v, &w);
+		assert!((Distance::Manhattan.calculate(&qv, &qw) - exact).abs() < 0.01);
+
+		let (qv, qw) =
+			(v.quantize(VectorQuantization::Binary), w.quantize(VectorQuantization::Binary));
+		assert_eq!(qv, Vector::Binary(Array1::from_vec(vec![0b0110]), 4));
+		assert_eq!(qv.len(), 4);
+		assert_eq!(Distance::Euclidean.calculate(&qv, &qw), 1.0);
+	}
 }
@@ -76,6 +76,10 @@ pub enum Category {
 	DatabaseModel,
 	/// crate::key::database::pa             /*{ns}*{db}!pa{pa}
 	DatabaseParameter,
+	/// crate::key::database::sb             /*{ns}*{db}!sb{sq}
+	DatabaseSequenceBatch,
+	/// crate::key::database::sq             /*{ns}*{db}!sq{sq}
+	DatabaseSequence,
 	/// crate::key::database::tb             /*{ns}*{db}!tb{tb}
 	DatabaseTable,
 	/// crate::key::database::ts             /*{ns}*{db}!ts{ts}
@@ -101,6 +105,14 @@ pub enum Category {
 	IndexDefinition,
 	/// crate::key::table::lq                /*{ns}*{db}*{tb}!lq{lq}
 	TableLiveQuery,
+	/// crate::key::table::er                /*{ns}*{db}*{tb}!er{id}
+	TableExpiryRecord,
+	/// crate::key::table::ex                /*{ns}*{db}*{tb}!ex{ts}{id}
+	TableExpiry,
+	/// crate::key::table::vd                /*{ns}*{db}*{tb}!vd{ft}{id}{st}{sk}
+	TableViewDependency,
+	/// crate::key::table::vp                /*{ns}*{db}*{tb}!vp
+	TableViewProgress,
 	///
 	/// ------------------------------
 	///
@@ -128,6 +140,10 @@ pub enum Category {
 	IndexBTreeNodeTerms,
 	/// crate::key::index::bu                /*{ns}*{db}*{tb}+{ix}!bu{id}
 	IndexTerms,
+	/// crate::key::index::gh                /*{ns}*{db}*{tb}+{ix}!gh{cell}{id}
+	IndexGeohashCell,
+	/// crate::key::index::gn                /*{ns}*{db}*{tb}+{ix}!gn{id}
+	IndexGeohashNonPoint,
 	/// crate::key::index::he                /*{ns}*{db}*{tb}+{ix}!he{id}
 	IndexHnswElements,
 	/// crate::key::index::hd                /*{ns}*{db}*{tb}+{ix}!hd{id}
@@ -188,6 +204,8 @@ impl Display for Category {
 			Self::DatabaseFunction => "DatabaseFunction",
 			Self::DatabaseModel => "DatabaseModel",
 			Self::DatabaseParameter => "DatabaseParameter",
+			Self::DatabaseSequenceBatch => "DatabaseSequenceBatch",
+			Self::DatabaseSequence => "DatabaseSequence",
 			Self::DatabaseTable => "DatabaseTable",
 			Self::DatabaseTableIdentifier => "DatabaseTableIdentifier",
 			Self::DatabaseTimestamp => "DatabaseTimestamp",
@@ -200,6 +218,10 @@ impl Display for Category {
 			Self::TableView => "TableView",
 			Self::IndexDefinition => "IndexDefinition",
 			Self::TableLiveQuery => "TableLiveQuery",
+			Self::TableExpiryRecord => "TableExpiryRecord",
+			Self::TableExpiry => "TableExpiry",
+			Self::TableViewDependency => "TableViewDependency",
+			Self::TableViewProgress => "TableViewProgress",
 			Self::IndexRoot => "IndexRoot",
 			Self::IndexTermDocList => "IndexTermDocList",
 			Self::IndexBTreeNode => "IndexBTreeNode",
@@ -212,6 +234,8 @@ impl Display for Category {
 			Self::IndexFullTextState => "IndexFullTextState",
 			Self::IndexBTreeNodeTerms => "IndexBTreeNodeTerms",
 			Self::IndexTerms => "IndexTerms",
+			Self::IndexGeohashCell => "IndexGeohashCell",
+			Self::IndexGeohashNonPoint => "IndexGeohashNonPoint",
 			Self::IndexHnswElements => "IndexHnswElements",
 			Self::IndexHnswDocIds => "IndexHnswDocIds",
 			Self::IndexHnswThings => "IndexHnswThings",
@@ -6,6 +6,8 @@ pub mod cg;
 pub mod fc;
 pub mod ml;
 pub mod pa;
+pub mod sb;
+pub mod sq;
 pub mod tb;
 pub mod ti;
 pub mod ts;
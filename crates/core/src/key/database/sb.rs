@@ -0,0 +1,64 @@
+//! Stores the batch allocation state of a DEFINE SEQUENCE
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Sb<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub sq: &'a str,
+}
+
+pub fn new<'a>(ns: &'a str, db: &'a str, sq: &'a str) -> Sb<'a> {
+	Sb::new(ns, db, sq)
+}
+
+impl Categorise for Sb<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseSequenceBatch
+	}
+}
+
+impl<'a> Sb<'a> {
+	pub fn new(ns: &'a str, db: &'a str, sq: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'!',
+			_d: b's',
+			_e: b'b',
+			sq,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Sb::new(
+			"testns",
+			"testdb",
+			"testsq",
+		);
+		let enc = Sb::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\0*testdb\0!sbtestsq\0");
+
+		let dec = Sb::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
@@ -0,0 +1,139 @@
+//! Stores the geohash cells of a spatial index
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use crate::sql::Id;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+/// Gh is used to store a record id under each geohash cell covered by its geometry.
+///
+/// As geohash cells share the prefix of their parent cells, a range scan
+/// on a cell prefix returns the records stored in any of its child cells.
+/// The value of the gh is the longitude and latitude of the geometry
+/// when it is a point, and empty otherwise.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Gh<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+	pub ix: &'a str,
+	_e: u8,
+	_f: u8,
+	_g: u8,
+	pub cell: &'a str,
+	pub id: Id,
+}
+
+impl Categorise for Gh<'_> {
+	fn categorise(&self) -> Category {
+		Category::IndexGeohashCell
+	}
+}
+
+impl<'a> Gh<'a> {
+	pub fn new(ns: &'a str, db: &'a str, tb: &'a str, ix: &'a str, cell: &'a str, id: Id) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'+',
+			ix,
+			_e: b'!',
+			_f: b'g',
+			_g: b'h',
+			cell,
+			id,
+		}
+	}
+}
+
+fn root(ns: &str, db: &str, tb: &str, ix: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns, db, tb, ix).encode().unwrap();
+	k.extend_from_slice(b"!gh");
+	k
+}
+
+pub fn prefix(ns: &str, db: &str, tb: &str, ix: &str) -> Vec<u8> {
+	let mut k = root(ns, db, tb, ix);
+	k.push(0x00);
+	k
+}
+
+pub fn suffix(ns: &str, db: &str, tb: &str, ix: &str) -> Vec<u8> {
+	let mut k = root(ns, db, tb, ix);
+	k.push(0xff);
+	k
+}
+
+/// Returns the start of the range of records stored in the cell or in any of its child cells
+pub fn prefix_area(ns: &str, db: &str, tb: &str, ix: &str, cell: &str) -> Vec<u8> {
+	let mut k = root(ns, db, tb, ix);
+	k.extend_from_slice(cell.as_bytes());
+	k
+}
+
+/// Returns the end of the range of records stored in the cell or in any of its child cells
+pub fn suffix_area(ns: &str, db: &str, tb: &str, ix: &str, cell: &str) -> Vec<u8> {
+	let mut k = prefix_area(ns, db, tb, ix, cell);
+	k.push(0xff);
+	k
+}
+
+/// Returns the start of the range of records stored in exactly this cell
+pub fn prefix_cell(ns: &str, db: &str, tb: &str, ix: &str, cell: &str) -> Vec<u8> {
+	let mut k = prefix_area(ns, db, tb, ix, cell);
+	k.push(0x00);
+	k
+}
+
+/// Returns the end of the range of records stored in exactly this cell
+pub fn suffix_cell(ns: &str, db: &str, tb: &str, ix: &str, cell: &str) -> Vec<u8> {
+	let mut k = prefix_cell(ns, db, tb, ix, cell);
+	k.push(0xff);
+	k
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		let val = Gh::new("testns", "testdb", "testtb", "testix", "gcpvj0", "testid".into());
+		let enc = Gh::encode(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/*testns\0*testdb\0*testtb\0+testix\0!ghgcpvj0\0\0\0\0\x01testid\0",
+			"{}",
+			String::from_utf8_lossy(&enc)
+		);
+
+		let dec = Gh::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn area() {
+		let beg = super::prefix_area("testns", "testdb", "testtb", "testix", "gcp");
+		let end = super::suffix_area("testns", "testdb", "testtb", "testix", "gcp");
+		assert_eq!(beg, b"/*testns\0*testdb\0*testtb\0+testix\0!ghgcp");
+		assert_eq!(end, b"/*testns\0*testdb\0*testtb\0+testix\0!ghgcp\xff");
+	}
+
+	#[test]
+	fn cell() {
+		let beg = super::prefix_cell("testns", "testdb", "testtb", "testix", "gcp");
+		let end = super::suffix_cell("testns", "testdb", "testtb", "testix", "gcp");
+		assert_eq!(beg, b"/*testns\0*testdb\0*testtb\0+testix\0!ghgcp\0");
+		assert_eq!(end, b"/*testns\0*testdb\0*testtb\0+testix\0!ghgcp\0\xff");
+	}
+}
@@ -0,0 +1,86 @@
+//! Stores the records of a spatial index which have no geometry
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use crate::sql::Id;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+/// Gn is used to store the records which are not a point in a spatial index.
+///
+/// This covers the records with a value which is not a geometry, and the
+/// records with a geometry other than a point, as these records have no
+/// distance to a point. The value of the gn is empty.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Gn<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+	pub ix: &'a str,
+	_e: u8,
+	_f: u8,
+	_g: u8,
+	pub id: Id,
+}
+
+impl Categorise for Gn<'_> {
+	fn categorise(&self) -> Category {
+		Category::IndexGeohashNonPoint
+	}
+}
+
+impl<'a> Gn<'a> {
+	pub fn new(ns: &'a str, db: &'a str, tb: &'a str, ix: &'a str, id: Id) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'+',
+			ix,
+			_e: b'!',
+			_f: b'g',
+			_g: b'n',
+			id,
+		}
+	}
+}
+
+pub fn prefix(ns: &str, db: &str, tb: &str, ix: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns, db, tb, ix).encode().unwrap();
+	k.extend_from_slice(b"!gn\x00");
+	k
+}
+
+pub fn suffix(ns: &str, db: &str, tb: &str, ix: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns, db, tb, ix).encode().unwrap();
+	k.extend_from_slice(b"!gn\xff");
+	k
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		let val = Gn::new("testns", "testdb", "testtb", "testix", "testid".into());
+		let enc = Gn::encode(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/*testns\0*testdb\0*testtb\0+testix\0!gn\0\0\0\x01testid\0",
+			"{}",
+			String::from_utf8_lossy(&enc)
+		);
+
+		let dec = Gn::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
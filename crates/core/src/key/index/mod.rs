@@ -11,6 +11,8 @@ pub mod bp;
 pub mod bs;
 pub mod bt;
 pub mod bu;
+pub mod gh;
+pub mod gn;
 pub mod hd;
 pub mod he;
 pub mod hi;
@@ -31,6 +31,8 @@
 /// crate::key::database::fc             /*{ns}*{db}!fn{fc}
 /// crate::key::database::ml             /*{ns}*{db}!ml{ml}{vn}
 /// crate::key::database::pa             /*{ns}*{db}!pa{pa}
+/// crate::key::database::sb             /*{ns}*{db}!sb{sq}
+/// crate::key::database::sq             /*{ns}*{db}!sq{sq}
 /// crate::key::database::tb             /*{ns}*{db}!tb{tb}
 /// crate::key::database::ti             /+{ns id}*{db id}!ti
 /// crate::key::database::ts             /*{ns}*{db}!ts{ts}
@@ -42,11 +44,15 @@
 /// crate::key::database::access::gr     /*{ns}*{db}&{ac}!gr{gr}
 ///
 /// crate::key::table::all               /*{ns}*{db}*{tb}
+/// crate::key::table::er                /*{ns}*{db}*{tb}!er{id}
 /// crate::key::table::ev                /*{ns}*{db}*{tb}!ev{ev}
+/// crate::key::table::ex                /*{ns}*{db}*{tb}!ex{ts}{id}
 /// crate::key::table::fd                /*{ns}*{db}*{tb}!fd{fd}
 /// crate::key::table::ft                /*{ns}*{db}*{tb}!ft{ft}
 /// crate::key::table::ix                /*{ns}*{db}*{tb}!ix{ix}
 /// crate::key::table::lq                /*{ns}*{db}*{tb}!lq{lq}
+/// crate::key::table::vd                /*{ns}*{db}*{tb}!vd{ft}{id}{st}{sk}
+/// crate::key::table::vp                /*{ns}*{db}*{tb}!vp
 ///
 /// crate::key::index::all               /*{ns}*{db}*{tb}+{ix}
 /// crate::key::index::bc                /*{ns}*{db}*{tb}+{ix}!bc{id}
@@ -60,6 +66,8 @@
 /// crate::key::index::bs                /*{ns}*{db}*{tb}+{ix}!bs
 /// crate::key::index::bt                /*{ns}*{db}*{tb}+{ix}!bt{id}
 /// crate::key::index::bu                /*{ns}*{db}*{tb}+{ix}!bu{id}
+/// crate::key::index::gh                /*{ns}*{db}*{tb}+{ix}!gh{cell}{id}
+/// crate::key::index::gn                /*{ns}*{db}*{tb}+{ix}!gn{id}
 /// crate::key::index                    /*{ns}*{db}*{tb}+{ix}*{fd}{id}
 ///
 /// crate::key::change                   /*{ns}*{db}#{ts}
@@ -0,0 +1,97 @@
+//! Stores the time at which a record in a table expires
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use crate::sql::Id;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+/// Ex is used to order the expiring records of a table by their expiry time.
+///
+/// The timestamp is the number of nanoseconds since the unix epoch,
+/// so that a range scan returns the records which expire soonest.
+/// The value of the ex is empty.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Ex<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub ts: u64,
+	pub id: Id,
+}
+
+pub fn new<'a>(ns: &'a str, db: &'a str, tb: &'a str, ts: u64, id: &Id) -> Ex<'a> {
+	Ex::new(ns, db, tb, ts, id.to_owned())
+}
+
+pub fn prefix(ns: &str, db: &str, tb: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns, db, tb).encode().unwrap();
+	k.extend_from_slice(b"!ex\x00");
+	k
+}
+
+/// Returns the end of the range of records which expire at or before the timestamp
+pub fn suffix_ts(ns: &str, db: &str, tb: &str, ts: u64) -> Vec<u8> {
+	let mut k = super::all::new(ns, db, tb).encode().unwrap();
+	k.extend_from_slice(b"!ex");
+	k.extend_from_slice(&ts.saturating_add(1).to_be_bytes());
+	k
+}
+
+pub fn suffix(ns: &str, db: &str, tb: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns, db, tb).encode().unwrap();
+	k.extend_from_slice(b"!ex\xff\xff\xff\xff\xff\xff\xff\xff\xff");
+	k
+}
+
+impl Categorise for Ex<'_> {
+	fn categorise(&self) -> Category {
+		Category::TableExpiry
+	}
+}
+
+impl<'a> Ex<'a> {
+	pub fn new(ns: &'a str, db: &'a str, tb: &'a str, ts: u64, id: Id) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'!',
+			_e: b'e',
+			_f: b'x',
+			ts,
+			id,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		let val = Ex::new("testns", "testdb", "testtb", 10, "testid".into());
+		let enc = Ex::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\0*testdb\0*testtb\0!ex\0\0\0\0\0\0\0\x0a\0\0\0\x01testid\0");
+
+		let dec = Ex::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn suffix_ts() {
+		let val = super::suffix_ts("testns", "testdb", "testtb", 10);
+		assert_eq!(val, b"/*testns\0*testdb\0*testtb\0!ex\0\0\0\0\0\0\0\x0b")
+	}
+}
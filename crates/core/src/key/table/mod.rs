@@ -1,6 +1,10 @@
 pub mod all;
+pub mod er;
 pub mod ev;
+pub mod ex;
 pub mod fd;
 pub mod ft;
 pub mod ix;
 pub mod lq;
+pub mod vd;
+pub mod vp;
@@ -0,0 +1,200 @@
+//! Stores a record which a table view row was projected through
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use crate::sql::Id;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+struct Prefix<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub ft: &'a str,
+}
+
+impl<'a> Prefix<'a> {
+	fn new(ns: &'a str, db: &'a str, tb: &'a str, ft: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'!',
+			_e: b'v',
+			_f: b'd',
+			ft,
+		}
+	}
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+struct PrefixId<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub ft: &'a str,
+	pub id: Id,
+}
+
+impl<'a> PrefixId<'a> {
+	fn new(ns: &'a str, db: &'a str, tb: &'a str, ft: &'a str, id: &Id) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'!',
+			_e: b'v',
+			_f: b'd',
+			ft,
+			id: id.to_owned(),
+		}
+	}
+}
+
+/// Vd is stored under the table of a linked or related record, and points
+/// to each record of a view source table which was projected through it.
+///
+/// When the linked record changes, the view rows of the source records are
+/// computed again. The value of the vd is empty.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Vd<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub ft: &'a str,
+	pub id: Id,
+	pub st: &'a str,
+	pub sk: Id,
+}
+
+pub fn new<'a>(
+	ns: &'a str,
+	db: &'a str,
+	tb: &'a str,
+	ft: &'a str,
+	id: &Id,
+	st: &'a str,
+	sk: &Id,
+) -> Vd<'a> {
+	Vd::new(ns, db, tb, ft, id.to_owned(), st, sk.to_owned())
+}
+
+pub fn prefix(ns: &str, db: &str, tb: &str, ft: &str) -> Vec<u8> {
+	let mut k = Prefix::new(ns, db, tb, ft).encode().unwrap();
+	k.extend_from_slice(&[0x00]);
+	k
+}
+
+pub fn suffix(ns: &str, db: &str, tb: &str, ft: &str) -> Vec<u8> {
+	let mut k = Prefix::new(ns, db, tb, ft).encode().unwrap();
+	k.extend_from_slice(&[0xff]);
+	k
+}
+
+pub fn prefix_id(ns: &str, db: &str, tb: &str, ft: &str, id: &Id) -> Vec<u8> {
+	let mut k = PrefixId::new(ns, db, tb, ft, id).encode().unwrap();
+	k.extend_from_slice(&[0x00]);
+	k
+}
+
+pub fn suffix_id(ns: &str, db: &str, tb: &str, ft: &str, id: &Id) -> Vec<u8> {
+	let mut k = PrefixId::new(ns, db, tb, ft, id).encode().unwrap();
+	k.extend_from_slice(&[0xff]);
+	k
+}
+
+impl Categorise for Vd<'_> {
+	fn categorise(&self) -> Category {
+		Category::TableViewDependency
+	}
+}
+
+impl<'a> Vd<'a> {
+	pub fn new(
+		ns: &'a str,
+		db: &'a str,
+		tb: &'a str,
+		ft: &'a str,
+		id: Id,
+		st: &'a str,
+		sk: Id,
+	) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'!',
+			_e: b'v',
+			_f: b'd',
+			ft,
+			id,
+			st,
+			sk,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		let val = Vd::new(
+			"testns",
+			"testdb",
+			"testtb",
+			"testft",
+			"testid".into(),
+			"testst",
+			"testsk".into(),
+		);
+		let enc = Vd::encode(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/*testns\0*testdb\0*testtb\0!vdtestft\0\0\0\0\x01testid\0testst\0\0\0\0\x01testsk\0"
+		);
+
+		let dec = Vd::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn prefix_id() {
+		let val = super::prefix_id("testns", "testdb", "testtb", "testft", &"testid".into());
+		assert_eq!(val, b"/*testns\0*testdb\0*testtb\0!vdtestft\0\0\0\0\x01testid\0\0")
+	}
+}
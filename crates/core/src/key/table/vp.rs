@@ -0,0 +1,63 @@
+//! Stores the change feed progress of an ASYNC table view
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+/// Vp is stored under the view table, and holds the versionstamp of the
+/// last change feed entry which has been applied to the view.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Vp<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+}
+
+pub fn new<'a>(ns: &'a str, db: &'a str, tb: &'a str) -> Vp<'a> {
+	Vp::new(ns, db, tb)
+}
+
+impl Categorise for Vp<'_> {
+	fn categorise(&self) -> Category {
+		Category::TableViewProgress
+	}
+}
+
+impl<'a> Vp<'a> {
+	pub fn new(ns: &'a str, db: &'a str, tb: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'!',
+			_e: b'v',
+			_f: b'p',
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		let val = Vp::new("testns", "testdb", "testtb");
+		let enc = Vp::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\0*testdb\0*testtb\0!vp");
+
+		let dec = Vp::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
@@ -0,0 +1,267 @@
+use super::Transaction;
+use crate::err::Error;
+use crate::key::database::ts;
+use crate::vs;
+use async_channel::Sender;
+use serde::{Deserialize, Serialize};
+
+/// The comment which starts the first line of a backup
+const PREFIX: &str = "-- SURREALDB BACKUP ";
+
+/// Describes the contents of a backup, as written on its first line.
+///
+/// A full backup is a SurrealQL export of a consistent snapshot of the
+/// database, so it can also be restored with a regular import. An
+/// incremental backup holds the binary change sets written to the change
+/// feed after the versionstamp of a previous backup, so it can only be
+/// taken when the database, or every one of its tables, has a `CHANGEFEED`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Header {
+	/// The versionstamp of the previous backup, if this is an incremental backup
+	pub since: Option<u64>,
+	/// The versionstamp of the last change included in this backup
+	pub until: u64,
+	/// The time at which this backup was taken, in seconds since the Unix epoch
+	pub time: u64,
+	/// The change feed timestamps, in seconds since the Unix epoch, and the
+	/// versionstamps which were current at those times
+	pub timestamps: Vec<(u64, u64)>,
+}
+
+impl Header {
+	/// Encodes this header as the first line of a backup
+	pub fn to_line(&self) -> Vec<u8> {
+		// A header only holds numbers, so it always serializes
+		let json = serde_json::to_string(self).unwrap_or_default();
+		bytes!(format!("{PREFIX}{json}"))
+	}
+	/// Decodes a header from the first line of a backup
+	pub fn from_line(line: &[u8]) -> Result<Self, Error> {
+		let line = std::str::from_utf8(line).map_err(|e| Error::InvalidBackup(e.to_string()))?;
+		match line.trim_end().strip_prefix(PREFIX) {
+			Some(json) => {
+				serde_json::from_str(json).map_err(|e| Error::InvalidBackup(e.to_string()))
+			}
+			None => Err(Error::InvalidBackup("the backup header is missing".to_owned())),
+		}
+	}
+	/// Calculates the versionstamp before which changes happened before a
+	/// point in time, or `None` if all of the changes happened before it.
+	pub(crate) fn cutoff(&self, time: Option<u64>) -> Result<Option<u64>, Error> {
+		match time {
+			// All of the changes happened before the point in time
+			None => Ok(None),
+			Some(time) if time >= self.time => Ok(None),
+			// A snapshot can not be restored to an earlier time
+			Some(_) if self.since.is_none() => Err(Error::InvalidBackup(
+				"the backup was taken after the point in time to restore".to_owned(),
+			)),
+			// Changes before the latest timestamp which precedes the point in time
+			Some(time) => Ok(Some(
+				self.timestamps
+					.iter()
+					.filter(|(ts, _)| *ts <= time)
+					.map(|(_, vs)| *vs)
+					.max()
+					.unwrap_or_default(),
+			)),
+		}
+	}
+}
+
+impl Transaction {
+	/// Writes a backup of the database, or of the changes since a previous backup.
+	///
+	/// The backup is read from this transaction, so that all tables, and the
+	/// recorded versionstamp, come from the same consistent snapshot.
+	pub async fn backup(
+		&self,
+		ns: &str,
+		db: &str,
+		since: Option<u64>,
+		time: u64,
+		chn: Sender<Vec<u8>>,
+	) -> Result<(), Error> {
+		// An incremental backup needs all of the changes since the previous backup
+		if let Some(since) = since {
+			self.check_changefeeds(ns, db).await?;
+			self.check_retained(ns, db, since, time).await?;
+		}
+		// Output the backup header
+		let header = Header {
+			since,
+			until: self.last_versionstamp(ns, db).await?.max(since.unwrap_or_default()),
+			time,
+			timestamps: match since {
+				Some(since) => self.versionstamp_timestamps(ns, db, since).await?,
+				None => Vec::new(),
+			},
+		};
+		chn.send(header.to_line()).await?;
+		match since {
+			// Output the change sets since the previous backup
+			Some(since) => self.sync(ns, db, None, since + 1, chn).await,
+			// Output a full snapshot of the database
+			None => self.export(ns, db, Default::default(), chn).await,
+		}
+	}
+
+	/// Reads the last versionstamp which was allocated to the database, which
+	/// is at least the versionstamp of the last change in the change feed
+	async fn last_versionstamp(&self, ns: &str, db: &str) -> Result<u64, Error> {
+		let key = crate::key::database::vs::new(ns, db);
+		match self.get(key, None).await? {
+			Some(v) => match <[u8; 10]>::try_from(v.as_slice()) {
+				Ok(v) => Ok(vs::versionstamp_to_u64(&v)),
+				Err(_) => Err(Error::Internal("versionstamp is not 10 bytes".to_string())),
+			},
+			None => Ok(0),
+		}
+	}
+
+	/// Checks that the changes to every table are recorded in the change
+	/// feed, as other changes can not be included in an incremental backup
+	async fn check_changefeeds(&self, ns: &str, db: &str) -> Result<(), Error> {
+		if self.get_db(ns, db).await?.changefeed.is_some() {
+			return Ok(());
+		}
+		for tb in self.all_tb(ns, db, None).await?.iter() {
+			if tb.changefeed.is_none() {
+				return Err(Error::ChangefeedRequired {
+					table: tb.name.to_raw(),
+				});
+			}
+		}
+		Ok(())
+	}
+
+	/// Checks that the change feed still holds all of the changes after a
+	/// versionstamp, as the older changes are removed once they expire
+	async fn check_retained(&self, ns: &str, db: &str, since: u64, time: u64) -> Result<(), Error> {
+		let dbs = self.get_db(ns, db).await?;
+		if let Some(watermark) = crate::cf::watermark(self, time, ns, &dbs).await? {
+			if since + 1 < vs::versionstamp_to_u64(&watermark) {
+				return Err(Error::FullBackupRequired {
+					since,
+				});
+			}
+		}
+		Ok(())
+	}
+
+	/// Lists the change feed timestamps recorded after a versionstamp
+	async fn versionstamp_timestamps(
+		&self,
+		ns: &str,
+		db: &str,
+		since: u64,
+	) -> Result<Vec<(u64, u64)>, Error> {
+		let beg = ts::prefix(ns, db);
+		let end = ts::suffix(ns, db);
+		let mut out = Vec::new();
+		for (k, v) in self.getr(beg..end, None).await? {
+			let Ok(v) = <[u8; 10]>::try_from(v.as_slice()) else {
+				continue;
+			};
+			let v = vs::versionstamp_to_u64(&v);
+			if v > since {
+				out.push((ts::Ts::decode(&k)?.ts, v));
+			}
+		}
+		Ok(out)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::dbs::Session;
+	use crate::kvs::{Datastore, LockType::Optimistic, TransactionType::Read};
+
+	#[test]
+	fn header_line() {
+		let h = Header {
+			since: Some(3),
+			until: 9,
+			time: 100,
+			timestamps: vec![(90, 5), (95, 8)],
+		};
+		let line = h.to_line();
+		assert!(line.ends_with(b"\n"));
+		assert_eq!(Header::from_line(&line).unwrap(), h);
+		assert!(Header::from_line(b"-- ------").is_err());
+	}
+
+	#[test]
+	fn header_cutoff() {
+		let h = Header {
+			since: Some(3),
+			until: 9,
+			time: 100,
+			timestamps: vec![(90, 5), (95, 8)],
+		};
+		assert_eq!(h.cutoff(None).unwrap(), None);
+		assert_eq!(h.cutoff(Some(100)).unwrap(), None);
+		assert_eq!(h.cutoff(Some(96)).unwrap(), Some(8));
+		assert_eq!(h.cutoff(Some(92)).unwrap(), Some(5));
+		assert_eq!(h.cutoff(Some(10)).unwrap(), Some(0));
+		let h = Header {
+			since: None,
+			..h
+		};
+		assert!(h.cutoff(Some(10)).is_err());
+	}
+
+	#[tokio::test]
+	async fn incremental_backup_retention() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test");
+		ds.execute("DEFINE TABLE user CHANGEFEED 1s; CREATE user:1", &ses, None).await.unwrap();
+		ds.changefeed_process_at(1000).await.unwrap();
+		ds.execute("CREATE user:2", &ses, None).await.unwrap();
+		ds.changefeed_process_at(1010).await.unwrap();
+		let txn = ds.transaction(Read, Optimistic).await.unwrap();
+		let last = txn.last_versionstamp("test", "test").await.unwrap();
+		// The changes before the expiry window may have been removed
+		let res = txn.check_retained("test", "test", 0, 1010).await;
+		assert!(
+			matches!(
+				res,
+				Err(Error::FullBackupRequired {
+					since: 0
+				})
+			),
+			"{res:?}"
+		);
+		txn.check_retained("test", "test", last, 1010).await.unwrap();
+		txn.cancel().await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn incremental_backup_changefeeds() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test");
+		ds.execute("DEFINE TABLE user CHANGEFEED 1h; CREATE user:1", &ses, None).await.unwrap();
+		let (chn, _rcv) = async_channel::unbounded();
+		let txn = ds.transaction(Read, Optimistic).await.unwrap();
+		txn.backup("test", "test", Some(0), 1000, chn.clone()).await.unwrap();
+		txn.cancel().await.unwrap();
+		// Changes to a table without a change feed can not be backed up
+		ds.execute("CREATE post:1", &ses, None).await.unwrap();
+		let txn = ds.transaction(Read, Optimistic).await.unwrap();
+		let res = txn.backup("test", "test", Some(0), 1000, chn.clone()).await;
+		assert!(
+			matches!(
+				&res,
+				Err(Error::ChangefeedRequired {
+					table
+				}) if table == "post"
+			),
+			"{res:?}"
+		);
+		// A full backup includes every table
+		txn.backup("test", "test", None, 1000, chn).await.unwrap();
+		txn.cancel().await.unwrap();
+	}
+}
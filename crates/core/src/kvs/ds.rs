@@ -1,4 +1,7 @@
+use super::backup;
 use super::export;
+use super::import;
+use super::sync;
 use super::tr::Transactor;
 use super::tx::Transaction;
 use super::version::Version;
@@ -9,7 +12,8 @@ use crate::dbs::capabilities::NetTarget;
 use crate::dbs::capabilities::{MethodTarget, RouteTarget};
 use crate::dbs::node::Timestamp;
 use crate::dbs::{
-	Attach, Capabilities, Executor, Notification, Options, Response, Session, Variables,
+	Attach, Capabilities, Executor, Notification, OpenTransaction, Options, QueryChunk, Response,
+	Session, Variables,
 };
 use crate::err::Error;
 #[cfg(feature = "jwks")]
@@ -23,13 +27,14 @@ use crate::kvs::clock::SizedClock;
 use crate::kvs::clock::SystemClock;
 #[cfg(not(target_arch = "wasm32"))]
 use crate::kvs::index::IndexBuilder;
+use crate::kvs::sequences::Sequences;
 use crate::kvs::{LockType, LockType::*, TransactionType, TransactionType::*};
 use crate::sql::{statements::DefineUserStatement, Base, Query, Value};
 use crate::syn;
 use crate::syn::parser::{Parser, PartialResult};
 use async_channel::{Receiver, Sender};
 use bytes::Bytes;
-use futures::{Future, Stream};
+use futures::{Future, Stream, StreamExt};
 use reblessive::{Stack, TreeStack};
 use std::fmt;
 #[cfg(storage)]
@@ -82,6 +87,8 @@ pub struct Datastore {
 	// The index asynchronous builder
 	#[cfg(not(target_arch = "wasm32"))]
 	index_builder: IndexBuilder,
+	// The sequences batch allocator
+	sequences: Sequences,
 	#[cfg(feature = "jwks")]
 	// The JWKS object cache
 	jwks_cache: Arc<RwLock<JwksCache>>,
@@ -266,6 +273,7 @@ impl Datastore {
 			index_stores: Default::default(),
 			#[cfg(not(target_arch = "wasm32"))]
 			index_builder: IndexBuilder::new(self.transaction_factory.clone()),
+			sequences: Sequences::new(self.transaction_factory.clone()),
 			#[cfg(feature = "jwks")]
 			jwks_cache: Arc::new(Default::default()),
 			#[cfg(storage)]
@@ -427,7 +435,8 @@ impl Datastore {
 				capabilities: Capabilities::default(),
 				index_stores: IndexStores::default(),
 				#[cfg(not(target_arch = "wasm32"))]
-				index_builder: IndexBuilder::new(tf),
+				index_builder: IndexBuilder::new(tf.clone()),
+				sequences: Sequences::new(tf),
 				#[cfg(feature = "jwks")]
 				jwks_cache: Arc::new(RwLock::new(JwksCache::new())),
 				#[cfg(storage)]
@@ -486,6 +495,16 @@ impl Datastore {
 		self
 	}
 
+	#[cfg(feature = "parquet")]
+	/// The directory used for temporary files, which defaults to the system one
+	pub(crate) fn temporary_directory(&self) -> std::path::PathBuf {
+		#[cfg(storage)]
+		if let Some(dir) = &self.temporary_directory {
+			return dir.as_ref().clone();
+		}
+		std::env::temp_dir()
+	}
+
 	pub fn index_store(&self) -> &IndexStores {
 		&self.index_stores
 	}
@@ -684,6 +703,10 @@ impl Datastore {
 			.as_secs();
 		// Save timestamps for current versionstamps
 		self.changefeed_versionstamp(ts).await?;
+		// Apply new changefeed data to ASYNC table views
+		if let Err(e) = self.changefeed_views().await {
+			warn!(target: TARGET, "Failed to update ASYNC table views: {e}");
+		}
 		// Garbage old changefeed data from all databases
 		self.changefeed_cleanup(ts).await?;
 		// Everything ok
@@ -697,12 +720,45 @@ impl Datastore {
 		trace!(target: TARGET, "Running changefeed garbage collection");
 		// Save timestamps for current versionstamps
 		self.changefeed_versionstamp(ts).await?;
+		// Apply new changefeed data to ASYNC table views
+		if let Err(e) = self.changefeed_views().await {
+			warn!(target: TARGET, "Failed to update ASYNC table views: {e}");
+		}
 		// Garbage old changefeed data from all databases
 		self.changefeed_cleanup(ts).await?;
 		// Everything ok
 		Ok(())
 	}
 
+	/// Run the background task to delete expired table records
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn expiry_process(&self) -> Result<(), Error> {
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Running table record expiry");
+		// Calculate the current system time
+		let ts = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.map_err(|e| {
+				Error::Internal(format!("Clock may have gone backwards: {:?}", e.duration()))
+			})?
+			.as_nanos();
+		// Delete the records which have expired
+		self.expiry_cleanup(ts as u64).await?;
+		// Everything ok
+		Ok(())
+	}
+
+	/// Run the background task to delete expired table records
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn expiry_process_at(&self, ts: u64) -> Result<(), Error> {
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Running table record expiry");
+		// Delete the records which have expired
+		self.expiry_cleanup(ts).await?;
+		// Everything ok
+		Ok(())
+	}
+
 	/// Run the datastore shutdown tasks, perfoming any necessary cleanup
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
 	pub async fn shutdown(&self) -> Result<(), Error> {
@@ -754,6 +810,31 @@ impl Datastore {
 		self.transaction_factory.transaction(write, lock).await
 	}
 
+	/// Starts a write transaction which can be held open across multiple queries
+	///
+	/// ```rust,no_run
+	/// use surrealdb_core::kvs::Datastore;
+	/// use surrealdb_core::err::Error;
+	/// use surrealdb_core::dbs::Session;
+	/// use surrealdb_core::sql::parse;
+	///
+	/// #[tokio::main]
+	/// async fn main() -> Result<(), Error> {
+	///     let ds = Datastore::new("memory").await?;
+	///     let ses = Session::owner().with_ns("test").with_db("test");
+	///     let txn = ds.begin().await?;
+	///     let ast = parse("CREATE person:one;")?;
+	///     ds.process_with_transaction(ast, &ses, None, &txn).await?;
+	///     txn.commit().await?;
+	///     Ok(())
+	/// }
+	/// ```
+	pub async fn begin(&self) -> Result<OpenTransaction, Error> {
+		let txn = self.transaction(Write, Optimistic).await?;
+		let sink = self.notification_channel.as_ref().map(|v| v.0.clone());
+		Ok(OpenTransaction::new(txn, sink))
+	}
+
 	/// Parse and execute an SQL query
 	///
 	/// ```rust,no_run
@@ -944,6 +1025,108 @@ impl Datastore {
 		Executor::execute(self, ctx.freeze(), opt, ast).await
 	}
 
+	/// Execute a pre-parsed SQL query, sending the output through a channel as it is produced
+	///
+	/// The records of any SELECT statement which is not within a transaction
+	/// block, and which has no GROUP, ORDER, SPLIT, or FETCH clause, are sent
+	/// in batches while the statement is running. The response of each
+	/// statement is sent once the statement has completed.
+	///
+	/// ```rust,no_run
+	/// use surrealdb_core::kvs::Datastore;
+	/// use surrealdb_core::err::Error;
+	/// use surrealdb_core::dbs::{QueryChunk, Session};
+	/// use surrealdb_core::sql::parse;
+	///
+	/// #[tokio::main]
+	/// async fn main() -> Result<(), Error> {
+	///     let ds = Datastore::new("memory").await?;
+	///     let ses = Session::owner().with_ns("test").with_db("test");
+	///     let ast = parse("SELECT * FROM person;")?;
+	///     let (send, recv) = async_channel::bounded(1);
+	///     let task = ds.process_chunked(ast, &ses, None, send);
+	///     let read = async {
+	///         while let Ok(chunk) = recv.recv().await {
+	///             if let QueryChunk::Records(_, records) = chunk {
+	///                 println!("{} records", records.len());
+	///             }
+	///         }
+	///     };
+	///     let (res, _) = futures::join!(task, read);
+	///     res
+	/// }
+	/// ```
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn process_chunked(
+		&self,
+		ast: Query,
+		sess: &Session,
+		vars: Variables,
+		chn: Sender<QueryChunk>,
+	) -> Result<(), Error> {
+		// Check if the session has expired
+		if sess.expired() {
+			return Err(Error::ExpiredSession);
+		}
+		// Check if anonymous actors can execute queries when auth is enabled
+		// TODO(sgirones): Check this as part of the authorisation layer
+		self.check_anon(sess).map_err(|_| IamError::NotAllowed {
+			actor: "anonymous".to_string(),
+			action: "process".to_string(),
+			resource: "query".to_string(),
+		})?;
+		// Create a new query options
+		let opt = self.setup_options(sess);
+		// Create a default context
+		let mut ctx = self.setup_ctx()?;
+		// Start an execution context
+		sess.context(&mut ctx);
+		// Store the query variables
+		vars.attach(&mut ctx)?;
+		// Process all statements
+		Executor::execute_chunked(self, ctx.freeze(), opt, ast, chn).await
+	}
+
+	/// Execute a pre-parsed SQL query within a transaction started with [`Datastore::begin`]
+	///
+	/// The changes made by the query are only stored once the transaction
+	/// is committed. If any statement fails, the transaction can no longer
+	/// be committed, and must be cancelled.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn process_with_transaction(
+		&self,
+		ast: Query,
+		sess: &Session,
+		vars: Variables,
+		txn: &OpenTransaction,
+	) -> Result<Vec<Response>, Error> {
+		// Check if the session has expired
+		if sess.expired() {
+			return Err(Error::ExpiredSession);
+		}
+		// Check if anonymous actors can execute queries when auth is enabled
+		// TODO(sgirones): Check this as part of the authorisation layer
+		self.check_anon(sess).map_err(|_| IamError::NotAllowed {
+			actor: "anonymous".to_string(),
+			action: "process".to_string(),
+			resource: "query".to_string(),
+		})?;
+		// Check the transaction is still open
+		if txn.closed().await {
+			return Err(Error::TxFinished);
+		}
+		// Create a new query options
+		let opt = self.setup_options(sess);
+		// Create a default context
+		let mut ctx = self.setup_ctx()?;
+		// Start an execution context
+		sess.context(&mut ctx);
+		// Store the query variables
+		vars.attach(&mut ctx)?;
+		// Process all statements
+		Executor::execute_with_transaction(ctx.freeze(), opt, ast, txn).await
+	}
+
 	/// Ensure a SQL [`Value`] is fully computed
 	///
 	/// ```rust,no_run
@@ -1141,6 +1324,29 @@ impl Datastore {
 		self.execute_import(sess, None, stream).await
 	}
 
+	/// Performs a database import, from SQL or from the records of a single table
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn import_stream_with_config<S>(
+		&self,
+		sess: &Session,
+		stream: S,
+		cfg: import::Config,
+	) -> Result<Vec<Response>, Error>
+	where
+		S: Stream<Item = Result<Bytes, Error>>,
+	{
+		// Import SQL statements as a query
+		if !cfg.format.is_records() {
+			return self.import_stream(sess, stream).await;
+		}
+		// Check if the session has expired
+		if sess.expired() {
+			return Err(Error::ExpiredSession);
+		}
+		// Insert the decoded records in batches
+		import::Importer::new(self, sess, cfg)?.import(stream).await
+	}
+
 	/// Performs a full database export as SQL
 	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
 	pub async fn export(
@@ -1167,17 +1373,174 @@ impl Datastore {
 		}
 		// Retrieve the provided NS and DB
 		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
+		// Check the export configuration before starting the job
+		cfg.check()?;
 		// Create a new readonly transaction
-		let txn = self.transaction(Read, Optimistic).await?;
+		let txn = self.transaction(Read, Optimistic).await?.enclose();
+		// Create the context for evaluating record conditions
+		let opt = self.setup_options(sess);
+		let mut ctx = self.setup_ctx()?;
+		sess.context(&mut ctx);
+		ctx.set_transaction(txn.clone());
+		let ctx = ctx.freeze();
 		// Return an async export job
 		Ok(async move {
 			// Process the export
-			txn.export(&ns, &db, cfg, chn).await?;
+			txn.export_with_context(&ns, &db, cfg, chn, Some((&ctx, &opt))).await?;
 			// Everything ok
 			Ok(())
 		})
 	}
 
+	/// Streams the database change feed, starting from a versionstamp, for replication
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn sync_export(
+		&self,
+		sess: &Session,
+		chn: Sender<Vec<u8>>,
+		tb: Option<String>,
+		since: u64,
+	) -> Result<impl Future<Output = Result<(), Error>>, Error> {
+		// Check if the session has expired
+		if sess.expired() {
+			return Err(Error::ExpiredSession);
+		}
+		// Retrieve the provided NS and DB
+		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
+		// Create a new readonly transaction
+		let txn = self.transaction(Read, Optimistic).await?;
+		// Return an async sync job
+		Ok(async move {
+			// Process the change feed
+			let res = txn.sync(&ns, &db, tb.as_deref(), since, chn).await;
+			// Cancel the readonly transaction
+			txn.cancel().await?;
+			// Return the result
+			res
+		})
+	}
+
+	/// Applies a replication stream, as created by [`Datastore::sync_export`]
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn sync_import(&self, sess: &Session, bytes: &[u8]) -> Result<Vec<Response>, Error> {
+		// Check if the session has expired
+		if sess.expired() {
+			return Err(Error::ExpiredSession);
+		}
+		// Decode the replicated change sets
+		let ast = sync::decode(bytes, None)?;
+		// Apply each change set in its own transaction
+		let res = self.process(ast, sess, None).await?;
+		// Fail if any of the change sets could not be applied
+		if res.iter().any(|v| v.result.is_err()) {
+			let mut errors: Vec<Error> = res.into_iter().filter_map(|v| v.result.err()).collect();
+			// Report the statement which failed, rather than those cancelled with it
+			let pos = errors
+				.iter()
+				.position(|e| {
+					!matches!(e, Error::QueryNotExecuted | Error::QueryNotExecutedDetail { .. })
+				})
+				.unwrap_or(0);
+			return Err(errors.swap_remove(pos));
+		}
+		Ok(res)
+	}
+
+	/// Writes a backup of the database, or of the change feed since a previous backup
+	///
+	/// A backup starts with a [`backup::Header`] line, which records the
+	/// versionstamp of its last change. An incremental backup can be taken
+	/// by passing this versionstamp as the starting point of the next backup.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn backup(
+		&self,
+		sess: &Session,
+		chn: Sender<Vec<u8>>,
+		since: Option<u64>,
+	) -> Result<impl Future<Output = Result<(), Error>>, Error> {
+		// Check if the session has expired
+		if sess.expired() {
+			return Err(Error::ExpiredSession);
+		}
+		// Retrieve the provided NS and DB
+		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
+		// Record the time of the backup, in seconds
+		let time = self.clock_now().await.value / 1000;
+		// Create a new readonly transaction
+		let txn = self.transaction(Read, Optimistic).await?;
+		// Return an async backup job
+		Ok(async move {
+			// Process the backup
+			let res = txn.backup(&ns, &db, since, time, chn).await;
+			// Cancel the readonly transaction
+			txn.cancel().await?;
+			// Return the result
+			res
+		})
+	}
+
+	/// Restores a backup, as created by [`Datastore::backup`]
+	///
+	/// If a point in time is specified, in seconds since the Unix epoch, the
+	/// changes in an incremental backup are only applied up to that time.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn restore<S>(
+		&self,
+		sess: &Session,
+		stream: S,
+		until: Option<u64>,
+	) -> Result<Vec<Response>, Error>
+	where
+		S: Stream<Item = Result<Bytes, Error>>,
+	{
+		// Check if the session has expired
+		if sess.expired() {
+			return Err(Error::ExpiredSession);
+		}
+		let mut stream = pin!(stream);
+		// Read the backup header line
+		let mut buffer = Vec::new();
+		let end = loop {
+			if let Some(i) = buffer.iter().position(|b| *b == b'\n') {
+				break i + 1;
+			}
+			match stream.next().await {
+				Some(bytes) => buffer.extend_from_slice(&bytes?),
+				None => break buffer.len(),
+			}
+		};
+		let header = backup::Header::from_line(&buffer[..end])?;
+		let cutoff = header.cutoff(until)?;
+		buffer.drain(..end);
+		match header.since {
+			// Import the snapshot of the database
+			None => {
+				let rest = futures::stream::once(async { Ok(Bytes::from(buffer)) });
+				self.execute_import(sess, None, rest.chain(stream)).await
+			}
+			// Apply the change sets up to the point in time, as they are received
+			Some(_) => {
+				let mut res = Vec::new();
+				let mut end = false;
+				loop {
+					let (ast, len, done) = sync::decode_partial(&buffer, cutoff, end)?;
+					if len > 0 {
+						buffer.drain(..len);
+						res.extend(self.process(ast, sess, None).await?);
+					}
+					if done || end {
+						break;
+					}
+					match stream.next().await {
+						Some(bytes) => buffer.extend_from_slice(&bytes?),
+						None => end = true,
+					}
+				}
+				Ok(res)
+			}
+		}
+	}
+
 	/// Checks the required permissions level for this session
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self, sess))]
 	pub fn check(&self, sess: &Session, action: Action, resource: Resource) -> Result<(), Error> {
@@ -1203,6 +1566,7 @@ impl Datastore {
 			.with_auth(sess.au.clone())
 			.with_strict(self.strict)
 			.with_auth_enabled(self.auth_enabled)
+			.with_retry(sess.retry)
 	}
 	pub fn setup_ctx(&self) -> Result<MutableContext, Error> {
 		let mut ctx = MutableContext::from_ds(
@@ -1212,6 +1576,7 @@ impl Datastore {
 			self.cache.clone(),
 			#[cfg(not(target_arch = "wasm32"))]
 			self.index_builder.clone(),
+			self.sequences.clone(),
 			#[cfg(storage)]
 			self.temporary_directory.clone(),
 		)?;
@@ -0,0 +1,134 @@
+use crate::cnf::NORMAL_FETCH_SIZE;
+use crate::dbs::Session;
+use crate::err::Error;
+use crate::key::table::ex;
+use crate::kvs::Datastore;
+use crate::kvs::Key;
+use crate::kvs::{LockType::*, TransactionType::*};
+use crate::sql::statements::DeleteStatement;
+use crate::sql::{Output, Thing, Value, Values};
+use reblessive::TreeStack;
+use std::slice;
+
+impl Datastore {
+	/// Deletes all table records which expired at or before the timestamp.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub(crate) async fn expiry_cleanup(&self, ts: u64) -> Result<(), Error> {
+		// Store the tables which have a record expiry
+		let mut tbs = Vec::new();
+		// Create a new transaction
+		let txn = self.transaction(Read, Optimistic).await?;
+		// Fetch all namespaces
+		let nss = catch!(txn, txn.all_ns().await);
+		// Loop over all namespaces
+		for ns in nss.iter() {
+			// Fetch all databases
+			let dbs = catch!(txn, txn.all_db(&ns.name).await);
+			// Loop over all databases
+			for db in dbs.iter() {
+				// Fetch all tables
+				let tables = catch!(txn, txn.all_tb(&ns.name, &db.name, None).await);
+				// Collect the tables with a record expiry
+				for tb in tables.iter().filter(|tb| tb.expire.is_some()) {
+					tbs.push((ns.name.to_raw(), db.name.to_raw(), tb.name.to_raw()));
+				}
+			}
+		}
+		// Cancel the transaction
+		txn.cancel().await?;
+		// Delete the expired records in each table
+		for (ns, db, tb) in tbs.iter() {
+			// A failing table should not stop the cleanup of other tables
+			if let Err(e) = self.expiry_cleanup_table(ns, db, tb, ts).await {
+				warn!("Failed to delete expired records in {ns}/{db}/{tb}: {e}");
+			}
+		}
+		// Everything ok
+		Ok(())
+	}
+
+	/// Deletes the expired records of a table in batches.
+	///
+	/// If a batch fails, its records are deleted one at a time, and any
+	/// record which fails to be deleted, for example because of a failing
+	/// event, is skipped so that it does not stop the expiry of the others.
+	async fn expiry_cleanup_table(
+		&self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		ts: u64,
+	) -> Result<(), Error> {
+		let mut beg = ex::prefix(ns, db, tb);
+		let end = ex::suffix_ts(ns, db, tb, ts);
+		loop {
+			// Fetch a batch of the expired records
+			let txn = self.transaction(Read, Optimistic).await?;
+			let keys =
+				catch!(txn, txn.keys(beg.clone()..end.clone(), *NORMAL_FETCH_SIZE, None).await);
+			txn.cancel().await?;
+			// Check if there are any expired records
+			let Some(last) = keys.last() else {
+				return Ok(());
+			};
+			// Continue after the last record of this batch
+			beg = last.clone();
+			beg.push(0x00);
+			// Delete the expired records
+			if self.expiry_cleanup_batch(ns, db, tb, &keys).await.is_err() {
+				for key in keys.iter() {
+					if let Err(e) =
+						self.expiry_cleanup_batch(ns, db, tb, slice::from_ref(key)).await
+					{
+						warn!("Failed to delete an expired record in {ns}/{db}/{tb}: {e}");
+					}
+				}
+			}
+		}
+	}
+
+	/// Deletes a batch of expired table records.
+	///
+	/// The records are deleted through the document pipeline, so that any
+	/// indexes, table views, events, and live queries are processed as normal.
+	async fn expiry_cleanup_batch(
+		&self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		keys: &[Key],
+	) -> Result<(), Error> {
+		// Create a new transaction
+		let txn = self.transaction(Write, Optimistic).await?.enclose();
+		// Decode the expired record ids
+		let mut what = Vec::with_capacity(keys.len());
+		for k in keys.iter() {
+			let id = catch!(txn, ex::Ex::decode(k)).id;
+			what.push(Value::Thing(Thing::from((tb, id))));
+		}
+		// Setup the delete statement
+		let stm = DeleteStatement {
+			what: Values(what),
+			output: Some(Output::None),
+			..DeleteStatement::default()
+		};
+		// Setup the query options
+		let opt = self.setup_options(&Session::owner().with_ns(ns).with_db(db));
+		// Setup the query context
+		let mut ctx = catch!(txn, self.setup_ctx());
+		ctx.set_transaction(txn.clone());
+		let ctx = ctx.freeze();
+		// Delete the expired records
+		let mut stack = TreeStack::new();
+		let res = stack.enter(|stk| stm.compute(stk, &ctx, &opt, None)).finish().await;
+		catch!(txn, res);
+		// Remove any entries for records which no longer exist
+		for k in keys.iter() {
+			catch!(txn, txn.del(k.clone()).await);
+		}
+		// Commit the changes
+		catch!(txn, txn.commit().await);
+		// Everything ok
+		Ok(())
+	}
+}
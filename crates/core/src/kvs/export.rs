@@ -1,26 +1,45 @@
-use super::Transaction;
+use super::format::{self, Column};
+use super::{Key, Transaction, Val};
 use crate::cnf::EXPORT_BATCH_SIZE;
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::key::thing;
 use crate::sql::paths::EDGE;
 use crate::sql::paths::IN;
 use crate::sql::paths::OUT;
+use crate::sql::statements::DefineSequenceStatement;
 use crate::sql::statements::DefineTableStatement;
+use crate::sql::Cond;
+use crate::sql::IdRange;
+use crate::sql::Object;
+use crate::sql::Table;
+use crate::sql::Thing;
 use crate::sql::Value;
 use async_channel::Sender;
 use chrono::prelude::Utc;
 use chrono::TimeZone;
+use reblessive::TreeStack;
+use std::collections::BTreeMap;
+use std::ops::{Bound, Range};
+
+pub use super::format::Format;
 
 #[derive(Clone, Debug)]
 pub struct Config {
 	pub users: bool,
 	pub accesses: bool,
 	pub params: bool,
+	pub sequences: bool,
 	pub functions: bool,
 	pub analyzers: bool,
 	pub tables: TableConfig,
 	pub versions: bool,
 	pub records: bool,
+	pub schema: bool,
+	pub filters: BTreeMap<String, RecordFilter>,
+	pub format: Format,
 }
 
 impl Default for Config {
@@ -29,11 +48,46 @@ impl Default for Config {
 			users: true,
 			accesses: true,
 			params: true,
+			sequences: true,
 			functions: true,
 			analyzers: true,
 			tables: TableConfig::default(),
 			versions: false,
 			records: true,
+			schema: true,
+			filters: BTreeMap::new(),
+			format: Format::default(),
+		}
+	}
+}
+
+impl Config {
+	/// Checks that the configuration can be exported in the selected format
+	pub(crate) fn check(&self) -> Result<(), Error> {
+		if self.format.is_records() {
+			self.records_table()?;
+		}
+		Ok(())
+	}
+
+	/// Returns the table to export in a record format
+	fn records_table(&self) -> Result<&str, Error> {
+		// Check the format is available
+		self.format.check()?;
+		// Record formats only hold the latest version of each record
+		if self.versions {
+			return Err(Error::InvalidExportConfig(
+				true.into(),
+				format!("versions to be disabled for the {} format", self.format),
+			));
+		}
+		// Record formats hold the records of exactly one table
+		match &self.tables {
+			TableConfig::Some(v) if v.len() == 1 => Ok(v[0].as_str()),
+			v => Err(Error::InvalidExportConfig(
+				v.clone().into(),
+				format!("a single table for the {} format", self.format),
+			)),
 		}
 	}
 }
@@ -44,14 +98,20 @@ impl From<Config> for Value {
 			"users" => config.users.into(),
 			"accesses" => config.accesses.into(),
 			"params" => config.params.into(),
+			"sequences" => config.sequences.into(),
 			"functions" => config.functions.into(),
 			"analyzers" => config.analyzers.into(),
 			"versions" => config.versions.into(),
-			"tables" => match config.tables {
-				TableConfig::All => true.into(),
-				TableConfig::None => false.into(),
-				TableConfig::Some(v) => v.into()
-			}
+			"records" => config.records.into(),
+			"schema" => config.schema.into(),
+			"tables" => config.tables.into(),
+			"filters" => config
+				.filters
+				.into_iter()
+				.map(|(tb, filter)| (tb, filter.into()))
+				.collect::<BTreeMap<String, Value>>()
+				.into(),
+			"format" => config.format.to_string().into(),
 		);
 
 		obj.into()
@@ -85,15 +145,33 @@ impl TryFrom<&Value> for Config {
 				bool_prop!(users);
 				bool_prop!(accesses);
 				bool_prop!(params);
+				bool_prop!(sequences);
 				bool_prop!(functions);
 				bool_prop!(analyzers);
 				bool_prop!(versions);
 				bool_prop!(records);
+				bool_prop!(schema);
 
 				if let Some(v) = obj.get("tables") {
 					config.tables = v.try_into()?;
 				}
 
+				match obj.get("filters") {
+					Some(Value::Object(v)) => {
+						for (tb, v) in v.iter() {
+							config.filters.insert(tb.to_owned(), RecordFilter::try_from((tb, v))?);
+						}
+					}
+					Some(v) => {
+						return Err(Error::InvalidExportConfig(v.to_owned(), "an object".into()))
+					}
+					_ => (),
+				}
+
+				if let Some(v) = obj.get("format") {
+					config.format = v.try_into()?;
+				}
+
 				Ok(config)
 			}
 			v => Err(Error::InvalidExportConfig(v.to_owned(), "an object".into())),
@@ -118,6 +196,16 @@ impl From<bool> for TableConfig {
 	}
 }
 
+impl From<TableConfig> for Value {
+	fn from(value: TableConfig) -> Self {
+		match value {
+			TableConfig::All => true.into(),
+			TableConfig::None => false.into(),
+			TableConfig::Some(v) => v.into(),
+		}
+	}
+}
+
 impl From<Vec<String>> for TableConfig {
 	fn from(value: Vec<String>) -> Self {
 		TableConfig::Some(value)
@@ -156,6 +244,79 @@ impl TryFrom<&Value> for TableConfig {
 	}
 }
 
+/// Restricts the records which are exported from a table
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct RecordFilter {
+	/// Only export the records with an id in this range
+	pub range: Option<IdRange>,
+	/// Only export the records which match this condition
+	pub cond: Option<Cond>,
+}
+
+impl RecordFilter {
+	/// Only export the records with an id in this range
+	pub fn with_range(mut self, range: IdRange) -> Self {
+		self.range = Some(range);
+		self
+	}
+	/// Only export the records which match this condition
+	pub fn with_cond(mut self, cond: Value) -> Self {
+		self.cond = Some(Cond(cond));
+		self
+	}
+}
+
+impl From<RecordFilter> for Value {
+	fn from(value: RecordFilter) -> Self {
+		let mut obj = Object::default();
+		if let Some(range) = value.range {
+			obj.insert("range".to_owned(), range.to_string().into());
+		}
+		if let Some(cond) = value.cond {
+			obj.insert("where".to_owned(), cond.0.to_string().into());
+		}
+		obj.into()
+	}
+}
+
+impl TryFrom<(&String, &Value)> for RecordFilter {
+	type Error = Error;
+	fn try_from((tb, value): (&String, &Value)) -> Result<Self, Self::Error> {
+		let Value::Object(obj) = value else {
+			return Err(Error::InvalidExportConfig(value.to_owned(), "an object".into()));
+		};
+		let mut filter = RecordFilter::default();
+		match obj.get("range") {
+			// The range is parsed as a record id range on the table
+			Some(Value::Strand(v)) => {
+				let rid = crate::syn::thing_with_range(&format!(
+					"{}:{}",
+					Table::from(tb.as_str()),
+					v.as_str()
+				));
+				match rid.map(|v| v.id) {
+					Ok(crate::sql::Id::Range(v)) => filter.range = Some(*v),
+					_ => {
+						return Err(Error::InvalidExportConfig(
+							v.to_owned().into(),
+							"a record id range".into(),
+						))
+					}
+				}
+			}
+			Some(v) => return Err(Error::InvalidExportConfig(v.to_owned(), "a string".into())),
+			None => (),
+		}
+		match obj.get("where") {
+			Some(Value::Strand(v)) => filter.cond = Some(Cond(crate::syn::value(v.as_str())?)),
+			Some(v) => return Err(Error::InvalidExportConfig(v.to_owned(), "a string".into())),
+			None => (),
+		}
+		Ok(filter)
+	}
+}
+
 impl TableConfig {
 	/// Check if we should export tables
 	pub(crate) fn is_any(&self) -> bool {
@@ -171,6 +332,94 @@ impl TableConfig {
 	}
 }
 
+/// Selects the records of a table which are exported
+struct Selection<'a> {
+	/// The key range of the selected records
+	range: Range<Key>,
+	/// The condition which the selected records match
+	cond: Option<&'a Cond>,
+	/// The context in which the condition is evaluated
+	env: Option<(&'a Context, &'a Options)>,
+}
+
+impl<'a> Selection<'a> {
+	fn new(
+		ns: &str,
+		db: &str,
+		tb: &str,
+		cfg: &'a Config,
+		env: Option<(&'a Context, &'a Options)>,
+	) -> Result<Self, Error> {
+		let filter = cfg.filters.get(tb);
+		// Calculate the key range of the records
+		let range = match filter.and_then(|f| f.range.as_ref()) {
+			Some(r) => {
+				let beg = match &r.beg {
+					Bound::Unbounded => thing::prefix(ns, db, tb),
+					Bound::Included(v) => thing::new(ns, db, tb, v).encode()?,
+					Bound::Excluded(v) => {
+						let mut key = thing::new(ns, db, tb, v).encode()?;
+						key.push(0x00);
+						key
+					}
+				};
+				let end = match &r.end {
+					Bound::Unbounded => thing::suffix(ns, db, tb),
+					Bound::Excluded(v) => thing::new(ns, db, tb, v).encode()?,
+					Bound::Included(v) => {
+						let mut key = thing::new(ns, db, tb, v).encode()?;
+						key.push(0x00);
+						key
+					}
+				};
+				beg..end
+			}
+			None => thing::prefix(ns, db, tb)..thing::suffix(ns, db, tb),
+		};
+		let cond = filter.and_then(|f| f.cond.as_ref());
+		if let Some(cond) = cond {
+			// Conditions are evaluated against the latest version of each record
+			if cfg.versions {
+				return Err(Error::InvalidExportConfig(
+					cond.0.clone(),
+					"no record conditions when exporting versions".into(),
+				));
+			}
+			// Conditions are evaluated in the context of the session
+			if env.is_none() {
+				return Err(Error::InvalidExportConfig(
+					cond.0.clone(),
+					"no record conditions when exporting without a session".into(),
+				));
+			}
+		}
+		Ok(Self {
+			range,
+			cond,
+			env,
+		})
+	}
+
+	/// Removes the records which do not match the condition
+	async fn filter(&self, values: Vec<(Key, Val)>) -> Result<Vec<(Key, Val)>, Error> {
+		let (Some(cond), Some((ctx, opt))) = (self.cond, self.env) else {
+			return Ok(values);
+		};
+		let mut stack = TreeStack::new();
+		let mut out = Vec::with_capacity(values.len());
+		for (k, v) in values {
+			let key: thing::Thing = (&k).into();
+			let rid = Thing::from((key.tb, key.id));
+			let doc = CursorDoc::new(Some(rid.into()), None, Value::from(&v));
+			let res = stack.enter(|stk| cond.compute(stk, ctx, opt, Some(&doc))).finish().await?;
+			if res.is_truthy() {
+				out.push((k, v));
+			}
+		}
+		Ok(out)
+	}
+}
+
 impl Transaction {
 	/// Writes the full database contents as binary SQL.
 	pub async fn export(
@@ -180,10 +429,29 @@ impl Transaction {
 		cfg: Config,
 		chn: Sender<Vec<u8>>,
 	) -> Result<(), Error> {
-		// Output USERS, ACCESSES, PARAMS, FUNCTIONS, ANALYZERS
+		self.export_with_context(ns, db, cfg, chn, None).await
+	}
+
+	/// Writes the database contents, evaluating any record conditions in a context.
+	///
+	/// The context must hold this transaction, so that the conditions are
+	/// evaluated against the same snapshot which is being exported.
+	pub(crate) async fn export_with_context(
+		&self,
+		ns: &str,
+		db: &str,
+		cfg: Config,
+		chn: Sender<Vec<u8>>,
+		env: Option<(&Context, &Options)>,
+	) -> Result<(), Error> {
+		// Output the records of a single table
+		if cfg.format.is_records() {
+			return self.export_records(ns, db, &cfg, &chn, env).await;
+		}
+		// Output USERS, ACCESSES, PARAMS, SEQUENCES, FUNCTIONS, ANALYZERS
 		self.export_metadata(&cfg, &chn, ns, db).await?;
 		// Output TABLES
-		self.export_tables(ns, db, &cfg, &chn).await?;
+		self.export_tables(ns, db, &cfg, &chn, env).await?;
 		Ok(())
 	}
 
@@ -197,6 +465,11 @@ impl Transaction {
 		// Output OPTIONS
 		self.export_section("OPTION", vec!["OPTION IMPORT"], chn).await?;
 
+		// Skip the definitions when only exporting data
+		if !cfg.schema {
+			return Ok(());
+		}
+
 		// Output USERS
 		if cfg.users {
 			let users = self.all_db_users(ns, db).await?;
@@ -215,6 +488,20 @@ impl Transaction {
 			self.export_section("PARAMS", params.to_vec(), chn).await?;
 		}
 
+		// Output SEQUENCES
+		if cfg.sequences {
+			let mut sequences = Vec::new();
+			for sq in self.all_db_sequences(ns, db).await?.iter() {
+				// Start from the next unreserved value, so imported sequences never repeat values
+				let start = super::sequences::next_unreserved(self, ns, db, sq).await?;
+				sequences.push(DefineSequenceStatement {
+					start,
+					..sq.clone()
+				});
+			}
+			self.export_section("SEQUENCES", sequences, chn).await?;
+		}
+
 		// Output FUNCTIONS
 		if cfg.functions {
 			let functions = self.all_db_functions(ns, db).await?;
@@ -259,6 +546,7 @@ impl Transaction {
 		db: &str,
 		cfg: &Config,
 		chn: &Sender<Vec<u8>>,
+		env: Option<(&Context, &Options)>,
 	) -> Result<(), Error> {
 		if !cfg.tables.is_any() {
 			return Ok(());
@@ -270,10 +558,13 @@ impl Transaction {
 				continue;
 			}
 
-			self.export_table_structure(ns, db, table, chn).await?;
+			if cfg.schema {
+				self.export_table_structure(ns, db, table, chn).await?;
+			}
 
 			if cfg.records {
-				self.export_table_data(ns, db, table, cfg, chn).await?;
+				let sel = Selection::new(ns, db, &table.name, cfg, env)?;
+				self.export_table_data(table, cfg, &sel, chn).await?;
 			}
 		}
 
@@ -317,10 +608,9 @@ impl Transaction {
 
 	async fn export_table_data(
 		&self,
-		ns: &str,
-		db: &str,
 		table: &DefineTableStatement,
 		cfg: &Config,
+		sel: &Selection<'_>,
 		chn: &Sender<Vec<u8>>,
 	) -> Result<(), Error> {
 		chn.send(bytes!("-- ------------------------------")).await?;
@@ -328,9 +618,7 @@ impl Transaction {
 		chn.send(bytes!("-- ------------------------------")).await?;
 		chn.send(bytes!("")).await?;
 
-		let beg = crate::key::thing::prefix(ns, db, &table.name);
-		let end = crate::key::thing::suffix(ns, db, &table.name);
-		let mut next = Some(beg..end);
+		let mut next = Some(sel.range.clone());
 
 		while let Some(rng) = next {
 			if cfg.versions {
@@ -350,6 +638,11 @@ impl Transaction {
 				if values.is_empty() {
 					break;
 				}
+				// Skip the records which do not match the condition
+				let values = sel.filter(values).await?;
+				if values.is_empty() {
+					continue;
+				}
 				self.export_regular_data(values, chn).await?;
 			}
 			// Fetch more records
@@ -556,4 +849,149 @@ impl Transaction {
 
 		Ok(())
 	}
+
+	/// Writes the records of a single table in a record format.
+	///
+	/// The records are read and written in batches of `EXPORT_BATCH_SIZE`.
+	/// The CSV and Parquet formats first scan the table to determine the
+	/// columns, so that the header or schema can be written up front.
+	async fn export_records(
+		&self,
+		ns: &str,
+		db: &str,
+		cfg: &Config,
+		chn: &Sender<Vec<u8>>,
+		env: Option<(&Context, &Options)>,
+	) -> Result<(), Error> {
+		let tb = cfg.records_table()?;
+		let sel = Selection::new(ns, db, tb, cfg, env)?;
+		match cfg.format {
+			Format::Csv => self.export_csv(&sel, chn).await,
+			#[cfg(feature = "parquet")]
+			Format::Parquet => self.export_parquet(&sel, chn).await,
+			_ => self.export_jsonl(&sel, chn).await,
+		}
+	}
+
+	/// Fetches the next non-empty batch of selected records from a table
+	async fn export_batch(
+		&self,
+		next: &mut Option<Range<Key>>,
+		sel: &Selection<'_>,
+	) -> Result<Vec<Object>, Error> {
+		while let Some(rng) = next.take() {
+			let batch = self.batch(rng, *EXPORT_BATCH_SIZE, true, None).await?;
+			*next = batch.next;
+			if batch.values.is_empty() {
+				break;
+			}
+			let records: Vec<_> = sel
+				.filter(batch.values)
+				.await?
+				.into_iter()
+				.filter_map(|(_, v)| match Value::from(&v) {
+					Value::Object(v) => Some(v),
+					_ => None,
+				})
+				.collect();
+			if !records.is_empty() {
+				return Ok(records);
+			}
+		}
+		Ok(Vec::new())
+	}
+
+	/// Determines the columns of a table, by scanning all of the selected records
+	async fn export_columns(
+		&self,
+		sel: &Selection<'_>,
+	) -> Result<Vec<(String, Option<Column>)>, Error> {
+		let mut cols = Vec::new();
+		let mut next = Some(sel.range.clone());
+		loop {
+			let records = self.export_batch(&mut next, sel).await?;
+			if records.is_empty() {
+				break;
+			}
+			cols = format::columns(cols, &records);
+		}
+		Ok(cols)
+	}
+
+	/// Writes the records of a table as JSON lines
+	async fn export_jsonl(&self, sel: &Selection<'_>, chn: &Sender<Vec<u8>>) -> Result<(), Error> {
+		let mut next = Some(sel.range.clone());
+		loop {
+			let records = self.export_batch(&mut next, sel).await?;
+			if records.is_empty() {
+				break;
+			}
+			let mut out = Vec::new();
+			for v in records {
+				out.extend(bytes!(Value::from(v).into_json()));
+			}
+			chn.send(out).await?;
+		}
+		Ok(())
+	}
+
+	/// Writes the records of a table as CSV, with a header row
+	async fn export_csv(&self, sel: &Selection<'_>, chn: &Sender<Vec<u8>>) -> Result<(), Error> {
+		let error = |e| Error::InvalidFormatData("csv".to_owned(), e);
+		let cols = self.export_columns(sel).await?;
+		// Output the header row
+		let mut writer = csv::Writer::from_writer(Vec::new());
+		writer.write_record(cols.iter().map(|(c, _)| c)).map_err(|e| error(e.to_string()))?;
+		let mut next = Some(sel.range.clone());
+		loop {
+			let records = self.export_batch(&mut next, sel).await?;
+			for v in records.iter() {
+				let row =
+					cols.iter().map(|(c, _)| v.get(c).map(format::csv_cell).unwrap_or_default());
+				writer.write_record(row).map_err(|e| error(e.to_string()))?;
+			}
+			// Output the rows written so far
+			let out = writer.into_inner().map_err(|e| error(e.to_string()))?;
+			if !out.is_empty() {
+				chn.send(out).await?;
+			}
+			if records.is_empty() {
+				break;
+			}
+			writer = csv::Writer::from_writer(Vec::new());
+		}
+		Ok(())
+	}
+
+	/// Writes the records of a table as a Parquet file, with one row group per batch
+	#[cfg(feature = "parquet")]
+	async fn export_parquet(
+		&self,
+		sel: &Selection<'_>,
+		chn: &Sender<Vec<u8>>,
+	) -> Result<(), Error> {
+		use super::format::parquet::{encode, error, schema};
+		// Columns without any values are stored as strings
+		let cols: Vec<_> = self
+			.export_columns(sel)
+			.await?
+			.into_iter()
+			.map(|(c, k)| (c, k.unwrap_or(Column::Text)))
+			.collect();
+		let schema = schema(&cols);
+		let mut writer = parquet::arrow::ArrowWriter::try_new(Vec::new(), schema.clone(), None)
+			.map_err(error)?;
+		let mut next = Some(sel.range.clone());
+		loop {
+			let records = self.export_batch(&mut next, sel).await?;
+			if records.is_empty() {
+				break;
+			}
+			writer.write(&encode(&schema, &cols, &records)?).map_err(error)?;
+			writer.flush().map_err(error)?;
+			chn.send(std::mem::take(writer.inner_mut())).await?;
+		}
+		chn.send(writer.into_inner().map_err(error)?).await?;
+		Ok(())
+	}
 }
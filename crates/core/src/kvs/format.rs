@@ -0,0 +1,316 @@
+//! The data formats used when importing and exporting table records.
+use crate::err::Error;
+use crate::sql::{Number, Object, Value};
+use std::fmt;
+use std::str::FromStr;
+
+/// The format of the data being imported or exported
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum Format {
+	/// SurrealQL statements, covering the whole database
+	#[default]
+	Surrealql,
+	/// One JSON object per line, covering the records of a single table
+	Jsonl,
+	/// Comma-separated values with a header row, covering the records of a single table
+	Csv,
+	/// An Apache Parquet file, covering the records of a single table
+	Parquet,
+}
+
+impl Format {
+	/// Check if this format only holds the records of a single table
+	pub fn is_records(&self) -> bool {
+		!matches!(self, Self::Surrealql)
+	}
+	/// Check if this format is available in this build
+	pub(crate) fn check(&self) -> Result<(), Error> {
+		match self {
+			#[cfg(not(feature = "parquet"))]
+			Self::Parquet => Err(Error::UnsupportedFormat(self.to_string())),
+			_ => Ok(()),
+		}
+	}
+}
+
+impl fmt::Display for Format {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Surrealql => f.write_str("surrealql"),
+			Self::Jsonl => f.write_str("jsonl"),
+			Self::Csv => f.write_str("csv"),
+			Self::Parquet => f.write_str("parquet"),
+		}
+	}
+}
+
+impl FromStr for Format {
+	type Err = Error;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.to_ascii_lowercase().as_str() {
+			"surrealql" | "surql" | "sql" => Ok(Self::Surrealql),
+			"jsonl" | "ndjson" => Ok(Self::Jsonl),
+			"csv" => Ok(Self::Csv),
+			"parquet" => Ok(Self::Parquet),
+			_ => Err(Error::UnsupportedFormat(s.to_owned())),
+		}
+	}
+}
+
+impl TryFrom<&Value> for Format {
+	type Error = Error;
+	fn try_from(value: &Value) -> Result<Self, Self::Error> {
+		match value {
+			Value::Strand(v) => v.as_str().parse(),
+			v => Err(Error::InvalidExportConfig(v.to_owned(), "a string".into())),
+		}
+	}
+}
+
+/// The type of a column, as inferred from the record values
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Column {
+	Bool,
+	Int,
+	Float,
+	Text,
+	Json,
+}
+
+impl Column {
+	/// The column type needed to store a value, if the value is not empty
+	pub(crate) fn of(v: &Value) -> Option<Self> {
+		match v {
+			Value::None | Value::Null => None,
+			Value::Bool(_) => Some(Self::Bool),
+			Value::Number(Number::Int(_)) => Some(Self::Int),
+			Value::Number(Number::Float(_)) => Some(Self::Float),
+			Value::Strand(_) | Value::Thing(_) => Some(Self::Text),
+			_ => Some(Self::Json),
+		}
+	}
+	/// The column type able to store the values of both column types
+	pub(crate) fn merge(self, other: Self) -> Self {
+		match (self, other) {
+			(a, b) if a == b => a,
+			(Self::Int, Self::Float) | (Self::Float, Self::Int) => Self::Float,
+			_ => Self::Json,
+		}
+	}
+}
+
+/// Infers the columns of a set of records, with the `id` column first
+pub(crate) fn columns<'a>(
+	mut cols: Vec<(String, Option<Column>)>,
+	records: impl IntoIterator<Item = &'a Object>,
+) -> Vec<(String, Option<Column>)> {
+	for record in records {
+		for (k, v) in record.iter() {
+			let kind = Column::of(v);
+			match cols.iter_mut().find(|(c, _)| c == k) {
+				Some((_, col)) => {
+					*col = match (*col, kind) {
+						(Some(a), Some(b)) => Some(a.merge(b)),
+						(a, b) => a.or(b),
+					}
+				}
+				None => cols.push((k.to_owned(), kind)),
+			}
+		}
+	}
+	// Output the record id before any other column
+	cols.sort_by(|(a, _), (b, _)| (a != "id").cmp(&(b != "id")).then_with(|| a.cmp(b)));
+	cols
+}
+
+/// Encodes a value as a CSV cell, leaving strings and record ids unquoted
+pub(crate) fn csv_cell(v: &Value) -> String {
+	match v {
+		Value::None => String::new(),
+		Value::Strand(v) => v.as_str().to_owned(),
+		Value::Thing(v) => v.to_string(),
+		v => v.clone().into_json().to_string(),
+	}
+}
+
+/// Decodes a CSV cell, falling back to a string if the cell is not valid JSON
+pub(crate) fn csv_value(cell: &str) -> Option<Value> {
+	match cell {
+		"" => None,
+		cell => Some(crate::syn::json(cell).unwrap_or_else(|_| cell.into())),
+	}
+}
+
+#[cfg(feature = "parquet")]
+pub(crate) mod parquet {
+	use super::Column;
+	use crate::err::Error;
+	use crate::sql::{Number, Object, Value};
+	use arrow_array::cast::AsArray;
+	use arrow_array::types::{Float64Type, Int64Type};
+	use arrow_array::{
+		Array, ArrayRef, BooleanArray, Float64Array, Int64Array, RecordBatch, StringArray,
+	};
+	use arrow_cast::display::{ArrayFormatter, FormatOptions};
+	use arrow_schema::{DataType, Field, Schema, SchemaRef};
+	use std::collections::HashMap;
+	use std::sync::Arc;
+
+	/// The field metadata marking a column of JSON encoded values
+	const ENCODING: &str = "encoding";
+
+	pub(crate) fn error(e: impl std::fmt::Display) -> Error {
+		Error::InvalidFormatData("parquet".to_owned(), e.to_string())
+	}
+
+	/// Builds the Arrow schema of a set of columns
+	pub(crate) fn schema(cols: &[(String, Column)]) -> SchemaRef {
+		let fields = cols.iter().map(|(name, col)| match col {
+			Column::Bool => Field::new(name, DataType::Boolean, true),
+			Column::Int => Field::new(name, DataType::Int64, true),
+			Column::Float => Field::new(name, DataType::Float64, true),
+			Column::Text => Field::new(name, DataType::Utf8, true),
+			Column::Json => Field::new(name, DataType::Utf8, true)
+				.with_metadata(HashMap::from([(ENCODING.to_owned(), "json".to_owned())])),
+		});
+		Arc::new(Schema::new(fields.collect::<Vec<_>>()))
+	}
+
+	/// Encodes a set of records as an Arrow record batch
+	pub(crate) fn encode(
+		schema: &SchemaRef,
+		cols: &[(String, Column)],
+		records: &[Object],
+	) -> Result<RecordBatch, Error> {
+		let arrays = cols.iter().map(|(name, col)| -> ArrayRef {
+			let values = records.iter().map(|r| r.get(name).filter(|v| !v.is_none_or_null()));
+			match col {
+				Column::Bool => {
+					Arc::new(values.map(|v| v.map(Value::is_true)).collect::<BooleanArray>())
+				}
+				Column::Int => Arc::new(
+					values
+						.map(|v| match v {
+							Some(Value::Number(Number::Int(v))) => Some(*v),
+							_ => None,
+						})
+						.collect::<Int64Array>(),
+				),
+				Column::Float => Arc::new(
+					values
+						.map(|v| match v {
+							Some(Value::Number(v)) => Some(v.to_float()),
+							_ => None,
+						})
+						.collect::<Float64Array>(),
+				),
+				Column::Text => {
+					Arc::new(values.map(|v| v.map(super::csv_cell)).collect::<StringArray>())
+				}
+				Column::Json => Arc::new(
+					values
+						.map(|v| v.map(|v| v.clone().into_json().to_string()))
+						.collect::<StringArray>(),
+				),
+			}
+		});
+		RecordBatch::try_new(schema.clone(), arrays.collect()).map_err(error)
+	}
+
+	/// Decodes an Arrow record batch into a set of records
+	pub(crate) fn decode(batch: &RecordBatch) -> Result<Vec<Object>, Error> {
+		let schema = batch.schema();
+		let mut records = vec![Object::default(); batch.num_rows()];
+		for (field, array) in schema.fields().iter().zip(batch.columns()) {
+			let name = field.name();
+			let json = field.metadata().get(ENCODING).is_some_and(|v| v == "json");
+			let ty = array.data_type();
+			let mut set = |i: usize, v: Value| {
+				if !array.is_null(i) {
+					records[i].insert(name.to_owned(), v);
+				}
+			};
+			if ty == &DataType::Boolean {
+				let array = array.as_boolean();
+				(0..array.len()).for_each(|i| set(i, array.value(i).into()));
+			} else if ty.is_integer() {
+				let array = arrow_cast::cast(array, &DataType::Int64).map_err(error)?;
+				let array = array.as_primitive::<Int64Type>();
+				(0..array.len()).for_each(|i| set(i, array.value(i).into()));
+			} else if ty.is_floating() {
+				let array = arrow_cast::cast(array, &DataType::Float64).map_err(error)?;
+				let array = array.as_primitive::<Float64Type>();
+				(0..array.len()).for_each(|i| set(i, array.value(i).into()));
+			} else if matches!(ty, DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View) {
+				let array = arrow_cast::cast(array, &DataType::Utf8).map_err(error)?;
+				let array = array.as_string::<i32>();
+				for i in 0..array.len() {
+					let v = array.value(i);
+					let v = match json {
+						true => crate::syn::json(v).unwrap_or_else(|_| v.into()),
+						false => v.into(),
+					};
+					set(i, v);
+				}
+			} else {
+				// Any other column type is imported as its display string
+				let fmt = ArrayFormatter::try_new(array.as_ref(), &FormatOptions::default())
+					.map_err(error)?;
+				(0..array.len()).for_each(|i| set(i, fmt.value(i).to_string().into()));
+			}
+		}
+		Ok(records)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn infer_columns() {
+		let a = crate::syn::value("{ id: t:1, a: 1, b: 'x', c: true }").unwrap();
+		let b = crate::syn::value("{ id: t:2, a: 1.5, b: [1], d: NULL }").unwrap();
+		let records: Vec<Object> = vec![a.try_into().unwrap(), b.try_into().unwrap()];
+		let cols = columns(Vec::new(), &records);
+		assert_eq!(
+			cols,
+			vec![
+				("id".to_owned(), Some(Column::Text)),
+				("a".to_owned(), Some(Column::Float)),
+				("b".to_owned(), Some(Column::Json)),
+				("c".to_owned(), Some(Column::Bool)),
+				("d".to_owned(), None),
+			]
+		);
+	}
+
+	#[test]
+	fn csv_cells() {
+		let v = crate::syn::value("{ a: 'text', b: 2, c: { d: [true] } }").unwrap();
+		for (k, v) in Object::try_from(v).unwrap().iter() {
+			assert_eq!(csv_value(&csv_cell(v)).as_ref(), Some(v), "{k}");
+		}
+		assert_eq!(csv_value(""), None);
+	}
+
+	#[cfg(feature = "parquet")]
+	#[test]
+	fn parquet_batch() {
+		let a = crate::syn::value("{ id: 't:1', a: 1, b: 'x', c: true, e: { f: 1 } }").unwrap();
+		let b = crate::syn::value("{ id: 't:2', a: 2.5, b: 'y' }").unwrap();
+		let records: Vec<Object> = vec![a.try_into().unwrap(), b.try_into().unwrap()];
+		let cols: Vec<_> = columns(Vec::new(), &records)
+			.into_iter()
+			.map(|(c, k)| (c, k.unwrap_or(Column::Text)))
+			.collect();
+		let schema = parquet::schema(&cols);
+		let batch = parquet::encode(&schema, &cols, &records).unwrap();
+		let out = parquet::decode(&batch).unwrap();
+		assert_eq!(out[0].get("a"), Some(&Value::from(1.0)));
+		assert_eq!(out[0].get("e"), records[0].get("e"));
+		assert_eq!(out[1].get("b"), records[1].get("b"));
+		assert_eq!(out[1].get("c"), None);
+	}
+}
@@ -0,0 +1,235 @@
+use super::format;
+use super::Datastore;
+use crate::cnf::EXPORT_BATCH_SIZE;
+use crate::dbs::{Response, Session};
+use crate::err::Error;
+use crate::sql::statements::InsertStatement;
+use crate::sql::{Data, Object, Output, Statement, Table, Thing, Value};
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+use std::pin::pin;
+
+pub use super::format::Format;
+
+#[derive(Clone, Debug, Default)]
+pub struct Config {
+	/// The format of the data being imported
+	pub format: Format,
+	/// The table into which records are imported
+	pub table: Option<String>,
+	/// The fields to rename, as pairs of source and target field names
+	pub mapping: Vec<(String, String)>,
+}
+
+/// Imports records into a single table, in batches of `EXPORT_BATCH_SIZE`.
+pub(crate) struct Importer<'a> {
+	ds: &'a Datastore,
+	sess: &'a Session,
+	cfg: Config,
+	tb: String,
+	batch: Vec<Value>,
+	res: Vec<Response>,
+}
+
+impl<'a> Importer<'a> {
+	pub(crate) fn new(ds: &'a Datastore, sess: &'a Session, cfg: Config) -> Result<Self, Error> {
+		// Check the format is available
+		cfg.format.check()?;
+		// Ensure a NS and DB are set
+		crate::iam::check::check_ns_db(sess)?;
+		// Record formats are imported into a single table
+		let Some(tb) = cfg.table.clone() else {
+			return Err(Error::InvalidImportConfig(
+				Value::None,
+				format!("a target table for the {} format", cfg.format),
+			));
+		};
+		Ok(Self {
+			ds,
+			sess,
+			cfg,
+			tb,
+			batch: Vec::with_capacity(*EXPORT_BATCH_SIZE as usize),
+			res: Vec::new(),
+		})
+	}
+
+	/// Decodes the import stream, and inserts the records into the table
+	pub(crate) async fn import<S>(mut self, stream: S) -> Result<Vec<Response>, Error>
+	where
+		S: Stream<Item = Result<Bytes, Error>>,
+	{
+		match self.cfg.format {
+			Format::Csv => self.import_csv(stream).await?,
+			#[cfg(feature = "parquet")]
+			Format::Parquet => self.import_parquet(stream).await?,
+			_ => self.import_jsonl(stream).await?,
+		}
+		self.flush().await?;
+		Ok(self.res)
+	}
+
+	async fn import_jsonl<S>(&mut self, stream: S) -> Result<(), Error>
+	where
+		S: Stream<Item = Result<Bytes, Error>>,
+	{
+		let mut stream = pin!(stream);
+		let mut buffer = Vec::new();
+		let mut done = false;
+		while !done {
+			match stream.next().await {
+				Some(bytes) => buffer.extend_from_slice(&bytes?),
+				None => done = true,
+			}
+			// Process all of the complete lines
+			let end = match done {
+				true => buffer.len(),
+				false => match buffer.iter().rposition(|b| *b == b'\n') {
+					Some(i) => i + 1,
+					None => continue,
+				},
+			};
+			for line in buffer.drain(..end).collect::<Vec<_>>().split(|b| *b == b'\n') {
+				let line = std::str::from_utf8(line).map_err(|e| jsonl_error(e.to_string()))?;
+				if line.trim().is_empty() {
+					continue;
+				}
+				match crate::syn::json(line)? {
+					Value::Object(v) => self.push(v).await?,
+					v => return Err(jsonl_error(format!("expected an object, found {v}"))),
+				}
+			}
+		}
+		Ok(())
+	}
+
+	async fn import_csv<S>(&mut self, stream: S) -> Result<(), Error>
+	where
+		S: Stream<Item = Result<Bytes, Error>>,
+	{
+		let mut stream = pin!(stream);
+		let mut header: Option<csv::StringRecord> = None;
+		let mut buffer = Vec::new();
+		// The position up to which the buffer has been scanned for rows
+		let mut scanned = 0;
+		// The end of the last complete row in the buffer
+		let mut end = 0;
+		// Whether the scan position is inside a quoted cell
+		let mut quoted = false;
+		let mut done = false;
+		while !done {
+			match stream.next().await {
+				Some(bytes) => buffer.extend_from_slice(&bytes?),
+				None => done = true,
+			}
+			// Newlines inside quoted cells do not end a row
+			for (i, b) in buffer.iter().enumerate().skip(scanned) {
+				match b {
+					b'"' => quoted = !quoted,
+					b'\n' if !quoted => end = i + 1,
+					_ => (),
+				}
+			}
+			scanned = buffer.len();
+			if done {
+				end = buffer.len();
+			}
+			if end == 0 {
+				continue;
+			}
+			// Process all of the complete rows
+			let rows = buffer.drain(..end).collect::<Vec<_>>();
+			scanned -= end;
+			end = 0;
+			let mut reader =
+				csv::ReaderBuilder::new().has_headers(false).flexible(true).from_reader(&rows[..]);
+			for row in reader.records() {
+				let row =
+					row.map_err(|e| Error::InvalidFormatData("csv".to_owned(), e.to_string()))?;
+				let Some(names) = &header else {
+					header = Some(row);
+					continue;
+				};
+				let mut v = Object::default();
+				for (name, cell) in names.iter().zip(row.iter()) {
+					if let Some(cell) = format::csv_value(cell) {
+						v.insert(name.to_owned(), cell);
+					}
+				}
+				self.push(v).await?;
+			}
+		}
+		Ok(())
+	}
+
+	#[cfg(feature = "parquet")]
+	async fn import_parquet<S>(&mut self, stream: S) -> Result<(), Error>
+	where
+		S: Stream<Item = Result<Bytes, Error>>,
+	{
+		use super::format::parquet::{decode, error};
+		use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+		use std::io::Write;
+		// The file footer holds the schema, so the file is spooled to disk
+		// first, and the row groups are then decoded one batch at a time
+		let mut file = tempfile::tempfile_in(self.ds.temporary_directory())?;
+		let mut stream = pin!(stream);
+		while let Some(bytes) = stream.next().await {
+			file.write_all(&bytes?)?;
+		}
+		let reader = ParquetRecordBatchReaderBuilder::try_new(file)
+			.map_err(error)?
+			.with_batch_size(*EXPORT_BATCH_SIZE as usize)
+			.build()
+			.map_err(error)?;
+		for batch in reader {
+			for v in decode(&batch.map_err(error)?)? {
+				self.push(v).await?;
+			}
+		}
+		Ok(())
+	}
+
+	/// Adds a record to the current batch, inserting the batch once it is full
+	async fn push(&mut self, mut v: Object) -> Result<(), Error> {
+		// Rename the mapped fields
+		let moved: Vec<_> = self
+			.cfg
+			.mapping
+			.iter()
+			.filter_map(|(src, dst)| v.remove(src).map(|x| (dst.to_owned(), x)))
+			.collect();
+		v.extend(moved);
+		// Convert a textual record id into a record id in the target table
+		if let Some(Value::Strand(id)) = v.get("id") {
+			if let Ok(id) = crate::syn::thing(id.as_str()) {
+				v.insert("id".to_owned(), Thing::from((self.tb.clone(), id.id)).into());
+			}
+		}
+		self.batch.push(v.into());
+		if self.batch.len() >= *EXPORT_BATCH_SIZE as usize {
+			self.flush().await?;
+		}
+		Ok(())
+	}
+
+	/// Inserts the current batch of records into the table
+	async fn flush(&mut self) -> Result<(), Error> {
+		if self.batch.is_empty() {
+			return Ok(());
+		}
+		let stm = InsertStatement {
+			into: Some(Table::from(self.tb.as_str()).into()),
+			data: Data::SingleExpression(std::mem::take(&mut self.batch).into()),
+			output: Some(Output::None),
+			..Default::default()
+		};
+		let res = self.ds.process(Statement::Insert(stm).into(), self.sess, None).await?;
+		self.res.extend(res);
+		Ok(())
+	}
+}
+
+fn jsonl_error(e: String) -> Error {
+	Error::InvalidFormatData("jsonl".to_owned(), e)
+}
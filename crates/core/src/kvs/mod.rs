@@ -14,18 +14,25 @@
 //! - `mem`: in-memory database
 
 mod api;
+pub mod backup;
 mod batch;
 mod cf;
 mod clock;
 mod ds;
+mod expiry;
 pub mod export;
+mod format;
+pub mod import;
 mod live;
 mod node;
 mod scanner;
+mod sequences;
 mod stash;
+mod sync;
 mod tr;
 mod tx;
 mod version;
+mod views;
 
 mod fdb;
 mod indxdb;
@@ -55,5 +62,6 @@ pub use self::ds::*;
 pub(crate) use self::index::*;
 pub use self::kv::*;
 pub use self::live::*;
+pub(crate) use self::sequences::Sequences;
 pub use self::tr::*;
 pub use self::tx::*;
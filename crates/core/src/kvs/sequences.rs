@@ -0,0 +1,160 @@
+//! Allocates values for sequences defined with `DEFINE SEQUENCE`.
+//!
+//! Each node reserves a batch of values by updating the `sb` key of a sequence in its own
+//! short-lived transaction, and hands out the values of that batch from memory. User
+//! transactions therefore never write to the allocation state, and can't conflict on it.
+use crate::ctx::Context;
+use crate::dbs::{Options, RetryPolicy};
+use crate::err::Error;
+use crate::key::database::sb;
+use crate::kvs::ds::TransactionFactory;
+use crate::kvs::{LockType::*, Transaction, TransactionType::*};
+use crate::sql::statements::DefineSequenceStatement;
+use dashmap::DashMap;
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+type SequenceKey = (String, String, String);
+
+/// The number of attempts to reserve a batch, before the conflict is returned
+const RESERVE_ATTEMPTS: u32 = 10;
+
+#[derive(Clone)]
+pub(crate) struct Sequences {
+	tf: TransactionFactory,
+	sequences: Arc<DashMap<SequenceKey, Arc<Mutex<Batch>>>>,
+}
+
+/// The range of values reserved by this node
+#[derive(Default)]
+struct Batch {
+	/// The definition this batch was reserved for
+	cache_ts: Uuid,
+	/// The next value to return
+	next: i64,
+	/// The end of the reserved range (exclusive)
+	end: i64,
+}
+
+/// The allocation state persisted for each sequence
+#[revisioned(revision = 1)]
+#[derive(Serialize, Deserialize, Store, Debug, Default)]
+#[non_exhaustive]
+struct BatchState {
+	/// The first value which has not been reserved by any node
+	next: i64,
+}
+
+impl Sequences {
+	pub(super) fn new(tf: TransactionFactory) -> Self {
+		Self {
+			tf,
+			sequences: Default::default(),
+		}
+	}
+
+	/// Returns the next value of the sequence
+	pub(crate) async fn next_val(
+		&self,
+		ctx: &Context,
+		opt: &Options,
+		sq: &str,
+	) -> Result<i64, Error> {
+		let ns = opt.ns()?;
+		let db = opt.db()?;
+		// Fetch the definition within the current transaction
+		let def = ctx.tx().get_db_sequence(ns, db, sq).await?;
+		// Get the batch for this sequence
+		let batch = self
+			.sequences
+			.entry((ns.to_string(), db.to_string(), sq.to_string()))
+			.or_default()
+			.value()
+			.clone();
+		let mut batch = batch.lock().await;
+		// Reserve a new batch if the current one is exhausted or outdated
+		if batch.cache_ts != def.cache_ts || batch.next >= batch.end {
+			let (next, end) = self.reserve(ns, db, &def).await?;
+			*batch = Batch {
+				cache_ts: def.cache_ts,
+				next,
+				end,
+			};
+		}
+		let val = batch.next;
+		batch.next += 1;
+		Ok(val)
+	}
+
+	/// Reserves a new batch in a dedicated transaction, retrying on conflicts
+	async fn reserve(
+		&self,
+		ns: &str,
+		db: &str,
+		def: &DefineSequenceStatement,
+	) -> Result<(i64, i64), Error> {
+		let retry = RetryPolicy::new(RESERVE_ATTEMPTS);
+		let mut attempts = 0;
+		loop {
+			attempts += 1;
+			match self.try_reserve(ns, db, def).await {
+				Err(e) if e.is_retryable() && retry.allows(attempts) => retry.wait(attempts).await,
+				res => return res,
+			}
+		}
+	}
+
+	async fn try_reserve(
+		&self,
+		ns: &str,
+		db: &str,
+		def: &DefineSequenceStatement,
+	) -> Result<(i64, i64), Error> {
+		let txn = self.tf.transaction(Write, Optimistic).await?;
+		let next = catch!(txn, next_unreserved(&txn, ns, db, def).await);
+		let end = match next.checked_add(def.batch as i64) {
+			Some(end) => end,
+			None => {
+				txn.cancel().await?;
+				return Err(Error::SqExhausted {
+					value: def.name.to_raw(),
+				});
+			}
+		};
+		let key = sb::new(ns, db, &def.name);
+		catch!(
+			txn,
+			txn.set(
+				key,
+				BatchState {
+					next: end
+				},
+				None
+			)
+			.await
+		);
+		txn.commit().await?;
+		Ok((next, end))
+	}
+}
+
+/// Returns the first value of the sequence which has not been reserved by any node
+pub(super) async fn next_unreserved(
+	txn: &Transaction,
+	ns: &str,
+	db: &str,
+	def: &DefineSequenceStatement,
+) -> Result<i64, Error> {
+	let key = sb::new(ns, db, &def.name);
+	// No value has been reserved yet if there is no state
+	let next: Option<i64> = txn.get(key, None).await?.map(|v| BatchState::from(v).next);
+	match next {
+		// Never go back on values reserved before a redefinition
+		Some(next) => Ok(next.max(def.start)),
+		None => Ok(def.start),
+	}
+}
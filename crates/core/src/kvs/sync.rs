@@ -0,0 +1,115 @@
+use super::Transaction;
+use crate::cf::{ChangeSet, DatabaseMutation, TableMutations};
+use crate::cnf::NORMAL_FETCH_SIZE;
+use crate::err::Error;
+use crate::key::change;
+use crate::sql::statements::{BeginStatement, CommitStatement, OptionStatement};
+use crate::sql::{Query, Statement, Statements};
+use crate::vs;
+use async_channel::Sender;
+use revision::Revisioned;
+
+impl Transaction {
+	/// Writes the database change feed, starting from a versionstamp, as binary change sets.
+	///
+	/// Each change set is serialized in its revisioned binary format, and
+	/// the change sets are written one after the other, in versionstamp order.
+	/// A consumer can resume the stream from the versionstamp following
+	/// the last change set which it has received.
+	pub async fn sync(
+		&self,
+		ns: &str,
+		db: &str,
+		tb: Option<&str>,
+		since: u64,
+		chn: Sender<Vec<u8>>,
+	) -> Result<(), Error> {
+		// Fetch the change feed in batches
+		let beg = change::prefix_ts(ns, db, vs::u64_to_versionstamp(since));
+		let end = change::suffix(ns, db);
+		let mut next = Some(beg..end);
+		// A versionstamp can have more entries than fit in a single batch
+		let mut current: Option<ChangeSet> = None;
+		while let Some(rng) = next {
+			let batch = self.batch(rng, *NORMAL_FETCH_SIZE, true, None).await?;
+			next = batch.next;
+			for (k, v) in batch.values {
+				let dec = change::Cf::decode(&k)?;
+				// Only output the changes for the desired table
+				if tb.is_some_and(|tb| tb != dec.tb) {
+					continue;
+				}
+				let tm: TableMutations = v.into();
+				// Add the changes to the change set of their versionstamp
+				if let Some(ChangeSet(vs, DatabaseMutation(tms))) = &mut current {
+					if *vs == dec.vs {
+						tms.push(tm);
+						continue;
+					}
+				}
+				// Output the previous change set, as all of its entries have been read
+				let cs = ChangeSet(dec.vs, DatabaseMutation(vec![tm]));
+				if let Some(cs) = current.replace(cs) {
+					if chn.send(cs.into()).await.is_err() {
+						return Ok(());
+					}
+				}
+			}
+		}
+		// Output the last change set, unless the receiver has gone away
+		if let Some(cs) = current {
+			let _ = chn.send(cs.into()).await;
+		}
+		// Everything ok
+		Ok(())
+	}
+}
+
+/// Decodes a sequence of binary change sets, as written by [`Transaction::sync`],
+/// into a query which applies each change set in its own transaction.
+///
+/// If a versionstamp is specified, only the change sets before it are applied.
+pub(crate) fn decode(bytes: &[u8], until: Option<u64>) -> Result<Query, Error> {
+	decode_partial(bytes, until, true).map(|(ast, _, _)| ast)
+}
+
+/// Decodes the complete change sets at the start of a buffer which is being streamed,
+/// returning the query, the number of bytes decoded, and whether the cutoff was reached.
+///
+/// Unless this is the end of the stream, a change set which is not complete yet is
+/// left in the buffer, to be decoded once the rest of it has been received.
+pub(crate) fn decode_partial(
+	bytes: &[u8],
+	until: Option<u64>,
+	end: bool,
+) -> Result<(Query, usize, bool), Error> {
+	// Disable events and table views, as their effects are replicated too
+	let mut out = vec![Statement::Option(OptionStatement {
+		name: "IMPORT".into(),
+		what: true,
+	})];
+	// Decode each of the change sets in turn
+	let mut rest = bytes;
+	let mut done = false;
+	while !rest.is_empty() {
+		let mut next = rest;
+		let cs = match ChangeSet::deserialize_revisioned(&mut next) {
+			Ok(cs) => cs,
+			// Wait for the rest of the change set
+			Err(_) if !end => break,
+			Err(e) => return Err(e.into()),
+		};
+		// Skip the change sets at or after the cutoff
+		if until.is_some_and(|v| vs::versionstamp_to_u64(&cs.0) >= v) {
+			done = true;
+			break;
+		}
+		rest = next;
+		out.push(Statement::Begin(BeginStatement));
+		for tm in cs.1 .0 {
+			out.extend(tm.1.into_iter().map(|m| m.into_statement()));
+		}
+		out.push(Statement::Commit(CommitStatement));
+	}
+	Ok((Query(Statements(out)), bytes.len() - rest.len(), done))
+}
@@ -22,6 +22,7 @@ use crate::sql::statements::DefineIndexStatement;
 use crate::sql::statements::DefineModelStatement;
 use crate::sql::statements::DefineNamespaceStatement;
 use crate::sql::statements::DefineParamStatement;
+use crate::sql::statements::DefineSequenceStatement;
 use crate::sql::statements::DefineTableStatement;
 use crate::sql::statements::DefineUserStatement;
 use crate::sql::statements::LiveStatement;
@@ -708,6 +709,29 @@ impl Transaction {
 		.try_into_pas()
 	}
 
+	/// Retrieve all sequence definitions for a specific database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn all_db_sequences(
+		&self,
+		ns: &str,
+		db: &str,
+	) -> Result<Arc<[DefineSequenceStatement]>, Error> {
+		let qey = cache::tx::Lookup::Sqs(ns, db);
+		match self.cache.get(&qey) {
+			Some(val) => val,
+			None => {
+				let beg = crate::key::database::sq::prefix(ns, db);
+				let end = crate::key::database::sq::suffix(ns, db);
+				let val = self.getr(beg..end, None).await?;
+				let val = val.convert().into();
+				let val = cache::tx::Entry::Sqs(Arc::clone(&val));
+				self.cache.insert(qey.into(), val.clone());
+				val
+			}
+		}
+		.try_into_sqs()
+	}
+
 	/// Retrieve all model definitions for a specific database.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub async fn all_db_models(
@@ -1284,6 +1308,31 @@ impl Transaction {
 		.try_into_type()
 	}
 
+	/// Retrieve a specific sequence definition from a database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn get_db_sequence(
+		&self,
+		ns: &str,
+		db: &str,
+		sq: &str,
+	) -> Result<Arc<DefineSequenceStatement>, Error> {
+		let qey = cache::tx::Lookup::Sq(ns, db, sq);
+		match self.cache.get(&qey) {
+			Some(val) => val,
+			None => {
+				let key = crate::key::database::sq::new(ns, db, sq).encode()?;
+				let val = self.get(key, None).await?.ok_or_else(|| Error::SqNotFound {
+					value: sq.to_owned(),
+				})?;
+				let val: DefineSequenceStatement = val.into();
+				let val = cache::tx::Entry::Any(Arc::new(val));
+				self.cache.insert(qey.into(), val.clone());
+				val
+			}
+		}
+		.try_into_type()
+	}
+
 	/// Retrieve a specific config definition from a database.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub async fn get_db_config(
@@ -0,0 +1,154 @@
+use crate::cf::{ChangeSet, TableMutation};
+use crate::cnf::NORMAL_FETCH_SIZE;
+use crate::dbs::Session;
+use crate::doc::Document;
+use crate::err::Error;
+use crate::key::table::vp;
+use crate::kvs::Datastore;
+use crate::kvs::{LockType::*, TransactionType::*};
+use crate::sql::statements::show::ShowSince;
+use crate::sql::statements::DefineTableStatement;
+use crate::sql::Value;
+use crate::vs;
+use reblessive::TreeStack;
+
+impl Datastore {
+	/// Applies the pending change feed entries to all ASYNC table views.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub(crate) async fn changefeed_views(&self) -> Result<(), Error> {
+		// Store the ASYNC table views
+		let mut fts = Vec::new();
+		// Create a new transaction
+		let txn = self.transaction(Read, Optimistic).await?;
+		// Fetch all namespaces
+		let nss = catch!(txn, txn.all_ns().await);
+		// Loop over all namespaces
+		for ns in nss.iter() {
+			// Fetch all databases
+			let dbs = catch!(txn, txn.all_db(&ns.name).await);
+			// Loop over all databases
+			for db in dbs.iter() {
+				// Fetch all tables
+				let tables = catch!(txn, txn.all_tb(&ns.name, &db.name, None).await);
+				// Collect the ASYNC table views
+				for tb in tables.iter() {
+					if tb.view.as_ref().is_some_and(|v| v.asynchronous) {
+						fts.push((ns.name.to_raw(), db.name.to_raw(), tb.clone()));
+					}
+				}
+			}
+		}
+		// Cancel the transaction
+		txn.cancel().await?;
+		// Process the change feed for each view
+		for (ns, db, ft) in fts.iter() {
+			loop {
+				match self.changefeed_views_batch(ns, db, ft).await {
+					Ok(true) => continue,
+					Ok(false) => break,
+					// A failing view should not stop the processing of other views
+					Err(e) => {
+						warn!("Failed to update the table view {ns}/{db}/{}: {e}", ft.name);
+						break;
+					}
+				}
+			}
+		}
+		// Everything ok
+		Ok(())
+	}
+
+	/// Applies a batch of change feed entries to an ASYNC table view,
+	/// returning whether any entries were applied.
+	///
+	/// The view records are written through the document pipeline, so that
+	/// the progress of the view is committed along with the view records.
+	async fn changefeed_views_batch(
+		&self,
+		ns: &str,
+		db: &str,
+		ft: &DefineTableStatement,
+	) -> Result<bool, Error> {
+		// Get the view definition
+		let Some(view) = &ft.view else {
+			return Ok(false);
+		};
+		// Create a new transaction
+		let txn = self.transaction(Write, Optimistic).await?.enclose();
+		// Fetch the last change feed entry applied to the view
+		let key = vp::new(ns, db, &ft.name);
+		let Some(progress) = catch!(txn, txn.get(key.clone(), None).await) else {
+			txn.cancel().await?;
+			return Ok(false);
+		};
+		let progress = match <[u8; 10]>::try_from(progress.as_slice()) {
+			Ok(v) => vs::versionstamp_to_u64(&v),
+			Err(e) => {
+				txn.cancel().await?;
+				return Err(Error::Tx(e.to_string()));
+			}
+		};
+		// Fetch a batch of the following change feed entries
+		let limit = *NORMAL_FETCH_SIZE;
+		let start = ShowSince::Versionstamp(progress + 1);
+		let mut changes =
+			catch!(txn, crate::cf::read(&txn, ns, db, None, start, Some(limit)).await);
+		// The entries of the last versionstamp may be incomplete if the limit was reached
+		let count: usize = changes.iter().map(|ChangeSet(_, v)| v.0.len()).sum();
+		if count >= limit as usize && changes.len() > 1 {
+			changes.pop();
+		}
+		// Check if there are any changes
+		let Some(ChangeSet(last, _)) = changes.last() else {
+			txn.cancel().await?;
+			return Ok(false);
+		};
+		let last = *last;
+		// Setup the query options
+		let opt = self.setup_options(&Session::owner().with_ns(ns).with_db(db));
+		// Setup the query context
+		let mut ctx = catch!(txn, self.setup_ctx());
+		ctx.set_transaction(txn.clone());
+		let ctx = ctx.freeze();
+		// Apply the changes of the source tables to the view
+		let mut stack = TreeStack::new();
+		for ChangeSet(_, changes) in changes.into_iter() {
+			for changes in changes.0.into_iter() {
+				// Skip changes for tables which are not a source of the view
+				if !view.what.iter().any(|tb| tb.0 == changes.0) {
+					continue;
+				}
+				for change in changes.1.into_iter() {
+					// Get the initial and current value of the record
+					let (rid, initial, current) = match change {
+						TableMutation::Set(id, current) => (id, Value::None, current),
+						TableMutation::SetWithDiff(id, current, diff) => {
+							let mut initial = current.clone();
+							catch!(txn, initial.patch(diff.into()));
+							(id, initial, current)
+						}
+						TableMutation::Del(id) => (id, Value::None, Value::None),
+						TableMutation::DelWithOriginal(id, initial) => (id, initial, Value::None),
+						TableMutation::Def(_) => continue,
+					};
+					// Process the view
+					let res = stack
+						.enter(|stk| {
+							Document::process_view_change(
+								stk, &ctx, &opt, ft, rid, initial, current,
+							)
+						})
+						.finish()
+						.await;
+					catch!(txn, res);
+				}
+			}
+		}
+		// Store the progress of the view
+		catch!(txn, txn.set(key, last.to_vec(), None).await);
+		// Commit the changes
+		catch!(txn, txn.commit().await);
+		// Everything ok
+		Ok(true)
+	}
+}
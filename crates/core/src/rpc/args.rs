@@ -9,7 +9,9 @@ pub trait Take {
 	fn needs_three(self) -> Result<(Value, Value, Value), RpcError>;
 	fn needs_one_or_two(self) -> Result<(Value, Value), RpcError>;
 	fn needs_one_two_or_three(self) -> Result<(Value, Value, Value), RpcError>;
+	fn needs_two_or_three(self) -> Result<(Value, Value, Value), RpcError>;
 	fn needs_three_or_four(self) -> Result<(Value, Value, Value, Value), RpcError>;
+	fn needs_two_three_or_four(self) -> Result<(Value, Value, Value, Value), RpcError>;
 }
 
 impl Take for Array {
@@ -72,6 +74,18 @@ impl Take for Array {
 			(_, _, _) => Ok((Value::None, Value::None, Value::None)),
 		}
 	}
+	/// Convert the array to three arguments
+	fn needs_two_or_three(self) -> Result<(Value, Value, Value), RpcError> {
+		if self.len() < 2 || self.len() > 3 {
+			return Err(RpcError::InvalidParams);
+		}
+		let mut x = self.into_iter();
+		match (x.next(), x.next(), x.next()) {
+			(Some(a), Some(b), Some(c)) => Ok((a, b, c)),
+			(Some(a), Some(b), None) => Ok((a, b, Value::None)),
+			(_, _, _) => Ok((Value::None, Value::None, Value::None)),
+		}
+	}
 	/// Convert the array to four arguments
 	fn needs_three_or_four(self) -> Result<(Value, Value, Value, Value), RpcError> {
 		if self.len() < 3 || self.len() > 4 {
@@ -84,4 +98,17 @@ impl Take for Array {
 			(_, _, _, _) => Ok((Value::None, Value::None, Value::None, Value::None)),
 		}
 	}
+	/// Convert the array to four arguments
+	fn needs_two_three_or_four(self) -> Result<(Value, Value, Value, Value), RpcError> {
+		if self.len() < 2 || self.len() > 4 {
+			return Err(RpcError::InvalidParams);
+		}
+		let mut x = self.into_iter();
+		match (x.next(), x.next(), x.next(), x.next()) {
+			(Some(a), Some(b), Some(c), Some(d)) => Ok((a, b, c, d)),
+			(Some(a), Some(b), Some(c), None) => Ok((a, b, c, Value::None)),
+			(Some(a), Some(b), None, None) => Ok((a, b, Value::None, Value::None)),
+			(_, _, _, _) => Ok((Value::None, Value::None, Value::None, Value::None)),
+		}
+	}
 }
@@ -10,14 +10,18 @@ use serde::Serialize;
 use super::{request::Request, RpcError};
 use crate::sql::Value;
 
-pub const PROTOCOLS: [&str; 5] = [
-	"json",     // For basic JSON serialisation
-	"cbor",     // For basic CBOR serialisation
-	"msgpack",  // For basic Msgpack serialisation
-	"bincode",  // For full internal serialisation
-	"revision", // For full versioned serialisation
+pub const PROTOCOLS: [&str; 6] = [
+	"json",        // For basic JSON serialisation
+	"cbor",        // For basic CBOR serialisation
+	"msgpack",     // For basic Msgpack serialisation
+	"bincode",     // For full internal serialisation
+	"revision-v2", // For full versioned serialisation, with the details of errors
+	"revision",    // For full versioned serialisation
 ];
 
+/// The protocol which includes the details of errors in versioned responses
+pub const DETAILED_PROTOCOL: &str = PROTOCOLS[4];
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 #[non_exhaustive]
 pub enum Format {
@@ -41,6 +45,7 @@ impl From<&str> for Format {
 			s if s == PROTOCOLS[2] => Format::Msgpack,
 			s if s == PROTOCOLS[3] => Format::Bincode,
 			s if s == PROTOCOLS[4] => Format::Revision,
+			s if s == PROTOCOLS[5] => Format::Revision,
 			_ => Format::None,
 		}
 	}
@@ -28,6 +28,14 @@ pub enum Method {
 	Run,
 	GraphQL,
 	InsertRelation,
+	Begin,
+	Commit,
+	Cancel,
+	Attach,
+	Detach,
+	Sessions,
+	QueryStream,
+	QueryStreamAck,
 }
 
 impl Method {
@@ -62,6 +70,14 @@ impl Method {
 			"run" => Self::Run,
 			"graphql" => Self::GraphQL,
 			"insert_relation" => Self::InsertRelation,
+			"begin" => Self::Begin,
+			"commit" => Self::Commit,
+			"cancel" => Self::Cancel,
+			"attach" => Self::Attach,
+			"detach" => Self::Detach,
+			"sessions" => Self::Sessions,
+			"query_stream" => Self::QueryStream,
+			"query_stream_ack" => Self::QueryStreamAck,
 			_ => Self::Unknown,
 		}
 	}
@@ -97,6 +113,14 @@ impl Method {
 			Self::Run => "run",
 			Self::GraphQL => "graphql",
 			Self::InsertRelation => "insert_relation",
+			Self::Begin => "begin",
+			Self::Commit => "commit",
+			Self::Cancel => "cancel",
+			Self::Attach => "attach",
+			Self::Detach => "detach",
+			Self::Sessions => "sessions",
+			Self::QueryStream => "query_stream",
+			Self::QueryStreamAck => "query_stream_ack",
 		}
 	}
 }
@@ -126,6 +150,14 @@ impl Method {
 				| Method::Run
 				| Method::GraphQL
 				| Method::InsertRelation
+				| Method::Begin
+				| Method::Commit
+				| Method::Cancel
+				| Method::Attach
+				| Method::Detach
+				| Method::Sessions
+				| Method::QueryStream
+				| Method::QueryStreamAck
 				| Method::Unknown
 		)
 	}
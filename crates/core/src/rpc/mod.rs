@@ -5,7 +5,9 @@ pub mod request;
 mod response;
 pub mod rpc_context;
 mod rpc_error;
+mod streams;
 
 pub use response::Data;
 pub use rpc_context::RpcContext;
 pub use rpc_error::RpcError;
+pub use streams::Streams;
@@ -4,16 +4,21 @@ use crate::rpc::RpcError;
 use crate::sql::Part;
 use crate::sql::{Array, Value};
 use std::sync::LazyLock;
+use uuid::Uuid;
 
 pub static ID: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from("id")]);
 pub static METHOD: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from("method")]);
 pub static PARAMS: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from("params")]);
+pub static TXN: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from("txn")]);
+pub static SESSION: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from("session")]);
 
 #[derive(Debug)]
 pub struct Request {
 	pub id: Option<Value>,
 	pub method: String,
 	pub params: Array,
+	pub txn: Option<Uuid>,
+	pub session: Option<Uuid>,
 }
 
 impl TryFrom<Cbor> for Request {
@@ -53,11 +58,29 @@ impl TryFrom<Value> for Request {
 			Value::Array(v) => v,
 			_ => Array::new(),
 		};
+		// Fetch the 'txn' argument
+		let txn = uuid(val.pick(&*TXN))?;
+		// Fetch the 'session' argument
+		let session = uuid(val.pick(&*SESSION))?;
 		// Return the parsed request
 		Ok(Request {
 			id,
 			method,
 			params,
+			txn,
+			session,
 		})
 	}
 }
+
+/// Parses an optional id, which is sent as a string over JSON
+fn uuid(val: Value) -> Result<Option<Uuid>, RpcError> {
+	match val {
+		Value::None | Value::Null => Ok(None),
+		Value::Uuid(v) => Ok(Some(v.0)),
+		Value::Strand(v) => {
+			Uuid::try_parse(v.as_str()).map(Some).map_err(|_| RpcError::InvalidRequest)
+		}
+		_ => Err(RpcError::InvalidRequest),
+	}
+}
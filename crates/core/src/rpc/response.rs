@@ -1,5 +1,6 @@
 use crate::dbs;
 use crate::dbs::Notification;
+use crate::dbs::Records;
 use crate::sql;
 use crate::sql::Value;
 use revision::revisioned;
@@ -15,12 +16,27 @@ pub enum Data {
 	/// Generally methods return a `sql::Value`
 	Other(Value),
 	/// The query methods, `query` and `query_with` return a `Vec` of responses
-	Query(Vec<dbs::Response>),
+	Query(#[serde(serialize_with = "dbs::serialize_fixed")] Vec<dbs::Response>),
 	/// Live queries return a notification
 	Live(Notification),
+	/// Streamed queries send the selected records in batches
+	Records(Records),
+	/// The query methods return responses with the code and details of
+	/// any errors, to clients which negotiated the RPC error details
+	DetailedQuery(Vec<dbs::DetailedQueryMethodResponse>),
 	// Add new variants here
 }
 
+impl Data {
+	/// Includes the code and details of any query errors in this data
+	pub fn into_detailed(self) -> Self {
+		match self {
+			Data::Query(v) => Data::DetailedQuery(v.iter().map(Into::into).collect()),
+			v => v,
+		}
+	}
+}
+
 impl From<Value> for Data {
 	fn from(v: Value) -> Self {
 		Data::Other(v)
@@ -39,6 +55,12 @@ impl From<Notification> for Data {
 	}
 }
 
+impl From<Records> for Data {
+	fn from(r: Records) -> Self {
+		Data::Records(r)
+	}
+}
+
 impl From<Vec<dbs::Response>> for Data {
 	fn from(v: Vec<dbs::Response>) -> Self {
 		Data::Query(v)
@@ -50,6 +72,8 @@ impl From<Data> for Value {
 		match val {
 			Data::Query(v) => sql::to_value(v).unwrap(),
 			Data::Live(v) => sql::to_value(v).unwrap(),
+			Data::Records(v) => sql::to_value(v).unwrap(),
+			Data::DetailedQuery(v) => sql::to_value(v).unwrap(),
 			Data::Other(v) => v,
 		}
 	}
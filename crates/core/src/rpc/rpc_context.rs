@@ -1,5 +1,5 @@
 use crate::err::Error;
-use std::{collections::BTreeMap, mem};
+use std::{borrow::Cow, collections::BTreeMap, mem, sync::Arc};
 
 #[cfg(all(not(target_arch = "wasm32"), surrealdb_unstable))]
 use async_graphql::BatchRequest;
@@ -8,7 +8,10 @@ use uuid::Uuid;
 #[cfg(all(not(target_arch = "wasm32"), surrealdb_unstable))]
 use crate::gql::SchemaCache;
 use crate::{
-	dbs::{capabilities::MethodTarget, QueryType, Response, Session},
+	dbs::{
+		capabilities::MethodTarget, OpenTransaction, QueryChunk, QueryType, Records, Response,
+		RetryPolicy, Session,
+	},
 	kvs::Datastore,
 	rpc::args::Take,
 	sql::{
@@ -20,7 +23,7 @@ use crate::{
 	},
 };
 
-use super::{method::Method, response::Data, rpc_error::RpcError};
+use super::{method::Method, response::Data, rpc_error::RpcError, streams::Streams};
 
 #[allow(async_fn_in_trait)]
 pub trait RpcContext {
@@ -57,6 +60,80 @@ pub trait RpcContext {
 		async { unimplemented!("cleanup_lqs function must be implemented if LQ_SUPPORT = true") }
 	}
 
+	// ------------------------------
+	// Transactions
+	// ------------------------------
+
+	/// Interactive transactions are disabled by default
+	const TXN_SUPPORT: bool = false;
+
+	/// Stores a transaction which was started on this RPC context
+	fn store_txn(
+		&self,
+		_id: Uuid,
+		_txn: Arc<OpenTransaction>,
+	) -> impl std::future::Future<Output = ()> + Send {
+		async { unimplemented!("store_txn function must be implemented if TXN_SUPPORT = true") }
+	}
+	/// Fetches a transaction which is open on this RPC context
+	fn fetch_txn(
+		&self,
+		_id: &Uuid,
+	) -> impl std::future::Future<Output = Option<Arc<OpenTransaction>>> + Send {
+		async { unimplemented!("fetch_txn function must be implemented if TXN_SUPPORT = true") }
+	}
+	/// Removes a transaction which is open on this RPC context
+	fn remove_txn(
+		&self,
+		_id: &Uuid,
+	) -> impl std::future::Future<Output = Option<Arc<OpenTransaction>>> + Send {
+		async { unimplemented!("remove_txn function must be implemented if TXN_SUPPORT = true") }
+	}
+
+	// ------------------------------
+	// Sessions
+	// ------------------------------
+
+	/// Multiple sessions are disabled by default
+	const SESSION_SUPPORT: bool = false;
+
+	/// Attaches a new session with the specified id to this RPC context
+	fn attach_session(&self, _id: Uuid) -> impl std::future::Future<Output = ()> + Send {
+		async {
+			unimplemented!("attach_session function must be implemented if SESSION_SUPPORT = true")
+		}
+	}
+	/// Detaches a session from this RPC context, returning whether it existed
+	fn detach_session(&self, _id: &Uuid) -> impl std::future::Future<Output = bool> + Send {
+		async {
+			unimplemented!("detach_session function must be implemented if SESSION_SUPPORT = true")
+		}
+	}
+	/// Lists the ids of the sessions attached to this RPC context
+	fn list_sessions(&self) -> impl std::future::Future<Output = Vec<Uuid>> + Send {
+		async {
+			unimplemented!("list_sessions function must be implemented if SESSION_SUPPORT = true")
+		}
+	}
+
+	// ------------------------------
+	// Streaming
+	// ------------------------------
+
+	/// Streamed queries are disabled by default
+	const STREAM_SUPPORT: bool = false;
+
+	/// Sends a batch of records selected by a streamed query
+	fn send_records(&self, _records: Records) -> impl std::future::Future<Output = ()> + Send {
+		async {
+			unimplemented!("send_records function must be implemented if STREAM_SUPPORT = true")
+		}
+	}
+	/// Returns the streamed queries which are running on this RPC context
+	fn streams(&self) -> &Streams {
+		unimplemented!("streams function must be implemented if STREAM_SUPPORT = true")
+	}
+
 	// ------------------------------
 	// GraphQL
 	// ------------------------------
@@ -76,7 +153,15 @@ pub trait RpcContext {
 	// ------------------------------
 
 	/// Executes any method on this RPC implementation
-	async fn execute_mutable(&mut self, method: Method, params: Array) -> Result<Data, RpcError> {
+	///
+	/// Methods which read or write data run within the open transaction
+	/// with the specified id, if one is specified.
+	async fn execute_mutable(
+		&mut self,
+		txn: Option<Uuid>,
+		method: Method,
+		params: Array,
+	) -> Result<Data, RpcError> {
 		// Check if capabilities allow executing the requested RPC method
 		if !self.kvs().allows_rpc_method(&MethodTarget {
 			method,
@@ -84,6 +169,10 @@ pub trait RpcContext {
 			warn!("Capabilities denied RPC method call attempt, target: '{}'", method.to_str());
 			return Err(RpcError::MethodNotAllowed);
 		}
+		// Methods which change the connection state can not run within a transaction
+		if txn.is_some() && method.needs_mutability() {
+			return Err(RpcError::InvalidRequest);
+		}
 		// Execute the desired method
 		match method {
 			Method::Ping => Ok(Value::None.into()),
@@ -98,26 +187,39 @@ pub trait RpcContext {
 			Method::Live => self.live(params).await,
 			Method::Set => self.set(params).await,
 			Method::Unset => self.unset(params).await,
-			Method::Select => self.select(params).await,
-			Method::Insert => self.insert(params).await,
-			Method::Create => self.create(params).await,
-			Method::Upsert => self.upsert(params).await,
-			Method::Update => self.update(params).await,
-			Method::Merge => self.merge(params).await,
-			Method::Patch => self.patch(params).await,
-			Method::Delete => self.delete(params).await,
+			Method::Select => self.select(txn, params).await,
+			Method::Insert => self.insert(txn, params).await,
+			Method::Create => self.create(txn, params).await,
+			Method::Upsert => self.upsert(txn, params).await,
+			Method::Update => self.update(txn, params).await,
+			Method::Merge => self.merge(txn, params).await,
+			Method::Patch => self.patch(txn, params).await,
+			Method::Delete => self.delete(txn, params).await,
 			Method::Version => self.version(params).await,
-			Method::Query => self.query(params).await,
-			Method::Relate => self.relate(params).await,
-			Method::Run => self.run(params).await,
+			Method::Query => self.query(txn, params).await,
+			Method::Relate => self.relate(txn, params).await,
+			Method::Run => self.run(txn, params).await,
 			Method::GraphQL => self.graphql(params).await,
-			Method::InsertRelation => self.insert_relation(params).await,
+			Method::InsertRelation => self.insert_relation(txn, params).await,
+			Method::Begin => self.begin(params).await,
+			Method::Commit => self.commit(params).await,
+			Method::Cancel => self.cancel(params).await,
+			Method::Attach => self.attach(params).await,
+			Method::Detach => self.detach(params).await,
+			Method::Sessions => self.sessions(params).await,
+			Method::QueryStream => self.query_stream(txn, params).await,
+			Method::QueryStreamAck => self.query_stream_ack(params).await,
 			Method::Unknown => Err(RpcError::MethodNotFound),
 		}
 	}
 
 	/// Executes any immutable method on this RPC implementation
-	async fn execute_immutable(&self, method: Method, params: Array) -> Result<Data, RpcError> {
+	async fn execute_immutable(
+		&self,
+		txn: Option<Uuid>,
+		method: Method,
+		params: Array,
+	) -> Result<Data, RpcError> {
 		// Check if capabilities allow executing the requested RPC method
 		if !self.kvs().allows_rpc_method(&MethodTarget {
 			method,
@@ -125,24 +227,36 @@ pub trait RpcContext {
 			warn!("Capabilities denied RPC method call attempt, target: '{}'", method.to_str());
 			return Err(RpcError::MethodNotAllowed);
 		}
+		// Methods which change the connection state can not run within a transaction
+		if txn.is_some() && method.needs_mutability() {
+			return Err(RpcError::InvalidRequest);
+		}
 		// Execute the desired method
 		match method {
 			Method::Ping => Ok(Value::None.into()),
 			Method::Info => self.info().await,
-			Method::Select => self.select(params).await,
-			Method::Insert => self.insert(params).await,
-			Method::Create => self.create(params).await,
-			Method::Upsert => self.upsert(params).await,
-			Method::Update => self.update(params).await,
-			Method::Merge => self.merge(params).await,
-			Method::Patch => self.patch(params).await,
-			Method::Delete => self.delete(params).await,
+			Method::Select => self.select(txn, params).await,
+			Method::Insert => self.insert(txn, params).await,
+			Method::Create => self.create(txn, params).await,
+			Method::Upsert => self.upsert(txn, params).await,
+			Method::Update => self.update(txn, params).await,
+			Method::Merge => self.merge(txn, params).await,
+			Method::Patch => self.patch(txn, params).await,
+			Method::Delete => self.delete(txn, params).await,
 			Method::Version => self.version(params).await,
-			Method::Query => self.query(params).await,
-			Method::Relate => self.relate(params).await,
-			Method::Run => self.run(params).await,
+			Method::Query => self.query(txn, params).await,
+			Method::Relate => self.relate(txn, params).await,
+			Method::Run => self.run(txn, params).await,
 			Method::GraphQL => self.graphql(params).await,
-			Method::InsertRelation => self.insert_relation(params).await,
+			Method::InsertRelation => self.insert_relation(txn, params).await,
+			Method::Begin => self.begin(params).await,
+			Method::Commit => self.commit(params).await,
+			Method::Cancel => self.cancel(params).await,
+			Method::Attach => self.attach(params).await,
+			Method::Detach => self.detach(params).await,
+			Method::Sessions => self.sessions(params).await,
+			Method::QueryStream => self.query_stream(txn, params).await,
+			Method::QueryStreamAck => self.query_stream_ack(params).await,
 			Method::Unknown => Err(RpcError::MethodNotFound),
 			_ => Err(RpcError::MethodNotFound),
 		}
@@ -331,7 +445,7 @@ pub trait RpcContext {
 		// Specify the query parameters
 		let var = Some(self.vars().clone());
 		// Execute the query on the database
-		let mut res = self.query_inner(Value::Query(sql), var).await?;
+		let mut res = self.query_inner(None, Value::Query(sql), var, None).await?;
 		// Extract the first query result
 		Ok(res.remove(0).result?.into())
 	}
@@ -351,7 +465,7 @@ pub trait RpcContext {
 		// Specify the query parameters
 		let var = Some(self.vars().clone());
 		// Execute the query on the database
-		let mut res = self.query_inner(Value::Query(sql), var).await?;
+		let mut res = self.query_inner(None, Value::Query(sql), var, None).await?;
 		// Extract the first query result
 		Ok(res.remove(0).result?.into())
 	}
@@ -360,7 +474,7 @@ pub trait RpcContext {
 	// Methods for selecting
 	// ------------------------------
 
-	async fn select(&self, params: Array) -> Result<Data, RpcError> {
+	async fn select(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		// Process the method arguments
 		let Ok(what) = params.needs_one() else {
 			return Err(RpcError::InvalidParams);
@@ -376,7 +490,7 @@ pub trait RpcContext {
 		// Specify the query parameters
 		let var = Some(self.vars().clone());
 		// Execute the query on the database
-		let mut res = self.kvs().process(sql, self.session(), var).await?;
+		let mut res = self.process_inner(txn, sql, var).await?;
 		// Extract the first query result
 		Ok(res
 			.remove(0)
@@ -392,7 +506,7 @@ pub trait RpcContext {
 	// Methods for inserting
 	// ------------------------------
 
-	async fn insert(&self, params: Array) -> Result<Data, RpcError> {
+	async fn insert(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		// Process the method arguments
 		let Ok((what, data)) = params.needs_two() else {
 			return Err(RpcError::InvalidParams);
@@ -411,7 +525,7 @@ pub trait RpcContext {
 		// Specify the query parameters
 		let var = Some(self.vars().clone());
 		// Execute the query on the database
-		let mut res = self.kvs().process(sql, self.session(), var).await?;
+		let mut res = self.process_inner(txn, sql, var).await?;
 		// Extract the first query result
 		Ok(res
 			.remove(0)
@@ -423,7 +537,7 @@ pub trait RpcContext {
 			.into())
 	}
 
-	async fn insert_relation(&self, params: Array) -> Result<Data, RpcError> {
+	async fn insert_relation(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		// Process the method arguments
 		let Ok((what, data)) = params.needs_two() else {
 			return Err(RpcError::InvalidParams);
@@ -443,7 +557,7 @@ pub trait RpcContext {
 		// Specify the query parameters
 		let var = Some(self.vars().clone());
 		// Execute the query on the database
-		let mut res = self.kvs().process(sql, self.session(), var).await?;
+		let mut res = self.process_inner(txn, sql, var).await?;
 		// Extract the first query result
 		Ok(res
 			.remove(0)
@@ -459,7 +573,7 @@ pub trait RpcContext {
 	// Methods for creating
 	// ------------------------------
 
-	async fn create(&self, params: Array) -> Result<Data, RpcError> {
+	async fn create(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		// Process the method arguments
 		let Ok((what, data)) = params.needs_one_or_two() else {
 			return Err(RpcError::InvalidParams);
@@ -480,7 +594,7 @@ pub trait RpcContext {
 		// Specify the query parameters
 		let var = Some(self.vars().clone());
 		// Execute the query on the database
-		let mut res = self.kvs().process(sql, self.session(), var).await?;
+		let mut res = self.process_inner(txn, sql, var).await?;
 		// Extract the first query result
 		Ok(res
 			.remove(0)
@@ -496,7 +610,7 @@ pub trait RpcContext {
 	// Methods for upserting
 	// ------------------------------
 
-	async fn upsert(&self, params: Array) -> Result<Data, RpcError> {
+	async fn upsert(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		// Process the method arguments
 		let Ok((what, data)) = params.needs_one_or_two() else {
 			return Err(RpcError::InvalidParams);
@@ -516,7 +630,7 @@ pub trait RpcContext {
 		// Specify the query parameters
 		let var = Some(self.vars().clone());
 		// Execute the query on the database
-		let mut res = self.kvs().process(sql, self.session(), var).await?;
+		let mut res = self.process_inner(txn, sql, var).await?;
 		// Extract the first query result
 		Ok(res
 			.remove(0)
@@ -532,7 +646,7 @@ pub trait RpcContext {
 	// Methods for updating
 	// ------------------------------
 
-	async fn update(&self, params: Array) -> Result<Data, RpcError> {
+	async fn update(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		// Process the method arguments
 		let Ok((what, data)) = params.needs_one_or_two() else {
 			return Err(RpcError::InvalidParams);
@@ -552,7 +666,7 @@ pub trait RpcContext {
 		// Specify the query parameters
 		let var = Some(self.vars().clone());
 		// Execute the query on the database
-		let mut res = self.kvs().process(sql, self.session(), var).await?;
+		let mut res = self.process_inner(txn, sql, var).await?;
 		// Extract the first query result
 		Ok(res
 			.remove(0)
@@ -568,7 +682,7 @@ pub trait RpcContext {
 	// Methods for merging
 	// ------------------------------
 
-	async fn merge(&self, params: Array) -> Result<Data, RpcError> {
+	async fn merge(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		// Process the method arguments
 		let Ok((what, data)) = params.needs_one_or_two() else {
 			return Err(RpcError::InvalidParams);
@@ -588,7 +702,7 @@ pub trait RpcContext {
 		// Specify the query parameters
 		let var = Some(self.vars().clone());
 		// Execute the query on the database
-		let mut res = self.kvs().process(sql, self.session(), var).await?;
+		let mut res = self.process_inner(txn, sql, var).await?;
 		// Extract the first query result
 		Ok(res
 			.remove(0)
@@ -604,7 +718,7 @@ pub trait RpcContext {
 	// Methods for patching
 	// ------------------------------
 
-	async fn patch(&self, params: Array) -> Result<Data, RpcError> {
+	async fn patch(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		// Process the method arguments
 		let Ok((what, data, diff)) = params.needs_one_two_or_three() else {
 			return Err(RpcError::InvalidParams);
@@ -624,7 +738,7 @@ pub trait RpcContext {
 		// Specify the query parameters
 		let var = Some(self.vars().clone());
 		// Execute the query on the database
-		let mut res = self.kvs().process(sql, self.session(), var).await?;
+		let mut res = self.process_inner(txn, sql, var).await?;
 		// Extract the first query result
 		Ok(res
 			.remove(0)
@@ -640,7 +754,7 @@ pub trait RpcContext {
 	// Methods for relating
 	// ------------------------------
 
-	async fn relate(&self, params: Array) -> Result<Data, RpcError> {
+	async fn relate(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		// Process the method arguments
 		let Ok((from, kind, with, data)) = params.needs_three_or_four() else {
 			return Err(RpcError::InvalidParams);
@@ -662,7 +776,7 @@ pub trait RpcContext {
 		// Specify the query parameters
 		let var = Some(self.vars().clone());
 		// Execute the query on the database
-		let mut res = self.kvs().process(sql, self.session(), var).await?;
+		let mut res = self.process_inner(txn, sql, var).await?;
 		// Extract the first query result
 		Ok(res
 			.remove(0)
@@ -678,7 +792,7 @@ pub trait RpcContext {
 	// Methods for deleting
 	// ------------------------------
 
-	async fn delete(&self, params: Array) -> Result<Data, RpcError> {
+	async fn delete(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		// Process the method arguments
 		let Ok(what) = params.needs_one() else {
 			return Err(RpcError::InvalidParams);
@@ -694,7 +808,7 @@ pub trait RpcContext {
 		// Specify the query parameters
 		let var = Some(self.vars().clone());
 		// Execute the query on the database
-		let mut res = self.kvs().process(sql, self.session(), var).await?;
+		let mut res = self.process_inner(txn, sql, var).await?;
 		// Extract the first query result
 		Ok(res
 			.remove(0)
@@ -721,9 +835,9 @@ pub trait RpcContext {
 	// Methods for querying
 	// ------------------------------
 
-	async fn query(&self, params: Array) -> Result<Data, RpcError> {
+	async fn query(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		// Process the method arguments
-		let Ok((query, vars)) = params.needs_one_or_two() else {
+		let Ok((query, vars, retry)) = params.needs_one_two_or_three() else {
 			return Err(RpcError::InvalidParams);
 		};
 		// Check the query input type
@@ -736,15 +850,101 @@ pub trait RpcContext {
 			Value::None | Value::Null => Some(self.vars().clone()),
 			_ => return Err(RpcError::InvalidParams),
 		};
+		// Specify the retry policy
+		let retry = match retry {
+			Value::None | Value::Null => None,
+			// Statements within an open transaction can not be retried
+			_ if txn.is_some() => return Err(RpcError::InvalidParams),
+			v => Some(RetryPolicy::try_from(&v)?),
+		};
 		// Execute the specified query
-		self.query_inner(query, vars).await.map(Into::into)
+		self.query_inner(txn, query, vars, retry).await.map(Into::into)
+	}
+
+	async fn query_stream(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
+		// Process the method arguments
+		let Ok((id, query, vars, credits)) = params.needs_two_three_or_four() else {
+			return Err(RpcError::InvalidParams);
+		};
+		// Check if streamed queries are supported
+		if !Self::STREAM_SUPPORT {
+			return Err(RpcError::BadStreamConfig);
+		}
+		// Streamed queries run in their own transactions
+		if txn.is_some() {
+			return Err(RpcError::InvalidRequest);
+		}
+		// If no live query handler force realtime off
+		if !Self::LQ_SUPPORT && self.session().rt {
+			return Err(RpcError::BadLQConfig);
+		}
+		// Parse the stream id argument
+		let id = uuid(id)?;
+		// Check the query input type
+		let query = match query {
+			Value::Query(v) => v,
+			Value::Strand(v) => crate::syn::parse(&v)?,
+			_ => return Err(RpcError::InvalidParams),
+		};
+		// Specify the query variables
+		let vars = match vars {
+			Value::Object(mut v) => Some(mrg! {v.0, &self.vars()}),
+			Value::None | Value::Null => Some(self.vars().clone()),
+			_ => return Err(RpcError::InvalidParams),
+		};
+		// Specify the batches which can be sent before they are acknowledged
+		let credits = match credits {
+			Value::Number(v) if v.is_int() && v.is_positive() => Some(v.to_usize()),
+			Value::None | Value::Null => None,
+			_ => return Err(RpcError::InvalidParams),
+		};
+		let credits = self.streams().open(id, credits).await;
+		// Execute the query, sending records as they are selected
+		let (chn, rcv) = async_channel::bounded(1);
+		let run = self.kvs().process_chunked(query, self.session(), vars, chn);
+		// The receiver is moved into the sender, so that the query stops
+		// executing once the client no longer wants the records
+		let send = async move {
+			let mut res = Vec::new();
+			while let Ok(chunk) = rcv.recv().await {
+				match chunk {
+					QueryChunk::Records(query, records) => {
+						// Wait until the client has room for the records
+						match credits.acquire().await {
+							Ok(permit) => permit.forget(),
+							Err(_) => break,
+						}
+						self.send_records(Records::new(id.into(), query, records)).await;
+					}
+					QueryChunk::Response(_, response) => {
+						self.handle_live_query_results(&response).await;
+						res.push(response);
+					}
+				}
+			}
+			res
+		};
+		let (run, res) = futures::join!(run, send);
+		self.streams().close(&id).await;
+		run?;
+		// Return the responses to the client
+		Ok(res.into())
+	}
+
+	async fn query_stream_ack(&self, params: Array) -> Result<Data, RpcError> {
+		// Check if streamed queries are supported
+		if !Self::STREAM_SUPPORT {
+			return Err(RpcError::BadStreamConfig);
+		}
+		// Acknowledge the records of the stream
+		self.streams().ack(params).await
 	}
 
 	// ------------------------------
 	// Methods for running functions
 	// ------------------------------
 
-	async fn run(&self, params: Array) -> Result<Data, RpcError> {
+	async fn run(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		// Process the method arguments
 		let Ok((name, version, args)) = params.needs_one_two_or_three() else {
 			return Err(RpcError::InvalidParams);
@@ -780,11 +980,111 @@ pub trait RpcContext {
 		// Specify the query variables
 		let vars = Some(self.vars().clone());
 		// Execute the function on the database
-		let mut res = self.kvs().process(func, self.session(), vars).await?;
+		let mut res = self.process_inner(txn, func, vars).await?;
 		// Extract the first query result
 		Ok(res.remove(0).result?.into())
 	}
 
+	// ------------------------------
+	// Methods for transactions
+	// ------------------------------
+
+	async fn begin(&self, params: Array) -> Result<Data, RpcError> {
+		// Check the method arguments
+		if !params.is_empty() {
+			return Err(RpcError::InvalidParams);
+		}
+		// Check if interactive transactions are supported
+		if !Self::TXN_SUPPORT {
+			return Err(RpcError::BadTxnConfig);
+		}
+		// Start a new write transaction
+		let txn = self.kvs().begin().await?;
+		// Store the transaction on this context
+		let id = Uuid::new_v4();
+		self.store_txn(id, Arc::new(txn)).await;
+		// Return the transaction id
+		Ok(Value::Uuid(id.into()).into())
+	}
+
+	async fn commit(&self, params: Array) -> Result<Data, RpcError> {
+		// Process the method arguments
+		let Ok(id) = params.needs_one() else {
+			return Err(RpcError::InvalidParams);
+		};
+		// Remove the transaction from this context
+		let txn = self.take_txn(id).await?;
+		// Commit the transaction
+		txn.commit().await?;
+		// Return nothing on success
+		Ok(Value::None.into())
+	}
+
+	async fn cancel(&self, params: Array) -> Result<Data, RpcError> {
+		// Process the method arguments
+		let Ok(id) = params.needs_one() else {
+			return Err(RpcError::InvalidParams);
+		};
+		// Remove the transaction from this context
+		let txn = self.take_txn(id).await?;
+		// Cancel the transaction
+		txn.cancel().await?;
+		// Return nothing on success
+		Ok(Value::None.into())
+	}
+
+	// ------------------------------
+	// Methods for sessions
+	// ------------------------------
+
+	async fn attach(&self, params: Array) -> Result<Data, RpcError> {
+		// Check the method arguments
+		if !params.is_empty() {
+			return Err(RpcError::InvalidParams);
+		}
+		// Check if multiple sessions are supported
+		if !Self::SESSION_SUPPORT {
+			return Err(RpcError::BadSessionConfig);
+		}
+		// Attach a new session to this context
+		let id = Uuid::new_v4();
+		self.attach_session(id).await;
+		// Return the session id
+		Ok(Value::Uuid(id.into()).into())
+	}
+
+	async fn detach(&self, params: Array) -> Result<Data, RpcError> {
+		// Process the method arguments
+		let Ok(id) = params.needs_one() else {
+			return Err(RpcError::InvalidParams);
+		};
+		// Check if multiple sessions are supported
+		if !Self::SESSION_SUPPORT {
+			return Err(RpcError::BadSessionConfig);
+		}
+		// Detach the session from this context
+		let id = uuid(id)?;
+		if !self.detach_session(&id).await {
+			return Err(RpcError::SessionNotFound(id.to_string()));
+		}
+		// Return nothing on success
+		Ok(Value::None.into())
+	}
+
+	async fn sessions(&self, params: Array) -> Result<Data, RpcError> {
+		// Check the method arguments
+		if !params.is_empty() {
+			return Err(RpcError::InvalidParams);
+		}
+		// Check if multiple sessions are supported
+		if !Self::SESSION_SUPPORT {
+			return Err(RpcError::BadSessionConfig);
+		}
+		// Return the ids of the attached sessions
+		let ids = self.list_sessions().await;
+		Ok(Value::from(ids.into_iter().map(|id| Value::Uuid(id.into())).collect::<Vec<_>>()).into())
+	}
+
 	// ------------------------------
 	// Methods for querying with GraphQL
 	// ------------------------------
@@ -909,18 +1209,29 @@ pub trait RpcContext {
 
 	async fn query_inner(
 		&self,
+		txn: Option<Uuid>,
 		query: Value,
 		vars: Option<BTreeMap<String, Value>>,
+		retry: Option<RetryPolicy>,
 	) -> Result<Vec<Response>, RpcError> {
 		// If no live query handler force realtime off
 		if !Self::LQ_SUPPORT && self.session().rt {
 			return Err(RpcError::BadLQConfig);
 		}
+		// Apply the retry policy of the query to the session
+		let session = match retry {
+			Some(_) => Cow::Owned(self.session().clone().with_retry(retry)),
+			None => Cow::Borrowed(self.session()),
+		};
 		// Execute the query on the database
-		let res = match query {
-			Value::Query(sql) => self.kvs().process(sql, self.session(), vars).await?,
-			Value::Strand(sql) => self.kvs().execute(&sql, self.session(), vars).await?,
-			_ => return Err(fail!("Unexpected query type: {query:?}").into()),
+		let res = match (query, txn) {
+			(Value::Query(sql), None) => self.kvs().process(sql, &session, vars).await?,
+			(Value::Query(sql), txn) => self.process_inner(txn, sql, vars).await?,
+			(Value::Strand(sql), None) => self.kvs().execute(&sql, &session, vars).await?,
+			(Value::Strand(sql), txn) => {
+				self.process_inner(txn, crate::syn::parse(&sql)?, vars).await?
+			}
+			(query, _) => return Err(fail!("Unexpected query type: {query:?}").into()),
 		};
 
 		// Post-process hooks for web layer
@@ -932,6 +1243,41 @@ pub trait RpcContext {
 		Ok(res)
 	}
 
+	async fn process_inner(
+		&self,
+		txn: Option<Uuid>,
+		query: Query,
+		vars: Option<BTreeMap<String, Value>>,
+	) -> Result<Vec<Response>, RpcError> {
+		match txn {
+			// Execute the query in its own transactions
+			None => Ok(self.kvs().process(query, self.session(), vars).await?),
+			// Execute the query within the open transaction
+			Some(id) => {
+				// Check if interactive transactions are supported
+				if !Self::TXN_SUPPORT {
+					return Err(RpcError::BadTxnConfig);
+				}
+				let Some(txn) = self.fetch_txn(&id).await else {
+					return Err(Error::TxNotFound(id.to_string()).into());
+				};
+				Ok(self.kvs().process_with_transaction(query, self.session(), vars, &txn).await?)
+			}
+		}
+	}
+
+	async fn take_txn(&self, id: Value) -> Result<Arc<OpenTransaction>, RpcError> {
+		// Check if interactive transactions are supported
+		if !Self::TXN_SUPPORT {
+			return Err(RpcError::BadTxnConfig);
+		}
+		let id = uuid(id)?;
+		match self.remove_txn(&id).await {
+			Some(txn) => Ok(txn),
+			None => Err(Error::TxNotFound(id.to_string()).into()),
+		}
+	}
+
 	async fn handle_live_query_results(&self, res: &Response) {
 		match &res.query_type {
 			QueryType::Live => {
@@ -948,3 +1294,12 @@ pub trait RpcContext {
 		}
 	}
 }
+
+/// Parses an id parameter, which is sent as a string over JSON
+pub(crate) fn uuid(id: Value) -> Result<Uuid, RpcError> {
+	match id {
+		Value::Uuid(v) => Ok(v.0),
+		Value::Strand(v) => Uuid::try_parse(v.as_str()).map_err(|_| RpcError::InvalidParams),
+		_ => Err(RpcError::InvalidParams),
+	}
+}
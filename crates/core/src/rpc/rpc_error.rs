@@ -1,6 +1,7 @@
 use thiserror::Error;
 
-use crate::err;
+use crate::err::{self, ErrorCode, ErrorData};
+use crate::sql::{Object, Value};
 
 #[derive(Debug, Error)]
 #[non_exhaustive]
@@ -23,10 +24,58 @@ pub enum RpcError {
 	BadLQConfig,
 	#[error("A GraphQL request was made, but GraphQL is not supported by the context")]
 	BadGQLConfig,
+	#[error("A transaction was requested, but transactions are not supported by the context")]
+	BadTxnConfig,
+	#[error("A session was requested, but multiple sessions are not supported by the context")]
+	BadSessionConfig,
+	#[error("A streamed query was requested, but streaming is not supported by the context")]
+	BadStreamConfig,
+	#[error("The session '{0}' does not exist, or has been detached")]
+	SessionNotFound(String),
 	#[error("Error: {0}")]
 	Thrown(String),
 }
 
+impl RpcError {
+	/// Returns the stable code identifying the kind of this error
+	pub fn code(&self) -> ErrorCode {
+		match self {
+			RpcError::ParseError => ErrorCode::ParseError,
+			RpcError::InvalidRequest => ErrorCode::InvalidRequest,
+			RpcError::MethodNotFound => ErrorCode::MethodNotFound,
+			RpcError::MethodNotAllowed => ErrorCode::MethodNotAllowed,
+			RpcError::InvalidParams => ErrorCode::InvalidParams,
+			RpcError::InternalError(e) => e.code(),
+			RpcError::LqNotSuported
+			| RpcError::BadLQConfig
+			| RpcError::BadGQLConfig
+			| RpcError::BadTxnConfig
+			| RpcError::BadSessionConfig
+			| RpcError::BadStreamConfig => ErrorCode::Unsupported,
+			RpcError::SessionNotFound(_) => ErrorCode::NotFound,
+			RpcError::Thrown(_) => ErrorCode::Thrown,
+		}
+	}
+
+	/// Returns the structured details of this error
+	pub fn details(&self) -> Object {
+		match self {
+			RpcError::InternalError(e) => e.details(),
+			RpcError::SessionNotFound(id) => map! {
+				"session" => Value::from(id.as_str()),
+			}
+			.into(),
+			_ => Object::default(),
+		}
+	}
+}
+
+impl From<&RpcError> for ErrorData {
+	fn from(error: &RpcError) -> Self {
+		ErrorData::new(error.code(), error.details())
+	}
+}
+
 impl From<err::Error> for RpcError {
 	fn from(e: err::Error) -> Self {
 		use err::Error;
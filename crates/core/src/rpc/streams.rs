@@ -0,0 +1,67 @@
+use std::{collections::HashMap, sync::Arc};
+
+use tokio::sync::{RwLock, Semaphore};
+use uuid::Uuid;
+
+use crate::sql::{Array, Value};
+
+use super::{args::Take, response::Data, rpc_context::uuid, rpc_error::RpcError};
+
+/// The streamed queries which are running on an RPC connection
+///
+/// Each stream holds credits for the batches of records which it may send
+/// before the client acknowledges them, so that a client which consumes the
+/// records slowly never has to buffer more than it granted.
+#[derive(Debug, Default)]
+pub struct Streams(RwLock<HashMap<Uuid, Arc<Semaphore>>>);
+
+impl Streams {
+	/// Starts a stream with the specified number of credits, or with no
+	/// limit if the client does not acknowledge the batches it receives
+	pub(crate) async fn open(&self, id: Uuid, credits: Option<usize>) -> Arc<Semaphore> {
+		let credits = credits.unwrap_or(Semaphore::MAX_PERMITS).min(Semaphore::MAX_PERMITS);
+		let credits = Arc::new(Semaphore::new(credits));
+		self.0.write().await.insert(id, credits.clone());
+		credits
+	}
+	/// Removes a stream once all of its records have been sent
+	pub(crate) async fn close(&self, id: &Uuid) {
+		self.0.write().await.remove(id);
+	}
+	/// Stops sending the records of a stream, as the client went away
+	pub async fn cancel(&self, id: &Uuid) {
+		if let Some(credits) = self.0.read().await.get(id) {
+			credits.close();
+		}
+	}
+	/// Acknowledges batches of records which the client has consumed
+	///
+	/// The parameters are the stream id and the number of batches. Without
+	/// a number of batches the client no longer wants the records, and the
+	/// query is stopped. Acknowledgements for streams which have already
+	/// ended are ignored.
+	pub async fn ack(&self, params: Array) -> Result<Data, RpcError> {
+		// Process the method arguments
+		let Ok((id, count)) = params.needs_one_or_two() else {
+			return Err(RpcError::InvalidParams);
+		};
+		// Parse the stream id argument
+		let id = uuid(id)?;
+		// Fetch the stream, if it is still running
+		let streams = self.0.read().await;
+		let Some(credits) = streams.get(&id) else {
+			return Ok(Value::None.into());
+		};
+		match count {
+			// The client no longer wants the records
+			Value::None | Value::Null => credits.close(),
+			// The client consumed some of the batches
+			Value::Number(v) if v.is_int() && v.is_positive() => {
+				let room = Semaphore::MAX_PERMITS - credits.available_permits();
+				credits.add_permits(v.to_usize().min(room));
+			}
+			_ => return Err(RpcError::InvalidParams),
+		}
+		Ok(Value::None.into())
+	}
+}
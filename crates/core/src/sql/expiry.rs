@@ -0,0 +1,59 @@
+use crate::sql::datetime::Datetime;
+use crate::sql::duration::Duration;
+use crate::sql::idiom::Idiom;
+use crate::sql::statements::info::InfoStructure;
+use crate::sql::Value;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// Specifies when the records in a table are automatically deleted
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum Expiry {
+	/// A record expires after a duration since it was last written
+	After(Duration),
+	/// A record expires at the datetime which is stored in a field
+	At(Idiom),
+}
+
+impl Expiry {
+	/// Returns the time at which a record expires, in nanoseconds since the unix epoch
+	pub(crate) fn timestamp(&self, doc: &Value) -> Option<u64> {
+		let at = match self {
+			Self::After(v) => {
+				let v = chrono::Duration::from_std(v.0).ok()?;
+				Datetime::default().0.checked_add_signed(v)?
+			}
+			Self::At(v) => match doc.pick(v) {
+				Value::Datetime(v) => v.0,
+				_ => return None,
+			},
+		};
+		Some(at.timestamp_nanos_opt()?.max(0) as u64)
+	}
+}
+
+impl Display for Expiry {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::After(v) => write!(f, "EXPIRE AFTER {v}"),
+			Self::At(v) => write!(f, "EXPIRE AT {v}"),
+		}
+	}
+}
+
+impl InfoStructure for Expiry {
+	fn structure(self) -> Value {
+		match self {
+			Self::After(v) => Value::from(map! {
+				"after".to_string() => v.structure(),
+			}),
+			Self::At(v) => Value::from(map! {
+				"at".to_string() => v.structure(),
+			}),
+		}
+	}
+}
@@ -3,7 +3,7 @@ use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::sql::statements::info::InfoStructure;
-use crate::sql::{fmt::Fmt, Idiom, Part, Value};
+use crate::sql::{fmt::Fmt, Function, Idiom, Part, Value, Window};
 use crate::syn;
 use reblessive::tree::Stk;
 use revision::revisioned;
@@ -42,6 +42,11 @@ impl Fields {
 		}
 	}
 
+	/// Check if any of the fields is computed over a window
+	pub(crate) fn has_windows(&self) -> bool {
+		self.0.iter().any(|v| matches!(v, Field::Window { .. }))
+	}
+
 	/// Check if the fields are only about counting
 	pub(crate) fn is_count_all_only(&self) -> bool {
 		let mut is_count_only = false;
@@ -128,6 +133,53 @@ impl Fields {
 		for v in self.other() {
 			match v {
 				Field::All => (),
+				Field::Window {
+					expr,
+					window,
+					alias,
+				} => {
+					let name = alias
+						.as_ref()
+						.map(Cow::Borrowed)
+						.unwrap_or_else(|| Cow::Owned(expr.to_idiom()));
+					// Compute the function arguments for this row
+					let args = match expr.args().len() {
+						// If no function arguments, then compute the result
+						0 if expr.is_aggregate() => {
+							vec![expr.compute(stk, ctx, opt, Some(doc)).await?]
+						}
+						_ => {
+							let mut args = Vec::with_capacity(expr.args().len());
+							for v in expr.args() {
+								args.push(v.compute(stk, ctx, opt, Some(doc)).await?);
+							}
+							args
+						}
+					};
+					// Compute the partition values for this row
+					let mut partition = Vec::with_capacity(window.partition.len());
+					for v in window.partition.iter() {
+						partition.push(v.compute(stk, ctx, opt, Some(doc)).await?);
+					}
+					// Compute the ordering values for this row
+					let mut order = Vec::new();
+					if let Some(orders) = &window.order {
+						for v in orders.0.iter() {
+							order.push(v.value.compute(stk, ctx, opt, Some(doc)).await?);
+						}
+					}
+					// Store the row values until the window is computed
+					let x = Value::from(vec![
+						Value::from(args),
+						Value::from(partition),
+						Value::from(order),
+					]);
+					// Check if this is a single VALUE field expression
+					match self.single().is_some() {
+						false => out.set(stk, ctx, opt, name.as_ref(), x).await?,
+						true => out = x,
+					}
+				}
 				Field::Single {
 					expr,
 					alias,
@@ -264,7 +316,7 @@ impl Fields {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -278,6 +330,14 @@ pub enum Field {
 		/// The `quality` in `SELECT rating AS quality FROM ...`
 		alias: Option<Idiom>,
 	},
+	/// The 'rank() OVER (ORDER BY rating)' in `SELECT rank() OVER (ORDER BY rating) FROM ...`
+	#[revision(start = 2)]
+	Window {
+		expr: Function,
+		window: Window,
+		/// The `place` in `SELECT rank() OVER (ORDER BY rating) AS place FROM ...`
+		alias: Option<Idiom>,
+	},
 }
 
 impl Display for Field {
@@ -296,6 +356,19 @@ impl Display for Field {
 					Ok(())
 				}
 			}
+			Self::Window {
+				expr,
+				window,
+				alias,
+			} => {
+				write!(f, "{expr} {window}")?;
+				if let Some(alias) = alias {
+					f.write_str(" AS ")?;
+					Display::fmt(alias, f)
+				} else {
+					Ok(())
+				}
+			}
 		}
 	}
 }
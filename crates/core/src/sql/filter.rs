@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::fmt::Display;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -16,6 +16,18 @@ pub enum Filter {
 	Snowball(Language),
 	Uppercase,
 	Mapper(String),
+	/// Removes the common words of a language
+	#[revision(start = 2)]
+	Stopwords(Language),
+	/// Expands a term into its synonyms, read from a file
+	#[revision(start = 2)]
+	Synonym(String),
+	/// Removes the terms which are shorter or longer than the given number of characters
+	#[revision(start = 2)]
+	Length(u16, u16),
+	/// Truncates the terms to the given number of characters
+	#[revision(start = 2)]
+	Truncate(u16),
 }
 
 impl Display for Filter {
@@ -28,6 +40,10 @@ impl Display for Filter {
 			Self::Snowball(lang) => write!(f, "SNOWBALL({lang})"),
 			Self::Uppercase => f.write_str("UPPERCASE"),
 			Self::Mapper(path) => write!(f, "MAPPER({path})"),
+			Self::Stopwords(lang) => write!(f, "STOPWORDS({lang})"),
+			Self::Synonym(path) => write!(f, "SYNONYM({path})"),
+			Self::Length(min, max) => write!(f, "LENGTH({min},{max})"),
+			Self::Truncate(max) => write!(f, "TRUNCATE({max})"),
 		}
 	}
 }
@@ -8,6 +8,7 @@ use crate::sql::fmt::Fmt;
 use crate::sql::idiom::Idiom;
 use crate::sql::script::Script;
 use crate::sql::value::Value;
+use crate::sql::window;
 use crate::sql::Permission;
 use futures::future::try_join_all;
 use reblessive::tree::Stk;
@@ -147,6 +148,20 @@ impl Function {
 			_ => false,
 		}
 	}
+	/// Check if this function is a window function
+	pub fn is_window(&self) -> bool {
+		match self {
+			Self::Normal(f, _) => matches!(
+				f.as_str(),
+				window::DENSE_RANK
+					| window::FIRST_VALUE
+					| window::LAG | window::LAST_VALUE
+					| window::LEAD | window::RANK
+					| window::ROW_NUMBER
+			),
+			_ => false,
+		}
+	}
 	/// Check if this function is a grouping function
 	pub fn is_aggregate(&self) -> bool {
 		match self {
@@ -12,7 +12,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::fmt::{Display, Formatter};
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -29,6 +29,9 @@ pub enum Index {
 	/// HNSW index for distance based metrics
 	#[revision(start = 2)]
 	Hnsw(HnswParams),
+	/// Geohash cell index for geometry based queries
+	#[revision(start = 3)]
+	Spatial(SpatialParams),
 }
 
 #[revisioned(revision = 2)]
@@ -53,7 +56,7 @@ pub struct SearchParams {
 	pub terms_cache: u32,
 }
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -70,9 +73,12 @@ pub struct MTreeParams {
 	pub doc_ids_cache: u32,
 	#[revision(start = 2)]
 	pub mtree_cache: u32,
+	#[revision(start = 3)]
+	pub quantization: Option<VectorQuantization>,
 }
 
 impl MTreeParams {
+	#[allow(clippy::too_many_arguments)]
 	pub fn new(
 		dimension: u16,
 		distance: Distance,
@@ -81,6 +87,7 @@ impl MTreeParams {
 		doc_ids_order: u32,
 		doc_ids_cache: u32,
 		mtree_cache: u32,
+		quantization: Option<VectorQuantization>,
 	) -> Self {
 		Self {
 			dimension,
@@ -90,6 +97,7 @@ impl MTreeParams {
 			doc_ids_order,
 			doc_ids_cache,
 			mtree_cache,
+			quantization,
 		}
 	}
 
@@ -109,6 +117,28 @@ impl MTreeParams {
 	}
 }
 
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct SpatialParams {
+	/// The length of the geohash cells in which the geometries are stored
+	pub precision: u8,
+}
+
+impl SpatialParams {
+	/// The default length of the geohash cells (about 38m by 19m)
+	pub const DEFAULT_PRECISION: u8 = 8;
+	/// The maximum length of a geohash cell
+	pub const MAX_PRECISION: u8 = 12;
+
+	pub fn new(precision: u8) -> Self {
+		Self {
+			precision,
+		}
+	}
+}
+
 #[revisioned(revision = 1)]
 #[derive(Clone, Default, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -122,7 +152,7 @@ pub enum Distance1 {
 	Minkowski(Number),
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -136,6 +166,8 @@ pub struct HnswParams {
 	pub extend_candidates: bool,
 	pub keep_pruned_connections: bool,
 	pub ml: Number,
+	#[revision(start = 2)]
+	pub quantization: Option<VectorQuantization>,
 }
 
 impl HnswParams {
@@ -150,6 +182,7 @@ impl HnswParams {
 		ef_construction: u16,
 		extend_candidates: bool,
 		keep_pruned_connections: bool,
+		quantization: Option<VectorQuantization>,
 	) -> Self {
 		Self {
 			dimension,
@@ -161,6 +194,7 @@ impl HnswParams {
 			ml,
 			extend_candidates,
 			keep_pruned_connections,
+			quantization,
 		}
 	}
 }
@@ -236,6 +270,29 @@ impl Display for VectorType {
 	}
 }
 
+/// The compression applied to the vectors stored by an HNSW or M-Tree index.
+/// The index is searched with the compressed vectors, and the candidates are
+/// then rescored with the full precision vectors of the indexed records.
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum VectorQuantization {
+	/// One signed byte per dimension, scaled by the largest absolute value
+	Scalar,
+	/// One bit per dimension, set when the value is positive
+	Binary,
+}
+
+impl Display for VectorQuantization {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Scalar => f.write_str("SCALAR"),
+			Self::Binary => f.write_str("BINARY"),
+		}
+	}
+}
+
 impl Display for Index {
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
 		match self {
@@ -266,7 +323,11 @@ impl Display for Index {
 					f,
 					"MTREE DIMENSION {} DIST {} TYPE {} CAPACITY {} DOC_IDS_ORDER {} DOC_IDS_CACHE {} MTREE_CACHE {}",
 					p.dimension, p.distance, p.vector_type, p.capacity, p.doc_ids_order, p.doc_ids_cache, p.mtree_cache
-				)
+				)?;
+				if let Some(q) = p.quantization {
+					write!(f, " QUANTIZATION {q}")?
+				}
+				Ok(())
 			}
 			Self::Hnsw(p) => {
 				write!(
@@ -280,8 +341,12 @@ impl Display for Index {
 				if p.keep_pruned_connections {
 					f.write_str(" KEEP_PRUNED_CONNECTIONS")?
 				}
+				if let Some(q) = p.quantization {
+					write!(f, " QUANTIZATION {q}")?
+				}
 				Ok(())
 			}
+			Self::Spatial(p) => write!(f, "SPATIAL PRECISION {}", p.precision),
 		}
 	}
 }
@@ -21,6 +21,7 @@ pub(crate) mod dir;
 pub(crate) mod duration;
 pub(crate) mod edges;
 pub(crate) mod escape;
+pub(crate) mod expiry;
 pub(crate) mod explain;
 pub(crate) mod expression;
 pub(crate) mod fetch;
@@ -70,6 +71,7 @@ pub(crate) mod uuid;
 pub(crate) mod value;
 pub(crate) mod version;
 pub(crate) mod view;
+pub(crate) mod window;
 pub(crate) mod with;
 
 pub mod index;
@@ -96,6 +98,7 @@ pub use self::datetime::Datetime;
 pub use self::dir::Dir;
 pub use self::duration::Duration;
 pub use self::edges::Edges;
+pub use self::expiry::Expiry;
 pub use self::explain::Explain;
 pub use self::expression::Expression;
 pub use self::fetch::Fetch;
@@ -155,6 +158,7 @@ pub use self::value::Value;
 pub use self::value::Values;
 pub use self::version::Version;
 pub use self::view::View;
+pub use self::window::{Frame, FrameBound, Window};
 pub use self::with::With;
 
 // module reexporting parsing function to prevent a breaking change.
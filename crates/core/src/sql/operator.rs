@@ -6,7 +6,7 @@ use std::fmt;
 use std::fmt::Write;
 
 /// Binary operators.
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -40,6 +40,8 @@ pub enum Operator {
 	AllLike,                   // *~
 	AnyLike,                   // ?~
 	Matches(Option<MatchRef>), // @{ref}@
+	#[revision(start = 3)]
+	FuzzyMatches(Option<MatchRef>, u8), // @{ref},FUZZY={distance}@
 	//
 	LessThan,        // <
 	LessThanOrEqual, // <=
@@ -141,6 +143,13 @@ impl fmt::Display for Operator {
 					f.write_str("@@")
 				}
 			}
+			Self::FuzzyMatches(reference, distance) => {
+				if let Some(r) = reference {
+					write!(f, "@{r},FUZZY={distance}@")
+				} else {
+					write!(f, "@FUZZY={distance}@")
+				}
+			}
 			Self::Knn(k, dist) => {
 				if let Some(d) = dist {
 					write!(f, "<|{k},{d}|>")
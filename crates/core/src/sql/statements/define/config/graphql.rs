@@ -129,7 +129,11 @@ impl Display for FunctionsConfig {
 				write!(f, "INCLUDE [")?;
 				if !cs.is_empty() {
 					let indent = pretty_indent();
-					write!(f, "{}", Fmt::pretty_comma_separated(cs.as_slice()))?;
+					write!(
+						f,
+						"{}",
+						Fmt::pretty_comma_separated(cs.iter().map(|c| format!("fn::{}", c.0)))
+					)?;
 					drop(indent);
 				}
 				f.write_char(']')?;
@@ -139,7 +143,11 @@ impl Display for FunctionsConfig {
 				write!(f, "EXCLUDE [")?;
 				if !cs.is_empty() {
 					let indent = pretty_indent();
-					write!(f, "{}", Fmt::pretty_comma_separated(cs.as_slice()))?;
+					write!(
+						f,
+						"{}",
+						Fmt::pretty_comma_separated(cs.iter().map(|c| format!("fn::{}", c.0)))
+					)?;
 					drop(indent);
 				}
 				f.write_char(']')?;
@@ -214,6 +214,12 @@ impl DefineFieldStatement {
 		}
 		// Clear the cache
 		txn.clear();
+		// Register the views which follow record links through this field
+		for ft in txn.all_tb_views(ns, db, &self.what).await?.iter() {
+			if ft.view.as_ref().is_some_and(|v| v.has_links()) {
+				DefineTableStatement::add_view_links(&txn, opt, ft).await?;
+			}
+		}
 		// Ok all good
 		Ok(Value::None)
 	}
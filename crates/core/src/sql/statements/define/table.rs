@@ -1,7 +1,7 @@
 use super::DefineFieldStatement;
 use crate::ctx::Context;
 use crate::dbs::{Force, Options};
-use crate::doc::CursorDoc;
+use crate::doc::{CursorDoc, Document};
 use crate::err::Error;
 use crate::iam::{Action, ResourceKind};
 use crate::kvs::Transaction;
@@ -9,11 +9,12 @@ use crate::sql::fmt::{is_pretty, pretty_indent};
 use crate::sql::paths::{IN, OUT};
 use crate::sql::statements::info::InfoStructure;
 use crate::sql::{
-	changefeed::ChangeFeed, statements::UpdateStatement, Base, Ident, Output, Permissions, Strand,
-	Value, Values, View,
+	changefeed::ChangeFeed, statements::UpdateStatement, Base, Expiry, Ident, Output, Permissions,
+	Strand, Thing, Value, Values, View,
 };
-use crate::sql::{Idiom, Kind, TableType};
+use crate::sql::{Idiom, Kind, Part, TableType};
 use derive::Store;
+use futures::StreamExt;
 use reblessive::tree::Stk;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
@@ -21,7 +22,7 @@ use std::fmt::{self, Display, Write};
 use std::sync::Arc;
 use uuid::Uuid;
 
-#[revisioned(revision = 5)]
+#[revisioned(revision = 6)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -56,6 +57,9 @@ pub struct DefineTableStatement {
 	/// The last time that a LIVE query was added to this table
 	#[revision(start = 5)]
 	pub cache_lives_ts: Uuid,
+	/// When the records in this table should automatically expire
+	#[revision(start = 6)]
+	pub expire: Option<Expiry>,
 }
 
 impl DefineTableStatement {
@@ -71,7 +75,8 @@ impl DefineTableStatement {
 		// Fetch the transaction
 		let txn = ctx.tx();
 		// Check if the definition exists
-		if txn.get_tb(opt.ns()?, opt.db()?, &self.name).await.is_ok() {
+		let prev = txn.get_tb(opt.ns()?, opt.db()?, &self.name).await.ok();
+		if prev.is_some() {
 			if self.if_not_exists {
 				return Ok(Value::None);
 			} else if !self.overwrite {
@@ -105,13 +110,34 @@ impl DefineTableStatement {
 		if dt.changefeed.is_some() {
 			txn.lock().await.record_table_change(opt.ns()?, opt.db()?, &self.name, &dt);
 		}
+		// Rebuild the record expiry data if the expiry has changed
+		if prev.as_ref().map_or(dt.expire.is_some(), |tb| tb.expire != dt.expire) {
+			Self::rebuild_expiry_data(&txn, &dt, opt).await?;
+		}
+		// Remove the links of the previous view
+		if let Some(prev) = prev.as_ref().filter(|tb| tb.view.is_some()) {
+			Self::remove_view_links(&txn, opt, prev).await?;
+		}
 		// Check if table is a view
 		if let Some(view) = &self.view {
+			// Check that ASYNC views can be processed from the change feed
+			if view.asynchronous {
+				Self::check_view_changefeed(&txn, opt, &self.name, view).await?;
+			}
+			// Register the view on the tables which it is projected through
+			Self::add_view_links(&txn, opt, &dt).await?;
 			// Force queries to run
 			let opt = &opt.new_with_force(Force::Table(Arc::new([dt])));
 			// Remove the table data
 			let key = crate::key::table::all::new(opt.ns()?, opt.db()?, &self.name);
 			txn.delp(key).await?;
+			// Store the change feed position of ASYNC views
+			if view.asynchronous {
+				let key = crate::key::database::vs::new(opt.ns()?, opt.db()?);
+				let vs = txn.lock().await.get_timestamp(key).await?;
+				let key = crate::key::table::vp::new(opt.ns()?, opt.db()?, &self.name);
+				txn.set(key, vs.to_vec(), None).await?;
+			}
 			// Process each foreign table
 			for ft in view.what.0.iter() {
 				// Save the view config
@@ -132,12 +158,17 @@ impl DefineTableStatement {
 				// Clear the cache
 				txn.clear();
 				// Process the view data
-				let stm = UpdateStatement {
-					what: Values(vec![Value::Table(ft.clone())]),
-					output: Some(Output::None),
-					..UpdateStatement::default()
-				};
-				stm.compute(stk, ctx, opt, doc).await?;
+				match view.asynchronous {
+					true => self.process_async_view(stk, ctx, opt, ft).await?,
+					false => {
+						let stm = UpdateStatement {
+							what: Values(vec![Value::Table(ft.clone())]),
+							output: Some(Output::None),
+							..UpdateStatement::default()
+						};
+						stm.compute(stk, ctx, opt, doc).await?;
+					}
+				}
 			}
 		}
 		// Clear the cache
@@ -207,6 +238,270 @@ impl DefineTableStatement {
 	}
 }
 
+impl DefineTableStatement {
+	/// Used to rebuild the expiry data for existing table records
+	async fn rebuild_expiry_data(
+		txn: &Transaction,
+		tb: &DefineTableStatement,
+		opt: &Options,
+	) -> Result<(), Error> {
+		let (ns, db) = (opt.ns()?, opt.db()?);
+		// Remove any existing expiry data
+		let beg = crate::key::table::ex::prefix(ns, db, &tb.name);
+		let end = crate::key::table::ex::suffix(ns, db, &tb.name);
+		txn.delr(beg..end).await?;
+		let beg = crate::key::table::er::prefix(ns, db, &tb.name);
+		let end = crate::key::table::er::suffix(ns, db, &tb.name);
+		txn.delr(beg..end).await?;
+		// Store the expiry data for each existing record
+		if let Some(expire) = &tb.expire {
+			let beg = crate::key::thing::prefix(ns, db, &tb.name);
+			let end = crate::key::thing::suffix(ns, db, &tb.name);
+			let mut stream = txn.stream(beg..end, None);
+			while let Some(res) = stream.next().await {
+				let (k, v) = res?;
+				let id = crate::key::thing::Thing::decode(&k)?.id;
+				if let Some(ts) = expire.timestamp(&Value::from(v)) {
+					let key = crate::key::table::ex::new(ns, db, &tb.name, ts, &id);
+					txn.set(key, vec![], None).await?;
+					let key = crate::key::table::er::new(ns, db, &tb.name, &id);
+					txn.set(key, ts.to_be_bytes().to_vec(), None).await?;
+				}
+			}
+		}
+		Ok(())
+	}
+}
+
+impl DefineTableStatement {
+	/// Registers a view on the tables which its records are
+	/// projected through, but which are not a source table
+	pub(crate) async fn add_view_links(
+		txn: &Transaction,
+		opt: &Options,
+		ft: &DefineTableStatement,
+	) -> Result<(), Error> {
+		let (ns, db) = (opt.ns()?, opt.db()?);
+		// Get the view definition
+		let Some(view) = &ft.view else {
+			return Ok(());
+		};
+		// Register the view on each linked table
+		for tb in Self::view_link_tables(txn, ns, db, ft, view).await? {
+			if view.what.iter().any(|v| v.0 == tb) {
+				continue;
+			}
+			// Save the view config
+			let key = crate::key::table::ft::new(ns, db, &tb, &ft.name);
+			txn.set(key, ft, None).await?;
+			// Refresh the table cache
+			if let Ok(tb) = txn.get_tb(ns, db, &tb).await {
+				let key = crate::key::database::tb::new(ns, db, &tb.name);
+				txn.set(
+					key,
+					DefineTableStatement {
+						cache_tables_ts: Uuid::now_v7(),
+						..tb.as_ref().clone()
+					},
+					None,
+				)
+				.await?;
+			}
+		}
+		// Clear the cache
+		txn.clear();
+		Ok(())
+	}
+
+	/// Resolves the tables which the records of a view are
+	/// projected through, by following the graph edges and the
+	/// record links of the view fields and condition. Record
+	/// links are only followed through fields which are defined
+	/// with a record type, as other fields hold plain values.
+	async fn view_link_tables(
+		txn: &Transaction,
+		ns: &str,
+		db: &str,
+		ft: &DefineTableStatement,
+		view: &View,
+	) -> Result<Vec<String>, Error> {
+		let mut tables = Vec::new();
+		for idiom in view.idioms() {
+			// Start from the source tables of the view
+			let mut current: Vec<String> = view.what.iter().map(|tb| tb.0.clone()).collect();
+			// The field path since the last record which was reached
+			let mut path = Vec::new();
+			for (i, part) in idiom.iter().enumerate() {
+				match part {
+					Part::Graph(g) => {
+						if g.what.is_empty() {
+							return Err(Error::ViewLinkUntyped {
+								view: ft.name.to_raw(),
+								field: idiom.to_string(),
+							});
+						}
+						current = g.what.iter().map(|tb| tb.0.clone()).collect();
+						tables.extend(current.iter().cloned());
+						path.clear();
+					}
+					// The last field of the idiom is not followed
+					Part::Field(_) if i + 1 == idiom.len() => break,
+					Part::Field(f) => {
+						path.push(Part::Field(f.clone()));
+						let field = Idiom::from(path.clone()).to_string();
+						let mut next = Vec::new();
+						for tb in current.iter() {
+							let kind = match txn.get_tb_field(ns, db, tb, &field).await {
+								Ok(fd) => fd.kind.clone().unwrap_or_default(),
+								Err(Error::FdNotFound {
+									..
+								}) => Kind::Any,
+								Err(e) => return Err(e),
+							};
+							match Self::record_tables(&kind) {
+								Some(tbs) => next.extend(tbs),
+								None => {
+									return Err(Error::ViewLinkUntyped {
+										view: ft.name.to_raw(),
+										field: format!("{tb}.{field}"),
+									})
+								}
+							}
+						}
+						// Continue along the field path if this is not a record link
+						if !next.is_empty() {
+							current = next;
+							tables.extend(current.iter().cloned());
+							path.clear();
+						}
+					}
+					// Array accessors do not change the linked tables
+					Part::All | Part::Flatten | Part::First | Part::Last | Part::Index(_) => {
+						continue
+					}
+					_ => break,
+				}
+			}
+		}
+		tables.retain(|tb| tb != ft.name.as_str());
+		tables.sort();
+		tables.dedup();
+		Ok(tables)
+	}
+
+	/// Returns the tables which a field of this type can link to,
+	/// or None if the type does not specify the linked tables
+	fn record_tables(kind: &Kind) -> Option<Vec<String>> {
+		match kind {
+			Kind::Record(tbs) if tbs.is_empty() => None,
+			Kind::Record(tbs) => Some(tbs.iter().map(|tb| tb.0.clone()).collect()),
+			Kind::Option(k) | Kind::Array(k, _) | Kind::Set(k, _) => Self::record_tables(k),
+			Kind::Either(ks) => {
+				ks.iter().map(Self::record_tables).collect::<Option<Vec<_>>>().map(|v| v.concat())
+			}
+			_ => Some(vec![]),
+		}
+	}
+
+	/// Removes a view from the tables which it is registered on,
+	/// along with the links to the source records of the view
+	pub(crate) async fn remove_view_links(
+		txn: &Transaction,
+		opt: &Options,
+		ft: &DefineTableStatement,
+	) -> Result<(), Error> {
+		let (ns, db) = (opt.ns()?, opt.db()?);
+		// Collect the tables which the view could be registered on
+		let mut tbs: Vec<String> =
+			txn.all_tb(ns, db, None).await?.iter().map(|tb| tb.name.to_raw()).collect();
+		if let Some(view) = &ft.view {
+			tbs.extend(view.what.iter().map(|tb| tb.0.clone()));
+		}
+		tbs.retain(|tb| tb != ft.name.as_str());
+		tbs.sort();
+		tbs.dedup();
+		// Remove the view from each table
+		for tb in tbs.iter() {
+			let key = crate::key::table::ft::new(ns, db, tb, &ft.name);
+			if txn.exists(key.clone(), None).await? {
+				txn.del(key).await?;
+				// Refresh the table cache
+				if let Ok(tb) = txn.get_tb(ns, db, tb).await {
+					let key = crate::key::database::tb::new(ns, db, &tb.name);
+					txn.set(
+						key,
+						DefineTableStatement {
+							cache_tables_ts: Uuid::now_v7(),
+							..tb.as_ref().clone()
+						},
+						None,
+					)
+					.await?;
+				}
+			}
+			// Remove the links to the source records
+			let beg = crate::key::table::vd::prefix(ns, db, tb, &ft.name);
+			let end = crate::key::table::vd::suffix(ns, db, tb, &ft.name);
+			txn.delr(beg..end).await?;
+		}
+		// Clear the cache
+		txn.clear();
+		Ok(())
+	}
+
+	/// Checks that the source tables of an ASYNC view record a change feed
+	async fn check_view_changefeed(
+		txn: &Transaction,
+		opt: &Options,
+		name: &str,
+		view: &View,
+	) -> Result<(), Error> {
+		let (ns, db) = (opt.ns()?, opt.db()?);
+		let dbs = txn.get_db(ns, db).await?;
+		// Grouped views need the original values to remove them from the group
+		let original = view.group.is_some();
+		for ft in view.what.iter() {
+			let tb = txn.get_tb(ns, db, ft).await?;
+			let ok = [&tb.changefeed, &dbs.changefeed]
+				.into_iter()
+				.flatten()
+				.any(|cf| !original || cf.store_diff);
+			if !ok {
+				return Err(Error::ViewChangefeedRequired {
+					view: name.to_owned(),
+					table: ft.0.clone(),
+					original,
+				});
+			}
+		}
+		Ok(())
+	}
+
+	/// Computes an ASYNC view from the existing records of a
+	/// source table, without writing to the source records so
+	/// that they are not also read from the change feed
+	async fn process_async_view(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		ft: &str,
+	) -> Result<(), Error> {
+		let (ns, db) = (opt.ns()?, opt.db()?);
+		let txn = ctx.tx();
+		let beg = crate::key::thing::prefix(ns, db, ft);
+		let end = crate::key::thing::suffix(ns, db, ft);
+		let mut stream = txn.stream(beg..end, None);
+		while let Some(res) = stream.next().await {
+			let (k, v) = res?;
+			let id = crate::key::thing::Thing::decode(&k)?.id;
+			let rid = Thing::from((ft.to_owned(), id));
+			Document::process_view_change(stk, ctx, opt, self, rid, Value::None, v.into()).await?;
+		}
+		Ok(())
+	}
+}
+
 impl Display for DefineTableStatement {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		write!(f, "DEFINE TABLE")?;
@@ -263,6 +558,9 @@ impl Display for DefineTableStatement {
 		if let Some(ref v) = self.changefeed {
 			write!(f, " {v}")?;
 		}
+		if let Some(ref v) = self.expire {
+			write!(f, " {v}")?;
+		}
 		let _indent = if is_pretty() {
 			Some(pretty_indent())
 		} else {
@@ -283,6 +581,7 @@ impl InfoStructure for DefineTableStatement {
 			"kind".to_string() => self.kind.structure(),
 			"view".to_string(), if let Some(v) = self.view => v.structure(),
 			"changefeed".to_string(), if let Some(v) = self.changefeed => v.structure(),
+			"expire".to_string(), if let Some(v) = self.expire => v.structure(),
 			"permissions".to_string() => self.permissions.structure(),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
 		})
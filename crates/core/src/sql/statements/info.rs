@@ -157,6 +157,7 @@ impl InfoStatement {
 						"functions".to_string() => process(txn.all_db_functions(ns, db).await?),
 						"models".to_string() => process(txn.all_db_models(ns, db).await?),
 						"params".to_string() => process(txn.all_db_params(ns, db).await?),
+						"sequences".to_string() => process(txn.all_db_sequences(ns, db).await?),
 						"tables".to_string() => process(txn.all_tb(ns, db, version).await?),
 						"users".to_string() => process(txn.all_db_users(ns, db).await?),
 						"configs".to_string() => process(txn.all_db_configs(ns, db).await?),
@@ -197,6 +198,13 @@ impl InfoStatement {
 							}
 							out.into()
 						},
+						"sequences".to_string() => {
+							let mut out = Object::default();
+							for v in txn.all_db_sequences(ns, db).await?.iter() {
+								out.insert(v.name.to_raw(), v.to_string().into());
+							}
+							out.into()
+						},
 						"tables".to_string() => {
 							let mut out = Object::default();
 							for v in txn.all_tb(ns, db, version).await?.iter() {
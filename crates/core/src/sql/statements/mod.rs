@@ -61,13 +61,13 @@ pub use self::alter::{AlterStatement, AlterTableStatement};
 pub use self::define::{
 	DefineAccessStatement, DefineAnalyzerStatement, DefineDatabaseStatement, DefineEventStatement,
 	DefineFieldStatement, DefineFunctionStatement, DefineIndexStatement, DefineModelStatement,
-	DefineNamespaceStatement, DefineParamStatement, DefineStatement, DefineTableStatement,
-	DefineUserStatement,
+	DefineNamespaceStatement, DefineParamStatement, DefineSequenceStatement, DefineStatement,
+	DefineTableStatement, DefineUserStatement,
 };
 
 pub use self::remove::{
 	RemoveAccessStatement, RemoveAnalyzerStatement, RemoveDatabaseStatement, RemoveEventStatement,
 	RemoveFieldStatement, RemoveFunctionStatement, RemoveIndexStatement, RemoveModelStatement,
-	RemoveNamespaceStatement, RemoveParamStatement, RemoveStatement, RemoveTableStatement,
-	RemoveUserStatement,
+	RemoveNamespaceStatement, RemoveParamStatement, RemoveSequenceStatement, RemoveStatement,
+	RemoveTableStatement, RemoveUserStatement,
 };
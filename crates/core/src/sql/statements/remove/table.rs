@@ -8,7 +8,6 @@ use derive::Store;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Formatter};
-use uuid::Uuid;
 
 #[revisioned(revision = 3)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
@@ -50,6 +49,10 @@ impl RemoveTableStatement {
 			};
 			// Process each attached foreign table
 			for ft in fts.iter() {
+				// Skip views which only link to this table
+				if !ft.view.as_ref().is_some_and(|v| v.what.iter().any(|p| p.0 == self.name.0)) {
+					continue;
+				}
 				// Refresh the table cache
 				let key = crate::key::database::tb::new(opt.ns()?, opt.db()?, &ft.name);
 				let tb = txn.get_tb(opt.ns()?, opt.db()?, &ft.name).await?;
@@ -64,26 +67,9 @@ impl RemoveTableStatement {
 				.await?;
 			}
 			// Check if this is a foreign table
-			if let Some(view) = &tb.view {
-				// Process each foreign table
-				for ft in view.what.0.iter() {
-					// Save the view config
-					let key = crate::key::table::ft::new(opt.ns()?, opt.db()?, ft, &self.name);
-					txn.del(key).await?;
-					// Refresh the table cache for foreign tables
-					let key = crate::key::database::tb::new(opt.ns()?, opt.db()?, ft);
-					if let Ok(tb) = txn.get_tb(opt.ns()?, opt.db()?, ft).await {
-						txn.set(
-							key,
-							DefineTableStatement {
-								cache_tables_ts: Uuid::now_v7(),
-								..tb.as_ref().clone()
-							},
-							None,
-						)
-						.await?;
-					}
-				}
+			if tb.view.is_some() {
+				// Remove the view from the tables it is registered on
+				DefineTableStatement::remove_view_links(&txn, opt, &tb).await?;
 			}
 			// Clear the cache
 			txn.clear();
@@ -85,6 +85,10 @@ impl SelectStatement {
 				expr,
 				..
 			} => expr.writeable(),
+			Field::Window {
+				expr,
+				..
+			} => expr.args().iter().any(Value::writeable),
 		}) {
 			return true;
 		}
@@ -108,6 +112,10 @@ impl SelectStatement {
 		let stm = Statement::from(self);
 		// Create a new iterator
 		let mut i = Iterator::new();
+		// Stream the records if requested for this statement
+		if !self.only {
+			i.stream_records(ctx.records());
+		}
 		// Ensure futures are stored and the version is set if specified
 		let version = match &self.version {
 			Some(v) => Some(v.compute(stk, ctx, opt, doc).await?),
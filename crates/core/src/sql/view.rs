@@ -1,10 +1,13 @@
 use crate::sql::statements::info::InfoStructure;
-use crate::sql::{cond::Cond, field::Fields, group::Groups, table::Tables, Value};
+use crate::sql::{
+	cond::Cond, field::Field, field::Fields, group::Groups, table::Tables, Expression, Idiom, Part,
+	Value,
+};
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -13,6 +16,63 @@ pub struct View {
 	pub what: Tables,
 	pub cond: Option<Cond>,
 	pub group: Option<Groups>,
+	/// Whether the view is maintained from the change feed instead of within each write
+	#[revision(start = 2)]
+	pub asynchronous: bool,
+}
+
+impl View {
+	/// Returns the idioms which the view fields and condition are computed from
+	pub(crate) fn idioms(&self) -> Vec<&Idiom> {
+		let mut idioms = Vec::new();
+		for field in self.expr.iter() {
+			if let Field::Single {
+				expr,
+				..
+			} = field
+			{
+				collect_idioms(expr, &mut idioms);
+			}
+		}
+		if let Some(cond) = &self.cond {
+			collect_idioms(&cond.0, &mut idioms);
+		}
+		idioms
+	}
+
+	/// Checks if the view fields or condition follow a
+	/// record link or traverse a graph edge from the record
+	pub(crate) fn has_links(&self) -> bool {
+		self.idioms().iter().any(|idiom| match idiom.first() {
+			Some(Part::Graph(_)) => true,
+			Some(Part::Field(_)) => idiom.len() > 1,
+			_ => false,
+		})
+	}
+}
+
+fn collect_idioms<'a>(val: &'a Value, idioms: &mut Vec<&'a Idiom>) {
+	match val {
+		Value::Idiom(v) => idioms.push(v),
+		Value::Expression(v) => match v.as_ref() {
+			Expression::Unary {
+				v,
+				..
+			} => collect_idioms(v, idioms),
+			Expression::Binary {
+				l,
+				r,
+				..
+			} => {
+				collect_idioms(l, idioms);
+				collect_idioms(r, idioms);
+			}
+		},
+		Value::Function(v) => v.args().iter().for_each(|v| collect_idioms(v, idioms)),
+		Value::Array(v) => v.iter().for_each(|v| collect_idioms(v, idioms)),
+		Value::Object(v) => v.values().for_each(|v| collect_idioms(v, idioms)),
+		_ => {}
+	}
 }
 
 impl fmt::Display for View {
@@ -24,6 +84,9 @@ impl fmt::Display for View {
 		if let Some(ref v) = self.group {
 			write!(f, " {v}")?
 		}
+		if self.asynchronous {
+			write!(f, " ASYNC")?
+		}
 		Ok(())
 	}
 }
@@ -0,0 +1,106 @@
+use crate::sql::fmt::Fmt;
+use crate::sql::idiom::Idiom;
+use crate::sql::order::OrderList;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+pub(crate) const ROW_NUMBER: &str = "row_number";
+pub(crate) const RANK: &str = "rank";
+pub(crate) const DENSE_RANK: &str = "dense_rank";
+pub(crate) const LAG: &str = "lag";
+pub(crate) const LEAD: &str = "lead";
+pub(crate) const FIRST_VALUE: &str = "first_value";
+pub(crate) const LAST_VALUE: &str = "last_value";
+
+/// The window specification of an analytic function, as in
+/// `OVER (PARTITION BY ... ORDER BY ... ROWS BETWEEN ... AND ...)`
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct Window {
+	/// The idioms which split the rows into separate partitions
+	pub partition: Vec<Idiom>,
+	/// The ordering of the rows within each partition
+	pub order: Option<OrderList>,
+	/// The rows, relative to the current row, which an aggregate is computed over
+	pub frame: Option<Frame>,
+}
+
+impl Display for Window {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		let mut clauses = Vec::new();
+		if !self.partition.is_empty() {
+			clauses.push(format!("PARTITION BY {}", Fmt::comma_separated(&self.partition)));
+		}
+		if let Some(ref v) = self.order {
+			clauses.push(format!("ORDER BY {v}"));
+		}
+		if let Some(ref v) = self.frame {
+			clauses.push(v.to_string());
+		}
+		write!(f, "OVER ({})", clauses.join(" "))
+	}
+}
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct Frame {
+	pub start: FrameBound,
+	pub end: FrameBound,
+}
+
+impl Frame {
+	/// Returns the range of row positions in a partition of `len` rows which is covered by this frame
+	pub(crate) fn range(&self, pos: usize, len: usize) -> std::ops::Range<usize> {
+		let len = len as i64;
+		let beg = self.start.position(pos as i64, len).clamp(0, len);
+		let end = self.end.position(pos as i64, len).saturating_add(1).clamp(0, len);
+		beg as usize..end.max(beg) as usize
+	}
+}
+
+impl Display for Frame {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "ROWS BETWEEN {} AND {}", self.start, self.end)
+	}
+}
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum FrameBound {
+	UnboundedPreceding,
+	Preceding(u64),
+	CurrentRow,
+	Following(u64),
+	UnboundedFollowing,
+}
+
+impl FrameBound {
+	fn position(&self, pos: i64, len: i64) -> i64 {
+		match self {
+			Self::UnboundedPreceding => 0,
+			Self::Preceding(n) => pos.saturating_sub(i64::try_from(*n).unwrap_or(i64::MAX)),
+			Self::CurrentRow => pos,
+			Self::Following(n) => pos.saturating_add(i64::try_from(*n).unwrap_or(i64::MAX)),
+			Self::UnboundedFollowing => len - 1,
+		}
+	}
+}
+
+impl Display for FrameBound {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::UnboundedPreceding => write!(f, "UNBOUNDED PRECEDING"),
+			Self::Preceding(n) => write!(f, "{n} PRECEDING"),
+			Self::CurrentRow => write!(f, "CURRENT ROW"),
+			Self::Following(n) => write!(f, "{n} FOLLOWING"),
+			Self::UnboundedFollowing => write!(f, "UNBOUNDED FOLLOWING"),
+		}
+	}
+}
@@ -69,12 +69,15 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("ASC") => TokenKind::Keyword(Keyword::Ascending),
 	UniCase::ascii("ASCII") => TokenKind::Keyword(Keyword::Ascii),
 	UniCase::ascii("ASSERT") => TokenKind::Keyword(Keyword::Assert),
+	UniCase::ascii("ASYNC") => TokenKind::Keyword(Keyword::Async),
 	UniCase::ascii("AT") => TokenKind::Keyword(Keyword::At),
 	UniCase::ascii("AUTHENTICATE") => TokenKind::Keyword(Keyword::Authenticate),
 	UniCase::ascii("AUTO") => TokenKind::Keyword(Keyword::Auto),
+	UniCase::ascii("BATCH") => TokenKind::Keyword(Keyword::Batch),
 	UniCase::ascii("BEARER") => TokenKind::Keyword(Keyword::Bearer),
 	UniCase::ascii("BEFORE") => TokenKind::Keyword(Keyword::Before),
 	UniCase::ascii("BEGIN") => TokenKind::Keyword(Keyword::Begin),
+	UniCase::ascii("BETWEEN") => TokenKind::Keyword(Keyword::Between),
 	UniCase::ascii("BLANK") => TokenKind::Keyword(Keyword::Blank),
 	UniCase::ascii("BM25") => TokenKind::Keyword(Keyword::Bm25),
 	UniCase::ascii("BREAK") => TokenKind::Keyword(Keyword::Break),
@@ -92,6 +95,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("CONTENT") => TokenKind::Keyword(Keyword::Content),
 	UniCase::ascii("CONTINUE") => TokenKind::Keyword(Keyword::Continue),
 	UniCase::ascii("CREATE") => TokenKind::Keyword(Keyword::Create),
+	UniCase::ascii("CURRENT") => TokenKind::Keyword(Keyword::Current),
 	UniCase::ascii("DATABASE") => TokenKind::Keyword(Keyword::Database),
 	UniCase::ascii("DB") => TokenKind::Keyword(Keyword::Database),
 	UniCase::ascii("DEFAULT") => TokenKind::Keyword(Keyword::Default),
@@ -117,6 +121,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("ENFORCED") => TokenKind::Keyword(Keyword::Enforced),
 	UniCase::ascii("EXCLUDE") => TokenKind::Keyword(Keyword::Exclude),
 	UniCase::ascii("EXISTS") => TokenKind::Keyword(Keyword::Exists),
+	UniCase::ascii("EXPIRE") => TokenKind::Keyword(Keyword::Expire),
 	UniCase::ascii("EXPIRED") => TokenKind::Keyword(Keyword::Expired),
 	UniCase::ascii("EXPLAIN") => TokenKind::Keyword(Keyword::Explain),
 	UniCase::ascii("EXPUNGE") => TokenKind::Keyword(Keyword::Expunge),
@@ -130,11 +135,13 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("FLEXIBLE") => TokenKind::Keyword(Keyword::Flexible),
 	UniCase::ascii("FLEXI") => TokenKind::Keyword(Keyword::Flexible),
 	UniCase::ascii("FLEX") => TokenKind::Keyword(Keyword::Flexible),
+	UniCase::ascii("FOLLOWING") => TokenKind::Keyword(Keyword::Following),
 	UniCase::ascii("FOR") => TokenKind::Keyword(Keyword::For),
 	UniCase::ascii("FROM") => TokenKind::Keyword(Keyword::From),
 	UniCase::ascii("FULL") => TokenKind::Keyword(Keyword::Full),
 	UniCase::ascii("FUNCTION") => TokenKind::Keyword(Keyword::Function),
 	UniCase::ascii("FUNCTIONS") => TokenKind::Keyword(Keyword::Functions),
+	UniCase::ascii("FUZZY") => TokenKind::Keyword(Keyword::Fuzzy),
 	UniCase::ascii("GRANT") => TokenKind::Keyword(Keyword::Grant),
 	UniCase::ascii("GRAPHQL") => TokenKind::Keyword(Keyword::Graphql),
 	UniCase::ascii("GROUP") => TokenKind::Keyword(Keyword::Group),
@@ -155,6 +162,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("KEEP_PRUNED_CONNECTIONS") => TokenKind::Keyword(Keyword::KeepPrunedConnections),
 	UniCase::ascii("KILL") => TokenKind::Keyword(Keyword::Kill),
 	UniCase::ascii("LET") => TokenKind::Keyword(Keyword::Let),
+	UniCase::ascii("LENGTH") => TokenKind::Keyword(Keyword::Length),
 	UniCase::ascii("LIMIT") => TokenKind::Keyword(Keyword::Limit),
 	UniCase::ascii("LIVE") => TokenKind::Keyword(Keyword::Live),
 	UniCase::ascii("LOWERCASE") => TokenKind::Keyword(Keyword::Lowercase),
@@ -181,17 +189,22 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("OPTION") => TokenKind::Keyword(Keyword::Option),
 	UniCase::ascii("ORDER") => TokenKind::Keyword(Keyword::Order),
 	UniCase::ascii("ORIGINAL") => TokenKind::Keyword(Keyword::Original),
+	UniCase::ascii("OVER") => TokenKind::Keyword(Keyword::Over),
 	UniCase::ascii("OVERWRITE") => TokenKind::Keyword(Keyword::Overwrite),
 	UniCase::ascii("PARALLEL") => TokenKind::Keyword(Keyword::Parallel),
 	UniCase::ascii("PARAM") => TokenKind::Keyword(Keyword::Param),
+	UniCase::ascii("PARTITION") => TokenKind::Keyword(Keyword::Partition),
 	UniCase::ascii("PASSHASH") => TokenKind::Keyword(Keyword::Passhash),
 	UniCase::ascii("PASSWORD") => TokenKind::Keyword(Keyword::Password),
 	UniCase::ascii("PATCH") => TokenKind::Keyword(Keyword::Patch),
 	UniCase::ascii("PERMISSIONS") => TokenKind::Keyword(Keyword::Permissions),
 	UniCase::ascii("POSTINGS_CACHE") => TokenKind::Keyword(Keyword::PostingsCache),
 	UniCase::ascii("POSTINGS_ORDER") => TokenKind::Keyword(Keyword::PostingsOrder),
+	UniCase::ascii("PRECEDING") => TokenKind::Keyword(Keyword::Preceding),
+	UniCase::ascii("PRECISION") => TokenKind::Keyword(Keyword::Precision),
 	UniCase::ascii("PUNCT") => TokenKind::Keyword(Keyword::Punct),
 	UniCase::ascii("PURGE") => TokenKind::Keyword(Keyword::Purge),
+	UniCase::ascii("QUANTIZATION") => TokenKind::Keyword(Keyword::Quantization),
 	UniCase::ascii("RANGE") => TokenKind::Keyword(Keyword::Range),
 	UniCase::ascii("READONLY") => TokenKind::Keyword(Keyword::Readonly),
 	UniCase::ascii("RELATE") => TokenKind::Keyword(Keyword::Relate),
@@ -206,6 +219,8 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("ROLES") => TokenKind::Keyword(Keyword::Roles),
 	UniCase::ascii("ROOT") => TokenKind::Keyword(Keyword::Root),
 	UniCase::ascii("KV") => TokenKind::Keyword(Keyword::Root),
+	UniCase::ascii("ROW") => TokenKind::Keyword(Keyword::Row),
+	UniCase::ascii("ROWS") => TokenKind::Keyword(Keyword::Rows),
 	UniCase::ascii("SCHEMAFULL") => TokenKind::Keyword(Keyword::Schemafull),
 	UniCase::ascii("SCHEMAFUL") => TokenKind::Keyword(Keyword::Schemafull),
 	UniCase::ascii("SCHEMALESS") => TokenKind::Keyword(Keyword::Schemaless),
@@ -213,6 +228,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("SC") => TokenKind::Keyword(Keyword::Scope),
 	UniCase::ascii("SEARCH") => TokenKind::Keyword(Keyword::Search),
 	UniCase::ascii("SELECT") => TokenKind::Keyword(Keyword::Select),
+	UniCase::ascii("SEQUENCE") => TokenKind::Keyword(Keyword::Sequence),
 	UniCase::ascii("SESSION") => TokenKind::Keyword(Keyword::Session),
 	UniCase::ascii("SET") => TokenKind::Keyword(Keyword::Set),
 	UniCase::ascii("SHOW") => TokenKind::Keyword(Keyword::Show),
@@ -221,9 +237,12 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("SINCE") => TokenKind::Keyword(Keyword::Since),
 	UniCase::ascii("SLEEP") => TokenKind::Keyword(Keyword::Sleep),
 	UniCase::ascii("SNOWBALL") => TokenKind::Keyword(Keyword::Snowball),
+	UniCase::ascii("SPATIAL") => TokenKind::Keyword(Keyword::Spatial),
 	UniCase::ascii("SPLIT") => TokenKind::Keyword(Keyword::Split),
 	UniCase::ascii("START") => TokenKind::Keyword(Keyword::Start),
+	UniCase::ascii("STOPWORDS") => TokenKind::Keyword(Keyword::Stopwords),
 	UniCase::ascii("STRUCTURE") => TokenKind::Keyword(Keyword::Structure),
+	UniCase::ascii("SYNONYM") => TokenKind::Keyword(Keyword::Synonym),
 	UniCase::ascii("TABLE") => TokenKind::Keyword(Keyword::Table),
 	UniCase::ascii("TABLES") => TokenKind::Keyword(Keyword::Tables),
 	UniCase::ascii("TB") => TokenKind::Keyword(Keyword::Table),
@@ -237,8 +256,11 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("TOKENIZERS") => TokenKind::Keyword(Keyword::Tokenizers),
 	UniCase::ascii("TOKEN") => TokenKind::Keyword(Keyword::Token),
 	UniCase::ascii("TRANSACTION") => TokenKind::Keyword(Keyword::Transaction),
+	UniCase::ascii("TRUNCATE") => TokenKind::Keyword(Keyword::Truncate),
 	UniCase::ascii("true") => TokenKind::Keyword(Keyword::True),
 	UniCase::ascii("TYPE") => TokenKind::Keyword(Keyword::Type),
+	UniCase::ascii("UNBOUNDED") => TokenKind::Keyword(Keyword::Unbounded),
+	UniCase::ascii("UNICODE") => TokenKind::Keyword(Keyword::Unicode),
 	UniCase::ascii("UNIQUE") => TokenKind::Keyword(Keyword::Unique),
 	UniCase::ascii("UNSET") => TokenKind::Keyword(Keyword::Unset),
 	UniCase::ascii("UPDATE") => TokenKind::Keyword(Keyword::Update),
@@ -279,7 +279,11 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("search::analyze") => PathKind::Function,
 		UniCase::ascii("search::score") => PathKind::Function,
 		UniCase::ascii("search::highlight") => PathKind::Function,
+		UniCase::ascii("search::linear") => PathKind::Function,
 		UniCase::ascii("search::offsets") => PathKind::Function,
+		UniCase::ascii("search::rrf") => PathKind::Function,
+		//
+		UniCase::ascii("sequence::nextval") => PathKind::Function,
 		//
 		UniCase::ascii("session::ac") => PathKind::Function,
 		UniCase::ascii("session::db") => PathKind::Function,
@@ -454,6 +458,14 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("vector::similarity::jaccard") => PathKind::Function,
 		UniCase::ascii("vector::similarity::pearson") => PathKind::Function,
 		UniCase::ascii("vector::similarity::spearman") => PathKind::Function,
+		//
+		UniCase::ascii("dense_rank") => PathKind::Function,
+		UniCase::ascii("first_value") => PathKind::Function,
+		UniCase::ascii("lag") => PathKind::Function,
+		UniCase::ascii("last_value") => PathKind::Function,
+		UniCase::ascii("lead") => PathKind::Function,
+		UniCase::ascii("rank") => PathKind::Function,
+		UniCase::ascii("row_number") => PathKind::Function,
 		// constants
 		UniCase::ascii("math::E") => PathKind::Constant(Constant::MathE),
 		UniCase::ascii("math::FRAC_1_PI") => PathKind::Constant(Constant::MathFrac1Pi),
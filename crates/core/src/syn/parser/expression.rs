@@ -290,6 +290,28 @@ impl Parser<'_> {
 		Ok(op)
 	}
 
+	/// Parses the matches operator `@[ref][,FUZZY=distance]@`, the first `@` already consumed
+	fn parse_matches(&mut self) -> ParseResult<Operator> {
+		if self.eat(t!("@")) {
+			return Ok(Operator::Matches(None));
+		}
+		let reference = if self.peek_kind() == t!("FUZZY") {
+			None
+		} else {
+			let number = self.next_token_value()?;
+			if !self.eat(t!(",")) {
+				expected!(self, t!("@"));
+				return Ok(Operator::Matches(Some(number)));
+			}
+			Some(number)
+		};
+		expected!(self, t!("FUZZY"));
+		expected!(self, t!("="));
+		let distance = self.next_token_value()?;
+		expected!(self, t!("@"));
+		Ok(Operator::FuzzyMatches(reference, distance))
+	}
+
 	fn expression_is_relation(value: &Value) -> bool {
 		if let Value::Expression(x) = value {
 			return Self::operator_is_relation(x.operator());
@@ -345,16 +367,7 @@ impl Parser<'_> {
 			t!("*~") => Operator::AllLike,
 			t!("?~") => Operator::AnyLike,
 			t!("~") => Operator::Like,
-			t!("@") => {
-				let reference = (!self.eat(t!("@")))
-					.then(|| {
-						let number = self.next_token_value()?;
-						expected!(self, t!("@"));
-						Ok(number)
-					})
-					.transpose()?;
-				Operator::Matches(reference)
-			}
+			t!("@") => self.parse_matches()?,
 			t!("<=") => Operator::LessThanOrEqual,
 			t!("<") => Operator::LessThan,
 			t!(">=") => Operator::MoreThanOrEqual,
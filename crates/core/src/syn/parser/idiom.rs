@@ -2,8 +2,10 @@ use reblessive::Stk;
 
 use crate::{
 	sql::{
+		order::OrderList,
 		part::{DestructurePart, Recurse, RecurseInstruction},
-		Dir, Edges, Field, Fields, Graph, Ident, Idiom, Part, Table, Tables, Value,
+		Dir, Edges, Field, Fields, Frame, FrameBound, Graph, Ident, Idiom, Part, Table, Tables,
+		Value, Window,
 	},
 	syn::{
 		error::bail,
@@ -27,39 +29,26 @@ impl Parser<'_> {
 
 	/// Parse fields of a selecting query: `foo, bar` in `SELECT foo, bar FROM baz`.
 	///
+	/// Window functions are only allowed when `windows` is set, as they are
+	/// only computed by `SELECT` statements.
+	///
 	/// # Parser State
 	/// Expects the next tokens to be of a field set.
-	pub(super) async fn parse_fields(&mut self, ctx: &mut Stk) -> ParseResult<Fields> {
+	pub(super) async fn parse_fields(
+		&mut self,
+		ctx: &mut Stk,
+		windows: bool,
+	) -> ParseResult<Fields> {
 		if self.eat(t!("VALUE")) {
-			let expr = ctx.run(|ctx| self.parse_value_field(ctx)).await?;
-			let alias = if self.eat(t!("AS")) {
-				Some(self.parse_plain_idiom(ctx).await?)
-			} else {
-				None
-			};
-			Ok(Fields(
-				vec![Field::Single {
-					expr,
-					alias,
-				}],
-				true,
-			))
+			let field = self.parse_field(ctx, windows).await?;
+			Ok(Fields(vec![field], true))
 		} else {
 			let mut fields = Vec::new();
 			loop {
 				let field = if self.eat(t!("*")) {
 					Field::All
 				} else {
-					let expr = ctx.run(|ctx| self.parse_value_field(ctx)).await?;
-					let alias = if self.eat(t!("AS")) {
-						Some(self.parse_plain_idiom(ctx).await?)
-					} else {
-						None
-					};
-					Field::Single {
-						expr,
-						alias,
-					}
+					self.parse_field(ctx, windows).await?
 				};
 				fields.push(field);
 				if !self.eat(t!(",")) {
@@ -70,6 +59,128 @@ impl Parser<'_> {
 		}
 	}
 
+	/// Parses a single field expression, with an optional window and alias
+	async fn parse_field(&mut self, ctx: &mut Stk, windows: bool) -> ParseResult<Field> {
+		let before = self.peek().span;
+		let expr = ctx.run(|ctx| self.parse_value_field(ctx)).await?;
+		let expr_span = before.covers(self.last_span());
+		if self.peek_kind() == t!("OVER") {
+			let over = self.pop_peek().span;
+			if !windows {
+				bail!("Unexpected token `OVER`, window functions are only supported in SELECT statements",
+					@over => "windows can not be used here");
+			}
+			let expr = match expr {
+				Value::Function(f) if f.is_window() || f.is_aggregate() => *f,
+				_ => bail!("Expected a window or aggregate function before `OVER`",
+					@expr_span => "this expression can not be computed over a window"),
+			};
+			let window = self.parse_window(ctx).await?;
+			let alias = if self.eat(t!("AS")) {
+				Some(self.parse_plain_idiom(ctx).await?)
+			} else {
+				None
+			};
+			return Ok(Field::Window {
+				expr,
+				window,
+				alias,
+			});
+		}
+		let alias = if self.eat(t!("AS")) {
+			Some(self.parse_plain_idiom(ctx).await?)
+		} else {
+			None
+		};
+		Ok(Field::Single {
+			expr,
+			alias,
+		})
+	}
+
+	/// Parses a window specification
+	///
+	/// # Parser State
+	/// Expects the parser to have just eaten the `OVER` keyword.
+	async fn parse_window(&mut self, ctx: &mut Stk) -> ParseResult<Window> {
+		let start = expected!(self, t!("(")).span;
+		let mut window = Window::default();
+		if self.eat(t!("PARTITION")) {
+			expected!(self, t!("BY"));
+			window.partition.push(self.parse_basic_idiom(ctx).await?);
+			while self.eat(t!(",")) {
+				window.partition.push(self.parse_basic_idiom(ctx).await?);
+			}
+		}
+		if self.eat(t!("ORDER")) {
+			expected!(self, t!("BY"));
+			let mut orders = vec![self.parse_order(ctx).await?];
+			while self.eat(t!(",")) {
+				orders.push(self.parse_order(ctx).await?);
+			}
+			window.order = Some(OrderList(orders));
+		}
+		if self.eat(t!("ROWS")) {
+			window.frame = Some(if self.eat(t!("BETWEEN")) {
+				let start = self.parse_frame_bound()?;
+				expected!(self, t!("AND"));
+				let end = self.parse_frame_bound()?;
+				Frame {
+					start,
+					end,
+				}
+			} else {
+				Frame {
+					start: self.parse_frame_bound()?,
+					end: FrameBound::CurrentRow,
+				}
+			});
+		}
+		self.expect_closing_delimiter(t!(")"), start)?;
+		Ok(window)
+	}
+
+	/// Parses a single bound of a window frame
+	fn parse_frame_bound(&mut self) -> ParseResult<FrameBound> {
+		let peek = self.peek();
+		match peek.kind {
+			t!("UNBOUNDED") => {
+				self.pop_peek();
+				match self.peek_kind() {
+					t!("PRECEDING") => {
+						self.pop_peek();
+						Ok(FrameBound::UnboundedPreceding)
+					}
+					t!("FOLLOWING") => {
+						self.pop_peek();
+						Ok(FrameBound::UnboundedFollowing)
+					}
+					_ => unexpected!(self, self.peek(), "`PRECEDING` or `FOLLOWING`"),
+				}
+			}
+			t!("CURRENT") => {
+				self.pop_peek();
+				expected!(self, t!("ROW"));
+				Ok(FrameBound::CurrentRow)
+			}
+			TokenKind::Digits => {
+				let n = self.next_token_value::<u64>()?;
+				match self.peek_kind() {
+					t!("PRECEDING") => {
+						self.pop_peek();
+						Ok(FrameBound::Preceding(n))
+					}
+					t!("FOLLOWING") => {
+						self.pop_peek();
+						Ok(FrameBound::Following(n))
+					}
+					_ => unexpected!(self, self.peek(), "`PRECEDING` or `FOLLOWING`"),
+				}
+			}
+			_ => unexpected!(self, peek, "`UNBOUNDED`, `CURRENT ROW` or a number of rows"),
+		}
+	}
+
 	/// Parses a list of idioms separated by a `,`
 	pub(super) async fn parse_idiom_list(&mut self, ctx: &mut Stk) -> ParseResult<Vec<Idiom>> {
 		let mut res = vec![self.parse_plain_idiom(ctx).await?];
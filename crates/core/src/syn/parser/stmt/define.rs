@@ -2,7 +2,7 @@ use reblessive::Stk;
 
 use crate::cnf::EXPERIMENTAL_BEARER_ACCESS;
 use crate::sql::access_type::JwtAccessVerify;
-use crate::sql::index::HnswParams;
+use crate::sql::index::{HnswParams, SpatialParams};
 use crate::sql::statements::define::config::graphql::{GraphQLConfig, TableConfig};
 use crate::sql::statements::define::config::ConfigInner;
 use crate::sql::statements::define::DefineConfigStatement;
@@ -17,14 +17,16 @@ use crate::{
 			define::config::graphql, DefineAccessStatement, DefineAnalyzerStatement,
 			DefineDatabaseStatement, DefineEventStatement, DefineFieldStatement,
 			DefineFunctionStatement, DefineIndexStatement, DefineNamespaceStatement,
-			DefineParamStatement, DefineStatement, DefineTableStatement, DefineUserStatement,
+			DefineParamStatement, DefineSequenceStatement, DefineStatement, DefineTableStatement,
+			DefineUserStatement,
 		},
 		table_type,
 		tokenizer::Tokenizer,
-		user, AccessType, Ident, Idioms, Index, Kind, Param, Permissions, Scoring, Strand,
+		user, AccessType, Ident, Idioms, Index, Kind, Number, Param, Permissions, Scoring, Strand,
 		TableType, Values,
 	},
 	syn::{
+		error::bail,
 		parser::{
 			mac::{expected, unexpected},
 			ParseResult, Parser,
@@ -47,6 +49,7 @@ impl Parser<'_> {
 			t!("TOKEN") => self.parse_define_token().map(DefineStatement::Access),
 			t!("SCOPE") => self.parse_define_scope(ctx).await.map(DefineStatement::Access),
 			t!("PARAM") => self.parse_define_param(ctx).await.map(DefineStatement::Param),
+			t!("SEQUENCE") => self.parse_define_sequence().map(DefineStatement::Sequence),
 			t!("TABLE") => self.parse_define_table(ctx).await.map(DefineStatement::Table),
 			t!("EVENT") => {
 				ctx.run(|ctx| self.parse_define_event(ctx)).await.map(DefineStatement::Event)
@@ -699,6 +702,53 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	pub fn parse_define_sequence(&mut self) -> ParseResult<DefineSequenceStatement> {
+		let (if_not_exists, overwrite) = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			(true, false)
+		} else if self.eat(t!("OVERWRITE")) {
+			(false, true)
+		} else {
+			(false, false)
+		};
+		let name = self.next_token_value()?;
+
+		let mut res = DefineSequenceStatement {
+			name,
+			if_not_exists,
+			overwrite,
+			..Default::default()
+		};
+
+		loop {
+			match self.peek_kind() {
+				t!("BATCH") => {
+					self.pop_peek();
+					let span = self.peek().span;
+					res.batch = self.next_token_value()?;
+					if res.batch == 0 {
+						bail!("Invalid sequence batch, expected a value greater than 0", @span);
+					}
+				}
+				t!("START") => {
+					self.pop_peek();
+					let span = self.peek().span;
+					let Number::Int(start) = self.next_token_value()? else {
+						bail!("Invalid sequence start, expected an integer", @span);
+					};
+					res.start = start;
+				}
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = Some(self.next_token_value()?);
+				}
+				_ => break,
+			}
+		}
+		Ok(res)
+	}
+
 	pub async fn parse_define_table(&mut self, ctx: &mut Stk) -> ParseResult<DefineTableStatement> {
 		let (if_not_exists, overwrite) = if self.eat(t!("IF")) {
 			expected!(self, t!("NOT"));
@@ -768,6 +818,10 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.changefeed = Some(self.parse_changefeed()?);
 				}
+				t!("EXPIRE") => {
+					self.pop_peek();
+					res.expire = Some(ctx.run(|ctx| self.parse_expiry(ctx)).await?);
+				}
 				t!("AS") => {
 					self.pop_peek();
 					let peek = self.peek();
@@ -1048,6 +1102,7 @@ impl Parser<'_> {
 					let mut doc_ids_cache = 100;
 					let mut doc_ids_order = 100;
 					let mut mtree_cache = 100;
+					let mut quantization = None;
 					loop {
 						match self.peek_kind() {
 							t!("DISTANCE") => {
@@ -1074,6 +1129,10 @@ impl Parser<'_> {
 								self.pop_peek();
 								mtree_cache = self.next_token_value()?
 							}
+							t!("QUANTIZATION") => {
+								self.pop_peek();
+								quantization = Some(self.parse_vector_quantization()?);
+							}
 							_ => break,
 						}
 					}
@@ -1085,6 +1144,7 @@ impl Parser<'_> {
 						doc_ids_order,
 						doc_ids_cache,
 						mtree_cache,
+						quantization,
 					))
 				}
 				t!("HNSW") => {
@@ -1099,6 +1159,7 @@ impl Parser<'_> {
 					let mut ef_construction = 150;
 					let mut extend_candidates = false;
 					let mut keep_pruned_connections = false;
+					let mut quantization = None;
 					loop {
 						match self.peek_kind() {
 							t!("DISTANCE") => {
@@ -1133,6 +1194,10 @@ impl Parser<'_> {
 								self.pop_peek();
 								keep_pruned_connections = true;
 							}
+							t!("QUANTIZATION") => {
+								self.pop_peek();
+								quantization = Some(self.parse_vector_quantization()?);
+							}
 							_ => {
 								break;
 							}
@@ -1152,8 +1217,25 @@ impl Parser<'_> {
 						ef_construction,
 						extend_candidates,
 						keep_pruned_connections,
+						quantization,
 					));
 				}
+				t!("SPATIAL") => {
+					self.pop_peek();
+					let mut precision = SpatialParams::DEFAULT_PRECISION;
+					if self.eat(t!("PRECISION")) {
+						let span = self.peek().span;
+						precision = self.next_token_value()?;
+						if !(1..=SpatialParams::MAX_PRECISION).contains(&precision) {
+							bail!(
+								"Invalid spatial index precision, expected a value between 1 and {}",
+								SpatialParams::MAX_PRECISION,
+								@span
+							);
+						}
+					}
+					res.index = Index::Spatial(SpatialParams::new(precision));
+				}
 				t!("CONCURRENTLY") => {
 					self.pop_peek();
 					res.concurrently = true;
@@ -1236,6 +1318,32 @@ impl Parser<'_> {
 								self.expect_closing_delimiter(t!(")"), open_span)?;
 								filters.push(Filter::Mapper(path.into()))
 							}
+							t!("STOPWORDS") => {
+								let open_span = expected!(self, t!("(")).span;
+								let language = self.next_token_value()?;
+								self.expect_closing_delimiter(t!(")"), open_span)?;
+								filters.push(Filter::Stopwords(language))
+							}
+							t!("SYNONYM") => {
+								let open_span = expected!(self, t!("(")).span;
+								let path: Strand = self.next_token_value()?;
+								self.expect_closing_delimiter(t!(")"), open_span)?;
+								filters.push(Filter::Synonym(path.into()))
+							}
+							t!("LENGTH") => {
+								let open_span = expected!(self, t!("(")).span;
+								let a = self.next_token_value()?;
+								expected!(self, t!(","));
+								let b = self.next_token_value()?;
+								self.expect_closing_delimiter(t!(")"), open_span)?;
+								filters.push(Filter::Length(a, b));
+							}
+							t!("TRUNCATE") => {
+								let open_span = expected!(self, t!("(")).span;
+								let a = self.next_token_value()?;
+								self.expect_closing_delimiter(t!(")"), open_span)?;
+								filters.push(Filter::Truncate(a));
+							}
 							_ => unexpected!(self, next, "a filter"),
 						}
 						if !self.eat(t!(",")) {
@@ -1255,6 +1363,7 @@ impl Parser<'_> {
 							t!("CAMEL") => Tokenizer::Camel,
 							t!("CLASS") => Tokenizer::Class,
 							t!("PUNCT") => Tokenizer::Punct,
+							t!("UNICODE") => Tokenizer::Unicode,
 							_ => unexpected!(self, next, "a tokenizer"),
 						};
 						tokenizers.push(tokenizer);
@@ -1354,8 +1463,16 @@ impl Parser<'_> {
 
 					let next = self.next();
 					match next.kind {
-						t!("INCLUDE") => {}
-						t!("EXCLUDE") => {}
+						t!("INCLUDE") => {
+							tmp_fncs = Some(FunctionsConfig::Include(
+								self.parse_graphql_function_configs()?,
+							))
+						}
+						t!("EXCLUDE") => {
+							tmp_fncs = Some(FunctionsConfig::Exclude(
+								self.parse_graphql_function_configs()?,
+							))
+						}
 						t!("NONE") => {
 							tmp_fncs = Some(FunctionsConfig::None);
 						}
@@ -1394,6 +1511,22 @@ impl Parser<'_> {
 		Ok(acc)
 	}
 
+	fn parse_graphql_function_configs(&mut self) -> ParseResult<Vec<Ident>> {
+		let start = expected!(self, t!("[")).span;
+		let mut acc = vec![];
+		loop {
+			if self.eat(t!("]")) {
+				break;
+			}
+			acc.push(self.parse_custom_function_name()?);
+			if !self.eat(t!(",")) {
+				self.expect_closing_delimiter(t!("]"), start)?;
+				break;
+			}
+		}
+		Ok(acc)
+	}
+
 	pub fn parse_relation_schema(&mut self) -> ParseResult<table_type::Relation> {
 		let mut res = table_type::Relation {
 			from: None,
@@ -654,7 +654,7 @@ impl Parser<'_> {
 				self.pop_peek();
 				Fields::default()
 			}
-			_ => self.parse_fields(stk).await?,
+			_ => self.parse_fields(stk, false).await?,
 		};
 		expected!(self, t!("FROM"));
 		let what = match self.peek().kind {
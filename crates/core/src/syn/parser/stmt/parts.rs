@@ -7,9 +7,9 @@ use crate::syn::error::bail;
 use crate::{
 	sql::{
 		changefeed::ChangeFeed,
-		index::{Distance, VectorType},
-		Base, Cond, Data, Duration, Fetchs, Field, Fields, Group, Groups, Ident, Idiom, Output,
-		Permission, Permissions, Tables, Timeout, Value, View,
+		index::{Distance, VectorQuantization, VectorType},
+		Base, Cond, Data, Duration, Expiry, Fetchs, Field, Fields, Group, Groups, Ident, Idiom,
+		Output, Permission, Permissions, Tables, Timeout, Value, View,
 	},
 	syn::{
 		parser::{
@@ -97,7 +97,7 @@ impl Parser<'_> {
 				self.pop_peek();
 				Output::Before
 			}
-			_ => Output::Fields(self.parse_fields(ctx).await?),
+			_ => Output::Fields(self.parse_fields(ctx, false).await?),
 		};
 		Ok(Some(res))
 	}
@@ -129,7 +129,7 @@ impl Parser<'_> {
 		match self.peek().kind {
 			t!("$param") => Ok(vec![Value::Param(self.next_token_value()?).into()]),
 			t!("TYPE") => {
-				let fields = self.parse_fields(ctx).await?;
+				let fields = self.parse_fields(ctx, false).await?;
 				let fetches = fields
 					.0
 					.into_iter()
@@ -168,12 +168,23 @@ impl Parser<'_> {
 	) -> ParseResult<&'a Field> {
 		let mut found = None;
 		for field in fields.iter() {
-			let Field::Single {
-				expr,
-				alias,
-			} = field
-			else {
-				unreachable!()
+			let (expr, alias) = match field {
+				Field::Single {
+					expr,
+					alias,
+				} => (expr, alias),
+				Field::Window {
+					expr,
+					alias,
+					..
+				} => {
+					if *idiom == alias.clone().unwrap_or_else(|| expr.to_idiom()) {
+						found = Some(field);
+						break;
+					}
+					continue;
+				}
+				Field::All => unreachable!(),
 			};
 
 			if let Some(alias) = alias {
@@ -425,6 +436,19 @@ impl Parser<'_> {
 		})
 	}
 
+	/// Parses a table expiry production
+	///
+	/// # Parser State
+	/// Expects the parser to have already eaten the `EXPIRE` keyword
+	pub async fn parse_expiry(&mut self, ctx: &mut Stk) -> ParseResult<Expiry> {
+		let peek = self.next();
+		match peek.kind {
+			t!("AFTER") => Ok(Expiry::After(self.next_token_value()?)),
+			t!("AT") => Ok(Expiry::At(self.parse_local_idiom(ctx).await?)),
+			_ => unexpected!(self, peek, "`AFTER` or `AT`"),
+		}
+	}
+
 	/// Parses a view production
 	///
 	/// # Parse State
@@ -433,7 +457,7 @@ impl Parser<'_> {
 	pub async fn parse_view(&mut self, stk: &mut Stk) -> ParseResult<View> {
 		expected!(self, t!("SELECT"));
 		let before_fields = self.peek().span;
-		let fields = self.parse_fields(stk).await?;
+		let fields = self.parse_fields(stk, false).await?;
 		let fields_span = before_fields.covers(self.recent_span());
 		expected!(self, t!("FROM"));
 		let mut from = vec![self.next_token_value()?];
@@ -443,12 +467,14 @@ impl Parser<'_> {
 
 		let cond = self.try_parse_condition(stk).await?;
 		let group = self.try_parse_group(stk, &fields, fields_span).await?;
+		let asynchronous = self.eat(t!("ASYNC"));
 
 		Ok(View {
 			expr: fields,
 			what: Tables(from),
 			cond,
 			group,
+			asynchronous,
 		})
 	}
 
@@ -490,6 +516,18 @@ impl Parser<'_> {
 		}
 	}
 
+	pub fn parse_vector_quantization(&mut self) -> ParseResult<VectorQuantization> {
+		let kind = self.next_token_value::<Ident>()?;
+		match kind.0.as_str() {
+			v if v.eq_ignore_ascii_case("SCALAR") => Ok(VectorQuantization::Scalar),
+			v if v.eq_ignore_ascii_case("BINARY") => Ok(VectorQuantization::Binary),
+			found => bail!(
+				"Unexpected vector quantization `{found}` expected `SCALAR` or `BINARY`",
+				@self.last_span()
+			),
+		}
+	}
+
 	pub fn parse_custom_function_name(&mut self) -> ParseResult<Ident> {
 		expected!(self, t!("fn"));
 		expected!(self, t!("::"));
@@ -8,6 +8,7 @@ use crate::{
 		With,
 	},
 	syn::{
+		error::bail,
 		parser::{
 			mac::{expected, unexpected},
 			ParseResult, Parser,
@@ -24,7 +25,7 @@ impl Parser<'_> {
 		stk: &mut Stk,
 	) -> ParseResult<SelectStatement> {
 		let before = self.peek().span;
-		let expr = self.parse_fields(stk).await?;
+		let expr = self.parse_fields(stk, true).await?;
 		let fields_span = before.covers(self.last_span());
 
 		let omit = if self.eat(t!("OMIT")) {
@@ -47,6 +48,10 @@ impl Parser<'_> {
 		let cond = self.try_parse_condition(stk).await?;
 		let split = self.try_parse_split(stk, &expr, fields_span).await?;
 		let group = self.try_parse_group(stk, &expr, fields_span).await?;
+		if group.is_some() && expr.has_windows() {
+			bail!("Window functions can not be used together with a GROUP clause",
+				@fields_span => "A window function was used here")
+		}
 		let order = self.try_parse_orders(stk, &expr, fields_span).await?;
 		let (limit, start) = if let t!("START") = self.peek_kind() {
 			let start = self.try_parse_start(stk).await?;
@@ -190,7 +195,10 @@ impl Parser<'_> {
 		Ok(Some(Ordering::Order(OrderList(orders))))
 	}
 
-	async fn parse_order(&mut self, ctx: &mut Stk) -> ParseResult<Order> {
+	pub(in crate::syn::parser) async fn parse_order(
+		&mut self,
+		ctx: &mut Stk,
+	) -> ParseResult<Order> {
 		let start = self.parse_basic_idiom(ctx).await?;
 		let collate = self.eat(t!("COLLATE"));
 		let numeric = self.eat(t!("NUMERIC"));
@@ -151,3 +151,22 @@ fn test_non_valid_utf8() {
 	let mut stack = reblessive::Stack::new();
 	stack.enter(|ctx| parser.parse_query(ctx)).finish().unwrap_err();
 }
+
+#[test]
+fn window_keywords_are_identifiers() {
+	// The keywords of window functions are not reserved, so existing names still parse
+	for src in [
+		"SELECT over, partition, rows, row, current, preceding, following, unbounded FROM rows",
+		"CREATE partition:1 SET row = 1",
+		"SELECT * FROM t WHERE current = over ORDER BY row",
+		"SELECT partition, count() AS rows FROM t GROUP BY partition",
+		"DEFINE FIELD current ON TABLE over",
+		"RETURN { current: 1, over: 2 }.current",
+	] {
+		let res = test_parse!(parse_query, src).unwrap();
+		let sql = res.to_string();
+		let out = test_parse!(parse_query, &sql).unwrap();
+		assert_eq!(res, out, "{src}");
+	}
+	assert_eq!(Ident("rows".to_string()).to_string(), "rows");
+}
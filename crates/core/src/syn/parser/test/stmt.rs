@@ -8,7 +8,10 @@ use crate::{
 		block::Entry,
 		changefeed::ChangeFeed,
 		filter::Filter,
-		index::{Distance, HnswParams, MTreeParams, SearchParams, VectorType},
+		index::{
+			Distance, HnswParams, MTreeParams, SearchParams, SpatialParams, VectorQuantization,
+			VectorType,
+		},
 		language::Language,
 		order::{OrderList, Ordering},
 		statements::{
@@ -23,18 +26,19 @@ use crate::{
 			ContinueStatement, CreateStatement, DefineAccessStatement, DefineAnalyzerStatement,
 			DefineDatabaseStatement, DefineEventStatement, DefineFieldStatement,
 			DefineFunctionStatement, DefineIndexStatement, DefineNamespaceStatement,
-			DefineParamStatement, DefineStatement, DefineTableStatement, DeleteStatement,
-			ForeachStatement, IfelseStatement, InfoStatement, InsertStatement, KillStatement,
-			OptionStatement, OutputStatement, RelateStatement, RemoveAccessStatement,
-			RemoveAnalyzerStatement, RemoveDatabaseStatement, RemoveEventStatement,
-			RemoveFieldStatement, RemoveFunctionStatement, RemoveIndexStatement,
-			RemoveNamespaceStatement, RemoveParamStatement, RemoveStatement, RemoveTableStatement,
-			RemoveUserStatement, SelectStatement, SetStatement, ThrowStatement, UpdateStatement,
-			UpsertStatement, UseStatement,
+			DefineParamStatement, DefineSequenceStatement, DefineStatement, DefineTableStatement,
+			DeleteStatement, ForeachStatement, IfelseStatement, InfoStatement, InsertStatement,
+			KillStatement, OptionStatement, OutputStatement, RelateStatement,
+			RemoveAccessStatement, RemoveAnalyzerStatement, RemoveDatabaseStatement,
+			RemoveEventStatement, RemoveFieldStatement, RemoveFunctionStatement,
+			RemoveIndexStatement, RemoveNamespaceStatement, RemoveParamStatement,
+			RemoveSequenceStatement, RemoveStatement, RemoveTableStatement, RemoveUserStatement,
+			SelectStatement, SetStatement, ThrowStatement, UpdateStatement, UpsertStatement,
+			UseStatement,
 		},
 		tokenizer::Tokenizer,
 		user::UserDuration,
-		Algorithm, Array, Base, Block, Cond, Data, Datetime, Dir, Duration, Edges, Explain,
+		Algorithm, Array, Base, Block, Cond, Data, Datetime, Dir, Duration, Edges, Expiry, Explain,
 		Expression, Fetch, Fetchs, Field, Fields, Future, Graph, Group, Groups, Id, Ident, Idiom,
 		Idioms, Index, Kind, Limit, Number, Object, Operator, Order, Output, Param, Part,
 		Permission, Permissions, Scoring, Split, Splits, Start, Statement, Strand, Subquery, Table,
@@ -224,6 +228,55 @@ fn parse_define_function() {
 	)
 }
 
+#[test]
+fn parse_define_config_graphql_functions() {
+	use crate::sql::statements::define::config::graphql::{
+		FunctionsConfig, GraphQLConfig, TableConfig, TablesConfig,
+	};
+	use crate::sql::statements::define::config::{ConfigInner, DefineConfigStatement};
+
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE CONFIG GRAPHQL TABLES INCLUDE foo FUNCTIONS INCLUDE [fn::foo, fn::foo::bar]"#
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Config(DefineConfigStatement {
+			inner: ConfigInner::GraphQL(GraphQLConfig {
+				tables: TablesConfig::Include(vec![TableConfig {
+					name: "foo".to_string()
+				}]),
+				functions: FunctionsConfig::Include(vec![
+					Ident("foo".to_string()),
+					Ident("foo::bar".to_string())
+				]),
+			}),
+			if_not_exists: false,
+			overwrite: false,
+		}))
+	);
+	assert_eq!(
+		res.to_string(),
+		"DEFINE CONFIG GRAPHQL TABLES INCLUDE foo FUNCTIONS INCLUDE [fn::foo, fn::foo::bar]"
+	);
+
+	let res =
+		test_parse!(parse_stmt, r#"DEFINE CONFIG GRAPHQL FUNCTIONS EXCLUDE [fn::foo]"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Config(DefineConfigStatement {
+			inner: ConfigInner::GraphQL(GraphQLConfig {
+				tables: TablesConfig::None,
+				functions: FunctionsConfig::Exclude(vec![Ident("foo".to_string())]),
+			}),
+			if_not_exists: false,
+			overwrite: false,
+		}))
+	);
+}
+
 #[test]
 fn parse_define_user() {
 	// Password.
@@ -1768,6 +1821,38 @@ fn parse_define_param() {
 	);
 }
 
+#[test]
+fn parse_define_sequence() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE SEQUENCE order_no BATCH 100 START -1000 COMMENT "orders""#
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Sequence(DefineSequenceStatement {
+			name: Ident("order_no".to_string()),
+			batch: 100,
+			start: -1000,
+			comment: Some(Strand("orders".to_string())),
+			if_not_exists: false,
+			overwrite: false,
+			cache_ts: uuid::Uuid::default(),
+		}))
+	);
+
+	let res = test_parse!(parse_stmt, r#"DEFINE SEQUENCE IF NOT EXISTS order_no"#).unwrap();
+	let Statement::Define(DefineStatement::Sequence(res)) = res else {
+		panic!()
+	};
+	assert_eq!(res.batch, 1000);
+	assert_eq!(res.start, 0);
+	assert!(res.if_not_exists);
+
+	test_parse!(parse_stmt, r#"DEFINE SEQUENCE order_no BATCH 0"#).unwrap_err();
+	test_parse!(parse_stmt, r#"DEFINE SEQUENCE order_no START 1.5"#).unwrap_err();
+}
+
 #[test]
 fn parse_define_table() {
 	let res =
@@ -1792,6 +1877,7 @@ fn parse_define_table() {
 				what: Tables(vec![Table("bar".to_owned())]),
 				cond: None,
 				group: Some(Groups(vec![Group(Idiom(vec![Part::Field(Ident("foo".to_owned()))]))])),
+				asynchronous: false,
 			}),
 			permissions: Permissions {
 				select: Permission::Specific(Value::Expression(Box::new(
@@ -1818,10 +1904,51 @@ fn parse_define_table() {
 			cache_tables_ts: uuid::Uuid::default(),
 			cache_indexes_ts: uuid::Uuid::default(),
 			cache_lives_ts: uuid::Uuid::default(),
+			expire: None,
 		}))
 	);
 }
 
+#[test]
+fn parse_define_table_async_view() {
+	let res =
+		test_parse!(parse_stmt, r#"DEFINE TABLE name AS SELECT foo FROM bar WHERE foo > 1 ASYNC"#)
+			.unwrap();
+	let Statement::Define(DefineStatement::Table(stm)) = res else {
+		panic!()
+	};
+	let view = stm.view.unwrap();
+	assert!(view.asynchronous);
+	assert_eq!(view.to_string(), "AS SELECT foo FROM bar WHERE foo > 1 ASYNC");
+
+	let res = test_parse!(parse_stmt, r#"DEFINE TABLE name AS SELECT foo FROM bar"#).unwrap();
+	let Statement::Define(DefineStatement::Table(stm)) = res else {
+		panic!()
+	};
+	assert!(!stm.view.unwrap().asynchronous);
+}
+
+#[test]
+fn parse_define_table_expire() {
+	let res = test_parse!(parse_stmt, r#"DEFINE TABLE name EXPIRE AFTER 1h"#).unwrap();
+	let Statement::Define(DefineStatement::Table(stm)) = res else {
+		panic!()
+	};
+	assert_eq!(stm.expire, Some(Expiry::After(Duration(std::time::Duration::from_secs(3600)))));
+
+	let res = test_parse!(parse_stmt, r#"DEFINE TABLE name EXPIRE AT expires.at"#).unwrap();
+	let Statement::Define(DefineStatement::Table(stm)) = res else {
+		panic!()
+	};
+	assert_eq!(
+		stm.expire,
+		Some(Expiry::At(Idiom(vec![
+			Part::Field(Ident("expires".to_owned())),
+			Part::Field(Ident("at".to_owned()))
+		])))
+	);
+}
+
 #[test]
 fn parse_define_event() {
 	let res =
@@ -1999,6 +2126,7 @@ fn parse_define_index() {
 				doc_ids_cache: 8,
 				mtree_cache: 9,
 				vector_type: VectorType::I16,
+				quantization: None,
 			}),
 			comment: None,
 			if_not_exists: false,
@@ -2026,6 +2154,7 @@ fn parse_define_index() {
 				extend_candidates: true,
 				keep_pruned_connections: true,
 				ml: 0.5.into(),
+				quantization: None,
 			}),
 			comment: None,
 			if_not_exists: false,
@@ -2033,6 +2162,95 @@ fn parse_define_index() {
 			concurrently: false
 		}))
 	);
+
+	let res =
+		test_parse!(parse_stmt, r#"DEFINE INDEX index ON TABLE table FIELDS a HNSW DIMENSION 128 DISTANCE COSINE QUANTIZATION SCALAR"#).unwrap();
+
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Index(DefineIndexStatement {
+			name: Ident("index".to_owned()),
+			what: Ident("table".to_owned()),
+			cols: Idioms(vec![Idiom(vec![Part::Field(Ident("a".to_owned()))]),]),
+			index: Index::Hnsw(HnswParams {
+				dimension: 128,
+				distance: Distance::Cosine,
+				vector_type: VectorType::F64,
+				m: 12,
+				m0: 24,
+				ef_construction: 150,
+				extend_candidates: false,
+				keep_pruned_connections: false,
+				ml: (1.0 / 12f64.ln()).into(),
+				quantization: Some(VectorQuantization::Scalar),
+			}),
+			comment: None,
+			if_not_exists: false,
+			overwrite: false,
+			concurrently: false
+		}))
+	);
+
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE INDEX index ON TABLE table FIELDS a MTREE DIMENSION 4 QUANTIZATION BINARY"#
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Index(DefineIndexStatement {
+			name: Ident("index".to_owned()),
+			what: Ident("table".to_owned()),
+			cols: Idioms(vec![Idiom(vec![Part::Field(Ident("a".to_owned()))]),]),
+			index: Index::MTree(MTreeParams {
+				dimension: 4,
+				distance: Distance::Euclidean,
+				capacity: 40,
+				doc_ids_order: 100,
+				doc_ids_cache: 100,
+				mtree_cache: 100,
+				vector_type: VectorType::F64,
+				quantization: Some(VectorQuantization::Binary),
+			}),
+			comment: None,
+			if_not_exists: false,
+			overwrite: false,
+			concurrently: false
+		}))
+	);
+
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE INDEX index ON TABLE table FIELDS a SPATIAL PRECISION 6"#
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Index(DefineIndexStatement {
+			name: Ident("index".to_owned()),
+			what: Ident("table".to_owned()),
+			cols: Idioms(vec![Idiom(vec![Part::Field(Ident("a".to_owned()))]),]),
+			index: Index::Spatial(SpatialParams {
+				precision: 6,
+			}),
+			comment: None,
+			if_not_exists: false,
+			overwrite: false,
+			concurrently: false
+		}))
+	);
+
+	let res =
+		test_parse!(parse_stmt, r#"DEFINE INDEX index ON TABLE table FIELDS a SPATIAL"#).unwrap();
+	let Statement::Define(DefineStatement::Index(res)) = res else {
+		panic!()
+	};
+	assert_eq!(res.index, Index::Spatial(SpatialParams::new(SpatialParams::DEFAULT_PRECISION)));
+
+	test_parse!(parse_stmt, r#"DEFINE INDEX index ON TABLE table FIELDS a SPATIAL PRECISION 13"#)
+		.unwrap_err();
 }
 
 #[test]
@@ -2068,6 +2286,32 @@ fn parse_define_analyzer() {
 	)
 }
 
+#[test]
+fn parse_define_analyzer_unicode_filters() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE ANALYZER ana TOKENIZERS UNICODE FILTERS LOWERCASE, STOPWORDS(english), SYNONYM('/synonyms.txt'), LENGTH(2,20), TRUNCATE(10)"#
+	).unwrap();
+	let Statement::Define(DefineStatement::Analyzer(res)) = res else {
+		panic!()
+	};
+	assert_eq!(res.tokenizers, Some(vec![Tokenizer::Unicode]));
+	assert_eq!(
+		res.filters,
+		Some(vec![
+			Filter::Lowercase,
+			Filter::Stopwords(Language::English),
+			Filter::Synonym("/synonyms.txt".to_owned()),
+			Filter::Length(2, 20),
+			Filter::Truncate(10),
+		])
+	);
+	assert_eq!(
+		res.to_string(),
+		"DEFINE ANALYZER ana TOKENIZERS UNICODE FILTERS LOWERCASE,STOPWORDS(ENGLISH),SYNONYM(/synonyms.txt),LENGTH(2,20),TRUNCATE(10)"
+	);
+}
+
 #[test]
 fn parse_delete() {
 	let res = test_parse!(
@@ -2744,6 +2988,15 @@ fn parse_remove() {
 		}))
 	);
 
+	let res = test_parse!(parse_stmt, r#"REMOVE SEQUENCE IF EXISTS foo"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Remove(RemoveStatement::Sequence(RemoveSequenceStatement {
+			name: Ident("foo".to_owned()),
+			if_exists: true,
+		}))
+	);
+
 	let res = test_parse!(parse_stmt, r#"REMOVE TABLE foo"#).unwrap();
 	assert_eq!(
 		res,
@@ -3111,3 +3364,20 @@ fn parse_access_purge() {
 fn parse_like_operator() {
 	test_parse!(parse_stmt, r#"SELECT * FROM "a" ~ "b"; "#).unwrap();
 }
+
+#[test]
+fn parse_window_only_in_select() {
+	test_parse!(parse_stmt, r#"SELECT rank() OVER (ORDER BY points DESC) AS rank FROM player"#)
+		.unwrap();
+	test_parse!(parse_stmt, r#"SELECT VALUE row_number() OVER () FROM player"#).unwrap();
+
+	for sql in [
+		r#"CREATE player RETURN rank() OVER (ORDER BY points DESC)"#,
+		r#"UPDATE player RETURN VALUE row_number() OVER ()"#,
+		r#"LIVE SELECT rank() OVER (ORDER BY points DESC) FROM player"#,
+		r#"DEFINE TABLE ranks AS SELECT rank() OVER (ORDER BY points DESC) FROM player"#,
+	] {
+		let res = test_parse!(parse_stmt, sql);
+		assert!(res.is_err(), "Unexpected successful parsing of a window in `{sql}`: {res:?}");
+	}
+}
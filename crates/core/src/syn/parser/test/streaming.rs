@@ -265,6 +265,7 @@ fn statements() -> Vec<Statement> {
 				what: Tables(vec![Table("bar".to_owned())]),
 				cond: None,
 				group: Some(Groups(vec![Group(Idiom(vec![Part::Field(Ident("foo".to_owned()))]))])),
+				asynchronous: false,
 			}),
 			permissions: Permissions {
 				select: Permission::Specific(Value::Expression(Box::new(
@@ -291,6 +292,7 @@ fn statements() -> Vec<Statement> {
 			cache_tables_ts: uuid::Uuid::default(),
 			cache_indexes_ts: uuid::Uuid::default(),
 			cache_lives_ts: uuid::Uuid::default(),
+			expire: None,
 		})),
 		Statement::Define(DefineStatement::Event(DefineEventStatement {
 			name: Ident("event".to_owned()),
@@ -378,6 +380,7 @@ fn statements() -> Vec<Statement> {
 				doc_ids_cache: 8,
 				mtree_cache: 9,
 				vector_type: VectorType::F64,
+				quantization: None,
 			}),
 			comment: None,
 			if_not_exists: false,
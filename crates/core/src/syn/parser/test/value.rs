@@ -53,6 +53,25 @@ fn parse_like_operator() {
 	test_parse!(parse_value_field, "a ~ b").unwrap();
 }
 
+#[test]
+fn parse_matches_operator() {
+	for (sql, op) in [
+		("a @@ 'b'", Operator::Matches(None)),
+		("a @1@ 'b'", Operator::Matches(Some(1))),
+		("a @fuzzy=2@ 'b'", Operator::FuzzyMatches(None, 2)),
+		("a @1,FUZZY=1@ 'b'", Operator::FuzzyMatches(Some(1), 1)),
+	] {
+		let value = test_parse!(parse_value_field, sql).unwrap();
+		let Value::Expression(x) = value else {
+			panic!("not the right value type");
+		};
+		assert_eq!(x.operator(), &op);
+	}
+	assert_eq!(Operator::FuzzyMatches(Some(1), 2).to_string(), "@1,FUZZY=2@");
+	assert_eq!(Operator::FuzzyMatches(None, 2).to_string(), "@FUZZY=2@");
+	test_parse!(parse_value_field, "a @1,2@ 'b'").unwrap_err();
+}
+
 #[test]
 fn parse_range_operator() {
 	test_parse!(parse_value_field, "1..2").unwrap();
@@ -35,12 +35,15 @@ keyword! {
 	Ascending => "ASCENDING",
 	Ascii => "ASCII",
 	Assert => "ASSERT",
+	Async => "ASYNC",
 	At => "AT",
 	Authenticate => "AUTHENTICATE",
 	Auto => "AUTO",
+	Batch => "BATCH",
 	Bearer => "BEARER",
 	Before => "BEFORE",
 	Begin => "BEGIN",
+	Between => "BETWEEN",
 	Blank => "BLANK",
 	Bm25 => "BM25",
 	Break => "BREAK",
@@ -58,6 +61,7 @@ keyword! {
 	Content => "CONTENT",
 	Continue => "CONTINUE",
 	Create => "CREATE",
+	Current => "CURRENT",
 	Database => "DATABASE",
 	Default => "DEFAULT",
 	Define => "DEFINE",
@@ -80,6 +84,7 @@ keyword! {
 	Enforced => "ENFORCED",
 	Exclude => "EXCLUDE",
 	Exists => "EXISTS",
+	Expire => "EXPIRE",
 	Expired => "EXPIRED",
 	Explain => "EXPLAIN",
 	Expunge => "EXPUNGE",
@@ -90,11 +95,13 @@ keyword! {
 	Fields => "FIELDS",
 	Filters => "FILTERS",
 	Flexible => "FLEXIBLE",
+	Following => "FOLLOWING",
 	For => "FOR",
 	From => "FROM",
 	Full => "FULL",
 	Function => "FUNCTION",
 	Functions => "FUNCTIONS",
+	Fuzzy => "FUZZY",
 	Grant => "GRANT",
 	Graphql => "GRAPHQL",
 	Group => "GROUP",
@@ -115,6 +122,7 @@ keyword! {
 	KeepPrunedConnections => "KEEP_PRUNED_CONNECTIONS",
 	Kill => "KILL",
 	Let => "LET",
+	Length => "LENGTH",
 	Limit => "LIMIT",
 	Live => "LIVE",
 	Lowercase => "LOWERCASE",
@@ -139,17 +147,22 @@ keyword! {
 	Option => "OPTION",
 	Order => "ORDER",
 	Original => "ORIGINAL",
+	Over => "OVER",
 	Overwrite => "OVERWRITE",
 	Parallel => "PARALLEL",
 	Param => "PARAM",
+	Partition => "PARTITION",
 	Passhash => "PASSHASH",
 	Password => "PASSWORD",
 	Patch => "PATCH",
 	Permissions => "PERMISSIONS",
 	PostingsCache => "POSTINGS_CACHE",
 	PostingsOrder => "POSTINGS_ORDER",
+	Preceding => "PRECEDING",
+	Precision => "PRECISION",
 	Punct => "PUNCT",
 	Purge => "PURGE",
+	Quantization => "QUANTIZATION",
 	Range => "RANGE",
 	Readonly => "READONLY",
 	Rebuild => "REBUILD",
@@ -163,11 +176,14 @@ keyword! {
 	Revoked => "REVOKED",
 	Roles => "ROLES",
 	Root => "ROOT",
+	Row => "ROW",
+	Rows => "ROWS",
 	Schemafull => "SCHEMAFULL",
 	Schemaless => "SCHEMALESS",
 	Scope => "SCOPE",
 	Search => "SEARCH",
 	Select => "SELECT",
+	Sequence => "SEQUENCE",
 	Session => "SESSION",
 	Set => "SET",
 	Show => "SHOW",
@@ -176,9 +192,12 @@ keyword! {
 	Since => "SINCE",
 	Sleep => "SLEEP",
 	Snowball => "SNOWBALL",
+	Spatial => "SPATIAL",
 	Split => "SPLIT",
 	Start => "START",
+	Stopwords => "STOPWORDS",
 	Structure => "STRUCTURE",
+	Synonym => "SYNONYM",
 	Table => "TABLE",
 	Tables => "TABLES",
 	TempFiles => "TEMPFILES",
@@ -191,8 +210,11 @@ keyword! {
 	Token => "TOKEN",
 	To => "TO",
 	Transaction => "TRANSACTION",
+	Truncate => "TRUNCATE",
 	True => "true",
 	Type => "TYPE",
+	Unbounded => "UNBOUNDED",
+	Unicode => "UNICODE",
 	Unique => "UNIQUE",
 	Unset => "UNSET",
 	Update => "UPDATE",
@@ -57,6 +57,7 @@ async fn mtree_index(
 		100,
 		cache_size as u32,
 		cache_size as u32,
+		None,
 	);
 	MTreeIndex::new(ds.index_store(), tx, IndexKeyBase::default(), &p, tt).await.unwrap()
 }
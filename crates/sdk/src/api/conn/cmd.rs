@@ -1,5 +1,9 @@
 use super::MlExportConfig;
-use crate::{opt::Resource, value::Notification, Result};
+use crate::{
+	opt::{Resource, RetryPolicy},
+	value::Notification,
+	Result,
+};
 use bincode::Options;
 use channel::Sender;
 use revision::Revisioned;
@@ -7,6 +11,7 @@ use serde::{ser::SerializeMap as _, Serialize};
 use std::io::Read;
 use std::path::PathBuf;
 use surrealdb_core::kvs::export::Config as DbExportConfig;
+use surrealdb_core::kvs::import::Config as DbImportConfig;
 use surrealdb_core::sql::{Array as CoreArray, Object as CoreObject, Query, Value as CoreValue};
 use uuid::Uuid;
 
@@ -68,6 +73,13 @@ pub(crate) enum Command {
 	Query {
 		query: Query,
 		variables: CoreObject,
+		retry: Option<RetryPolicy>,
+	},
+	QueryStream {
+		id: Uuid,
+		query: Query,
+		variables: CoreObject,
+		records: Sender<Result<Vec<CoreValue>>>,
 	},
 	ExportFile {
 		path: PathBuf,
@@ -87,10 +99,19 @@ pub(crate) enum Command {
 	},
 	ImportFile {
 		path: PathBuf,
+		config: Option<DbImportConfig>,
 	},
 	ImportMl {
 		path: PathBuf,
 	},
+	Backup {
+		path: PathBuf,
+		since: Option<u64>,
+	},
+	Restore {
+		path: PathBuf,
+		until: Option<u64>,
+	},
 	Health,
 	Version,
 	Set {
@@ -112,9 +133,34 @@ pub(crate) enum Command {
 		version: Option<String>,
 		args: CoreArray,
 	},
+	Begin,
+	Commit {
+		txn: Uuid,
+	},
+	Cancel {
+		txn: Uuid,
+	},
 }
 
 impl Command {
+	/// Applies the retry policy of the session to a query which does not have its own,
+	/// unless the query is run within a transaction, as only its statements are retried
+	#[cfg(any(feature = "protocol-ws", feature = "protocol-http"))]
+	pub(crate) fn with_session_retry(self, txn: Option<Uuid>, policy: Option<RetryPolicy>) -> Self {
+		match self {
+			Command::Query {
+				query,
+				variables,
+				retry: None,
+			} if txn.is_none() => Command::Query {
+				query,
+				variables,
+				retry: policy,
+			},
+			command => command,
+		}
+	}
+
 	#[cfg(any(feature = "protocol-ws", feature = "protocol-http"))]
 	pub(crate) fn into_router_request(self, id: Option<i64>) -> Option<RouterRequest> {
 		let res = match self {
@@ -123,6 +169,7 @@ impl Command {
 				database,
 			} => RouterRequest {
 				id,
+				txn: None,
 				method: "use",
 				params: Some(vec![CoreValue::from(namespace), CoreValue::from(database)].into()),
 			},
@@ -130,6 +177,7 @@ impl Command {
 				credentials,
 			} => RouterRequest {
 				id,
+				txn: None,
 				method: "signup",
 				params: Some(vec![CoreValue::from(credentials)].into()),
 			},
@@ -137,6 +185,7 @@ impl Command {
 				credentials,
 			} => RouterRequest {
 				id,
+				txn: None,
 				method: "signin",
 				params: Some(vec![CoreValue::from(credentials)].into()),
 			},
@@ -144,11 +193,13 @@ impl Command {
 				token,
 			} => RouterRequest {
 				id,
+				txn: None,
 				method: "authenticate",
 				params: Some(vec![CoreValue::from(token)].into()),
 			},
 			Command::Invalidate => RouterRequest {
 				id,
+				txn: None,
 				method: "invalidate",
 				params: None,
 			},
@@ -163,6 +214,7 @@ impl Command {
 
 				RouterRequest {
 					id,
+					txn: None,
 					method: "create",
 					params: Some(params.into()),
 				}
@@ -179,6 +231,7 @@ impl Command {
 
 				RouterRequest {
 					id,
+					txn: None,
 					method: "upsert",
 					params: Some(params.into()),
 				}
@@ -196,6 +249,7 @@ impl Command {
 
 				RouterRequest {
 					id,
+					txn: None,
 					method: "update",
 					params: Some(params.into()),
 				}
@@ -217,6 +271,7 @@ impl Command {
 
 				RouterRequest {
 					id,
+					txn: None,
 					method: "insert",
 					params: Some(params.into()),
 				}
@@ -237,6 +292,7 @@ impl Command {
 
 				RouterRequest {
 					id,
+					txn: None,
 					method: "insert_relation",
 					params: Some(params.into()),
 				}
@@ -254,6 +310,7 @@ impl Command {
 
 				RouterRequest {
 					id,
+					txn: None,
 					method: "patch",
 					params: Some(params.into()),
 				}
@@ -270,6 +327,7 @@ impl Command {
 
 				RouterRequest {
 					id,
+					txn: None,
 					method: "merge",
 					params: Some(params.into()),
 				}
@@ -279,6 +337,7 @@ impl Command {
 				..
 			} => RouterRequest {
 				id,
+				txn: None,
 				method: "select",
 				params: Some(CoreValue::Array(vec![what.into_core_value()].into())),
 			},
@@ -287,20 +346,40 @@ impl Command {
 				..
 			} => RouterRequest {
 				id,
+				txn: None,
 				method: "delete",
 				params: Some(CoreValue::Array(vec![what.into_core_value()].into())),
 			},
 			Command::Query {
 				query,
 				variables,
+				retry,
 			} => {
-				let params: Vec<CoreValue> = vec![query.into(), variables.into()];
+				let mut params: Vec<CoreValue> = vec![query.into(), variables.into()];
+				if let Some(retry) = retry {
+					params.push(retry.into());
+				}
 				RouterRequest {
 					id,
+					txn: None,
 					method: "query",
 					params: Some(params.into()),
 				}
 			}
+			Command::QueryStream {
+				id: stream,
+				query,
+				variables,
+				..
+			} => {
+				let params: Vec<CoreValue> = vec![stream.into(), query.into(), variables.into()];
+				RouterRequest {
+					id,
+					txn: None,
+					method: "query_stream",
+					params: Some(params.into()),
+				}
+			}
 			Command::ExportFile {
 				..
 			}
@@ -318,14 +397,22 @@ impl Command {
 			}
 			| Command::ImportMl {
 				..
+			}
+			| Command::Backup {
+				..
+			}
+			| Command::Restore {
+				..
 			} => return None,
 			Command::Health => RouterRequest {
 				id,
+				txn: None,
 				method: "ping",
 				params: None,
 			},
 			Command::Version => RouterRequest {
 				id,
+				txn: None,
 				method: "version",
 				params: None,
 			},
@@ -334,6 +421,7 @@ impl Command {
 				value,
 			} => RouterRequest {
 				id,
+				txn: None,
 				method: "let",
 				params: Some(CoreValue::from(vec![CoreValue::from(key), value])),
 			},
@@ -341,6 +429,7 @@ impl Command {
 				key,
 			} => RouterRequest {
 				id,
+				txn: None,
 				method: "unset",
 				params: Some(CoreValue::from(vec![CoreValue::from(key)])),
 			},
@@ -351,6 +440,7 @@ impl Command {
 				uuid,
 			} => RouterRequest {
 				id,
+				txn: None,
 				method: "kill",
 				params: Some(CoreValue::from(vec![CoreValue::from(uuid)])),
 			},
@@ -360,12 +450,35 @@ impl Command {
 				args,
 			} => RouterRequest {
 				id,
+				txn: None,
 				method: "run",
 				params: Some(
 					vec![CoreValue::from(name), CoreValue::from(version), CoreValue::Array(args)]
 						.into(),
 				),
 			},
+			Command::Begin => RouterRequest {
+				id,
+				txn: None,
+				method: "begin",
+				params: None,
+			},
+			Command::Commit {
+				txn,
+			} => RouterRequest {
+				id,
+				txn: None,
+				method: "commit",
+				params: Some(CoreValue::from(vec![CoreValue::from(txn)])),
+			},
+			Command::Cancel {
+				txn,
+			} => RouterRequest {
+				id,
+				txn: None,
+				method: "cancel",
+				params: Some(CoreValue::from(vec![CoreValue::from(txn)])),
+			},
 		};
 		Some(res)
 	}
@@ -410,6 +523,7 @@ impl Command {
 #[derive(Debug)]
 pub(crate) struct RouterRequest {
 	id: Option<i64>,
+	txn: Option<Uuid>,
 	method: &'static str,
 	params: Option<CoreValue>,
 }
@@ -426,6 +540,45 @@ fn stringify_queries(value: CoreValue) -> CoreValue {
 }
 
 impl RouterRequest {
+	/// Sends this request within an open transaction
+	#[cfg(feature = "protocol-ws")]
+	pub(crate) fn with_txn(self, txn: Option<Uuid>) -> Self {
+		Self {
+			txn,
+			..self
+		}
+	}
+
+	/// Lets the server send the specified number of record batches of a
+	/// streamed query before they are acknowledged
+	#[cfg(feature = "protocol-ws")]
+	pub(crate) fn with_credits(self, credits: u32) -> Self {
+		let params = match self.params {
+			Some(CoreValue::Array(mut params)) => {
+				params.push(credits.into());
+				Some(params.into())
+			}
+			params => params,
+		};
+		Self {
+			params,
+			..self
+		}
+	}
+
+	/// Acknowledges a number of record batches of a streamed query, or
+	/// stops the stream without a number of batches
+	#[cfg(feature = "protocol-ws")]
+	pub(crate) fn query_stream_ack(stream: Uuid, count: Option<u32>) -> Self {
+		let params: Vec<CoreValue> = vec![stream.into(), count.map(Into::into).unwrap_or_default()];
+		Self {
+			id: None,
+			txn: None,
+			method: "query_stream_ack",
+			params: Some(params.into()),
+		}
+	}
+
 	#[cfg(feature = "protocol-ws")]
 	pub(crate) fn stringify_queries(self) -> Self {
 		Self {
@@ -488,7 +641,10 @@ impl Serialize for RouterRequest {
 			where
 				S: serde::Serializer,
 			{
-				let size = 1 + self.0.id.is_some() as usize + self.0.params.is_some() as usize;
+				let size = 1
+					+ self.0.id.is_some() as usize
+					+ self.0.txn.is_some() as usize
+					+ self.0.params.is_some() as usize;
 				let mut map = serializer.serialize_map(Some(size))?;
 				if let Some(id) = self.0.id.as_ref() {
 					map.serialize_entry("id", &InnerNumberVariant(*id))?;
@@ -497,6 +653,9 @@ impl Serialize for RouterRequest {
 				if let Some(params) = self.0.params.as_ref() {
 					map.serialize_entry("params", params)?;
 				}
+				if let Some(txn) = self.0.txn {
+					map.serialize_entry("txn", &CoreValue::from(txn))?;
+				}
 				map.end()
 			}
 		}
@@ -535,7 +694,10 @@ impl Revisioned for RouterRequest {
 		// object wrapper version
 		Revisioned::serialize_revisioned(&1u32, w)?;
 
-		let size = 1 + self.id.is_some() as usize + self.params.is_some() as usize;
+		let size = 1
+			+ self.id.is_some() as usize
+			+ self.txn.is_some() as usize
+			+ self.params.is_some() as usize;
 		size.serialize_revisioned(w)?;
 
 		let serializer = bincode::options()
@@ -588,6 +750,13 @@ impl Revisioned for RouterRequest {
 			x.serialize_revisioned(w)?;
 		}
 
+		if let Some(x) = self.txn {
+			serializer
+				.serialize_into(&mut *w, "txn")
+				.map_err(|err| revision::Error::Serialize(err.to_string()))?;
+			CoreValue::from(x).serialize_revisioned(w)?;
+		}
+
 		Ok(())
 	}
 
@@ -631,6 +800,15 @@ mod test {
 		};
 		assert_eq!(x.0, req.method);
 
+		assert_eq!(
+			obj.get("txn").cloned().and_then(|x| if let Value::Uuid(x) = x {
+				Some(x.0)
+			} else {
+				None
+			}),
+			req.txn
+		);
+
 		assert_eq!(obj.get("params").cloned(), req.params);
 	}
 
@@ -638,6 +816,7 @@ mod test {
 	fn router_request_value_conversion() {
 		let request = RouterRequest {
 			id: Some(1234),
+			txn: Some(uuid::Uuid::nil()),
 			method: "request",
 			params: Some(vec![Value::from(1234i64), Value::from("request")].into()),
 		};
@@ -13,17 +13,20 @@ use serde::de::DeserializeOwned;
 use std::collections::HashSet;
 use std::sync::atomic::AtomicI64;
 use std::sync::atomic::Ordering;
+use std::sync::Arc;
 use surrealdb_core::sql::{from_value as from_core_value, Value as CoreValue};
+use uuid::Uuid;
 
 mod cmd;
 pub(crate) use cmd::Command;
-#[cfg(feature = "protocol-http")]
+#[cfg(any(feature = "protocol-http", feature = "protocol-ws"))]
 pub(crate) use cmd::RouterRequest;
 
 #[derive(Debug)]
 #[allow(dead_code)] // used by the embedded and remote connections
 pub struct RequestData {
 	pub(crate) id: i64,
+	pub(crate) txn: Option<Uuid>,
 	pub(crate) command: Command,
 }
 
@@ -38,8 +41,10 @@ pub(crate) struct Route {
 #[derive(Debug)]
 pub struct Router {
 	pub(crate) sender: Sender<Route>,
-	pub(crate) last_id: AtomicI64,
+	pub(crate) last_id: Arc<AtomicI64>,
 	pub(crate) features: HashSet<ExtraFeatures>,
+	/// The open transaction which requests are sent within
+	pub(crate) txn: Option<Uuid>,
 }
 
 impl Router {
@@ -47,6 +52,16 @@ impl Router {
 		self.last_id.fetch_add(1, Ordering::SeqCst)
 	}
 
+	/// Creates a router which sends requests within an open transaction
+	pub(crate) fn with_txn(&self, txn: Uuid) -> Self {
+		Self {
+			sender: self.sender.clone(),
+			last_id: self.last_id.clone(),
+			features: self.features.clone(),
+			txn: Some(txn),
+		}
+	}
+
 	pub(crate) fn send(
 		&self,
 		command: Command,
@@ -57,6 +72,7 @@ impl Router {
 			let route = Route {
 				request: RequestData {
 					id,
+					txn: self.txn,
 					command,
 				},
 				response: sender,
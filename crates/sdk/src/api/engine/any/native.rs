@@ -158,9 +158,10 @@ impl Connection for Any {
 						}
 						let client = builder.build()?;
 						let base_url = address.url;
+						let retry = address.config.retry;
 						engine::remote::http::health(client.get(base_url.join("health")?)).await?;
 						tokio::spawn(engine::remote::http::native::run_router(
-							base_url, client, route_rx,
+							base_url, client, retry, route_rx,
 						));
 					}
 
@@ -217,7 +218,8 @@ impl Connection for Any {
 				Arc::new(OnceLock::with_value(Router {
 					features,
 					sender: route_tx,
-					last_id: AtomicI64::new(0),
+					last_id: Arc::new(AtomicI64::new(0)),
+					txn: None,
 				})),
 				Arc::new(watch::channel(Some(WaitFor::Connection))),
 			))
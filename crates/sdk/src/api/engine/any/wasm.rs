@@ -176,7 +176,8 @@ impl Connection for Any {
 				Arc::new(OnceLock::with_value(Router {
 					features,
 					sender: route_tx,
-					last_id: AtomicI64::new(0),
+					last_id: Arc::new(AtomicI64::new(0)),
+					txn: None,
 				})),
 				Arc::new(watch::channel(Some(WaitFor::Connection))),
 			))
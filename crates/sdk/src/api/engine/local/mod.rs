@@ -44,10 +44,11 @@ use std::{
 	mem,
 	sync::Arc,
 };
+use surrealdb_core::err::Error as CoreError;
 use surrealdb_core::kvs::export::Config as DbExportConfig;
 use surrealdb_core::sql::Function;
 use surrealdb_core::{
-	dbs::{Response, Session},
+	dbs::{OpenTransaction, QueryChunk, Response, Session},
 	iam,
 	kvs::Datastore,
 	sql::{
@@ -65,8 +66,6 @@ use uuid::Uuid;
 #[cfg(not(target_arch = "wasm32"))]
 use std::{future::Future, path::PathBuf};
 #[cfg(not(target_arch = "wasm32"))]
-use surrealdb_core::err::Error as CoreError;
-#[cfg(not(target_arch = "wasm32"))]
 use tokio::{
 	fs::OpenOptions,
 	io::{self, AsyncReadExt, AsyncWriteExt},
@@ -496,6 +495,51 @@ async fn export_file(
 	Ok(())
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+fn file_stream(
+	mut file: tokio::fs::File,
+) -> impl futures::Stream<Item = std::result::Result<tokio_util::bytes::Bytes, CoreError>> {
+	let mut buffer = BytesMut::with_capacity(4096);
+	poll_fn(move |ctx| {
+		// Doing it this way optimizes allocation.
+		// It is highly likely that the buffer we return from this stream will be dropped
+		// between calls to this function.
+		// If this is the case than instead of allocating new memory the call to reserve
+		// will instead reclaim the existing used memory.
+		if buffer.capacity() == 0 {
+			buffer.reserve(4096);
+		}
+
+		let future = pin!(file.read_buf(&mut buffer));
+		match ready!(future.poll(ctx)) {
+			Ok(0) => Poll::Ready(None),
+			Ok(_) => Poll::Ready(Some(Ok(buffer.split().freeze()))),
+			Err(e) => {
+				let error = CoreError::QueryStream(e.to_string());
+				Poll::Ready(Some(Err(error)))
+			}
+		}
+	})
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn backup_file(
+	kvs: &Datastore,
+	sess: &Session,
+	chn: channel::Sender<Vec<u8>>,
+	since: Option<u64>,
+) -> Result<()> {
+	if let Err(error) = kvs.backup(sess, chn, since).await?.await {
+		if let crate::error::Db::Channel(message) = error {
+			// This is not really an error. Just logging it for improved visibility.
+			trace!("{message}");
+			return Ok(());
+		}
+		return Err(error.into());
+	}
+	Ok(())
+}
+
 #[cfg(all(not(target_arch = "wasm32"), feature = "ml"))]
 async fn export_ml(
 	kvs: &Datastore,
@@ -557,8 +601,23 @@ async fn kill_live_query(
 	take(true, response).await
 }
 
+async fn process_query(
+	kvs: &Datastore,
+	txn: Option<&OpenTransaction>,
+	query: Query,
+	session: &Session,
+	vars: BTreeMap<String, CoreValue>,
+) -> Result<Vec<Response>> {
+	let response = match txn {
+		Some(txn) => kvs.process_with_transaction(query, session, Some(vars), txn).await?,
+		None => kvs.process(query, session, Some(vars)).await?,
+	};
+	Ok(response)
+}
+
 async fn router(
 	RequestData {
+		txn,
 		command,
 		..
 	}: RequestData,
@@ -566,7 +625,16 @@ async fn router(
 	session: &mut Session,
 	vars: &mut BTreeMap<String, CoreValue>,
 	live_queries: &mut HashMap<Uuid, Sender<Notification<CoreValue>>>,
+	transactions: &mut HashMap<Uuid, Arc<OpenTransaction>>,
 ) -> Result<DbResponse> {
+	// Find the open transaction which the request is sent within
+	let txn = match txn {
+		Some(id) => match transactions.get(&id) {
+			Some(txn) => Some(txn.clone()),
+			None => return Err(CoreError::TxNotFound(id.to_string()).into()),
+		},
+		None => None,
+	};
 	match command {
 		Command::Use {
 			namespace,
@@ -615,7 +683,8 @@ async fn router(
 				stmt
 			};
 			query.0 .0 = vec![Statement::Create(statement)];
-			let response = kvs.process(query, &*session, Some(vars.clone())).await?;
+			let response =
+				process_query(kvs, txn.as_deref(), query, &*session, vars.clone()).await?;
 			let value = take(true, response).await?;
 			Ok(DbResponse::Other(value))
 		}
@@ -634,7 +703,7 @@ async fn router(
 			};
 			query.0 .0 = vec![Statement::Upsert(statement)];
 			let vars = vars.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
-			let response = kvs.process(query, &*session, Some(vars)).await?;
+			let response = process_query(kvs, txn.as_deref(), query, &*session, vars).await?;
 			let value = take(one, response).await?;
 			Ok(DbResponse::Other(value))
 		}
@@ -653,7 +722,7 @@ async fn router(
 			};
 			query.0 .0 = vec![Statement::Update(statement)];
 			let vars = vars.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
-			let response = kvs.process(query, &*session, Some(vars)).await?;
+			let response = process_query(kvs, txn.as_deref(), query, &*session, vars).await?;
 			let value = take(one, response).await?;
 			Ok(DbResponse::Other(value))
 		}
@@ -672,7 +741,7 @@ async fn router(
 			};
 			query.0 .0 = vec![Statement::Insert(statement)];
 			let vars = vars.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
-			let response = kvs.process(query, &*session, Some(vars)).await?;
+			let response = process_query(kvs, txn.as_deref(), query, &*session, vars).await?;
 			let value = take(one, response).await?;
 			Ok(DbResponse::Other(value))
 		}
@@ -691,7 +760,8 @@ async fn router(
 				stmt
 			};
 			query.0 .0 = vec![Statement::Insert(statement)];
-			let response = kvs.process(query, &*session, Some(vars.clone())).await?;
+			let response =
+				process_query(kvs, txn.as_deref(), query, &*session, vars.clone()).await?;
 			let value = take(one, response).await?;
 			Ok(DbResponse::Other(value))
 		}
@@ -710,7 +780,7 @@ async fn router(
 			};
 			query.0 .0 = vec![Statement::Update(statement)];
 			let vars = vars.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
-			let response = kvs.process(query, &*session, Some(vars)).await?;
+			let response = process_query(kvs, txn.as_deref(), query, &*session, vars).await?;
 			let value = take(one, response).await?;
 			Ok(DbResponse::Other(value))
 		}
@@ -729,7 +799,7 @@ async fn router(
 			};
 			query.0 .0 = vec![Statement::Update(statement)];
 			let vars = vars.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
-			let response = kvs.process(query, &*session, Some(vars)).await?;
+			let response = process_query(kvs, txn.as_deref(), query, &*session, vars).await?;
 			let value = take(one, response).await?;
 			Ok(DbResponse::Other(value))
 		}
@@ -746,7 +816,7 @@ async fn router(
 			};
 			query.0 .0 = vec![Statement::Select(statement)];
 			let vars = vars.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
-			let response = kvs.process(query, &*session, Some(vars)).await?;
+			let response = process_query(kvs, txn.as_deref(), query, &*session, vars).await?;
 			let value = take(one, response).await?;
 			Ok(DbResponse::Other(value))
 		}
@@ -763,20 +833,83 @@ async fn router(
 			};
 			query.0 .0 = vec![Statement::Delete(statement)];
 			let vars = vars.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
-			let response = kvs.process(query, &*session, Some(vars)).await?;
+			let response = process_query(kvs, txn.as_deref(), query, &*session, vars).await?;
 			let value = take(one, response).await?;
 			Ok(DbResponse::Other(value))
 		}
 		Command::Query {
 			query,
 			mut variables,
+			retry,
 		} => {
 			let mut vars = vars.clone();
 			vars.append(&mut variables.0);
-			let response = kvs.process(query, &*session, Some(vars)).await?;
+			let response = match retry {
+				Some(_) if txn.is_some() => {
+					return Err(Error::InvalidRequest(
+						"Queries run within a transaction can not be retried".to_owned(),
+					)
+					.into());
+				}
+				Some(_) => {
+					let session = session.clone().with_retry(retry);
+					process_query(kvs, None, query, &session, vars).await?
+				}
+				None => process_query(kvs, txn.as_deref(), query, &*session, vars).await?,
+			};
 			let response = process(response);
 			Ok(DbResponse::Query(response))
 		}
+		Command::QueryStream {
+			query,
+			mut variables,
+			records,
+			..
+		} => {
+			// Errors are sent on the record stream, as the response is not awaited
+			if txn.is_some() {
+				let error = Error::InvalidRequest(
+					"Streamed queries can not be run within a transaction".to_owned(),
+				);
+				let _ = records.send(Err(error.into())).await;
+				return Ok(DbResponse::Other(CoreValue::None));
+			}
+			let mut vars = vars.clone();
+			vars.append(&mut variables.0);
+			let kvs = kvs.clone();
+			let session = session.clone();
+			let future = async move {
+				let (tx, rx) = crate::channel::bounded(1);
+				let query = kvs.process_chunked(query, &session, Some(vars), tx);
+				let bridge = async {
+					while let Ok(chunk) = rx.recv().await {
+						let batch = match chunk {
+							QueryChunk::Records(_, batch) => batch,
+							QueryChunk::Response(_, response) => {
+								process(vec![response]).send_records(&records).await;
+								continue;
+							}
+							_ => continue,
+						};
+						if records.send(Ok(batch)).await.is_err() {
+							// The stream was dropped, so cancel the query
+							break;
+						}
+					}
+				};
+				if let (Err(error), _) = futures::join!(query, bridge) {
+					let _ = records.send(Err(error.into())).await;
+				}
+			};
+
+			#[cfg(not(target_arch = "wasm32"))]
+			tokio::spawn(future);
+
+			#[cfg(target_arch = "wasm32")]
+			wasm_bindgen_futures::spawn_local(future);
+
+			Ok(DbResponse::Other(CoreValue::None))
+		}
 
 		#[cfg(target_arch = "wasm32")]
 		Command::ExportFile {
@@ -787,6 +920,12 @@ async fn router(
 		}
 		| Command::ImportFile {
 			..
+		}
+		| Command::Backup {
+			..
+		}
+		| Command::Restore {
+			..
 		} => Err(crate::api::Error::BackupsNotSupported.into()),
 
 		#[cfg(any(target_arch = "wasm32", not(feature = "ml")))]
@@ -955,8 +1094,9 @@ async fn router(
 		#[cfg(not(target_arch = "wasm32"))]
 		Command::ImportFile {
 			path,
+			config,
 		} => {
-			let mut file = match OpenOptions::new().read(true).open(&path).await {
+			let file = match OpenOptions::new().read(true).open(&path).await {
 				Ok(path) => path,
 				Err(error) => {
 					return Err(Error::FileOpen {
@@ -967,31 +1107,85 @@ async fn router(
 				}
 			};
 
-			let mut file = pin!(file);
-			let mut buffer = BytesMut::with_capacity(4096);
-
-			let stream = poll_fn(|ctx| {
-				// Doing it this way optimizes allocation.
-				// It is highly likely that the buffer we return from this stream will be dropped
-				// between calls to this function.
-				// If this is the case than instead of allocating new memory the call to reserve
-				// will instead reclaim the existing used memory.
-				if buffer.capacity() == 0 {
-					buffer.reserve(4096);
+			let stream = file_stream(file);
+
+			let responses = match config {
+				// Import the records of a single table
+				Some(config) if config.format.is_records() => {
+					kvs.import_stream_with_config(session, stream, config).await?
+				}
+				_ => kvs.execute_import(&*session, Some(vars.clone()), stream).await?,
+			};
+
+			for response in responses {
+				response.result?;
+			}
+
+			Ok(DbResponse::Other(CoreValue::None))
+		}
+		#[cfg(not(target_arch = "wasm32"))]
+		Command::Backup {
+			path,
+			since,
+		} => {
+			let (tx, rx) = crate::channel::bounded(1);
+			let (mut writer, mut reader) = io::duplex(10_240);
+
+			// Write to channel.
+			let backup = backup_file(kvs, session, tx, since);
+
+			// Read from channel and write to pipe.
+			let bridge = async move {
+				while let Ok(value) = rx.recv().await {
+					if writer.write_all(&value).await.is_err() {
+						// Broken pipe. Let either side's error be propagated.
+						break;
+					}
 				}
+				Ok(())
+			};
 
-				let future = pin!(file.read_buf(&mut buffer));
-				match ready!(future.poll(ctx)) {
-					Ok(0) => Poll::Ready(None),
-					Ok(_) => Poll::Ready(Some(Ok(buffer.split().freeze()))),
-					Err(e) => {
-						let error = CoreError::QueryStream(e.to_string());
-						Poll::Ready(Some(Err(error)))
+			// Output to the file.
+			let mut output = match OpenOptions::new()
+				.write(true)
+				.create(true)
+				.truncate(true)
+				.open(&path)
+				.await
+			{
+				Ok(path) => path,
+				Err(error) => {
+					return Err(Error::FileOpen {
+						path,
+						error,
 					}
+					.into());
 				}
-			});
+			};
 
-			let responses = kvs.execute_import(&*session, Some(vars.clone()), stream).await?;
+			// Copy from pipe to output.
+			let copy = copy(path, &mut reader, &mut output);
+
+			tokio::try_join!(backup, bridge, copy)?;
+			Ok(DbResponse::Other(CoreValue::None))
+		}
+		#[cfg(not(target_arch = "wasm32"))]
+		Command::Restore {
+			path,
+			until,
+		} => {
+			let file = match OpenOptions::new().read(true).open(&path).await {
+				Ok(path) => path,
+				Err(error) => {
+					return Err(Error::FileOpen {
+						path,
+						error,
+					}
+					.into());
+				}
+			};
+
+			let responses = kvs.restore(session, file_stream(file), until).await?;
 
 			for response in responses {
 				response.result?;
@@ -1131,10 +1325,34 @@ async fn router(
 
 			let stmt = Statement::Value(func);
 
-			let response = kvs.process(stmt.into(), &*session, Some(vars.clone())).await?;
+			let response =
+				process_query(kvs, txn.as_deref(), stmt.into(), &*session, vars.clone()).await?;
 			let value = take(true, response).await?;
 
 			Ok(DbResponse::Other(value))
 		}
+		Command::Begin => {
+			let id = Uuid::new_v4();
+			transactions.insert(id, Arc::new(kvs.begin().await?));
+			Ok(DbResponse::Other(CoreValue::from(id)))
+		}
+		Command::Commit {
+			txn,
+		} => {
+			let Some(txn) = transactions.remove(&txn) else {
+				return Err(CoreError::TxNotFound(txn.to_string()).into());
+			};
+			txn.commit().await?;
+			Ok(DbResponse::Other(CoreValue::None))
+		}
+		Command::Cancel {
+			txn,
+		} => {
+			let Some(txn) = transactions.remove(&txn) else {
+				return Err(CoreError::TxNotFound(txn.to_string()).into());
+			};
+			txn.cancel().await?;
+			Ok(DbResponse::Other(CoreValue::None))
+		}
 	}
 }
@@ -54,7 +54,8 @@ impl Connection for Db {
 				Arc::new(OnceLock::with_value(Router {
 					features,
 					sender: route_tx,
-					last_id: AtomicI64::new(0),
+					last_id: Arc::new(AtomicI64::new(0)),
+					txn: None,
 				})),
 				Arc::new(watch::channel(Some(WaitFor::Connection))),
 			))
@@ -111,7 +112,8 @@ pub(crate) async fn run_router(
 	let kvs = Arc::new(kvs);
 	let mut vars = BTreeMap::new();
 	let mut live_queries = HashMap::new();
-	let mut session = Session::default().with_rt(true);
+	let mut transactions = HashMap::new();
+	let mut session = Session::default().with_rt(true).with_retry(address.config.retry);
 
 	let canceller = CancellationToken::new();
 
@@ -128,6 +130,9 @@ pub(crate) async fn run_router(
 	if let Some(interval) = address.config.changefeed_gc_interval {
 		opt.changefeed_gc_interval = interval;
 	}
+	if let Some(interval) = address.config.expiry_interval {
+		opt.expiry_interval = interval;
+	}
 	let tasks = tasks::init(kvs.clone(), canceller.clone(), &opt);
 
 	let mut notifications = kvs.notifications().map(Box::pin);
@@ -151,6 +156,7 @@ pub(crate) async fn run_router(
 					&mut session,
 					&mut vars,
 					&mut live_queries,
+					&mut transactions,
 				)
 				.await
 				{
@@ -190,6 +196,10 @@ pub(crate) async fn run_router(
 			}
 		}
 	}
+	// Cancel any transactions which are still open
+	for (_, txn) in transactions.drain() {
+		let _ = txn.cancel().await;
+	}
 	// Shutdown and stop closed tasks
 	canceller.cancel();
 	// Wait for background tasks to finish
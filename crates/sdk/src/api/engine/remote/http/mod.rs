@@ -8,11 +8,12 @@ use crate::api::err::Error;
 use crate::api::Connect;
 use crate::api::Result;
 use crate::api::Surreal;
-use crate::engine::remote::Response;
+use crate::engine::remote::{Data, Failure, Response};
 use crate::headers::AUTH_DB;
 use crate::headers::AUTH_NS;
 use crate::headers::DB;
 use crate::headers::NS;
+use crate::headers::RPC_REVISION;
 use crate::opt::IntoEndpoint;
 use crate::Value;
 use futures::TryStreamExt;
@@ -22,6 +23,7 @@ use reqwest::header::HeaderValue;
 use reqwest::header::ACCEPT;
 use reqwest::header::CONTENT_TYPE;
 use reqwest::RequestBuilder;
+use revision::revisioned;
 use serde::Deserialize;
 use serde::Serialize;
 use std::marker::PhantomData;
@@ -49,6 +51,7 @@ pub(crate) mod wasm;
 
 // const SQL_PATH: &str = "sql";
 const RPC_PATH: &str = "rpc";
+const DETAILED_RPC_REVISION: &str = "2";
 
 // The HTTP scheme used to connect to `http://` endpoints
 #[derive(Debug)]
@@ -100,6 +103,8 @@ pub(crate) fn default_headers() -> HeaderMap {
 	let mut headers = HeaderMap::new();
 	headers.insert(ACCEPT, HeaderValue::from_static("application/surrealdb"));
 	headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/surrealdb"));
+	// Negotiate the RPC revision which includes the details of errors
+	headers.insert(&RPC_REVISION, HeaderValue::from_static(DETAILED_RPC_REVISION));
 	headers
 }
 
@@ -197,6 +202,84 @@ async fn export_file(request: RequestBuilder, path: PathBuf) -> Result<()> {
 	Ok(())
 }
 
+type RecordSender = channel::Sender<Result<Vec<CoreValue>>>;
+
+/// Sends the records of a streamed query as they are read from the response,
+/// which holds a length-prefixed message with each batch of records, followed
+/// by a message with the results of the query set
+async fn query_records(request: RequestBuilder, records: RecordSender) -> Result<()> {
+	let response = request.send().await?.error_for_status()?;
+	// Servers which negotiated the RPC revision send the error details
+	let detailed =
+		response.headers().get(&RPC_REVISION).is_some_and(|v| v == DETAILED_RPC_REVISION);
+
+	let future = async move {
+		let mut response = response.bytes_stream();
+		let mut buffer = Vec::new();
+		loop {
+			// Decode each of the complete messages received so far
+			while let Some(message) = next_message(&mut buffer) {
+				let response: Result<Response> = match detailed {
+					true => deserialize(&message, false),
+					false => deserialize::<LegacyResponse>(&message, false).map(Into::into),
+				};
+				match response.map(|response| response.result) {
+					// A batch of records selected by a statement
+					Ok(Ok(Data::Records(batch))) => {
+						if records.send(Ok(batch.records)).await.is_err() {
+							return;
+						}
+					}
+					// The results of the query set end the stream
+					Ok(result) => {
+						match DbResponse::from_server_result(result) {
+							Ok(DbResponse::Query(response)) => {
+								response.send_records(&records).await
+							}
+							Ok(DbResponse::Other(..)) => {}
+							Err(error) => {
+								let _ = records.send(Err(error)).await;
+							}
+						}
+						return;
+					}
+					Err(error) => {
+						let _ = records.send(Err(error)).await;
+						return;
+					}
+				}
+			}
+			match response.try_next().await {
+				Ok(Some(b)) => buffer.extend_from_slice(&b),
+				Ok(None) => break,
+				Err(error) => {
+					let _ = records.send(Err(error.into())).await;
+					break;
+				}
+			}
+		}
+	};
+
+	#[cfg(not(target_arch = "wasm32"))]
+	tokio::spawn(future);
+
+	#[cfg(target_arch = "wasm32")]
+	spawn_local(future);
+
+	Ok(())
+}
+
+/// Takes the next complete length-prefixed message out of the buffer
+fn next_message(buffer: &mut Vec<u8>) -> Option<Vec<u8>> {
+	let len = u32::from_be_bytes(buffer.get(..4)?.try_into().ok()?) as usize;
+	if buffer.len() < 4 + len {
+		return None;
+	}
+	let message = buffer[4..4 + len].to_vec();
+	buffer.drain(..4 + len);
+	Some(message)
+}
+
 async fn export_bytes(request: RequestBuilder, bytes: BackupSender) -> Result<()> {
 	let response = request.send().await?.error_for_status()?;
 
@@ -257,6 +340,44 @@ pub(crate) async fn health(request: RequestBuilder) -> Result<()> {
 	Ok(())
 }
 
+/// A failure from a server which did not send the error details
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Deserialize)]
+struct LegacyFailure {
+	code: i64,
+	message: String,
+}
+
+impl From<LegacyFailure> for Failure {
+	fn from(failure: LegacyFailure) -> Self {
+		Failure {
+			code: failure.code,
+			message: failure.message,
+			data: None,
+		}
+	}
+}
+
+/// A response from a server which did not send the error details
+///
+/// Failures in binary formats which are not self-describing, such as
+/// bincode, only include the error details when these were negotiated.
+#[revisioned(revision = 1)]
+#[derive(Debug, Deserialize)]
+struct LegacyResponse {
+	id: Option<CoreValue>,
+	result: std::result::Result<Data, LegacyFailure>,
+}
+
+impl From<LegacyResponse> for Response {
+	fn from(res: LegacyResponse) -> Self {
+		Response {
+			id: res.id,
+			result: res.result.map_err(Into::into),
+		}
+	}
+}
+
 async fn send_request(
 	req: RouterRequest,
 	base_url: &Url,
@@ -268,9 +389,15 @@ async fn send_request(
 	let http_req =
 		client.post(url).headers(headers.clone()).auth(auth).body(serialize(&req, false)?);
 	let response = http_req.send().await?.error_for_status()?;
+	// Servers which negotiated the RPC revision send the error details
+	let detailed =
+		response.headers().get(&RPC_REVISION).is_some_and(|v| v == DETAILED_RPC_REVISION);
 	let bytes = response.bytes().await?;
 
-	let response: Response = deserialize(&bytes, false)?;
+	let response: Response = match detailed {
+		true => deserialize(&bytes, false)?,
+		false => deserialize::<LegacyResponse>(&bytes, false)?.into(),
+	};
 	DbResponse::from_server_result(response.result)
 }
 
@@ -296,16 +423,43 @@ async fn router(
 		Command::Query {
 			query,
 			mut variables,
+			retry,
 		} => {
 			variables.extend(vars.clone());
 			let req = Command::Query {
 				query,
 				variables,
+				retry,
 			}
 			.into_router_request(None)
 			.expect("query should be valid request");
 			send_request(req, base_url, client, headers, auth).await
 		}
+		Command::QueryStream {
+			id,
+			query,
+			mut variables,
+			records,
+		} => {
+			variables.extend(vars.clone());
+			let req = Command::QueryStream {
+				id,
+				query,
+				variables,
+				records: records.clone(),
+			}
+			.into_router_request(None)
+			.expect("query stream should be valid request");
+			// The records are sent in the response body as they are selected
+			let url = base_url.join(RPC_PATH).unwrap();
+			let request =
+				client.post(url).headers(headers.clone()).auth(auth).body(serialize(&req, false)?);
+			// Errors are sent on the record stream, as the response is not awaited
+			if let Err(error) = query_records(request, records.clone()).await {
+				let _ = records.send(Err(error)).await;
+			}
+			Ok(DbResponse::Other(CoreValue::None))
+		}
 		Command::Use {
 			namespace,
 			database,
@@ -401,6 +555,7 @@ async fn router(
 			let req = Command::Query {
 				query,
 				variables,
+				retry: None,
 			}
 			.into_router_request(None)
 			.expect("query is valid request");
@@ -435,6 +590,12 @@ async fn router(
 		}
 		| Command::ImportMl {
 			..
+		}
+		| Command::Backup {
+			..
+		}
+		| Command::Restore {
+			..
 		} => {
 			// TODO: Better error message here, some backups are supported
 			Err(Error::BackupsNotSupported.into())
@@ -507,10 +668,21 @@ async fn router(
 		#[cfg(not(target_arch = "wasm32"))]
 		Command::ImportFile {
 			path,
+			config,
 		} => {
 			let req_path = base_url.join("import")?;
-			let request = client
-				.post(req_path)
+			let mut request = client.post(req_path);
+			if let Some(config) = config {
+				let mut query = vec![("format", config.format.to_string())];
+				if let Some(table) = config.table {
+					query.push(("table", table));
+				}
+				for (source, target) in config.mapping {
+					query.push(("map", format!("{source}:{target}")));
+				}
+				request = request.query(&query);
+			}
+			let request = request
 				.headers(headers.clone())
 				.auth(auth)
 				.header(CONTENT_TYPE, "application/octet-stream");
@@ -530,6 +702,40 @@ async fn router(
 			import(request, path).await?;
 			Ok(DbResponse::Other(CoreValue::None))
 		}
+		#[cfg(not(target_arch = "wasm32"))]
+		Command::Backup {
+			path,
+			since,
+		} => {
+			let req_path = base_url.join("backup")?;
+			let mut request = client.get(req_path);
+			if let Some(since) = since {
+				request = request.query(&[("since", since)]);
+			}
+			let request = request
+				.headers(headers.clone())
+				.auth(auth)
+				.header(ACCEPT, "application/octet-stream");
+			export_file(request, path).await?;
+			Ok(DbResponse::Other(CoreValue::None))
+		}
+		#[cfg(not(target_arch = "wasm32"))]
+		Command::Restore {
+			path,
+			until,
+		} => {
+			let req_path = base_url.join("backup")?;
+			let mut request = client.post(req_path);
+			if let Some(until) = until {
+				request = request.query(&[("until", until)]);
+			}
+			let request = request
+				.headers(headers.clone())
+				.auth(auth)
+				.header(CONTENT_TYPE, "application/octet-stream");
+			import(request, path).await?;
+			Ok(DbResponse::Other(CoreValue::None))
+		}
 		Command::SubscribeLive {
 			..
 		} => Err(Error::LiveQueriesNotSupported.into()),
@@ -10,6 +10,7 @@ use crate::api::ExtraFeatures;
 use crate::api::OnceLockExt;
 use crate::api::Result;
 use crate::api::Surreal;
+use crate::opt::RetryPolicy;
 use crate::opt::WaitFor;
 use channel::Receiver;
 use indexmap::IndexMap;
@@ -45,6 +46,7 @@ impl Connection for Client {
 			let client = builder.build()?;
 
 			let base_url = address.url;
+			let retry = address.config.retry;
 
 			super::health(client.get(base_url.join("health")?)).await?;
 
@@ -53,7 +55,7 @@ impl Connection for Client {
 				capacity => channel::bounded(capacity),
 			};
 
-			tokio::spawn(run_router(base_url, client, route_rx));
+			tokio::spawn(run_router(base_url, client, retry, route_rx));
 
 			let mut features = HashSet::new();
 			features.insert(ExtraFeatures::Backup);
@@ -62,7 +64,8 @@ impl Connection for Client {
 				Arc::new(OnceLock::with_value(Router {
 					features,
 					sender: route_tx,
-					last_id: AtomicI64::new(0),
+					last_id: Arc::new(AtomicI64::new(0)),
+					txn: None,
 				})),
 				Arc::new(watch::channel(Some(WaitFor::Connection))),
 			))
@@ -70,15 +73,21 @@ impl Connection for Client {
 	}
 }
 
-pub(crate) async fn run_router(base_url: Url, client: reqwest::Client, route_rx: Receiver<Route>) {
+pub(crate) async fn run_router(
+	base_url: Url,
+	client: reqwest::Client,
+	retry: Option<RetryPolicy>,
+	route_rx: Receiver<Route>,
+) {
 	let mut headers = HeaderMap::new();
 	let mut vars = IndexMap::new();
 	let mut auth = None;
 
 	while let Ok(route) = route_rx.recv().await {
+		let mut request = route.request;
+		request.command = request.command.with_session_retry(request.txn, retry);
 		let result =
-			super::router(route.request, &base_url, &client, &mut headers, &mut vars, &mut auth)
-				.await;
+			super::router(request, &base_url, &client, &mut headers, &mut vars, &mut auth).await;
 		let _ = route.response.send(result).await;
 	}
 }
@@ -40,7 +40,8 @@ impl Connection for Client {
 				Arc::new(OnceLock::with_value(Router {
 					features: HashSet::new(),
 					sender: route_tx,
-					last_id: AtomicI64::new(0),
+					last_id: Arc::new(AtomicI64::new(0)),
+					txn: None,
 				})),
 				Arc::new(watch::channel(Some(WaitFor::Connection))),
 			))
@@ -63,6 +64,7 @@ pub(crate) async fn run_router(
 	route_rx: Receiver<Route>,
 ) {
 	let base_url = address.url;
+	let retry = address.config.retry;
 
 	let client = match client(&base_url).await {
 		Ok(client) => {
@@ -80,9 +82,9 @@ pub(crate) async fn run_router(
 	let mut auth = None;
 
 	while let Ok(route) = route_rx.recv().await {
-		match super::router(route.request, &base_url, &client, &mut headers, &mut vars, &mut auth)
-			.await
-		{
+		let mut request = route.request;
+		request.command = request.command.with_session_retry(request.txn, retry);
+		match super::router(request, &base_url, &client, &mut headers, &mut vars, &mut auth).await {
 			Ok(value) => {
 				let _ = route.response.send(Ok(value)).await;
 			}
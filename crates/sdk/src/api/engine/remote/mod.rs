@@ -11,6 +11,7 @@ pub mod ws;
 use crate::api::{self, conn::DbResponse, err::Error, method::query::QueryResult, Result};
 use crate::dbs::{self, Status};
 use crate::method::Stats;
+use crate::Object;
 use indexmap::IndexMap;
 use revision::revisioned;
 use revision::Revisioned;
@@ -19,6 +20,7 @@ use rust_decimal::Decimal;
 use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use std::time::Duration;
+use surrealdb_core::err::ErrorData;
 use surrealdb_core::sql::Value as CoreValue;
 
 const NANOS_PER_SEC: i64 = 1_000_000_000;
@@ -78,11 +80,13 @@ mod tests {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Deserialize)]
 pub(crate) struct Failure {
 	pub(crate) code: i64,
 	pub(crate) message: String,
+	#[revision(start = 2)]
+	pub(crate) data: Option<ErrorData>,
 }
 
 #[revisioned(revision = 1)]
@@ -91,6 +95,8 @@ pub(crate) enum Data {
 	Other(CoreValue),
 	Query(Vec<dbs::QueryMethodResponse>),
 	Live(dbs::Notification),
+	Records(dbs::Records),
+	DetailedQuery(Vec<dbs::DetailedQueryMethodResponse>),
 }
 
 type ServerResult = std::result::Result<Data, Failure>;
@@ -102,7 +108,20 @@ impl From<Failure> for Error {
 			-32602 => Self::InvalidParams(failure.message),
 			-32603 => Self::InternalError(failure.message),
 			-32700 => Self::ParseError(failure.message),
-			_ => Self::Query(failure.message),
+			_ => match failure.data {
+				Some(data) => Self::server(failure.message, data),
+				None => Self::Query(failure.message),
+			},
+		}
+	}
+}
+
+impl Error {
+	fn server(message: String, data: ErrorData) -> Self {
+		Self::Server {
+			code: data.code,
+			message,
+			details: Object::from_inner(data.details),
 		}
 	}
 }
@@ -118,36 +137,46 @@ impl DbResponse {
 	fn from_server_result(result: ServerResult) -> Result<Self> {
 		match result.map_err(Error::from)? {
 			Data::Other(value) => Ok(DbResponse::Other(value)),
-			Data::Query(responses) => {
-				let mut map =
-					IndexMap::<usize, (Stats, QueryResult)>::with_capacity(responses.len());
+			Data::Query(responses) => Ok(Self::from_query_responses(
+				responses.into_iter().map(|res| (res.time, res.status, res.result, None)),
+			)),
+			Data::DetailedQuery(responses) => Ok(Self::from_query_responses(
+				responses.into_iter().map(|res| (res.time, res.status, res.result, res.error)),
+			)),
+			// Live notifications and streamed records don't call this method
+			Data::Live(..) | Data::Records(..) => unreachable!(),
+		}
+	}
 
-				for (index, response) in responses.into_iter().enumerate() {
-					let stats = Stats {
-						execution_time: duration_from_str(&response.time),
+	fn from_query_responses(
+		responses: impl ExactSizeIterator<Item = (String, Status, CoreValue, Option<ErrorData>)>,
+	) -> Self {
+		let mut map = IndexMap::<usize, (Stats, QueryResult)>::with_capacity(responses.len());
+
+		for (index, (time, status, result, error)) in responses.enumerate() {
+			let stats = Stats {
+				execution_time: duration_from_str(&time),
+			};
+			match status {
+				Status::Ok => {
+					map.insert(index, (stats, Ok(result)));
+				}
+				Status::Err => {
+					let message = result.as_raw_string();
+					let error = match error {
+						Some(data) => Error::server(message, data),
+						None => Error::Query(message),
 					};
-					match response.status {
-						Status::Ok => {
-							map.insert(index, (stats, Ok(response.result)));
-						}
-						Status::Err => {
-							map.insert(
-								index,
-								(stats, Err(Error::Query(response.result.as_raw_string()).into())),
-							);
-						}
-						_ => unreachable!(),
-					}
+					map.insert(index, (stats, Err(error.into())));
 				}
-
-				Ok(DbResponse::Query(api::Response {
-					results: map,
-					..api::Response::new()
-				}))
+				_ => unreachable!(),
 			}
-			// Live notifications don't call this method
-			Data::Live(..) => unreachable!(),
 		}
+
+		DbResponse::Query(api::Response {
+			results: map,
+			..api::Response::new()
+		})
 	}
 }
 
@@ -7,14 +7,17 @@ pub(crate) mod wasm;
 
 use crate::api::conn::Command;
 use crate::api::conn::DbResponse;
+use crate::api::conn::RouterRequest;
 use crate::api::Connect;
 use crate::api::Result;
 use crate::api::Surreal;
 use crate::opt::IntoEndpoint;
+use crate::opt::RetryPolicy;
 use crate::value::Notification;
 use channel::Sender;
 use indexmap::IndexMap;
 use std::collections::HashMap;
+use std::future::Future;
 use std::marker::PhantomData;
 use std::time::Duration;
 use surrealdb_core::sql::Value as CoreValue;
@@ -24,6 +27,12 @@ use uuid::Uuid;
 pub(crate) const PATH: &str = "rpc";
 const PING_INTERVAL: Duration = Duration::from_secs(5);
 const REVISION_HEADER: &str = "revision";
+/// The versioned protocol which includes the details of errors, preferred
+/// over the plain versioned protocol by servers which support it
+const DETAILED_REVISION_HEADER: &str = "revision-v2";
+/// The number of record batches of a streamed query which the server may
+/// send before the client acknowledges them
+const RECORD_CREDITS: u32 = 16;
 
 enum RequestEffect {
 	/// Completing this request sets a variable to a give value.
@@ -37,6 +46,10 @@ enum RequestEffect {
 	},
 	/// Insert requests repsonses need to be flattened in an array.
 	Insert,
+	/// Completing this request ends the record stream with this id.
+	Stream {
+		id: Uuid,
+	},
 	/// No effect
 	None,
 }
@@ -64,6 +77,12 @@ struct RouterState<Sink, Stream> {
 	replay: IndexMap<ReplayMethod, Command>,
 	/// Pending live queries
 	live_queries: HashMap<Uuid, channel::Sender<Notification<CoreValue>>>,
+	/// Pending record streams of streamed queries, buffered so that a slow
+	/// consumer never blocks the messages for other requests
+	record_streams: HashMap<Uuid, channel::Sender<Result<Vec<CoreValue>>>>,
+	/// Acknowledgements of the record batches consumed by the streams, which
+	/// are sent to the server to let it send more
+	record_acks: (channel::Sender<RecordAck>, channel::Receiver<RecordAck>),
 	/// Send requests which are still awaiting an awnser.
 	pending_requests: HashMap<i64, PendingRequest>,
 	/// The last time a message was recieved from the server.
@@ -72,22 +91,69 @@ struct RouterState<Sink, Stream> {
 	sink: Sink,
 	/// The stream from which messages are recieved from surrealdb
 	stream: Stream,
+	/// The retry policy of the session, for the queries which do not set their own
+	retry: Option<RetryPolicy>,
 }
 
 impl<Sink, Stream> RouterState<Sink, Stream> {
-	pub fn new(sink: Sink, stream: Stream) -> Self {
+	pub fn new(sink: Sink, stream: Stream, retry: Option<RetryPolicy>) -> Self {
 		RouterState {
 			vars: IndexMap::new(),
 			replay: IndexMap::new(),
 			live_queries: HashMap::new(),
+			record_streams: HashMap::new(),
+			record_acks: channel::unbounded(),
 			pending_requests: HashMap::new(),
 			last_activity: Instant::now(),
 			sink,
 			stream,
+			retry,
+		}
+	}
+}
+
+/// An acknowledgement of the records of a streamed query
+enum RecordAck {
+	/// The stream consumed a batch of records
+	Consumed(Uuid),
+	/// The stream was dropped, so no more records are wanted
+	Dropped(Uuid),
+}
+
+impl RecordAck {
+	/// Converts this acknowledgement into a request for the server
+	fn into_router_request(self) -> RouterRequest {
+		match self {
+			RecordAck::Consumed(stream) => RouterRequest::query_stream_ack(stream, Some(1)),
+			RecordAck::Dropped(stream) => RouterRequest::query_stream_ack(stream, None),
 		}
 	}
 }
 
+/// Buffers the records of a streamed query, returning the buffer and a task
+/// which forwards the buffered records to the stream as they are received.
+///
+/// The server sends no more batches than the stream has credits for, so the
+/// router never waits on the bounded buffer. Each batch is acknowledged once
+/// the stream receives it, granting the server a credit for another batch.
+fn buffer_records(
+	stream: Uuid,
+	records: Sender<Result<Vec<CoreValue>>>,
+	acks: Sender<RecordAck>,
+) -> (Sender<Result<Vec<CoreValue>>>, impl Future<Output = ()>) {
+	let (buffer, rx) = channel::bounded(RECORD_CREDITS as usize);
+	let forward = async move {
+		while let Ok(batch) = rx.recv().await {
+			if records.send(batch).await.is_err() {
+				let _ = acks.send(RecordAck::Dropped(stream)).await;
+				break;
+			}
+			let _ = acks.send(RecordAck::Consumed(stream)).await;
+		}
+	};
+	(buffer, forward)
+}
+
 enum HandleResult {
 	/// Socket disconnected, should continue to reconnect
 	Disconnected,
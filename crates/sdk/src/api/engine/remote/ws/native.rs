@@ -1,4 +1,6 @@
-use super::{HandleResult, PendingRequest, ReplayMethod, RequestEffect, PATH};
+use super::{
+	buffer_records, HandleResult, PendingRequest, ReplayMethod, RequestEffect, PATH, RECORD_CREDITS,
+};
 use crate::api::conn::Route;
 use crate::api::conn::Router;
 use crate::api::conn::{Command, DbResponse};
@@ -76,9 +78,10 @@ pub(crate) async fn connect(
 ) -> Result<WebSocketStream<MaybeTlsStream<TcpStream>>> {
 	let mut request = (&endpoint.url).into_client_request()?;
 
+	let protocols = format!("{}, {}", super::DETAILED_REVISION_HEADER, super::REVISION_HEADER);
 	request
 		.headers_mut()
-		.insert(SEC_WEBSOCKET_PROTOCOL, HeaderValue::from_static(super::REVISION_HEADER));
+		.insert(SEC_WEBSOCKET_PROTOCOL, HeaderValue::from_str(&protocols).map_err(WsError::from)?);
 
 	#[cfg(any(feature = "native-tls", feature = "rustls"))]
 	let (socket, _) = tokio_tungstenite::connect_async_tls_with_config(
@@ -132,7 +135,8 @@ impl Connection for Client {
 				Arc::new(OnceLock::with_value(Router {
 					features,
 					sender: route_tx,
-					last_id: AtomicI64::new(0),
+					last_id: Arc::new(AtomicI64::new(0)),
+					txn: None,
 				})),
 				Arc::new(watch::channel(Some(WaitFor::Connection))),
 			))
@@ -149,8 +153,10 @@ async fn router_handle_route(
 ) -> HandleResult {
 	let RequestData {
 		id,
+		txn,
 		command,
 	} = request;
+	let command = command.with_session_retry(txn, state.retry);
 
 	// We probably shouldn't be sending duplicate id requests.
 	let entry = state.pending_requests.entry(id);
@@ -202,6 +208,19 @@ async fn router_handle_route(
 		} => {
 			state.live_queries.remove(uuid);
 		}
+		Command::QueryStream {
+			id: ref stream,
+			ref records,
+			..
+		} => {
+			let (buffer, forward) =
+				buffer_records(*stream, records.clone(), state.record_acks.0.clone());
+			tokio::spawn(forward);
+			state.record_streams.insert(*stream, buffer);
+			effect = RequestEffect::Stream {
+				id: *stream,
+			};
+		}
 		Command::Use {
 			..
 		} => {
@@ -235,8 +254,15 @@ async fn router_handle_route(
 			let _ = response.send(Err(Error::BackupsNotSupported.into())).await;
 			return HandleResult::Ok;
 		};
+		// Streamed queries only send as many batches as the stream can buffer
+		let request = match effect {
+			RequestEffect::Stream {
+				..
+			} => request.with_credits(RECORD_CREDITS),
+			_ => request,
+		};
 		trace!("Request {:?}", request);
-		let payload = serialize(&request.stringify_queries(), true).unwrap();
+		let payload = serialize(&request.with_txn(txn).stringify_queries(), true).unwrap();
 		Message::Binary(payload)
 	};
 
@@ -270,6 +296,30 @@ async fn router_handle_response(response: Message, state: &mut RouterState) -> H
 					Some(id) => {
 						if let Ok(id) = id.coerce_to_i64() {
 							if let Some(pending) = state.pending_requests.remove(&id) {
+								// The results of a streamed query end its record stream
+								if let RequestEffect::Stream {
+									id,
+								} = pending.effect
+								{
+									if let Some(records) = state.record_streams.remove(&id) {
+										match DbResponse::from_server_result(response.result) {
+											// The results are sent after the buffered records,
+											// without waiting for the stream to receive them
+											Ok(DbResponse::Query(response)) => {
+												tokio::spawn(async move {
+													response.send_records(&records).await
+												});
+											}
+											Ok(DbResponse::Other(..)) => {}
+											Err(error) => {
+												tokio::spawn(async move {
+													let _ = records.send(Err(error)).await;
+												});
+											}
+										}
+									}
+									return HandleResult::Ok;
+								}
 								let resp = match DbResponse::from_server_result(response.result) {
 									Ok(x) => x,
 									Err(e) => {
@@ -301,6 +351,9 @@ async fn router_handle_response(response: Message, state: &mut RouterState) -> H
 											return HandleResult::Ok;
 										}
 									}
+									RequestEffect::Stream {
+										..
+									} => unreachable!(),
 									RequestEffect::Set {
 										key,
 										value,
@@ -351,6 +404,14 @@ async fn router_handle_response(response: Message, state: &mut RouterState) -> H
 									}
 								}
 							}
+							Ok(Data::Records(records)) => {
+								// Buffer the records, without waiting for the stream to receive them
+								if let Some(sender) = state.record_streams.get(&records.stream) {
+									if sender.try_send(Ok(records.records)).is_err() {
+										state.record_streams.remove(&records.stream);
+									}
+								}
+							}
 							Ok(..) => { /* Ignored responses like pings */ }
 							Err(error) => error!("{error:?}"),
 						}
@@ -457,7 +518,8 @@ pub(crate) async fn run_router(
 	};
 
 	let (socket_sink, socket_stream) = socket.split();
-	let mut state = RouterState::new(socket_sink, socket_stream);
+	let mut state = RouterState::new(socket_sink, socket_stream, endpoint.config.retry);
+	let record_acks = state.record_acks.1.clone();
 
 	'router: loop {
 		let mut interval = time::interval(PING_INTERVAL);
@@ -556,6 +618,22 @@ pub(crate) async fn run_router(
 						}
 					}
 				}
+				Ok(ack) = record_acks.recv() => {
+					// Let the server send more records to the stream
+					let request = ack.into_router_request();
+					let message = Message::Binary(serialize(&request, true).unwrap());
+					if let Err(error) = state.sink.send(message).await {
+						trace!("failed to acknowledge streamed records; {error:?}");
+						router_reconnect(
+							&maybe_connector,
+							&config,
+							&mut state,
+							&endpoint,
+						)
+						.await;
+						continue 'router;
+					}
+				}
 				_ = pinger.next() => {
 					// only ping if we haven't talked to the server recently
 					if state.last_activity.elapsed() >= PING_INTERVAL {
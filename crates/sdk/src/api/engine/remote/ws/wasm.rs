@@ -1,4 +1,6 @@
-use super::{HandleResult, PendingRequest, ReplayMethod, RequestEffect, PATH};
+use super::{
+	buffer_records, HandleResult, PendingRequest, ReplayMethod, RequestEffect, PATH, RECORD_CREDITS,
+};
 use crate::api::conn::DbResponse;
 use crate::api::conn::Route;
 use crate::api::conn::Router;
@@ -78,7 +80,8 @@ impl Connection for Client {
 				Arc::new(OnceLock::with_value(Router {
 					features,
 					sender: route_tx,
-					last_id: AtomicI64::new(0),
+					last_id: Arc::new(AtomicI64::new(0)),
+					txn: None,
 				})),
 				Arc::new(watch::channel(Some(WaitFor::Connection))),
 			))
@@ -96,8 +99,10 @@ async fn router_handle_request(
 ) -> HandleResult {
 	let RequestData {
 		id,
+		txn,
 		command,
 	} = request;
+	let command = command.with_session_retry(txn, state.retry);
 
 	let entry = state.pending_requests.entry(id);
 	// We probably shouldn't be sending duplicate id requests.
@@ -149,6 +154,19 @@ async fn router_handle_request(
 		} => {
 			state.live_queries.remove(uuid);
 		}
+		Command::QueryStream {
+			id: ref stream,
+			ref records,
+			..
+		} => {
+			let (buffer, forward) =
+				buffer_records(*stream, records.clone(), state.record_acks.0.clone());
+			spawn_local(forward);
+			state.record_streams.insert(*stream, buffer);
+			effect = RequestEffect::Stream {
+				id: *stream,
+			};
+		}
 		Command::Use {
 			..
 		} => {
@@ -182,8 +200,15 @@ async fn router_handle_request(
 			let _ = response.send(Err(Error::BackupsNotSupported.into())).await;
 			return HandleResult::Ok;
 		};
+		// Streamed queries only send as many batches as the stream can buffer
+		let req = match effect {
+			RequestEffect::Stream {
+				..
+			} => req.with_credits(RECORD_CREDITS),
+			_ => req,
+		};
 		trace!("Request {:?}", req);
-		let payload = serialize(&req.stringify_queries(), true).unwrap();
+		let payload = serialize(&req.with_txn(txn).stringify_queries(), true).unwrap();
 		Message::Binary(payload)
 	};
 
@@ -224,6 +249,29 @@ async fn router_handle_response(
 							if let Some(pending) = state.pending_requests.remove(&id) {
 								match pending.effect {
 									RequestEffect::None => {}
+									// The results of a streamed query end its record stream
+									RequestEffect::Stream {
+										id,
+									} => {
+										if let Some(records) = state.record_streams.remove(&id) {
+											match DbResponse::from_server_result(response.result) {
+												// The results are sent after the buffered records,
+												// without waiting for the stream to receive them
+												Ok(DbResponse::Query(response)) => {
+													spawn_local(async move {
+														response.send_records(&records).await
+													});
+												}
+												Ok(DbResponse::Other(..)) => {}
+												Err(error) => {
+													spawn_local(async move {
+														let _ = records.send(Err(error)).await;
+													});
+												}
+											}
+										}
+										return HandleResult::Ok;
+									}
 									RequestEffect::Insert => {
 										// For insert, we need to flatten single responses in an array
 										if let Ok(Data::Other(CoreValue::Array(value))) =
@@ -302,6 +350,14 @@ async fn router_handle_response(
 								}
 							}
 						}
+						Ok(Data::Records(records)) => {
+							// Buffer the records, without waiting for the stream to receive them
+							if let Some(sender) = state.record_streams.get(&records.stream) {
+								if sender.try_send(Ok(records.records)).is_err() {
+									state.record_streams.remove(&records.stream);
+								}
+							}
+						}
 						Ok(..) => { /* Ignored responses like pings */ }
 						Err(error) => error!("{error:?}"),
 					},
@@ -347,7 +403,11 @@ async fn router_reconnect(
 ) {
 	loop {
 		trace!("Reconnecting...");
-		let connect = WsMeta::connect(&endpoint.url, vec![super::REVISION_HEADER]).await;
+		let connect = WsMeta::connect(
+			&endpoint.url,
+			vec![super::DETAILED_REVISION_HEADER, super::REVISION_HEADER],
+		)
+		.await;
 		match connect {
 			Ok((mut meta, stream)) => {
 				let (new_sink, new_stream) = stream.split();
@@ -408,7 +468,11 @@ pub(crate) async fn run_router(
 	conn_tx: Sender<Result<()>>,
 	route_rx: Receiver<Route>,
 ) {
-	let connect = WsMeta::connect(&endpoint.url, vec![super::REVISION_HEADER]).await;
+	let connect = WsMeta::connect(
+		&endpoint.url,
+		vec![super::DETAILED_REVISION_HEADER, super::REVISION_HEADER],
+	)
+	.await;
 	let (mut ws, socket) = match connect {
 		Ok(pair) => pair,
 		Err(error) => {
@@ -443,7 +507,8 @@ pub(crate) async fn run_router(
 
 	let (socket_sink, socket_stream) = socket.split();
 
-	let mut state = RouterState::new(socket_sink, socket_stream);
+	let mut state = RouterState::new(socket_sink, socket_stream, endpoint.config.retry);
+	let record_acks = state.record_acks.1.clone();
 
 	'router: loop {
 		let mut interval = time::interval(PING_INTERVAL);
@@ -513,6 +578,19 @@ pub(crate) async fn run_router(
 						_ => {}
 					}
 				}
+				ack = record_acks.recv().fuse() => {
+					let Ok(ack) = ack else {
+						continue;
+					};
+					// Let the server send more records to the stream
+					let request = ack.into_router_request();
+					let message = Message::Binary(serialize(&request, true).unwrap());
+					if let Err(error) = state.sink.send(message).await {
+						trace!("failed to acknowledge streamed records; {error:?}");
+						router_reconnect(&mut state, &mut events, &endpoint, capacity).await;
+						break;
+					}
+				}
 				_ = pinger.next().fuse() => {
 					if state.last_activity.elapsed() >= PING_INTERVAL {
 						trace!("Pinging the server");
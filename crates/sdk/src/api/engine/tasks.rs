@@ -47,7 +47,8 @@ pub fn init(dbs: Arc<Datastore>, canceller: CancellationToken, opts: &EngineOpti
 	let task2 = spawn_task_node_membership_check(dbs.clone(), canceller.clone(), opts);
 	let task3 = spawn_task_node_membership_cleanup(dbs.clone(), canceller.clone(), opts);
 	let task4 = spawn_task_changefeed_cleanup(dbs.clone(), canceller.clone(), opts);
-	Tasks(vec![task1, task2, task3, task4])
+	let task5 = spawn_task_expiry_cleanup(dbs.clone(), canceller.clone(), opts);
+	Tasks(vec![task1, task2, task3, task4, task5])
 }
 
 fn spawn_task_node_membership_refresh(
@@ -174,6 +175,37 @@ fn spawn_task_changefeed_cleanup(
 	}))
 }
 
+fn spawn_task_expiry_cleanup(
+	dbs: Arc<Datastore>,
+	canceller: CancellationToken,
+	opts: &EngineOptions,
+) -> Task {
+	// Get the delay interval from the config
+	let delay = opts.expiry_interval;
+	// Spawn a future
+	Box::pin(spawn(async move {
+		// Log the interval frequency
+		trace!("Running table record expiry every {delay:?}");
+		// Create a new time-based interval ticket
+		let mut ticker = interval_ticker(delay).await;
+		// Loop continuously until the task is cancelled
+		loop {
+			tokio::select! {
+				biased;
+				// Check if this has shutdown
+				_ = canceller.cancelled() => break,
+				// Receive a notification on the channel
+				Some(_) = ticker.next() => {
+					if let Err(e) = dbs.expiry_process().await {
+						error!("Error running table record expiry: {e}");
+					}
+				}
+			}
+		}
+		trace!("Background task exited: Running table record expiry");
+	}))
+}
+
 async fn interval_ticker(interval: Duration) -> IntervalStream {
 	#[cfg(not(target_arch = "wasm32"))]
 	use tokio::{time, time::MissedTickBehavior};
@@ -1,8 +1,9 @@
-use crate::{api::Response, Value};
+use crate::{api::Response, Object, Value};
 use serde::Serialize;
 use std::path::PathBuf;
 use std::{convert::Infallible, io};
 use surrealdb_core::dbs::capabilities::{ParseFuncTargetError, ParseNetTargetError};
+use surrealdb_core::err::ErrorCode;
 use thiserror::Error;
 
 /// An error originating from a remote SurrealDB database
@@ -13,6 +14,14 @@ pub enum Error {
 	#[error("{0}")]
 	Query(String),
 
+	/// There was an error processing the request, identified by a stable code
+	#[error("{message}")]
+	Server {
+		code: ErrorCode,
+		message: String,
+		details: Object,
+	},
+
 	/// There was an error processing a remote HTTP request
 	#[error("There was an error processing a remote HTTP request: {0}")]
 	Http(String),
@@ -264,6 +273,25 @@ impl serde::de::Error for Error {
 	}
 }
 
+impl crate::Error {
+	/// Returns the stable code identifying the kind of this error, if it has one
+	pub fn code(&self) -> Option<ErrorCode> {
+		match self {
+			Self::Db(error) => Some(error.code()),
+			Self::Api(Error::Server {
+				code,
+				..
+			}) => Some(*code),
+			Self::Api(_) => None,
+		}
+	}
+
+	/// Check if the operation which failed with this error can be retried
+	pub fn is_retryable(&self) -> bool {
+		self.code().is_some_and(|code| code.is_retryable())
+	}
+}
+
 impl From<Infallible> for crate::Error {
 	fn from(_: Infallible) -> Self {
 		unreachable!()
@@ -0,0 +1,65 @@
+use crate::api::conn::Command;
+use crate::api::method::BoxFuture;
+use crate::api::Connection;
+use crate::api::Error;
+use crate::api::ExtraFeatures;
+use crate::api::Result;
+use crate::method::OnceLockExt;
+use crate::Surreal;
+use std::borrow::Cow;
+use std::future::IntoFuture;
+use std::path::PathBuf;
+
+/// A database backup future
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct CreateBackup<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) file: PathBuf,
+	pub(super) since: Option<u64>,
+}
+
+impl<C> CreateBackup<'_, C>
+where
+	C: Connection,
+{
+	/// Only back up the changes made after a previous backup
+	///
+	/// This is the `until` versionstamp recorded in the header line of the
+	/// previous backup.
+	pub fn since(mut self, versionstamp: u64) -> Self {
+		self.since = Some(versionstamp);
+		self
+	}
+
+	/// Converts to an owned type which can easily be moved to a different thread
+	pub fn into_owned(self) -> CreateBackup<'static, C> {
+		CreateBackup {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for CreateBackup<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<()>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.router.extract()?;
+			if !router.features.contains(&ExtraFeatures::Backup) {
+				return Err(Error::BackupsNotSupported.into());
+			}
+			router
+				.execute_unit(Command::Backup {
+					path: self.file,
+					since: self.since,
+				})
+				.await
+		})
+	}
+}
@@ -1,12 +1,20 @@
+use crate::api::conn::Command;
+use crate::api::err::Error;
 use crate::api::method::BoxFuture;
 use crate::api::method::Cancel;
 use crate::api::method::Commit;
+use crate::api::opt::RetryPolicy;
 use crate::api::Connection;
+use crate::api::OnceLockExt;
 use crate::api::Result;
 use crate::api::Surreal;
+use std::future::Future;
 use std::future::IntoFuture;
 use std::ops::Deref;
-use surrealdb_core::sql::statements::BeginStatement;
+use std::sync::Arc;
+use std::sync::OnceLock;
+use surrealdb_core::sql::Value as CoreValue;
+use uuid::Uuid;
 
 /// A beginning of a transaction
 #[derive(Debug)]
@@ -24,18 +32,126 @@ where
 
 	fn into_future(self) -> Self::IntoFuture {
 		Box::pin(async move {
-			self.client.query(BeginStatement::default()).await?;
+			let router = self.client.router.extract()?;
+			let CoreValue::Uuid(id) = router.execute_value(Command::Begin).await?.into_inner()
+			else {
+				return Err(Error::InternalError(
+					"successful transaction begin didn't return a uuid".to_string(),
+				)
+				.into());
+			};
+			let id = *id;
+			// Send all requests on the transaction within it
+			let router = Arc::new(OnceLock::with_value(router.with_txn(id)));
 			Ok(Transaction {
+				id,
+				txn: Surreal::new_from_router_waiter(router, self.client.waiter.clone()),
 				client: self.client,
 			})
 		})
 	}
 }
 
+impl<C> Begin<C>
+where
+	C: Connection,
+{
+	/// Runs a function within a transaction, retrying it on transaction conflicts
+	///
+	/// The function is called with a client whose requests are made within a new
+	/// transaction. If the function succeeds the transaction is committed, and
+	/// otherwise it is cancelled. When the function or the commit fails with an
+	/// error which can be retried, the whole transaction is run again, until it
+	/// succeeds or the retry policy is exhausted.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use surrealdb::opt::RetryPolicy;
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// let balance: Option<i64> = db
+	///     .transaction()
+	///     .retry(RetryPolicy::new(5), |txn| async move {
+	///         txn.query("UPDATE account:one SET balance -= 10").await?.check()?;
+	///         txn.query("UPDATE account:two SET balance += 10").await?.check()?;
+	///         txn.query("account:one.balance").await?.take(0)
+	///     })
+	///     .await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn retry<F, Fut, T>(self, policy: RetryPolicy, function: F) -> Retry<C, F>
+	where
+		F: FnMut(Surreal<C>) -> Fut,
+		Fut: Future<Output = Result<T>>,
+	{
+		Retry {
+			client: self.client,
+			policy,
+			function,
+		}
+	}
+}
+
+/// A transaction which is retried on transaction conflicts
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Retry<C: Connection, F> {
+	client: Surreal<C>,
+	policy: RetryPolicy,
+	function: F,
+}
+
+impl<C, F, Fut, T> IntoFuture for Retry<C, F>
+where
+	C: Connection,
+	F: FnMut(Surreal<C>) -> Fut + Send + Sync + 'static,
+	Fut: Future<Output = Result<T>> + Send + Sync + 'static,
+	T: Send + Sync + 'static,
+{
+	type Output = Result<T>;
+	type IntoFuture = BoxFuture<'static, Self::Output>;
+
+	fn into_future(mut self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let mut attempts = 1;
+			loop {
+				let txn = Begin {
+					client: self.client.clone(),
+				}
+				.await?;
+				let res = match (self.function)((*txn).clone()).await {
+					Ok(value) => txn.commit().await.map(|_| value),
+					Err(error) => {
+						let _ = txn.cancel().await;
+						Err(error)
+					}
+				};
+				match res {
+					Err(error) if error.is_retryable() && self.policy.allows(attempts) => {
+						self.policy.wait(attempts).await;
+						attempts += 1;
+					}
+					res => return res,
+				}
+			}
+		})
+	}
+}
+
 /// An ongoing transaction
+///
+/// Requests made through the transaction only see each other's changes
+/// until the transaction is committed. A transaction which is neither
+/// committed nor cancelled is cancelled by the server after a timeout.
 #[derive(Debug)]
 #[must_use = "transactions must be committed or cancelled to complete them"]
 pub struct Transaction<C: Connection> {
+	id: Uuid,
+	txn: Surreal<C>,
 	client: Surreal<C>,
 }
 
@@ -43,10 +159,16 @@ impl<C> Transaction<C>
 where
 	C: Connection,
 {
+	/// The id of this transaction on the server
+	pub fn id(&self) -> Uuid {
+		self.id
+	}
+
 	/// Creates a commit future
 	pub fn commit(self) -> Commit<C> {
 		Commit {
 			client: self.client,
+			txn: self.id,
 		}
 	}
 
@@ -54,6 +176,7 @@ where
 	pub fn cancel(self) -> Cancel<C> {
 		Cancel {
 			client: self.client,
+			txn: self.id,
 		}
 	}
 }
@@ -65,6 +188,6 @@ where
 	type Target = Surreal<C>;
 
 	fn deref(&self) -> &Self::Target {
-		&self.client
+		&self.txn
 	}
 }
@@ -1,15 +1,18 @@
+use crate::api::conn::Command;
 use crate::api::method::BoxFuture;
 use crate::api::Connection;
+use crate::api::OnceLockExt;
 use crate::api::Result;
 use crate::api::Surreal;
 use std::future::IntoFuture;
-use surrealdb_core::sql::statements::CancelStatement;
+use uuid::Uuid;
 
 /// A transaction cancellation future
 #[derive(Debug)]
 #[must_use = "futures do nothing unless you `.await` or poll them"]
 pub struct Cancel<C: Connection> {
 	pub(crate) client: Surreal<C>,
+	pub(crate) txn: Uuid,
 }
 
 impl<C> IntoFuture for Cancel<C>
@@ -21,7 +24,12 @@ where
 
 	fn into_future(self) -> Self::IntoFuture {
 		Box::pin(async move {
-			self.client.query(CancelStatement::default()).await?;
+			let router = self.client.router.extract()?;
+			router
+				.execute_unit(Command::Cancel {
+					txn: self.txn,
+				})
+				.await?;
 			Ok(self.client)
 		})
 	}
@@ -8,6 +8,8 @@ use crate::api::Result;
 use crate::method::ExportConfig as Config;
 use crate::method::Model;
 use crate::method::OnceLockExt;
+use crate::opt::KeyRange;
+use crate::RecordIdKey;
 use crate::Surreal;
 use channel::Receiver;
 use futures::Stream;
@@ -20,7 +22,8 @@ use std::path::PathBuf;
 use std::pin::Pin;
 use std::task::Context;
 use std::task::Poll;
-use surrealdb_core::kvs::export::{Config as DbExportConfig, TableConfig};
+use surrealdb_core::kvs::export::{Config as DbExportConfig, Format, TableConfig};
+use surrealdb_core::sql::IdRange;
 
 /// A database export future
 #[derive(Debug)]
@@ -30,6 +33,7 @@ pub struct Export<'r, C: Connection, R, T = ()> {
 	pub(super) target: R,
 	pub(super) ml_config: Option<MlExportConfig>,
 	pub(super) db_config: Option<DbExportConfig>,
+	pub(super) error: Option<crate::Error>,
 	pub(super) response: PhantomData<R>,
 	pub(super) export_type: PhantomData<T>,
 }
@@ -48,6 +52,7 @@ where
 				version: version.to_string(),
 			}),
 			db_config: self.db_config,
+			error: self.error,
 			response: self.response,
 			export_type: PhantomData,
 		}
@@ -61,6 +66,7 @@ where
 			ml_config: self.ml_config,
 			// Use default configuration options
 			db_config: Some(Default::default()),
+			error: self.error,
 			response: self.response,
 			export_type: PhantomData,
 		}
@@ -95,6 +101,14 @@ where
 		self
 	}
 
+	/// Whether to export sequences from the database
+	pub fn sequences(mut self, sequences: bool) -> Self {
+		if let Some(cfg) = self.db_config.as_mut() {
+			cfg.sequences = sequences;
+		}
+		self
+	}
+
 	/// Whether to export functions from the database
 	pub fn functions(mut self, functions: bool) -> Self {
 		if let Some(cfg) = self.db_config.as_mut() {
@@ -145,6 +159,71 @@ where
 		}
 		self
 	}
+
+	/// Whether to export the definitions of the database and its tables
+	///
+	/// Disabling this, and keeping `records` enabled, exports only data.
+	/// Disabling `records` instead exports only the schema.
+	pub fn schema(mut self, schema: bool) -> Self {
+		if let Some(cfg) = self.db_config.as_mut() {
+			cfg.schema = schema;
+		}
+		self
+	}
+
+	/// Only export the records of a table with an id in a range
+	///
+	/// ```ignore
+	/// db.export("users.surql").with_config().tables(vec!["user"]).range("user", 100..200);
+	/// ```
+	pub fn range(mut self, table: impl Into<String>, range: impl Into<KeyRange>) -> Self {
+		let range = range.into();
+		let range = IdRange {
+			beg: range.start.map(RecordIdKey::into_inner),
+			end: range.end.map(RecordIdKey::into_inner),
+		};
+		if let Some(cfg) = self.db_config.as_mut() {
+			let table = table.into();
+			let filter = cfg.filters.remove(&table).unwrap_or_default();
+			cfg.filters.insert(table, filter.with_range(range));
+		}
+		self
+	}
+
+	/// Only export the records of a table which match a SurrealQL condition
+	///
+	/// ```ignore
+	/// db.export("tenant.surql").with_config().condition("user", "tenant = 'acme'");
+	/// ```
+	pub fn condition(mut self, table: impl Into<String>, cond: &str) -> Self {
+		let cond = match surrealdb_core::syn::value(cond) {
+			Ok(v) => v,
+			Err(error) => {
+				self.error.get_or_insert(error.into());
+				return self;
+			}
+		};
+		if let Some(cfg) = self.db_config.as_mut() {
+			let table = table.into();
+			let filter = cfg.filters.remove(&table).unwrap_or_default();
+			cfg.filters.insert(table, filter.with_cond(cond));
+		}
+		self
+	}
+
+	/// The format of the export
+	///
+	/// Formats other than SurrealQL export the records of a single table,
+	/// which is selected with `tables`:
+	/// ```ignore
+	/// db.export().with_config().format(Format::Csv).tables(vec!["users"]);
+	/// ```
+	pub fn format(mut self, format: Format) -> Self {
+		if let Some(cfg) = self.db_config.as_mut() {
+			cfg.format = format;
+		}
+		self
+	}
 }
 
 impl<C, R, T> Export<'_, C, R, T>
@@ -169,6 +248,9 @@ where
 
 	fn into_future(self) -> Self::IntoFuture {
 		Box::pin(async move {
+			if let Some(error) = self.error {
+				return Err(error);
+			}
 			let router = self.client.router.extract()?;
 			if !router.features.contains(&ExtraFeatures::Backup) {
 				return Err(Error::BackupsNotSupported.into());
@@ -202,6 +284,9 @@ where
 
 	fn into_future(self) -> Self::IntoFuture {
 		Box::pin(async move {
+			if let Some(error) = self.error {
+				return Err(error);
+			}
 			let router = self.client.router.extract()?;
 			if !router.features.contains(&ExtraFeatures::Backup) {
 				return Err(Error::BackupsNotSupported.into());
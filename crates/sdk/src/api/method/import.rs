@@ -4,6 +4,7 @@ use crate::api::Connection;
 use crate::api::Error;
 use crate::api::ExtraFeatures;
 use crate::api::Result;
+use crate::method::ImportConfig as Config;
 use crate::method::Model;
 use crate::method::OnceLockExt;
 use crate::Surreal;
@@ -11,6 +12,7 @@ use std::borrow::Cow;
 use std::future::IntoFuture;
 use std::marker::PhantomData;
 use std::path::PathBuf;
+use surrealdb_core::kvs::import::{Config as DbImportConfig, Format};
 
 /// An database import future
 #[derive(Debug)]
@@ -19,6 +21,7 @@ pub struct Import<'r, C: Connection, T = ()> {
 	pub(super) client: Cow<'r, Surreal<C>>,
 	pub(super) file: PathBuf,
 	pub(super) is_ml: bool,
+	pub(super) db_config: Option<DbImportConfig>,
 	pub(super) import_type: PhantomData<T>,
 }
 
@@ -32,9 +35,62 @@ where
 			client: self.client,
 			file: self.file,
 			is_ml: true,
+			db_config: self.db_config,
 			import_type: PhantomData,
 		}
 	}
+
+	/// Configure the import options
+	pub fn with_config(self) -> Import<'r, C, Config> {
+		Import {
+			client: self.client,
+			file: self.file,
+			is_ml: self.is_ml,
+			// Use default configuration options
+			db_config: Some(Default::default()),
+			import_type: PhantomData,
+		}
+	}
+}
+
+impl<C> Import<'_, C, Config>
+where
+	C: Connection,
+{
+	/// The format of the imported file
+	///
+	/// Formats other than SurrealQL hold the records of a single table,
+	/// which are imported into the table selected with `table`:
+	/// ```ignore
+	/// db.import("users.csv").with_config().format(Format::Csv).table("users");
+	/// ```
+	pub fn format(mut self, format: Format) -> Self {
+		if let Some(cfg) = self.db_config.as_mut() {
+			cfg.format = format;
+		}
+		self
+	}
+
+	/// The table to import records into
+	pub fn table(mut self, table: impl Into<String>) -> Self {
+		if let Some(cfg) = self.db_config.as_mut() {
+			cfg.table = Some(table.into());
+		}
+		self
+	}
+
+	/// Renames a field of the imported records
+	///
+	/// This can be called multiple times, to rename several fields:
+	/// ```ignore
+	/// db.import("users.jsonl").with_config().map("user_name", "name").map("mail", "email");
+	/// ```
+	pub fn map(mut self, source: impl Into<String>, target: impl Into<String>) -> Self {
+		if let Some(cfg) = self.db_config.as_mut() {
+			cfg.mapping.push((source.into(), target.into()));
+		}
+		self
+	}
 }
 
 impl<C, T> Import<'_, C, T>
@@ -75,6 +131,7 @@ where
 			router
 				.execute_unit(Command::ImportFile {
 					path: self.file,
+					config: self.db_config,
 				})
 				.await
 		})
@@ -25,6 +25,7 @@ pub(crate) mod live;
 pub(crate) mod query;
 
 mod authenticate;
+mod backup;
 mod begin;
 mod cancel;
 mod commit;
@@ -39,6 +40,7 @@ mod insert_relation;
 mod invalidate;
 mod merge;
 mod patch;
+mod restore;
 mod run;
 mod select;
 mod set;
@@ -56,7 +58,9 @@ mod tests;
 
 pub use authenticate::Authenticate;
 #[doc(hidden)] // Not supported yet
+pub use backup::CreateBackup;
 pub use begin::Begin;
+pub use begin::Retry;
 #[doc(hidden)] // Not supported yet
 pub use begin::Transaction;
 #[doc(hidden)] // Not supported yet
@@ -76,7 +80,10 @@ pub use live::Stream;
 pub use merge::Merge;
 pub use patch::Patch;
 pub use query::Query;
+pub use query::QueryRecords;
 pub use query::QueryStream;
+pub use query::RecordStream;
+pub use restore::Restore;
 pub use run::IntoFn;
 pub use run::Run;
 pub use select::Select;
@@ -112,6 +119,9 @@ pub struct Model;
 /// Marker type for configured exports
 pub struct ExportConfig;
 
+/// Marker type for configured imports
+pub struct ImportConfig;
+
 /// Live query marker type
 pub struct Live;
 
@@ -253,7 +263,40 @@ where
 		}
 	}
 
-	#[doc(hidden)] // Not supported yet
+	/// Begins an interactive transaction
+	///
+	/// Requests made through the returned transaction are executed within it,
+	/// and are only visible to other clients once it is committed. Open
+	/// transactions are supported by the WebSocket and embedded engines.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use serde::{Deserialize, Serialize};
+	///
+	/// #[derive(Debug, Serialize, Deserialize)]
+	/// struct Account {
+	///     balance: i64,
+	/// }
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// # db.use_ns("namespace").use_db("database").await?;
+	/// let txn = db.transaction().await?;
+	/// let account: Option<Account> = txn.select(("account", "one")).await?;
+	/// if let Some(account) = account {
+	///     let _: Option<Account> = txn
+	///         .update(("account", "one"))
+	///         .content(Account {
+	///             balance: account.balance + 100,
+	///         })
+	///         .await?;
+	/// }
+	/// let db = txn.commit().await?;
+	/// # Ok(())
+	/// # }
+	/// ```
 	pub fn transaction(self) -> Begin<C> {
 		Begin {
 			client: self,
@@ -647,6 +690,7 @@ where
 			query: x,
 			bindings: Default::default(),
 			register_live_queries: true,
+			retry: None,
 		});
 
 		Query {
@@ -1370,6 +1414,7 @@ where
 			target: target.into_export_destination(),
 			ml_config: None,
 			db_config: None,
+			error: None,
 			response: PhantomData,
 			export_type: PhantomData,
 		}
@@ -1402,7 +1447,85 @@ where
 			client: Cow::Borrowed(self),
 			file: file.as_ref().to_owned(),
 			is_ml: false,
+			db_config: None,
 			import_type: PhantomData,
 		}
 	}
+
+	/// Backs up the database to a file
+	///
+	/// Without a starting point this writes a consistent snapshot of the
+	/// database. Passing the versionstamp recorded by a previous backup to
+	/// `since` writes only the changes made after that backup, which are
+	/// read from the change feed, so the database, or every one of its tables,
+	/// needs a `CHANGEFEED` which is retained for longer than the backup
+	/// interval. Otherwise the backup fails, and a full backup is required.
+	///
+	/// # Support
+	///
+	/// Currently only supported by HTTP and the local engines. *Not* supported on WebAssembly.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// // Select the namespace/database to use
+	/// db.use_ns("namespace").use_db("database").await?;
+	///
+	/// // Take a full backup
+	/// db.backup("full.surql").await?;
+	///
+	/// // Take an incremental backup, from the `until` versionstamp
+	/// // recorded in the header line of the previous backup
+	/// db.backup("incremental.surql").since(42).await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn backup<P>(&self, file: P) -> CreateBackup<C>
+	where
+		P: AsRef<Path>,
+	{
+		CreateBackup {
+			client: Cow::Borrowed(self),
+			file: file.as_ref().to_owned(),
+			since: None,
+		}
+	}
+
+	/// Restores a backup, as created by [`Surreal::backup`], from a file
+	///
+	/// Backups are restored in the order in which they were taken, starting
+	/// with a full backup. The changes in an incremental backup can be
+	/// restored up to a point in time with `until`.
+	///
+	/// # Support
+	///
+	/// Currently only supported by HTTP and the local engines. *Not* supported on WebAssembly.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// // Select the namespace/database to use
+	/// db.use_ns("namespace").use_db("database").await?;
+	///
+	/// db.restore("full.surql").await?;
+	/// db.restore("incremental.surql").until(chrono::Utc::now()).await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn restore<P>(&self, file: P) -> Restore<C>
+	where
+		P: AsRef<Path>,
+	{
+		Restore {
+			client: Cow::Borrowed(self),
+			file: file.as_ref().to_owned(),
+			until: None,
+		}
+	}
 }
@@ -3,6 +3,7 @@ use crate::api::conn::Command;
 use crate::api::err::Error;
 use crate::api::method::BoxFuture;
 use crate::api::opt;
+use crate::api::opt::RetryPolicy;
 use crate::api::Connection;
 use crate::api::ExtraFeatures;
 use crate::api::Result;
@@ -12,7 +13,10 @@ use crate::method::Stats;
 use crate::method::WithStats;
 use crate::value::Notification;
 use crate::{Surreal, Value};
+use channel::Receiver;
+use channel::Sender;
 use futures::future::Either;
+use futures::ready;
 use futures::stream::SelectAll;
 use futures::StreamExt;
 use indexmap::IndexMap;
@@ -21,12 +25,15 @@ use serde::Serialize;
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::future::IntoFuture;
+use std::marker::PhantomData;
 use std::pin::Pin;
 use std::task::Context;
 use std::task::Poll;
 use surrealdb_core::sql::{
-	self, to_value as to_core_value, Object as CoreObject, Statement, Value as CoreValue,
+	self, from_value as from_core_value, to_value as to_core_value, Object as CoreObject,
+	Statement, Value as CoreValue,
 };
+use uuid::Uuid;
 
 /// A query future
 #[derive(Debug)]
@@ -41,6 +48,7 @@ pub(crate) struct ValidQuery<'r, C: Connection> {
 	pub query: Vec<Statement>,
 	pub bindings: CoreObject,
 	pub register_live_queries: bool,
+	pub retry: Option<RetryPolicy>,
 }
 
 impl<'r, C> Query<'r, C>
@@ -59,6 +67,7 @@ where
 				query,
 				bindings,
 				register_live_queries,
+				retry: None,
 			}),
 		}
 	}
@@ -85,11 +94,13 @@ where
 				query,
 				bindings,
 				register_live_queries,
+				retry,
 			}) => Ok(ValidQuery::<'static, C> {
 				client: Cow::Owned(client.into_owned()),
 				query,
 				bindings,
 				register_live_queries,
+				retry,
 			}),
 			Err(e) => Err(e),
 		};
@@ -113,6 +124,7 @@ where
 			query,
 			bindings,
 			register_live_queries,
+			retry,
 		} = match self.inner {
 			Ok(x) => x,
 			Err(error) => return Box::pin(async move { Err(error) }),
@@ -156,6 +168,7 @@ where
 				.execute_query(Command::Query {
 					query,
 					variables: bindings,
+					retry,
 				})
 				.await?;
 
@@ -212,7 +225,7 @@ where
 	}
 }
 
-impl<C> Query<'_, C>
+impl<'r, C> Query<'r, C>
 where
 	C: Connection,
 {
@@ -230,6 +243,77 @@ where
 		WithStats(self)
 	}
 
+	/// Retries the statements of the query which fail with a transaction conflict
+	///
+	/// Statements which are not within a transaction block are executed again
+	/// in a new transaction, until they succeed or the policy is exhausted.
+	/// Queries run within an interactive transaction can not be retried.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use std::time::Duration;
+	/// use surrealdb::opt::RetryPolicy;
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// let policy = RetryPolicy::new(5).with_backoff(Duration::from_millis(20));
+	/// let response = db.query("UPDATE counter:visits SET count += 1").retry(policy).await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn retry(mut self, policy: RetryPolicy) -> Self {
+		if let Ok(valid) = &mut self.inner {
+			valid.retry = Some(policy);
+		}
+		self
+	}
+
+	/// Streams the records returned by the query, as they are selected
+	///
+	/// The records of simple `SELECT` statements are sent by the database in
+	/// batches while the statements are running, so they don't all need to
+	/// be held in memory at once. The results of all other statements are
+	/// streamed once they have completed. Streamed queries can not be run
+	/// within a transaction.
+	///
+	/// Over a WebSocket connection, the records are buffered as they are
+	/// received, so a slowly consumed stream does not hold up the other
+	/// requests on the connection. Over HTTP, the records are read from the
+	/// response as the stream is consumed.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use futures::StreamExt;
+	/// use serde::Deserialize;
+	///
+	/// #[derive(Debug, Deserialize)]
+	/// # #[allow(dead_code)]
+	/// struct User {
+	///     name: String,
+	/// }
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// let mut stream = db.query("SELECT * FROM user").records::<User>().await?;
+	///
+	/// while let Some(user) = stream.next().await {
+	///     println!("{:?}", user?);
+	/// }
+	/// #
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn records<R>(self) -> QueryRecords<'r, C, R> {
+		QueryRecords {
+			query: self,
+			response_type: PhantomData,
+		}
+	}
+
 	/// Binds a parameter or parameters to a query
 	///
 	/// # Examples
@@ -301,6 +385,91 @@ where
 	}
 }
 
+/// A query future which streams the records returned by the query
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct QueryRecords<'r, C: Connection, R> {
+	query: Query<'r, C>,
+	response_type: PhantomData<R>,
+}
+
+impl<'r, Client, R> IntoFuture for QueryRecords<'r, Client, R>
+where
+	Client: Connection,
+	R: Send + Sync + 'r,
+{
+	type Output = Result<RecordStream<R>>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let ValidQuery {
+				client,
+				query,
+				bindings,
+				..
+			} = self.query.inner?;
+			let router = client.router.extract()?;
+			let (records, rx) = crate::channel::bounded(1);
+			let mut statements = sql::Query::default();
+			statements.0 .0 = query;
+			// The records are sent on the channel, so the response is not awaited
+			router
+				.send(Command::QueryStream {
+					id: Uuid::new_v4(),
+					query: statements,
+					variables: bindings,
+					records,
+				})
+				.await?;
+			Ok(RecordStream {
+				rx: Box::pin(rx),
+				batch: Vec::new().into_iter(),
+				response_type: PhantomData,
+			})
+		})
+	}
+}
+
+/// A stream of the records returned by a query
+#[derive(Debug)]
+#[must_use = "streams do nothing unless you poll them"]
+pub struct RecordStream<R> {
+	rx: Pin<Box<Receiver<Result<Vec<CoreValue>>>>>,
+	batch: std::vec::IntoIter<CoreValue>,
+	response_type: PhantomData<R>,
+}
+
+impl<R> RecordStream<R> {
+	fn poll_record(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<CoreValue>>> {
+		loop {
+			if let Some(record) = self.batch.next() {
+				return Poll::Ready(Some(Ok(record)));
+			}
+			match ready!(self.rx.poll_next_unpin(cx)) {
+				Some(Ok(batch)) => self.batch = batch.into_iter(),
+				Some(Err(error)) => return Poll::Ready(Some(Err(error))),
+				None => return Poll::Ready(None),
+			}
+		}
+	}
+}
+
+impl<R> futures::Stream for RecordStream<R>
+where
+	R: DeserializeOwned + Unpin,
+{
+	type Item = Result<R>;
+
+	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		match ready!(self.poll_record(cx)) {
+			Some(Ok(record)) => Poll::Ready(Some(from_core_value(record).map_err(Into::into))),
+			Some(Err(error)) => Poll::Ready(Some(Err(error))),
+			None => Poll::Ready(None),
+		}
+	}
+}
+
 pub(crate) type QueryResult = Result<CoreValue>;
 
 /// The response type of a `Surreal::query` request
@@ -540,6 +709,23 @@ impl Response {
 	pub fn num_statements(&self) -> usize {
 		self.results.len()
 	}
+
+	/// Sends the results of the statements to a record stream
+	#[allow(dead_code)] // used by the embedded and remote connections
+	pub(crate) async fn send_records(self, records: &Sender<Result<Vec<CoreValue>>>) {
+		for (_, (_, result)) in self.results {
+			let batch = match result {
+				Ok(CoreValue::None) => continue,
+				Ok(CoreValue::Array(array)) if array.is_empty() => continue,
+				Ok(CoreValue::Array(array)) => Ok(array.0),
+				Ok(value) => Ok(vec![value]),
+				Err(error) => Err(error),
+			};
+			if records.send(batch).await.is_err() {
+				break;
+			}
+		}
+	}
 }
 
 impl WithStats<Response> {
@@ -0,0 +1,68 @@
+use crate::api::conn::Command;
+use crate::api::method::BoxFuture;
+use crate::api::Connection;
+use crate::api::Error;
+use crate::api::ExtraFeatures;
+use crate::api::Result;
+use crate::method::OnceLockExt;
+use crate::Datetime;
+use crate::Surreal;
+use std::borrow::Cow;
+use std::future::IntoFuture;
+use std::path::PathBuf;
+
+/// A database restore future
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Restore<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) file: PathBuf,
+	pub(super) until: Option<u64>,
+}
+
+impl<C> Restore<'_, C>
+where
+	C: Connection,
+{
+	/// Only restore the changes made up to a point in time
+	///
+	/// Changes are restored up to the latest change feed timestamp before
+	/// this time, so the precision depends on how often the change feed
+	/// timestamps are recorded.
+	pub fn until(mut self, time: impl Into<Datetime>) -> Self {
+		let time = time.into().into_inner().0.timestamp();
+		self.until = Some(time.max(0) as u64);
+		self
+	}
+
+	/// Converts to an owned type which can easily be moved to a different thread
+	pub fn into_owned(self) -> Restore<'static, C> {
+		Restore {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for Restore<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<()>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.router.extract()?;
+			if !router.features.contains(&ExtraFeatures::Backup) {
+				return Err(Error::BackupsNotSupported.into());
+			}
+			router
+				.execute_unit(Command::Restore {
+					path: self.file,
+					until: self.until,
+				})
+				.await
+		})
+	}
+}
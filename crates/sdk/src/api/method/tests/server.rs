@@ -43,6 +43,9 @@ pub(super) fn mock(route_rx: Receiver<Route>) {
 				} => Ok(DbResponse::Other(CoreValue::None)),
 				Command::Query {
 					..
+				}
+				| Command::QueryStream {
+					..
 				} => Ok(DbResponse::Query(QueryResponse::new())),
 				Command::Create {
 					data,
@@ -112,6 +115,13 @@ pub(super) fn mock(route_rx: Receiver<Route>) {
 				Command::Run {
 					..
 				} => Ok(DbResponse::Other(CoreValue::None)),
+				Command::Begin => Ok(DbResponse::Other(uuid::Uuid::nil().into())),
+				Command::Commit {
+					..
+				}
+				| Command::Cancel {
+					..
+				} => Ok(DbResponse::Other(CoreValue::None)),
 				Command::ExportMl {
 					..
 				}
@@ -129,6 +139,12 @@ pub(super) fn mock(route_rx: Receiver<Route>) {
 				}
 				| Command::ImportFile {
 					..
+				}
+				| Command::Backup {
+					..
+				}
+				| Command::Restore {
+					..
 				} => Ok(DbResponse::Other(CoreValue::None)),
 			};
 
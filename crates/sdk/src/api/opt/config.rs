@@ -1,4 +1,5 @@
 use crate::opt::capabilities::Capabilities;
+use crate::opt::RetryPolicy;
 #[cfg(storage)]
 use std::path::PathBuf;
 use std::time::Duration;
@@ -10,6 +11,7 @@ pub struct Config {
 	pub(crate) strict: bool,
 	pub(crate) query_timeout: Option<Duration>,
 	pub(crate) transaction_timeout: Option<Duration>,
+	pub(crate) retry: Option<RetryPolicy>,
 	#[cfg(any(feature = "native-tls", feature = "rustls"))]
 	pub(crate) tls_config: Option<super::Tls>,
 	// Only used by the local engines
@@ -24,6 +26,7 @@ pub struct Config {
 	pub(crate) node_membership_check_interval: Option<Duration>,
 	pub(crate) node_membership_cleanup_interval: Option<Duration>,
 	pub(crate) changefeed_gc_interval: Option<Duration>,
+	pub(crate) expiry_interval: Option<Duration>,
 }
 
 impl Config {
@@ -56,6 +59,15 @@ impl Config {
 		self
 	}
 
+	/// Set the retry policy for statements which fail with a transaction conflict
+	///
+	/// The policy applies to every query which is not run within a transaction,
+	/// unless the query sets its own with [`Query::retry`](crate::method::Query::retry).
+	pub fn retry(mut self, policy: impl Into<Option<RetryPolicy>>) -> Self {
+		self.retry = policy.into();
+		self
+	}
+
 	/// Set the default user
 	pub fn user(mut self, user: crate::opt::auth::Root<'_>) -> Self {
 		self.auth = Level::Root;
@@ -127,4 +139,10 @@ impl Config {
 		self.changefeed_gc_interval = interval.into().filter(|x| !x.is_zero());
 		self
 	}
+
+	/// Set the interval at which the database should delete expired table records
+	pub fn expiry_interval(mut self, interval: impl Into<Option<Duration>>) -> Self {
+		self.expiry_interval = interval.into().filter(|x| !x.is_zero());
+		self
+	}
 }
@@ -19,6 +19,7 @@ pub use query::*;
 pub use resource::*;
 use serde_content::Serializer;
 use serde_content::Value as Content;
+pub use surrealdb_core::dbs::RetryPolicy;
 #[cfg(any(feature = "native-tls", feature = "rustls"))]
 pub use tls::*;
 
@@ -9,10 +9,13 @@ mod api_integration {
 	use std::borrow::Cow;
 	use std::ops::Bound;
 	use std::path::PathBuf;
+	use std::sync::atomic::AtomicU32;
+	use std::sync::atomic::Ordering;
 	use std::sync::Arc;
 	use std::sync::LazyLock;
 	use std::sync::Mutex;
 	use std::time::Duration;
+	use surrealdb::err::ErrorCode;
 	use surrealdb::error::Api as ApiError;
 	use surrealdb::error::Db as DbError;
 	use surrealdb::opt::auth::Database;
@@ -24,6 +27,7 @@ mod api_integration {
 	use surrealdb::opt::Config;
 	use surrealdb::opt::PatchOp;
 	use surrealdb::opt::Resource;
+	use surrealdb::opt::RetryPolicy;
 	use surrealdb::sql::statements::BeginStatement;
 	use surrealdb::sql::statements::CommitStatement;
 	use surrealdb::sql::thing;
@@ -98,6 +102,33 @@ mod api_integration {
 			drop(permit);
 		}
 
+		#[test_log::test(tokio::test)]
+		async fn session_retry() {
+			let permit = PERMITS.acquire().await.unwrap();
+			let policy = RetryPolicy::new(3).with_backoff(Duration::from_millis(1));
+			let config = Config::new().retry(policy);
+			let db = Surreal::new::<Ws>(("127.0.0.1:8000", config)).await.unwrap();
+			db.signin(Root {
+				username: ROOT_USER,
+				password: ROOT_PASS,
+			})
+			.await
+			.unwrap();
+			db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+			drop(permit);
+			db.query("CREATE counter:one SET count = 0").await.unwrap().check().unwrap();
+			// Update the record while a slower query is updating it
+			let slow =
+				db.query("UPDATE counter:one SET count += 1, slept = sleep(100ms)").into_owned();
+			let slow = tokio::spawn(async move { slow.await });
+			tokio::time::sleep(Duration::from_millis(20)).await;
+			db.query("UPDATE counter:one SET count += 1").await.unwrap().check().unwrap();
+			// The conflicting statement was executed again with the policy of the session
+			slow.await.unwrap().unwrap().check().unwrap();
+			let count: Option<i64> = db.query("counter:one.count").await.unwrap().take(0).unwrap();
+			assert_eq!(count, Some(2));
+		}
+
 		#[test_log::test(tokio::test)]
 		async fn wait_for() {
 			use surrealdb::opt::WaitFor::{Connection, Database};
@@ -142,9 +173,68 @@ mod api_integration {
 			drop(permit);
 		}
 
+		#[test_log::test(tokio::test)]
+		async fn query_records_concurrently() {
+			use futures::StreamExt;
+			let (permit, db) = new_db().await;
+			db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+			drop(permit);
+			db.query("FOR $i IN 0..2500 { CREATE user }").await.unwrap().check().unwrap();
+			// Start streaming the records, without consuming them
+			let mut stream = db.query("SELECT * FROM user").records::<ApiRecordId>().await.unwrap();
+			// Other requests on the connection are not blocked by the stream
+			let count: Option<usize> = tokio::time::timeout(Duration::from_secs(10), async {
+				db.query("count(SELECT * FROM user)").await
+			})
+			.await
+			.unwrap()
+			.unwrap()
+			.take(0)
+			.unwrap();
+			assert_eq!(count, Some(2500));
+			let mut count = 0;
+			while let Some(record) = stream.next().await {
+				record.unwrap();
+				count += 1;
+			}
+			assert_eq!(count, 2500);
+		}
+
+		#[test_log::test(tokio::test)]
+		async fn query_records_with_pending_requests() {
+			use futures::StreamExt;
+			let (permit, db) = new_db().await;
+			db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+			drop(permit);
+			// More batches are selected than the stream has credits for
+			db.query("FOR $i IN 0..20000 { CREATE user }").await.unwrap().check().unwrap();
+			// Start streaming the records, without consuming them
+			let mut stream = db.query("SELECT * FROM user").records::<ApiRecordId>().await.unwrap();
+			// Changing the connection state waits for the stream to finish
+			let set = tokio::spawn({
+				let db = db.clone();
+				async move { db.set("name", "stream").await }
+			});
+			// The records are still acknowledged while the change is pending
+			let mut count = 0;
+			while let Some(record) =
+				tokio::time::timeout(Duration::from_secs(10), stream.next()).await.unwrap()
+			{
+				record.unwrap();
+				count += 1;
+			}
+			assert_eq!(count, 20000);
+			set.await.unwrap().unwrap();
+			// Dropping a stream stops the query, so the connection state can change
+			let stream = db.query("SELECT * FROM user").records::<ApiRecordId>().await.unwrap();
+			drop(stream);
+			tokio::time::timeout(Duration::from_secs(10), db.unset("name")).await.unwrap().unwrap();
+		}
+
 		include!("api/mod.rs");
 		include!("api/serialisation.rs");
 		include!("api/live.rs");
+		include!("api/transaction.rs");
 	}
 
 	#[cfg(feature = "protocol-http")]
@@ -172,6 +262,33 @@ mod api_integration {
 			drop(permit);
 		}
 
+		#[test_log::test(tokio::test)]
+		async fn session_retry() {
+			let permit = PERMITS.acquire().await.unwrap();
+			let policy = RetryPolicy::new(3).with_backoff(Duration::from_millis(1));
+			let config = Config::new().retry(policy);
+			let db = Surreal::new::<Http>(("127.0.0.1:8000", config)).await.unwrap();
+			db.signin(Root {
+				username: ROOT_USER,
+				password: ROOT_PASS,
+			})
+			.await
+			.unwrap();
+			db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+			drop(permit);
+			db.query("CREATE counter:one SET count = 0").await.unwrap().check().unwrap();
+			// Update the record while a slower query is updating it
+			let slow =
+				db.query("UPDATE counter:one SET count += 1, slept = sleep(100ms)").into_owned();
+			let slow = tokio::spawn(async move { slow.await });
+			tokio::time::sleep(Duration::from_millis(20)).await;
+			db.query("UPDATE counter:one SET count += 1").await.unwrap().check().unwrap();
+			// The conflicting statement was executed again with the policy of the session
+			slow.await.unwrap().unwrap().check().unwrap();
+			let count: Option<i64> = db.query("counter:one.count").await.unwrap().take(0).unwrap();
+			assert_eq!(count, Some(2));
+		}
+
 		include!("api/mod.rs");
 		include!("api/serialisation.rs");
 		include!("api/backup.rs");
@@ -270,6 +387,7 @@ mod api_integration {
 		include!("api/mod.rs");
 		include!("api/serialisation.rs");
 		include!("api/live.rs");
+		include!("api/transaction.rs");
 		include!("api/backup.rs");
 	}
 
@@ -313,6 +431,7 @@ mod api_integration {
 		include!("api/mod.rs");
 		include!("api/serialisation.rs");
 		include!("api/live.rs");
+		include!("api/transaction.rs");
 		include!("api/backup.rs");
 	}
 
@@ -357,6 +476,7 @@ mod api_integration {
 		include!("api/mod.rs");
 		include!("api/serialisation.rs");
 		include!("api/live.rs");
+		include!("api/transaction.rs");
 		include!("api/backup.rs");
 	}
 
@@ -388,6 +508,7 @@ mod api_integration {
 		include!("api/mod.rs");
 		include!("api/serialisation.rs");
 		include!("api/live.rs");
+		include!("api/transaction.rs");
 		include!("api/backup.rs");
 	}
 
@@ -416,6 +537,7 @@ mod api_integration {
 		include!("api/mod.rs");
 		include!("api/serialisation.rs");
 		include!("api/live.rs");
+		include!("api/transaction.rs");
 		include!("api/backup.rs");
 	}
 
@@ -460,6 +582,7 @@ mod api_integration {
 		include!("api/mod.rs");
 		include!("api/serialisation.rs");
 		include!("api/live.rs");
+		include!("api/transaction.rs");
 		include!("api/backup.rs");
 	}
 
@@ -504,6 +627,7 @@ mod api_integration {
 		include!("api/mod.rs");
 		include!("api/serialisation.rs");
 		include!("api/live.rs");
+		include!("api/transaction.rs");
 		include!("api/version.rs");
 		include!("api/backup.rs");
 		include!("api/backup_version.rs");
@@ -1,6 +1,8 @@
 // Tests for exporting and importing data
 // Supported by the storage engines and the HTTP protocol
 
+use surrealdb::kvs::backup::Header;
+use surrealdb::kvs::export::Format;
 use surrealdb::sql::Array;
 use surrealdb_core::sql::Table;
 use tokio::fs::remove_file;
@@ -118,6 +120,274 @@ async fn export_with_config() {
 	}
 }
 
+#[tokio::test]
+async fn export_with_record_filters() {
+	let (permit, db) = new_db().await;
+	let db_name = Ulid::new().to_string();
+	db.use_ns(NS).use_db(&db_name).await.unwrap();
+
+	// Insert records for two tenants
+	db.query("FOR $i IN 0..10 { CREATE type::thing('user', $i) SET tenant = IF $i % 2 = 0 { 'a' } ELSE { 'b' } }")
+		.await
+		.unwrap()
+		.check()
+		.unwrap();
+
+	// Drop the permit to release the database lock
+	drop(permit);
+
+	// Define the export file name
+	let file = format!("{db_name}.sql");
+
+	// Export the even records in a range, and import only the data
+	let res = async {
+		db.export(&file)
+			.with_config()
+			.tables(vec!["user"])
+			.range("user", 2..8)
+			.condition("user", "tenant = 'a'")
+			.schema(false)
+			.await?;
+		db.query("REMOVE TABLE user").await?;
+		db.import(&file).await?;
+		Result::<(), Error>::Ok(())
+	}
+	.await;
+
+	// Remove the export file
+	remove_file(&file).await.unwrap();
+
+	// Check the result of the export/import operations
+	res.unwrap();
+
+	// Verify that only the selected records were imported
+	let mut response = db.query("SELECT VALUE record::id(id) FROM user").await.unwrap();
+	let ids: Vec<i64> = response.take(0).unwrap();
+	assert_eq!(ids, vec![2, 4, 6]);
+	// Without the schema the table is schemaless, and not defined by the import
+	let mut response = db.query("INFO FOR DB").await.unwrap();
+	let info: Option<serde_json::Value> = response.take(0).unwrap();
+	assert_eq!(info.unwrap()["tables"]["user"], json!("DEFINE TABLE user TYPE ANY SCHEMALESS PERMISSIONS NONE"));
+}
+
+#[tokio::test]
+async fn export_with_invalid_condition() {
+	let (permit, db) = new_db().await;
+	let db_name = Ulid::new().to_string();
+	db.use_ns(NS).use_db(&db_name).await.unwrap();
+	drop(permit);
+	let res = db.export(()).with_config().condition("user", "tenant = ").await;
+	res.unwrap_err();
+}
+
+#[tokio::test]
+async fn export_import_jsonl_with_mapping() {
+	let (permit, db) = new_db().await;
+	let db_name = Ulid::new().to_string();
+	db.use_ns(NS).use_db(&db_name).await.unwrap();
+
+	// Insert records
+	db.query("FOR $i IN 0..10 { CREATE type::thing('user', $i) SET name = 'User ' + <string> $i, age = $i, tags = ['a', $i] }")
+		.await
+		.unwrap()
+		.check()
+		.unwrap();
+
+	// Drop the permit to release the database lock
+	drop(permit);
+
+	// Define the export file name
+	let file = format!("{db_name}.jsonl");
+
+	// Export the user table, and import it into the person table
+	let res = async {
+		db.export(&file).with_config().format(Format::Jsonl).tables(vec!["user"]).await?;
+		db.import(&file)
+			.with_config()
+			.format(Format::Jsonl)
+			.table("person")
+			.map("name", "full_name")
+			.await?;
+		Result::<(), Error>::Ok(())
+	}
+	.await;
+
+	// Remove the export file
+	remove_file(&file).await.unwrap();
+
+	// Check the result of the export/import operations
+	res.unwrap();
+
+	// Verify that the records were imported with the renamed field
+	let mut response = db
+		.query("SELECT full_name, age, tags, name FROM person ORDER BY age")
+		.await
+		.unwrap();
+	let rows: Vec<serde_json::Value> = response.take(0).unwrap();
+	assert_eq!(rows.len(), 10);
+	assert_eq!(rows[3], json!({ "full_name": "User 3", "age": 3, "tags": ["a", 3], "name": null }));
+	// The user ids were converted into person ids
+	let mut response = db.query("SELECT VALUE full_name FROM person:7").await.unwrap();
+	let name: Option<String> = response.take(0).unwrap();
+	assert_eq!(name.as_deref(), Some("User 7"));
+}
+
+#[tokio::test]
+async fn export_import_csv() {
+	let (permit, db) = new_db().await;
+	let db_name = Ulid::new().to_string();
+	db.use_ns(NS).use_db(&db_name).await.unwrap();
+
+	// Insert records, with quoted and multi-line strings
+	db.query("FOR $i IN 0..10 { CREATE type::thing('user', $i) SET name = 'User \"' + <string> $i + '\",\nline', score = $i * 1.5 }")
+		.await
+		.unwrap()
+		.check()
+		.unwrap();
+	db.query("UPDATE user:1 SET nested = { active: true }").await.unwrap().check().unwrap();
+
+	// Drop the permit to release the database lock
+	drop(permit);
+
+	// Define the export file name
+	let file = format!("{db_name}.csv");
+
+	// Export, remove table, and import
+	let res = async {
+		db.export(&file).with_config().format(Format::Csv).tables(vec!["user"]).await?;
+		db.query("REMOVE TABLE user").await?;
+		db.import(&file).with_config().format(Format::Csv).table("user").await?;
+		Result::<(), Error>::Ok(())
+	}
+	.await;
+
+	// Remove the export file
+	remove_file(&file).await.unwrap();
+
+	// Check the result of the export/import operations
+	res.unwrap();
+
+	// Verify that all records exist post-import
+	let mut response =
+		db.query("SELECT name, score FROM user ORDER BY score").await.unwrap();
+	let rows: Vec<serde_json::Value> = response.take(0).unwrap();
+	assert_eq!(rows.len(), 10);
+	assert_eq!(rows[2], json!({ "name": "User \"2\",\nline", "score": 3.0 }));
+	// Nested values were imported from their JSON encoding
+	let mut response = db.query("SELECT VALUE nested FROM user:1").await.unwrap();
+	let nested: Option<serde_json::Value> = response.take(0).unwrap();
+	assert_eq!(nested, Some(json!({ "active": true })));
+}
+
+#[tokio::test]
+#[cfg(feature = "parquet")]
+async fn export_import_parquet() {
+	let (permit, db) = new_db().await;
+	let db_name = Ulid::new().to_string();
+	db.use_ns(NS).use_db(&db_name).await.unwrap();
+
+	// Insert records
+	db.query("FOR $i IN 0..10 { CREATE type::thing('user', $i) SET name = 'User ' + <string> $i, age = $i }")
+		.await
+		.unwrap()
+		.check()
+		.unwrap();
+
+	// Drop the permit to release the database lock
+	drop(permit);
+
+	// Define the export file name
+	let file = format!("{db_name}.parquet");
+
+	// Export, remove table, and import
+	let res = async {
+		db.export(&file).with_config().format(Format::Parquet).tables(vec!["user"]).await?;
+		db.query("REMOVE TABLE user").await?;
+		db.import(&file)
+			.with_config()
+			.format(Format::Parquet)
+			.table("user")
+			.map("name", "full_name")
+			.await?;
+		Result::<(), Error>::Ok(())
+	}
+	.await;
+
+	// Remove the export file
+	remove_file(&file).await.unwrap();
+
+	// Check the result of the export/import operations
+	res.unwrap();
+
+	// Verify that all records exist post-import
+	let mut response = db.query("SELECT full_name, age FROM user ORDER BY age").await.unwrap();
+	let rows: Vec<serde_json::Value> = response.take(0).unwrap();
+	assert_eq!(rows.len(), 10);
+	assert_eq!(rows[4], json!({ "full_name": "User 4", "age": 4 }));
+}
+
+#[tokio::test]
+async fn export_records_requires_a_single_table() {
+	let (permit, db) = new_db().await;
+	let db_name = Ulid::new().to_string();
+	db.use_ns(NS).use_db(&db_name).await.unwrap();
+	drop(permit);
+	let file = format!("{db_name}.jsonl");
+	let res = db.export(&file).with_config().format(Format::Jsonl).await;
+	let _ = remove_file(&file).await;
+	res.unwrap_err();
+}
+
+#[tokio::test]
+async fn backup_restore_incremental() {
+	let (permit, db) = new_db().await;
+	let db_name = Ulid::new().to_string();
+	db.use_ns(NS).use_db(&db_name).await.unwrap();
+
+	// Incremental backups are read from the change feed
+	db.query("DEFINE TABLE user CHANGEFEED 1h; CREATE user:1 SET name = 'a'; CREATE user:2 SET name = 'b'")
+		.await
+		.unwrap()
+		.check()
+		.unwrap();
+
+	// Drop the permit to release the database lock
+	drop(permit);
+
+	// Define the backup file names
+	let full = format!("{db_name}.full.surql");
+	let incremental = format!("{db_name}.incremental.surql");
+
+	// Back up, change the data, back up the changes, and restore into another database
+	let res = async {
+		db.backup(&full).await?;
+		let header = std::fs::read_to_string(&full).unwrap();
+		let header = Header::from_line(header.lines().next().unwrap().as_bytes()).unwrap();
+		assert_eq!(header.since, None);
+		db.query("UPDATE user:1 SET name = 'c'; DELETE user:2; CREATE user:3 SET name = 'd'")
+			.await?
+			.check()?;
+		db.backup(&incremental).since(header.until).await?;
+		db.use_db(format!("{db_name}_restored")).await?;
+		db.restore(&full).await?;
+		db.restore(&incremental).await?;
+		Result::<(), Error>::Ok(())
+	}
+	.await;
+
+	// Remove the backup files
+	remove_file(&full).await.unwrap();
+	remove_file(&incremental).await.unwrap();
+
+	// Check the result of the backup/restore operations
+	res.unwrap();
+
+	// Verify that the changes were restored
+	let mut response = db.query("SELECT VALUE name FROM user").await.unwrap();
+	let names: Vec<String> = response.take(0).unwrap();
+	assert_eq!(names, vec!["c", "d"]);
+}
+
 #[test_log::test(tokio::test)]
 #[cfg(feature = "ml")]
 async fn ml_export_import() {
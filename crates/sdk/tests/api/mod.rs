@@ -22,7 +22,11 @@ async fn yuse() {
 		// Local engines return this error
 		Error::Db(DbError::NsEmpty) => {}
 		// Remote engines return this error
-		Error::Api(ApiError::Query(error)) if error.contains("Specify a namespace to use") => {}
+		Error::Api(ApiError::Server {
+			code: ErrorCode::NoNamespace,
+			message,
+			..
+		}) if message.contains("Specify a namespace to use") => {}
 		error => panic!("{:?}", error),
 	}
 	db.use_ns(NS).await.unwrap();
@@ -30,7 +34,11 @@ async fn yuse() {
 		// Local engines return this error
 		Error::Db(DbError::DbEmpty) => {}
 		// Remote engines return this error
-		Error::Api(ApiError::Query(error)) if error.contains("Specify a database to use") => {}
+		Error::Api(ApiError::Server {
+			code: ErrorCode::NoDatabase,
+			message,
+			..
+		}) if message.contains("Specify a database to use") => {}
 		error => panic!("{:?}", error),
 	}
 	db.use_db(item.as_str()).await.unwrap();
@@ -198,7 +206,10 @@ async fn record_access_throws_error() {
 		.await
 	{
 		Err(Error::Db(surrealdb::err::Error::Thrown(e))) => assert_eq!(e, "signup_thrown_error"),
-		Err(Error::Api(surrealdb::error::Api::Query(e))) => assert!(e.contains("signup")),
+		Err(Error::Api(surrealdb::error::Api::Server {
+			message,
+			..
+		})) => assert!(message.contains("signup")),
 		Err(Error::Api(surrealdb::error::Api::Http(e))) => assert_eq!(
 			e,
 			"HTTP status client error (400 Bad Request) for url (http://127.0.0.1:8000/signup)"
@@ -219,7 +230,10 @@ async fn record_access_throws_error() {
 		.await
 	{
 		Err(Error::Db(surrealdb::err::Error::Thrown(e))) => assert_eq!(e, "signin_thrown_error"),
-		Err(Error::Api(surrealdb::error::Api::Query(e))) => assert!(e.contains("signin")),
+		Err(Error::Api(surrealdb::error::Api::Server {
+			message,
+			..
+		})) => assert!(message.contains("signin")),
 		Err(Error::Api(surrealdb::error::Api::Http(e))) => assert_eq!(
 			e,
 			"HTTP status client error (400 Bad Request) for url (http://127.0.0.1:8000/signin)"
@@ -261,9 +275,12 @@ async fn record_access_invalid_query() {
 		.await
 	{
 		Err(Error::Db(surrealdb::err::Error::AccessRecordSignupQueryFailed)) => (),
-		Err(Error::Api(surrealdb::error::Api::Query(e))) => {
+		Err(Error::Api(surrealdb::error::Api::Server {
+			message,
+			..
+		})) => {
 			assert_eq!(
-				e,
+				message,
 				"There was a problem with the database: The record access signup query failed"
 			)
 		}
@@ -287,9 +304,12 @@ async fn record_access_invalid_query() {
 		.await
 	{
 		Err(Error::Db(surrealdb::err::Error::AccessRecordSigninQueryFailed)) => (),
-		Err(Error::Api(surrealdb::error::Api::Query(e))) => {
+		Err(Error::Api(surrealdb::error::Api::Server {
+			message,
+			..
+		})) => {
 			assert_eq!(
-				e,
+				message,
 				"There was a problem with the database: The record access signin query failed"
 			)
 		}
@@ -425,6 +445,49 @@ async fn query_chaining() {
 	response.check().unwrap();
 }
 
+#[test_log::test(tokio::test)]
+async fn query_records() {
+	use futures::StreamExt;
+	let (permit, db) = new_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	db.query("FOR $i IN 0..2500 { CREATE user }").await.unwrap().check().unwrap();
+	// The records of all statements are streamed in order
+	let sql = "SELECT * FROM user; SELECT * FROM user ORDER BY id LIMIT 5";
+	let mut stream = db.query(sql).records::<ApiRecordId>().await.unwrap();
+	let mut count = 0;
+	while let Some(record) = stream.next().await {
+		record.unwrap();
+		count += 1;
+	}
+	assert_eq!(count, 2505);
+	// Statement errors are returned on the stream
+	let sql = "SELECT * FROM user LIMIT 1; CREATE bar SET baz = rand('a')";
+	let mut stream = db.query(sql).records::<ApiRecordId>().await.unwrap();
+	stream.next().await.unwrap().unwrap();
+	stream.next().await.unwrap().unwrap_err();
+	assert!(stream.next().await.is_none());
+}
+
+#[test_log::test(tokio::test)]
+async fn query_records_bindings() {
+	use futures::StreamExt;
+	let (permit, db) = new_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	// The bindings keep their types, however large they are
+	let sql = "RETURN type::is::datetime($at) AND string::len($text) = 100000";
+	let mut stream = db
+		.query(sql)
+		.bind(("at", surrealdb::sql::Datetime::default()))
+		.bind(("text", "a".repeat(100_000)))
+		.records::<bool>()
+		.await
+		.unwrap();
+	assert!(stream.next().await.unwrap().unwrap());
+	assert!(stream.next().await.is_none());
+}
+
 #[test_log::test(tokio::test)]
 async fn mixed_results_query() {
 	let (permit, db) = new_db().await;
@@ -436,6 +499,65 @@ async fn mixed_results_query() {
 	let _: Option<ApiRecordId> = response.take(1).unwrap();
 }
 
+#[test_log::test(tokio::test)]
+async fn query_error_codes() {
+	let (permit, db) = new_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	let sql = "
+		DEFINE FIELD age ON user TYPE option<int>;
+		DEFINE INDEX email ON user FIELDS email UNIQUE;
+		CREATE user:one SET email = 'one@example.com';
+	";
+	db.query(sql).await.unwrap().check().unwrap();
+	// Statement errors carry a stable code
+	let sql = "CREATE user SET email = 'one@example.com'; CREATE user SET age = 'one'";
+	let mut response = db.query(sql).await.unwrap();
+	let error = response.take::<Value>(0).unwrap_err();
+	assert_eq!(error.code(), Some(ErrorCode::UniqueIndexViolation));
+	assert!(!error.is_retryable());
+	let error = response.take::<Value>(1).unwrap_err();
+	assert_eq!(error.code(), Some(ErrorCode::FieldCoercion));
+	// Method errors carry a stable code and structured details
+	let error = db
+		.create::<Option<ApiRecordId>>(("user", "two"))
+		.content(json!({ "email": "one@example.com" }))
+		.await
+		.unwrap_err();
+	assert_eq!(error.code(), Some(ErrorCode::UniqueIndexViolation));
+	match error {
+		Error::Db(DbError::IndexExists {
+			index,
+			..
+		}) => assert_eq!(index, "email"),
+		Error::Api(ApiError::Server {
+			details,
+			..
+		}) => assert_eq!(details.get("index"), Some(&Value::from_inner("email".into()))),
+		error => panic!("unexpected error; {error:?}"),
+	}
+}
+
+#[test_log::test(tokio::test)]
+async fn query_retry() {
+	let (permit, db) = new_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	db.query("CREATE counter:one SET count = 0").await.unwrap().check().unwrap();
+	// Update the record while a slower query is updating it
+	let slow = db
+		.query("UPDATE counter:one SET count += 1, slept = sleep(100ms)")
+		.retry(RetryPolicy::new(3).with_backoff(Duration::from_millis(1)))
+		.into_owned();
+	let slow = tokio::spawn(async move { slow.await });
+	tokio::time::sleep(Duration::from_millis(20)).await;
+	db.query("UPDATE counter:one SET count += 1").await.unwrap().check().unwrap();
+	// The conflicting statement was executed again
+	slow.await.unwrap().unwrap().check().unwrap();
+	let count: Option<i64> = db.query("counter:one.count").await.unwrap().take(0).unwrap();
+	assert_eq!(count, Some(2));
+}
+
 #[test_log::test(tokio::test)]
 async fn create_record_no_id() {
 	let (permit, db) = new_db().await;
@@ -543,7 +665,8 @@ async fn create_record_with_id_in_content() {
 		surrealdb::Error::Db(DbError::IdMismatch {
 			..
 		}) => {}
-		surrealdb::Error::Api(ApiError::Query {
+		surrealdb::Error::Api(ApiError::Server {
+			code: ErrorCode::RecordMismatch,
 			..
 		}) => {}
 		error => panic!("unexpected error; {error:?}"),
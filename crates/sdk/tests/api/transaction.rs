@@ -0,0 +1,109 @@
+// Tests for interactive transactions
+// Supported by the storage engines and the WS protocol
+
+#[tokio::test]
+async fn transaction_commit() {
+	let (permit, db) = new_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	let txn = db.clone().transaction().await.unwrap();
+	let _: Option<ApiRecordId> = txn
+		.create(("user", "one"))
+		.content(Record {
+			name: "One".to_owned(),
+		})
+		.await
+		.unwrap();
+	// The record is visible within the transaction
+	let record: Option<ApiRecordId> = txn.select(("user", "one")).await.unwrap();
+	assert!(record.is_some());
+	// The record is not visible outside of the transaction
+	let record: Option<ApiRecordId> = db.select(("user", "one")).await.unwrap();
+	assert!(record.is_none());
+	txn.commit().await.unwrap();
+	let record: Option<ApiRecordId> = db.select(("user", "one")).await.unwrap();
+	assert!(record.is_some());
+}
+
+#[tokio::test]
+async fn transaction_cancel() {
+	let (permit, db) = new_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	let txn = db.clone().transaction().await.unwrap();
+	txn.query("CREATE user:one SET name = 'One'").await.unwrap().check().unwrap();
+	let db = txn.cancel().await.unwrap();
+	let record: Option<ApiRecordId> = db.select(("user", "one")).await.unwrap();
+	assert!(record.is_none());
+}
+
+#[tokio::test]
+async fn transaction_finished() {
+	let (permit, db) = new_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	let txn = db.clone().transaction().await.unwrap();
+	let client = (*txn).clone();
+	txn.commit().await.unwrap();
+	// Requests can not be made on a finished transaction
+	let res: Result<Option<ApiRecordId>, _> = client.select(("user", "one")).await;
+	res.unwrap_err();
+}
+
+#[tokio::test]
+async fn transaction_retry() {
+	let (permit, db) = new_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	db.query("CREATE counter:one SET count = 0").await.unwrap().check().unwrap();
+	let attempts = Arc::new(AtomicU32::new(0));
+	let count: Option<i64> = db
+		.clone()
+		.transaction()
+		.retry(RetryPolicy::new(3), {
+			let db = db.clone();
+			let attempts = attempts.clone();
+			move |txn| {
+				let db = db.clone();
+				let attempts = attempts.clone();
+				async move {
+					let sql = "UPDATE counter:one SET count += 1 RETURN VALUE count";
+					let mut response = txn.query(sql).await?;
+					// Update the record outside of the first transaction, so that it conflicts
+					if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+						db.query("UPDATE counter:one SET count += 10").await?.check()?;
+					}
+					response.take(0)
+				}
+			}
+		})
+		.await
+		.unwrap();
+	// The transaction was run again after the conflict
+	assert_eq!(attempts.load(Ordering::SeqCst), 2);
+	assert_eq!(count, Some(11));
+	let count: Option<i64> = db.query("counter:one.count").await.unwrap().take(0).unwrap();
+	assert_eq!(count, Some(11));
+}
+
+#[tokio::test]
+async fn transaction_failed_statement() {
+	let (permit, db) = new_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	db.query("DEFINE FIELD age ON user TYPE int").await.unwrap().check().unwrap();
+	let txn = db.clone().transaction().await.unwrap();
+	txn.query("CREATE user:one SET name = 'One', age = 1").await.unwrap().check().unwrap();
+	// The statement fails after creating the first record
+	txn.query("INSERT INTO user [{ id: user:two, age: 2 }, { id: user:three, age: 'three' }]")
+		.await
+		.unwrap()
+		.check()
+		.unwrap_err();
+	// Subsequent statements are not executed
+	txn.query("CREATE user:four SET name = 'Four', age = 4").await.unwrap().check().unwrap_err();
+	// The transaction can not be committed, and is cancelled instead
+	txn.commit().await.unwrap_err();
+	let records: Vec<ApiRecordId> = db.select("user").await.unwrap();
+	assert!(records.is_empty());
+}
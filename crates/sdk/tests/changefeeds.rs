@@ -780,3 +780,73 @@ async fn changefeed_with_original() -> Result<(), Error> {
 
 	Ok(())
 }
+
+#[test_log::test(tokio::test)]
+async fn database_change_feed_sync() -> Result<(), Error> {
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let src = new_ds().await?;
+	src.execute("DEFINE DATABASE test CHANGEFEED 1h;", &ses, None).await?.remove(0).result?;
+	let sql = "
+		DEFINE TABLE person;
+		CREATE person:tobie SET name = 'Tobie';
+		CREATE person:jaime SET name = 'Jaime';
+		UPDATE person:tobie SET name = 'Tobie Morgan';
+		DELETE person:jaime;
+	";
+	for res in src.execute(sql, &ses, None).await? {
+		res.result?;
+	}
+	// Stream the change feed out of the source datastore
+	let (snd, rcv) = surrealdb::channel::bounded(1);
+	let task = src.sync_export(&ses, snd, None, 0).await?;
+	tokio::spawn(task);
+	let mut bytes = Vec::new();
+	while let Ok(v) = rcv.recv().await {
+		bytes.extend(v);
+	}
+	// Apply the change feed to the target datastore
+	let dst = new_ds().await?;
+	for res in dst.sync_import(&ses, &bytes).await? {
+		res.result?;
+	}
+	let tmp = dst.execute("SELECT * FROM person", &ses, None).await?.remove(0).result?;
+	let val = Value::parse("[{ id: person:tobie, name: 'Tobie Morgan' }]");
+	assert_eq!(tmp, val);
+	Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn database_change_feed_sync_large_transaction() -> Result<(), Error> {
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let src = new_ds().await?;
+	src.execute("DEFINE DATABASE test CHANGEFEED 1h;", &ses, None).await?.remove(0).result?;
+	// Change more tables in a single versionstamp than are fetched in one batch
+	let mut sql = String::from("BEGIN;");
+	for i in 0..120 {
+		sql.push_str(&format!("CREATE test{i}:one SET num = {i};"));
+	}
+	sql.push_str("COMMIT;");
+	for res in src.execute(&sql, &ses, None).await? {
+		res.result?;
+	}
+	// Stream the change feed out of the source datastore
+	let (snd, rcv) = surrealdb::channel::bounded(1);
+	let task = src.sync_export(&ses, snd, None, 0).await?;
+	tokio::spawn(task);
+	let mut bytes = Vec::new();
+	while let Ok(v) = rcv.recv().await {
+		bytes.extend(v);
+	}
+	// Apply the change feed to the target datastore
+	let dst = new_ds().await?;
+	for res in dst.sync_import(&ses, &bytes).await? {
+		res.result?;
+	}
+	for i in 0..120 {
+		let sql = format!("SELECT * FROM test{i}");
+		let tmp = dst.execute(&sql, &ses, None).await?.remove(0).result?;
+		let val = Value::parse(&format!("[{{ id: test{i}:one, num: {i} }}]"));
+		assert_eq!(tmp, val);
+	}
+	Ok(())
+}
@@ -0,0 +1,172 @@
+mod parse;
+use parse::Parse;
+mod helpers;
+use helpers::new_ds;
+use surrealdb::dbs::Session;
+use surrealdb::err::Error;
+use surrealdb::sql::Value;
+
+/// Returns a timestamp, in nanoseconds since the unix epoch, far in the future
+fn future() -> u64 {
+	let now = chrono::Utc::now() + chrono::Duration::days(1);
+	now.timestamp_nanos_opt().unwrap() as u64
+}
+
+#[tokio::test]
+async fn table_expire_after() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE session EXPIRE AFTER 1h;
+		DEFINE EVENT expired ON session WHEN $event = 'DELETE' THEN {
+			CREATE log SET session = $before.id;
+		};
+		CREATE session:one, session:two;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	for _ in 0..3 {
+		res.remove(0).result?;
+	}
+	// Nothing has expired yet
+	dbs.expiry_process().await?;
+	let res = &mut dbs.execute("SELECT VALUE id FROM session", &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[session:one, session:two]");
+	assert_eq!(tmp, val);
+	// Everything has expired
+	dbs.expiry_process_at(future()).await?;
+	let sql = "
+		SELECT VALUE id FROM session;
+		SELECT VALUE session FROM log ORDER BY session;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[session:one, session:two]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn table_expire_at_field() -> Result<(), Error> {
+	let sql = "
+		CREATE token:existing SET expires = d'2000-01-01T00:00:00Z';
+		DEFINE TABLE OVERWRITE token EXPIRE AT expires;
+		CREATE token:past SET expires = d'2000-01-01T00:00:00Z';
+		CREATE token:future SET expires = time::now() + 1w;
+		CREATE token:never;
+		CREATE token:moved SET expires = d'2000-01-01T00:00:00Z';
+		UPDATE token:moved SET expires = time::now() + 1w;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	for _ in 0..7 {
+		res.remove(0).result?;
+	}
+	// Only the records in the past have expired
+	dbs.expiry_process().await?;
+	let res = &mut dbs.execute("SELECT VALUE id FROM token", &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[token:future, token:moved, token:never]");
+	assert_eq!(tmp, val);
+	// Records without an expiry field never expire
+	dbs.expiry_process_at(future() + 604_800_000_000_000).await?;
+	let res = &mut dbs.execute("SELECT VALUE id FROM token", &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[token:never]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn table_expire_removed() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE session EXPIRE AFTER 1h;
+		CREATE session:one;
+		DEFINE TABLE OVERWRITE session;
+		INFO FOR TABLE session;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	for _ in 0..4 {
+		res.remove(0).result?;
+	}
+	// The table no longer expires records
+	dbs.expiry_process_at(future()).await?;
+	let res = &mut dbs.execute("SELECT VALUE id FROM session", &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[session:one]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn table_expire_failure() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE audit EXPIRE AFTER 1h;
+		DEFINE EVENT keep ON audit WHEN $event = 'DELETE' THEN {
+			THROW 'audit records can not be deleted';
+		};
+		DEFINE TABLE session EXPIRE AFTER 1h;
+		CREATE audit:one, session:one;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	for _ in 0..4 {
+		res.remove(0).result?;
+	}
+	// A table which fails does not stop the other tables from expiring
+	dbs.expiry_process_at(future()).await?;
+	let sql = "
+		SELECT VALUE id FROM audit;
+		SELECT VALUE id FROM session;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[audit:one]");
+	assert_eq!(tmp, val);
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn table_expire_record_failure() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE audit EXPIRE AFTER 1h;
+		DEFINE EVENT keep ON audit WHEN $event = 'DELETE' AND $before.keep THEN {
+			THROW 'kept audit records can not be deleted';
+		};
+		CREATE audit:one SET keep = true;
+		FOR $i IN 0..100 { CREATE audit SET keep = false };
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	for _ in 0..4 {
+		res.remove(0).result?;
+	}
+	// A record which fails does not stop the other records from expiring
+	dbs.expiry_process_at(future()).await?;
+	let res = &mut dbs.execute("SELECT VALUE id FROM audit", &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[audit:one]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
@@ -750,3 +750,111 @@ async fn select_where_matches_analyser_with_mapper() -> Result<(), Error> {
 	t.skip_ok(2)?;
 	Ok(())
 }
+
+#[tokio::test]
+async fn select_where_matches_analyser_with_synonyms() -> Result<(), Error> {
+	let sql = r"
+		DEFINE ANALYZER synonyms TOKENIZERS blank,class FILTERS lowercase,stopwords(english),synonym('../../tests/data/synonyms-en.txt');
+		CREATE t:1 SET text = 'A red Automobile parked by the couch';
+		CREATE t:2 SET text = 'The car is in the garage';
+		DEFINE INDEX search_idx ON TABLE t COLUMNS text SEARCH ANALYZER synonyms BM25;
+		SELECT id FROM t WHERE text @@ 'car' ORDER BY id;
+		SELECT id FROM t WHERE text @@ 'the sofa'";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(6)?;
+	t.skip_ok(4)?;
+	t.expect_val("[{ id: t:1 }, { id: t:2 }]")?;
+	t.expect_val("[{ id: t:1 }]")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_analyser_with_missing_synonyms_file() -> Result<(), Error> {
+	let sql = r"
+		DEFINE ANALYZER synonyms TOKENIZERS blank FILTERS synonym('../../tests/data/missing.txt');";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(1)?;
+	t.expect_error(
+		"Internal database error: Invalid synonym path: \"../../tests/data/missing.txt\"",
+	)?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_where_matches_phrase_and_proximity() -> Result<(), Error> {
+	let sql = r#"
+		DEFINE ANALYZER simple TOKENIZERS blank,class FILTERS lowercase;
+		CREATE t:1 SET text = 'The quick brown fox jumps over the lazy dog';
+		CREATE t:2 SET text = 'The brown quick fox';
+		CREATE t:3 SET text = ['A quick one', 'Brown, fast fox'];
+		DEFINE INDEX search_idx ON TABLE t COLUMNS text SEARCH ANALYZER simple BM25;
+		SELECT id FROM t WHERE text @@ '"quick brown"' ORDER BY id;
+		SELECT id FROM t WHERE text @@ '"brown fast"' ORDER BY id;
+		SELECT id FROM t WHERE text @@ 'quick NEAR/1 fox' ORDER BY id;
+		SELECT id FROM t WHERE text @@ 'quick NEAR/0 brown' ORDER BY id;
+		SELECT id FROM t WHERE text @@ '"the quick" NEAR/6 dog' ORDER BY id;
+	"#;
+	let mut t = Test::new(sql).await?;
+	t.expect_size(10)?;
+	t.skip_ok(5)?;
+	t.expect_val("[{ id: t:1 }]")?;
+	t.expect_val("[{ id: t:3 }]")?;
+	t.expect_val("[{ id: t:1 }, { id: t:2 }]")?;
+	t.expect_val("[{ id: t:1 }, { id: t:2 }]")?;
+	t.expect_val("[{ id: t:1 }]")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_where_matches_boolean_and_prefix() -> Result<(), Error> {
+	let sql = r#"
+		DEFINE ANALYZER simple TOKENIZERS blank,class FILTERS lowercase;
+		CREATE t:1 SET text = 'Reset your password from the settings page';
+		CREATE t:2 SET text = 'Password resets are sent by email';
+		CREATE t:3 SET text = 'Change the email address of your account';
+		DEFINE INDEX search_idx ON TABLE t COLUMNS text SEARCH ANALYZER simple BM25 HIGHLIGHTS;
+		SELECT id FROM t WHERE text @@ '+password -email' ORDER BY id;
+		SELECT id FROM t WHERE text @@ 'email -"password resets"' ORDER BY id;
+		SELECT id FROM t WHERE text @@ '-password' ORDER BY id;
+		SELECT id, search::highlight('<b>', '</b>', 1) AS text FROM t WHERE text @1@ 'reset*' ORDER BY id;
+	"#;
+	let mut t = Test::new(sql).await?;
+	t.expect_size(9)?;
+	t.skip_ok(5)?;
+	t.expect_val("[{ id: t:1 }]")?;
+	t.expect_val("[{ id: t:3 }]")?;
+	t.expect_val("[]")?;
+	t.expect_val(
+		"[
+			{ id: t:1, text: '<b>Reset</b> your password from the settings page' },
+			{ id: t:2, text: 'Password <b>resets</b> are sent by email' }
+		]",
+	)?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_where_matches_fuzzy() -> Result<(), Error> {
+	let sql = r#"
+		DEFINE ANALYZER simple TOKENIZERS blank,class FILTERS lowercase;
+		CREATE t:1 SET name = 'SurrealDB Cloud';
+		CREATE t:2 SET name = 'Surrealist';
+		CREATE t:3 SET name = 'Serial Port Adapter';
+		DEFINE INDEX search_idx ON TABLE t COLUMNS name SEARCH ANALYZER simple BM25 HIGHLIGHTS;
+		SELECT id FROM t WHERE name @@ 'surealdb' ORDER BY id;
+		SELECT id, search::highlight('<b>', '</b>', 1) AS name FROM t WHERE name @1,fuzzy=1@ 'surealdb cloude' ORDER BY id;
+		SELECT id FROM t WHERE name @fuzzy=2@ 'serail' ORDER BY id;
+		SELECT id FROM t WHERE name @fuzzy=1@ '"surealdb cloud"' ORDER BY id;
+		SELECT id FROM t WHERE name @fuzzy=1@ 'seral NEAR/0 prt' ORDER BY id;
+	"#;
+	let mut t = Test::new(sql).await?;
+	t.expect_size(10)?;
+	t.skip_ok(5)?;
+	t.expect_val("[]")?;
+	t.expect_val("[{ id: t:1, name: '<b>SurrealDB</b> <b>Cloud</b>' }]")?;
+	t.expect_val("[{ id: t:3 }]")?;
+	// Quoted phrases are matched exactly
+	t.expect_val("[]")?;
+	t.expect_val("[{ id: t:3 }]")?;
+	Ok(())
+}
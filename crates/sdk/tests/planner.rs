@@ -3176,6 +3176,155 @@ async fn select_composite_unique_index() -> Result<(), Error> {
 	select_composite_index(true).await
 }
 
+async fn select_composite_range_index(unique: bool) -> Result<(), Error> {
+	//
+	let sql = format!(
+		"
+		DEFINE INDEX reading_idx ON TABLE reading COLUMNS device, ts {};
+		CREATE reading:a1 SET device = 'a', ts = 1, value = 50;
+		CREATE reading:a2 SET device = 'a', ts = 2, value = 40;
+		CREATE reading:a3 SET device = 'a', ts = 3, value = 30;
+		CREATE reading:a4 SET device = 'a', ts = 4.5, value = 20;
+		CREATE reading:a5 SET device = 'a', ts = 5, value = 10;
+		CREATE reading:b1 SET device = 'b', ts = 1, value = 5;
+		CREATE reading:b2 SET device = 'b', ts = 2, value = 4;
+		CREATE reading:b3 SET device = 'b', ts = 3, value = 3;
+		SELECT id FROM reading WHERE device = 'a' AND ts > 2 AND ts <= 5 EXPLAIN;
+		SELECT id, ts FROM reading WHERE device = 'a' AND ts > 2 AND ts <= 5 ORDER BY ts;
+		SELECT id, ts FROM reading WHERE device = 'a' AND 4 > ts ORDER BY ts;
+		SELECT id, ts FROM reading WHERE device = 'a' AND ts >= 3 ORDER BY ts LIMIT 2;
+		SELECT id, ts FROM reading WHERE device = 'b' ORDER BY ts LIMIT 2 EXPLAIN;
+		SELECT id, ts FROM reading WHERE device = 'b' ORDER BY ts LIMIT 2;
+		SELECT id, value FROM reading WHERE device = 'a' AND ts > 1 ORDER BY value LIMIT 1;
+	",
+		if unique {
+			"UNIQUE"
+		} else {
+			""
+		}
+	);
+	let mut t = Test::new(&sql).await?;
+	//
+	t.expect_size(16)?;
+	t.skip_ok(9)?;
+	//
+	t.expect_vals(&[
+		"[
+			{
+				detail: {
+					plan: {
+						from: {
+							inclusive: false,
+							value: 2
+						},
+						index: 'reading_idx',
+						prefix: 'a',
+						to: {
+							inclusive: true,
+							value: 5
+						}
+					},
+					table: 'reading'
+				},
+				operation: 'Iterate Index'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			}
+		]",
+		"[{ id: reading:a3, ts: 3 }, { id: reading:a4, ts: 4.5f }, { id: reading:a5, ts: 5 }]",
+		"[{ id: reading:a1, ts: 1 }, { id: reading:a2, ts: 2 }, { id: reading:a3, ts: 3 }]",
+		"[{ id: reading:a3, ts: 3 }, { id: reading:a4, ts: 4.5f }]",
+		"[
+			{
+				detail: {
+					plan: {
+						index: 'reading_idx',
+						operator: '=',
+						value: 'b'
+					},
+					table: 'reading'
+				},
+				operation: 'Iterate Index'
+			},
+			{
+				detail: {
+					type: 'MemoryOrdered'
+				},
+				operation: 'Collector'
+			}
+		]",
+		"[{ id: reading:b1, ts: 1 }, { id: reading:b2, ts: 2 }]",
+		"[{ id: reading:a5, value: 10 }]",
+	])?;
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_composite_range_standard_index() -> Result<(), Error> {
+	select_composite_range_index(false).await
+}
+
+#[tokio::test]
+async fn select_composite_range_unique_index() -> Result<(), Error> {
+	select_composite_range_index(true).await
+}
+
+#[tokio::test]
+async fn select_composite_range_index_secondary_order() -> Result<(), Error> {
+	let sql = "
+		DEFINE INDEX item_ab ON TABLE item COLUMNS a, b;
+		CREATE item:1 SET a = 1, b = 11, c = 3;
+		CREATE item:2 SET a = 1, b = 12, c = 2;
+		CREATE item:3 SET a = 1, b = 13, c = 1;
+		CREATE item:4 SET a = 1, b = 5, c = 0;
+		CREATE item:5 SET a = 2, b = 20, c = 0;
+		SELECT id, a, c FROM item WHERE a = 1 AND b > 10 ORDER BY a, c LIMIT 2;
+		SELECT id, a, c FROM item WHERE a = 1 AND b > 10 ORDER BY c, a LIMIT 2;
+		SELECT id, a, b FROM item WHERE a = 1 AND b > 10 ORDER BY a, b LIMIT 2;
+	";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(9)?;
+	t.skip_ok(6)?;
+	t.expect_vals(&[
+		"[{ id: item:3, a: 1, c: 1 }, { id: item:2, a: 1, c: 2 }]",
+		"[{ id: item:3, a: 1, c: 1 }, { id: item:2, a: 1, c: 2 }]",
+		"[{ id: item:1, a: 1, b: 11 }, { id: item:2, a: 1, b: 12 }]",
+	])?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_composite_range_index_time_series() -> Result<(), Error> {
+	let sql = "
+		DEFINE INDEX device_ts ON TABLE metric COLUMNS device, ts;
+		CREATE metric:1 SET device = device:1, ts = d'2024-01-01T00:00:00Z';
+		CREATE metric:2 SET device = device:2, ts = d'2024-01-01T00:01:00Z';
+		CREATE metric:3 SET device = device:1, ts = d'2024-01-01T00:02:00Z';
+		CREATE metric:4 SET device = device:1, ts = d'2024-01-01T00:03:00Z';
+		CREATE metric:5 SET device = device:1, ts = d'2024-01-01T00:04:00Z';
+		SELECT id, ts FROM metric WHERE device = device:1 AND ts >= d'2024-01-01T00:01:00Z' ORDER BY ts LIMIT 2;
+		SELECT id, ts FROM metric WHERE device = device:1 AND ts < d'2024-01-01T00:03:00Z' ORDER BY ts START 1 LIMIT 2;
+	";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(8)?;
+	t.skip_ok(6)?;
+	t.expect_vals(&[
+		"[
+			{ id: metric:3, ts: d'2024-01-01T00:02:00Z' },
+			{ id: metric:4, ts: d'2024-01-01T00:03:00Z' }
+		]",
+		"[
+			{ id: metric:3, ts: d'2024-01-01T00:02:00Z' }
+		]",
+	])?;
+	Ok(())
+}
+
 #[tokio::test]
 async fn select_where_index_boolean_behaviour() -> Result<(), Error> {
 	let sql = r"
@@ -3320,3 +3469,153 @@ async fn select_limit_start_parallel() -> Result<(), Error> {
 	}
 	Ok(())
 }
+
+#[tokio::test]
+async fn select_where_spatial_index() -> Result<(), Error> {
+	let sql = r"
+		DEFINE INDEX location ON place FIELDS location SPATIAL PRECISION 7;
+		CREATE place:london SET location = (-0.1275, 51.50722);
+		CREATE place:paris SET location = (2.3522, 48.8566);
+		CREATE place:new_york SET location = (-73.9857, 40.7484);
+		CREATE place:thames SET location = { type: 'LineString', coordinates: [[-0.5, 51.4], [0.5, 51.5]] };
+		CREATE place:unknown;
+		LET $europe = { type: 'Polygon', coordinates: [[[-10.0, 35.0], [30.0, 35.0], [30.0, 60.0], [-10.0, 60.0], [-10.0, 35.0]]] };
+		SELECT id FROM place WHERE location INSIDE $europe EXPLAIN;
+		SELECT VALUE id FROM place WHERE location INSIDE $europe ORDER BY id;
+		SELECT VALUE id FROM place WHERE location INTERSECTS $europe ORDER BY id;
+		SELECT VALUE id FROM place WHERE $europe INTERSECTS location ORDER BY id;
+		SELECT id FROM place WHERE location OUTSIDE $europe EXPLAIN;
+		SELECT VALUE id FROM place WHERE location OUTSIDE $europe ORDER BY id;
+		UPDATE place:paris SET location = (-74.006, 40.7128);
+		SELECT VALUE id FROM place WHERE location INSIDE $europe ORDER BY id;
+		DELETE place:london;
+		SELECT VALUE id FROM place WHERE location INSIDE $europe ORDER BY id;
+	";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(17)?;
+	t.skip_ok(7)?;
+	t.expect_val(
+		"[
+			{
+				detail: {
+					plan: {
+						index: 'location',
+						operator: 'INSIDE',
+						value: {
+							type: 'Polygon',
+							coordinates: [[[-10.0, 35.0], [30.0, 35.0], [30.0, 60.0], [-10.0, 60.0], [-10.0, 35.0]]]
+						}
+					},
+					table: 'place',
+				},
+				operation: 'Iterate Index'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			}
+		]",
+	)?;
+	t.expect_val("[place:london, place:paris, place:thames]")?;
+	t.expect_val("[place:london, place:paris, place:thames]")?;
+	t.expect_val("[place:london, place:paris, place:thames]")?;
+	t.expect_val(
+		"[
+			{
+				detail: {
+					plan: {
+						index: 'location',
+						operator: 'OUTSIDE',
+						value: {
+							type: 'Polygon',
+							coordinates: [[[-10.0, 35.0], [30.0, 35.0], [30.0, 60.0], [-10.0, 60.0], [-10.0, 35.0]]]
+						}
+					},
+					table: 'place',
+				},
+				operation: 'Iterate Index'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			}
+		]",
+	)?;
+	t.expect_val("[place:new_york, place:unknown]")?;
+	t.skip_ok(1)?;
+	t.expect_val("[place:london, place:thames]")?;
+	t.skip_ok(1)?;
+	t.expect_val("[place:thames]")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_order_by_spatial_index_distance() -> Result<(), Error> {
+	let sql = r"
+		DEFINE INDEX location ON place FIELDS location SPATIAL;
+		CREATE place:london SET location = (-0.1275, 51.50722);
+		CREATE place:paris SET location = (2.3522, 48.8566);
+		CREATE place:new_york SET location = (-73.9857, 40.7484);
+		CREATE place:thames SET location = { type: 'LineString', coordinates: [[-0.5, 51.4], [0.5, 51.5]] };
+		SELECT id, geo::distance(location, (2.2945, 48.8584)) AS dist FROM place ORDER BY dist LIMIT 3 EXPLAIN;
+		SELECT VALUE id FROM (SELECT id, geo::distance(location, (2.2945, 48.8584)) AS dist FROM place ORDER BY dist LIMIT 3);
+		SELECT VALUE id FROM (SELECT id, geo::distance((-70.0, 40.0), location) AS dist FROM place ORDER BY dist LIMIT 2);
+	";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(8)?;
+	t.skip_ok(5)?;
+	t.expect_val(
+		"[
+			{
+				detail: {
+					plan: {
+						index: 'location',
+						operator: 'Nearest',
+						value: (2.2945, 48.8584)
+					},
+					table: 'place',
+				},
+				operation: 'Iterate Index'
+			},
+			{
+				detail: {
+					type: 'MemoryOrdered'
+				},
+				operation: 'Collector'
+			}
+		]",
+	)?;
+	t.expect_val("[place:thames, place:paris, place:london]")?;
+	t.expect_val("[place:thames, place:new_york]")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_order_by_spatial_index_distance_matches_table_scan() -> Result<(), Error> {
+	let sql = r"
+		DEFINE INDEX location ON point FIELDS location SPATIAL PRECISION 9;
+		FOR $i IN 0..600 {
+			CREATE point SET location = type::point([($i * 37 % 360) - 179.5, ($i * 13 % 170) - 84.75]);
+		};
+		FOR $i IN 0..200 {
+			CREATE point SET location = type::point([2.35 + $i * 0.00001, 48.85 + $i * 0.000003]);
+		};
+		SELECT VALUE id FROM (SELECT id, geo::distance(location, (2.3522, 48.8566)) AS dist FROM point ORDER BY dist LIMIT 20);
+		SELECT VALUE id FROM (SELECT id, geo::distance(location, (2.3522, 48.8566)) AS dist FROM point WITH NOINDEX ORDER BY dist LIMIT 20);
+		SELECT VALUE id FROM (SELECT id, geo::distance(location, (150.0, -30.0)) AS dist FROM point ORDER BY dist LIMIT 20);
+		SELECT VALUE id FROM (SELECT id, geo::distance(location, (150.0, -30.0)) AS dist FROM point WITH NOINDEX ORDER BY dist LIMIT 20);
+	";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(7)?;
+	t.skip_ok(3)?;
+	for _ in 0..2 {
+		let with_index = t.next_value()?;
+		let without_index = t.next_value()?;
+		assert_eq!(with_index, without_index);
+	}
+	Ok(())
+}
@@ -0,0 +1,177 @@
+mod parse;
+use parse::Parse;
+mod helpers;
+use helpers::{new_ds, Test};
+use std::collections::HashSet;
+use std::sync::Arc;
+use surrealdb::dbs::Session;
+use surrealdb::err::Error;
+use surrealdb::sql::Value;
+
+#[tokio::test]
+async fn define_sequence_nextval() -> Result<(), Error> {
+	let sql = "
+		DEFINE SEQUENCE order_no BATCH 10 START 1000;
+		sequence::nextval('order_no');
+		sequence::nextval('order_no');
+		CREATE order:1 SET number = sequence::nextval('order_no');
+		SELECT VALUE number FROM order;
+		INFO FOR DB;
+	";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(6)?;
+	t.skip_ok(1)?;
+	t.expect_val("1000")?;
+	t.expect_val("1001")?;
+	t.skip_ok(1)?;
+	t.expect_val("[1002]")?;
+	t.expect_val(
+		"{
+			accesses: {},
+			analyzers: {},
+			configs: {},
+			functions: {},
+			models: {},
+			params: {},
+			sequences: { order_no: 'DEFINE SEQUENCE order_no BATCH 10 START 1000' },
+			tables: { order: 'DEFINE TABLE order TYPE ANY SCHEMALESS PERMISSIONS NONE' },
+			users: {},
+		}",
+	)?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_sequence_errors() -> Result<(), Error> {
+	let sql = "
+		sequence::nextval('missing');
+		DEFINE SEQUENCE seq;
+		DEFINE SEQUENCE seq;
+		DEFINE SEQUENCE IF NOT EXISTS seq START 50;
+		sequence::nextval('seq');
+		REMOVE SEQUENCE seq;
+		REMOVE SEQUENCE seq;
+		REMOVE SEQUENCE IF EXISTS seq;
+	";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(8)?;
+	t.expect_error("The sequence 'missing' does not exist")?;
+	t.skip_ok(1)?;
+	t.expect_error("The sequence 'seq' already exists")?;
+	t.skip_ok(1)?;
+	t.expect_val("0")?;
+	t.skip_ok(1)?;
+	t.expect_error("The sequence 'seq' does not exist")?;
+	t.skip_ok(1)?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn sequence_values_survive_restart() -> Result<(), Error> {
+	let sql = "
+		DEFINE SEQUENCE seq BATCH 100;
+		sequence::nextval('seq');
+		sequence::nextval('seq');
+	";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(3)?;
+	t.skip_ok(1)?;
+	t.expect_val("0")?;
+	t.expect_val("1")?;
+	// The batch reserved before the restart is never handed out again
+	let mut t = t.restart("sequence::nextval('seq'); sequence::nextval('seq');").await?;
+	t.expect_size(2)?;
+	t.expect_val("100")?;
+	t.expect_val("101")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn sequence_overwrite_does_not_reuse_values() -> Result<(), Error> {
+	let sql = "
+		DEFINE SEQUENCE seq BATCH 5;
+		sequence::nextval('seq');
+		DEFINE SEQUENCE OVERWRITE seq BATCH 5 START 1;
+		sequence::nextval('seq');
+		DEFINE SEQUENCE OVERWRITE seq BATCH 5 START 1000;
+		sequence::nextval('seq');
+		REMOVE SEQUENCE seq;
+		DEFINE SEQUENCE seq BATCH 5;
+		sequence::nextval('seq');
+	";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(9)?;
+	t.skip_ok(1)?;
+	t.expect_val("0")?;
+	t.skip_ok(1)?;
+	t.expect_val("5")?;
+	t.skip_ok(1)?;
+	t.expect_val("1000")?;
+	t.skip_ok(2)?;
+	t.expect_val("0")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn sequence_negative_start() -> Result<(), Error> {
+	let sql = "
+		DEFINE SEQUENCE seq BATCH 2 START -5;
+		sequence::nextval('seq');
+		sequence::nextval('seq');
+		sequence::nextval('seq');
+		DEFINE SEQUENCE OVERWRITE seq BATCH 2 START -10;
+		sequence::nextval('seq');
+	";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(6)?;
+	t.skip_ok(1)?;
+	t.expect_val("-5")?;
+	t.expect_val("-4")?;
+	t.expect_val("-3")?;
+	t.skip_ok(1)?;
+	// The values reserved before the redefinition are not handed out again
+	t.expect_val("-1")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn sequence_concurrent_nextval_is_unique() -> Result<(), Error> {
+	let dbs = Arc::new(new_ds().await?);
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute("DEFINE SEQUENCE seq BATCH 7", &ses, None).await?;
+	res.remove(0).result?;
+	// Request values from many concurrent queries
+	let mut tasks = Vec::new();
+	for _ in 0..20 {
+		let dbs = dbs.clone();
+		let ses = ses.clone();
+		tasks.push(tokio::spawn(async move {
+			let sql =
+				"[sequence::nextval('seq'), sequence::nextval('seq'), sequence::nextval('seq')]";
+			let res = &mut dbs.execute(sql, &ses, None).await?;
+			res.remove(0).result
+		}));
+	}
+	let mut values = HashSet::new();
+	for task in tasks {
+		let Value::Array(arr) = task.await.unwrap()? else {
+			panic!("Expected an array");
+		};
+		// Values are increasing within a single query
+		assert!(arr.windows(2).all(|w| w[0] < w[1]), "{arr}");
+		for v in arr {
+			let Value::Number(n) = v else {
+				panic!("Expected a number");
+			};
+			assert!(values.insert(n.as_int()), "Duplicate sequence value {n}");
+		}
+	}
+	assert_eq!(values.len(), 60);
+	// All the values come from a gapless range, as all the batches were exhausted in order
+	assert_eq!(values.iter().max(), Some(&59));
+	let res = &mut dbs.execute("sequence::nextval('seq')", &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("60");
+	assert_eq!(tmp, val);
+	Ok(())
+}
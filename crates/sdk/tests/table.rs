@@ -2,7 +2,7 @@ mod parse;
 use parse::Parse;
 mod helpers;
 use crate::helpers::skip_ok;
-use helpers::new_ds;
+use helpers::{new_ds, Test};
 use surrealdb::dbs::Session;
 use surrealdb::err::Error;
 use surrealdb::sql::Value;
@@ -328,3 +328,204 @@ async fn define_foreign_table_with_no_cond_and_group_max() -> Result<(), Error>
 async fn define_foreign_table_with_no_cond_and_group_sum() -> Result<(), Error> {
 	define_foreign_table_group(false, "math::sum(value)").await
 }
+
+#[tokio::test]
+async fn define_foreign_table_through_record_links() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE person SCHEMALESS;
+		DEFINE TABLE post SCHEMALESS;
+		DEFINE FIELD author ON post TYPE record<person>;
+		DEFINE TABLE post_view AS SELECT title, author.name AS author FROM post;
+		CREATE person:tobie SET name = 'Tobie';
+		CREATE person:jaime SET name = 'Jaime';
+		CREATE post:one SET title = 'Hello', author = person:tobie;
+		SELECT * FROM post_view;
+		UPDATE person:tobie SET name = 'Tobias';
+		SELECT * FROM post_view;
+		UPDATE post:one SET author = person:jaime;
+		UPDATE person:tobie SET name = 'Tobie';
+		SELECT * FROM post_view;
+		UPDATE person:jaime SET name = 'Jaime M';
+		SELECT * FROM post_view;
+		DELETE person:jaime;
+		SELECT * FROM post_view;
+		INFO FOR TABLE person;
+	";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(18)?;
+	t.skip_ok(7)?;
+	t.expect_val("[{ author: 'Tobie', id: post_view:one, title: 'Hello' }]")?;
+	t.skip_ok(1)?;
+	t.expect_val("[{ author: 'Tobias', id: post_view:one, title: 'Hello' }]")?;
+	t.skip_ok(2)?;
+	t.expect_val("[{ author: 'Jaime', id: post_view:one, title: 'Hello' }]")?;
+	t.skip_ok(1)?;
+	t.expect_val("[{ author: 'Jaime M', id: post_view:one, title: 'Hello' }]")?;
+	t.skip_ok(1)?;
+	t.expect_val("[{ id: post_view:one, title: 'Hello' }]")?;
+	t.expect_val(
+		"{
+			events: {},
+			fields: {},
+			indexes: {},
+			lives: {},
+			tables: {
+				post_view: 'DEFINE TABLE post_view TYPE ANY SCHEMALESS AS SELECT title, author.name AS author FROM post PERMISSIONS NONE'
+			},
+		}",
+	)?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_foreign_table_through_untyped_record_links() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE person SCHEMALESS;
+		DEFINE TABLE post SCHEMALESS;
+		DEFINE TABLE post_view AS SELECT title, author.name AS author FROM post;
+		DEFINE FIELD OVERWRITE author ON post TYPE record;
+		DEFINE TABLE person_posts AS SELECT ->?.title AS titles FROM person;
+		DEFINE FIELD OVERWRITE author ON post TYPE option<record<person>>;
+		DEFINE TABLE OVERWRITE post_view AS SELECT title, author.name AS author FROM post;
+		INFO FOR TABLE person;
+	";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(8)?;
+	t.skip_ok(3)?;
+	t.expect_error(
+		"Unable to define the view `post_view`, as the `post.author` field does not specify the tables which it links to",
+	)?;
+	t.expect_error(
+		"Unable to define the view `person_posts`, as the `->?.title` field does not specify the tables which it links to",
+	)?;
+	t.skip_ok(2)?;
+	t.expect_val(
+		"{
+			events: {},
+			fields: {},
+			indexes: {},
+			lives: {},
+			tables: {
+				post_view: 'DEFINE TABLE post_view TYPE ANY SCHEMALESS AS SELECT title, author.name AS author FROM post PERMISSIONS NONE'
+			},
+		}",
+	)?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_foreign_table_through_graph_edges() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE person SCHEMALESS;
+		DEFINE TABLE person_posts AS SELECT name, ->wrote->post.title AS titles FROM person;
+		CREATE person:tobie SET name = 'Tobie';
+		CREATE post:one SET title = 'One';
+		SELECT * FROM person_posts;
+		RELATE person:tobie->wrote->post:one;
+		SELECT * FROM person_posts;
+		UPDATE post:one SET title = 'First';
+		SELECT * FROM person_posts;
+		DELETE wrote;
+		SELECT * FROM person_posts;
+		REMOVE TABLE person_posts;
+		INFO FOR TABLE post;
+	";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(13)?;
+	t.skip_ok(4)?;
+	t.expect_val("[{ id: person_posts:tobie, name: 'Tobie', titles: [] }]")?;
+	t.skip_ok(1)?;
+	t.expect_val("[{ id: person_posts:tobie, name: 'Tobie', titles: ['One'] }]")?;
+	t.skip_ok(1)?;
+	t.expect_val("[{ id: person_posts:tobie, name: 'Tobie', titles: ['First'] }]")?;
+	t.skip_ok(1)?;
+	t.expect_val("[{ id: person_posts:tobie, name: 'Tobie', titles: [] }]")?;
+	t.skip_ok(1)?;
+	t.expect_val(
+		"{
+			events: {},
+			fields: {},
+			indexes: {},
+			lives: {},
+			tables: {},
+		}",
+	)?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_foreign_table_async() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE person CHANGEFEED 1h INCLUDE ORIGINAL;
+		CREATE person:one SET age = 20;
+		DEFINE TABLE person_by_age AS SELECT count() AS total, age FROM person GROUP BY age ASYNC;
+		SELECT * FROM person_by_age;
+		CREATE person:two SET age = 20;
+		UPDATE person:one SET age = 30;
+		SELECT * FROM person_by_age;
+	";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(7)?;
+	t.skip_ok(3)?;
+	t.expect_val("[{ age: 20, id: person_by_age:[20], total: 1 }]")?;
+	t.skip_ok(2)?;
+	t.expect_val("[{ age: 20, id: person_by_age:[20], total: 1 }]")?;
+	// The view is updated from the change feed
+	t.ds.changefeed_process().await?;
+	let mut t = Test::new_ds(t.ds, "SELECT * FROM person_by_age; DELETE person:two;").await?;
+	t.expect_val(
+		"[
+			{ age: 20, id: person_by_age:[20], total: 1 },
+			{ age: 30, id: person_by_age:[30], total: 1 },
+		]",
+	)?;
+	t.skip_ok(1)?;
+	t.ds.changefeed_process().await?;
+	let mut t = Test::new_ds(t.ds, "SELECT * FROM person_by_age").await?;
+	t.expect_val("[{ age: 30, id: person_by_age:[30], total: 1 }]")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_foreign_table_async_keeps_changefeed() -> Result<(), Error> {
+	let ds = new_ds().await?;
+	ds.changefeed_process_at(0).await?;
+	let sql = "
+		DEFINE TABLE person CHANGEFEED 1h;
+		DEFINE TABLE person_view AS
+			SELECT name, (IF age < 0 { THROW 'Invalid age' } ELSE { age }) AS age FROM person ASYNC;
+		CREATE person:one SET name = 'One', age = -1;
+	";
+	let mut t = Test::new_ds(ds, sql).await?;
+	t.expect_size(3)?;
+	t.skip_ok(3)?;
+	// The view fails to apply the change
+	t.ds.changefeed_process_at(1).await?;
+	// The change is not removed once the change feed expires
+	t.ds.changefeed_process_at(3602).await?;
+	let mut t = Test::new_ds(t.ds, "SHOW CHANGES FOR TABLE person SINCE 0").await?;
+	let val = t.next_value()?;
+	assert!(val.to_string().contains("person:one"), "changes: {val}");
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_foreign_table_async_requires_changefeed() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE person;
+		DEFINE TABLE person_view AS SELECT * FROM person ASYNC;
+		DEFINE TABLE OVERWRITE person CHANGEFEED 1h;
+		DEFINE TABLE person_by_age AS SELECT count(), age FROM person GROUP BY age ASYNC;
+		DEFINE TABLE person_view AS SELECT * FROM person ASYNC;
+	";
+	let mut t = Test::new(sql).await?;
+	t.expect_size(5)?;
+	t.skip_ok(1)?;
+	t.expect_error(
+		"Unable to define the ASYNC view `person_view`, as the `person` table has no change feed",
+	)?;
+	t.skip_ok(1)?;
+	t.expect_error("Unable to define the ASYNC view `person_by_age`, as the `person` table has no change feed which includes original values")?;
+	t.skip_ok(1)?;
+	Ok(())
+}
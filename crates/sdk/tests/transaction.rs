@@ -2,8 +2,11 @@ mod parse;
 use parse::Parse;
 mod helpers;
 use helpers::new_ds;
-use surrealdb::dbs::Session;
+use std::sync::Arc;
+use std::time::Duration;
+use surrealdb::dbs::{RetryPolicy, Session};
 use surrealdb::err::Error;
+use surrealdb::kvs::Datastore;
 use surrealdb::sql::Value;
 
 #[tokio::test]
@@ -200,3 +203,38 @@ async fn transaction_with_throw_and_return() -> Result<(), Error> {
 	//
 	Ok(())
 }
+
+async fn update_with_conflict(dbs: &Arc<Datastore>, ses: &Session) -> Result<Value, Error> {
+	// Update the record while a slower statement is updating it
+	let slow = {
+		let dbs = dbs.clone();
+		let ses = ses.clone();
+		tokio::spawn(async move {
+			let sql = "UPDATE counter:one SET count += 1, slept = sleep(100ms)";
+			dbs.execute(sql, &ses, None).await?.remove(0).result
+		})
+	};
+	tokio::time::sleep(Duration::from_millis(20)).await;
+	let res = &mut dbs.execute("UPDATE counter:one SET count += 1", ses, None).await?;
+	res.remove(0).result?;
+	slow.await.unwrap()
+}
+
+#[tokio::test]
+async fn transaction_conflict_is_retried() -> Result<(), Error> {
+	let dbs = Arc::new(new_ds().await?);
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute("CREATE counter:one SET count = 0", &ses, None).await?;
+	res.remove(0).result?;
+	// Without a retry policy the conflict is returned
+	let err = update_with_conflict(&dbs, &ses).await.unwrap_err();
+	assert!(err.is_retryable(), "{err:?}");
+	// With a retry policy the statement is executed again
+	let ses = ses.with_retry(Some(RetryPolicy::new(3).with_backoff(Duration::from_millis(1))));
+	update_with_conflict(&dbs, &ses).await?;
+	let res = &mut dbs.execute("counter:one.count", &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("3");
+	assert_eq!(tmp, val);
+	Ok(())
+}
@@ -292,6 +292,96 @@ async fn select_where_hnsw_knn() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn select_hnsw_knn_with_quantization() -> Result<(), Error> {
+	for q in ["SCALAR", "BINARY"] {
+		let sql = format!(
+			"
+			DEFINE INDEX hnsw_pts ON pts FIELDS point HNSW DIMENSION 4 DIST EUCLIDEAN TYPE F32 QUANTIZATION {q};
+			CREATE pts:1 SET point = [1,2,3,4], flag = true;
+			CREATE pts:2 SET point = [4,5,6,7], flag = false;
+			CREATE pts:3 SET point = [8,9,10,11], flag = true;
+			CREATE pts:4 SET point = [12,13,14,15], flag = true;
+			SELECT id, vector::distance::knn() AS dist FROM pts WHERE point <|2,100|> [2,3,4,5];
+			SELECT id, vector::distance::knn() AS dist FROM pts WHERE flag = true AND point <|2,100|> [2,3,4,5];
+		"
+		);
+		let mut t = Test::new(&sql).await?;
+		t.skip_ok(5)?;
+		// The candidates are rescored with the full precision vectors
+		t.expect_val(
+			"[
+				{
+					id: pts:1,
+					dist: 2f
+				},
+				{
+					id: pts:2,
+					dist: 4f
+				}
+			]",
+		)?;
+		t.expect_val(
+			"[
+				{
+					id: pts:1,
+					dist: 2f
+				},
+				{
+					id: pts:3,
+					dist: 12f
+				}
+			]",
+		)?;
+	}
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_mtree_knn_with_quantization() -> Result<(), Error> {
+	for q in ["SCALAR", "BINARY"] {
+		let sql = format!(
+			"
+			DEFINE INDEX mt_pts ON pts FIELDS point MTREE DIMENSION 4 DIST EUCLIDEAN TYPE F32 CAPACITY 2 QUANTIZATION {q};
+			CREATE pts:1 SET point = [1,2,3,4], flag = true;
+			CREATE pts:2 SET point = [4,5,6,7], flag = false;
+			CREATE pts:3 SET point = [8,9,10,11], flag = true;
+			CREATE pts:4 SET point = [12,13,14,15], flag = true;
+			SELECT id, vector::distance::knn() AS dist FROM pts WHERE point <|2|> [2,3,4,5];
+			SELECT id, vector::distance::knn() AS dist FROM pts WHERE flag = true AND point <|2|> [2,3,4,5];
+		"
+		);
+		let mut t = Test::new(&sql).await?;
+		t.skip_ok(5)?;
+		// The candidates are rescored with the full precision vectors
+		t.expect_val(
+			"[
+				{
+					id: pts:1,
+					dist: 2f
+				},
+				{
+					id: pts:2,
+					dist: 4f
+				}
+			]",
+		)?;
+		t.expect_val(
+			"[
+				{
+					id: pts:1,
+					dist: 2f
+				},
+				{
+					id: pts:3,
+					dist: 12f
+				}
+			]",
+		)?;
+	}
+	Ok(())
+}
+
 #[tokio::test]
 async fn select_mtree_knn_with_condition() -> Result<(), Error> {
 	let sql = r"
@@ -502,6 +592,130 @@ async fn select_bruteforce_knn_with_condition() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn select_hnsw_knn_with_selective_condition() -> Result<(), Error> {
+	let sql = r"
+		DEFINE INDEX hn_pt ON pts FIELDS point HNSW DIMENSION 1;
+		DEFINE INDEX ix_tenant ON pts FIELDS tenant;
+		INSERT INTO pts [
+			{ id: pts:1, point: [ 10f ], tenant: 'a' },
+			{ id: pts:2, point: [ 20f ], tenant: 'a' },
+			{ id: pts:3, point: [ 1000f ], tenant: 'a' }
+		];
+		FOR $i IN 0..1100 {
+			CREATE pts SET point = [<float> $i * 2], tenant = 'b';
+			CREATE pts SET point = [<float> $i * 2 + 1], tenant = 'c';
+		};
+		SELECT id, vector::distance::knn() AS distance FROM pts
+			WHERE point <|2,10|> [1500f] AND tenant = 'a'
+			ORDER BY distance EXPLAIN;
+		SELECT id, vector::distance::knn() AS distance FROM pts
+			WHERE point <|2,10|> [1500f] AND tenant = 'a'
+			ORDER BY distance;
+		SELECT point, tenant FROM pts WHERE tenant = 'b' AND point <|2,10|> [50.5f] EXPLAIN;
+		SELECT point, tenant, vector::distance::knn() AS distance FROM pts
+			WHERE tenant = 'b' AND point <|2,10|> [50.5f]
+			ORDER BY distance;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 8);
+	//
+	skip_ok(res, 4)?;
+	// The few records of the tenant are compared exhaustively
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+				{
+					detail: {
+						plan: {
+							index: 'hn_pt',
+							operator: '<|2,10|>',
+							prefilter: {
+								index: 'ix_tenant',
+								strategy: 'exact'
+							},
+							value: [1500f]
+						},
+						table: 'pts'
+					},
+					operation: 'Iterate Index'
+				},
+				{
+					detail: {
+						type: 'MemoryOrdered'
+					},
+					operation: 'Collector'
+				}
+			]",
+	);
+	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+				{
+					distance: 500f,
+					id: pts:3
+				},
+				{
+					distance: 1480f,
+					id: pts:2
+				}
+			]",
+	);
+	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
+	// The many records of the tenant are searched with an expanded ef
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+				{
+					detail: {
+						plan: {
+							index: 'hn_pt',
+							operator: '<|2,10|>',
+							prefilter: {
+								ef: 20,
+								index: 'ix_tenant',
+								strategy: 'ann'
+							},
+							value: [50.5f]
+						},
+						table: 'pts'
+					},
+					operation: 'Iterate Index'
+				},
+				{
+					detail: {
+						type: 'Memory'
+					},
+					operation: 'Collector'
+				}
+			]",
+	);
+	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+				{
+					distance: 0.5f,
+					point: [50f],
+					tenant: 'b'
+				},
+				{
+					distance: 1.5f,
+					point: [52f],
+					tenant: 'b'
+				}
+			]",
+	);
+	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn check_hnsw_persistence() -> Result<(), Error> {
 	let sql = r"
@@ -564,3 +778,108 @@ async fn check_hnsw_persistence() -> Result<(), Error> {
 	)?;
 	Ok(())
 }
+
+#[tokio::test]
+async fn select_hybrid_search() -> Result<(), Error> {
+	let sql = r"
+		DEFINE ANALYZER simple TOKENIZERS blank,class FILTERS lowercase;
+		DEFINE INDEX ft_content ON doc FIELDS content SEARCH ANALYZER simple BM25;
+		DEFINE INDEX hn_emb ON doc FIELDS emb HNSW DIMENSION 2;
+		CREATE doc:1 SET content = 'the quick brown fox', emb = [1, 1];
+		CREATE doc:2 SET content = 'the lazy dog', emb = [5, 5];
+		CREATE doc:3 SET content = 'a quick fox jumps over the quick dog', emb = [9, 9];
+		CREATE doc:4 SET content = 'nothing here', emb = [2, 2];
+		CREATE doc:5 SET content = 'a slow cat', emb = [3, 3];
+		CREATE doc:6 SET content = 'a sleepy cat', emb = [4, 4];
+		SELECT id, search::rrf() AS rrf, search::linear(0.5, 0.5) AS linear FROM doc
+			WHERE content @1@ 'quick' OR emb <|2,10|> [0, 0]
+			ORDER BY rrf DESC;
+		SELECT id, search::rrf(0) AS rrf FROM doc
+			WHERE content @1@ 'quick' OR emb <|2,10|> [0, 0]
+			ORDER BY rrf DESC;
+		SELECT id, search::rrf() AS rrf FROM doc:1;
+	";
+	let mut t = Test::new(sql).await?;
+	t.skip_ok(9)?;
+	// doc:1 is ranked second by the full-text search, and first by the vector search
+	t.expect_val(
+		"[
+			{
+				id: doc:1,
+				linear: 0.505f,
+				rrf: 0.03252247488101534f
+			},
+			{
+				id: doc:3,
+				linear: 0.5f,
+				rrf: 0.01639344262295082f
+			},
+			{
+				id: doc:4,
+				linear: 0.005f,
+				rrf: 0.016129032258064516f
+			}
+		]",
+	)?;
+	t.expect_val(
+		"[
+			{
+				id: doc:1,
+				rrf: 1.5f
+			},
+			{
+				id: doc:3,
+				rrf: 1f
+			},
+			{
+				id: doc:4,
+				rrf: 0.5f
+			}
+		]",
+	)?;
+	// Without any full-text or nearest neighbours operator, there is nothing to fuse
+	t.expect_val(
+		"[
+			{
+				id: doc:1,
+				rrf: NONE
+			}
+		]",
+	)?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_hybrid_search_bruteforce() -> Result<(), Error> {
+	let sql = r"
+		DEFINE ANALYZER simple TOKENIZERS blank,class FILTERS lowercase;
+		DEFINE INDEX ft_content ON doc FIELDS content SEARCH ANALYZER simple BM25;
+		CREATE doc:1 SET content = 'the quick brown fox', emb = [1, 1];
+		CREATE doc:2 SET content = 'the lazy dog', emb = [5, 5];
+		CREATE doc:3 SET content = 'a quick fox jumps over the quick dog', emb = [9, 9];
+		CREATE doc:4 SET content = 'nothing here', emb = [2, 2];
+		SELECT id, search::rrf(0) AS rrf FROM doc
+			WHERE emb <|2,EUCLIDEAN|> [0, 0] OR content @1@ 'quick'
+			ORDER BY rrf DESC, id;
+	";
+	let mut t = Test::new(sql).await?;
+	t.skip_ok(6)?;
+	// doc:1 is ranked first by both the brute force and the full-text search
+	t.expect_val(
+		"[
+			{
+				id: doc:1,
+				rrf: 2f
+			},
+			{
+				id: doc:3,
+				rrf: 0.5f
+			},
+			{
+				id: doc:4,
+				rrf: 0.5f
+			}
+		]",
+	)?;
+	Ok(())
+}
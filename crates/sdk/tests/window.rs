@@ -0,0 +1,109 @@
+mod helpers;
+mod parse;
+use crate::helpers::Test;
+use surrealdb::err::Error;
+
+#[tokio::test]
+async fn select_window_ranking() -> Result<(), Error> {
+	let sql = "
+		CREATE score:1 SET team = 'red', points = 10;
+		CREATE score:2 SET team = 'red', points = 30;
+		CREATE score:3 SET team = 'red', points = 30;
+		CREATE score:4 SET team = 'blue', points = 20;
+		CREATE score:5 SET team = 'blue', points = 5;
+		SELECT id,
+			row_number() OVER (PARTITION BY team ORDER BY points DESC) AS num,
+			rank() OVER (PARTITION BY team ORDER BY points DESC) AS rank,
+			dense_rank() OVER (PARTITION BY team ORDER BY points DESC) AS dense
+		FROM score ORDER BY id;
+		SELECT id,
+			lag(points) OVER (ORDER BY id) AS prev,
+			lead(points, 2, 0) OVER (ORDER BY id) AS next
+		FROM score ORDER BY id;
+	";
+	let mut t = Test::new(sql).await?;
+	t.skip_ok(5)?;
+	t.expect_val(
+		"[
+			{ id: score:1, num: 3, rank: 3, dense: 2 },
+			{ id: score:2, num: 1, rank: 1, dense: 1 },
+			{ id: score:3, num: 2, rank: 1, dense: 1 },
+			{ id: score:4, num: 1, rank: 1, dense: 1 },
+			{ id: score:5, num: 2, rank: 2, dense: 2 },
+		]",
+	)?;
+	t.expect_val(
+		"[
+			{ id: score:1, prev: NONE, next: 30 },
+			{ id: score:2, prev: 10, next: 20 },
+			{ id: score:3, prev: 30, next: 5 },
+			{ id: score:4, prev: 30, next: 0 },
+			{ id: score:5, prev: 20, next: 0 },
+		]",
+	)?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_window_aggregate() -> Result<(), Error> {
+	let sql = "
+		CREATE reading:1 SET device = 'a', value = 1;
+		CREATE reading:2 SET device = 'a', value = 2;
+		CREATE reading:3 SET device = 'a', value = 3;
+		CREATE reading:4 SET device = 'b', value = 10;
+		SELECT id,
+			math::sum(value) OVER (PARTITION BY device ORDER BY id) AS running,
+			math::mean(value) OVER (ORDER BY id ROWS BETWEEN 1 PRECEDING AND CURRENT ROW) AS moving,
+			count() OVER (PARTITION BY device) AS total
+		FROM reading ORDER BY id;
+		SELECT VALUE rank() OVER (ORDER BY value DESC) FROM reading;
+		RETURN rank();
+	";
+	let mut t = Test::new(sql).await?;
+	t.skip_ok(4)?;
+	t.expect_val(
+		"[
+			{ id: reading:1, running: 1, moving: 1, total: 3 },
+			{ id: reading:2, running: 3, moving: 1.5, total: 3 },
+			{ id: reading:3, running: 6, moving: 2.5, total: 3 },
+			{ id: reading:4, running: 10, moving: 6.5, total: 1 },
+		]",
+	)?;
+	t.expect_val("[4, 3, 2, 1]")?;
+	t.expect_error(
+		"There was a problem running the rank() function. This function can only be used with an OVER clause.",
+	)?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_window_default_frame() -> Result<(), Error> {
+	let sql = "
+		CREATE sale:1 SET day = 1, amount = 10;
+		CREATE sale:2 SET day = 2, amount = 20;
+		CREATE sale:3 SET day = 2, amount = 30;
+		CREATE sale:4 SET day = 3, amount = 40;
+		SELECT id,
+			math::sum(amount) OVER (ORDER BY day) AS running,
+			last_value(amount) OVER (ORDER BY day) AS last,
+			math::sum(amount) OVER (ORDER BY id ROWS BETWEEN 18446744073709551615 PRECEDING AND CURRENT ROW) AS rows
+		FROM sale ORDER BY id;
+	";
+	let mut t = Test::new(sql).await?;
+	t.skip_ok(4)?;
+	t.expect_val(
+		"[
+			{ id: sale:1, running: 10, last: 10, rows: 10 },
+			{ id: sale:2, running: 60, last: 30, rows: 30 },
+			{ id: sale:3, running: 60, last: 30, rows: 60 },
+			{ id: sale:4, running: 100, last: 40, rows: 100 },
+		]",
+	)?;
+	Ok(())
+}
+
+#[test]
+fn select_window_with_group() {
+	let sql = "SELECT count() OVER (ORDER BY value) AS total FROM reading GROUP BY total";
+	assert!(surrealdb::sql::parse(sql).is_err());
+}
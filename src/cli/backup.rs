@@ -0,0 +1,112 @@
+use crate::cli::abstraction::auth::{CredentialsBuilder, CredentialsLevel};
+use crate::cli::abstraction::{
+	AuthArguments, DatabaseConnectionArguments, DatabaseSelectionArguments,
+};
+use crate::err::Error;
+use clap::Args;
+use std::path::{Path, PathBuf};
+use surrealdb::engine::any::{connect, IntoEndpoint};
+use surrealdb::kvs::backup::Header;
+use tokio::fs::File;
+use tokio::io::{AsyncBufReadExt, BufReader};
+
+#[derive(Args, Debug)]
+pub struct BackupCommandArguments {
+	#[arg(help = "Path to the file to write the backup to")]
+	#[arg(index = 1)]
+	file: String,
+	#[arg(
+		help = "Only back up the changes after this versionstamp, as recorded by a previous backup"
+	)]
+	#[arg(long, conflicts_with = "base")]
+	since: Option<u64>,
+	#[arg(help = "Only back up the changes after this previous backup file")]
+	#[arg(long, value_parser = super::validator::file_exists)]
+	base: Option<PathBuf>,
+	#[command(flatten)]
+	conn: DatabaseConnectionArguments,
+	#[command(flatten)]
+	auth: AuthArguments,
+	#[command(flatten)]
+	sel: DatabaseSelectionArguments,
+}
+
+pub async fn init(
+	BackupCommandArguments {
+		file,
+		since,
+		base,
+		conn: DatabaseConnectionArguments {
+			endpoint,
+		},
+		auth: AuthArguments {
+			username,
+			password,
+			token,
+			auth_level,
+		},
+		sel: DatabaseSelectionArguments {
+			namespace,
+			database,
+		},
+	}: BackupCommandArguments,
+) -> Result<(), Error> {
+	// Continue from the versionstamp of the previous backup
+	let since = match base {
+		Some(path) => Some(header(&path).await?.until),
+		None => since,
+	};
+	// If username and password are specified, and we are connecting to a remote SurrealDB server, then we need to authenticate.
+	// If we are connecting directly to a datastore (i.e. surrealkv://local.skv or tikv://...), then we don't need to authenticate because we use an embedded (local) SurrealDB instance with auth disabled.
+	let client = if username.is_some()
+		&& password.is_some()
+		&& !endpoint.clone().into_endpoint()?.parse_kind()?.is_local()
+	{
+		debug!("Connecting to the database engine with authentication");
+		let creds = CredentialsBuilder::default()
+			.with_username(username.as_deref())
+			.with_password(password.as_deref())
+			.with_namespace(namespace.as_str())
+			.with_database(database.as_str());
+
+		let client = connect(endpoint).await?;
+
+		debug!("Signing in to the database engine at '{:?}' level", auth_level);
+		match auth_level {
+			CredentialsLevel::Root => client.signin(creds.root()?).await?,
+			CredentialsLevel::Namespace => client.signin(creds.namespace()?).await?,
+			CredentialsLevel::Database => client.signin(creds.database()?).await?,
+		};
+
+		client
+	} else if token.is_some() && !endpoint.clone().into_endpoint()?.parse_kind()?.is_local() {
+		let client = connect(endpoint).await?;
+		client.authenticate(token.unwrap()).await?;
+
+		client
+	} else {
+		debug!("Connecting to the database engine without authentication");
+		connect(endpoint).await?
+	};
+
+	// Use the specified namespace / database
+	client.use_ns(namespace).use_db(database).await?;
+	// Back up the database
+	debug!("Backing up the database");
+	match since {
+		Some(since) => client.backup(&file).since(since).await?,
+		None => client.backup(&file).await?,
+	}
+	let until = header(Path::new(&file)).await?.until;
+	info!("The backup was written successfully, up to versionstamp {until}");
+	// Everything OK
+	Ok(())
+}
+
+/// Reads the header line of a backup file
+pub(super) async fn header(path: &Path) -> Result<Header, Error> {
+	let file = File::open(path).await?;
+	let mut line = Vec::new();
+	BufReader::new(file).read_until(b'\n', &mut line).await?;
+	Ok(Header::from_line(&line)?)
+}
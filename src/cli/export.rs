@@ -5,15 +5,38 @@ use crate::cli::abstraction::{
 use crate::err::Error;
 use clap::Args;
 use futures_util::StreamExt;
-use surrealdb::engine::any::{connect, IntoEndpoint};
+use surrealdb::engine::any::{connect, Any, IntoEndpoint};
+use surrealdb::kvs::export::Format;
+use surrealdb::method::{Export, ExportConfig};
+use surrealdb::opt::KeyRange;
 use tokio::io::{self, AsyncWriteExt};
 
 #[derive(Args, Debug)]
 pub struct ExportCommandArguments {
-	#[arg(help = "Path to the SurrealQL file to export. Use dash - to write into stdout.")]
+	#[arg(help = "Path to the file to export. Use dash - to write into stdout.")]
 	#[arg(default_value = "-")]
 	#[arg(index = 1)]
 	file: String,
+	#[arg(help = "The format of the export: surrealql, jsonl, csv or parquet")]
+	#[arg(long, default_value = "surrealql", value_parser = super::validator::data_format)]
+	format: Format,
+	#[arg(help = "Only export these tables. Formats other than surrealql need exactly one table")]
+	#[arg(long = "table")]
+	tables: Vec<String>,
+	#[arg(
+		help = "Only export the records of a table which match a condition, in the form table:condition"
+	)]
+	#[arg(long = "where", value_parser = super::validator::table_condition)]
+	conditions: Vec<(String, String)>,
+	#[arg(help = "Only export the records of a table in a record id range, such as user:100..200")]
+	#[arg(long = "range", value_parser = super::validator::record_range)]
+	ranges: Vec<(String, KeyRange)>,
+	#[arg(help = "Only export the definitions of the database and its tables")]
+	#[arg(long, conflicts_with = "data_only")]
+	schema_only: bool,
+	#[arg(help = "Only export the records of the tables")]
+	#[arg(long)]
+	data_only: bool,
 	#[command(flatten)]
 	conn: DatabaseConnectionArguments,
 	#[command(flatten)]
@@ -25,6 +48,12 @@ pub struct ExportCommandArguments {
 pub async fn init(
 	ExportCommandArguments {
 		file,
+		format,
+		tables,
+		conditions,
+		ranges,
+		schema_only,
+		data_only,
 		conn: DatabaseConnectionArguments {
 			endpoint,
 		},
@@ -77,9 +106,21 @@ pub async fn init(
 	client.use_ns(namespace).use_db(database).await?;
 	// Export the data from the database
 	debug!("Exporting data from the database");
+	// Select the tables and records to export
+	let selection = Selection {
+		tables,
+		conditions,
+		ranges,
+		schema_only,
+		data_only,
+	};
+	let selected = selection.is_any();
 	if file == "-" {
 		// Prepare the backup
-		let mut backup = client.export(()).await?;
+		let mut backup = match (format, selected) {
+			(Format::Surrealql, false) => client.export(()).await?,
+			_ => configure(client.export(()).with_config(), format, &selection).await?,
+		};
 		// Get a handle to standard output
 		let mut stdout = io::stdout();
 		// Write the backup to standard output
@@ -87,9 +128,51 @@ pub async fn init(
 			stdout.write_all(&bytes?).await?;
 		}
 	} else {
-		client.export(file).await?;
+		match (format, selected) {
+			(Format::Surrealql, false) => client.export(file).await?,
+			_ => configure(client.export(file).with_config(), format, &selection).await?,
+		}
 	}
-	info!("The SurrealQL file was exported successfully");
+	info!("The {format} file was exported successfully");
 	// Everything OK
 	Ok(())
 }
+
+/// The tables and records selected for export
+struct Selection {
+	tables: Vec<String>,
+	conditions: Vec<(String, String)>,
+	ranges: Vec<(String, KeyRange)>,
+	schema_only: bool,
+	data_only: bool,
+}
+
+impl Selection {
+	/// Check if anything other than the whole database is selected
+	fn is_any(&self) -> bool {
+		!self.tables.is_empty()
+			|| !self.conditions.is_empty()
+			|| !self.ranges.is_empty()
+			|| self.schema_only
+			|| self.data_only
+	}
+}
+
+/// Applies the export format and the selection of tables and records
+fn configure<'r, R>(
+	export: Export<'r, Any, R, ExportConfig>,
+	format: Format,
+	sel: &Selection,
+) -> Export<'r, Any, R, ExportConfig> {
+	let mut export = export.format(format).schema(!sel.data_only).records(!sel.schema_only);
+	if !sel.tables.is_empty() {
+		export = export.tables(sel.tables.clone());
+	}
+	for (table, cond) in sel.conditions.iter() {
+		export = export.condition(table, cond);
+	}
+	for (table, range) in sel.ranges.iter() {
+		export = export.range(table, range.clone());
+	}
+	export
+}
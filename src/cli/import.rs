@@ -5,13 +5,23 @@ use crate::cli::abstraction::{
 use crate::err::Error;
 use clap::Args;
 use surrealdb::engine::any::{connect, IntoEndpoint};
+use surrealdb::kvs::export::Format;
 use surrealdb::opt::{capabilities::Capabilities, Config};
 
 #[derive(Args, Debug)]
 pub struct ImportCommandArguments {
-	#[arg(help = "Path to the SurrealQL file to import")]
+	#[arg(help = "Path to the file to import")]
 	#[arg(index = 1)]
 	file: String,
+	#[arg(help = "The format of the import: surrealql, jsonl, csv or parquet")]
+	#[arg(long, default_value = "surrealql", value_parser = super::validator::data_format)]
+	format: Format,
+	#[arg(help = "The table to import records into, for formats other than surrealql")]
+	#[arg(long)]
+	table: Option<String>,
+	#[arg(help = "Renames a field of the imported records, in the form source:target")]
+	#[arg(long = "map", value_parser = super::validator::field_mapping)]
+	mapping: Vec<(String, String)>,
 	#[command(flatten)]
 	conn: DatabaseConnectionArguments,
 	#[command(flatten)]
@@ -23,6 +33,9 @@ pub struct ImportCommandArguments {
 pub async fn init(
 	ImportCommandArguments {
 		file,
+		format,
+		table,
+		mapping,
 		conn: DatabaseConnectionArguments {
 			endpoint,
 		},
@@ -76,8 +89,20 @@ pub async fn init(
 	// Use the specified namespace / database
 	client.use_ns(namespace).use_db(database).await?;
 	// Import the data into the database
-	client.import(file).await?;
-	info!("The SurrealQL file was imported successfully");
+	match format {
+		Format::Surrealql => client.import(file).await?,
+		_ => {
+			let mut import = client.import(file).with_config().format(format);
+			if let Some(table) = table {
+				import = import.table(table);
+			}
+			for (source, target) in mapping {
+				import = import.map(source, target);
+			}
+			import.await?
+		}
+	}
+	info!("The {format} file was imported successfully");
 	// All ok
 	Ok(())
 }
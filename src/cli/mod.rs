@@ -1,10 +1,12 @@
 pub(crate) mod abstraction;
+mod backup;
 mod config;
 mod export;
 mod fix;
 mod import;
 mod isready;
 mod ml;
+mod restore;
 mod sql;
 mod start;
 #[cfg(test)]
@@ -22,6 +24,7 @@ use crate::cli::version_client::VersionClient;
 use crate::cnf::DEBUG_BUILD_WARNING;
 use crate::cnf::{LOGO, PKG_VERSION};
 use crate::env::RELEASE;
+use backup::BackupCommandArguments;
 use clap::{Parser, Subcommand};
 pub use config::CF;
 use export::ExportCommandArguments;
@@ -29,6 +32,7 @@ use fix::FixCommandArguments;
 use import::ImportCommandArguments;
 use isready::IsReadyCommandArguments;
 use ml::MlCommand;
+use restore::RestoreCommandArguments;
 use semver::Version;
 use sql::SqlCommandArguments;
 use start::StartCommandArguments;
@@ -76,10 +80,10 @@ struct Cli {
 enum Commands {
 	#[command(about = "Start the database server")]
 	Start(StartCommandArguments),
-	/* Not implemented yet
-	#[command(about = "Backup data to or from an existing database")]
+	#[command(about = "Back up an existing database, or the changes since a previous backup")]
 	Backup(BackupCommandArguments),
-	*/
+	#[command(about = "Restore a full backup, and any incremental backups, into a database")]
+	Restore(RestoreCommandArguments),
 	#[command(about = "Import a SurrealQL script into an existing database")]
 	Import(ImportCommandArguments),
 	#[command(about = "Export an existing database as a SurrealQL script")]
@@ -139,6 +143,8 @@ pub async fn init() -> ExitCode {
 	// After version warning we can run the respective command
 	let output = match args.command {
 		Commands::Start(args) => start::init(args).await,
+		Commands::Backup(args) => backup::init(args).await,
+		Commands::Restore(args) => restore::init(args).await,
 		Commands::Import(args) => import::init(args).await,
 		Commands::Export(args) => export::init(args).await,
 		Commands::Version(args) => version::init(args).await,
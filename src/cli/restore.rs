@@ -0,0 +1,112 @@
+use crate::cli::abstraction::auth::{CredentialsBuilder, CredentialsLevel};
+use crate::cli::abstraction::{
+	AuthArguments, DatabaseConnectionArguments, DatabaseSelectionArguments,
+};
+use crate::err::Error;
+use clap::Args;
+use std::path::PathBuf;
+use surrealdb::engine::any::{connect, IntoEndpoint};
+use surrealdb::opt::{capabilities::Capabilities, Config};
+use surrealdb::sql::Datetime;
+
+#[derive(Args, Debug)]
+pub struct RestoreCommandArguments {
+	#[arg(
+		help = "Paths to the backup files, starting with a full backup, in the order they were taken"
+	)]
+	#[arg(index = 1, required = true, value_parser = super::validator::file_exists)]
+	files: Vec<PathBuf>,
+	#[arg(help = "Only restore the changes made up to this point in time")]
+	#[arg(long, value_parser = super::validator::datetime)]
+	until: Option<Datetime>,
+	#[command(flatten)]
+	conn: DatabaseConnectionArguments,
+	#[command(flatten)]
+	auth: AuthArguments,
+	#[command(flatten)]
+	sel: DatabaseSelectionArguments,
+}
+
+pub async fn init(
+	RestoreCommandArguments {
+		files,
+		until,
+		conn: DatabaseConnectionArguments {
+			endpoint,
+		},
+		auth: AuthArguments {
+			username,
+			password,
+			token,
+			auth_level,
+		},
+		sel: DatabaseSelectionArguments {
+			namespace,
+			database,
+		},
+	}: RestoreCommandArguments,
+) -> Result<(), Error> {
+	// Check that each backup continues from the previous one
+	let mut previous: Option<u64> = None;
+	for file in files.iter() {
+		let header = super::backup::header(file).await?;
+		if header.since != previous {
+			return Err(Error::Other(match previous {
+				None => format!("The backup {} is not a full backup", file.display()),
+				Some(_) => format!(
+					"The backup {} does not continue from the previous backup",
+					file.display()
+				),
+			}));
+		}
+		previous = Some(header.until);
+	}
+	// Default datastore configuration for local engines
+	let config = Config::new().capabilities(Capabilities::all());
+	// If username and password are specified, and we are connecting to a remote SurrealDB server, then we need to authenticate.
+	// If we are connecting directly to a datastore (i.e. surrealkv://local.skv or tikv://...), then we don't need to authenticate because we use an embedded (local) SurrealDB instance with auth disabled.
+	let client = if username.is_some()
+		&& password.is_some()
+		&& !endpoint.clone().into_endpoint()?.parse_kind()?.is_local()
+	{
+		debug!("Connecting to the database engine with authentication");
+		let creds = CredentialsBuilder::default()
+			.with_username(username.as_deref())
+			.with_password(password.as_deref())
+			.with_namespace(namespace.as_str())
+			.with_database(database.as_str());
+
+		let client = connect(endpoint).await?;
+
+		debug!("Signing in to the database engine at '{:?}' level", auth_level);
+		match auth_level {
+			CredentialsLevel::Root => client.signin(creds.root()?).await?,
+			CredentialsLevel::Namespace => client.signin(creds.namespace()?).await?,
+			CredentialsLevel::Database => client.signin(creds.database()?).await?,
+		};
+
+		client
+	} else if token.is_some() && !endpoint.clone().into_endpoint()?.parse_kind()?.is_local() {
+		let client = connect(endpoint).await?;
+		client.authenticate(token.unwrap()).await?;
+
+		client
+	} else {
+		debug!("Connecting to the database engine without authentication");
+		connect((endpoint, config)).await?
+	};
+
+	// Use the specified namespace / database
+	client.use_ns(namespace).use_db(database).await?;
+	// Restore each of the backups in turn
+	for file in files {
+		debug!("Restoring the backup {}", file.display());
+		match &until {
+			Some(until) => client.restore(&file).until(until.0).await?,
+			None => client.restore(&file).await?,
+		}
+	}
+	info!("The backups were restored successfully");
+	// All ok
+	Ok(())
+}
@@ -6,6 +6,10 @@ use std::{
 };
 
 use surrealdb::dbs::capabilities::{FuncTarget, MethodTarget, NetTarget, RouteTarget, Targets};
+use surrealdb::kvs::export::Format;
+use surrealdb::opt::KeyRange;
+use surrealdb::sql::Id;
+use surrealdb::RecordIdKey;
 
 pub(crate) mod parser;
 
@@ -89,6 +93,42 @@ pub(crate) fn duration(v: &str) -> Result<Duration, String> {
 	surrealdb::sql::Duration::from_str(v).map(|d| d.0).map_err(|_| String::from("invalid duration"))
 }
 
+pub(crate) fn datetime(v: &str) -> Result<surrealdb::sql::Datetime, String> {
+	surrealdb::sql::Datetime::from_str(v).map_err(|_| String::from("invalid datetime"))
+}
+
+pub(crate) fn data_format(v: &str) -> Result<Format, String> {
+	Format::from_str(v).map_err(|e| e.to_string())
+}
+
+pub(crate) fn field_mapping(v: &str) -> Result<(String, String), String> {
+	match v.split_once(':') {
+		Some((src, dst)) if !src.is_empty() && !dst.is_empty() => {
+			Ok((src.to_owned(), dst.to_owned()))
+		}
+		_ => Err(String::from("Provide a field mapping in the form source:target")),
+	}
+}
+
+pub(crate) fn table_condition(v: &str) -> Result<(String, String), String> {
+	match v.split_once(':') {
+		Some((tb, cond)) if !tb.trim().is_empty() && !cond.trim().is_empty() => {
+			Ok((tb.trim().to_owned(), cond.trim().to_owned()))
+		}
+		_ => Err(String::from("Provide a record condition in the form table:condition")),
+	}
+}
+
+pub(crate) fn record_range(v: &str) -> Result<(String, KeyRange), String> {
+	match surrealdb::syn::thing_with_range(v).map(|v| (v.tb, v.id)) {
+		Ok((tb, Id::Range(range))) => Ok((
+			tb,
+			(range.beg.map(RecordIdKey::from_inner), range.end.map(RecordIdKey::from_inner)).into(),
+		)),
+		_ => Err(String::from("Provide a record id range, such as user:100..200")),
+	}
+}
+
 pub(crate) fn net_targets(value: &str) -> Result<Targets<NetTarget>, String> {
 	if ["*", ""].contains(&value) {
 		return Ok(Targets::All);
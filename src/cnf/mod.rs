@@ -71,6 +71,10 @@ pub static WEBSOCKET_MAX_MESSAGE_SIZE: LazyLock<usize> =
 pub static WEBSOCKET_MAX_CONCURRENT_REQUESTS: LazyLock<usize> =
 	lazy_env_parse!("SURREAL_WEBSOCKET_MAX_CONCURRENT_REQUESTS", usize, 24);
 
+/// How long, in seconds, a transaction can stay open on a WebSocket before it is cancelled (defaults to 10)
+pub static WEBSOCKET_TRANSACTION_TIMEOUT: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_WEBSOCKET_TRANSACTION_TIMEOUT", u64, 10);
+
 /// What is the number of runtime worker threads to start (defaults to the number of CPU cores)
 pub static RUNTIME_WORKER_THREADS: LazyLock<usize> =
 	lazy_env_parse_or_else!("SURREAL_RUNTIME_WORKER_THREADS", usize, |_| {
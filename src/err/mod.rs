@@ -9,6 +9,7 @@ use reqwest::Error as ReqwestError;
 use serde::Serialize;
 use std::io::Error as IoError;
 use std::string::FromUtf8Error as Utf8Error;
+use surrealdb::err::{ErrorCode, ErrorData};
 use surrealdb::error::Db as SurrealDbError;
 use surrealdb::iam::Error as SurrealIamError;
 use surrealdb::Error as SurrealError;
@@ -170,6 +171,22 @@ impl From<surrealdb::rpc::RpcError> for Error {
 	}
 }
 
+impl Error {
+	/// Returns the machine-readable description of this error, if there is one
+	pub fn data(&self) -> Option<ErrorData> {
+		let code = match self {
+			Error::Db(SurrealError::Db(e)) => return Some(e.into()),
+			Error::InvalidAuth => ErrorCode::AuthenticationFailed,
+			Error::ForbiddenRoute(_) => ErrorCode::CapabilityDenied,
+			Error::NoNamespace => ErrorCode::NoNamespace,
+			Error::NoDatabase => ErrorCode::NoDatabase,
+			Error::InvalidType | Error::OperationUnsupported => ErrorCode::Unsupported,
+			_ => return None,
+		};
+		Some(ErrorData::new(code, Default::default()))
+	}
+}
+
 impl Serialize for Error {
 	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 	where
@@ -187,10 +204,14 @@ pub(super) struct Message {
 	description: Option<String>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	information: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	data: Option<serde_json::Value>,
 }
 
 impl IntoResponse for Error {
 	fn into_response(self) -> Response {
+		// Describe the error in a machine-readable way
+		let data = self.data().map(|data| surrealdb::sql::Value::from(data).into_json());
 		match self {
 			err @ Error::InvalidAuth | err @ Error::Db(SurrealError::Db(SurrealDbError::InvalidAuth)) => (
 				StatusCode::UNAUTHORIZED,
@@ -199,6 +220,7 @@ impl IntoResponse for Error {
 					details: Some("Authentication failed".to_string()),
 					description: Some("Your authentication details are invalid. Reauthenticate using valid authentication parameters.".to_string()),
 					information: Some(err.to_string()),
+					data,
 				})
 			),
 			err @ Error::ForbiddenRoute(_) | err @ Error::Db(SurrealError::Db(SurrealDbError::IamError(SurrealIamError::NotAllowed { .. }))) => (
@@ -208,6 +230,7 @@ impl IntoResponse for Error {
 					details: Some("Forbidden".to_string()),
 					description: Some("Not allowed to do this.".to_string()),
 					information: Some(err.to_string()),
+					data,
 				})
 			),
 			Error::InvalidType => (
@@ -217,6 +240,7 @@ impl IntoResponse for Error {
 					details: Some("Unsupported media type".to_string()),
 					description: Some("The request needs to adhere to certain constraints. Refer to the documentation for supported content types.".to_string()),
 					information: None,
+					data,
 				}),
 			),
 			Error::InvalidStorage => (
@@ -226,6 +250,7 @@ impl IntoResponse for Error {
 					details: Some("Health check failed".to_string()),
 					description: Some("The database health check for this instance failed. There was an issue with the underlying storage engine.".to_string()),
 					information: Some(self.to_string()),
+					data,
 				}),
 			),
 			_ => (
@@ -235,6 +260,7 @@ impl IntoResponse for Error {
 					details: Some("Request problems detected".to_string()),
 					description: Some("There is a problem with your request. Refer to the documentation for further information.".to_string()),
 					information: Some(self.to_string()),
+					data,
 				}),
 			),
 		}.into_response()
@@ -0,0 +1,131 @@
+use super::headers::Accept;
+use super::AppState;
+use crate::cnf::HTTP_MAX_IMPORT_BODY_SIZE;
+use crate::err::Error;
+use crate::net::output;
+use axum::body::Body;
+use axum::extract::DefaultBodyLimit;
+use axum::extract::Query;
+use axum::extract::Request;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use axum::routing::get;
+use axum::{Extension, Router};
+use axum_extra::TypedHeader;
+use bytes::Bytes;
+use futures::TryStreamExt;
+use http::StatusCode;
+use serde::Deserialize;
+use surrealdb::dbs::capabilities::RouteTarget;
+use surrealdb::dbs::Session;
+use surrealdb::iam::check::check_ns_db;
+use surrealdb::iam::Action::{Edit, View};
+use surrealdb::iam::ResourceKind::Any;
+use tower_http::limit::RequestBodyLimitLayer;
+
+#[derive(Default, Deserialize, Debug, Clone)]
+struct BackupOptions {
+	/// The versionstamp of the previous backup
+	pub since: Option<u64>,
+}
+
+#[derive(Default, Deserialize, Debug, Clone)]
+struct RestoreOptions {
+	/// The point in time to restore to, in seconds since the Unix epoch
+	pub until: Option<u64>,
+}
+
+pub(super) fn router<S>() -> Router<S>
+where
+	S: Clone + Send + Sync + 'static,
+{
+	Router::new()
+		.route("/backup", get(save).post(load))
+		.route_layer(DefaultBodyLimit::disable())
+		.layer(RequestBodyLimitLayer::new(*HTTP_MAX_IMPORT_BODY_SIZE))
+}
+
+async fn load(
+	Extension(state): Extension<AppState>,
+	Extension(session): Extension<Session>,
+	accept: Option<TypedHeader<Accept>>,
+	Query(query): Query<RestoreOptions>,
+	request: Request,
+) -> Result<impl IntoResponse, impl IntoResponse> {
+	// Get the datastore reference
+	let db = &state.datastore;
+	// Check if capabilities allow querying the requested HTTP route
+	if !db.allows_http_route(&RouteTarget::Backup) {
+		warn!("Capabilities denied HTTP route request attempt, target: '{}'", &RouteTarget::Backup);
+		return Err(Error::ForbiddenRoute(RouteTarget::Backup.to_string()));
+	}
+	// Ensure a NS and DB are set
+	let (nsv, dbv) = check_ns_db(&session)?;
+	// Check the permissions level
+	db.check(&session, Edit, Any.on_db(&nsv, &dbv))?;
+	// Stream the request body
+	let body_stream = request
+		.into_body()
+		.into_data_stream()
+		.map_err(|e| surrealdb_core::err::Error::QueryStream(e.to_string()));
+	// Restore the backup into the database
+	match db.restore(&session, body_stream, query.until).await {
+		Ok(res) => match accept.as_deref() {
+			// Simple serialization
+			Some(Accept::ApplicationJson) => Ok(output::json(&output::simplify(res))),
+			Some(Accept::ApplicationCbor) => Ok(output::cbor(&output::simplify(res))),
+			Some(Accept::ApplicationPack) => Ok(output::pack(&output::simplify(res))),
+			// Return nothing
+			Some(Accept::ApplicationOctetStream) => Ok(output::none()),
+			// Internal serialization
+			Some(Accept::Surrealdb) => Ok(output::full(&res)),
+			// An incorrect content-type was requested
+			_ => Err(Error::InvalidType),
+		},
+		// There was an error when restoring the backup
+		Err(err) => Err(Error::from(err)),
+	}
+}
+
+async fn save(
+	Extension(state): Extension<AppState>,
+	Extension(session): Extension<Session>,
+	Query(query): Query<BackupOptions>,
+) -> Result<impl IntoResponse, impl IntoResponse> {
+	// Get the datastore reference
+	let db = &state.datastore;
+	// Check if capabilities allow querying the requested HTTP route
+	if !db.allows_http_route(&RouteTarget::Backup) {
+		warn!("Capabilities denied HTTP route request attempt, target: '{}'", &RouteTarget::Backup);
+		return Err(Error::ForbiddenRoute(RouteTarget::Backup.to_string()));
+	}
+	// Ensure a NS and DB are set
+	let (nsv, dbv) = check_ns_db(&session)?;
+	// Check the permissions level
+	db.check(&session, View, Any.on_db(&nsv, &dbv))?;
+	// Create a chunked response
+	let (chn, body_stream) = surrealdb::channel::bounded::<Result<Bytes, Error>>(1);
+	let body = Body::from_stream(body_stream);
+	// Create a new bounded channel
+	let (snd, rcv) = surrealdb::channel::bounded(1);
+	// Start the backup task
+	let task = db.backup(&session, snd, query.since).await?;
+	// Spawn a new backup job
+	let task = tokio::spawn(task);
+	// Process all backup data
+	tokio::spawn(async move {
+		while let Ok(v) = rcv.recv().await {
+			let _ = chn.send(Ok(Bytes::from(v))).await;
+		}
+		// Abort the response if the job failed, so it is not mistaken for a complete backup
+		let err = match task.await {
+			Ok(Ok(())) => return,
+			Ok(Err(e)) => Error::from(e),
+			Err(e) => Error::Other(e.to_string()),
+		};
+		warn!("Backup job failed: {err}");
+		let _ = chn.send(Err(err)).await;
+	});
+	// Return the chunked body
+	Ok(Response::builder().status(StatusCode::OK).body(body).unwrap())
+}
@@ -8,9 +8,11 @@ use axum::response::IntoResponse;
 use axum::routing::options;
 use axum::Router;
 use axum::{response::Response, Extension};
+use axum_extra::extract::Query;
 use axum_extra::TypedHeader;
 use bytes::Bytes;
 use http::StatusCode;
+use serde::Deserialize;
 use surrealdb::dbs::capabilities::RouteTarget;
 use surrealdb::dbs::Session;
 use surrealdb::iam::check::check_ns_db;
@@ -19,6 +21,12 @@ use surrealdb::iam::ResourceKind::Any;
 use surrealdb::kvs::export;
 use surrealdb::rpc::format::Format;
 
+#[derive(Default, Deserialize, Debug, Clone)]
+struct ExportOptions {
+	pub format: Option<String>,
+	pub table: Option<String>,
+}
+
 pub(super) fn router<S>() -> Router<S>
 where
 	S: Clone + Send + Sync + 'static,
@@ -29,8 +37,16 @@ where
 async fn get_handler(
 	Extension(state): Extension<AppState>,
 	Extension(session): Extension<Session>,
+	Query(options): Query<ExportOptions>,
 ) -> Result<impl IntoResponse, Error> {
-	let cfg = export::Config::default();
+	let mut cfg = export::Config::default();
+	// Export the records of a single table
+	if let Some(format) = options.format {
+		cfg.format = format.parse()?;
+	}
+	if let Some(table) = options.table {
+		cfg.tables = vec![table].into();
+	}
 	handle_inner(state, session, cfg).await
 }
 
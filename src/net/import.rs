@@ -9,14 +9,50 @@ use axum::response::IntoResponse;
 use axum::routing::post;
 use axum::Extension;
 use axum::Router;
+use axum_extra::extract::Query;
 use axum_extra::TypedHeader;
 use futures::TryStreamExt;
+use serde::Deserialize;
 use surrealdb::dbs::capabilities::RouteTarget;
 use surrealdb::dbs::Session;
 use surrealdb::iam::Action::Edit;
 use surrealdb::iam::ResourceKind::Any;
+use surrealdb::kvs::import;
 use tower_http::limit::RequestBodyLimitLayer;
 
+#[derive(Default, Deserialize, Debug, Clone)]
+struct ImportOptions {
+	pub format: Option<String>,
+	pub table: Option<String>,
+	#[serde(default)]
+	pub map: Vec<String>,
+}
+
+impl TryFrom<ImportOptions> for import::Config {
+	type Error = surrealdb::err::Error;
+	fn try_from(opts: ImportOptions) -> Result<Self, Self::Error> {
+		let mut cfg = import::Config {
+			table: opts.table,
+			..Default::default()
+		};
+		if let Some(format) = opts.format {
+			cfg.format = format.parse()?;
+		}
+		for map in opts.map {
+			match map.split_once(':') {
+				Some((src, dst)) => cfg.mapping.push((src.to_owned(), dst.to_owned())),
+				None => {
+					return Err(surrealdb::err::Error::InvalidImportConfig(
+						map.into(),
+						"a field mapping in the form source:target".into(),
+					))
+				}
+			}
+		}
+		Ok(cfg)
+	}
+}
+
 pub(super) fn router<S>() -> Router<S>
 where
 	S: Clone + Send + Sync + 'static,
@@ -31,6 +67,7 @@ async fn handler(
 	Extension(state): Extension<AppState>,
 	Extension(session): Extension<Session>,
 	accept: Option<TypedHeader<Accept>>,
+	Query(options): Query<ImportOptions>,
 	request: Request,
 ) -> Result<impl IntoResponse, impl IntoResponse> {
 	// Get the datastore reference
@@ -42,6 +79,8 @@ async fn handler(
 	}
 	// Check the permissions level
 	db.check(&session, Edit, Any.on_level(session.au.level().to_owned()))?;
+	// Parse the import options
+	let cfg = import::Config::try_from(options)?;
 
 	let body_stream = request
 		.into_body()
@@ -49,7 +88,7 @@ async fn handler(
 		.map_err(|e| surrealdb_core::err::Error::QueryStream(e.to_string()));
 
 	// Execute the sql query in the database
-	match db.import_stream(&session, body_stream).await {
+	match db.import_stream_with_config(&session, body_stream, cfg).await {
 		Ok(res) => match accept.as_deref() {
 			// Simple serialization
 			Some(Accept::ApplicationJson) => Ok(output::json(&output::simplify(res))),
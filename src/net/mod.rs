@@ -1,4 +1,5 @@
 mod auth;
+mod backup;
 pub mod client_ip;
 mod export;
 #[cfg(surrealdb_unstable)]
@@ -159,6 +160,8 @@ pub async fn init(ds: Arc<Datastore>, ct: CancellationToken) -> Result<(), Error
 				.max_age(Duration::from_secs(86400)),
 		);
 
+	let rpc_state = Arc::new(RpcState::new());
+
 	let axum_app = Router::<Arc<RpcState>>::new()
 		// Redirect until we provide a UI
 		.route("/", get(|| async { Redirect::temporary(cnf::APP_ENDPOINT) }))
@@ -166,6 +169,7 @@ pub async fn init(ds: Arc<Datastore>, ct: CancellationToken) -> Result<(), Error
 		.merge(health::router())
 		.merge(export::router())
 		.merge(import::router())
+		.merge(backup::router())
 		.merge(rpc::router())
 		.merge(version::router())
 		.merge(sync::router())
@@ -179,7 +183,7 @@ pub async fn init(ds: Arc<Datastore>, ct: CancellationToken) -> Result<(), Error
 		#[cfg(surrealdb_unstable)]
 		{
 			warn!("❌🔒IMPORTANT: GraphQL is a pre-release feature with known security flaws. This is not recommended for production use.🔒❌");
-			axum_app.merge(gql::router(ds.clone()).await)
+			axum_app.merge(gql::router(ds.clone(), rpc_state.gql_subscriptions.clone()).await)
 		}
 		#[cfg(not(surrealdb_unstable))]
 		{
@@ -195,8 +199,6 @@ pub async fn init(ds: Arc<Datastore>, ct: CancellationToken) -> Result<(), Error
 	// Get a new server handler
 	let handle = Handle::new();
 
-	let rpc_state = Arc::new(RpcState::new());
-
 	// Setup the graceful shutdown handler
 	let shutdown_handler = graceful_shutdown(rpc_state.clone(), ct.clone(), handle.clone());
 
@@ -9,10 +9,14 @@ use crate::err::Error;
 use crate::rpc::connection::Connection;
 use crate::rpc::format::HttpFormat;
 use crate::rpc::post_context::PostRpcContext;
+use crate::rpc::response::DetailedResponse;
 use crate::rpc::response::IntoRpcResponse;
+use crate::rpc::response::{success, Response};
 use crate::rpc::RpcState;
+use axum::body::Body;
 use axum::extract::DefaultBodyLimit;
 use axum::extract::State;
+use axum::response::Response as AxumResponse;
 use axum::routing::options;
 use axum::{
 	extract::ws::{WebSocket, WebSocketUpgrade},
@@ -22,14 +26,20 @@ use axum::{
 use axum_extra::headers::Header;
 use axum_extra::TypedHeader;
 use bytes::Bytes;
-use http::header::SEC_WEBSOCKET_PROTOCOL;
+use http::header::{CONTENT_TYPE, SEC_WEBSOCKET_PROTOCOL};
 use http::HeaderMap;
+use http::HeaderValue;
+use surrealdb::channel;
 use surrealdb::dbs::Session;
+use surrealdb::headers::RPC_REVISION;
 use surrealdb::kvs::Datastore;
 use surrealdb::mem::ALLOC;
 use surrealdb::rpc::format::Format;
+use surrealdb::rpc::format::DETAILED_PROTOCOL;
 use surrealdb::rpc::format::PROTOCOLS;
 use surrealdb::rpc::method::Method;
+use surrealdb::rpc::RpcError;
+use surrealdb::sql::Array;
 use tower_http::limit::RequestBodyLimitLayer;
 use tower_http::request_id::RequestId;
 use uuid::Uuid;
@@ -38,6 +48,9 @@ use super::headers::Accept;
 use super::headers::ContentType;
 use super::AppState;
 
+/// The RPC revision which HTTP clients request to receive error details
+const DETAILED_RPC_REVISION: &str = "2";
+
 use surrealdb::dbs::capabilities::RouteTarget;
 use surrealdb::rpc::rpc_context::RpcContext;
 
@@ -132,14 +145,14 @@ async fn handle_socket(
 	id: Uuid,
 ) {
 	// Check if there is a WebSocket protocol specified
-	let format = match ws.protocol().and_then(|h| h.to_str().ok()) {
+	let (format, detailed) = match ws.protocol().and_then(|h| h.to_str().ok()) {
 		// Any selected protocol will always be a valie value
-		Some(protocol) => protocol.into(),
+		Some(protocol) => (protocol.into(), protocol == DETAILED_PROTOCOL),
 		// No protocol format was specified
-		_ => Format::None,
+		_ => (Format::None, false),
 	};
 	// Create a new connection instance
-	let rpc = Connection::new(datastore, state, id, sess, format);
+	let rpc = Connection::new(datastore, state, id, sess, format, detailed);
 	// Serve the socket connection requests
 	Connection::serve(rpc, ws).await;
 }
@@ -149,6 +162,7 @@ async fn post_handler(
 	Extension(session): Extension<Session>,
 	accept: Option<TypedHeader<Accept>>,
 	content_type: TypedHeader<ContentType>,
+	headers: HeaderMap,
 	body: Bytes,
 ) -> Result<impl IntoResponse, impl IntoResponse> {
 	// Get the datastore reference
@@ -183,11 +197,94 @@ async fn post_handler(
 		Ok(req) => {
 			// Parse the request RPC method type
 			let method = Method::parse(req.method);
+			// Only include the error details if the client negotiated them
+			let detailed = matches!(fmt, Format::Bincode | Format::Revision)
+				&& headers.get(&RPC_REVISION).is_some_and(|v| v == DETAILED_RPC_REVISION);
+			// Streamed queries send the records in the response body
+			if method == Method::QueryStream && req.session.is_none() {
+				let mut res = stream(rpc, fmt, detailed, req.txn, req.params);
+				if detailed {
+					res.headers_mut()
+						.insert(&RPC_REVISION, HeaderValue::from_static(DETAILED_RPC_REVISION));
+				}
+				return Ok(res);
+			}
 			// Execute the specified method
-			let res = rpc.execute_mutable(method, req.params).await;
+			// Attached sessions are only supported on WebSockets
+			let res = match req.session {
+				Some(_) => Err(RpcError::BadSessionConfig),
+				None => rpc.execute_mutable(req.txn, method, req.params).await,
+			};
 			// Return the HTTP response
-			fmt.res_http(res.into_response(None)).map_err(Error::from)
+			let res = res.into_response(None);
+			match detailed {
+				true => {
+					let mut res = fmt.res_http(DetailedResponse::from(res)).map_err(Error::from)?;
+					res.headers_mut()
+						.insert(&RPC_REVISION, HeaderValue::from_static(DETAILED_RPC_REVISION));
+					Ok(res)
+				}
+				false => fmt.res_http(res).map_err(Error::from),
+			}
 		}
 		Err(err) => Err(Error::from(err)),
 	}
 }
+
+/// Executes a streamed query, sending the batches of records in the response
+/// body as they are selected, followed by the results of the query set.
+///
+/// Each message in the body is prefixed with its length, as a big-endian
+/// 32-bit integer.
+fn stream(
+	mut rpc: PostRpcContext,
+	fmt: Format,
+	detailed: bool,
+	txn: Option<Uuid>,
+	params: Array,
+) -> AxumResponse {
+	// Create a chunked response
+	let (out, body) = channel::bounded::<Result<Bytes, Error>>(1);
+	// Create a channel for the selected records
+	let (chn, rcv) = channel::bounded(1);
+	rpc.records = Some(chn);
+	let streams = rpc.streams.clone();
+	// Execute the query, sending each batch of records as a message
+	tokio::spawn(async move {
+		let run = async {
+			let res = rpc.execute_immutable(txn, Method::QueryStream, params).await;
+			// No more records are sent once the query set has finished
+			rpc.records = None;
+			res
+		};
+		let send = async {
+			while let Ok(records) = rcv.recv().await {
+				let id = records.stream;
+				let msg = message(fmt, detailed, success(None, records)).map_err(Error::from);
+				if out.send(msg).await.is_err() {
+					// The client disconnected, so the query is stopped
+					streams.cancel(&id).await;
+					break;
+				}
+			}
+		};
+		let (res, _) = futures::join!(run, send);
+		let msg = message(fmt, detailed, res.into_response(None)).map_err(Error::from);
+		let _ = out.send(msg).await;
+	});
+	// Return the chunked body
+	([(CONTENT_TYPE, HeaderValue::from(ContentType::from(&fmt)))], Body::from_stream(body))
+		.into_response()
+}
+
+/// Serializes a response as a length-prefixed message of a response body
+fn message(fmt: Format, detailed: bool, res: Response) -> Result<Bytes, RpcError> {
+	let res = match detailed {
+		true => fmt.res(DetailedResponse::from(res)),
+		false => fmt.res(res),
+	}?;
+	let mut out = Vec::with_capacity(res.len() + 4);
+	out.extend_from_slice(&(res.len() as u32).to_be_bytes());
+	out.extend_from_slice(&res);
+	Ok(Bytes::from(out))
+}
@@ -5,20 +5,29 @@ use crate::err::Error;
 use crate::net::input::bytes_to_utf8;
 use crate::net::output;
 use crate::net::params::Params;
+use axum::body::Body;
 use axum::extract::ws::Message;
 use axum::extract::ws::WebSocket;
 use axum::extract::DefaultBodyLimit;
 use axum::extract::Query;
 use axum::extract::WebSocketUpgrade;
 use axum::response::IntoResponse;
+use axum::response::Response;
 use axum::routing::options;
 use axum::Extension;
 use axum::Router;
 use axum_extra::TypedHeader;
 use bytes::Bytes;
 use futures::{SinkExt, StreamExt};
+use http::header::{HeaderValue, CONTENT_TYPE};
+use serde_json::json;
+use serde_json::Value as JsonValue;
+use std::collections::BTreeMap;
+use std::sync::Arc;
 use surrealdb::dbs::capabilities::RouteTarget;
-use surrealdb::dbs::Session;
+use surrealdb::dbs::{QueryChunk, Session};
+use surrealdb::kvs::Datastore;
+use surrealdb::sql::Value;
 use tower_http::limit::RequestBodyLimitLayer;
 
 pub(super) fn router<S>() -> Router<S>
@@ -47,15 +56,26 @@ async fn post_handler(
 	}
 	// Convert the received sql query
 	let sql = bytes_to_utf8(&sql)?;
+	// Stream the query results if requested
+	if let Some(Accept::ApplicationNdjson) = output.as_deref() {
+		let ast = surrealdb::syn::parse(sql)?;
+		return Ok(stream(state.datastore, session, ast, params.0.parse()));
+	}
 	// Execute the received sql query
 	match db.execute(sql, &session, params.0.parse().into()).await {
 		Ok(res) => match output.as_deref() {
 			// Simple serialization
-			Some(Accept::ApplicationJson) => Ok(output::json(&output::simplify(res))),
-			Some(Accept::ApplicationCbor) => Ok(output::cbor(&output::simplify(res))),
-			Some(Accept::ApplicationPack) => Ok(output::pack(&output::simplify(res))),
+			Some(Accept::ApplicationJson) => {
+				Ok(output::json(&output::simplify(res)).into_response())
+			}
+			Some(Accept::ApplicationCbor) => {
+				Ok(output::cbor(&output::simplify(res)).into_response())
+			}
+			Some(Accept::ApplicationPack) => {
+				Ok(output::pack(&output::simplify(res)).into_response())
+			}
 			// Internal serialization
-			Some(Accept::Surrealdb) => Ok(output::full(&res)),
+			Some(Accept::Surrealdb) => Ok(output::full(&res).into_response()),
 			// An incorrect content-type was requested
 			_ => Err(Error::InvalidType),
 		},
@@ -64,6 +84,62 @@ async fn post_handler(
 	}
 }
 
+/// Executes a query set, sending the results as newline-delimited JSON.
+///
+/// The records selected by a statement are sent in batches, as lines with
+/// the statement index and the `records`, before the line with the statement
+/// response. An `error` line is sent if the query set could not be executed.
+fn stream(
+	db: Arc<Datastore>,
+	session: Session,
+	ast: surrealdb::sql::Query,
+	vars: BTreeMap<String, Value>,
+) -> Response {
+	// Create a chunked response
+	let (chn, body_stream) = surrealdb::channel::bounded::<Result<Bytes, Error>>(1);
+	let body = Body::from_stream(body_stream);
+	// Create a new bounded channel
+	let (snd, rcv) = surrealdb::channel::bounded(1);
+	// Execute the query, sending each chunk as a line
+	tokio::spawn(async move {
+		let run = db.process_chunked(ast, &session, Some(vars), snd);
+		let out = chn.clone();
+		// The receiver is moved into the sender, so that it is dropped once
+		// the client disconnects, and the query then stops executing
+		let send = async move {
+			while let Ok(chunk) = rcv.recv().await {
+				let line = match chunk {
+					QueryChunk::Records(query, records) => json!({
+						"query": query,
+						"records": output::simplify(records),
+					}),
+					QueryChunk::Response(query, response) => {
+						let mut line = output::simplify(response);
+						line["query"] = query.into();
+						line
+					}
+					_ => continue,
+				};
+				if out.send(Ok(ndjson(&line))).await.is_err() {
+					break;
+				}
+			}
+		};
+		if let (Err(err), _) = futures::join!(run, send) {
+			let _ = chn.send(Ok(ndjson(&json!({ "error": err.to_string() })))).await;
+		}
+	});
+	// Return the chunked body
+	([(CONTENT_TYPE, HeaderValue::from(Accept::ApplicationNdjson))], body).into_response()
+}
+
+/// Serializes a value as a line of newline-delimited JSON
+fn ndjson(line: &JsonValue) -> Bytes {
+	let mut out = serde_json::to_vec(line).unwrap_or_default();
+	out.push(b'\n');
+	Bytes::from(out)
+}
+
 async fn get_handler(
 	ws: WebSocketUpgrade,
 	Extension(state): Extension<AppState>,
@@ -1,20 +1,49 @@
+use super::headers::Accept;
+use super::AppState;
+use crate::cnf::HTTP_MAX_IMPORT_BODY_SIZE;
 use crate::err::Error;
+use crate::net::output;
+use axum::body::Body;
+use axum::extract::DefaultBodyLimit;
+use axum::extract::Query;
 use axum::response::IntoResponse;
+use axum::response::Response;
 use axum::routing::get;
 use axum::{Extension, Router};
+use axum_extra::TypedHeader;
+use bytes::Bytes;
+use http::StatusCode;
+use serde::Deserialize;
 use surrealdb::dbs::capabilities::RouteTarget;
+use surrealdb::dbs::Session;
+use surrealdb::iam::check::check_ns_db;
+use surrealdb::iam::Action::{Edit, View};
+use surrealdb::iam::ResourceKind::Any;
+use tower_http::limit::RequestBodyLimitLayer;
 
-use super::AppState;
+#[derive(Default, Deserialize, Debug, Clone)]
+struct SyncOptions {
+	/// The versionstamp from which to stream changes
+	pub since: Option<u64>,
+	/// Only stream changes for this table
+	pub table: Option<String>,
+}
 
 pub(super) fn router<S>() -> Router<S>
 where
 	S: Clone + Send + Sync + 'static,
 {
-	Router::new().route("/sync", get(save).post(load))
+	Router::new()
+		.route("/sync", get(save).post(load))
+		.route_layer(DefaultBodyLimit::disable())
+		.layer(RequestBodyLimitLayer::new(*HTTP_MAX_IMPORT_BODY_SIZE))
 }
 
 async fn load(
 	Extension(state): Extension<AppState>,
+	Extension(session): Extension<Session>,
+	accept: Option<TypedHeader<Accept>>,
+	body: Bytes,
 ) -> Result<impl IntoResponse, impl IntoResponse> {
 	// Get the datastore reference
 	let db = &state.datastore;
@@ -23,12 +52,33 @@ async fn load(
 		warn!("Capabilities denied HTTP route request attempt, target: '{}'", &RouteTarget::Sync);
 		return Err(Error::ForbiddenRoute(RouteTarget::Sync.to_string()));
 	}
-
-	Ok("Load")
+	// Ensure a NS and DB are set
+	let (nsv, dbv) = check_ns_db(&session)?;
+	// Check the permissions level
+	db.check(&session, Edit, Any.on_db(&nsv, &dbv))?;
+	// Apply the replicated changes to the database
+	match db.sync_import(&session, &body).await {
+		Ok(res) => match accept.as_deref() {
+			// Simple serialization
+			Some(Accept::ApplicationJson) => Ok(output::json(&output::simplify(res))),
+			Some(Accept::ApplicationCbor) => Ok(output::cbor(&output::simplify(res))),
+			Some(Accept::ApplicationPack) => Ok(output::pack(&output::simplify(res))),
+			// Return nothing
+			Some(Accept::ApplicationOctetStream) => Ok(output::none()),
+			// Internal serialization
+			Some(Accept::Surrealdb) => Ok(output::full(&res)),
+			// An incorrect content-type was requested
+			_ => Err(Error::InvalidType),
+		},
+		// There was an error when applying the changes
+		Err(err) => Err(Error::from(err)),
+	}
 }
 
 async fn save(
 	Extension(state): Extension<AppState>,
+	Extension(session): Extension<Session>,
+	Query(query): Query<SyncOptions>,
 ) -> Result<impl IntoResponse, impl IntoResponse> {
 	// Get the datastore reference
 	let db = &state.datastore;
@@ -37,6 +87,33 @@ async fn save(
 		warn!("Capabilities denied HTTP route request attempt, target: '{}'", &RouteTarget::Sync);
 		return Err(Error::ForbiddenRoute(RouteTarget::Sync.to_string()));
 	}
-
-	Ok("Save")
+	// Ensure a NS and DB are set
+	let (nsv, dbv) = check_ns_db(&session)?;
+	// Check the permissions level
+	db.check(&session, View, Any.on_db(&nsv, &dbv))?;
+	// Create a chunked response
+	let (chn, body_stream) = surrealdb::channel::bounded::<Result<Bytes, Error>>(1);
+	let body = Body::from_stream(body_stream);
+	// Create a new bounded channel
+	let (snd, rcv) = surrealdb::channel::bounded(1);
+	// Start the sync task
+	let task = db.sync_export(&session, snd, query.table, query.since.unwrap_or(0)).await?;
+	// Spawn a new change feed streaming job
+	let task = tokio::spawn(task);
+	// Process all change sets
+	tokio::spawn(async move {
+		while let Ok(v) = rcv.recv().await {
+			let _ = chn.send(Ok(Bytes::from(v))).await;
+		}
+		// Abort the response if the job failed, so it is not mistaken for a complete stream
+		let err = match task.await {
+			Ok(Ok(())) => return,
+			Ok(Err(e)) => Error::from(e),
+			Err(e) => Error::Other(e.to_string()),
+		};
+		warn!("Change feed streaming job failed: {err}");
+		let _ = chn.send(Err(err)).await;
+	});
+	// Return the chunked body
+	Ok(Response::builder().status(StatusCode::OK).body(body).unwrap())
 }
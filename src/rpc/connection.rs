@@ -1,9 +1,11 @@
 use crate::cnf::{
 	PKG_NAME, PKG_VERSION, WEBSOCKET_MAX_CONCURRENT_REQUESTS, WEBSOCKET_PING_FREQUENCY,
+	WEBSOCKET_TRANSACTION_TIMEOUT,
 };
 use crate::rpc::failure::Failure;
 use crate::rpc::format::WsFormat;
-use crate::rpc::response::{failure, IntoRpcResponse};
+use crate::rpc::response::{failure, success, IntoRpcResponse};
+use crate::rpc::session_context::SessionContext;
 use crate::rpc::CONN_CLOSED_ERR;
 use crate::telemetry;
 use crate::telemetry::metrics::ws::RequestContext;
@@ -13,10 +15,11 @@ use futures_util::stream::{SplitSink, SplitStream};
 use futures_util::{SinkExt, StreamExt};
 use opentelemetry::trace::FutureExt;
 use opentelemetry::Context as TelemetryContext;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
+use std::time::Duration;
 use surrealdb::channel::{self, Receiver, Sender};
-use surrealdb::dbs::Session;
+use surrealdb::dbs::{OpenTransaction, Records, Session};
 #[cfg(surrealdb_unstable)]
 use surrealdb::gql::{Pessimistic, SchemaCache};
 use surrealdb::kvs::Datastore;
@@ -25,6 +28,8 @@ use surrealdb::rpc::format::Format;
 use surrealdb::rpc::method::Method;
 use surrealdb::rpc::Data;
 use surrealdb::rpc::RpcContext;
+use surrealdb::rpc::RpcError;
+use surrealdb::rpc::Streams;
 use surrealdb::sql::Array;
 use surrealdb::sql::Value;
 use tokio::sync::{RwLock, Semaphore};
@@ -40,13 +45,15 @@ const SERVER_OVERLOADED: &str = "The server is unable to handle the request";
 /// An error string sent when the server is gracefully shutting down
 const SERVER_SHUTTING_DOWN: &str = "The server is gracefully shutting down";
 
-use super::RpcState;
+use super::{RpcState, Sessions, Transactions};
 
 pub struct Connection {
 	/// The unique id of this WebSocket connection
 	pub(crate) id: Uuid,
 	/// The request and response format for messages
 	pub(crate) format: Format,
+	/// Whether the client negotiated the details of errors
+	pub(crate) detailed: bool,
 	/// The persistent session for this WebSocket connection
 	pub(crate) session: Session,
 	/// The system state for all RPC WebSocket connections
@@ -55,6 +62,14 @@ pub struct Connection {
 	pub(crate) datastore: Arc<Datastore>,
 	/// The persistent parameters for this WebSocket connection
 	pub(crate) vars: BTreeMap<String, Value>,
+	/// The transactions which are open on this WebSocket connection
+	pub(crate) transactions: Arc<Transactions>,
+	/// The additional sessions attached to this WebSocket connection
+	pub(crate) sessions: Sessions,
+	/// The streamed queries which are running on this WebSocket connection
+	pub(crate) streams: Arc<Streams>,
+	/// The live queries started by attached sessions, mapped to the session id
+	pub(crate) session_lqs: RwLock<HashMap<Uuid, Uuid>>,
 	/// A cancellation token called when shutting down the server
 	pub(crate) shutdown: CancellationToken,
 	/// A cancellation token for cancelling all spawned tasks
@@ -68,6 +83,26 @@ pub struct Connection {
 	pub(crate) gql_schema: SchemaCache<Pessimistic>,
 }
 
+/// The connection state which is needed to handle each WebSocket message,
+/// fetched once so that messages can be handled without the connection lock
+#[derive(Clone)]
+struct MessageContext {
+	/// The unique id of the WebSocket connection
+	id: Uuid,
+	/// The request and response format for messages
+	format: Format,
+	/// Whether the client negotiated the details of errors
+	detailed: bool,
+	/// A cancellation token called when shutting down the server
+	shutdown: CancellationToken,
+	/// A cancellation token for cancelling all spawned tasks
+	canceller: CancellationToken,
+	/// A semaphore for limiting the number of concurrent calls
+	semaphore: Arc<Semaphore>,
+	/// The streamed queries which are running on the WebSocket connection
+	streams: Arc<Streams>,
+}
+
 impl Connection {
 	/// Instantiate a new RPC
 	pub fn new(
@@ -76,6 +111,7 @@ impl Connection {
 		id: Uuid,
 		mut session: Session,
 		format: Format,
+		detailed: bool,
 	) -> Arc<RwLock<Connection>> {
 		// Enable real-time mode
 		session.rt = true;
@@ -84,8 +120,13 @@ impl Connection {
 			id,
 			state,
 			format,
+			detailed,
 			session,
 			vars: BTreeMap::new(),
+			transactions: Arc::new(Transactions::default()),
+			sessions: Sessions::default(),
+			streams: Arc::new(Streams::default()),
+			session_lqs: RwLock::default(),
 			shutdown: CancellationToken::new(),
 			canceller: CancellationToken::new(),
 			semaphore: Arc::new(Semaphore::new(*WEBSOCKET_MAX_CONCURRENT_REQUESTS)),
@@ -136,6 +177,8 @@ impl Connection {
 		trace!("WebSocket {} disconnected", id);
 		// Cleanup the live queries for this WebSocket
 		rpc.read().await.cleanup_lqs().await;
+		// Cancel the open transactions for this WebSocket
+		rpc.read().await.cleanup_txns().await;
 		// Remove this WebSocket from the list
 		state.web_sockets.write().await.remove(&id);
 		// Stop telemetry metrics for this connection
@@ -215,15 +258,25 @@ impl Connection {
 		internal_sender: Sender<Message>,
 	) {
 		// Get all required values
-		let (shutdown, canceller) = {
+		let (shutdown, canceller, mut ctx) = {
 			// Read the connection state
 			let rpc = rpc.read().await;
 			// Clone the WebSocket shutdown token
 			let shutdown = rpc.shutdown.clone();
 			// Clone the WebSocket cancellation token
 			let canceller = rpc.canceller.clone();
+			// Fetch the state for handling messages
+			let ctx = MessageContext {
+				id: rpc.id,
+				format: rpc.format,
+				detailed: rpc.detailed,
+				shutdown: shutdown.clone(),
+				canceller: canceller.clone(),
+				semaphore: rpc.semaphore.clone(),
+				streams: rpc.streams.clone(),
+			};
 			// Return the required values
-			(shutdown, canceller)
+			(shutdown, canceller, ctx)
 		};
 		// Store spawned tasks so we can wait for them
 		let mut tasks = JoinSet::new();
@@ -266,8 +319,13 @@ impl Connection {
 								// Exit out of the loop
 								break;
 							}
+							// Text messages default to the JSON format
+							if ctx.format.is_none() {
+								ctx.format = Format::Json;
+								rpc.write().await.format = Format::Json;
+							}
 							// Otherwise spawn and handle the message
-							tasks.spawn(Self::handle_message(rpc.clone(), msg, chn));
+							tasks.spawn(Self::handle_message(rpc.clone(), ctx.clone(), msg, chn));
 						}
 						Message::Binary(_) => {
 							// Clone the response sending channel
@@ -281,8 +339,13 @@ impl Connection {
 								// Exit out of the loop
 								break;
 							}
+							// Binary messages default to the Bincode format
+							if ctx.format.is_none() {
+								ctx.format = Format::Bincode;
+								rpc.write().await.format = Format::Bincode;
+							}
 							// Otherwise spawn and handle the message
-							tasks.spawn(Self::handle_message(rpc.clone(), msg, chn));
+							tasks.spawn(Self::handle_message(rpc.clone(), ctx.clone(), msg, chn));
 						}
 						Message::Close(_) => {
 							// Respond with a close message
@@ -329,40 +392,26 @@ impl Connection {
 	}
 
 	/// Handle an individual WebSocket message
-	async fn handle_message(rpc: Arc<RwLock<Connection>>, msg: Message, chn: Sender<Message>) {
+	async fn handle_message(
+		rpc: Arc<RwLock<Connection>>,
+		ctx: MessageContext,
+		msg: Message,
+		chn: Sender<Message>,
+	) {
 		// Get all required values
-		let (id, fmt, shutdown, canceller, semaphore) = {
-			// Read the connection state
-			let rpc = rpc.read().await;
-			// Fetch the connection id
-			let id = rpc.id;
-			// Fetch the connection output format
-			let format = rpc.format;
-			// Clone the WebSocket cancellation token
-			let shutdown = rpc.shutdown.clone();
-			// Clone the WebSocket cancellation token
-			let canceller = rpc.canceller.clone();
-			// Clone the request limiter
-			let semaphore = rpc.semaphore.clone();
-			// Return the required values
-			(id, format, shutdown, canceller, semaphore)
-		};
-		// Calculate the message lenght and format
-		let (len, fmt) = match msg {
-			Message::Text(ref msg) => match fmt.is_none() {
-				true => {
-					rpc.write().await.format = Format::Json;
-					(msg.len(), Format::Json)
-				}
-				false => (msg.len(), fmt),
-			},
-			Message::Binary(ref msg) => match fmt.is_none() {
-				true => {
-					rpc.write().await.format = Format::Bincode;
-					(msg.len(), Format::Bincode)
-				}
-				false => (msg.len(), fmt),
-			},
+		let MessageContext {
+			id,
+			format: fmt,
+			detailed,
+			shutdown,
+			canceller,
+			semaphore,
+			streams,
+		} = ctx;
+		// Calculate the message length
+		let len = match msg {
+			Message::Text(ref msg) => msg.len(),
+			Message::Binary(ref msg) => msg.len(),
 			_ => unreachable!(),
 		};
 		// Prepare span and otel context
@@ -395,13 +444,23 @@ impl Connection {
 						_ = canceller.cancelled() => (),
 						// Wait for the message to be processed
 						_ = async move {
+							// Acknowledgements of streamed records are processed without the
+							// connection lock, as the stream holds it while it waits for them
+							if method == Method::QueryStreamAck {
+								let res = streams.ack(req.params).await.map_err(Failure::from);
+								// Process the response
+								res.into_response(req.id)
+									.send(otel_cx.clone(), fmt, detailed, &chn)
+									.with_context(otel_cx.as_ref().clone())
+									.await;
+							}
 							// Ping messages should be responded to immediately
-							if method == Method::Ping {
+							else if method == Method::Ping {
 								// Process ping messages immediately
-								let res = Self::process_message(rpc.clone(), method, req.params).await;
+								let res = Self::process_message(rpc.clone(), req.session, req.txn, method, req.params).await;
 								// Process the response
 								res.into_response(req.id)
-									.send(otel_cx.clone(), fmt, &chn)
+									.send(otel_cx.clone(), fmt, detailed, &chn)
 									.with_context(otel_cx.as_ref().clone())
 									.await;
 							}
@@ -411,7 +470,7 @@ impl Connection {
 								if shutdown.is_cancelled() {
 									// Process the response
 									failure(req.id, Failure::custom(SERVER_SHUTTING_DOWN))
-										.send(otel_cx.clone(), fmt, &chn)
+										.send(otel_cx.clone(), fmt, detailed, &chn)
 										.with_context(otel_cx.as_ref().clone())
 										.await;
 								}
@@ -419,7 +478,7 @@ impl Connection {
 								else if ALLOC.is_beyond_threshold() {
 									// Process the response
 									failure(req.id, Failure::custom(SERVER_OVERLOADED))
-										.send(otel_cx.clone(), fmt, &chn)
+										.send(otel_cx.clone(), fmt, detailed, &chn)
 										.with_context(otel_cx.as_ref().clone())
 										.await;
 								}
@@ -431,14 +490,14 @@ impl Connection {
 									if ALLOC.is_beyond_threshold() {
 										// Process the response
 										failure(req.id, Failure::custom(SERVER_OVERLOADED))
-											.send(otel_cx.clone(), fmt, &chn)
+											.send(otel_cx.clone(), fmt, detailed, &chn)
 											.with_context(otel_cx.as_ref().clone())
 											.await;
 									} else {
 										// Process the message when the semaphore is acquired
-										Self::process_message(rpc.clone(), method, req.params).await
+										Self::process_message(rpc.clone(), req.session, req.txn, method, req.params).await
 											.into_response(req.id)
-											.send(otel_cx.clone(), fmt, &chn)
+											.send(otel_cx.clone(), fmt, detailed, &chn)
 											.with_context(otel_cx.as_ref().clone())
 											.await;
 									}
@@ -452,7 +511,7 @@ impl Connection {
 				Err(err) => {
 					// Process the response
 					failure(None, err)
-						.send(otel_cx.clone(), fmt, &chn)
+						.send(otel_cx.clone(), fmt, detailed, &chn)
 						.with_context(otel_cx.as_ref().clone())
 						.await
 				}
@@ -465,6 +524,8 @@ impl Connection {
 	/// Process a WebSocket message and generate a response
 	async fn process_message(
 		rpc: Arc<RwLock<Connection>>,
+		session: Option<Uuid>,
+		txn: Option<Uuid>,
 		method: Method,
 		params: Array,
 	) -> Result<Data, Failure> {
@@ -473,10 +534,153 @@ impl Connection {
 		if !method.is_valid() {
 			return Err(Failure::METHOD_NOT_FOUND);
 		}
+		// Execute the method on an attached session
+		if let Some(id) = session {
+			return Self::process_session_message(rpc, id, txn, method, params).await;
+		}
 		// Execute the specified method
 		match method.needs_mutability() {
-			true => rpc.write().await.execute_mutable(method, params).await.map_err(Into::into),
-			false => rpc.read().await.execute_immutable(method, params).await.map_err(Into::into),
+			true => {
+				rpc.write().await.execute_mutable(txn, method, params).await.map_err(Into::into)
+			}
+			false => {
+				rpc.read().await.execute_immutable(txn, method, params).await.map_err(Into::into)
+			}
+		}
+	}
+
+	/// Process a WebSocket message on a session attached to this WebSocket
+	async fn process_session_message(
+		rpc: Arc<RwLock<Connection>>,
+		id: Uuid,
+		txn: Option<Uuid>,
+		method: Method,
+		params: Array,
+	) -> Result<Data, Failure> {
+		match method.needs_mutability() {
+			true => {
+				// Changes to the session state are exclusive
+				let rpc = rpc.write().await;
+				let Some(state) = rpc.sessions.read().await.get(&id).cloned() else {
+					return Err(RpcError::SessionNotFound(id.to_string()).into());
+				};
+				let mut ctx = SessionContext::new(&rpc, id, state);
+				let res = ctx.execute_mutable(txn, method, params).await;
+				// Store the modified session state
+				rpc.sessions.write().await.insert(id, (ctx.session, ctx.vars));
+				res.map_err(Into::into)
+			}
+			false => {
+				let rpc = rpc.read().await;
+				let Some(state) = rpc.sessions.read().await.get(&id).cloned() else {
+					return Err(RpcError::SessionNotFound(id.to_string()).into());
+				};
+				let ctx = SessionContext::new(&rpc, id, state);
+				ctx.execute_immutable(txn, method, params).await.map_err(Into::into)
+			}
+		}
+	}
+
+	/// Cancel all of the transactions which are open on this WebSocket
+	async fn cleanup_txns(&self) {
+		for (id, (_, txn)) in self.transactions.write().await.drain() {
+			trace!("Cancelling transaction {} on websocket {}", id, self.id);
+			if let Err(err) = txn.cancel().await {
+				error!("Error cancelling transaction {}: {}", id, err);
+			}
+		}
+	}
+
+	/// Stores a transaction which is owned by a session on this WebSocket,
+	/// cancelling it if it is still open after the timeout
+	pub(crate) async fn store_session_txn(
+		&self,
+		session: Option<Uuid>,
+		id: Uuid,
+		txn: Arc<OpenTransaction>,
+	) {
+		self.transactions.write().await.insert(id, (session, txn));
+		trace!("Started transaction {} on websocket {}", id, self.id);
+		// Cancel the transaction once it times out
+		let transactions = self.transactions.clone();
+		let timeout = Duration::from_secs(*WEBSOCKET_TRANSACTION_TIMEOUT);
+		tokio::spawn(async move {
+			tokio::time::sleep(timeout).await;
+			if let Some((_, txn)) = transactions.write().await.remove(&id) {
+				trace!("Cancelling transaction {} after timing out", id);
+				if let Err(err) = txn.cancel().await {
+					error!("Error cancelling transaction {}: {}", id, err);
+				}
+			}
+		});
+	}
+
+	/// Fetches a transaction, if it is owned by the session on this WebSocket
+	pub(crate) async fn fetch_session_txn(
+		&self,
+		session: Option<Uuid>,
+		id: &Uuid,
+	) -> Option<Arc<OpenTransaction>> {
+		match self.transactions.read().await.get(id) {
+			Some((owner, txn)) if *owner == session => Some(txn.clone()),
+			_ => None,
+		}
+	}
+
+	/// Removes a transaction, if it is owned by the session on this WebSocket
+	pub(crate) async fn remove_session_txn(
+		&self,
+		session: Option<Uuid>,
+		id: &Uuid,
+	) -> Option<Arc<OpenTransaction>> {
+		let mut transactions = self.transactions.write().await;
+		match transactions.get(id) {
+			Some((owner, _)) if *owner == session => transactions.remove(id).map(|(_, txn)| txn),
+			_ => None,
+		}
+	}
+
+	/// Cancel the transactions which were started by an attached session
+	async fn cleanup_session_txns(&self, session: &Uuid) {
+		let mut gc = Vec::new();
+		// Find all transactions for this session
+		self.transactions.write().await.retain(|id, (owner, txn)| {
+			if owner.as_ref() == Some(session) {
+				gc.push((*id, txn.clone()));
+				return false;
+			}
+			true
+		});
+		// Cancel the transactions for this session
+		for (id, txn) in gc {
+			trace!("Cancelling transaction {} on websocket {}", id, self.id);
+			if let Err(err) = txn.cancel().await {
+				error!("Error cancelling transaction {}: {}", id, err);
+			}
+		}
+	}
+
+	/// Kill the live queries which were started by an attached session
+	pub(crate) async fn cleanup_session_lqs(&self, session: &Uuid) {
+		let mut gc = Vec::new();
+		// Find all live queries for this session
+		self.session_lqs.write().await.retain(|key, value| {
+			if value == session {
+				gc.push(*key);
+				return false;
+			}
+			true
+		});
+		// Unregister the live queries from this connection
+		let mut live_queries = self.state.live_queries.write().await;
+		for lqid in gc.iter() {
+			trace!("Removing live query: {}", lqid);
+			live_queries.remove(lqid);
+		}
+		drop(live_queries);
+		// Garbage collect the live queries for this session
+		if let Err(err) = self.kvs().delete_queries(gc).await {
+			error!("Error handling RPC connection: {}", err);
 		}
 	}
 
@@ -562,6 +766,81 @@ impl RpcContext for Connection {
 		}
 	}
 
+	// ------------------------------
+	// Transactions
+	// ------------------------------
+
+	/// Interactive transactions are enabled on WebSockets
+	const TXN_SUPPORT: bool = true;
+
+	/// Stores a transaction, cancelling it if it is still open after the timeout
+	async fn store_txn(&self, id: Uuid, txn: Arc<OpenTransaction>) {
+		self.store_session_txn(None, id, txn).await
+	}
+
+	/// Fetches a transaction which is open on this WebSocket
+	async fn fetch_txn(&self, id: &Uuid) -> Option<Arc<OpenTransaction>> {
+		self.fetch_session_txn(None, id).await
+	}
+
+	/// Removes a transaction which is open on this WebSocket
+	async fn remove_txn(&self, id: &Uuid) -> Option<Arc<OpenTransaction>> {
+		self.remove_session_txn(None, id).await
+	}
+
+	// ------------------------------
+	// Sessions
+	// ------------------------------
+
+	/// Multiple sessions are enabled on WebSockets
+	const SESSION_SUPPORT: bool = true;
+
+	/// Attaches a new unauthenticated session to this WebSocket
+	async fn attach_session(&self, id: Uuid) {
+		let mut session = Session::default().with_rt(true);
+		session.ip.clone_from(&self.session.ip);
+		session.or.clone_from(&self.session.or);
+		session.id.clone_from(&self.session.id);
+		self.sessions.write().await.insert(id, (session, BTreeMap::new()));
+		trace!("Attached session {} on websocket {}", id, self.id);
+	}
+
+	/// Detaches a session from this WebSocket
+	async fn detach_session(&self, id: &Uuid) -> bool {
+		if self.sessions.write().await.remove(id).is_none() {
+			return false;
+		}
+		trace!("Detached session {} on websocket {}", id, self.id);
+		// Kill the live queries for this session
+		self.cleanup_session_lqs(id).await;
+		// Cancel the transactions for this session
+		self.cleanup_session_txns(id).await;
+		true
+	}
+
+	/// Lists the sessions attached to this WebSocket
+	async fn list_sessions(&self) -> Vec<Uuid> {
+		self.sessions.read().await.keys().copied().collect()
+	}
+
+	// ------------------------------
+	// Streaming
+	// ------------------------------
+
+	/// Streamed queries are enabled on WebSockets
+	const STREAM_SUPPORT: bool = true;
+
+	/// Sends a batch of streamed records to the WebSocket
+	async fn send_records(&self, records: Records) {
+		let cx = Arc::new(TelemetryContext::current());
+		success(None, records).send(cx, self.format, self.detailed, &self.channel.0).await
+	}
+
+	/// Returns the streamed queries which are running on the WebSocket
+	fn streams(&self) -> &Streams {
+		&self.streams
+	}
+
 	// ------------------------------
 	// GraphQL
 	// ------------------------------
@@ -3,6 +3,7 @@ use revision::revisioned;
 use revision::Revisioned;
 use serde::Serialize;
 use std::borrow::Cow;
+use surrealdb::err::ErrorData;
 use surrealdb::rpc::RpcError;
 use surrealdb::sql::Value;
 
@@ -10,6 +11,9 @@ use surrealdb::sql::Value;
 pub struct Failure {
 	pub(crate) code: i64,
 	pub(crate) message: Cow<'static, str>,
+	/// The details of the error, only sent to clients which negotiated them
+	#[serde(skip)]
+	pub(crate) data: Option<ErrorData>,
 }
 
 #[revisioned(revision = 1)]
@@ -40,6 +44,56 @@ impl Revisioned for Failure {
 	}
 }
 
+/// A failure which includes the details of the error, for clients which
+/// negotiated the detailed RPC protocol
+#[derive(Clone, Debug)]
+pub struct DetailedFailure(pub(crate) Failure);
+
+#[revisioned(revision = 2)]
+#[derive(Clone, Debug, Serialize)]
+struct DetailedInner {
+	code: i64,
+	message: String,
+	#[revision(start = 2)]
+	data: Option<ErrorData>,
+}
+
+impl From<&DetailedFailure> for DetailedInner {
+	fn from(failure: &DetailedFailure) -> Self {
+		DetailedInner {
+			code: failure.0.code,
+			message: failure.0.message.as_ref().to_owned(),
+			data: failure.0.data.clone(),
+		}
+	}
+}
+
+impl Serialize for DetailedFailure {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		DetailedInner::from(self).serialize(serializer)
+	}
+}
+
+impl Revisioned for DetailedFailure {
+	fn serialize_revisioned<W: std::io::Write>(
+		&self,
+		writer: &mut W,
+	) -> Result<(), revision::Error> {
+		DetailedInner::from(self).serialize_revisioned(writer)
+	}
+
+	fn deserialize_revisioned<R: std::io::Read>(_reader: &mut R) -> Result<Self, revision::Error> {
+		unreachable!("deserialization not supported for this type")
+	}
+
+	fn revision() -> u16 {
+		2
+	}
+}
+
 impl From<&str> for Failure {
 	fn from(err: &str) -> Self {
 		Failure::custom(err.to_string())
@@ -48,12 +102,14 @@ impl From<&str> for Failure {
 
 impl From<Error> for Failure {
 	fn from(err: Error) -> Self {
-		Failure::custom(err.to_string())
+		let data = err.data();
+		Failure::custom(err.to_string()).with_data(data)
 	}
 }
 
 impl From<RpcError> for Failure {
 	fn from(err: RpcError) -> Self {
+		let data = Some(ErrorData::from(&err));
 		match err {
 			RpcError::ParseError => Failure::PARSE_ERROR,
 			RpcError::InvalidRequest => Failure::INVALID_REQUEST,
@@ -63,6 +119,7 @@ impl From<RpcError> for Failure {
 			RpcError::Thrown(_) => Failure::custom(err.to_string()),
 			_ => Failure::custom(err.to_string()),
 		}
+		.with_data(data)
 	}
 }
 
@@ -71,6 +128,7 @@ impl From<Failure> for Value {
 		map! {
 			String::from("code") => Value::from(err.code),
 			String::from("message") => Value::from(err.message.to_string()),
+			String::from("data"), if let Some(data) = err.data => Value::from(data),
 		}
 		.into()
 	}
@@ -81,26 +139,31 @@ impl Failure {
 	pub const PARSE_ERROR: Failure = Failure {
 		code: -32700,
 		message: Cow::Borrowed("Parse error"),
+		data: None,
 	};
 
 	pub const INVALID_REQUEST: Failure = Failure {
 		code: -32600,
 		message: Cow::Borrowed("Invalid Request"),
+		data: None,
 	};
 
 	pub const METHOD_NOT_FOUND: Failure = Failure {
 		code: -32601,
 		message: Cow::Borrowed("Method not found"),
+		data: None,
 	};
 
 	pub const INVALID_PARAMS: Failure = Failure {
 		code: -32602,
 		message: Cow::Borrowed("Invalid params"),
+		data: None,
 	};
 
 	pub const INTERNAL_ERROR: Failure = Failure {
 		code: -32603,
 		message: Cow::Borrowed("Internal error"),
+		data: None,
 	};
 
 	pub fn custom<S>(message: S) -> Failure
@@ -110,6 +173,13 @@ impl Failure {
 		Failure {
 			code: -32000,
 			message: message.into(),
+			data: None,
 		}
 	}
+
+	/// Attach the machine-readable description of the error
+	pub fn with_data(mut self, data: Option<ErrorData>) -> Failure {
+		self.data = data;
+		self
+	}
 }
@@ -1,12 +1,11 @@
 use crate::net::headers::{Accept, ContentType};
 use crate::rpc::failure::Failure;
-use crate::rpc::response::Response;
 use axum::extract::ws::Message;
 use axum::response::IntoResponse;
 use axum::response::Response as AxumResponse;
 use bytes::Bytes;
 use http::header::{HeaderValue, CONTENT_TYPE};
-use surrealdb::rpc::format::Format;
+use surrealdb::rpc::format::{Format, ResTrait};
 use surrealdb::rpc::request::Request;
 use surrealdb::rpc::RpcError;
 
@@ -17,6 +16,7 @@ impl From<&Accept> for Format {
 			Accept::ApplicationJson => Format::Json,
 			Accept::ApplicationCbor => Format::Cbor,
 			Accept::ApplicationPack => Format::Msgpack,
+			Accept::ApplicationNdjson => Format::Unsupported,
 			Accept::ApplicationOctetStream => Format::Unsupported,
 			Accept::Surrealdb => Format::Bincode,
 		}
@@ -51,7 +51,7 @@ impl From<&Format> for ContentType {
 
 pub trait WsFormat {
 	fn req_ws(&self, msg: Message) -> Result<Request, Failure>;
-	fn res_ws(&self, res: Response) -> Result<(usize, Message), Failure>;
+	fn res_ws(&self, res: impl ResTrait) -> Result<(usize, Message), Failure>;
 }
 
 impl WsFormat for Format {
@@ -60,7 +60,7 @@ impl WsFormat for Format {
 		self.req(val).map_err(Into::into)
 	}
 
-	fn res_ws(&self, res: Response) -> Result<(usize, Message), Failure> {
+	fn res_ws(&self, res: impl ResTrait) -> Result<(usize, Message), Failure> {
 		let res = self.res(res).map_err(Failure::from)?;
 		if matches!(self, Format::Json) {
 			// If this has significant performance overhead it could be replaced with unsafe { String::from_utf8_unchecked(res) }
@@ -74,7 +74,7 @@ impl WsFormat for Format {
 
 pub trait HttpFormat {
 	fn req_http(&self, body: Bytes) -> Result<Request, RpcError>;
-	fn res_http(&self, res: Response) -> Result<AxumResponse, RpcError>;
+	fn res_http(&self, res: impl ResTrait) -> Result<AxumResponse, RpcError>;
 }
 
 impl HttpFormat for Format {
@@ -82,7 +82,7 @@ impl HttpFormat for Format {
 		self.req(body).map_err(Into::into)
 	}
 
-	fn res_http(&self, res: Response) -> Result<AxumResponse, RpcError> {
+	fn res_http(&self, res: impl ResTrait) -> Result<AxumResponse, RpcError> {
 		let res = self.res(res)?;
 		if matches!(self, Format::Json) {
 			// If this has significant performance overhead it could be replaced with unsafe { String::from_utf8_unchecked(res) }
@@ -3,15 +3,20 @@ pub mod failure;
 pub mod format;
 pub mod post_context;
 pub mod response;
+pub mod session_context;
 
 use crate::rpc::connection::Connection;
 use crate::rpc::response::success;
 use crate::telemetry::metrics::ws::NotificationContext;
 use opentelemetry::Context as TelemetryContext;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 use std::time::Duration;
+use surrealdb::dbs::{OpenTransaction, Session};
+#[cfg(surrealdb_unstable)]
+use surrealdb::gql::Subscriptions;
 use surrealdb::kvs::Datastore;
+use surrealdb::sql::Value;
 use tokio::sync::RwLock;
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
@@ -23,12 +28,19 @@ type WebSocket = Arc<RwLock<Connection>>;
 type WebSockets = RwLock<HashMap<Uuid, WebSocket>>;
 /// Mapping of LIVE Query ID to WebSocket ID
 type LiveQueries = RwLock<HashMap<Uuid, Uuid>>;
+/// Mapping of transaction ID to the owning attached session and open transaction
+type Transactions = RwLock<HashMap<Uuid, (Option<Uuid>, Arc<OpenTransaction>)>>;
+/// Mapping of session ID to an attached session and its parameters
+type Sessions = RwLock<HashMap<Uuid, (Session, BTreeMap<String, Value>)>>;
 
 pub struct RpcState {
 	/// Stores the currently connected WebSockets
 	pub web_sockets: WebSockets,
 	/// Stores the currently initiated LIVE queries
 	pub live_queries: LiveQueries,
+	/// Stores the LIVE queries of GraphQL subscriptions
+	#[cfg(surrealdb_unstable)]
+	pub gql_subscriptions: Subscriptions,
 }
 
 impl RpcState {
@@ -36,6 +48,8 @@ impl RpcState {
 		RpcState {
 			web_sockets: WebSockets::default(),
 			live_queries: LiveQueries::default(),
+			#[cfg(surrealdb_unstable)]
+			gql_subscriptions: Subscriptions::default(),
 		}
 	}
 }
@@ -78,12 +92,19 @@ pub(crate) async fn notifications(
 								  .with_live_id(id.to_string());
 							let cx = Arc::new(cx.with_value(not_ctx));
 							// Get the WebSocket output format
-							let format = rpc.read().await.format;
+							let (format, detailed) = {
+								let rpc = rpc.read().await;
+								(rpc.format, rpc.detailed)
+							};
 							// get the WebSocket sending channel
 							let sender = rpc.read().await.channel.0.clone();
 							// Send the notification to the client
-							message.send(cx, format, &sender).await
+							message.send(cx, format, detailed, &sender).await
 						}
+					} else {
+						// Otherwise the notification may belong to a GraphQL subscription
+						#[cfg(surrealdb_unstable)]
+						state.gql_subscriptions.notify(notification).await;
 					}
 				},
 			}
@@ -2,11 +2,13 @@ use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use crate::cnf::{PKG_NAME, PKG_VERSION};
-use surrealdb_core::dbs::Session;
+use surrealdb::channel::Sender;
+use surrealdb_core::dbs::{Records, Session};
 use surrealdb_core::kvs::Datastore;
 use surrealdb_core::rpc::Data;
 use surrealdb_core::rpc::RpcContext;
 use surrealdb_core::rpc::RpcError;
+use surrealdb_core::rpc::Streams;
 use surrealdb_core::sql::Array;
 use surrealdb_core::sql::Value;
 
@@ -17,6 +19,10 @@ pub struct PostRpcContext {
 	pub kvs: Arc<Datastore>,
 	pub session: Session,
 	pub vars: BTreeMap<String, Value>,
+	/// The channel which the records of a streamed query are sent to
+	pub records: Option<Sender<Records>>,
+	/// The streamed query which is running on this request
+	pub streams: Arc<Streams>,
 	#[cfg(surrealdb_unstable)]
 	pub gql_schema: SchemaCache<Pessimistic>,
 }
@@ -27,6 +33,8 @@ impl PostRpcContext {
 			kvs: kvs.clone(),
 			session,
 			vars,
+			records: None,
+			streams: Arc::new(Streams::default()),
 			#[cfg(surrealdb_unstable)]
 			gql_schema: SchemaCache::new(kvs.clone()),
 		}
@@ -66,6 +74,25 @@ impl RpcContext for PostRpcContext {
 	/// Live queries are disabled on HTTP
 	const LQ_SUPPORT: bool = false;
 
+	// ------------------------------
+	// Streaming
+	// ------------------------------
+
+	/// Streamed queries are enabled on HTTP, sending the records in the response body
+	const STREAM_SUPPORT: bool = true;
+
+	/// Sends a batch of streamed records to the response body
+	async fn send_records(&self, records: Records) {
+		if let Some(chn) = &self.records {
+			let _ = chn.send(records).await;
+		}
+	}
+
+	/// Returns the streamed query which is running on this request
+	fn streams(&self) -> &Streams {
+		&self.streams
+	}
+
 	// ------------------------------
 	// GraphQL
 	// ------------------------------
@@ -1,4 +1,4 @@
-use crate::rpc::failure::Failure;
+use crate::rpc::failure::{DetailedFailure, Failure};
 use crate::rpc::format::WsFormat;
 use crate::telemetry::metrics::ws::record_rpc;
 use axum::extract::ws::Message;
@@ -37,7 +37,16 @@ impl Response {
 	}
 
 	/// Send the response to the WebSocket channel
-	pub async fn send(self, cx: Arc<TelemetryContext>, fmt: Format, chn: &Sender<Message>) {
+	///
+	/// The details of errors are only included for clients which negotiated
+	/// the detailed protocol, as existing clients can not decode them.
+	pub async fn send(
+		self,
+		cx: Arc<TelemetryContext>,
+		fmt: Format,
+		detailed: bool,
+		chn: &Sender<Message>,
+	) {
 		// Create a new tracing span
 		let span = Span::current();
 		// Log the rpc response call
@@ -57,7 +66,11 @@ impl Response {
 		// than to clone the entire response, which can be arbitrary size
 		let id = self.id.clone();
 		// Process the response for the format
-		let (len, msg) = match fmt.res_ws(self) {
+		let res = match detailed {
+			true => fmt.res_ws(DetailedResponse::from(self)),
+			false => fmt.res_ws(self),
+		};
+		let (len, msg) = match res {
 			Ok((l, m)) => (l, m),
 			Err(err) => {
 				fmt.res_ws(failure(id, err)).expect("Serialising known thrown error should succeed")
@@ -76,6 +89,34 @@ impl From<Response> for Value {
 	}
 }
 
+/// A response which includes the details of errors, for clients which
+/// negotiated the detailed protocol
+#[revisioned(revision = 1)]
+#[derive(Debug, Serialize)]
+pub struct DetailedResponse {
+	id: Option<Value>,
+	result: Result<Data, DetailedFailure>,
+}
+
+impl From<Response> for DetailedResponse {
+	fn from(value: Response) -> Self {
+		DetailedResponse {
+			id: value.id,
+			result: value.result.map(Data::into_detailed).map_err(DetailedFailure),
+		}
+	}
+}
+
+impl From<DetailedResponse> for Value {
+	fn from(value: DetailedResponse) -> Self {
+		Response {
+			id: value.id,
+			result: value.result.map_err(|err| err.0),
+		}
+		.into_value()
+	}
+}
+
 /// Create a JSON RPC result response
 pub fn success<T: Into<Data>>(id: Option<Value>, data: T) -> Response {
 	Response {
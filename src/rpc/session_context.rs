@@ -0,0 +1,166 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::rpc::connection::Connection;
+use surrealdb::dbs::{OpenTransaction, Records, Session};
+use surrealdb::kvs::Datastore;
+use surrealdb::rpc::Data;
+use surrealdb::rpc::RpcContext;
+use surrealdb::rpc::Streams;
+use surrealdb::sql::Value;
+use uuid::Uuid;
+
+#[cfg(surrealdb_unstable)]
+use surrealdb::gql::SchemaCache;
+
+/// An RPC context for a session which is attached to a WebSocket connection.
+///
+/// Each attached session has its own authentication, selected namespace and
+/// database, parameters, and transactions, while live queries and other
+/// sessions are shared with the WebSocket connection.
+pub struct SessionContext<'a> {
+	pub conn: &'a Connection,
+	pub id: Uuid,
+	pub session: Session,
+	pub vars: BTreeMap<String, Value>,
+}
+
+impl<'a> SessionContext<'a> {
+	pub fn new(
+		conn: &'a Connection,
+		id: Uuid,
+		(session, vars): (Session, BTreeMap<String, Value>),
+	) -> Self {
+		Self {
+			conn,
+			id,
+			session,
+			vars,
+		}
+	}
+}
+
+impl RpcContext for SessionContext<'_> {
+	/// The datastore for this RPC interface
+	fn kvs(&self) -> &Datastore {
+		self.conn.kvs()
+	}
+	/// The current session for this RPC context
+	fn session(&self) -> &Session {
+		&self.session
+	}
+	/// Mutable access to the current session for this RPC context
+	fn session_mut(&mut self) -> &mut Session {
+		&mut self.session
+	}
+	/// The current parameters stored on this RPC context
+	fn vars(&self) -> &BTreeMap<String, Value> {
+		&self.vars
+	}
+	/// Mutable access to the current parameters stored on this RPC context
+	fn vars_mut(&mut self) -> &mut BTreeMap<String, Value> {
+		&mut self.vars
+	}
+	/// The version information for this RPC context
+	fn version_data(&self) -> Data {
+		self.conn.version_data()
+	}
+
+	// ------------------------------
+	// Realtime
+	// ------------------------------
+
+	/// Live queries are enabled on WebSockets
+	const LQ_SUPPORT: bool = true;
+
+	/// Handles the execution of a LIVE statement
+	async fn handle_live(&self, lqid: &Uuid) {
+		self.conn.handle_live(lqid).await;
+		self.conn.session_lqs.write().await.insert(*lqid, self.id);
+	}
+
+	/// Handles the execution of a KILL statement
+	async fn handle_kill(&self, lqid: &Uuid) {
+		self.conn.handle_kill(lqid).await;
+		self.conn.session_lqs.write().await.remove(lqid);
+	}
+
+	/// Handles the cleanup of live queries for this session
+	async fn cleanup_lqs(&self) {
+		self.conn.cleanup_session_lqs(&self.id).await
+	}
+
+	// ------------------------------
+	// Transactions
+	// ------------------------------
+
+	/// Interactive transactions are enabled on WebSockets
+	const TXN_SUPPORT: bool = true;
+
+	/// Stores a transaction on the WebSocket connection, owned by this session
+	async fn store_txn(&self, id: Uuid, txn: Arc<OpenTransaction>) {
+		self.conn.store_session_txn(Some(self.id), id, txn).await
+	}
+
+	/// Fetches a transaction which was started by this session
+	async fn fetch_txn(&self, id: &Uuid) -> Option<Arc<OpenTransaction>> {
+		self.conn.fetch_session_txn(Some(self.id), id).await
+	}
+
+	/// Removes a transaction which was started by this session
+	async fn remove_txn(&self, id: &Uuid) -> Option<Arc<OpenTransaction>> {
+		self.conn.remove_session_txn(Some(self.id), id).await
+	}
+
+	// ------------------------------
+	// Sessions
+	// ------------------------------
+
+	/// Multiple sessions are enabled on WebSockets
+	const SESSION_SUPPORT: bool = true;
+
+	/// Attaches a new session to the WebSocket connection
+	async fn attach_session(&self, id: Uuid) {
+		self.conn.attach_session(id).await
+	}
+
+	/// Detaches a session from the WebSocket connection
+	async fn detach_session(&self, id: &Uuid) -> bool {
+		self.conn.detach_session(id).await
+	}
+
+	/// Lists the sessions attached to the WebSocket connection
+	async fn list_sessions(&self) -> Vec<Uuid> {
+		self.conn.list_sessions().await
+	}
+
+	// ------------------------------
+	// Streaming
+	// ------------------------------
+
+	/// Streamed queries are enabled on WebSockets
+	const STREAM_SUPPORT: bool = true;
+
+	/// Sends a batch of streamed records to the WebSocket
+	async fn send_records(&self, records: Records) {
+		self.conn.send_records(records).await
+	}
+
+	/// Returns the streamed queries which are running on the WebSocket connection
+	fn streams(&self) -> &Streams {
+		self.conn.streams()
+	}
+
+	// ------------------------------
+	// GraphQL
+	// ------------------------------
+
+	/// GraphQL queries are enabled on WebSockets
+	#[cfg(surrealdb_unstable)]
+	const GQL_SUPPORT: bool = true;
+
+	#[cfg(surrealdb_unstable)]
+	fn graphql_schema_cache(&self) -> &SchemaCache {
+		self.conn.graphql_schema_cache()
+	}
+}
@@ -677,6 +677,194 @@ async fn concurrency() -> Result<(), Box<dyn std::error::Error>> {
 	Ok(())
 }
 
+#[test(tokio::test)]
+async fn attached_sessions() -> Result<(), Box<dyn std::error::Error>> {
+	// Setup database server
+	let (addr, mut server) = common::start_server_with_defaults().await.unwrap();
+	// Connect to WebSocket
+	let mut socket = Socket::connect(&addr, SERVER, FORMAT).await?;
+	// Authenticate the connection
+	socket.send_message_signin(USER, PASS, None, None, None).await?;
+	// Specify a namespace and database
+	socket.send_message_use(Some(NS), Some(DB)).await?;
+	socket.send_request("let", json!(["var", "connection"])).await?;
+	// Attach two sessions to the connection
+	let res = socket.send_request("attach", json!([])).await?;
+	let one = res["result"].as_str().unwrap().to_owned();
+	let res = socket.send_request("attach", json!([])).await?;
+	let two = res["result"].as_str().unwrap().to_owned();
+	let res = socket.send_request("sessions", json!([])).await?;
+	assert_eq!(res["result"].as_array().unwrap().len(), 2, "result: {res:?}");
+	// Attached sessions are not authenticated
+	let res = socket.send_session_request(Some(&one), "query", json!(["INFO FOR ROOT"])).await?;
+	assert!(res["error"].is_object(), "result: {res:?}");
+	// Authenticate and select a database on the first session
+	let params = json!([{ "user": USER, "pass": PASS }]);
+	let res = socket.send_session_request(Some(&one), "signin", params).await?;
+	assert!(res["result"].is_string(), "result: {res:?}");
+	let res = socket.send_session_request(Some(&one), "use", json!([NS, "other"])).await?;
+	assert!(res.get("error").is_none(), "result: {res:?}");
+	// Each session has its own parameters
+	let res = socket.send_session_request(Some(&one), "let", json!(["var", "one"])).await?;
+	assert!(res.get("error").is_none(), "result: {res:?}");
+	let res = socket
+		.send_session_request(Some(&one), "query", json!(["RETURN [$var, session::db()]"]))
+		.await?;
+	assert_eq!(res["result"][0]["result"], json!(["one", "other"]), "result: {res:?}");
+	let res = socket.send_message_query("RETURN [$var, session::db()]").await?;
+	assert_eq!(res[0]["result"], json!(["connection", DB]), "result: {res:?}");
+	let res = socket.send_session_request(Some(&two), "query", json!(["RETURN $var"])).await?;
+	assert_eq!(res["result"][0]["result"], json!(null), "result: {res:?}");
+	// Detach the first session
+	let res = socket.send_request("detach", json!([one])).await?;
+	assert!(res.get("error").is_none(), "result: {res:?}");
+	let res = socket.send_session_request(Some(&one), "query", json!(["RETURN $var"])).await?;
+	assert!(res["error"].is_object(), "result: {res:?}");
+	let res = socket.send_request("sessions", json!([])).await?;
+	assert_eq!(res["result"], json!([two]), "result: {res:?}");
+	// Test passed
+	server.finish().unwrap();
+	Ok(())
+}
+
+#[test(tokio::test)]
+async fn attached_session_transactions() -> Result<(), Box<dyn std::error::Error>> {
+	// Setup database server
+	let (addr, mut server) = common::start_server_with_defaults().await.unwrap();
+	// Connect to WebSocket
+	let mut socket = Socket::connect(&addr, SERVER, FORMAT).await?;
+	// Authenticate the connection
+	socket.send_message_signin(USER, PASS, None, None, None).await?;
+	// Attach two authenticated sessions to the connection
+	let res = socket.send_request("attach", json!([])).await?;
+	let one = res["result"].as_str().unwrap().to_owned();
+	let res = socket.send_request("attach", json!([])).await?;
+	let two = res["result"].as_str().unwrap().to_owned();
+	for session in [&one, &two] {
+		let params = json!([{ "user": USER, "pass": PASS }]);
+		let res = socket.send_session_request(Some(session), "signin", params).await?;
+		assert!(res["result"].is_string(), "result: {res:?}");
+		let res = socket.send_session_request(Some(session), "use", json!([NS, DB])).await?;
+		assert!(res.get("error").is_none(), "result: {res:?}");
+	}
+	// Begin a transaction on the first session
+	let res = socket.send_session_request(Some(&one), "begin", json!([])).await?;
+	let txn = res["result"].as_str().unwrap().to_owned();
+	// Other sessions can not finish the transaction
+	let res = socket.send_session_request(Some(&two), "commit", json!([txn])).await?;
+	assert!(res["error"].is_object(), "result: {res:?}");
+	let res = socket.send_request("cancel", json!([txn])).await?;
+	assert!(res["error"].is_object(), "result: {res:?}");
+	// The owning session can finish the transaction
+	let res = socket.send_session_request(Some(&one), "commit", json!([txn])).await?;
+	assert!(res.get("error").is_none(), "result: {res:?}");
+	// Transactions are cancelled when their session is detached
+	let res = socket.send_session_request(Some(&two), "begin", json!([])).await?;
+	let txn = res["result"].as_str().unwrap().to_owned();
+	let res = socket.send_request("detach", json!([two])).await?;
+	assert!(res.get("error").is_none(), "result: {res:?}");
+	let res = socket.send_session_request(Some(&one), "commit", json!([txn])).await?;
+	assert!(res["error"].is_object(), "result: {res:?}");
+	// Test passed
+	server.finish().unwrap();
+	Ok(())
+}
+
+#[test(tokio::test)]
+async fn query_stream() -> Result<(), Box<dyn std::error::Error>> {
+	// Setup database server
+	let (addr, mut server) = common::start_server_with_defaults().await.unwrap();
+	// Connect to WebSocket
+	let mut socket = Socket::connect(&addr, SERVER, FORMAT).await?;
+	// Authenticate the connection
+	socket.send_message_signin(USER, PASS, None, None, None).await?;
+	// Specify a namespace and database
+	socket.send_message_use(Some(NS), Some(DB)).await?;
+	socket.send_message_query("FOR $i IN 0..2500 { CREATE tester }").await?;
+	// Stream the records of the query
+	let id = "d0b3f4a8-5c1e-4b0f-9a57-3e2d7b1c6f90";
+	let sql = "SELECT * FROM tester; SELECT * FROM tester ORDER BY id LIMIT 5";
+	let res = socket.send_request("query_stream", json!([id, sql])).await?;
+	let res = res["result"].as_array().unwrap();
+	assert_eq!(res.len(), 2, "result: {res:?}");
+	// The records of the first statement were streamed
+	assert_eq!(res[0]["result"], json!([]), "result: {res:?}");
+	// Ordered statements are not streamed
+	assert_eq!(res[1]["result"].as_array().unwrap().len(), 5, "result: {res:?}");
+	// The streamed records were sent before the response
+	let mut count = 0;
+	while count < 2500 {
+		let msg = socket.receive_other_message().await?;
+		assert_eq!(msg["result"]["stream"], id, "message: {msg:?}");
+		assert_eq!(msg["result"]["query"], 0, "message: {msg:?}");
+		count += msg["result"]["records"].as_array().unwrap().len();
+	}
+	assert_eq!(count, 2500);
+	// Streamed queries need a valid stream id
+	let res = socket.send_request("query_stream", json!(["invalid", sql])).await?;
+	assert!(res["error"].is_object(), "result: {res:?}");
+	// Test passed
+	server.finish().unwrap();
+	Ok(())
+}
+
+#[test(tokio::test)]
+async fn error_codes() -> Result<(), Box<dyn std::error::Error>> {
+	// Setup database server
+	let (addr, mut server) = common::start_server_with_defaults().await.unwrap();
+	// Connect to WebSocket
+	let mut socket = Socket::connect(&addr, SERVER, FORMAT).await?;
+	// Authenticate the connection
+	socket.send_message_signin(USER, PASS, None, None, None).await?;
+	// Specify a namespace and database
+	socket.send_message_use(Some(NS), Some(DB)).await?;
+	socket
+		.send_message_query(
+			"DEFINE INDEX email ON tester FIELDS email UNIQUE; CREATE tester:one SET email = 'one@example.com'",
+		)
+		.await?;
+	// Failed statements include a code and details
+	let res = socket.send_message_query("CREATE tester:two SET email = 'one@example.com'").await?;
+	assert_eq!(res[0]["status"], "ERR", "result: {res:?}");
+	assert_eq!(res[0]["code"], "unique_index_violation", "result: {res:?}");
+	assert_eq!(res[0]["details"]["index"], "email", "result: {res:?}");
+	// Failed methods include a code in the error data
+	let res = socket.send_request("create", json!(["tester:one"])).await?;
+	assert!(res["error"].is_object(), "result: {res:?}");
+	assert_eq!(res["error"]["data"]["code"], "record_exists", "result: {res:?}");
+	// Protocol errors do not include a code
+	let res = socket.send_request("unknown", json!([])).await?;
+	assert!(res["error"]["data"].is_null(), "result: {res:?}");
+	// Test passed
+	server.finish().unwrap();
+	Ok(())
+}
+
+#[test(tokio::test)]
+async fn query_retry() -> Result<(), Box<dyn std::error::Error>> {
+	// Setup database server
+	let (addr, mut server) = common::start_server_with_defaults().await.unwrap();
+	// Connect to WebSocket
+	let mut socket = Socket::connect(&addr, SERVER, FORMAT).await?;
+	// Authenticate the connection
+	socket.send_message_signin(USER, PASS, None, None, None).await?;
+	// Specify a namespace and database
+	socket.send_message_use(Some(NS), Some(DB)).await?;
+	// Queries can specify a retry policy
+	let policy = json!({ "max_attempts": 5, "backoff": "10ms", "max_backoff": "100ms" });
+	let res = socket.send_request("query", json!(["CREATE tester:one", {}, policy])).await?;
+	assert!(res["result"].is_array(), "result: {res:?}");
+	assert_eq!(res["result"][0]["status"], "OK", "result: {res:?}");
+	// The retry policy must be valid
+	let policy = json!({ "max_attempts": 0 });
+	let res = socket.send_request("query", json!(["CREATE tester:two", {}, policy])).await?;
+	assert!(res["error"].is_object(), "result: {res:?}");
+	assert_eq!(res["error"]["data"]["code"], "invalid_query", "result: {res:?}");
+	// Test passed
+	server.finish().unwrap();
+	Ok(())
+}
+
 #[test(tokio::test)]
 async fn live_query() -> Result<(), Box<dyn std::error::Error>> {
 	// Setup database server
@@ -1111,7 +1299,7 @@ async fn session_expiration() {
 	let res = res.as_object().unwrap();
 	assert_eq!(
 		res["error"],
-		json!({"code": -32000, "message": "There was a problem with the database: The session has expired"})
+		json!({"code": -32000, "message": "There was a problem with the database: The session has expired", "data": {"code": "session_expired", "details": {}}})
 	);
 	// Sign in again using the same session
 	let res = socket
@@ -1222,7 +1410,7 @@ async fn session_expiration_operations() {
 	let res = res.as_object().unwrap();
 	assert_eq!(
 		res["error"],
-		json!({"code": -32000, "message": "There was a problem with the database: The session has expired"})
+		json!({"code": -32000, "message": "There was a problem with the database: The session has expired", "data": {"code": "session_expired", "details": {}}})
 	);
 	// Test operations that SHOULD NOT work with an expired session
 	let operations_ko = vec![
@@ -1297,7 +1485,7 @@ async fn session_expiration_operations() {
 		let res = res.as_object().unwrap();
 		assert_eq!(
 			res["error"],
-			json!({"code": -32000, "message": "There was a problem with the database: The session has expired"})
+			json!({"code": -32000, "message": "There was a problem with the database: The session has expired", "data": {"code": "session_expired", "details": {}}})
 		);
 	}
 
@@ -1349,7 +1537,7 @@ async fn session_expiration_operations() {
 	let res = res.as_object().unwrap();
 	assert_eq!(
 		res["error"],
-		json!({"code": -32000, "message": "There was a problem with the database: The session has expired"})
+		json!({"code": -32000, "message": "There was a problem with the database: The session has expired", "data": {"code": "session_expired", "details": {}}})
 	);
 	let res = socket
 		.send_request(
@@ -1381,7 +1569,7 @@ async fn session_expiration_operations() {
 	let res = res.as_object().unwrap();
 	assert_eq!(
 		res["error"],
-		json!({"code": -32000, "message": "There was a problem with the database: The session has expired"})
+		json!({"code": -32000, "message": "There was a problem with the database: The session has expired", "data": {"code": "session_expired", "details": {}}})
 	);
 
 	// This needs to be last operation as the session will no longer expire afterwards
@@ -1564,7 +1752,7 @@ async fn session_reauthentication_expired() {
 	let res = res.as_object().unwrap();
 	assert_eq!(
 		res["error"],
-		json!({"code": -32000, "message": "There was a problem with the database: The session has expired"})
+		json!({"code": -32000, "message": "There was a problem with the database: The session has expired", "data": {"code": "session_expired", "details": {}}})
 	);
 	// Authenticate using the root token, which has not expired yet
 	socket.send_request("authenticate", json!([root_token,])).await.unwrap();
@@ -2041,7 +2229,7 @@ async fn rpc_capability() {
 			let res = res.unwrap();
 			assert!(res.is_object(), "result: {res:?}");
 			let res = res.as_object().unwrap();
-			assert_eq!(res["error"], json!({"code": -32000, "message": "Method not allowed"}));
+			assert_eq!(res["error"], json!({"code": -32000, "message": "Method not allowed", "data": {"code": "method_not_allowed", "details": {}}}));
 		}
 
 		// Test operations that SHOULD work with the provided capabilities
@@ -2210,7 +2398,7 @@ async fn rpc_capability() {
 			let res = res.unwrap();
 			assert!(res.is_object(), "result: {res:?}");
 			let res = res.as_object().unwrap();
-			assert_eq!(res["error"], json!({"code": -32000, "message": "Method not allowed"}));
+			assert_eq!(res["error"], json!({"code": -32000, "message": "Method not allowed", "data": {"code": "method_not_allowed", "details": {}}}));
 		}
 
 		// Test operations that SHOULD work with the provided capabilities
@@ -370,9 +370,15 @@ mod graphql_integration {
 					{
 						"name": "_get_foo"
 					},
+					{
+						"name": "_connection_foo"
+					},
 					{
 						"name": "_get_bar"
 					},
+					{
+						"name": "_connection_bar"
+					},
 					{
 						"name": "_get"
 					}
@@ -412,6 +418,470 @@ mod graphql_integration {
 					{
 						"name": "_get_foo"
 					},
+					{
+						"name": "_connection_foo"
+					},
+					{
+						"name": "_get"
+					}
+				]
+			);
+			assert_equal_arrs!(fields, &expected_fields);
+		}
+
+		Ok(())
+	}
+
+	#[test(tokio::test)]
+	async fn mutations() -> Result<(), Box<dyn std::error::Error>> {
+		let (addr, _server) = common::start_server_gql_without_auth().await.unwrap();
+		let gql_url = &format!("http://{addr}/graphql");
+		let sql_url = &format!("http://{addr}/sql");
+
+		let mut headers = reqwest::header::HeaderMap::new();
+		let ns = Ulid::new().to_string();
+		let db = Ulid::new().to_string();
+		headers.insert("surreal-ns", ns.parse()?);
+		headers.insert("surreal-db", db.parse()?);
+		headers.insert(header::ACCEPT, "application/json".parse()?);
+		let client = Client::builder()
+			.connect_timeout(Duration::from_millis(10))
+			.default_headers(headers)
+			.build()?;
+
+		// add schema
+		{
+			let res = client
+				.post(sql_url)
+				.body(
+					r#"
+                    DEFINE CONFIG GRAPHQL AUTO;
+                    DEFINE TABLE person SCHEMAFUL;
+                    DEFINE FIELD name ON person TYPE string;
+                    DEFINE FIELD age ON person TYPE option<int>;
+                    DEFINE TABLE likes TYPE RELATION IN person OUT person SCHEMAFUL;
+                    DEFINE FIELD strength ON likes TYPE int;
+                "#,
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+		}
+
+		// check the generated mutations
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({ "query": r#"{__schema {mutationType {fields {name}}}}"# }).to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.text().await?;
+			let res_obj = serde_json::Value::from_str(&body).unwrap();
+			let fields = &res_obj["data"]["__schema"]["mutationType"]["fields"];
+			let expected_fields = json!(
+				[
+					{
+						"name": "create_person"
+					},
+					{
+						"name": "update_person"
+					},
+					{
+						"name": "upsert_person"
+					},
+					{
+						"name": "delete_person"
+					},
+					{
+						"name": "update_likes"
+					},
+					{
+						"name": "delete_likes"
+					},
+					{
+						"name": "relate_likes"
+					}
+				]
+			);
+			assert_equal_arrs!(fields, &expected_fields);
+		}
+
+		// create records
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"mutation{
+						a: create_person(id: "tobie", data: {name: "Tobie", age: 30}){id, name, age}
+						b: create_person(id: "jaime", data: {name: "Jaime"}){id, name, age}
+					}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.text().await?;
+			let expected = json!({
+				"data": {
+					"a": {
+						"id": "person:tobie",
+						"name": "Tobie",
+						"age": 30
+					},
+					"b": {
+						"id": "person:jaime",
+						"name": "Jaime",
+						"age": null
+					}
+				}
+			});
+			assert_eq!(expected.to_string(), body)
+		}
+
+		// check required fields
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"mutation{create_person(data: {age: 1}){id}}"#}).to_string(),
+				)
+				.send()
+				.await?;
+			let body = res.text().await?;
+			assert!(body.contains("is required but not provided"), "body: {body}")
+		}
+
+		// update and upsert records
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"mutation{
+						a: update_person(id: "person:tobie", data: {age: null}){id, name, age}
+						b: update_person(id: "nobody", data: {age: 1}){id}
+						c: upsert_person(id: "jaime", data: {name: "Jaime", age: 40}){id, name, age}
+					}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.text().await?;
+			let expected = json!({
+				"data": {
+					"a": {
+						"id": "person:tobie",
+						"name": "Tobie",
+						"age": null
+					},
+					"b": null,
+					"c": {
+						"id": "person:jaime",
+						"name": "Jaime",
+						"age": 40
+					}
+				}
+			});
+			assert_eq!(expected.to_string(), body)
+		}
+
+		// relate records
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"mutation{
+						relate_likes(in: "person:tobie", out: "person:jaime", data: {strength: 3}){
+							strength, in {id}, out {id}
+						}
+					}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.text().await?;
+			let expected = json!({
+				"data": {
+					"relate_likes": {
+						"strength": 3,
+						"in": {
+							"id": "person:tobie"
+						},
+						"out": {
+							"id": "person:jaime"
+						}
+					}
+				}
+			});
+			assert_eq!(expected.to_string(), body)
+		}
+
+		// delete records
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"mutation{
+						a: delete_person(id: "jaime")
+						b: delete_person(id: "jaime")
+					}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.text().await?;
+			let expected = json!({
+				"data": {
+					"a": "person:jaime",
+					"b": null
+				}
+			});
+			assert_eq!(expected.to_string(), body)
+		}
+
+		Ok(())
+	}
+
+	#[test(tokio::test)]
+	async fn subscriptions() -> Result<(), Box<dyn std::error::Error>> {
+		use futures::{SinkExt, StreamExt};
+		use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+		use tokio_tungstenite::tungstenite::Message;
+
+		let (addr, _server) = common::start_server_gql_without_auth().await.unwrap();
+		let sql_url = &format!("http://{addr}/sql");
+
+		let mut headers = reqwest::header::HeaderMap::new();
+		let ns = Ulid::new().to_string();
+		let db = Ulid::new().to_string();
+		headers.insert("surreal-ns", ns.parse()?);
+		headers.insert("surreal-db", db.parse()?);
+		headers.insert(header::ACCEPT, "application/json".parse()?);
+		let client = Client::builder()
+			.connect_timeout(Duration::from_millis(10))
+			.default_headers(headers)
+			.build()?;
+
+		// add schema
+		{
+			let res = client
+				.post(sql_url)
+				.body(
+					r#"
+                    DEFINE CONFIG GRAPHQL AUTO;
+                    DEFINE TABLE foo SCHEMAFUL;
+                    DEFINE FIELD val ON foo TYPE int;
+                "#,
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+		}
+
+		// connect using the graphql-ws protocol
+		let mut req = format!("ws://{addr}/graphql").into_client_request()?;
+		req.headers_mut().insert("surreal-ns", ns.parse()?);
+		req.headers_mut().insert("surreal-db", db.parse()?);
+		req.headers_mut().insert("Sec-WebSocket-Protocol", "graphql-transport-ws".parse()?);
+		let (mut socket, _) = tokio_tungstenite::connect_async(req).await?;
+
+		macro_rules! next_message {
+			() => {{
+				let msg =
+					tokio::time::timeout(Duration::from_secs(5), socket.next()).await?.unwrap()?;
+				serde_json::Value::from_str(msg.to_text()?)?
+			}};
+		}
+
+		socket.send(Message::Text(json!({"type": "connection_init"}).to_string())).await?;
+		assert_eq!(next_message!(), json!({"type": "connection_ack"}));
+
+		socket
+			.send(Message::Text(
+				json!({
+					"id": "1",
+					"type": "subscribe",
+					"payload": {
+						"query": r#"subscription{foo(filter: {val: {eq: 1}}){action, id, record{val}}}"#
+					}
+				})
+				.to_string(),
+			))
+			.await?;
+		// wait for the live query to be started
+		tokio::time::sleep(Duration::from_millis(500)).await;
+
+		// change records
+		{
+			let res = client
+				.post(sql_url)
+				.body(
+					r#"
+                    CREATE foo:1 SET val = 1;
+                    CREATE foo:2 SET val = 2;
+                    DELETE foo:1;
+                "#,
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+		}
+
+		let expected = json!({
+			"id": "1",
+			"type": "next",
+			"payload": {
+				"data": {
+					"foo": {
+						"action": "CREATE",
+						"id": "foo:1",
+						"record": {
+							"val": 1
+						}
+					}
+				}
+			}
+		});
+		assert_eq!(next_message!(), expected);
+
+		let expected = json!({
+			"id": "1",
+			"type": "next",
+			"payload": {
+				"data": {
+					"foo": {
+						"action": "DELETE",
+						"id": "foo:1",
+						"record": null
+					}
+				}
+			}
+		});
+		assert_eq!(next_message!(), expected);
+
+		Ok(())
+	}
+
+	#[test(tokio::test)]
+	async fn functions() -> Result<(), Box<dyn std::error::Error>> {
+		let (addr, _server) = common::start_server_gql_without_auth().await.unwrap();
+		let gql_url = &format!("http://{addr}/graphql");
+		let sql_url = &format!("http://{addr}/sql");
+
+		let mut headers = reqwest::header::HeaderMap::new();
+		let ns = Ulid::new().to_string();
+		let db = Ulid::new().to_string();
+		headers.insert("surreal-ns", ns.parse()?);
+		headers.insert("surreal-db", db.parse()?);
+		headers.insert(header::ACCEPT, "application/json".parse()?);
+		let client = Client::builder()
+			.connect_timeout(Duration::from_millis(10))
+			.default_headers(headers)
+			.build()?;
+
+		// add schema and data
+		{
+			let res = client
+				.post(sql_url)
+				.body(
+					r#"
+                    DEFINE CONFIG GRAPHQL AUTO;
+                    DEFINE TABLE foo SCHEMAFUL;
+                    DEFINE FIELD val ON foo TYPE int;
+                    CREATE foo:1 SET val = 42;
+                    DEFINE FUNCTION fn::double($x: int) -> int { RETURN $x * 2 };
+                    DEFINE FUNCTION fn::foo::get($id: int) -> option<record<foo>> {
+                        RETURN type::thing("foo", $id)
+                    };
+                    DEFINE FUNCTION fn::greet($name: option<string>) {
+                        RETURN "hello " + ($name ?? "world")
+                    };
+                    DEFINE FUNCTION fn::foo_get() { RETURN 1 };
+                    DEFINE FUNCTION fn::echo($val: any) { RETURN $val };
+                "#,
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+		}
+
+		// call functions via graphql
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"query{
+						fn_double(x: 21)
+						fn_foo_get(id: 1){id, val}
+						a: fn_greet
+						b: fn_greet(name: "you")
+						c: fn_echo(val: "not a value")
+						d: fn_echo(val: "1d")
+					}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.text().await?;
+			let expected = json!({
+				"data": {
+					"fn_double": 42,
+					"fn_foo_get": {
+						"id": "foo:1",
+						"val": 42
+					},
+					"a": "hello world",
+					"b": "hello you",
+					"c": "not a value",
+					"d": "1d"
+				}
+			});
+			assert_eq!(expected.to_string(), body)
+		}
+
+		{
+			let res = client
+				.post(sql_url)
+				.body(
+					r#"
+                    DEFINE CONFIG OVERWRITE GRAPHQL TABLES AUTO FUNCTIONS INCLUDE [fn::double];
+                "#,
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+		}
+
+		{
+			let res = client
+				.post(gql_url)
+				.body(json!({ "query": r#"{__schema {queryType {fields {name}}}}"# }).to_string())
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.text().await?;
+			let res_obj = serde_json::Value::from_str(&body).unwrap();
+			let fields = &res_obj["data"]["__schema"]["queryType"]["fields"];
+			let expected_fields = json!(
+				[
+					{
+						"name": "foo"
+					},
+					{
+						"name": "_get_foo"
+					},
+					{
+						"name": "_connection_foo"
+					},
+					{
+						"name": "fn_double"
+					},
 					{
 						"name": "_get"
 					}
@@ -422,4 +892,189 @@ mod graphql_integration {
 
 		Ok(())
 	}
+
+	#[test(tokio::test)]
+	async fn connections_and_edges() -> Result<(), Box<dyn std::error::Error>> {
+		let (addr, _server) = common::start_server_gql_without_auth().await.unwrap();
+		let gql_url = &format!("http://{addr}/graphql");
+		let sql_url = &format!("http://{addr}/sql");
+
+		let mut headers = reqwest::header::HeaderMap::new();
+		let ns = Ulid::new().to_string();
+		let db = Ulid::new().to_string();
+		headers.insert("surreal-ns", ns.parse()?);
+		headers.insert("surreal-db", db.parse()?);
+		headers.insert(header::ACCEPT, "application/json".parse()?);
+		let client = Client::builder()
+			.connect_timeout(Duration::from_millis(10))
+			.default_headers(headers)
+			.build()?;
+
+		// add schema and data
+		{
+			let res = client
+				.post(sql_url)
+				.body(
+					r#"
+                    DEFINE CONFIG GRAPHQL AUTO;
+                    DEFINE TABLE person SCHEMAFUL;
+                    DEFINE FIELD name ON person TYPE string;
+                    DEFINE TABLE post SCHEMAFUL;
+                    DEFINE FIELD title ON post TYPE string;
+                    DEFINE TABLE likes TYPE RELATION FROM person TO post SCHEMAFUL;
+                    CREATE person:1 SET name = "alice";
+                    CREATE person:2 SET name = "bob";
+                    CREATE person:3 SET name = "carol";
+                    CREATE post:1 SET title = "first";
+                    CREATE post:2 SET title = "second";
+                    RELATE person:1->likes:1->post:1;
+                    RELATE person:1->likes:2->post:2;
+                    RELATE person:2->likes:3->post:1;
+                    DEFINE TABLE follows TYPE RELATION SCHEMAFUL;
+                    RELATE person:2->follows:1->person:1;
+                "#,
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+		}
+
+		// paginate with cursors
+		let end_cursor = {
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"query{
+						_connection_person(first: 2){
+							edges{node{name}}
+							pageInfo{hasNextPage, hasPreviousPage, endCursor}
+						}
+					}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.text().await?;
+			let res_obj = serde_json::Value::from_str(&body).unwrap();
+			let conn = &res_obj["data"]["_connection_person"];
+			assert_eq!(
+				conn["edges"],
+				json!([{"node": {"name": "alice"}}, {"node": {"name": "bob"}}])
+			);
+			assert_eq!(conn["pageInfo"]["hasNextPage"], json!(true));
+			assert_eq!(conn["pageInfo"]["hasPreviousPage"], json!(false));
+			conn["pageInfo"]["endCursor"].as_str().unwrap().to_string()
+		};
+
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": format!(r#"query{{
+						_connection_person(first: 2, after: "{end_cursor}"){{
+							edges{{node{{name}}}}
+							pageInfo{{hasNextPage, hasPreviousPage}}
+						}}
+					}}"#)})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.text().await?;
+			let expected = json!({
+				"data": {
+					"_connection_person": {
+						"edges": [
+							{"node": {"name": "carol"}}
+						],
+						"pageInfo": {
+							"hasNextPage": false,
+							"hasPreviousPage": true
+						}
+					}
+				}
+			});
+			assert_eq!(expected.to_string(), body)
+		}
+
+		// invalid cursors are rejected
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"query{_connection_person(after: "not a cursor"){edges{cursor}}}"#})
+						.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.text().await?;
+			assert!(body.contains("invalid cursor"), "body: {body}");
+		}
+
+		// traverse relation edges
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"query{
+						_get_person(id: "person:1"){
+							name
+							_out_likes{out{title}}
+							_in_follows{in{id, ...on person{name}}}
+							_out_likes_post{title}
+							_in_follows_person{name}
+						}
+						_get_post(id: "post:1"){
+							_in_likes(limit: 1){id, in{id}}
+							_in_likes_person{name}
+						}
+						__type(name: "follows"){interfaces{name}}
+					}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.text().await?;
+			let expected = json!({
+				"data": {
+					"_get_person": {
+						"name": "alice",
+						"_out_likes": [
+							{"out": {"title": "first"}},
+							{"out": {"title": "second"}}
+						],
+						"_in_follows": [
+							{"in": {"id": "person:2", "name": "bob"}}
+						],
+						"_out_likes_post": [
+							{"title": "first"},
+							{"title": "second"}
+						],
+						"_in_follows_person": [
+							{"name": "bob"}
+						]
+					},
+					"_get_post": {
+						"_in_likes": [
+							{"id": "likes:1", "in": {"id": "person:1"}}
+						],
+						"_in_likes_person": [
+							{"name": "alice"},
+							{"name": "bob"}
+						]
+					},
+					"__type": {
+						"interfaces": [{"name": "record"}, {"name": "relation"}]
+					}
+				}
+			});
+			assert_eq!(expected.to_string(), body)
+		}
+
+		Ok(())
+	}
 }
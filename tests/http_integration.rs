@@ -557,6 +557,36 @@ mod http_integration {
 			assert!(res.is_ok(), "upgrade err: {}", res.unwrap_err());
 		}
 
+		// Test WebSocket protocol negotiation
+		for (requested, selected) in
+			[("revision", "revision"), ("revision-v2, revision", "revision-v2")]
+		{
+			let res = client
+				.get(url)
+				.header(header::CONNECTION, "Upgrade")
+				.header(header::UPGRADE, "websocket")
+				.header(header::SEC_WEBSOCKET_VERSION, "13")
+				.header(header::SEC_WEBSOCKET_KEY, "dGhlIHNhbXBsZSBub25jZQ==")
+				.header(header::SEC_WEBSOCKET_PROTOCOL, requested)
+				.send()
+				.await?;
+			assert_eq!(res.headers()[header::SEC_WEBSOCKET_PROTOCOL], selected);
+		}
+
+		// Test HTTP RPC revision negotiation
+		{
+			let res = client
+				.post(url)
+				.header(header::CONTENT_TYPE, "application/json")
+				.header("surreal-rpc-revision", "2")
+				.body(r#"{"id":1,"method":"version","params":[]}"#)
+				.send()
+				.await?;
+			// Only binary formats include the error details
+			assert!(res.headers().get("surreal-rpc-revision").is_none());
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+		}
+
 		Ok(())
 	}
 
@@ -996,6 +1026,76 @@ mod http_integration {
 		Ok(())
 	}
 
+	#[test(tokio::test)]
+	async fn sql_endpoint_with_streaming() -> Result<(), Box<dyn std::error::Error>> {
+		let (addr, _server) = common::start_server_with_defaults().await.unwrap();
+		let url = &format!("http://{addr}/sql");
+
+		// Prepare HTTP client
+		let mut headers = reqwest::header::HeaderMap::new();
+		headers.insert("surreal-ns", Ulid::new().to_string().parse()?);
+		headers.insert("surreal-db", Ulid::new().to_string().parse()?);
+		headers.insert(header::ACCEPT, "application/x-ndjson".parse()?);
+		let client = reqwest::Client::builder()
+			.connect_timeout(Duration::from_millis(10))
+			.default_headers(headers)
+			.build()?;
+
+		// Create the records to stream
+		{
+			let res = client
+				.post(url)
+				.basic_auth(USER, Some(PASS))
+				.body("FOR $i IN 0..2500 { CREATE foo }")
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			// The query is cancelled if the response is not read
+			let body = res.text().await?;
+			assert!(body.contains(r#""status":"OK""#), "body: {body}");
+		}
+
+		// Records are streamed as lines before the statement response
+		{
+			let res = client
+				.post(url)
+				.basic_auth(USER, Some(PASS))
+				.body("SELECT * FROM foo; SELECT * FROM foo ORDER BY id LIMIT 5")
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			assert_eq!(res.headers()["content-type"], "application/x-ndjson");
+			let body = res.text().await?;
+			let lines: Vec<serde_json::Value> =
+				body.lines().map(serde_json::from_str).collect::<Result<_, _>>()?;
+			let (records, responses): (Vec<_>, Vec<_>) =
+				lines.into_iter().partition(|line| line.get("records").is_some());
+			let count: usize =
+				records.iter().map(|line| line["records"].as_array().unwrap().len()).sum();
+			assert_eq!(count, 2500, "body: {body}");
+			assert!(records.iter().all(|line| line["query"] == 0), "body: {body}");
+			assert_eq!(responses.len(), 2, "body: {body}");
+			assert_eq!(responses[0]["query"], 0, "body: {body}");
+			assert_eq!(responses[0]["status"], "OK", "body: {body}");
+			assert_eq!(responses[0]["result"], json!([]), "body: {body}");
+			assert_eq!(responses[1]["query"], 1, "body: {body}");
+			assert_eq!(responses[1]["result"].as_array().unwrap().len(), 5, "body: {body}");
+		}
+
+		// Invalid queries are not streamed
+		{
+			let res = client
+				.post(url)
+				.basic_auth(USER, Some(PASS))
+				.body("SELEC * FROM foo")
+				.send()
+				.await?;
+			assert_eq!(res.status(), 400);
+		}
+
+		Ok(())
+	}
+
 	#[test(tokio::test)]
 	async fn sync_endpoint() -> Result<(), Box<dyn std::error::Error>> {
 		let (addr, _server) = common::start_server_with_defaults().await.unwrap();
@@ -1011,19 +1111,75 @@ mod http_integration {
 			.default_headers(headers)
 			.build()?;
 
-		// GET
+		// Create some data with a change feed
+		{
+			let res = client
+				.post(format!("http://{addr}/sql"))
+				.basic_auth(USER, Some(PASS))
+				.body("DEFINE TABLE foo CHANGEFEED 1h; CREATE foo:one SET name = 'one'")
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+		}
+		// GET without authentication
 		{
 			let res = client.get(url).send().await?;
+			assert_eq!(res.status(), 403, "body: {}", res.text().await?);
+		}
+		// GET
+		let changes = {
+			let res = client.get(url).basic_auth(USER, Some(PASS)).send().await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+			let body = res.bytes().await?.to_vec();
+			assert!(!body.is_empty(), "body: {body:?}");
+			body
+		};
+		// POST into another database
+		let db = Ulid::new().to_string();
+		{
+			let res = client
+				.post(url)
+				.basic_auth(USER, Some(PASS))
+				.header("surreal-db", &db)
+				.body(changes.clone())
+				.send()
+				.await?;
 			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
-			let body = res.text().await?;
-			assert_eq!(body, r#"Save"#, "body: {body}");
 		}
-		// POST
+		// Check that the changes were applied
 		{
-			let res = client.post(url).body("").send().await?;
+			let res = client
+				.post(format!("http://{addr}/sql"))
+				.basic_auth(USER, Some(PASS))
+				.header("surreal-db", &db)
+				.body("SELECT * FROM foo")
+				.send()
+				.await?;
 			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
 			let body = res.text().await?;
-			assert_eq!(body, r#"Load"#, "body: {body}");
+			assert!(body.contains("foo:one"), "body: {body}");
+		}
+		// POST into a database where the changes can not be applied
+		let db = Ulid::new().to_string();
+		{
+			let res = client
+				.post(format!("http://{addr}/sql"))
+				.basic_auth(USER, Some(PASS))
+				.header("surreal-db", &db)
+				.body(
+					"DEFINE INDEX name ON foo FIELDS name UNIQUE; CREATE foo:two SET name = 'one'",
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+			let res = client
+				.post(url)
+				.basic_auth(USER, Some(PASS))
+				.header("surreal-db", &db)
+				.body(changes.clone())
+				.send()
+				.await?;
+			assert_eq!(res.status(), 400, "body: {}", res.text().await?);
 		}
 
 		Ok(())